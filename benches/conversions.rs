@@ -0,0 +1,182 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Throughput benchmarks for conversions, parsing, and color differences.
+//!
+//! Run with `cargo bench --features bench --bench conversions`. Each
+//! benchmark group reports its throughput in colors per second. The targets
+//! below give the expected time per color for an optimized build on a typical
+//! desktop core, with enough headroom that only genuine regressions exceed
+//! them; compare against them and criterion's saved baselines when changing
+//! conversion code:
+//!
+//! | Benchmark                  | Target (ns/color) |
+//! | -------------------------- | ----------------- |
+//! | Rgb ↔ Hsl, Hsv, Cmyk       | 150               |
+//! | Rgb ↔ Xyz, Lab, Lch        | 400               |
+//! | Rgb ↔ Oklab, Oklch         | 400               |
+//! | Rgb ↔ YCoCgR               | 100               |
+//! | Bulk slice conversions     | same as single    |
+//! | Backend Rgb ↔ Oklab        | 200               |
+//! | Hex code parsing           | 100               |
+//! | Short code round trip      | 300               |
+//! | ΔE*76 / ΔE*94              | 600               |
+//! | ΔE*00                      | 1200              |
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Internal modules.
+mod support;
+
+// Local imports.
+use support::random_colors;
+
+// External library imports.
+use color::Cmyk;
+use color::Hsl;
+use color::Hsv;
+use color::Lab;
+use color::Lch;
+use color::Oklab;
+use color::Oklch;
+use color::Rgb;
+use color::Xyz;
+use color::YCoCgR;
+use color::backend::Backend;
+use color::backend::ConversionBackend;
+use color::short_code;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use criterion::Throughput;
+
+// Standard library imports.
+use std::hint::black_box;
+
+
+/// The number of colors in each bulk slice.
+const BULK: usize = 4096;
+
+
+/// Benchmarks single-color conversions, each converting to the space and
+/// back.
+fn single(c: &mut Criterion) {
+    let color = random_colors(1, 1)[0];
+    let mut group = c.benchmark_group("round trip");
+    group.throughput(Throughput::Elements(1));
+
+    macro_rules! round_trip {
+        ($name:expr, $space:ty) => {
+            group.bench_function($name, |b| b.iter(|| {
+                Rgb::from(<$space>::from(black_box(color)))
+            }));
+        };
+    }
+    round_trip!("Rgb <-> Hsl", Hsl);
+    round_trip!("Rgb <-> Hsv", Hsv);
+    round_trip!("Rgb <-> Cmyk", Cmyk);
+    round_trip!("Rgb <-> Xyz", Xyz);
+    round_trip!("Rgb <-> Lab", Lab);
+    round_trip!("Rgb <-> Lch", Lch);
+    round_trip!("Rgb <-> Oklab", Oklab);
+    round_trip!("Rgb <-> Oklch", Oklch);
+    round_trip!("Rgb <-> YCoCgR", YCoCgR);
+    group.finish();
+}
+
+/// Benchmarks bulk slice conversions.
+fn bulk(c: &mut Criterion) {
+    let colors = random_colors(BULK, 1);
+    let mut group = c.benchmark_group("bulk");
+    group.throughput(Throughput::Elements(BULK as u64));
+
+    macro_rules! bulk {
+        ($name:expr, $space:ty) => {
+            group.bench_function($name, |b| b.iter(|| {
+                black_box(&colors)
+                    .iter()
+                    .map(|&c| <$space>::from(c))
+                    .collect::<Vec<_>>()
+            }));
+        };
+    }
+    bulk!("[Rgb] -> [Hsl]", Hsl);
+    bulk!("[Rgb] -> [Lab]", Lab);
+    bulk!("[Rgb] -> [Oklab]", Oklab);
+    group.finish();
+}
+
+/// Benchmarks the conversion backends against each other.
+fn backends(c: &mut Criterion) {
+    let colors = random_colors(BULK, 1);
+    let mut oklab = vec![Oklab::default(); BULK];
+    let mut rgb = vec![Rgb::default(); BULK];
+    let mut group = c.benchmark_group("backend");
+    group.throughput(Throughput::Elements(BULK as u64));
+
+    for backend in [Backend::Scalar, Backend::Lut, Backend::Batched] {
+        group.bench_function(
+            format!("{:?} [Rgb] -> [Oklab]", backend),
+            |b| b.iter(|| backend.rgb_to_oklab(black_box(&colors), &mut oklab)));
+        group.bench_function(
+            format!("{:?} [Oklab] -> [Rgb]", backend),
+            |b| b.iter(|| backend.oklab_to_rgb(black_box(&oklab), &mut rgb)));
+    }
+    group.finish();
+}
+
+/// Benchmarks parsing and encoding.
+fn parsing(c: &mut Criterion) {
+    let colors = random_colors(BULK, 1);
+    let color = colors[0];
+    let hex_codes: Vec<String> = colors
+        .iter()
+        .map(|c| format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b))
+        .collect();
+
+    let mut group = c.benchmark_group("parse");
+    group
+        .throughput(Throughput::Elements(BULK as u64))
+        .bench_function("Rgb::from_hex_code", |b| b.iter(|| {
+            black_box(&hex_codes)
+                .iter()
+                .filter(|code| Rgb::from_hex_code(code).is_ok())
+                .count()
+        }));
+    group
+        .throughput(Throughput::Elements(1))
+        .bench_function("short_code round trip", |b| b.iter(|| {
+            let code = short_code::encode_color(black_box(color));
+            short_code::decode_color(&code)
+        }));
+    group.finish();
+}
+
+/// Benchmarks color differences.
+fn differences(c: &mut Criterion) {
+    let colors = random_colors(2, 1);
+    let (color, other) = (colors[0], colors[1]);
+    let mut group = c.benchmark_group("delta E");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("76", |b| b.iter(|| {
+        Lab::distance(black_box(color), black_box(other))
+    }));
+    group.bench_function("94", |b| b.iter(|| {
+        Lab::delta_e_94(black_box(color), black_box(other))
+    }));
+    group.bench_function("2000", |b| b.iter(|| {
+        Lab::delta_e(black_box(color), black_box(other))
+    }));
+    group.finish();
+}
+
+
+criterion_group!(benches, single, bulk, backends, parsing, differences);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Speed and accuracy comparison of the built-in color metrics.
+//!
+//! Run with `cargo bench --features bench --bench metric`. Times each
+//! metric's distance computation, and before measuring, prints how often the
+//! metric picks the same nearest color from a random palette as ΔE*00, which
+//! is taken as the reference.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Internal modules.
+mod support;
+
+// Local imports.
+use support::random_colors;
+
+// External library imports.
+use color::metric::DeltaE;
+use color::metric::Euclidean;
+use color::metric::Metric;
+use color::metric::Redmean;
+use color::metric::Weighted;
+use color::palette::Palette;
+use color::palette::nearest_in;
+use color::Rgb;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use criterion::Throughput;
+
+// Standard library imports.
+use std::hint::black_box;
+
+
+/// The number of color pairs timed for each metric.
+const PAIRS: usize = 4096;
+
+/// The number of colors matched against the palette.
+const SAMPLES: usize = 2_000;
+
+/// The number of colors in the palette.
+const PALETTE_SIZE: usize = 64;
+
+
+/// Benchmarks each metric and reports its agreement with ΔE*00.
+fn metrics(c: &mut Criterion) {
+    let pairs: Vec<(Rgb, Rgb)> = random_colors(PAIRS * 2, 1)
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect();
+    let samples = random_colors(SAMPLES, 2);
+    let palette: Palette = random_colors(PALETTE_SIZE, 3)
+        .into_iter()
+        .map(|c| (format!("{:?}", c), c))
+        .collect();
+    let reference: Vec<Option<usize>> = samples
+        .iter()
+        .map(|&c| nearest_in(c, &palette, DeltaE::Ciede2000).map(|n| n.0))
+        .collect();
+
+    let metrics: Vec<(&str, Box<dyn Metric>)> = vec![
+        ("Euclidean::Rgb", Box::new(Euclidean::Rgb)),
+        ("Weighted::LUMA", Box::new(Weighted::LUMA)),
+        ("Redmean", Box::new(Redmean)),
+        ("Euclidean::LinearRgb", Box::new(Euclidean::LinearRgb)),
+        ("Euclidean::Oklab", Box::new(Euclidean::Oklab)),
+        ("DeltaE::Cie76", Box::new(DeltaE::Cie76)),
+        ("DeltaE::Cie94", Box::new(DeltaE::Cie94)),
+        ("DeltaE::Ciede2000", Box::new(DeltaE::Ciede2000)),
+    ];
+
+    println!("{:<22} {:>12}", "metric", "agreement");
+    for (name, metric) in &metrics {
+        let agreed = samples
+            .iter()
+            .zip(reference.iter())
+            .filter(|(c, r)| {
+                nearest_in(**c, &palette, |a, b| metric.distance(a, b))
+                    .map(|n| n.0) == **r
+            })
+            .count();
+        let agreement = 100.0 * agreed as f64 / SAMPLES as f64;
+        println!("{:<22} {:>11.1}%", name, agreement);
+    }
+
+    let mut group = c.benchmark_group("metric");
+    group.throughput(Throughput::Elements(PAIRS as u64));
+    for (name, metric) in &metrics {
+        group.bench_function(*name, |b| b.iter(|| {
+            black_box(&pairs)
+                .iter()
+                .map(|&(a, b)| metric.distance(a, b))
+                .sum::<f32>()
+        }));
+    }
+    group.finish();
+}
+
+
+criterion_group!(benches, metrics);
+criterion_main!(benches);
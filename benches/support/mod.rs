@@ -0,0 +1,33 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Input data shared by the benchmarks.
+//!
+////////////////////////////////////////////////////////////////////////////////
+#![allow(dead_code)]
+
+// External library imports.
+use color::Rgb;
+
+
+/// Returns `count` pseudo-random colors from a fixed seed.
+pub fn random_colors(count: usize, seed: u64) -> Vec<Rgb> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            // SplitMix64.
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            Rgb::new(z as u8, (z >> 8) as u8, (z >> 16) as u8)
+        })
+        .collect()
+}
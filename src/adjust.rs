@@ -0,0 +1,1537 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Image-wide adjustments of pixel buffers.
+//!
+//! Pixel buffers are given as slices of [`Rgb`] colors and are adjusted in
+//! place. Each adjustment matches the corresponding [`Rgb`] method, except
+//! where it depends on statistics of the whole image.
+//!
+//! The [`recolor`] and [`recolor_soft`] functions map an image from one
+//! palette onto another, and [`replace_color`] replaces a single color and
+//! those near it.
+//!
+//! [`Rgb`]: ../struct.Rgb.html
+//! [`recolor`]: fn.recolor.html
+//! [`recolor_soft`]: fn.recolor_soft.html
+//! [`replace_color`]: fn.replace_color.html
+//!
+//! Selective edits are made by building a mask of per-pixel weights, such as
+//! with [`mask_by_hue`], and applying an adjustment through it with
+//! [`apply_masked`].
+//!
+//! [`mask_by_hue`]: fn.mask_by_hue.html
+//! [`apply_masked`]: fn.apply_masked.html
+//!
+//! Tonal adjustments such as [`Levels`] and [`Curve`] compile to a [`Lut`]
+//! which applies them to many pixels efficiently. Any transform can be baked
+//! into a [`Lut3d`] and exported for use in other software.
+//!
+//! [`Levels`]: struct.Levels.html
+//! [`Curve`]: struct.Curve.html
+//! [`Lut`]: struct.Lut.html
+//! [`Lut3d`]: struct.Lut3d.html
+//!
+//! Color grading adjustments include [`SplitTone`] and the three-way
+//! [`LiftGammaGain`].
+//!
+//! [`SplitTone`]: struct.SplitTone.html
+//! [`LiftGammaGain`]: struct.LiftGammaGain.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Hsl;
+use crate::illuminant::adaptation_matrix;
+use crate::illuminant::AdaptationMethod;
+use crate::metric::Metric;
+use crate::Oklab;
+use crate::Oklch;
+use crate::palette::Palette;
+use crate::quantize::palette_indices;
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::utility::clamped;
+use crate::utility::linear_to_srgb;
+use crate::utility::matrix_product;
+use crate::utility::matrix_transform;
+use crate::utility::srgb_to_linear;
+use crate::Xyz;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::collections::BTreeMap;
+use std::io;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The number of degrees of hue over which a mask made by [`mask_by_hue`]
+/// falls off outside its hue range.
+///
+/// [`mask_by_hue`]: fn.mask_by_hue.html
+const MASK_HUE_FEATHER: f32 = 10.0;
+
+/// The Oklch chroma over which a mask made by [`mask_by_hue`] rises above
+/// its minimum chroma.
+///
+/// [`mask_by_hue`]: fn.mask_by_hue.html
+const MASK_CHROMA_FEATHER: f32 = 0.02;
+
+/// The range of color temperatures, in kelvin, over which white points are
+/// placed on the Planckian locus by [`white_balance`].
+///
+/// [`white_balance`]: fn.white_balance.html
+const WHITE_BALANCE_KELVIN: [f32; 2] = [1000.0, 15000.0];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ContrastPivot
+////////////////////////////////////////////////////////////////////////////////
+/// The channel ratio about which [`adjust_contrast`] scales the contrast.
+///
+/// [`adjust_contrast`]: fn.adjust_contrast.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContrastPivot {
+    /// Pivot about 0.5, the middle of the encoded channel range, as
+    /// [`Rgb::adjust_contrast`] does.
+    ///
+    /// [`Rgb::adjust_contrast`]: ../struct.Rgb.html#method.adjust_contrast
+    #[default]
+    Midpoint,
+    /// Pivot about the mean encoded channel ratio of the image, which keeps
+    /// the overall brightness of dark or light images unchanged.
+    Mean,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lut
+////////////////////////////////////////////////////////////////////////////////
+/// A lookup table mapping each octet of each channel to a new octet.
+///
+/// Adjustments such as [`Levels`] and [`Curve`] compile to a `Lut`, which is
+/// then applied to a color with three table lookups. Tables are combined with
+/// [`Lut::then`] so that a chain of adjustments is applied in a single pass.
+///
+/// [`Levels`]: struct.Levels.html
+/// [`Curve`]: struct.Curve.html
+/// [`Lut::then`]: struct.Lut.html#method.then
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::Lut;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut invert = [0; 256];
+/// for (i, v) in invert.iter_mut().enumerate() { *v = 255 - i as u8; }
+/// let lut = Lut::new(invert, Lut::identity().tables()[1], invert);
+///
+/// assert_eq!(lut.apply(Rgb::new(0, 100, 255)), Rgb::new(255, 100, 0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Lut {
+    /// The tables of the red, green, and blue channels.
+    tables: [[u8; 256]; 3],
+}
+
+impl Lut {
+    /// Constructs a new `Lut` from the tables of the red, green, and blue
+    /// channels.
+    pub fn new(red: [u8; 256], green: [u8; 256], blue: [u8; 256]) -> Self {
+        Lut { tables: [red, green, blue] }
+    }
+
+    /// Constructs a new `Lut` applying the same table to every channel.
+    pub fn uniform(table: [u8; 256]) -> Self {
+        Lut { tables: [table; 3] }
+    }
+
+    /// Constructs the identity `Lut`, which leaves colors unchanged.
+    pub fn identity() -> Self {
+        let mut table = [0; 256];
+        for (i, v) in table.iter_mut().enumerate() { *v = i as u8; }
+        Lut::uniform(table)
+    }
+
+    /// Returns the tables of the red, green, and blue channels.
+    pub fn tables(&self) -> &[[u8; 256]; 3] {
+        &self.tables
+    }
+
+    /// Returns the `Lut` which applies this table and then the given one.
+    pub fn then(&self, next: &Lut) -> Self {
+        let mut tables = [[0; 256]; 3];
+        for (c, table) in tables.iter_mut().enumerate() {
+            for (i, v) in table.iter_mut().enumerate() {
+                *v = next.tables[c][self.tables[c][i] as usize];
+            }
+        }
+        Lut { tables }
+    }
+
+    /// Returns the given color mapped through the tables.
+    pub fn apply(&self, color: Rgb) -> Rgb {
+        Rgb::new(
+            self.tables[0][color.r as usize],
+            self.tables[1][color.g as usize],
+            self.tables[2][color.b as usize])
+    }
+
+    /// Maps each of the given pixels through the tables.
+    pub fn apply_pixels(&self, pixels: &mut [Rgb]) {
+        for pixel in pixels.iter_mut() {
+            *pixel = self.apply(*pixel);
+        }
+    }
+}
+
+impl Default for Lut {
+    fn default() -> Self {
+        Lut::identity()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lut3d
+////////////////////////////////////////////////////////////////////////////////
+/// A three-dimensional lookup table, sampling a color transform on a regular
+/// grid of RGB colors.
+///
+/// Any transform, including those which mix channels, can be baked into a
+/// `Lut3d` and exported in the `.cube` format read by video editors, game
+/// engines, and image editors. Colors between the grid points are
+/// interpolated trilinearly.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::Lut3d;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let lut = Lut3d::bake(|c: Rgb| c.sepia(0.8).adjust_contrast(1.2), 17);
+///
+/// let color = Rgb::new(40, 150, 220);
+/// let expected = color.sepia(0.8).adjust_contrast(1.2);
+/// assert!(Rgb::distance(lut.apply(color), expected) < 3.0);
+///
+/// let mut cube = Vec::new();
+/// lut.write_cube(&mut cube)?;
+/// assert!(String::from_utf8(cube)?.starts_with("LUT_3D_SIZE 17\n"));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Lut3d {
+    /// The number of grid points along each axis.
+    size: usize,
+    /// The output `[R, G, B]` ratios at each grid point, with red varying
+    /// fastest and blue slowest.
+    entries: Vec<[f32; 3]>,
+}
+
+impl Lut3d {
+    /// Constructs a new `Lut3d` by sampling the given transform on a grid of
+    /// `size` points along each axis.
+    ///
+    /// Grid points fall between octets, so each is rounded to the nearest
+    /// color before sampling. Sizes of 17, 33, and 65 are most common.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2 or greater than 256.
+    pub fn bake<F>(transform: F, size: usize) -> Self
+        where F: Fn(Rgb) -> Rgb
+    {
+        assert!((2..=256).contains(&size),
+            "LUT size must be between 2 and 256");
+        let octet = |i: usize| {
+            (i as f32 * u8::MAX as f32 / (size - 1) as f32).round() as u8
+        };
+
+        let mut entries = Vec::with_capacity(size * size * size);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let input = Rgb::new(octet(r), octet(g), octet(b));
+                    entries.push(transform(input).ratios());
+                }
+            }
+        }
+        Lut3d { size, entries }
+    }
+
+    /// Returns the number of grid points along each axis.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the given color transformed by the table.
+    pub fn apply(&self, color: Rgb) -> Rgb {
+        let n = self.size;
+        let scale = (n - 1) as f32;
+        let cells = color.ratios().map(|v| {
+            let position = v * scale;
+            let i = (position.floor() as usize).min(n - 2);
+            (i, position - i as f32)
+        });
+        let [(ri, rt), (gi, gt), (bi, bt)] = cells;
+
+        let mut sum = [0.0; 3];
+        for corner in 0..8 {
+            let (dr, dg, db) = (corner & 1, (corner >> 1) & 1, corner >> 2);
+            let weight = if dr == 1 { rt } else { 1.0 - rt }
+                * if dg == 1 { gt } else { 1.0 - gt }
+                * if db == 1 { bt } else { 1.0 - bt };
+            let index = (ri + dr) + (gi + dg) * n + (bi + db) * n * n;
+            for (s, v) in sum.iter_mut().zip(self.entries[index].iter()) {
+                *s += v * weight;
+            }
+        }
+        let [r, g, b] = sum.map(octet);
+        Rgb::new(r, g, b)
+    }
+
+    /// Transforms each of the given pixels by the table.
+    pub fn apply_pixels(&self, pixels: &mut [Rgb]) {
+        for pixel in pixels.iter_mut() {
+            *pixel = self.apply(*pixel);
+        }
+    }
+
+    /// Writes the table in the Adobe / Resolve `.cube` format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data cannot be written.
+    pub fn write_cube<W>(&self, mut writer: W) -> io::Result<()>
+        where W: io::Write
+    {
+        writeln!(writer, "LUT_3D_SIZE {}", self.size)?;
+        writeln!(writer, "DOMAIN_MIN 0.0 0.0 0.0")?;
+        writeln!(writer, "DOMAIN_MAX 1.0 1.0 1.0")?;
+        for [r, g, b] in self.entries.iter() {
+            writeln!(writer, "{:.6} {:.6} {:.6}", r, g, b)?;
+        }
+        Ok(())
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Levels
+////////////////////////////////////////////////////////////////////////////////
+/// A levels adjustment, remapping the range of encoded channel ratios between
+/// the black and white points to the full range, with a gamma correction of
+/// the midtones.
+///
+/// Ratios at or below `black` become 0, and those at or above `white`
+/// become 1. A `gamma` greater than 1 brightens the midtones and a `gamma`
+/// less than 1 darkens them, as in the levels dialog of most image editors.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::Levels;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let levels = Levels { black: 0.2, white: 0.8, gamma: 1.0 };
+/// let lut = levels.lut();
+///
+/// assert_eq!(lut.apply(Rgb::new(40, 128, 220)), Rgb::new(0, 128, 255));
+/// assert_eq!(levels.apply(Rgb::new(40, 128, 220)), Rgb::new(0, 128, 255));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Levels {
+    /// The black point, the ratio mapped to 0.
+    pub black: f32,
+    /// The white point, the ratio mapped to 1.
+    pub white: f32,
+    /// The gamma correction of the midtones.
+    pub gamma: f32,
+}
+
+impl Levels {
+    /// Returns the adjusted value of the given channel ratio.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `black` is not less than `white`, or if `gamma` is not a
+    /// positive finite number.
+    pub fn value(&self, ratio: f32) -> f32 {
+        assert!(self.black < self.white,
+            "black point must be less than white point");
+        assert!(self.gamma > 0.0 && self.gamma.is_finite(),
+            "gamma must be a positive finite number");
+        let t = (ratio - self.black) / (self.white - self.black);
+        t.clamp(0.0, 1.0).powf(1.0 / self.gamma)
+    }
+
+    /// Returns the table of the adjustment, mapping each octet to its
+    /// adjusted octet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `black` is not less than `white`, or if `gamma` is not a
+    /// positive finite number.
+    pub fn table(&self) -> [u8; 256] {
+        compile_table(|ratio| self.value(ratio))
+    }
+
+    /// Returns the adjustment compiled to a [`Lut`] applying it to every
+    /// channel.
+    ///
+    /// [`Lut`]: struct.Lut.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if `black` is not less than `white`, or if `gamma` is not a
+    /// positive finite number.
+    pub fn lut(&self) -> Lut {
+        Lut::uniform(self.table())
+    }
+
+    /// Returns the given color with the adjustment applied to each channel.
+    /// To adjust many colors, compile the adjustment with [`Levels::lut`].
+    ///
+    /// [`Levels::lut`]: struct.Levels.html#method.lut
+    ///
+    /// # Panics
+    ///
+    /// Panics if `black` is not less than `white`, or if `gamma` is not a
+    /// positive finite number.
+    pub fn apply(&self, color: Rgb) -> Rgb {
+        let [r, g, b] = color.ratios().map(|v| octet(self.value(v)));
+        Rgb::new(r, g, b)
+    }
+}
+
+impl Default for Levels {
+    fn default() -> Self {
+        Levels { black: 0.0, white: 1.0, gamma: 1.0 }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Curve
+////////////////////////////////////////////////////////////////////////////////
+/// A tone curve through control points mapping encoded channel ratios to new
+/// ratios, as in the curves dialog of most image editors.
+///
+/// The curve is a monotone cubic spline, so it passes smoothly through each
+/// control point without overshooting between them. Ratios before the first
+/// control point or after the last take the value of that point. A new curve
+/// is the identity, with control points at `[0, 0]` and `[1, 1]`.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::Curve;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // An S-curve increasing contrast.
+/// let curve = Curve::new()
+///     .with_point(0.25, 0.15)
+///     .with_point(0.75, 0.85);
+///
+/// let lut = curve.lut();
+/// let color = lut.apply(Rgb::new(64, 128, 191));
+///
+/// assert!(color.r < 64 && color.b > 191);
+/// assert_eq!(color, curve.apply(Rgb::new(64, 128, 191)));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Curve {
+    /// The control points, as `[input, output]` ratios sorted by input.
+    points: Vec<[f32; 2]>,
+}
+
+impl Curve {
+    /// Constructs a new identity `Curve`.
+    pub fn new() -> Self {
+        Curve { points: vec![[0.0, 0.0], [1.0, 1.0]] }
+    }
+
+    /// Returns the curve with the given control point added, replacing any
+    /// control point with the same input.
+    pub fn with_point(mut self, input: f32, output: f32) -> Self {
+        self.add_point(input, output);
+        self
+    }
+
+    /// Adds the given control point, replacing any control point with the
+    /// same input.
+    pub fn add_point(&mut self, input: f32, output: f32) {
+        let point = [input.clamp(0.0, 1.0), output.clamp(0.0, 1.0)];
+        match self.points.binary_search_by(|p| p[0].total_cmp(&point[0])) {
+            Ok(i)  => self.points[i] = point,
+            Err(i) => self.points.insert(i, point),
+        }
+    }
+
+    /// Removes the control point with the given input, if there is one and
+    /// it is not the last one remaining.
+    pub fn remove_point(&mut self, input: f32) {
+        if self.points.len() <= 1 { return; }
+        self.points.retain(|p| p[0] != input);
+    }
+
+    /// Returns the control points, as `[input, output]` ratios sorted by
+    /// input.
+    pub fn points(&self) -> &[[f32; 2]] {
+        &self.points[..]
+    }
+
+    /// Returns the value of the curve at the given channel ratio.
+    pub fn value(&self, ratio: f32) -> f32 {
+        self.evaluator()(ratio)
+    }
+
+    /// Returns the table of the curve, mapping each octet to its adjusted
+    /// octet.
+    pub fn table(&self) -> [u8; 256] {
+        compile_table(self.evaluator())
+    }
+
+    /// Returns the curve compiled to a [`Lut`] applying it to every channel.
+    ///
+    /// [`Lut`]: struct.Lut.html
+    pub fn lut(&self) -> Lut {
+        Lut::uniform(self.table())
+    }
+
+    /// Returns the given color with the curve applied to each channel. To
+    /// adjust many colors, compile the curve with [`Curve::lut`].
+    ///
+    /// [`Curve::lut`]: struct.Curve.html#method.lut
+    pub fn apply(&self, color: Rgb) -> Rgb {
+        let value = self.evaluator();
+        let [r, g, b] = color.ratios().map(|v| octet(value(v)));
+        Rgb::new(r, g, b)
+    }
+
+    /// Returns a function evaluating the curve, with the slopes of the
+    /// spline computed once.
+    fn evaluator(&self) -> impl Fn(f32) -> f32 + '_ {
+        let points = &self.points;
+        let slopes = monotone_slopes(points);
+        move |ratio: f32| {
+            let first = points[0];
+            let last = points[points.len() - 1];
+            if ratio <= first[0] { return first[1]; }
+            if ratio >= last[0] { return last[1]; }
+
+            let i = points.partition_point(|p| p[0] <= ratio) - 1;
+            let ([x0, y0], [x1, y1]) = (points[i], points[i + 1]);
+            let h = x1 - x0;
+            let t = (ratio - x0) / h;
+            let (t2, t3) = (t * t, t * t * t);
+            let value = (2.0*t3 - 3.0*t2 + 1.0) * y0
+                + (t3 - 2.0*t2 + t) * h * slopes[i]
+                + (-2.0*t3 + 3.0*t2) * y1
+                + (t3 - t2) * h * slopes[i + 1];
+            value.clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl Default for Curve {
+    fn default() -> Self {
+        Curve::new()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SplitTone
+////////////////////////////////////////////////////////////////////////////////
+/// A split-toning adjustment, tinting the shadows and highlights of an image
+/// with separate hues.
+///
+/// Each tint is given by an [`Hsl`] hue, in degrees, and a saturation
+/// between 0 for no tint and 1 for the full hue. The tints are applied in
+/// linear light by scaling the channels of each color toward the tint and
+/// then restoring the color's luminance, so tinting never brightens or
+/// darkens the image.
+///
+/// The shadow tint fades out and the highlight tint fades in across the
+/// tonal range. The `balance`, between -1 and 1, moves the crossover point:
+/// negative values extend the shadow tint into brighter tones, and positive
+/// values extend the highlight tint into darker tones.
+///
+/// [`Hsl`]: ../struct.Hsl.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::SplitTone;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // Teal shadows and orange highlights.
+/// let split = SplitTone {
+///     shadow_hue: 190.0,
+///     shadow_saturation: 0.4,
+///     highlight_hue: 30.0,
+///     highlight_saturation: 0.4,
+///     balance: 0.0,
+/// };
+///
+/// let shadow = split.apply(Rgb::new(40, 40, 40));
+/// let highlight = split.apply(Rgb::new(220, 220, 220));
+///
+/// assert!(shadow.b > shadow.r);
+/// assert!(highlight.r > highlight.b);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SplitTone {
+    /// The hue of the shadow tint, in degrees.
+    pub shadow_hue: f32,
+    /// The saturation of the shadow tint, between 0 and 1.
+    pub shadow_saturation: f32,
+    /// The hue of the highlight tint, in degrees.
+    pub highlight_hue: f32,
+    /// The saturation of the highlight tint, between 0 and 1.
+    pub highlight_saturation: f32,
+    /// The balance between the shadow and highlight tints, between -1 and 1.
+    pub balance: f32,
+}
+
+impl SplitTone {
+    /// Returns the given color with the split-toning applied.
+    pub fn apply(&self, color: Rgb) -> Rgb {
+        let [wr, wg, wb] = Srgb::XYZ_FROM_LINEAR[1];
+        let luminance = |[r, g, b]: [f32; 3]| wr * r + wg * g + wb * b;
+
+        let linear = color.linear_ratios();
+        let y = luminance(linear);
+        if y <= 0.0 { return color; }
+
+        // Weigh the tints by the perceptual tonal position of the color.
+        let position = linear_to_srgb(y);
+        let pivot = 0.5 - 0.5 * clamped(self.balance, -1.0, 1.0);
+        let highlight = smoothstep(pivot - 0.5, pivot + 0.5, position);
+        let shadow = 1.0 - highlight;
+
+        let shadow_tint = tint_factors(self.shadow_hue,
+            self.shadow_saturation * shadow);
+        let highlight_tint = tint_factors(self.highlight_hue,
+            self.highlight_saturation * highlight);
+        let tinted = [0, 1, 2]
+            .map(|i| linear[i] * shadow_tint[i] * highlight_tint[i]);
+
+        // Restore the original luminance.
+        let scale = y / luminance(tinted).max(f32::EPSILON);
+        let [r, g, b] = tinted.map(|v| {
+            octet(linear_to_srgb((v * scale).clamp(0.0, 1.0)))
+        });
+        Rgb::new(r, g, b)
+    }
+
+    /// Applies the split-toning to each of the given pixels.
+    pub fn apply_pixels(&self, pixels: &mut [Rgb]) {
+        // Images repeat colors heavily, so each distinct color is toned once.
+        let mut cache = BTreeMap::new();
+        for pixel in pixels.iter_mut() {
+            *pixel = *cache.entry(*pixel).or_insert_with(|| self.apply(*pixel));
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// LiftGammaGain
+////////////////////////////////////////////////////////////////////////////////
+/// A three-way color grade, adjusting the shadows, midtones, and highlights
+/// of each channel in linear light, as with the color wheels of video
+/// grading tools.
+///
+/// Each field holds one value per `[R, G, B]` channel. The `lift` raises
+/// the shadows while leaving white fixed, the `gain` scales the highlights
+/// while leaving black fixed, and the `gamma` bends the midtones, with
+/// values greater than 1 brightening them. For a linear channel value `x`,
+/// the result is `(gain · (x + lift · (1 - x)))^(1 / gamma)`, clamped
+/// between 0 and 1. The default grade is neutral, with a lift of 0 and a
+/// gamma and gain of 1.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::LiftGammaGain;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // Lift the blue shadows and warm the highlights.
+/// let grade = LiftGammaGain {
+///     lift: [0.0, 0.0, 0.02],
+///     gain: [1.05, 1.0, 0.95],
+///     ..LiftGammaGain::default()
+/// };
+///
+/// let black = grade.apply(Rgb::new(0, 0, 0));
+/// assert!(black.b > 0 && black.r == 0);
+///
+/// let white = grade.apply(Rgb::new(255, 255, 255));
+/// assert!(white.r > white.b);
+/// assert_eq!(grade.lut().apply(Rgb::new(90, 160, 40)),
+///     grade.apply(Rgb::new(90, 160, 40)));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LiftGammaGain {
+    /// The lift of each channel, raising the shadows.
+    pub lift: [f32; 3],
+    /// The gamma of each channel, bending the midtones.
+    pub gamma: [f32; 3],
+    /// The gain of each channel, scaling the highlights.
+    pub gain: [f32; 3],
+}
+
+impl LiftGammaGain {
+    /// Returns true if the grade leaves colors unchanged.
+    pub fn is_neutral(&self) -> bool {
+        *self == LiftGammaGain::default()
+    }
+
+    /// Returns the graded value of the given encoded channel ratio of the
+    /// given channel, where 0, 1, and 2 are red, green, and blue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is greater than 2, or if its gamma is not a
+    /// positive finite number.
+    pub fn value(&self, channel: usize, ratio: f32) -> f32 {
+        let gamma = self.gamma[channel];
+        assert!(gamma > 0.0 && gamma.is_finite(),
+            "gamma must be a positive finite number");
+        let x = srgb_to_linear(ratio);
+        let lifted = x + self.lift[channel] * (1.0 - x);
+        let graded = (self.gain[channel] * lifted)
+            .clamp(0.0, 1.0)
+            .powf(1.0 / gamma);
+        linear_to_srgb(graded)
+    }
+
+    /// Returns the grade compiled to a [`Lut`].
+    ///
+    /// [`Lut`]: struct.Lut.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if any gamma is not a positive finite number.
+    pub fn lut(&self) -> Lut {
+        let [r, g, b] = [0, 1, 2]
+            .map(|c| compile_table(|ratio| self.value(c, ratio)));
+        Lut::new(r, g, b)
+    }
+
+    /// Returns the given color with the grade applied. To grade many
+    /// colors, compile the grade with [`LiftGammaGain::lut`].
+    ///
+    /// [`LiftGammaGain::lut`]: struct.LiftGammaGain.html#method.lut
+    ///
+    /// # Panics
+    ///
+    /// Panics if any gamma is not a positive finite number.
+    pub fn apply(&self, color: Rgb) -> Rgb {
+        let ratios = color.ratios();
+        let [r, g, b] = [0, 1, 2].map(|c| octet(self.value(c, ratios[c])));
+        Rgb::new(r, g, b)
+    }
+}
+
+impl Default for LiftGammaGain {
+    fn default() -> Self {
+        LiftGammaGain {
+            lift: [0.0; 3],
+            gamma: [1.0; 3],
+            gain: [1.0; 3],
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Adjustments
+////////////////////////////////////////////////////////////////////////////////
+/// Adds the given brightness delta to each channel ratio of the pixels, as
+/// [`Rgb::adjust_brightness`] does.
+///
+/// [`Rgb::adjust_brightness`]: ../struct.Rgb.html#method.adjust_brightness
+///
+/// # Panics
+///
+/// Panics if `delta` is not finite.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::adjust_brightness;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut pixels = vec![Rgb::new(0, 0, 0), Rgb::new(128, 64, 240)];
+///
+/// adjust_brightness(&mut pixels, 0.2);
+///
+/// assert_eq!(pixels, [Rgb::new(51, 51, 51), Rgb::new(179, 115, 255)]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn adjust_brightness(pixels: &mut [Rgb], delta: f32) {
+    for pixel in pixels.iter_mut() {
+        *pixel = pixel.adjust_brightness(delta);
+    }
+}
+
+/// Scales the contrast of the pixels by the given factor about the given
+/// pivot.
+///
+/// A factor greater than 1 pushes channels away from the pivot, and a factor
+/// less than 1 pulls them toward it. A factor of 0 turns every pixel the
+/// pivot gray.
+///
+/// # Panics
+///
+/// Panics if `factor` is negative or not finite.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::ContrastPivot;
+/// # use color::adjust::adjust_contrast;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let dark = [Rgb::new(20, 20, 20), Rgb::new(60, 60, 60)];
+///
+/// let mut midpoint = dark;
+/// adjust_contrast(&mut midpoint, 0.0, ContrastPivot::Midpoint);
+/// assert_eq!(midpoint, [Rgb::new(128, 128, 128); 2]);
+///
+/// let mut mean = dark;
+/// adjust_contrast(&mut mean, 0.0, ContrastPivot::Mean);
+/// assert_eq!(mean, [Rgb::new(40, 40, 40); 2]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn adjust_contrast(pixels: &mut [Rgb], factor: f32, pivot: ContrastPivot)
+{
+    let pivot = match pivot {
+        ContrastPivot::Midpoint => 0.5,
+        ContrastPivot::Mean     => mean_ratio(pixels),
+    };
+    for pixel in pixels.iter_mut() {
+        *pixel = pixel.adjust_contrast_about(factor, pivot);
+    }
+}
+
+/// Adjusts the white balance of the pixels by the given temperature and tint
+/// shifts, as in photo editing tools.
+///
+/// The white point of the pixels is estimated as their mean color in linear
+/// light, assuming the scene averages to gray. This white point is moved
+/// along the Planckian locus of black body colors by `temp_shift`, and across
+/// it by `tint_shift`, and the pixels are then chromatically adapted from the
+/// estimated white point to the moved one using the Bradford transform.
+/// Unlike scaling each channel independently, this models the way the eye
+/// adapts to a change of illuminant, so colors keep their relationships.
+///
+/// The temperature shift is in mireds, or millions divided by the color
+/// temperature in kelvin, which change evenly in perceived warmth. Positive
+/// shifts warm the image and negative shifts cool it; a shift of 50 is a
+/// strong adjustment. The tint shift is in Δuv, the distance from the locus
+/// in the CIE 1960 UCS. Positive shifts move toward magenta and negative
+/// shifts toward green; a shift of 0.01 is a strong adjustment.
+///
+/// The pixels are left unchanged if they are all black.
+///
+/// # Panics
+///
+/// Panics if `temp_shift` or `tint_shift` is not finite.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::white_balance;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut pixels = vec![Rgb::new(128, 128, 128), Rgb::new(60, 120, 200)];
+///
+/// white_balance(&mut pixels, 40.0, 0.0);
+///
+/// // The neutral gray is warmed.
+/// assert!(pixels[0].r > 128 && pixels[0].b < 128);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn white_balance(pixels: &mut [Rgb], temp_shift: f32, tint_shift: f32) {
+    assert!(temp_shift.is_finite(), "temperature shift must be finite");
+    assert!(tint_shift.is_finite(), "tint shift must be finite");
+
+    // Estimate the white point by the gray world assumption.
+    let mut sum = [0.0; 3];
+    for pixel in pixels.iter() {
+        let linear = pixel.linear_ratios();
+        for i in 0..3 { sum[i] += linear[i]; }
+    }
+    let estimate = matrix_transform(Srgb::XYZ_FROM_LINEAR, sum);
+    let source_uv = match xyz_to_uv(estimate) {
+        Some(uv) => uv,
+        None     => return,
+    };
+
+    // Move the white point along and across the locus, keeping its offset
+    // from the locus.
+    let [low, high] = WHITE_BALANCE_KELVIN;
+    let kelvin = uv_to_kelvin(source_uv).clamp(low, high);
+    let mireds = 1_000_000.0 / kelvin + temp_shift;
+    let shifted = (1_000_000.0 / mireds.max(1.0)).clamp(low, high);
+    let base = planckian_uv(kelvin);
+    let target = planckian_uv(shifted);
+    let normal = planckian_normal(shifted);
+    let target_uv = [
+        target[0] + source_uv[0] - base[0] - normal[0] * tint_shift,
+        target[1] + source_uv[1] - base[1] - normal[1] * tint_shift,
+    ];
+
+    let adapt = adaptation_matrix(
+        uv_to_xyz(source_uv),
+        uv_to_xyz(target_uv),
+        AdaptationMethod::Bradford);
+    let matrix = matrix_product(
+        Srgb::LINEAR_FROM_XYZ,
+        matrix_product(adapt, Srgb::XYZ_FROM_LINEAR));
+
+    // Images repeat colors heavily, so each distinct color is adapted once.
+    let mut cache = BTreeMap::new();
+    for pixel in pixels.iter_mut() {
+        *pixel = *cache.entry(*pixel).or_insert_with(|| {
+            Rgb::from_linear_ratios(
+                matrix_transform(matrix, pixel.linear_ratios()))
+        });
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Recolorization
+////////////////////////////////////////////////////////////////////////////////
+/// Recolors the pixels by replacing each with the entry of the target palette
+/// corresponding to its nearest entry in the source palette, as measured by
+/// the given [`Metric`]. Entries correspond by their position in the palettes.
+///
+/// The pixels are left unchanged if the source palette is empty.
+///
+/// [`Metric`]: ../metric/trait.Metric.html
+///
+/// # Panics
+///
+/// Panics if the palettes have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::recolor;
+/// # use color::metric::Euclidean;
+/// # use color::palette::Palette;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let source: Palette = vec![
+///     ("sky", Rgb::new(90, 160, 230)),
+///     ("grass", Rgb::new(60, 160, 60)),
+/// ].into_iter().collect();
+/// let target: Palette = vec![
+///     ("sky", Rgb::new(250, 140, 90)),
+///     ("grass", Rgb::new(200, 170, 60)),
+/// ].into_iter().collect();
+///
+/// let mut pixels = vec![Rgb::new(100, 170, 220), Rgb::new(50, 140, 70)];
+/// recolor(&mut pixels, &source, &target, Euclidean::Oklab);
+///
+/// assert_eq!(pixels, [Rgb::new(250, 140, 90), Rgb::new(200, 170, 60)]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn recolor<M>(
+    pixels: &mut [Rgb],
+    source: &Palette,
+    target: &Palette,
+    metric: M)
+    where M: Metric
+{
+    assert_eq!(source.len(), target.len(),
+        "source and target palettes must have the same length");
+    let targets: Vec<Rgb> = target.colors().collect();
+    let indices = palette_indices(pixels, source, metric);
+    for (pixel, index) in pixels.iter_mut().zip(indices) {
+        *pixel = targets[index];
+    }
+}
+
+/// Recolors the pixels by replacing each with a blend of the entries of the
+/// target palette, weighted by the nearness of the corresponding entries of
+/// the source palette, as measured by the given [`Metric`]. Entries
+/// correspond by their position in the palettes.
+///
+/// Each weight is the inverse of the distance raised to the given `falloff`
+/// power, and the targets are blended in [`Oklab`]. Higher falloffs give
+/// sharper transitions, approaching [`recolor`]. A pixel exactly matching a
+/// source entry is replaced by its target entry.
+///
+/// The pixels are left unchanged if the source palette is empty.
+///
+/// [`Metric`]: ../metric/trait.Metric.html
+/// [`Oklab`]: ../struct.Oklab.html
+/// [`recolor`]: fn.recolor.html
+///
+/// # Panics
+///
+/// Panics if the palettes have different lengths, or if `falloff` is not a
+/// positive finite number.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::recolor_soft;
+/// # use color::metric::Euclidean;
+/// # use color::palette::Palette;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let source: Palette = vec![
+///     ("dark", Rgb::new(0, 0, 0)),
+///     ("light", Rgb::new(255, 255, 255)),
+/// ].into_iter().collect();
+/// let target: Palette = vec![
+///     ("dark", Rgb::new(40, 0, 80)),
+///     ("light", Rgb::new(255, 230, 150)),
+/// ].into_iter().collect();
+///
+/// let mut pixels = vec![Rgb::new(0, 0, 0), Rgb::new(128, 128, 128)];
+/// recolor_soft(&mut pixels, &source, &target, Euclidean::Oklab, 2.0);
+///
+/// assert_eq!(pixels[0], Rgb::new(40, 0, 80));
+/// assert!(pixels[1].r > 40 && pixels[1].r < 255);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn recolor_soft<M>(
+    pixels: &mut [Rgb],
+    source: &Palette,
+    target: &Palette,
+    metric: M,
+    falloff: f32)
+    where M: Metric
+{
+    assert_eq!(source.len(), target.len(),
+        "source and target palettes must have the same length");
+    assert!(falloff > 0.0 && falloff.is_finite(),
+        "falloff must be a positive finite number");
+    if source.is_empty() { return; }
+
+    let sources: Vec<Rgb> = source.colors().collect();
+    let targets: Vec<Oklab> = target.colors().map(Oklab::from).collect();
+
+    // Images repeat colors heavily, so each distinct color is blended once.
+    let mut cache = BTreeMap::new();
+    for pixel in pixels.iter_mut() {
+        *pixel = *cache.entry(*pixel).or_insert_with(|| {
+            let distances: Vec<f32> = sources
+                .iter()
+                .map(|&s| metric.distance(*pixel, s))
+                .collect();
+            if let Some(exact) = distances.iter().position(|&d| d <= 0.0) {
+                return Rgb::from(targets[exact]);
+            }
+
+            // Distances are taken relative to the nearest so that the
+            // weights do not overflow for high falloffs.
+            let nearest = distances.iter().copied().fold(f32::MAX, f32::min);
+            let mut sum = [0.0; 3];
+            let mut total = 0.0;
+            for (d, t) in distances.iter().zip(targets.iter()) {
+                let weight = (nearest / d).powf(falloff);
+                sum[0] += t.l * weight;
+                sum[1] += t.a * weight;
+                sum[2] += t.b * weight;
+                total += weight;
+            }
+            let [l, a, b] = sum.map(|v| v / total);
+            Rgb::from(Oklab::new(l, a, b))
+        });
+    }
+}
+
+/// Replaces the target color and the colors near it in the pixels, as for
+/// the recolor tool of an image editor.
+///
+/// Each pixel is shifted in [`Oklab`] by the difference between the
+/// replacement and the target, so that shading and texture within the
+/// selected colors are kept and a pixel exactly matching the target becomes
+/// the replacement. Pixels within `tolerance` of the target, measured as the
+/// Euclidean distance in Oklab, are shifted fully. The shift then falls off
+/// smoothly to nothing over the following `softness`, feathering the edge of
+/// the selection.
+///
+/// Oklab distances between colors range up to about 1, and distances below
+/// about 0.02 are barely noticeable.
+///
+/// [`Oklab`]: ../struct.Oklab.html
+///
+/// # Panics
+///
+/// Panics if `tolerance` or `softness` is negative or not finite.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::replace_color;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let red = Rgb::new(220, 30, 30);
+/// let mut pixels = vec![red, Rgb::new(200, 40, 35), Rgb::new(30, 30, 220)];
+///
+/// replace_color(&mut pixels, red, Rgb::new(30, 160, 60), 0.05, 0.1);
+///
+/// assert_eq!(pixels[0], Rgb::new(30, 160, 60));
+/// assert!(pixels[1].g > pixels[1].r);
+/// assert_eq!(pixels[2], Rgb::new(30, 30, 220));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn replace_color<C, D>(
+    pixels: &mut [Rgb],
+    target: C,
+    replacement: D,
+    tolerance: f32,
+    softness: f32)
+    where
+        C: Into<Rgb>,
+        D: Into<Rgb>,
+{
+    assert!(tolerance >= 0.0 && tolerance.is_finite(),
+        "tolerance must be a non-negative finite number");
+    assert!(softness >= 0.0 && softness.is_finite(),
+        "softness must be a non-negative finite number");
+
+    let target = Oklab::from(target.into());
+    let replacement = Oklab::from(replacement.into());
+    let shift = [
+        replacement.l - target.l,
+        replacement.a - target.a,
+        replacement.b - target.b,
+    ];
+
+    // Images repeat colors heavily, so each distinct color is shifted once.
+    let mut cache = BTreeMap::new();
+    for pixel in pixels.iter_mut() {
+        *pixel = *cache.entry(*pixel).or_insert_with(|| {
+            let color = Oklab::from(*pixel);
+            let distance = ((color.l - target.l).powi(2)
+                + (color.a - target.a).powi(2)
+                + (color.b - target.b).powi(2))
+                .sqrt();
+            let weight = feather(distance, tolerance, softness);
+            if weight <= 0.0 { return *pixel; }
+
+            Rgb::from(Oklab::new(
+                color.l + shift[0] * weight,
+                color.a + shift[1] * weight,
+                color.b + shift[2] * weight))
+        });
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Masking
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a mask selecting the pixels within the given band of [`Oklch`]
+/// hues, as a weight between 0 and 1 for each pixel.
+///
+/// The hue range runs counterclockwise from its start to its end, in
+/// degrees, so it wraps through 0 if the start is greater than the end, and
+/// a range spanning 360° or more selects every hue. Pixels with a chroma
+/// below `chroma_min` have no meaningful hue and are excluded. To avoid hard
+/// edges in selective edits, the weights fall off smoothly over 10° outside
+/// the hue range and rise smoothly over a chroma of 0.02 above `chroma_min`.
+///
+/// [`Oklch`]: ../struct.Oklch.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::apply_masked;
+/// # use color::adjust::mask_by_hue;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let blue = Rgb::new(40, 80, 220);
+/// let red = Rgb::new(220, 40, 40);
+/// let gray = Rgb::new(128, 128, 128);
+/// let mut pixels = vec![blue, red, gray];
+///
+/// // Shift only the blues.
+/// let mask = mask_by_hue(&pixels, [220.0, 290.0], 0.03);
+/// assert_eq!(mask, [1.0, 0.0, 0.0]);
+///
+/// apply_masked(&mut pixels, &mask, |c| c.rotate_hue(-30.0));
+/// assert_eq!(pixels, [blue.rotate_hue(-30.0), red, gray]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn mask_by_hue(pixels: &[Rgb], hue_range: [f32; 2], chroma_min: f32)
+    -> Vec<f32>
+{
+    let full_circle = hue_range[1] - hue_range[0] >= 360.0;
+    let [start, end] = hue_range.map(|h| h.rem_euclid(360.0));
+    let angle = |a: f32, b: f32| {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    };
+
+    pixels
+        .iter()
+        .map(|&pixel| {
+            let oklch = Oklch::from(pixel);
+            let h = oklch.h.rem_euclid(360.0);
+            let within = if full_circle {
+                true
+            } else if start <= end {
+                start <= h && h <= end
+            } else {
+                h >= start || h <= end
+            };
+            let hue_distance = if within {
+                0.0
+            } else {
+                angle(h, start).min(angle(h, end))
+            };
+            let chroma_distance = (chroma_min + MASK_CHROMA_FEATHER - oklch.c)
+                .max(0.0);
+            feather(hue_distance, 0.0, MASK_HUE_FEATHER)
+                * feather(chroma_distance, 0.0, MASK_CHROMA_FEATHER)
+        })
+        .collect()
+}
+
+/// Applies the given adjustment to the pixels through the given mask of
+/// per-pixel weights between 0 and 1.
+///
+/// Each pixel is replaced by the adjusted pixel where its weight is 1, and
+/// left unchanged where its weight is 0. Between these, the original and
+/// adjusted pixels are interpolated in [`Oklab`].
+///
+/// [`Oklab`]: ../struct.Oklab.html
+///
+/// # Panics
+///
+/// Panics if the mask and the pixels have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::adjust::apply_masked;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut pixels = vec![Rgb::new(200, 100, 50); 3];
+///
+/// apply_masked(&mut pixels, &[0.0, 0.5, 1.0], |c| c.invert());
+///
+/// assert_eq!(pixels[0], Rgb::new(200, 100, 50));
+/// assert_eq!(pixels[2], Rgb::new(55, 155, 205));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn apply_masked<F>(pixels: &mut [Rgb], mask: &[f32], adjustment: F)
+    where F: Fn(Rgb) -> Rgb
+{
+    assert_eq!(pixels.len(), mask.len(),
+        "mask must have one weight per pixel");
+    for (pixel, &weight) in pixels.iter_mut().zip(mask.iter()) {
+        let weight = weight.clamp(0.0, 1.0);
+        if weight <= 0.0 { continue; }
+
+        let adjusted = adjustment(*pixel);
+        if weight >= 1.0 {
+            *pixel = adjusted;
+            continue;
+        }
+        let a = Oklab::from(*pixel);
+        let b = Oklab::from(adjusted);
+        *pixel = Rgb::from(Oklab::new(
+            a.l + (b.l - a.l) * weight,
+            a.a + (b.a - a.a) * weight,
+            a.b + (b.b - a.b) * weight));
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the mean encoded channel ratio of the pixels, or 0.5 if there are
+/// none.
+fn mean_ratio(pixels: &[Rgb]) -> f32 {
+    if pixels.is_empty() { return 0.5; }
+    let total: u64 = pixels
+        .iter()
+        .map(|p| p.r as u64 + p.g as u64 + p.b as u64)
+        .sum();
+    total as f32 / (pixels.len() as f32 * 3.0 * u8::MAX as f32)
+}
+
+/// Returns the selection weight at the given distance from a selection of
+/// the given tolerance, falling off smoothly from 1 to 0 over the given
+/// softness.
+fn feather(distance: f32, tolerance: f32, softness: f32) -> f32 {
+    if distance <= tolerance { return 1.0; }
+    if distance >= tolerance + softness { return 0.0; }
+    1.0 - smoothstep(tolerance, tolerance + softness, distance)
+}
+
+/// Returns the smooth Hermite step from 0 at `low` to 1 at `high` at the
+/// given value.
+fn smoothstep(low: f32, high: f32, value: f32) -> f32 {
+    let t = ((value - low) / (high - low)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Returns the linear channel scales tinting a color toward the given
+/// [`Hsl`] hue by the given amount.
+///
+/// [`Hsl`]: ../struct.Hsl.html
+fn tint_factors(hue: f32, amount: f32) -> [f32; 3] {
+    let tint = Rgb::from(Hsl::new(hue, 1.0, 0.5))
+        .ratios()
+        .map(srgb_to_linear);
+    let max = tint[0].max(tint[1]).max(tint[2]);
+    let amount = clamped(amount, 0.0, 1.0);
+    tint.map(|v| 1.0 + (v / max - 1.0) * amount)
+}
+
+/// Returns the CIE 1960 UCS chromaticity of the given CIE XYZ color, or
+/// `None` if it is black.
+fn xyz_to_uv([x, y, z]: [f32; 3]) -> Option<[f32; 2]> {
+    let d = x + 15.0 * y + 3.0 * z;
+    if d <= 0.0 || y <= 0.0 { return None; }
+    Some([4.0 * x / d, 6.0 * y / d])
+}
+
+/// Returns the CIE XYZ color of unit luminance with the given CIE 1960 UCS
+/// chromaticity.
+fn uv_to_xyz([u, v]: [f32; 2]) -> Xyz {
+    let d = 2.0 * u - 8.0 * v + 4.0;
+    let (x, y) = (3.0 * u / d, 2.0 * v / d);
+    Xyz { x: x / y, y: 1.0, z: (1.0 - x - y) / y }
+}
+
+/// Returns the approximate correlated color temperature, in kelvin, of the
+/// given CIE 1960 UCS chromaticity, using McCamy's formula.
+fn uv_to_kelvin([u, v]: [f32; 2]) -> f32 {
+    let d = 2.0 * u - 8.0 * v + 4.0;
+    let (x, y) = (3.0 * u / d, 2.0 * v / d);
+    let n = (x - 0.3320) / (0.1858 - y);
+    ((449.0 * n + 3525.0) * n + 6823.3) * n + 5520.33
+}
+
+/// Returns the CIE 1960 UCS chromaticity of the Planckian locus at the given
+/// temperature, in kelvin, using Krystek's approximation.
+fn planckian_uv(kelvin: f32) -> [f32; 2] {
+    let t = kelvin as f64;
+    let u = (0.860117757 + 1.54118254e-4 * t + 1.28641212e-7 * t * t)
+        / (1.0 + 8.42420235e-4 * t + 7.08145163e-7 * t * t);
+    let v = (0.317398726 + 4.22806245e-5 * t + 4.20481691e-8 * t * t)
+        / (1.0 - 2.89741816e-5 * t + 1.61456053e-7 * t * t);
+    [u as f32, v as f32]
+}
+
+/// Returns the unit normal of the Planckian locus at the given temperature,
+/// in kelvin, pointing toward green, away from magenta.
+fn planckian_normal(kelvin: f32) -> [f32; 2] {
+    let a = planckian_uv(kelvin - 1.0);
+    let b = planckian_uv(kelvin + 1.0);
+    let (du, dv) = (b[0] - a[0], b[1] - a[1]);
+    let length = (du * du + dv * dv).sqrt();
+    // Rising temperatures run toward lower u, so the normal is the tangent
+    // rotated clockwise.
+    [dv / length, -du / length]
+}
+
+/// Converts a channel ratio to an octet, rounding and clamping.
+fn octet(ratio: f32) -> u8 {
+    (ratio * u8::MAX as f32).round().clamp(0.0, u8::MAX as f32) as u8
+}
+
+/// Returns the table mapping each octet through the given function of
+/// channel ratios.
+fn compile_table<F>(f: F) -> [u8; 256] where F: Fn(f32) -> f32 {
+    let mut table = [0; 256];
+    for (i, v) in table.iter_mut().enumerate() {
+        *v = octet(f(i as f32 / u8::MAX as f32));
+    }
+    table
+}
+
+/// Returns the slopes of the monotone cubic spline through the given points,
+/// sorted by their first coordinate, using the Fritsch-Carlson method.
+fn monotone_slopes(points: &[[f32; 2]]) -> Vec<f32> {
+    let n = points.len();
+    if n < 2 { return vec![0.0; n]; }
+
+    let secants: Vec<f32> = points
+        .windows(2)
+        .map(|w| (w[1][1] - w[0][1]) / (w[1][0] - w[0][0]))
+        .collect();
+    let mut slopes = Vec::with_capacity(n);
+    slopes.push(secants[0]);
+    for w in secants.windows(2) {
+        // The slope is flat at local extrema.
+        if w[0] * w[1] <= 0.0 {
+            slopes.push(0.0);
+        } else {
+            slopes.push((w[0] + w[1]) / 2.0);
+        }
+    }
+    slopes.push(secants[n - 2]);
+
+    // Limit the slopes so that each segment is monotone.
+    for (i, &secant) in secants.iter().enumerate() {
+        if secant == 0.0 {
+            slopes[i] = 0.0;
+            slopes[i + 1] = 0.0;
+            continue;
+        }
+        let alpha = slopes[i] / secant;
+        let beta = slopes[i + 1] / secant;
+        let radius = alpha.hypot(beta);
+        if radius > 3.0 {
+            slopes[i] = 3.0 / radius * alpha * secant;
+            slopes[i + 1] = 3.0 / radius * beta * secant;
+        }
+    }
+    slopes
+}
@@ -0,0 +1,484 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Analysis functions for pixel buffers.
+//!
+//! Pixel buffers are given as row-major slices of [`Rgb`] colors along with
+//! the width of each row.
+//!
+//! [`Rgb`]: ../struct.Rgb.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::contrast::composite;
+use crate::contrast::contrast_ratio;
+use crate::contrast::solve_scrim_opacity;
+use crate::gradient::Gradient;
+use crate::Hsl;
+use crate::Rgb;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The maximum number of stops of a gradient made by [`extract_ramp`].
+///
+/// [`extract_ramp`]: fn.extract_ramp.html
+const RAMP_MAX_STOPS: usize = 16;
+
+/// The ΔE*00 within which a gradient made by [`extract_ramp`] fits the
+/// averaged pixels.
+///
+/// [`extract_ramp`]: fn.extract_ramp.html
+const RAMP_TOLERANCE: f32 = 1.0;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Background
+////////////////////////////////////////////////////////////////////////////////
+/// The result of a background detection.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Background {
+    /// The estimated background color.
+    pub color: Rgb,
+    /// The fraction of border pixels which match the background color, between
+    /// 0 and 1.
+    pub confidence: f32,
+    /// The suggested color to use when padding the image.
+    pub matte: Rgb,
+}
+
+impl Background {
+    /// Returns true if the background is considered uniform, i.e., if more
+    /// than half of the border pixels match the background color.
+    pub fn is_uniform(&self) -> bool {
+        self.confidence > 0.5
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// detect_background
+////////////////////////////////////////////////////////////////////////////////
+/// Detects a near-uniform background color along the border of a pixel buffer.
+///
+/// The background color is the component-wise median of the border pixels, and
+/// the confidence is the fraction of border pixels within `tolerance` of it,
+/// measured with [`Rgb::distance`]. If the background is uniform, the
+/// suggested matte is the mean of the matching border pixels; otherwise it is
+/// a neutral gray with the mean lightness of the border, which is the least
+/// distracting padding for a busy edge.
+///
+/// Returns `None` if the buffer is empty.
+///
+/// [`Rgb::distance`]: ../struct.Rgb.html#method.distance
+///
+/// # Panics
+///
+/// Panics if `width` is zero or does not evenly divide the buffer length.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::analysis::detect_background;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let white = Rgb::new(255, 255, 255);
+/// let red = Rgb::new(255, 0, 0);
+/// let pixels = vec![
+///     white, white, white,
+///     white, red,   white,
+///     white, white, white,
+/// ];
+///
+/// let background = detect_background(&pixels, 3, 8.0).unwrap();
+///
+/// assert_eq!(background.color, white);
+/// assert_eq!(background.confidence, 1.0);
+/// assert_eq!(background.matte, white);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn detect_background(pixels: &[Rgb], width: usize, tolerance: f32)
+    -> Option<Background>
+{
+    let border = border_pixels(pixels, width);
+    if border.is_empty() { return None; }
+
+    let color = median(&border);
+    let matching: Vec<Rgb> = border
+        .iter()
+        .copied()
+        .filter(|&px| Rgb::distance(px, color) <= tolerance)
+        .collect();
+    let confidence = matching.len() as f32 / border.len() as f32;
+
+    let mut background = Background { color, confidence, matte: color };
+    background.matte = if background.is_uniform() {
+        mean(&matching)
+    } else {
+        let mut hsl = Hsl::from(mean(&border));
+        hsl.set_saturation(0.0);
+        Rgb::from(hsl)
+    };
+
+    Some(background)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Region
+////////////////////////////////////////////////////////////////////////////////
+/// A rectangular region of a pixel buffer.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Region {
+    /// The column of the left edge of the region.
+    pub x: usize,
+    /// The row of the top edge of the region.
+    pub y: usize,
+    /// The width of the region in pixels.
+    pub width: usize,
+    /// The height of the region in pixels.
+    pub height: usize,
+}
+
+impl Region {
+    /// Returns the pixels of the given buffer which lie within the region.
+    /// Parts of the region outside of the buffer are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero or does not evenly divide the buffer length.
+    pub fn pixels<T>(&self, pixels: &[T], width: usize) -> Vec<T>
+        where T: Copy
+    {
+        let height = buffer_height(pixels, width);
+        let x_end = (self.x + self.width).min(width);
+        let y_end = (self.y + self.height).min(height);
+
+        let mut selected = Vec::new();
+        for y in self.y.min(y_end)..y_end {
+            let row = &pixels[y * width..(y + 1) * width];
+            selected.extend_from_slice(&row[self.x.min(x_end)..x_end]);
+        }
+        selected
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Scrim
+////////////////////////////////////////////////////////////////////////////////
+/// A translucent layer placed between a background and overlaid text.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scrim {
+    /// The scrim color.
+    pub color: Rgb,
+    /// The scrim opacity, between 0 and 1.
+    pub opacity: f32,
+}
+
+impl Scrim {
+    /// Returns the color produced by drawing the scrim over the given color.
+    pub fn apply(&self, background: Rgb) -> Rgb {
+        composite(background, self.color, self.opacity)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Legibility
+////////////////////////////////////////////////////////////////////////////////
+/// The result of a legibility analysis.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Legibility {
+    /// The lowest contrast ratio between the text color and any pixel.
+    pub worst_contrast: f32,
+    /// The pixel color with the lowest contrast against the text color.
+    pub worst_color: Rgb,
+    /// The mean contrast ratio between the text color and the pixels.
+    pub mean_contrast: f32,
+    /// The scrim needed to reach the target contrast over every pixel, or
+    /// `None` if the target cannot be reached with either a black or white
+    /// scrim. A scrim with zero opacity indicates that the text is already
+    /// legible.
+    pub scrim: Option<Scrim>,
+}
+
+impl Legibility {
+    /// Returns true if the text reaches the target contrast without a scrim.
+    pub fn is_legible(&self) -> bool {
+        self.scrim.is_some_and(|scrim| scrim.opacity == 0.0)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// legibility
+////////////////////////////////////////////////////////////////////////////////
+/// Analyzes the legibility of `text` drawn over the given region of a pixel
+/// buffer, and solves for the scrim needed to reach the `target` contrast
+/// ratio everywhere in the region.
+///
+/// The scrim is black for light text and white for dark text, whichever
+/// contrasts more with the text color.
+///
+/// Returns `None` if the region contains no pixels.
+///
+/// # Panics
+///
+/// Panics if `width` is zero or does not evenly divide the buffer length.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::analysis::Region;
+/// # use color::analysis::legibility;
+/// # use color::contrast::AA;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let dark = Rgb::new(20, 20, 40);
+/// let light = Rgb::new(230, 230, 210);
+/// let pixels = vec![
+///     dark, dark,  dark,
+///     dark, light, dark,
+/// ];
+/// let caption = Region { x: 0, y: 1, width: 3, height: 1 };
+/// let text = Rgb::new(255, 255, 255);
+///
+/// let result = legibility(&pixels, 3, caption, text, AA).unwrap();
+///
+/// assert_eq!(result.worst_color, light);
+/// assert!(!result.is_legible());
+///
+/// let scrim = result.scrim.unwrap();
+/// assert_eq!(scrim.color, Rgb::new(0, 0, 0));
+/// assert!(scrim.opacity > 0.0 && scrim.opacity < 1.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn legibility(
+    pixels: &[Rgb],
+    width: usize,
+    region: Region,
+    text: Rgb,
+    target: f32)
+    -> Option<Legibility>
+{
+    let mut selected = region.pixels(pixels, width);
+    if selected.is_empty() { return None; }
+
+    let total: f32 = selected.iter().map(|&px| contrast_ratio(text, px)).sum();
+    let mean_contrast = total / selected.len() as f32;
+
+    selected.sort_unstable();
+    selected.dedup();
+
+    let (worst_color, worst_contrast) = selected
+        .iter()
+        .map(|&px| (px, contrast_ratio(text, px)))
+        .fold((selected[0], f32::INFINITY), |worst, next| {
+            if next.1 < worst.1 { next } else { worst }
+        });
+
+    let black = Rgb::new(0, 0, 0);
+    let white = Rgb::new(255, 255, 255);
+    let color = if contrast_ratio(text, black) >= contrast_ratio(text, white) {
+        black
+    } else {
+        white
+    };
+
+    let scrim = selected
+        .iter()
+        .map(|&px| solve_scrim_opacity(text, px, color, target))
+        .try_fold(0.0f32, |max, opacity| opacity.map(|o| max.max(o)))
+        .map(|opacity| Scrim { color, opacity });
+
+    Some(Legibility { worst_contrast, worst_color, mean_contrast, scrim })
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Axis
+////////////////////////////////////////////////////////////////////////////////
+/// The direction of a pixel buffer along which [`extract_ramp`] runs.
+///
+/// [`extract_ramp`]: fn.extract_ramp.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Axis {
+    /// From left to right, averaging each column.
+    #[default]
+    Horizontal,
+    /// From top to bottom, averaging each row.
+    Vertical,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// extract_ramp
+////////////////////////////////////////////////////////////////////////////////
+/// Extracts a representative [`Gradient`] running along the given [`Axis`]
+/// of a pixel buffer, such as for a UI background matching an artwork
+/// header.
+///
+/// Each column or row across the axis is averaged in linear light, and a
+/// gradient of at most 16 stops is fit through the averages using
+/// [`Gradient::fit`], so that every average is within a ΔE*00 of 1 of the
+/// gradient where possible.
+///
+/// Returns `None` if the buffer is empty.
+///
+/// [`Gradient`]: ../gradient/struct.Gradient.html
+/// [`Axis`]: enum.Axis.html
+/// [`Gradient::fit`]: ../gradient/struct.Gradient.html#method.fit
+///
+/// # Panics
+///
+/// Panics if `width` is zero or does not evenly divide the buffer length.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::analysis::Axis;
+/// # use color::analysis::extract_ramp;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let red = Rgb::new(255, 0, 0);
+/// let blue = Rgb::new(0, 0, 255);
+/// let pixels = vec![
+///     red, red, blue,
+///     red, red, blue,
+/// ];
+///
+/// let ramp = extract_ramp(&pixels, 3, Axis::Horizontal).unwrap();
+///
+/// assert_eq!(ramp.color(0.0), red);
+/// assert_eq!(ramp.color(1.0), blue);
+///
+/// let ramp = extract_ramp(&pixels, 3, Axis::Vertical).unwrap();
+///
+/// assert_eq!(ramp.color(0.0), ramp.color(1.0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn extract_ramp(pixels: &[Rgb], width: usize, axis: Axis)
+    -> Option<Gradient<Rgb>>
+{
+    let height = buffer_height(pixels, width);
+    if height == 0 { return None; }
+
+    let samples: Vec<Rgb> = match axis {
+        Axis::Horizontal => (0..width)
+            .map(|x| linear_mean((0..height).map(|y| pixels[y * width + x])))
+            .collect(),
+        Axis::Vertical => pixels
+            .chunks_exact(width)
+            .map(|row| linear_mean(row.iter().copied()))
+            .collect(),
+    };
+    Some(Gradient::fit(&samples, RAMP_MAX_STOPS, RAMP_TOLERANCE))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the height of a pixel buffer.
+pub(in crate) fn buffer_height<T>(pixels: &[T], width: usize) -> usize {
+    assert!(width > 0);
+    assert_eq!(pixels.len() % width, 0);
+    pixels.len() / width
+}
+
+/// Returns the pixels along the border of a pixel buffer, each only once.
+fn border_pixels(pixels: &[Rgb], width: usize) -> Vec<Rgb> {
+    let height = buffer_height(pixels, width);
+    if height == 0 { return Vec::new(); }
+
+    let mut border = Vec::with_capacity(2 * (width + height));
+    for y in 0..height {
+        let row = &pixels[y * width..(y + 1) * width];
+        if y == 0 || y == height - 1 {
+            border.extend_from_slice(row);
+        } else {
+            border.push(row[0]);
+            if width > 1 { border.push(row[width - 1]); }
+        }
+    }
+    border
+}
+
+/// Returns the component-wise median of the given colors.
+fn median(colors: &[Rgb]) -> Rgb {
+    let mid = colors.len() / 2;
+    let channel = |f: fn(&Rgb) -> u8| {
+        let mut values: Vec<u8> = colors.iter().map(f).collect();
+        values.sort_unstable();
+        values[mid]
+    };
+    Rgb::new(channel(|c| c.r), channel(|c| c.g), channel(|c| c.b))
+}
+
+/// Returns the mean of the given colors in linear light.
+fn linear_mean<I>(colors: I) -> Rgb where I: Iterator<Item=Rgb> {
+    let mut sum = [0.0f32; 3];
+    let mut count = 0;
+    for color in colors {
+        let linear = color.linear_ratios();
+        for i in 0..3 { sum[i] += linear[i]; }
+        count += 1;
+    }
+    Rgb::from_linear_ratios(sum.map(|total| total / count.max(1) as f32))
+}
+
+/// Returns the component-wise mean of the given colors.
+pub(in crate) fn mean(colors: &[Rgb]) -> Rgb {
+    let count = colors.len().max(1) as u64;
+    let (r, g, b) = colors.iter().fold((0u64, 0u64, 0u64), |(r, g, b), c| {
+        (r + c.r as u64, g + c.g as u64, b + c.b as u64)
+    });
+    Rgb::new(
+        ((r + count / 2) / count) as u8,
+        ((g + count / 2) / count) as u8,
+        ((b + count / 2) / count) as u8)
+}
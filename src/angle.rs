@@ -0,0 +1,183 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Functions for working with hue angles, measured in degrees.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::utility::clamped;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// wrap
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given angle wrapped into the range [0, 360).
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::angle::wrap;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(wrap(370.0), 10.0);
+/// assert_eq!(wrap(-90.0), 270.0);
+/// assert_eq!(wrap(360.0), 0.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn wrap(degrees: f32) -> f32 {
+    let wrapped = degrees.rem_euclid(360.0);
+    // Tiny negative angles round up to 360.
+    if wrapped >= 360.0 { 0.0 } else { wrapped }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// difference
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the signed difference from one angle to another along the shorter
+/// arc, in the range (-180, 180]. Positive differences are counterclockwise,
+/// i.e., increasing.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::angle::difference;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(difference(350.0, 10.0), 20.0);
+/// assert_eq!(difference(10.0, 350.0), -20.0);
+/// assert_eq!(difference(0.0, 180.0), 180.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn difference(from: f32, to: f32) -> f32 {
+    let delta = wrap(to - from);
+    if delta > 180.0 { delta - 360.0 } else { delta }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// mean
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the circular mean of the given angles, or `None` if there are no
+/// angles or they cancel out, such as two opposite angles.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::angle::mean;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let average = mean(vec![350.0, 10.0, 30.0]).unwrap();
+/// assert!((average - 10.0).abs() < 1e-4);
+///
+/// assert_eq!(mean(vec![90.0, 270.0]), None);
+/// assert_eq!(mean(Vec::new()), None);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn mean<I>(angles: I) -> Option<f32> where I: IntoIterator<Item=f32> {
+    let (mut x, mut y, mut count) = (0.0f64, 0.0f64, 0);
+    for angle in angles {
+        let (sin, cos) = (angle as f64).to_radians().sin_cos();
+        x += cos;
+        y += sin;
+        count += 1;
+    }
+    if count == 0 || x.hypot(y) < 1e-6 * count as f64 { return None; }
+    Some(wrap(y.atan2(x).to_degrees() as f32))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Arc
+////////////////////////////////////////////////////////////////////////////////
+/// The arc of the hue circle followed when interpolating between two angles.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Arc {
+    /// The shorter of the two arcs.
+    #[default]
+    Shorter,
+    /// The longer of the two arcs.
+    Longer,
+    /// The arc along which the angle increases.
+    Increasing,
+    /// The arc along which the angle decreases.
+    Decreasing,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// lerp
+////////////////////////////////////////////////////////////////////////////////
+/// Performs a linear interpolation between the angles `start` and `end` along
+/// the given arc, returning the angle located at the ratio given by `amount`,
+/// which is clamped between 0 and 1. The result is wrapped into the range
+/// [0, 360).
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::angle::Arc;
+/// # use color::angle::lerp;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(lerp(340.0, 20.0, 0.5, Arc::Shorter), 0.0);
+/// assert_eq!(lerp(340.0, 20.0, 0.5, Arc::Longer), 180.0);
+/// assert_eq!(lerp(20.0, 60.0, 0.5, Arc::Decreasing), 220.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn lerp(start: f32, end: f32, amount: f32, arc: Arc) -> f32 {
+    let shorter = difference(start, end);
+    let delta = match arc {
+        Arc::Shorter    => shorter,
+        Arc::Longer if shorter > 0.0 => shorter - 360.0,
+        Arc::Longer if shorter < 0.0 => shorter + 360.0,
+        Arc::Longer     => 0.0,
+        Arc::Increasing => wrap(end - start),
+        Arc::Decreasing => -wrap(start - end),
+    };
+    wrap(start + delta * clamped(amount, 0.0, 1.0))
+}
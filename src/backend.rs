@@ -0,0 +1,332 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Interchangeable implementations of bulk color conversions.
+//!
+//! A [`ConversionBackend`] converts whole slices of colors at once. Code which
+//! is generic over the trait selects a backend at compile time, while the
+//! [`Backend`] enum selects one at runtime, defaulting to the fastest
+//! for the target the crate was compiled for. The slice conversions in the
+//! [`bulk`] module use the default backend. Floating point results may differ
+//! between backends in the last few bits, so `Rgb` octets may very rarely
+//! differ by one when a value lies on a rounding boundary.
+//!
+//! [`ConversionBackend`]: trait.ConversionBackend.html
+//! [`Backend`]: enum.Backend.html
+//! [`bulk`]: ../bulk/index.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::oklab::from_linear_srgb;
+use crate::color_space::oklab::linear_srgb;
+use crate::color_space::oklab::lms_from_linear_srgb;
+use crate::color_space::oklab::oklab_from_lms;
+use crate::Oklab;
+use crate::Rgb;
+use crate::utility::linear_to_srgb;
+use crate::utility::linear_to_srgb_octet;
+use crate::utility::srgb_octet_to_linear;
+use crate::utility::srgb_to_linear;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The number of colors processed together by the batched conversions.
+const LANES: usize = 8;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ConversionBackend
+////////////////////////////////////////////////////////////////////////////////
+/// An implementation of bulk color conversions.
+///
+/// Only the sRGB transfer functions are required; the remaining conversions
+/// have default implementations built on them, which backends may override.
+///
+/// # Panics
+///
+/// Each method panics if the source and destination slices have different
+/// lengths.
+pub trait ConversionBackend {
+    /// Decodes sRGB colors into linear light ratios.
+    fn decode(&self, src: &[Rgb], dst: &mut [[f32; 3]]);
+
+    /// Encodes linear light ratios into sRGB colors, clamping them into
+    /// range.
+    fn encode(&self, src: &[[f32; 3]], dst: &mut [Rgb]);
+
+    /// Converts sRGB colors into `Oklab` colors.
+    fn rgb_to_oklab(&self, src: &[Rgb], dst: &mut [Oklab]) {
+        assert_eq!(src.len(), dst.len());
+        let mut linear = [[0.0; 3]; LANES];
+        for (src, dst) in src.chunks(LANES).zip(dst.chunks_mut(LANES)) {
+            let linear = &mut linear[..src.len()];
+            self.decode(src, linear);
+            for (rgb, oklab) in linear.iter().zip(dst.iter_mut()) {
+                *oklab = from_linear_srgb(*rgb);
+            }
+        }
+    }
+
+    /// Converts `Oklab` colors into sRGB colors, clamping them into the sRGB
+    /// gamut.
+    fn oklab_to_rgb(&self, src: &[Oklab], dst: &mut [Rgb]) {
+        assert_eq!(src.len(), dst.len());
+        let mut linear = [[0.0; 3]; LANES];
+        for (src, dst) in src.chunks(LANES).zip(dst.chunks_mut(LANES)) {
+            let linear = &mut linear[..src.len()];
+            for (oklab, rgb) in src.iter().zip(linear.iter_mut()) {
+                *rgb = linear_srgb(*oklab);
+            }
+            self.encode(linear, dst);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Scalar
+////////////////////////////////////////////////////////////////////////////////
+/// A backend which evaluates the conversion formulas directly for each color.
+///
+/// This is the reference implementation of the sRGB transfer functions.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Scalar;
+
+impl ConversionBackend for Scalar {
+    fn decode(&self, src: &[Rgb], dst: &mut [[f32; 3]]) {
+        assert_eq!(src.len(), dst.len());
+        for (rgb, linear) in src.iter().zip(dst.iter_mut()) {
+            *linear = rgb.ratios().map(srgb_to_linear);
+        }
+    }
+
+    fn encode(&self, src: &[[f32; 3]], dst: &mut [Rgb]) {
+        assert_eq!(src.len(), dst.len());
+        for (linear, rgb) in src.iter().zip(dst.iter_mut()) {
+            let [r, g, b] = linear.map(|c| {
+                (linear_to_srgb(c).clamp(0.0, 1.0) * u8::MAX as f32)
+                    .round() as u8
+            });
+            *rgb = Rgb::new(r, g, b);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lut
+////////////////////////////////////////////////////////////////////////////////
+/// A backend which uses lookup tables for the sRGB transfer functions.
+///
+/// Decoding is a single table lookup per channel, and encoding is a binary
+/// search over the decision thresholds between octets. The tables are built
+/// on first use.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Lut;
+
+impl ConversionBackend for Lut {
+    fn decode(&self, src: &[Rgb], dst: &mut [[f32; 3]]) {
+        assert_eq!(src.len(), dst.len());
+        for (rgb, linear) in src.iter().zip(dst.iter_mut()) {
+            *linear = rgb.octets().map(srgb_octet_to_linear);
+        }
+    }
+
+    fn encode(&self, src: &[[f32; 3]], dst: &mut [Rgb]) {
+        assert_eq!(src.len(), dst.len());
+        for (linear, rgb) in src.iter().zip(dst.iter_mut()) {
+            let [r, g, b] = linear.map(linear_to_srgb_octet);
+            *rgb = Rgb::new(r, g, b);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Batched
+////////////////////////////////////////////////////////////////////////////////
+/// A backend which processes colors in groups, using lookup tables for the
+/// sRGB transfer functions and branch-free arithmetic for the rest.
+///
+/// This is portable Rust with no explicit SIMD instructions. Its loops are
+/// laid out so that the compiler can vectorize them when the target's SIMD
+/// instructions are enabled at compile time.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Batched;
+
+impl ConversionBackend for Batched {
+    fn decode(&self, src: &[Rgb], dst: &mut [[f32; 3]]) {
+        Lut.decode(src, dst)
+    }
+
+    fn encode(&self, src: &[[f32; 3]], dst: &mut [Rgb]) {
+        Lut.encode(src, dst)
+    }
+
+    fn rgb_to_oklab(&self, src: &[Rgb], dst: &mut [Oklab]) {
+        assert_eq!(src.len(), dst.len());
+        let mut linear = [[0.0; 3]; LANES];
+        for (src, dst) in src.chunks(LANES).zip(dst.chunks_mut(LANES)) {
+            self.decode(src, &mut linear[..src.len()]);
+
+            let mut lms = [[0.0; LANES]; 3];
+            for (lane, rgb) in linear.iter().enumerate() {
+                let [l, m, s] = lms_from_linear_srgb(*rgb);
+                lms[0][lane] = l;
+                lms[1][lane] = m;
+                lms[2][lane] = s;
+            }
+            for channel in lms.iter_mut() {
+                for value in channel.iter_mut() {
+                    *value = fast_cbrt(*value);
+                }
+            }
+            for (lane, oklab) in dst.iter_mut().enumerate() {
+                let roots = [lms[0][lane], lms[1][lane], lms[2][lane]];
+                *oklab = oklab_from_lms(roots);
+            }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Backend
+////////////////////////////////////////////////////////////////////////////////
+/// A conversion backend selected at runtime.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Backend {
+    /// The [`Scalar`] backend.
+    ///
+    /// [`Scalar`]: struct.Scalar.html
+    Scalar,
+    /// The [`Lut`] backend.
+    ///
+    /// [`Lut`]: struct.Lut.html
+    Lut,
+    /// The [`Batched`] backend.
+    ///
+    /// [`Batched`]: struct.Batched.html
+    Batched,
+}
+
+impl Backend {
+    /// Returns the fastest backend for the target the crate was compiled
+    /// for.
+    ///
+    /// This is decided by the target features enabled at compile time, not
+    /// by detecting the features of the running CPU: the [`Batched`] backend
+    /// is chosen if the target has SIMD instructions for the compiler to
+    /// vectorize it with, and the [`Lut`] backend otherwise.
+    ///
+    /// [`Batched`]: struct.Batched.html
+    /// [`Lut`]: struct.Lut.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # use color::Rgb;
+    /// # use color::backend::Backend;
+    /// # use color::backend::ConversionBackend;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let pixels = vec![Rgb::new(255, 99, 71); 100];
+    /// let mut oklab = vec![Oklab::default(); pixels.len()];
+    /// let mut round_trip = vec![Rgb::default(); pixels.len()];
+    ///
+    /// let backend = Backend::for_target();
+    /// backend.rgb_to_oklab(&pixels, &mut oklab);
+    /// backend.oklab_to_rgb(&oklab, &mut round_trip);
+    ///
+    /// assert_eq!(round_trip, pixels);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn for_target() -> Self {
+        if cfg!(any(
+            target_feature = "sse2",
+            target_feature = "neon",
+            target_feature = "simd128"))
+        {
+            Backend::Batched
+        } else {
+            Backend::Lut
+        }
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Backend::for_target()
+    }
+}
+
+impl ConversionBackend for Backend {
+    fn decode(&self, src: &[Rgb], dst: &mut [[f32; 3]]) {
+        match self {
+            Backend::Scalar  => Scalar.decode(src, dst),
+            Backend::Lut     => Lut.decode(src, dst),
+            Backend::Batched => Batched.decode(src, dst),
+        }
+    }
+
+    fn encode(&self, src: &[[f32; 3]], dst: &mut [Rgb]) {
+        match self {
+            Backend::Scalar  => Scalar.encode(src, dst),
+            Backend::Lut     => Lut.encode(src, dst),
+            Backend::Batched => Batched.encode(src, dst),
+        }
+    }
+
+    fn rgb_to_oklab(&self, src: &[Rgb], dst: &mut [Oklab]) {
+        match self {
+            Backend::Scalar  => Scalar.rgb_to_oklab(src, dst),
+            Backend::Lut     => Lut.rgb_to_oklab(src, dst),
+            Backend::Batched => Batched.rgb_to_oklab(src, dst),
+        }
+    }
+
+    fn oklab_to_rgb(&self, src: &[Oklab], dst: &mut [Rgb]) {
+        match self {
+            Backend::Scalar  => Scalar.oklab_to_rgb(src, dst),
+            Backend::Lut     => Lut.oklab_to_rgb(src, dst),
+            Backend::Batched => Batched.oklab_to_rgb(src, dst),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the cube root of a value using only arithmetic which vectorizes,
+/// accurate to within a few units in the last place.
+#[inline]
+fn fast_cbrt(value: f32) -> f32 {
+    // An initial estimate from dividing the exponent by three, refined by
+    // Newton's method.
+    let bits = value.to_bits();
+    let sign = bits & 0x8000_0000;
+    let estimate = (bits & 0x7FFF_FFFF) / 3 + 0x2A51_19F2;
+    let mut y = f32::from_bits(sign | estimate);
+    for _ in 0..3 {
+        y = (2.0 * y + value / (y * y)) / 3.0;
+    }
+    if value == 0.0 { 0.0 } else { y }
+}
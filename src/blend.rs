@@ -0,0 +1,335 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Blend modes for layering colors.
+//!
+//! A [`BlendMode`] combines a source color drawn on top of a backdrop color,
+//! as in the layer modes of image editors. The modes and their formulas follow
+//! the W3C Compositing and Blending specification, so results match CSS
+//! `mix-blend-mode` and common editors.
+//!
+//! [`BlendMode`]: enum.BlendMode.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Rgb;
+use crate::Rgba;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The channel weights of the luminosity used by the non-separable blend
+/// modes.
+const LUMINOSITY_WEIGHTS: [f32; 3] = [0.3, 0.59, 0.11];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// BlendMode
+////////////////////////////////////////////////////////////////////////////////
+/// A method of combining a source color with the backdrop color beneath it.
+///
+/// The separable modes combine each channel independently. The non-separable
+/// modes, [`Hue`], [`Saturation`], [`Color`], and [`Luminosity`], combine
+/// components of the colors as a whole.
+///
+/// [`Hue`]: #variant.Hue
+/// [`Saturation`]: #variant.Saturation
+/// [`Color`]: #variant.Color
+/// [`Luminosity`]: #variant.Luminosity
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::blend::BlendMode;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let backdrop = Rgb::new(200, 100, 50);
+/// let source = Rgb::new(128, 128, 255);
+///
+/// assert_eq!(BlendMode::Multiply.blend(backdrop, source),
+///     Rgb::new(100, 50, 50));
+/// assert_eq!(BlendMode::Screen.blend(backdrop, source),
+///     Rgb::new(228, 178, 255));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BlendMode {
+    /// The source color replaces the backdrop.
+    #[default]
+    Normal,
+    /// The channels are multiplied, which always darkens.
+    Multiply,
+    /// The inverted channels are multiplied, which always lightens.
+    Screen,
+    /// Multiplies or screens depending on the backdrop, preserving its
+    /// highlights and shadows. This is [`HardLight`] with the colors swapped.
+    ///
+    /// [`HardLight`]: #variant.HardLight
+    Overlay,
+    /// The darker of each channel is kept.
+    Darken,
+    /// The lighter of each channel is kept.
+    Lighten,
+    /// The backdrop is brightened to reflect the source.
+    ColorDodge,
+    /// The backdrop is darkened to reflect the source.
+    ColorBurn,
+    /// Multiplies or screens depending on the source, as if shining a harsh
+    /// spotlight on the backdrop.
+    HardLight,
+    /// Darkens or lightens depending on the source, as if shining a diffused
+    /// spotlight on the backdrop.
+    SoftLight,
+    /// The darker of each channel is subtracted from the lighter.
+    Difference,
+    /// Similar to [`Difference`], but with lower contrast.
+    ///
+    /// [`Difference`]: #variant.Difference
+    Exclusion,
+    /// The hue of the source with the saturation and luminosity of the
+    /// backdrop.
+    Hue,
+    /// The saturation of the source with the hue and luminosity of the
+    /// backdrop.
+    Saturation,
+    /// The hue and saturation of the source with the luminosity of the
+    /// backdrop.
+    Color,
+    /// The luminosity of the source with the hue and saturation of the
+    /// backdrop.
+    Luminosity,
+}
+
+impl BlendMode {
+    /// Returns true if the blend mode combines each channel independently.
+    pub fn is_separable(self) -> bool {
+        !matches!(self,
+            BlendMode::Hue
+            | BlendMode::Saturation
+            | BlendMode::Color
+            | BlendMode::Luminosity)
+    }
+
+    /// Returns the result of blending the opaque source color over the
+    /// opaque backdrop color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::blend::BlendMode;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let backdrop = Rgb::new(128, 128, 128);
+    /// let red = Rgb::new(255, 0, 0);
+    ///
+    /// let tinted = BlendMode::Color.blend(backdrop, red);
+    ///
+    /// assert!(tinted.r > tinted.g && tinted.g == tinted.b);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn blend(self, backdrop: Rgb, source: Rgb) -> Rgb {
+        let [r, g, b] = self
+            .blend_ratios(backdrop.ratios(), source.ratios())
+            .map(octet);
+        Rgb::new(r, g, b)
+    }
+
+    /// Returns the result of blending the source color over the backdrop
+    /// color, accounting for the alpha of both.
+    ///
+    /// Where the backdrop is transparent, the source is drawn unblended. The
+    /// blended color is then composited over the backdrop using the
+    /// source-over operator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # use color::blend::BlendMode;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let source = Rgba::new(255, 0, 0, 255);
+    ///
+    /// let transparent = Rgba::new(0, 0, 255, 0);
+    /// assert_eq!(BlendMode::Multiply.blend_rgba(transparent, source), source);
+    ///
+    /// let blue = Rgba::new(0, 0, 255, 255);
+    /// assert_eq!(BlendMode::Multiply.blend_rgba(blue, source),
+    ///     Rgba::new(0, 0, 0, 255));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn blend_rgba(self, backdrop: Rgba, source: Rgba) -> Rgba {
+        let [cb @ .., ab] = backdrop.ratios();
+        let [cs @ .., a_s] = source.ratios();
+
+        let blended = self.blend_ratios(cb, cs);
+        let alpha = a_s + ab * (1.0 - a_s);
+        let mut color = [0.0; 3];
+        if alpha > 0.0 {
+            for i in 0..3 {
+                let mixed = (1.0 - ab) * cs[i] + ab * blended[i];
+                color[i] = (a_s * mixed + ab * cb[i] * (1.0 - a_s)) / alpha;
+            }
+        }
+        let [r, g, b] = color.map(octet);
+        Rgba::new(r, g, b, octet(alpha))
+    }
+
+    /// Returns the blend of the given backdrop and source channel ratios.
+    fn blend_ratios(self, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+        match self {
+            BlendMode::Hue => {
+                set_lum(set_sat(cs, sat(cb)), lum(cb))
+            },
+            BlendMode::Saturation => {
+                set_lum(set_sat(cb, sat(cs)), lum(cb))
+            },
+            BlendMode::Color      => set_lum(cs, lum(cb)),
+            BlendMode::Luminosity => set_lum(cb, lum(cs)),
+            _ => [0, 1, 2].map(|i| self.blend_channel(cb[i], cs[i])),
+        }
+    }
+
+    /// Returns the blend of the given backdrop and source channel ratios
+    /// using a separable blend mode.
+    fn blend_channel(self, cb: f32, cs: f32) -> f32 {
+        match self {
+            BlendMode::Multiply   => cb * cs,
+            BlendMode::Screen     => cb + cs - cb * cs,
+            BlendMode::Overlay    => BlendMode::HardLight.blend_channel(cs, cb),
+            BlendMode::Darken     => cb.min(cs),
+            BlendMode::Lighten    => cb.max(cs),
+            BlendMode::ColorDodge => {
+                if cb <= 0.0 {
+                    0.0
+                } else if cs >= 1.0 {
+                    1.0
+                } else {
+                    (cb / (1.0 - cs)).min(1.0)
+                }
+            },
+            BlendMode::ColorBurn  => {
+                if cb >= 1.0 {
+                    1.0
+                } else if cs <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - cb) / cs).min(1.0)
+                }
+            },
+            BlendMode::HardLight  => {
+                if cs <= 0.5 {
+                    cb * 2.0 * cs
+                } else {
+                    let s = 2.0 * cs - 1.0;
+                    cb + s - cb * s
+                }
+            },
+            BlendMode::SoftLight  => {
+                if cs <= 0.5 {
+                    cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                    } else {
+                        cb.sqrt()
+                    };
+                    cb + (2.0 * cs - 1.0) * (d - cb)
+                }
+            },
+            BlendMode::Difference => (cb - cs).abs(),
+            BlendMode::Exclusion  => cb + cs - 2.0 * cb * cs,
+            _                     => cs,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Converts a channel ratio to an octet, rounding and clamping.
+fn octet(ratio: f32) -> u8 {
+    (ratio * u8::MAX as f32).round().clamp(0.0, u8::MAX as f32) as u8
+}
+
+/// Returns the luminosity of the given channel ratios.
+fn lum(c: [f32; 3]) -> f32 {
+    (0..3).map(|i| c[i] * LUMINOSITY_WEIGHTS[i]).sum()
+}
+
+/// Returns the saturation, the range of the channel ratios.
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+/// Returns the channel ratios shifted to the given luminosity, and then
+/// brought within range while preserving that luminosity.
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    let c = c.map(|v| v + d);
+
+    let l = lum(c);
+    let min = c[0].min(c[1]).min(c[2]);
+    let max = c[0].max(c[1]).max(c[2]);
+    if min < 0.0 {
+        c.map(|v| l + (v - l) * l / (l - min))
+    } else if max > 1.0 {
+        c.map(|v| l + (v - l) * (1.0 - l) / (max - l))
+    } else {
+        c
+    }
+}
+
+/// Returns the channel ratios scaled to the given saturation, with the
+/// smallest channel set to zero.
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| c[i].total_cmp(&c[j]));
+    let [min, mid, max] = order;
+
+    let mut result = [0.0; 3];
+    if c[max] > c[min] {
+        result[mid] = (c[mid] - c[min]) * s / (c[max] - c[min]);
+        result[max] = s;
+    }
+    result
+}
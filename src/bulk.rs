@@ -0,0 +1,456 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Resumable bulk processing.
+//!
+//! A [`Job`] performs a long-running operation, such as converting or
+//! quantizing a large image, a bounded chunk at a time. Each call to
+//! [`Job::process_chunk`] does a limited amount of work and returns, so a job
+//! can be driven from a GUI event loop within a frame budget, or from an
+//! async task which yields between chunks, without a thread pool.
+//!
+//! When the work doesn't need to be interleaved, [`convert_slice`] and
+//! [`convert_slice_into`] convert a whole slice of colors at once.
+//!
+//! With the `rayon` feature enabled, [`par_convert_slice`] and
+//! [`par_convert_slice_into`] divide the slice into chunks converted on
+//! the [rayon](https://crates.io/crates/rayon) thread pool.
+//!
+//! The slice conversions between `Rgb` and `Oklab` go through the default
+//! [`Backend`], so their results may differ from converting each color
+//! individually in the last few bits. Other conversions use `From`.
+//!
+//! [`Backend`]: ../backend/enum.Backend.html
+//! [`convert_slice`]: fn.convert_slice.html
+//! [`convert_slice_into`]: fn.convert_slice_into.html
+//! [`Job`]: trait.Job.html
+//! [`Job::process_chunk`]: trait.Job.html#tymethod.process_chunk
+//! [`par_convert_slice`]: fn.par_convert_slice.html
+//! [`par_convert_slice_into`]: fn.par_convert_slice_into.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::backend::Backend;
+use crate::backend::ConversionBackend;
+use crate::Oklab;
+use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::trace::without_spans;
+
+// External library imports.
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+// Standard library imports.
+use std::any::Any;
+use std::any::TypeId;
+use std::time::Duration;
+use std::time::Instant;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The budget of each chunk processed by [`Job::process_for`].
+///
+/// [`Job::process_for`]: trait.Job.html#method.process_for
+const TIMED_CHUNK: usize = 1024;
+
+/// The number of colors passed to the conversion backend at a time.
+const BACKEND_CHUNK: usize = 256;
+
+/// The number of colors in each chunk processed by the parallel functions.
+#[cfg(feature = "rayon")]
+pub(in crate) const PARALLEL_CHUNK: usize = 4096;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Progress
+////////////////////////////////////////////////////////////////////////////////
+/// The progress of a [`Job`], measured in units of work of roughly one color
+/// each.
+///
+/// [`Job`]: trait.Job.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Progress {
+    /// The units of work done so far.
+    pub done: usize,
+    /// The estimated total units of work. This may be refined as the job
+    /// proceeds, such as when an iterative algorithm converges early.
+    pub total: usize,
+}
+
+impl Progress {
+    /// Returns true if the job is complete.
+    pub fn is_complete(&self) -> bool {
+        self.done >= self.total
+    }
+
+    /// Returns the fraction of the work done, between 0 and 1.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.done as f32 / self.total as f32).min(1.0)
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Job
+////////////////////////////////////////////////////////////////////////////////
+/// A long-running operation which can be performed incrementally.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Oklab;
+/// # use color::Rgb;
+/// # use color::bulk::Convert;
+/// # use color::bulk::Job;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels = vec![Rgb::new(255, 99, 71); 10_000];
+/// let mut job = Convert::<Rgb, Oklab>::new(&pixels);
+///
+/// // Process up to 4096 colors at a time, e.g. once per frame.
+/// while !job.process_chunk(4096).is_complete() {
+///     // Yield to the event loop here.
+/// }
+///
+/// let oklab = job.finish().unwrap();
+/// assert_eq!(oklab.len(), pixels.len());
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub trait Job {
+    /// The result of the job.
+    type Output;
+
+    /// Performs up to `budget` units of work and returns the progress made
+    /// so far. Moving between the stages of a multi-stage job may do a small
+    /// amount of additional work. Does nothing if the job is complete.
+    fn process_chunk(&mut self, budget: usize) -> Progress;
+
+    /// Returns the progress made so far.
+    fn progress(&self) -> Progress;
+
+    /// Returns the result of the job, or `None` if it is not complete.
+    fn finish(self) -> Option<Self::Output>;
+
+    /// Processes chunks until the given duration has elapsed or the job is
+    /// complete, and returns the progress made so far. At least one chunk is
+    /// processed.
+    fn process_for(&mut self, duration: Duration) -> Progress {
+        let start = Instant::now();
+        loop {
+            let progress = self.process_chunk(TIMED_CHUNK);
+            if progress.is_complete() || start.elapsed() >= duration {
+                return progress;
+            }
+        }
+    }
+
+    /// Runs the job to completion and returns its result.
+    fn run(mut self) -> Self::Output where Self: Sized {
+        while !self.process_chunk(usize::MAX).is_complete() {}
+        self.finish().expect("completed job")
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Convert
+////////////////////////////////////////////////////////////////////////////////
+/// A [`Job`] converting a slice of colors into another color type.
+///
+/// [`Job`]: trait.Job.html
+#[derive(Debug)]
+pub struct Convert<'a, S, D> {
+    /// The colors to convert.
+    src: &'a [S],
+    /// The converted colors.
+    dst: Vec<D>,
+}
+
+impl<'a, S, D> Convert<'a, S, D> where S: Copy, D: From<S> {
+    /// Constructs a new `Convert` job for the given colors.
+    pub fn new(src: &'a [S]) -> Self {
+        Convert { src, dst: Vec::with_capacity(src.len()) }
+    }
+}
+
+impl<'a, S, D> Job for Convert<'a, S, D> where S: Copy, D: From<S> {
+    type Output = Vec<D>;
+
+    fn process_chunk(&mut self, budget: usize) -> Progress {
+        let start = self.dst.len();
+        let end = start.saturating_add(budget).min(self.src.len());
+        let src = &self.src[start..end];
+        let dst = &mut self.dst;
+        without_spans(|| dst.extend(src.iter().map(|&c| D::from(c))));
+        self.progress()
+    }
+
+    fn progress(&self) -> Progress {
+        Progress { done: self.dst.len(), total: self.src.len() }
+    }
+
+    fn finish(self) -> Option<Self::Output> {
+        if self.progress().is_complete() { Some(self.dst) } else { None }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Slice conversion
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given colors converted into another color type.
+///
+/// The conversion is recorded as a single tracing span for the whole slice,
+/// rather than one span for each color, so converting a large image doesn't
+/// pay for per-color instrumentation.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Oklab;
+/// # use color::Rgb;
+/// # use color::bulk::convert_slice;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels = vec![Rgb::new(255, 99, 71); 10_000];
+///
+/// let oklab: Vec<Oklab> = convert_slice(&pixels);
+///
+/// let round_trip: Vec<Rgb> = convert_slice(&oklab);
+///
+/// assert_eq!(oklab.len(), pixels.len());
+/// assert_eq!(round_trip, pixels);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn convert_slice<S, D>(src: &[S]) -> Vec<D>
+    where S: Copy + 'static, D: From<S> + 'static
+{
+    let span = span!(Level::DEBUG, "convert_slice", len = src.len());
+    let _enter = span.enter();
+
+    let mut dst = Vec::with_capacity(src.len());
+    without_spans(|| convert_with(&Backend::default(), src, |_, c| dst.push(c)));
+    dst
+}
+
+/// Converts the given colors into another color type, writing them into the
+/// given buffer without allocating.
+///
+/// As with [`convert_slice`], the conversion is recorded as a single tracing
+/// span.
+///
+/// [`convert_slice`]: fn.convert_slice.html
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::Xyz;
+/// # use color::bulk::convert_slice_into;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels = [Rgb::new(255, 255, 255), Rgb::new(0, 0, 0)];
+/// let mut xyz = [Xyz::default(); 2];
+///
+/// convert_slice_into(&pixels, &mut xyz);
+///
+/// assert_eq!(xyz[0], Xyz::from(pixels[0]));
+/// assert_eq!(xyz[1], Xyz::from(pixels[1]));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn convert_slice_into<S, D>(src: &[S], dst: &mut [D])
+    where S: Copy + 'static, D: From<S> + 'static
+{
+    assert!(src.len() == dst.len(),
+        "source and destination lengths differ: {} != {}",
+        src.len(),
+        dst.len());
+    let span = span!(Level::DEBUG, "convert_slice_into", len = src.len());
+    let _enter = span.enter();
+
+    without_spans(|| convert_with(&Backend::default(), src, |i, c| dst[i] = c))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Parallel slice conversion
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given colors converted into another color type, divided
+/// into chunks converted in parallel.
+///
+/// The result is the same as [`convert_slice`].
+///
+/// [`convert_slice`]: fn.convert_slice.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Oklab;
+/// # use color::Rgb;
+/// # use color::bulk::convert_slice;
+/// # use color::bulk::par_convert_slice;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels: Vec<Rgb> = (0..100_000u32)
+///     .map(|v| Rgb::new(v as u8, (v >> 8) as u8, (v >> 16) as u8))
+///     .collect();
+///
+/// let oklab: Vec<Oklab> = par_convert_slice(&pixels);
+///
+/// assert_eq!(oklab, convert_slice::<Rgb, Oklab>(&pixels));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_convert_slice<S, D>(src: &[S]) -> Vec<D>
+    where S: Copy + Sync + 'static, D: From<S> + Send + 'static
+{
+    par_map(src, convert_slice)
+}
+
+/// Converts the given colors into another color type, writing them into the
+/// given buffer in parallel chunks.
+///
+/// The result is the same as [`convert_slice_into`].
+///
+/// [`convert_slice_into`]: fn.convert_slice_into.html
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+#[cfg(feature = "rayon")]
+pub fn par_convert_slice_into<S, D>(src: &[S], dst: &mut [D])
+    where S: Copy + Sync + 'static, D: From<S> + Send + 'static
+{
+    assert!(src.len() == dst.len(),
+        "source and destination lengths differ: {} != {}",
+        src.len(),
+        dst.len());
+    src.par_chunks(PARALLEL_CHUNK)
+        .zip(dst.par_chunks_mut(PARALLEL_CHUNK))
+        .for_each(|(s, d)| convert_slice_into(s, d));
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the concatenated results of calling the given function on chunks
+/// of the given slice in parallel.
+#[cfg(feature = "rayon")]
+pub(in crate) fn par_map<S, D, F>(src: &[S], f: F) -> Vec<D>
+    where S: Sync, D: Send, F: Fn(&[S]) -> Vec<D> + Send + Sync
+{
+    src.par_chunks(PARALLEL_CHUNK).flat_map_iter(f).collect()
+}
+
+/// Converts the given colors into another color type, passing each result
+/// and its index to `write`.
+///
+/// The conversions implemented by [`ConversionBackend`] are done by the given
+/// backend, and all others by `From`.
+///
+/// [`ConversionBackend`]: ../backend/trait.ConversionBackend.html
+fn convert_with<S, D, B, F>(backend: &B, src: &[S], mut write: F)
+    where
+        S: Copy + 'static,
+        D: From<S> + 'static,
+        B: ConversionBackend,
+        F: FnMut(usize, D),
+{
+    if is::<S, Rgb>() && is::<D, Oklab>() {
+        convert_batches(src, write, |s, d| backend.rgb_to_oklab(s, d));
+    } else if is::<S, Oklab>() && is::<D, Rgb>() {
+        convert_batches(src, write, |s, d| backend.oklab_to_rgb(s, d));
+    } else {
+        for (i, &c) in src.iter().enumerate() {
+            write(i, D::from(c));
+        }
+    }
+}
+
+/// Converts the given colors in batches with the given function, where `S`
+/// and `D` are the same types as `T` and `U`.
+fn convert_batches<S, D, T, U, F, C>(src: &[S], mut write: F, convert: C)
+    where
+        S: Copy + 'static,
+        D: 'static,
+        T: Copy + Default + 'static,
+        U: Copy + Default + 'static,
+        F: FnMut(usize, D),
+        C: Fn(&[T], &mut [U]),
+{
+    let mut input = [T::default(); BACKEND_CHUNK];
+    let mut output = [U::default(); BACKEND_CHUNK];
+    for (n, chunk) in src.chunks(BACKEND_CHUNK).enumerate() {
+        let input = &mut input[..chunk.len()];
+        let output = &mut output[..chunk.len()];
+        for (t, s) in input.iter_mut().zip(chunk.iter()) {
+            *t = cast(*s);
+        }
+        convert(input, output);
+        for (i, u) in output.iter().enumerate() {
+            write(n * BACKEND_CHUNK + i, cast(*u));
+        }
+    }
+}
+
+/// Returns true if `T` and `U` are the same type.
+fn is<T: 'static, U: 'static>() -> bool {
+    TypeId::of::<T>() == TypeId::of::<U>()
+}
+
+/// Returns the given value as type `U`, which must be the same type as `T`.
+fn cast<T: 'static, U: 'static>(value: T) -> U {
+    let mut value = Some(value);
+    let any: &mut dyn Any = &mut value;
+    any.downcast_mut::<Option<U>>()
+        .and_then(Option::take)
+        .expect("cast between matching types")
+}
@@ -16,6 +16,8 @@ use crate::Cmyk;
 use crate::Hsl;
 use crate::Hsv;
 use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
 use crate::utility::clamped;
 use crate::Xyz;
 
@@ -24,8 +26,6 @@ use crate::Xyz;
 use serde::Deserialize;
 #[cfg(feature = "serde")]
 use serde::Serialize;
-use tracing::Level;
-use tracing::span;
 
 // Standard library imports.
 use std::fmt;
@@ -36,14 +36,26 @@ use std::fmt::Debug;
 // Color
 ////////////////////////////////////////////////////////////////////////////////
 /// An RGB encoded color with extension methods.
+///
+/// # Layout
+///
+/// `Color` is `#[repr(transparent)]` over [`Rgb`], and so has the same size,
+/// alignment, and layout. This layout is guaranteed to remain stable.
+///
+/// [`Rgb`]: struct.Rgb.html
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
 pub struct Color {
     /// The base RGB format of the color.
     rgb: Rgb
 }
 
+// Layout guarantees.
+const _: () = assert!(size_of::<Color>() == size_of::<Rgb>());
+const _: () = assert!(align_of::<Color>() == align_of::<Rgb>());
+
 impl Color {
     /// Constructs a new `Color`.
     ///
@@ -65,9 +77,8 @@ impl Color {
     /// #     example().unwrap();
     /// # }
     /// ```
-    
     pub fn new<C>(color: C) -> Self where C: Into<Rgb> {
-        let span = tracing::span!(tracing::Level::TRACE, "Color::new");
+        let span = span!(Level::TRACE, "Color::new");
         let _enter = span.enter();
         
         Color {
@@ -1610,7 +1621,7 @@ impl Color {
     ///
     /// let lerp_color = Color::xyz_linear_interpolate(color_a, color_b, 0.65);
     ///
-    /// assert_eq!(lerp_color, Rgb {r: 54, g: 182, b: 86}.into());
+    /// assert_eq!(lerp_color, Rgb {r: 79, g: 193, b: 89}.into());
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -1648,7 +1659,7 @@ impl Color {
     ///     color_a, color_b, 0.0, 0.0, 0.39);
     ///
     /// assert_eq!(cerp_color, 
-    ///     Color::new(Rgb::new(89, 217, 75)));
+    ///     Color::new(Rgb::new(105, 225, 78)));
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -1813,7 +1824,7 @@ impl Color {
     /// let color_a = Color::new(Rgb {r: 127, g: 255, b: 64});
     /// let color_b = Color::new(Rgb {r: 15, g: 144, b: 99});
     ///
-    /// assert_eq!(Color::xyz_distance(color_a, color_b), 0.5080837);
+    /// assert_eq!(Color::xyz_distance(color_a, color_b), 0.64568216);
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -1868,7 +1879,7 @@ impl From<Rgb> for Color {
         let span = span!(Level::DEBUG, "Color::from<Rgb>");
         let _enter = span.enter();
         
-        Color {rgb: rgb}
+        Color {rgb}
     }
 }
 
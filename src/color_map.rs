@@ -0,0 +1,446 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Mappings from scalar data to colors.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::gradient::Gradient;
+use crate::gradient::Interpolate;
+use crate::render::preview;
+use crate::render::AnsiMode;
+use crate::render::PREVIEW_WIDTH;
+use crate::render::write_swatch_row;
+use crate::Rgb;
+use crate::utility::clamped;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::io;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The values labelled by [`ColorMap::print_preview`].
+///
+/// [`ColorMap::print_preview`]: trait.ColorMap.html#method.print_preview
+const PREVIEW_LABELS: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+////////////////////////////////////////////////////////////////////////////////
+// ColorMap
+////////////////////////////////////////////////////////////////////////////////
+/// A mapping from a normalized scalar value to a color.
+pub trait ColorMap {
+    /// Returns the color for the value `t`, which is clamped between 0 and 1.
+    fn map(&self, t: f32) -> Rgb;
+
+    /// Returns the color for the given value after normalizing it with the
+    /// given [`Domain`].
+    ///
+    /// [`Domain`]: struct.Domain.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::color_map::ColorMap;
+    /// # use color::color_map::Domain;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(200, 0, 0));
+    /// let domain = Domain::log(1.0, 10000.0);
+    ///
+    /// assert_eq!(gradient.map_in(100.0, &domain), Rgb::new(100, 0, 0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    fn map_in(&self, value: f32, domain: &Domain) -> Rgb {
+        self.map(domain.normalize(value))
+    }
+
+    /// Writes a truecolor preview of the color map, followed by a labelled
+    /// swatch row with the hex code of the color at each quarter of the
+    /// interval.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::color_map::ColorMap;
+    /// # use color::color_map::LookupTable;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let map = LookupTable::new(vec![
+    ///     Rgb::new(0, 0, 0),
+    ///     Rgb::new(255, 255, 255),
+    /// ]);
+    ///
+    /// let mut out = Vec::new();
+    /// map.print_preview(&mut out)?;
+    /// let out = String::from_utf8(out)?;
+    ///
+    /// assert_eq!(out.lines().count(), 6);
+    /// assert!(out.contains("#000000 0.00"));
+    /// assert!(out.contains("#ffffff 1.00"));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    fn print_preview(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        writeln!(out, "{}", preview(self, PREVIEW_WIDTH, AnsiMode::TrueColor))?;
+        for &t in PREVIEW_LABELS.iter() {
+            write_swatch_row(out, self.map(t), &format!("{:.2}", t))?;
+        }
+        Ok(())
+    }
+}
+
+impl<C> ColorMap for Gradient<C> where C: Interpolate + Into<Rgb> {
+    fn map(&self, t: f32) -> Rgb {
+        self.color(t).into()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Scale
+////////////////////////////////////////////////////////////////////////////////
+/// The scale used by a [`Domain`] to normalize values.
+///
+/// [`Domain`]: struct.Domain.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Scale {
+    /// Values are normalized linearly.
+    Linear,
+    /// Values are normalized by their logarithm. Non-positive values are
+    /// treated as the domain minimum.
+    Log,
+    /// Values are normalized by a symmetric logarithm, which is linear within
+    /// `threshold` of zero and logarithmic outside of it. This allows mapping
+    /// domains which span zero or contain negative values.
+    Symlog {
+        /// The distance from zero at which the scale becomes logarithmic.
+        threshold: f32,
+    },
+}
+
+impl Scale {
+    /// Applies the scale's transform to a value.
+    fn transform(&self, value: f32) -> f32 {
+        match self {
+            Scale::Linear => value,
+            Scale::Log    => value.max(f32::MIN_POSITIVE).ln(),
+            Scale::Symlog { threshold } => {
+                value.signum() * (1.0 + value.abs() / threshold).ln()
+            },
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Domain
+////////////////////////////////////////////////////////////////////////////////
+/// The range of input values for a [`ColorMap`], used to normalize data
+/// values into the interval [0, 1].
+///
+/// [`ColorMap`]: trait.ColorMap.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "DomainRepr"))]
+pub struct Domain {
+    /// The value mapped to 0.
+    min: f32,
+    /// The value mapped to 1.
+    max: f32,
+    /// The scale used to normalize values.
+    scale: Scale,
+}
+
+impl Domain {
+    /// Constructs a new linear `Domain`.
+    pub fn linear(min: f32, max: f32) -> Self {
+        Domain { min, max, scale: Scale::Linear }
+    }
+
+    /// Constructs a new logarithmic `Domain`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` or `max` is not positive.
+    pub fn log(min: f32, max: f32) -> Self {
+        assert!(min > 0.0 && max > 0.0);
+        Domain { min, max, scale: Scale::Log }
+    }
+
+    /// Constructs a new symmetric logarithmic `Domain`, which is linear within
+    /// `threshold` of zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not positive.
+    pub fn symlog(min: f32, max: f32, threshold: f32) -> Self {
+        assert!(threshold > 0.0);
+        Domain { min, max, scale: Scale::Symlog { threshold } }
+    }
+
+    /// Returns the value mapped to 0.
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    /// Returns the value mapped to 1.
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// Returns the scale used to normalize values.
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// Returns the given value normalized into the interval [0, 1].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::color_map::Domain;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let domain = Domain::linear(-10.0, 30.0);
+    /// assert_eq!(domain.normalize(0.0), 0.25);
+    ///
+    /// let domain = Domain::symlog(-100.0, 100.0, 1.0);
+    /// assert_eq!(domain.normalize(0.0), 0.5);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn normalize(&self, value: f32) -> f32 {
+        let min = self.scale.transform(self.min);
+        let max = self.scale.transform(self.max);
+        if min == max { return 0.0; }
+
+        clamped((self.scale.transform(value) - min) / (max - min), 0.0, 1.0)
+    }
+}
+
+/// The unvalidated serialized form of a [`Domain`].
+///
+/// [`Domain`]: struct.Domain.html
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct DomainRepr {
+    min: f32,
+    max: f32,
+    scale: Scale,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<DomainRepr> for Domain {
+    type Error = String;
+
+    fn try_from(repr: DomainRepr) -> Result<Self, Self::Error> {
+        let DomainRepr { min, max, scale } = repr;
+        match scale {
+            Scale::Log if min > 0.0 && max > 0.0 => (),
+            Scale::Log => return Err(format!(
+                "log domain bounds {} and {} must be positive", min, max)),
+            Scale::Symlog { threshold } if threshold > 0.0 => (),
+            Scale::Symlog { threshold } => return Err(format!(
+                "symlog threshold {} must be positive", threshold)),
+            Scale::Linear => (),
+        }
+        Ok(Domain { min, max, scale })
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// LookupTable
+////////////////////////////////////////////////////////////////////////////////
+/// A [`ColorMap`] which selects the nearest entry from a table of evenly
+/// spaced colors.
+///
+/// [`ColorMap`]: trait.ColorMap.html
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ColorsRepr"))]
+pub struct LookupTable {
+    /// The table entries.
+    colors: Vec<Rgb>,
+}
+
+impl LookupTable {
+    /// Constructs a new `LookupTable` from the given colors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `colors` is empty.
+    pub fn new<I>(colors: I) -> Self where I: IntoIterator<Item=Rgb> {
+        let colors: Vec<Rgb> = colors.into_iter().collect();
+        assert!(!colors.is_empty());
+        LookupTable { colors }
+    }
+
+    /// Returns the table entries.
+    pub fn colors(&self) -> &[Rgb] {
+        &self.colors[..]
+    }
+}
+
+/// The unvalidated serialized form of a [`LookupTable`] or [`Discrete`]
+/// color map.
+///
+/// [`LookupTable`]: struct.LookupTable.html
+/// [`Discrete`]: struct.Discrete.html
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ColorsRepr {
+    colors: Vec<Rgb>,
+}
+
+#[cfg(feature = "serde")]
+impl ColorsRepr {
+    /// Returns the colors, or an error if there are none.
+    fn validate(self) -> Result<Vec<Rgb>, String> {
+        if self.colors.is_empty() {
+            Err("color map has no colors".to_owned())
+        } else {
+            Ok(self.colors)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ColorsRepr> for LookupTable {
+    type Error = String;
+
+    fn try_from(repr: ColorsRepr) -> Result<Self, Self::Error> {
+        Ok(LookupTable { colors: repr.validate()? })
+    }
+}
+
+impl ColorMap for LookupTable {
+    fn map(&self, t: f32) -> Rgb {
+        let last = (self.colors.len() - 1) as f32;
+        let index = (clamped(t, 0.0, 1.0) * last).round() as usize;
+        self.colors[index]
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Discrete
+////////////////////////////////////////////////////////////////////////////////
+/// A [`ColorMap`] which divides the interval [0, 1] into equally sized
+/// classes, each with its own color.
+///
+/// [`ColorMap`]: trait.ColorMap.html
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ColorsRepr"))]
+pub struct Discrete {
+    /// The class colors.
+    colors: Vec<Rgb>,
+}
+
+impl Discrete {
+    /// Constructs a new `Discrete` color map from the given class colors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `colors` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::color_map::ColorMap;
+    /// # use color::color_map::Discrete;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let map = Discrete::new(vec![
+    ///     Rgb::new(255, 0, 0),
+    ///     Rgb::new(0, 255, 0),
+    /// ]);
+    ///
+    /// assert_eq!(map.map(0.49), Rgb::new(255, 0, 0));
+    /// assert_eq!(map.map(0.51), Rgb::new(0, 255, 0));
+    /// assert_eq!(map.class(3), Rgb::new(0, 255, 0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn new<I>(colors: I) -> Self where I: IntoIterator<Item=Rgb> {
+        let colors: Vec<Rgb> = colors.into_iter().collect();
+        assert!(!colors.is_empty());
+        Discrete { colors }
+    }
+
+    /// Returns the class colors.
+    pub fn colors(&self) -> &[Rgb] {
+        &self.colors[..]
+    }
+
+    /// Returns the color for the given class index, cycling through the
+    /// class colors if the index is out of range.
+    pub fn class(&self, index: usize) -> Rgb {
+        self.colors[index % self.colors.len()]
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ColorsRepr> for Discrete {
+    type Error = String;
+
+    fn try_from(repr: ColorsRepr) -> Result<Self, Self::Error> {
+        Ok(Discrete { colors: repr.validate()? })
+    }
+}
+
+impl ColorMap for Discrete {
+    fn map(&self, t: f32) -> Rgb {
+        let count = self.colors.len();
+        let index = (clamped(t, 0.0, 1.0) * count as f32) as usize;
+        self.colors[index.min(count - 1)]
+    }
+}
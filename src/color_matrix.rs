@@ -0,0 +1,307 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Affine color transforms.
+//!
+//! A [`ColorMatrix`] is a 4×5 matrix transforming the channels of an [`Rgba`]
+//! color, the model used by the SVG `feColorMatrix` filter and Android's
+//! `ColorMatrix`. Common filters are provided as constructors, and matrices
+//! are combined by multiplication so that a chain of filters is applied in a
+//! single pass.
+//!
+//! [`ColorMatrix`]: struct.ColorMatrix.html
+//! [`Rgba`]: ../struct.Rgba.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::rgb::SEPIA_WEIGHTS;
+use crate::Rgb;
+use crate::Rgba;
+use crate::utility::clamped;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::ops::Mul;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The channel weights of the luminance preserved by the saturation and hue
+/// rotation matrices, as given by the SVG `feColorMatrix` filter.
+const LUMINANCE_WEIGHTS: [f32; 3] = [0.213, 0.715, 0.072];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ColorMatrix
+////////////////////////////////////////////////////////////////////////////////
+/// A 4×5 matrix applying an affine transform to the `[R, G, B, A]` channel
+/// ratios of a color.
+///
+/// Each row gives one output channel as the weights of the red, green, blue,
+/// and alpha input channels, followed by a constant offset. The channels are
+/// encoded, straight alpha ratios between 0 and 1, and the results are
+/// clamped to that range.
+///
+/// The product `a * b` is the matrix which applies `b` and then `a`.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::color_matrix::ColorMatrix;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let filter = ColorMatrix::hue_rotate(180.0) * ColorMatrix::saturation(0.5);
+///
+/// let color = Rgb::new(200, 80, 40);
+///
+/// assert_eq!(filter.apply_rgb(color),
+///     ColorMatrix::hue_rotate(180.0).apply_rgb(
+///         ColorMatrix::saturation(0.5).apply_rgb(color)));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColorMatrix {
+    /// The rows of the matrix, one per output channel.
+    rows: [[f32; 5]; 4],
+}
+
+impl ColorMatrix {
+    /// Constructs a new `ColorMatrix` from the given rows.
+    pub fn new(rows: [[f32; 5]; 4]) -> Self {
+        ColorMatrix { rows }
+    }
+
+    /// Constructs the identity `ColorMatrix`, which leaves colors unchanged.
+    pub fn identity() -> Self {
+        ColorMatrix::new([
+            [1.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+
+    /// Constructs a `ColorMatrix` scaling the saturation of colors by the
+    /// given amount. An amount of 0 gives grayscale, 1 leaves colors
+    /// unchanged, and greater amounts oversaturate. This matches the SVG
+    /// `saturate` color matrix and the CSS `saturate()` filter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::color_matrix::ColorMatrix;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gray = ColorMatrix::saturation(0.0).apply_rgb(Rgb::new(255, 0, 0));
+    ///
+    /// assert_eq!(gray, Rgb::new(54, 54, 54));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn saturation(amount: f32) -> Self {
+        let [wr, wg, wb] = LUMINANCE_WEIGHTS;
+        let s = amount;
+        ColorMatrix::new([
+            [wr + (1.0 - wr) * s, wg - wg * s, wb - wb * s, 0.0, 0.0],
+            [wr - wr * s, wg + (1.0 - wg) * s, wb - wb * s, 0.0, 0.0],
+            [wr - wr * s, wg - wg * s, wb + (1.0 - wb) * s, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+
+    /// Constructs a `ColorMatrix` rotating the hue of colors by the given
+    /// number of degrees while approximately preserving their luminance.
+    /// This matches the SVG `hueRotate` color matrix and the CSS
+    /// `hue-rotate()` filter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::color_matrix::ColorMatrix;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gray = Rgb::new(100, 100, 100);
+    ///
+    /// assert_eq!(ColorMatrix::hue_rotate(90.0).apply_rgb(gray), gray);
+    /// assert_eq!(ColorMatrix::hue_rotate(360.0).apply_rgb(
+    ///     Rgb::new(200, 80, 40)), Rgb::new(200, 80, 40));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn hue_rotate(degrees: f32) -> Self {
+        let [wr, wg, wb] = LUMINANCE_WEIGHTS;
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        ColorMatrix::new([
+            [
+                wr + cos * (1.0 - wr) - sin * wr,
+                wg - cos * wg - sin * wg,
+                wb - cos * wb + sin * (1.0 - wb),
+                0.0,
+                0.0,
+            ],
+            [
+                wr - cos * wr + sin * 0.143,
+                wg + cos * (1.0 - wg) + sin * 0.140,
+                wb - cos * wb - sin * 0.283,
+                0.0,
+                0.0,
+            ],
+            [
+                wr - cos * wr - sin * (1.0 - wr),
+                wg - cos * wg + sin * wg,
+                wb + cos * (1.0 - wb) + sin * wb,
+                0.0,
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+
+    /// Constructs a `ColorMatrix` applying a sepia tone by the given amount,
+    /// between 0 for the original color and 1 for full sepia. This matches
+    /// [`Rgb::sepia`] and the CSS `sepia()` filter.
+    ///
+    /// [`Rgb::sepia`]: ../struct.Rgb.html#method.sepia
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::color_matrix::ColorMatrix;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(128, 128, 128);
+    ///
+    /// assert_eq!(ColorMatrix::sepia(1.0).apply_rgb(color), color.sepia(1.0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn sepia(amount: f32) -> Self {
+        let a = clamped(amount, 0.0, 1.0);
+        let mut matrix = ColorMatrix::identity();
+        for (i, weights) in SEPIA_WEIGHTS.iter().enumerate() {
+            for (j, &w) in weights.iter().enumerate() {
+                let identity = if i == j { 1.0 } else { 0.0 };
+                matrix.rows[i][j] = identity + (w - identity) * a;
+            }
+        }
+        matrix
+    }
+
+    /// Returns the rows of the matrix, one per output channel.
+    pub fn rows(&self) -> [[f32; 5]; 4] {
+        self.rows
+    }
+
+    /// Returns the given color transformed by the matrix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # use color::color_matrix::ColorMatrix;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// // Halve the alpha and swap the red and blue channels.
+    /// let matrix = ColorMatrix::new([
+    ///     [0.0, 0.0, 1.0, 0.0, 0.0],
+    ///     [0.0, 1.0, 0.0, 0.0, 0.0],
+    ///     [1.0, 0.0, 0.0, 0.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 0.5, 0.0],
+    /// ]);
+    ///
+    /// assert_eq!(matrix.apply(Rgba::new(10, 20, 30, 200)),
+    ///     Rgba::new(30, 20, 10, 100));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn apply(&self, color: Rgba) -> Rgba {
+        let input = color.ratios();
+        let [r, g, b, a] = self.rows.map(|row| {
+            let v = (0..4).map(|j| row[j] * input[j]).sum::<f32>() + row[4];
+            (v.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8
+        });
+        Rgba::new(r, g, b, a)
+    }
+
+    /// Returns the given opaque color transformed by the matrix, discarding
+    /// the resulting alpha.
+    pub fn apply_rgb(&self, color: Rgb) -> Rgb {
+        self.apply(Rgba::from(color)).rgb()
+    }
+}
+
+impl Default for ColorMatrix {
+    fn default() -> Self {
+        ColorMatrix::identity()
+    }
+}
+
+impl Mul for ColorMatrix {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // The matrices are multiplied as 5×5 matrices with an implicit last
+        // row of `[0, 0, 0, 0, 1]`.
+        let rhs_at = |k: usize, j: usize| match k {
+            4 if j == 4 => 1.0,
+            4           => 0.0,
+            _           => rhs.rows[k][j],
+        };
+        let mut rows = [[0.0; 5]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = (0..5).map(|k| self.rows[i][k] * rhs_at(k, j)).sum();
+            }
+        }
+        ColorMatrix { rows }
+    }
+}
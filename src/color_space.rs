@@ -13,5 +13,16 @@
 pub(in crate) mod cmyk;
 pub(in crate) mod hsl;
 pub(in crate) mod hsv;
+pub(in crate) mod lab;
+pub(in crate) mod lab64;
+pub(in crate) mod lch;
+pub(in crate) mod luma;
+pub(in crate) mod oklab;
+pub(in crate) mod oklch;
 pub(in crate) mod rgb;
+pub(in crate) mod rgb48;
+pub(in crate) mod rgba;
+pub(in crate) mod rgba64;
 pub(in crate) mod xyz;
+pub(in crate) mod xyz64;
+pub(in crate) mod ycocg;
@@ -13,6 +13,8 @@
 // Local imports.
 use crate::Hsl;
 use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
 use crate::utility::cerp_u8;
 use crate::utility::clamped;
 use crate::utility::distance;
@@ -20,25 +22,34 @@ use crate::utility::lerp_u8;
 use crate::utility::nearly_equal;
 
 // External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
 #[cfg(feature = "serde")]
 use serde::Deserialize;
 #[cfg(feature = "serde")]
 use serde::Serialize;
-use tracing::Level;
-use tracing::span;
 
 // Standard library imports.
 use std::convert::From;
 use std::fmt;
-use std::u8;
 
 
 ////////////////////////////////////////////////////////////////////////////////
 // Cmyk
 ////////////////////////////////////////////////////////////////////////////////
 /// The encoded CMYK color.
+///
+/// # Layout
+///
+/// `Cmyk` is `#[repr(C)]` with four `u8` components in `[c, m, y, k]` order. It
+/// has a size of 4 bytes and an alignment of 1 byte. This layout is guaranteed
+/// to remain stable.
 #[derive(Debug, PartialOrd, PartialEq, Eq, Hash, Ord, Clone, Copy, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
 pub struct Cmyk {
     /// The cyan component.
     pub c: u8,
@@ -50,6 +61,10 @@ pub struct Cmyk {
     pub k: u8,
 }
 
+// Layout guarantees.
+const _: () = assert!(size_of::<Cmyk>() == 4);
+const _: () = assert!(align_of::<Cmyk>() == 1);
+
 
 impl Cmyk {
     /// Constructs a new `Cmyk` color.
@@ -70,7 +85,7 @@ impl Cmyk {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn new(
+    pub const fn new(
         cyan: u8, 
         magenta: u8, 
         yellow: u8,
@@ -322,10 +337,85 @@ impl Cmyk {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn octets(&self) -> [u8; 4] {
+    pub const fn octets(&self) -> [u8; 4] {
         [self.c, self.m, self.y, self.k]
     }
 
+    /// Returns the color with each of its ink channels inverted, including
+    /// the key channel. Inverting twice returns the original color.
+    ///
+    /// This inverts the ink amounts rather than the displayed color. To
+    /// invert the displayed color, convert to [`Rgb`] and use
+    /// [`Rgb::invert`].
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    /// [`Rgb::invert`]: struct.Rgb.html#method.invert
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Cmyk;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Cmyk { c: 255, m: 0, y: 100, k: 10 };
+    ///
+    /// assert_eq!(color.invert(), Cmyk { c: 0, m: 255, y: 155, k: 245 });
+    /// assert_eq!(color.invert().invert(), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn invert(&self) -> Self {
+        Cmyk {
+            c: u8::MAX - self.c,
+            m: u8::MAX - self.m,
+            y: u8::MAX - self.y,
+            k: u8::MAX - self.k,
+        }
+    }
+
+    /// Returns the memory representation of the color as a byte array,
+    /// matching the `#[repr(C)]` layout of `Cmyk`. This is equivalent to
+    /// [`octets`], and is provided for uniformity with the floating point
+    /// color types.
+    ///
+    /// [`octets`]: struct.Cmyk.html#method.octets
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Cmyk;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Cmyk::new(127, 255, 64, 100);
+    ///
+    /// let bytes = color.to_ne_bytes();
+    ///
+    /// assert_eq!(Cmyk::from_ne_bytes(bytes), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn to_ne_bytes(&self) -> [u8; 4] {
+        self.octets()
+    }
+
+    /// Constructs a new `Cmyk` color from its memory representation as a byte
+    /// array.
+    pub const fn from_ne_bytes(bytes: [u8; 4]) -> Self {
+        Cmyk::new(bytes[0], bytes[1], bytes[2], bytes[3])
+    }
+
     /// Returns an array containing the `[C, M, Y, K]` component ratios.
     ///
     /// # Example
@@ -378,13 +468,42 @@ impl Cmyk {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn hex(&self) -> u32 {
+    pub const fn hex(&self) -> u32 {
         (self.c as u32) << 24 | 
         (self.m as u32) << 16 | 
         (self.y as u32) << 8 | 
         (self.k as u32)
     }
 
+    /// Constructs a new `Cmyk` color from its hex code. This is the `const`
+    /// form of `Cmyk::from(u32)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Cmyk;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// const INK: Cmyk = Cmyk::from_hex(0x7FFF4064);
+    ///
+    /// assert_eq!(INK, Cmyk {c: 127, m: 255, y: 64, k: 100});
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn from_hex(hex: u32) -> Self {
+        Cmyk::new(
+            (hex >> 24) as u8,
+            (hex >> 16) as u8,
+            (hex >> 8) as u8,
+            hex as u8)
+    }
+
     /// Performs a component-wise linear interpolation between given colors,
     /// returning the color located at the ratio given by `amount`, which is
     /// clamped between 1 and 0.
@@ -542,12 +661,7 @@ impl From<u32> for Cmyk {
         let span = span!(Level::DEBUG, "Cmyk::from<u32>");
         let _enter = span.enter();
 
-        Cmyk {
-            c: ((hex & 0xFF000000) >> 24) as u8,
-            m: ((hex & 0x00FF0000) >> 16) as u8,
-            y: ((hex & 0x0000FF00) >> 8) as u8,
-            k: ((hex & 0x000000FF)) as u8,
-        }
+        Cmyk::from_hex(hex)
     }
 }
 
@@ -12,9 +12,12 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Local imports.
+use crate::angle;
 use crate::Cmyk;
 use crate::Hsv;
 use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
 use crate::utility::cerp_f32;
 use crate::utility::clamped;
 use crate::utility::lerp_f32;
@@ -26,8 +29,6 @@ use crate::Xyz;
 use serde::Deserialize;
 #[cfg(feature = "serde")]
 use serde::Serialize;
-use tracing::Level;
-use tracing::span;
 
 // Standard library imports.
 use std::convert::From;
@@ -39,8 +40,15 @@ use std::f32;
 // Hsl
 ////////////////////////////////////////////////////////////////////////////////
 /// The encoded HSL color.
+///
+/// # Layout
+///
+/// `Hsl` is `#[repr(C)]` with three `f32` components in `[h, s, l]` order. It
+/// has a size of 12 bytes and an alignment of 4 bytes. This layout is
+/// guaranteed to remain stable.
 #[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
 pub struct Hsl {
     /// The hue component.
     pub(in crate) h: f32,
@@ -50,6 +58,10 @@ pub struct Hsl {
     pub(in crate) l: f32,
 }
 
+// Layout guarantees.
+const _: () = assert!(size_of::<Hsl>() == 12);
+const _: () = assert!(align_of::<Hsl>() == 4);
+
 
 impl Hsl {
     /// Constructs a new `Hsl` color.
@@ -173,7 +185,7 @@ impl Hsl {
     /// ```
     pub fn set_hue(&mut self, hue: f32) {
         assert!(hue.is_finite());
-        self.h = hue % 360.0;
+        self.h = angle::wrap(hue);
     }
 
     /// Sets the saturation component of the color as a ratio.
@@ -226,6 +238,207 @@ impl Hsl {
         self.l = clamped(lightness, 0.0, 1.0);
     }
 
+    /// Returns the color with its hue rotated by the given number of degrees,
+    /// wrapping into the range [0, 360). Negative rotations are clockwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degrees` is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(300.0, 0.5, 0.5);
+    ///
+    /// assert_eq!(color.rotate_hue(90.0).hue(), 30.0);
+    /// assert_eq!(color.rotate_hue(-330.0).hue(), 330.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn rotate_hue(&self, degrees: f32) -> Self {
+        let mut color = *self;
+        color.set_hue(self.h + degrees);
+        color
+    }
+
+    /// Returns the color with its lightness increased by `amount`, clamped
+    /// between 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(134.0, 0.23, 0.5);
+    ///
+    /// assert_eq!(color.lighten(0.25).lightness(), 0.75);
+    /// assert_eq!(color.lighten(0.75).lightness(), 1.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn lighten(&self, amount: f32) -> Self {
+        let mut color = *self;
+        color.l = clamped(self.l + amount, 0.0, 1.0);
+        color
+    }
+
+    /// Returns the color with its lightness decreased by `amount`, clamped
+    /// between 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(134.0, 0.23, 0.5);
+    ///
+    /// assert_eq!(color.darken(0.25).lightness(), 0.25);
+    /// assert_eq!(color.darken(0.75).lightness(), 0.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn darken(&self, amount: f32) -> Self {
+        let mut color = *self;
+        color.l = clamped(self.l - amount, 0.0, 1.0);
+        color
+    }
+
+    /// Returns the color with its lightness moved toward 1 by the given
+    /// ratio of the remaining distance. A ratio of 1 gives the maximum
+    /// lightness.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(134.0, 0.23, 0.5);
+    ///
+    /// assert_eq!(color.lighten_relative(0.5).lightness(), 0.75);
+    /// assert_eq!(color.lighten_relative(1.0).lightness(), 1.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn lighten_relative(&self, ratio: f32) -> Self {
+        let mut color = *self;
+        color.l = clamped(self.l + (1.0 - self.l) * ratio, 0.0, 1.0);
+        color
+    }
+
+    /// Returns the color with its lightness reduced by the given ratio. A
+    /// ratio of 1 gives a lightness of 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(134.0, 0.23, 0.5);
+    ///
+    /// assert_eq!(color.darken_relative(0.5).lightness(), 0.25);
+    /// assert_eq!(color.darken_relative(1.0).lightness(), 0.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn darken_relative(&self, ratio: f32) -> Self {
+        let mut color = *self;
+        color.l = clamped(self.l * (1.0 - ratio), 0.0, 1.0);
+        color
+    }
+
+    /// Returns the color with its saturation increased by `amount`, clamped
+    /// between 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(134.0, 0.5, 0.5);
+    ///
+    /// assert_eq!(color.saturate(0.25).saturation(), 0.75);
+    /// assert_eq!(color.saturate(0.75).saturation(), 1.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn saturate(&self, amount: f32) -> Self {
+        let mut color = *self;
+        color.s = clamped(self.s + amount, 0.0, 1.0);
+        color
+    }
+
+    /// Returns the color with its saturation decreased by `amount`, clamped
+    /// between 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(134.0, 0.5, 0.5);
+    ///
+    /// assert_eq!(color.desaturate(0.25).saturation(), 0.25);
+    /// assert_eq!(color.desaturate(0.75).saturation(), 0.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn desaturate(&self, amount: f32) -> Self {
+        let mut color = *self;
+        color.s = clamped(self.s - amount, 0.0, 1.0);
+        color
+    }
+
     /// Returns an array containing the `[H, S, L]` components.
     ///
     /// # Example
@@ -248,10 +461,56 @@ impl Hsl {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn components(&self) -> [f32; 3] {
+    pub const fn components(&self) -> [f32; 3] {
         [self.h, self.s, self.l]
     }
 
+    /// Returns the memory representation of the color as a byte array in
+    /// native byte order, matching the `#[repr(C)]` layout of `Hsl`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsl;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Hsl::new(134.0, 0.23, 0.55);
+    ///
+    /// let bytes = color.to_ne_bytes();
+    ///
+    /// assert_eq!(Hsl::from_ne_bytes(bytes), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_ne_bytes(&self) -> [u8; 12] {
+        let [a, b, c] = self.components();
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&a.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&b.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&c.to_ne_bytes());
+        bytes
+    }
+
+    /// Constructs a new `Hsl` color from its memory representation as a byte
+    /// array in native byte order. The components are validated as in
+    /// [`Hsl::new`].
+    ///
+    /// [`Hsl::new`]: struct.Hsl.html#method.new
+    pub fn from_ne_bytes(bytes: [u8; 12]) -> Self {
+        let component = |i: usize| {
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[i..i + 4]);
+            f32::from_ne_bytes(word)
+        };
+        Hsl::new(component(0), component(4), component(8))
+    }
+
     /// Performs a component-wise linear interpolation between given colors,
     /// returning the color located at the ratio given by `amount`, which is
     /// clamped between 1 and 0.
@@ -444,7 +703,7 @@ impl From<Rgb> for Hsl {
         
         if nearly_equal(delta, 0.0) {
             // No need to compute saturation and hue for grayscale colors.
-            Hsl {h: 0.0, s: 0.0, l: l}
+            Hsl {h: 0.0, s: 0.0, l}
 
         } else {
 
@@ -456,7 +715,7 @@ impl From<Rgb> for Hsl {
             };
 
             // Compute hue.
-            let mut h = 60.0 * match max_index {
+            let h = 60.0 * match max_index {
                 0 => (ratios[1] - ratios[2]) / delta,
                 1 => (ratios[2] - ratios[0]) / delta + 2.0,
                 2 => (ratios[0] - ratios[1]) / delta + 4.0,
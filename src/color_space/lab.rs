@@ -0,0 +1,678 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 96-bit CIE L*a*b* color space.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::angle;
+use crate::Color;
+use crate::gamut::checked_rgb;
+use crate::gamut::in_gamut;
+use crate::gamut::OutOfGamut;
+use crate::illuminant::Illuminant;
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::utility::lerp_f32;
+use crate::utility::matrix_transform;
+use crate::Xyz;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The CIE constant ε, where the L* curve switches from linear to cubic.
+const EPSILON: f32 = 216.0 / 24389.0;
+
+/// The CIE constant κ, the slope of the linear part of the L* curve.
+const KAPPA: f32 = 24389.0 / 27.0;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lab
+////////////////////////////////////////////////////////////////////////////////
+/// A CIE L*a*b* color, relative to the D65 white point.
+///
+/// Conversions to and from [`Xyz`] and [`Rgb`] are relative to the D65 white
+/// point, and [`from_xyz`] converts relative to any other white point.
+///
+/// [`Xyz`]: struct.Xyz.html
+/// [`Rgb`]: struct.Rgb.html
+/// [`from_xyz`]: #method.from_xyz
+///
+/// The `l` component is the lightness, between 0 and 100. The `a` and `b`
+/// components are the green-red and blue-yellow opponent axes, which are
+/// roughly within ±128 for sRGB colors.
+///
+/// # Layout
+///
+/// `Lab` is `#[repr(C)]`, with the `l`, `a`, and `b` components stored as
+/// consecutive `f32` values.
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Lab {
+    /// The lightness component.
+    pub l: f32,
+    /// The green-red component.
+    pub a: f32,
+    /// The blue-yellow component.
+    pub b: f32,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Lab>() == 12);
+const _: () = assert!(align_of::<Lab>() == 4);
+
+
+impl Lab {
+    /// Constructs a new `Lab` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab::new(53.24, 80.09, 67.20);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Lab { l, a, b }
+    }
+
+    /// Returns an array containing the `[L, a, b]` components.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab::new(53.24, 80.09, 67.20);
+    ///
+    /// assert_eq!(color.components(), [53.24, 80.09, 67.20]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn components(&self) -> [f32; 3] {
+        [self.l, self.a, self.b]
+    }
+
+    /// Returns the memory representation of the color as a byte array in
+    /// native byte order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab::new(53.24, 80.09, 67.20);
+    ///
+    /// assert_eq!(Lab::from_ne_bytes(color.to_ne_bytes()), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_ne_bytes(&self) -> [u8; 12] {
+        let [a, b, c] = self.components();
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&a.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&b.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&c.to_ne_bytes());
+        bytes
+    }
+
+    /// Constructs a new `Lab` color from its memory representation as a byte
+    /// array in native byte order.
+    pub fn from_ne_bytes(bytes: [u8; 12]) -> Self {
+        let component = |i: usize| {
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[i..i + 4]);
+            f32::from_ne_bytes(word)
+        };
+        Lab::new(component(0), component(4), component(8))
+    }
+
+    /// Returns the chroma of the color, i.e., its distance from the neutral
+    /// axis.
+    pub fn chroma(&self) -> f32 {
+        self.a.hypot(self.b)
+    }
+
+    /// Performs a component-wise linear interpolation between given colors,
+    /// returning the color located at the ratio given by `amount`, which is
+    /// clamped between 1 and 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Lab::new(20.0, -40.0, 10.0);
+    /// let color_b = Lab::new(60.0, 40.0, 30.0);
+    ///
+    /// let lerp_color = Lab::linear_interpolate(color_a, color_b, 0.5);
+    ///
+    /// assert_eq!(lerp_color, Lab::new(40.0, 0.0, 20.0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn linear_interpolate<C, D>(start: C, end: D, amount: f32) -> Self
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+        Lab {
+            l: lerp_f32(s.l, e.l, amount),
+            a: lerp_f32(s.a, e.a, amount),
+            b: lerp_f32(s.b, e.b, amount),
+        }
+    }
+
+    /// Returns the CIE76 color difference between the given colors, which is
+    /// their euclidean distance in L*a*b* space.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Lab::new(50.0, 0.0, 0.0);
+    /// let color_b = Lab::new(50.0, 3.0, 4.0);
+    ///
+    /// assert_eq!(Lab::distance(color_a, color_b), 5.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn distance<C, D>(start: C, end: D) -> f32
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+
+        let l = s.l - e.l;
+        let a = s.a - e.a;
+        let b = s.b - e.b;
+
+        (l*l + a*a + b*b).sqrt()
+    }
+
+    /// Returns the CIEDE2000 color difference between the given colors.
+    ///
+    /// A difference of about 1 is the smallest difference noticeable to a
+    /// typical observer, while differences above 10 are obvious at a glance.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let red = Rgb::new(255, 0, 0);
+    /// let near_red = Rgb::new(252, 2, 1);
+    /// let blue = Rgb::new(0, 0, 255);
+    ///
+    /// assert!(Lab::delta_e(red, near_red) < 1.0);
+    /// assert!(Lab::delta_e(red, blue) > 50.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn delta_e<C, D>(start: C, end: D) -> f32
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+
+        // Adjust the a* axis to compensate for the non-uniformity of
+        // low-chroma colors.
+        let c_mean = (s.chroma() + e.chroma()) / 2.0;
+        let c_mean7 = c_mean.powi(7);
+        let g = 0.5 * (1.0 - (c_mean7 / (c_mean7 + 25f32.powi(7))).sqrt());
+        let a1 = s.a * (1.0 + g);
+        let a2 = e.a * (1.0 + g);
+
+        let c1 = a1.hypot(s.b);
+        let c2 = a2.hypot(e.b);
+        let h1 = hue_angle(a1, s.b);
+        let h2 = hue_angle(a2, e.b);
+
+        // Differences in lightness, chroma, and hue.
+        let dl = e.l - s.l;
+        let dc = c2 - c1;
+        let dh = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 <= h1 {
+            h2 - h1 + 360.0
+        } else {
+            h2 - h1 - 360.0
+        };
+        let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+        // Means of lightness, chroma, and hue.
+        let l_mean = (s.l + e.l) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let t = 1.0
+            - 0.17 * (h_mean - 30.0).to_radians().cos()
+            + 0.24 * (2.0 * h_mean).to_radians().cos()
+            + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+            - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+
+        // Weighting functions.
+        let l_offset = (l_mean - 50.0) * (l_mean - 50.0);
+        let sl = 1.0 + 0.015 * l_offset / (20.0 + l_offset).sqrt();
+        let sc = 1.0 + 0.045 * c_mean;
+        let sh = 1.0 + 0.015 * c_mean * t;
+
+        // Rotation term for the blue region.
+        let theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let c_mean7 = c_mean.powi(7);
+        let rc = 2.0 * (c_mean7 / (c_mean7 + 25f32.powi(7))).sqrt();
+        let rt = -rc * (2.0 * theta).to_radians().sin();
+
+        let l = dl / sl;
+        let c = dc / sc;
+        let h = dh / sh;
+        (l*l + c*c + h*h + rt * c * h).sqrt()
+    }
+
+    /// Returns the CIE94 color difference between the given colors, using the
+    /// graphic arts weights. This is cheaper than [`delta_e`] and more
+    /// uniform than [`distance`], but is not symmetric: `start` is the
+    /// reference color.
+    ///
+    /// [`delta_e`]: #method.delta_e
+    /// [`distance`]: #method.distance
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Lab::new(50.0, 0.0, 0.0);
+    /// let color_b = Lab::new(50.0, 0.0, 10.0);
+    ///
+    /// assert_eq!(Lab::delta_e_94(color_a, color_b), 10.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn delta_e_94<C, D>(start: C, end: D) -> f32
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+
+        let c1 = s.chroma();
+        let c2 = e.chroma();
+        let dl = s.l - e.l;
+        let dc = c1 - c2;
+        let da = s.a - e.a;
+        let db = s.b - e.b;
+        let dh_squared = (da*da + db*db - dc*dc).max(0.0);
+
+        let sc = 1.0 + 0.045 * c1;
+        let sh = 1.0 + 0.015 * c1;
+        let c = dc / sc;
+        (dl*dl + c*c + dh_squared / (sh*sh)).sqrt()
+    }
+
+    /// Returns the `Lab` color of the given [`Xyz`] color relative to the
+    /// given white point.
+    ///
+    /// [`Xyz`]: struct.Xyz.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # use color::illuminant::Illuminant;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let white = Lab::new(100.0, 0.0, 0.0).to_xyz(Illuminant::D50);
+    /// assert!((white.x - 0.9642).abs() < 1e-3);
+    ///
+    /// let lab = Lab::from_xyz(white, Illuminant::D50);
+    ///
+    /// assert!((lab.l - 100.0).abs() < 1e-4);
+    /// assert!(lab.a.abs() < 1e-4 && lab.b.abs() < 1e-4);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_xyz(xyz: Xyz, white: Illuminant) -> Self {
+        let white = white.white_point();
+        let fx = lab_f(xyz.x / white.x);
+        let fy = lab_f(xyz.y / white.y);
+        let fz = lab_f(xyz.z / white.z);
+
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+
+    /// Returns the [`Xyz`] color of the color relative to the given white
+    /// point.
+    ///
+    /// [`Xyz`]: struct.Xyz.html
+    pub fn to_xyz(&self, white: Illuminant) -> Xyz {
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+
+        let white = white.white_point();
+        Xyz::new(
+            lab_f_inverse(fx) * white.x,
+            lab_f_inverse(fy) * white.y,
+            lab_f_inverse(fz) * white.z)
+    }
+
+    /// Returns true if the color lies within the gamut of the given
+    /// [`RgbSpace`].
+    ///
+    /// [`RgbSpace`]: rgb_space/trait.RgbSpace.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # use color::rgb_space::Rec2020;
+    /// # use color::rgb_space::Srgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab::new(80.0, -110.0, 80.0);
+    ///
+    /// assert!(!color.in_gamut::<Srgb>());
+    /// assert!(color.in_gamut::<Rec2020>());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn in_gamut<S>(&self) -> bool where S: RgbSpace {
+        in_gamut::<S>(Xyz::from(xyz_components(*self)))
+    }
+
+    /// Returns true if the color lies within the sRGB gamut, and so can be
+    /// converted to [`Rgb`] without clipping.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Lab::new(50.0, 20.0, -30.0).is_displayable());
+    /// assert!(!Lab::new(80.0, -110.0, 80.0).is_displayable());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_displayable(&self) -> bool {
+        self.in_gamut::<Srgb>()
+    }
+
+    /// Converts the color to [`Rgb`], returning an [`OutOfGamut`] error
+    /// holding the clamped color if it lies outside of the sRGB gamut.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    /// [`OutOfGamut`]: gamut/struct.OutOfGamut.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab::new(80.0, -110.0, 80.0);
+    ///
+    /// let clamped = Rgb::from(color);
+    /// assert_eq!(color.checked_rgb().unwrap_err().clamped(), clamped);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn checked_rgb(&self) -> Result<Rgb, OutOfGamut> {
+        checked_rgb(*self, linear_srgb(*self))
+    }
+}
+
+
+impl fmt::Display for Lab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lab conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[f32; 3]> for Lab {
+    fn from(components: [f32; 3]) -> Self {
+        let span = span!(Level::DEBUG, "Lab::from<[f32; 3]>");
+        let _enter = span.enter();
+
+        Lab {
+            l: components[0],
+            a: components[1],
+            b: components[2],
+        }
+    }
+}
+
+impl From<Rgb> for Lab {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Lab::from<Rgb>");
+        let _enter = span.enter();
+
+        let [x, y, z] = matrix_transform(
+            Srgb::XYZ_FROM_LINEAR,
+            rgb.linear_ratios());
+        Lab::from_xyz(Xyz::new(x, y, z), Srgb::WHITE_POINT)
+    }
+}
+
+impl From<Xyz> for Lab {
+    fn from(xyz: Xyz) -> Self {
+        let span = span!(Level::DEBUG, "Lab::from<Xyz>");
+        let _enter = span.enter();
+
+        Lab::from_xyz(xyz, Illuminant::D65)
+    }
+}
+
+impl From<Lab> for Xyz {
+    fn from(lab: Lab) -> Self {
+        let span = span!(Level::DEBUG, "Xyz::from<Lab>");
+        let _enter = span.enter();
+
+        lab.to_xyz(Illuminant::D65)
+    }
+}
+
+impl From<Color> for Lab {
+    fn from(color: Color) -> Self {
+        let span = span!(Level::DEBUG, "Lab::from<Color>");
+        let _enter = span.enter();
+
+        Lab::from(Rgb::from(color))
+    }
+}
+
+impl From<Lab> for Rgb {
+    fn from(lab: Lab) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Lab>");
+        let _enter = span.enter();
+
+        Rgb::from_linear_ratios(linear_srgb(lab))
+    }
+}
+
+impl From<Lab> for Color {
+    fn from(lab: Lab) -> Self {
+        let span = span!(Level::DEBUG, "Color::from<Lab>");
+        let _enter = span.enter();
+
+        Color::from(Rgb::from(lab))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the linear sRGB ratios of the given color, which are outside of
+/// [0, 1] if the color is out of the sRGB gamut.
+pub(in crate) fn linear_srgb(lab: Lab) -> [f32; 3] {
+    matrix_transform(Srgb::LINEAR_FROM_XYZ, xyz_components(lab))
+}
+
+/// Returns the D65-relative CIE XYZ components of the given color.
+pub(in crate) fn xyz_components(lab: Lab) -> [f32; 3] {
+    lab.to_xyz(Srgb::WHITE_POINT).components()
+}
+
+/// The CIE L*a*b* companding function.
+pub(in crate) fn lab_f(t: f32) -> f32 {
+    if t > EPSILON {
+        t.cbrt()
+    } else {
+        (KAPPA * t + 16.0) / 116.0
+    }
+}
+
+/// The inverse of the CIE L*a*b* companding function.
+pub(in crate) fn lab_f_inverse(f: f32) -> f32 {
+    let t = f * f * f;
+    if t > EPSILON {
+        t
+    } else {
+        (116.0 * f - 16.0) / KAPPA
+    }
+}
+
+/// Returns the hue angle of the given opponent components in degrees, between
+/// 0 and 360.
+fn hue_angle(a: f32, b: f32) -> f32 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        angle::wrap(b.atan2(a).to_degrees())
+    }
+}
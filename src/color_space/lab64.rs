@@ -0,0 +1,427 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 192-bit double precision CIE L*a*b* color space.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::xyz64::white_point;
+use crate::illuminant::Illuminant;
+use crate::Lab;
+use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::Xyz64;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The CIE constant ε, where the L* curve switches from linear to cubic.
+const EPSILON: f64 = 216.0 / 24389.0;
+
+/// The CIE constant κ, the slope of the linear part of the L* curve.
+const KAPPA: f64 = 24389.0 / 27.0;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lab64
+////////////////////////////////////////////////////////////////////////////////
+/// A CIE L*a*b* color with double precision components.
+///
+/// This is the double precision form of [`Lab`]. Conversions from
+/// [`Xyz64`] and [`Rgb`] are relative to the D65 white point, and
+/// [`from_xyz`] converts relative to any other white point.
+///
+/// [`Lab`]: struct.Lab.html
+/// [`Xyz64`]: struct.Xyz64.html
+/// [`Rgb`]: struct.Rgb.html
+/// [`from_xyz`]: #method.from_xyz
+///
+/// # Layout
+///
+/// `Lab64` is `#[repr(C)]`, with the `l`, `a`, and `b` components stored as
+/// consecutive `f64` values.
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Lab64 {
+    /// The lightness component.
+    pub l: f64,
+    /// The green-red component.
+    pub a: f64,
+    /// The blue-yellow component.
+    pub b: f64,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Lab64>() == 24);
+const _: () = assert!(align_of::<Lab64>() == 8);
+
+
+impl Lab64 {
+    /// Constructs a new `Lab64` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab64::new(53.24, 80.09, 67.20);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(l: f64, a: f64, b: f64) -> Self {
+        Lab64 { l, a, b }
+    }
+
+    /// Returns an array containing the `[L, a, b]` components.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab64::new(53.24, 80.09, 67.20);
+    ///
+    /// assert_eq!(color.components(), [53.24, 80.09, 67.20]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn components(&self) -> [f64; 3] {
+        [self.l, self.a, self.b]
+    }
+
+    /// Returns the chroma, or distance from the neutral axis.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lab64::new(50.0, 3.0, 4.0);
+    ///
+    /// assert_eq!(color.chroma(), 5.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn chroma(&self) -> f64 {
+        self.a.hypot(self.b)
+    }
+
+    /// Returns the `Lab64` color of the given [`Xyz64`] color relative to
+    /// the given white point.
+    ///
+    /// [`Xyz64`]: struct.Xyz64.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab64;
+    /// # use color::illuminant::Illuminant;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let white = Lab64::new(100.0, 0.0, 0.0).to_xyz(Illuminant::D50);
+    /// assert!((white.x - 0.9642).abs() < 1e-3);
+    ///
+    /// let lab = Lab64::from_xyz(white, Illuminant::D50);
+    ///
+    /// assert!((lab.l - 100.0).abs() < 1e-9);
+    /// assert!(lab.a.abs() < 1e-9 && lab.b.abs() < 1e-9);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_xyz(xyz: Xyz64, white: Illuminant) -> Self {
+        let [wx, wy, wz] = white_point(white);
+        let fx = lab_f(xyz.x / wx);
+        let fy = lab_f(xyz.y / wy);
+        let fz = lab_f(xyz.z / wz);
+
+        Lab64 {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+
+    /// Returns the [`Xyz64`] color of the color relative to the given white
+    /// point.
+    ///
+    /// [`Xyz64`]: struct.Xyz64.html
+    pub fn to_xyz(&self, white: Illuminant) -> Xyz64 {
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+
+        let [wx, wy, wz] = white_point(white);
+        Xyz64 {
+            x: lab_f_inverse(fx) * wx,
+            y: lab_f_inverse(fy) * wy,
+            z: lab_f_inverse(fz) * wz,
+        }
+    }
+
+    /// Returns the CIEDE2000 color difference between the given colors,
+    /// computed in double precision.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lab64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// // A reference pair from Sharma, Wu, and Dalal (2005).
+    /// let color_a = Lab64::new(50.0, 2.6772, -79.7751);
+    /// let color_b = Lab64::new(50.0, 0.0, -82.7485);
+    ///
+    /// assert!((Lab64::delta_e(color_a, color_b) - 2.0425).abs() < 1e-4);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn delta_e<C, D>(start: C, end: D) -> f64
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+
+        // Adjust the a* axis to compensate for the non-uniformity of
+        // low-chroma colors.
+        let c_mean = (s.chroma() + e.chroma()) / 2.0;
+        let c_mean7 = c_mean.powi(7);
+        let g = 0.5 * (1.0 - (c_mean7 / (c_mean7 + 25f64.powi(7))).sqrt());
+        let a1 = s.a * (1.0 + g);
+        let a2 = e.a * (1.0 + g);
+
+        let c1 = a1.hypot(s.b);
+        let c2 = a2.hypot(e.b);
+        let h1 = hue_angle(a1, s.b);
+        let h2 = hue_angle(a2, e.b);
+
+        // Differences in lightness, chroma, and hue.
+        let dl = e.l - s.l;
+        let dc = c2 - c1;
+        let dh = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 <= h1 {
+            h2 - h1 + 360.0
+        } else {
+            h2 - h1 - 360.0
+        };
+        let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+        // Means of lightness, chroma, and hue.
+        let l_mean = (s.l + e.l) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let t = 1.0
+            - 0.17 * (h_mean - 30.0).to_radians().cos()
+            + 0.24 * (2.0 * h_mean).to_radians().cos()
+            + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+            - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+
+        // Weighting functions.
+        let l_offset = (l_mean - 50.0) * (l_mean - 50.0);
+        let sl = 1.0 + 0.015 * l_offset / (20.0 + l_offset).sqrt();
+        let sc = 1.0 + 0.045 * c_mean;
+        let sh = 1.0 + 0.015 * c_mean * t;
+
+        // Rotation term for the blue region.
+        let theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let c_mean7 = c_mean.powi(7);
+        let rc = 2.0 * (c_mean7 / (c_mean7 + 25f64.powi(7))).sqrt();
+        let rt = -rc * (2.0 * theta).to_radians().sin();
+
+        let l = dl / sl;
+        let c = dc / sc;
+        let h = dh / sh;
+        (l*l + c*c + h*h + rt * c * h).sqrt()
+    }
+}
+
+
+impl fmt::Display for Lab64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lab64 conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[f64; 3]> for Lab64 {
+    fn from(components: [f64; 3]) -> Self {
+        let span = span!(Level::DEBUG, "Lab64::from<[f64; 3]>");
+        let _enter = span.enter();
+
+        Lab64 {
+            l: components[0],
+            a: components[1],
+            b: components[2],
+        }
+    }
+}
+
+impl From<Lab> for Lab64 {
+    fn from(lab: Lab) -> Self {
+        let span = span!(Level::DEBUG, "Lab64::from<Lab>");
+        let _enter = span.enter();
+
+        Lab64 {
+            l: f64::from(lab.l),
+            a: f64::from(lab.a),
+            b: f64::from(lab.b),
+        }
+    }
+}
+
+impl From<Lab64> for Lab {
+    fn from(lab: Lab64) -> Self {
+        let span = span!(Level::DEBUG, "Lab::from<Lab64>");
+        let _enter = span.enter();
+
+        Lab {
+            l: lab.l as f32,
+            a: lab.a as f32,
+            b: lab.b as f32,
+        }
+    }
+}
+
+impl From<Xyz64> for Lab64 {
+    fn from(xyz: Xyz64) -> Self {
+        let span = span!(Level::DEBUG, "Lab64::from<Xyz64>");
+        let _enter = span.enter();
+
+        Lab64::from_xyz(xyz, Illuminant::D65)
+    }
+}
+
+impl From<Lab64> for Xyz64 {
+    fn from(lab: Lab64) -> Self {
+        let span = span!(Level::DEBUG, "Xyz64::from<Lab64>");
+        let _enter = span.enter();
+
+        lab.to_xyz(Illuminant::D65)
+    }
+}
+
+impl From<Rgb> for Lab64 {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Lab64::from<Rgb>");
+        let _enter = span.enter();
+
+        Lab64::from(Xyz64::from(rgb))
+    }
+}
+
+impl From<Lab64> for Rgb {
+    fn from(lab: Lab64) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Lab64>");
+        let _enter = span.enter();
+
+        Rgb::from(Xyz64::from(lab))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// The CIE L*a*b* companding function.
+fn lab_f(t: f64) -> f64 {
+    if t > EPSILON {
+        t.cbrt()
+    } else {
+        (KAPPA * t + 16.0) / 116.0
+    }
+}
+
+/// The inverse of the CIE L*a*b* companding function.
+fn lab_f_inverse(f: f64) -> f64 {
+    let t = f * f * f;
+    if t > EPSILON {
+        t
+    } else {
+        (116.0 * f - 16.0) / KAPPA
+    }
+}
+
+/// Returns the hue angle of the given opponent components in degrees, between
+/// 0 and 360.
+fn hue_angle(a: f64, b: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        let h = b.atan2(a).to_degrees();
+        if h < 0.0 { h + 360.0 } else { h }
+    }
+}
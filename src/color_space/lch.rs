@@ -0,0 +1,426 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 96-bit CIE LCh color space.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::angle;
+use crate::Color;
+use crate::color_space::lab::linear_srgb;
+use crate::gamut::checked_rgb;
+use crate::gamut::OutOfGamut;
+use crate::Lab;
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::utility::lerp_f32;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lch
+////////////////////////////////////////////////////////////////////////////////
+/// A CIE LCh color, the cylindrical form of [`Lab`].
+///
+/// The `l` component is the same lightness as in [`Lab`], the `c`
+/// component is the chroma, or distance from the neutral axis, and the `h`
+/// component is the hue angle in degrees, within [0, 360).
+///
+/// [`Lab`]: struct.Lab.html
+///
+/// # Layout
+///
+/// `Lch` is `#[repr(C)]`, with the `l`, `c`, and `h` components stored as
+/// consecutive `f32` values.
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Lch {
+    /// The lightness component.
+    pub l: f32,
+    /// The chroma component.
+    pub c: f32,
+    /// The hue component, in degrees.
+    pub h: f32,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Lch>() == 12);
+const _: () = assert!(align_of::<Lch>() == 4);
+
+
+impl Lch {
+    /// Constructs a new `Lch` color. The hue is wrapped into the range
+    /// [0, 360).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hue is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lch::new(50.0, 40.0, -30.0);
+    ///
+    /// assert_eq!(color.h, 330.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        assert!(h.is_finite());
+        Lch { l, c, h: angle::wrap(h) }
+    }
+
+    /// Returns an array containing the `[L, C, h]` components.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lch::new(50.0, 40.0, 30.0);
+    ///
+    /// assert_eq!(color.components(), [50.0, 40.0, 30.0]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn components(&self) -> [f32; 3] {
+        [self.l, self.c, self.h]
+    }
+
+    /// Sets the hue component of the color in degrees, wrapping it into the
+    /// range [0, 360).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hue is not finite.
+    pub fn set_hue(&mut self, hue: f32) {
+        assert!(hue.is_finite());
+        self.h = angle::wrap(hue);
+    }
+
+    /// Returns the color with its hue rotated by the given number of degrees,
+    /// wrapping into the range [0, 360). Negative rotations are clockwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degrees` is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lch::new(50.0, 40.0, 300.0);
+    ///
+    /// assert_eq!(color.rotate_hue(90.0).h, 30.0);
+    /// assert_eq!(color.rotate_hue(-330.0).h, 330.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn rotate_hue(&self, degrees: f32) -> Self {
+        let mut color = *self;
+        color.set_hue(self.h + degrees);
+        color
+    }
+
+    /// Returns the memory representation of the color as a byte array in
+    /// native byte order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lch::new(50.0, 40.0, 30.0);
+    ///
+    /// assert_eq!(Lch::from_ne_bytes(color.to_ne_bytes()), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_ne_bytes(&self) -> [u8; 12] {
+        let [a, b, c] = self.components();
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&a.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&b.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&c.to_ne_bytes());
+        bytes
+    }
+
+    /// Constructs a new `Lch` color from its memory representation as a
+    /// byte array in native byte order.
+    pub fn from_ne_bytes(bytes: [u8; 12]) -> Self {
+        let component = |i: usize| {
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[i..i + 4]);
+            f32::from_ne_bytes(word)
+        };
+        Lch { l: component(0), c: component(4), h: component(8) }
+    }
+
+    /// Performs a component-wise linear interpolation between given colors,
+    /// returning the color located at the ratio given by `amount`, which is
+    /// clamped between 1 and 0.
+    ///
+    /// The hue is interpolated directly between the two angles. Use the
+    /// [`Interpolate`] implementation to follow the shorter arc, or to
+    /// interpolate in rectangular form.
+    ///
+    /// [`Interpolate`]: gradient/trait.Interpolate.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Lch::new(25.0, 20.0, 20.0);
+    /// let color_b = Lch::new(75.0, 60.0, 60.0);
+    ///
+    /// let lerp_color = Lch::linear_interpolate(color_a, color_b, 0.5);
+    ///
+    /// assert_eq!(lerp_color, Lch::new(50.0, 40.0, 40.0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn linear_interpolate<C, D>(start: C, end: D, amount: f32) -> Self
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+        Lch {
+            l: lerp_f32(s.l, e.l, amount),
+            c: lerp_f32(s.c, e.c, amount),
+            h: lerp_f32(s.h, e.h, amount),
+        }
+    }
+
+    /// Returns true if the color lies within the gamut of the given
+    /// [`RgbSpace`].
+    ///
+    /// [`RgbSpace`]: rgb_space/trait.RgbSpace.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # use color::rgb_space::Rec2020;
+    /// # use color::rgb_space::Srgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lch::new(80.0, 130.0, 135.0);
+    ///
+    /// assert!(!color.in_gamut::<Srgb>());
+    /// assert!(color.in_gamut::<Rec2020>());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn in_gamut<S>(&self) -> bool where S: RgbSpace {
+        Lab::from(*self).in_gamut::<S>()
+    }
+
+    /// Returns true if the color lies within the sRGB gamut, and so can be
+    /// converted to [`Rgb`] without clipping.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Lch::new(50.0, 30.0, 300.0).is_displayable());
+    /// assert!(!Lch::new(80.0, 130.0, 135.0).is_displayable());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_displayable(&self) -> bool {
+        self.in_gamut::<Srgb>()
+    }
+
+    /// Converts the color to [`Rgb`], returning an [`OutOfGamut`] error
+    /// holding the clamped color if it lies outside of the sRGB gamut.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    /// [`OutOfGamut`]: gamut/struct.OutOfGamut.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Lch::new(80.0, 130.0, 135.0);
+    ///
+    /// let clamped = Rgb::from(color);
+    /// assert_eq!(color.checked_rgb().unwrap_err().clamped(), clamped);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn checked_rgb(&self) -> Result<Rgb, OutOfGamut> {
+        checked_rgb(*self, linear_srgb(Lab::from(*self)))
+    }
+}
+
+
+impl fmt::Display for Lch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lch conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[f32; 3]> for Lch {
+    fn from(components: [f32; 3]) -> Self {
+        let span = span!(Level::DEBUG, "Lch::from<[f32; 3]>");
+        let _enter = span.enter();
+
+        Lch::new(components[0], components[1], components[2])
+    }
+}
+
+impl From<Lab> for Lch {
+    fn from(lab: Lab) -> Self {
+        let span = span!(Level::DEBUG, "Lch::from<Lab>");
+        let _enter = span.enter();
+
+        let c = lab.a.hypot(lab.b);
+        let h = if c == 0.0 {
+            0.0
+        } else {
+            angle::wrap(lab.b.atan2(lab.a).to_degrees())
+        };
+        Lch { l: lab.l, c, h }
+    }
+}
+
+impl From<Lch> for Lab {
+    fn from(lch: Lch) -> Self {
+        let span = span!(Level::DEBUG, "Lab::from<Lch>");
+        let _enter = span.enter();
+
+        let (sin, cos) = lch.h.to_radians().sin_cos();
+        Lab::new(lch.l, lch.c * cos, lch.c * sin)
+    }
+}
+
+impl From<Rgb> for Lch {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Lch::from<Rgb>");
+        let _enter = span.enter();
+
+        Lch::from(Lab::from(rgb))
+    }
+}
+
+impl From<Color> for Lch {
+    fn from(color: Color) -> Self {
+        let span = span!(Level::DEBUG, "Lch::from<Color>");
+        let _enter = span.enter();
+
+        Lch::from(Rgb::from(color))
+    }
+}
+
+impl From<Lch> for Rgb {
+    fn from(lch: Lch) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Lch>");
+        let _enter = span.enter();
+
+        Rgb::from(Lab::from(lch))
+    }
+}
+
+impl From<Lch> for Color {
+    fn from(lch: Lch) -> Self {
+        let span = span!(Level::DEBUG, "Color::from<Lch>");
+        let _enter = span.enter();
+
+        Color::from(Rgb::from(lch))
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines an 8-bit grayscale encoding.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Color;
+use crate::Oklab;
+use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GrayscaleMethod
+////////////////////////////////////////////////////////////////////////////////
+/// A method for converting a color to grayscale.
+///
+/// The luminosity methods weight the encoded channel values, as video luma
+/// does. They are cheap and match most image editors, but are only an
+/// approximation of perceived lightness.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GrayscaleMethod {
+    /// A weighted sum of the channels using the Rec. 709 (sRGB and HDTV)
+    /// coefficients 0.2126, 0.7152, and 0.0722.
+    #[default]
+    Rec709,
+    /// A weighted sum of the channels using the Rec. 601 (SDTV and JPEG)
+    /// coefficients 0.299, 0.587, and 0.114.
+    Rec601,
+    /// The mean of the channels.
+    Average,
+    /// The mean of the largest and smallest channels, which is the HSL
+    /// lightness.
+    Lightness,
+    /// The gray with the same [`Oklab`] lightness, as when the chroma is
+    /// reduced to zero. This is the most perceptually accurate method.
+    ///
+    /// [`Oklab`]: struct.Oklab.html
+    Desaturation,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Luma
+////////////////////////////////////////////////////////////////////////////////
+/// An 8-bit grayscale color.
+///
+/// Converting from [`Rgb`] uses the default [`GrayscaleMethod`]. Use
+/// [`Rgb::to_luma`] to choose another.
+///
+/// [`Rgb`]: struct.Rgb.html
+/// [`GrayscaleMethod`]: enum.GrayscaleMethod.html
+/// [`Rgb::to_luma`]: struct.Rgb.html#method.to_luma
+#[derive(Debug, PartialOrd, PartialEq, Eq, Hash, Ord, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(transparent)]
+pub struct Luma {
+    /// The gray level.
+    pub y: u8,
+}
+
+
+impl Luma {
+    /// Constructs a new `Luma` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Luma;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gray = Luma::new(128);
+    ///
+    /// assert_eq!(Rgb::from(gray), Rgb::new(128, 128, 128));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(y: u8) -> Self {
+        Luma { y }
+    }
+
+    /// Returns the `Luma` color for the given color and [`GrayscaleMethod`].
+    ///
+    /// [`GrayscaleMethod`]: enum.GrayscaleMethod.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::GrayscaleMethod;
+    /// # use color::Luma;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(0, 255, 0);
+    ///
+    /// assert_eq!(Luma::from_rgb(color, GrayscaleMethod::Rec709).y, 182);
+    /// assert_eq!(Luma::from_rgb(color, GrayscaleMethod::Rec601).y, 150);
+    /// assert_eq!(Luma::from_rgb(color, GrayscaleMethod::Average).y, 85);
+    /// assert_eq!(Luma::from_rgb(color, GrayscaleMethod::Lightness).y, 128);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_rgb(rgb: Rgb, method: GrayscaleMethod) -> Self {
+        let [r, g, b] = rgb.octets().map(f32::from);
+        let y = match method {
+            GrayscaleMethod::Rec709 => 0.2126 * r + 0.7152 * g + 0.0722 * b,
+            GrayscaleMethod::Rec601 => 0.299 * r + 0.587 * g + 0.114 * b,
+            GrayscaleMethod::Average => (r + g + b) / 3.0,
+            GrayscaleMethod::Lightness => {
+                (r.max(g).max(b) + r.min(g).min(b)) / 2.0
+            },
+            GrayscaleMethod::Desaturation => {
+                let l = Oklab::from(rgb).l;
+                return Luma::new(Rgb::from(Oklab::new(l, 0.0, 0.0)).g);
+            },
+        };
+        Luma::new(y.round().clamp(0.0, 255.0) as u8)
+    }
+}
+
+
+impl fmt::Display for Luma {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Luma conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<u8> for Luma {
+    fn from(y: u8) -> Self {
+        let span = span!(Level::DEBUG, "Luma::from<u8>");
+        let _enter = span.enter();
+
+        Luma::new(y)
+    }
+}
+
+impl From<Luma> for u8 {
+    fn from(luma: Luma) -> Self {
+        let span = span!(Level::DEBUG, "u8::from<Luma>");
+        let _enter = span.enter();
+
+        luma.y
+    }
+}
+
+impl From<Rgb> for Luma {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Luma::from<Rgb>");
+        let _enter = span.enter();
+
+        Luma::from_rgb(rgb, GrayscaleMethod::default())
+    }
+}
+
+impl From<Color> for Luma {
+    fn from(color: Color) -> Self {
+        let span = span!(Level::DEBUG, "Luma::from<Color>");
+        let _enter = span.enter();
+
+        Luma::from(Rgb::from(color))
+    }
+}
+
+impl From<Luma> for Rgb {
+    fn from(luma: Luma) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Luma>");
+        let _enter = span.enter();
+
+        Rgb::new(luma.y, luma.y, luma.y)
+    }
+}
+
+impl From<Luma> for Color {
+    fn from(luma: Luma) -> Self {
+        let span = span!(Level::DEBUG, "Color::from<Luma>");
+        let _enter = span.enter();
+
+        Color::from(Rgb::from(luma))
+    }
+}
@@ -0,0 +1,449 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 96-bit Oklab color space.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Color;
+use crate::gamut::checked_rgb;
+use crate::gamut::in_gamut;
+use crate::gamut::OutOfGamut;
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::utility::lerp_f32;
+use crate::utility::matrix_transform;
+use crate::Xyz;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Oklab
+////////////////////////////////////////////////////////////////////////////////
+/// An [Oklab] color, a perceptually uniform color space designed for image
+/// processing.
+///
+/// The `l` component is the perceived lightness, between 0 and 1. The `a` and
+/// `b` components are the green-red and blue-yellow opponent axes, which are
+/// roughly within ±0.4 for sRGB colors.
+///
+/// [Oklab]: https://bottosson.github.io/posts/oklab/
+///
+/// # Layout
+///
+/// `Oklab` is `#[repr(C)]`, with the `l`, `a`, and `b` components stored as
+/// consecutive `f32` values.
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Oklab {
+    /// The lightness component.
+    pub l: f32,
+    /// The green-red component.
+    pub a: f32,
+    /// The blue-yellow component.
+    pub b: f32,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Oklab>() == 12);
+const _: () = assert!(align_of::<Oklab>() == 4);
+
+
+impl Oklab {
+    /// Constructs a new `Oklab` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklab::new(0.628, 0.225, 0.126);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Oklab { l, a, b }
+    }
+
+    /// Returns an array containing the `[L, a, b]` components.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklab::new(0.628, 0.225, 0.126);
+    ///
+    /// assert_eq!(color.components(), [0.628, 0.225, 0.126]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn components(&self) -> [f32; 3] {
+        [self.l, self.a, self.b]
+    }
+
+    /// Returns the memory representation of the color as a byte array in
+    /// native byte order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklab::new(0.628, 0.225, 0.126);
+    ///
+    /// assert_eq!(Oklab::from_ne_bytes(color.to_ne_bytes()), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_ne_bytes(&self) -> [u8; 12] {
+        let [a, b, c] = self.components();
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&a.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&b.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&c.to_ne_bytes());
+        bytes
+    }
+
+    /// Constructs a new `Oklab` color from its memory representation as a byte
+    /// array in native byte order.
+    pub fn from_ne_bytes(bytes: [u8; 12]) -> Self {
+        let component = |i: usize| {
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[i..i + 4]);
+            f32::from_ne_bytes(word)
+        };
+        Oklab::new(component(0), component(4), component(8))
+    }
+
+    /// Returns the chroma of the color, i.e., its distance from the neutral
+    /// axis.
+    pub fn chroma(&self) -> f32 {
+        self.a.hypot(self.b)
+    }
+
+    /// Performs a component-wise linear interpolation between given colors,
+    /// returning the color located at the ratio given by `amount`, which is
+    /// clamped between 1 and 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Oklab::new(0.25, -0.125, 0.0);
+    /// let color_b = Oklab::new(0.75, 0.125, 0.25);
+    ///
+    /// let lerp_color = Oklab::linear_interpolate(color_a, color_b, 0.5);
+    ///
+    /// assert_eq!(lerp_color, Oklab::new(0.5, 0.0, 0.125));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn linear_interpolate<C, D>(start: C, end: D, amount: f32) -> Self
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+        Oklab {
+            l: lerp_f32(s.l, e.l, amount),
+            a: lerp_f32(s.a, e.a, amount),
+            b: lerp_f32(s.b, e.b, amount),
+        }
+    }
+
+    /// Returns the euclidean distance between the given colors, which
+    /// approximates their perceived difference.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Oklab::new(0.5, 0.0, 0.0);
+    /// let color_b = Oklab::new(0.5, 0.3, 0.4);
+    ///
+    /// assert_eq!(Oklab::distance(color_a, color_b), 0.5);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn distance<C, D>(start: C, end: D) -> f32
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+
+        let l = s.l - e.l;
+        let a = s.a - e.a;
+        let b = s.b - e.b;
+
+        (l*l + a*a + b*b).sqrt()
+    }
+
+    /// Returns true if the color lies within the gamut of the given
+    /// [`RgbSpace`].
+    ///
+    /// [`RgbSpace`]: rgb_space/trait.RgbSpace.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # use color::rgb_space::Rec2020;
+    /// # use color::rgb_space::Srgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklab::new(0.85, -0.3, 0.2);
+    ///
+    /// assert!(!color.in_gamut::<Srgb>());
+    /// assert!(color.in_gamut::<Rec2020>());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn in_gamut<S>(&self) -> bool where S: RgbSpace {
+        let linear = linear_srgb(*self);
+        in_gamut::<S>(Xyz::from(
+            matrix_transform(Srgb::XYZ_FROM_LINEAR, linear)))
+    }
+
+    /// Returns true if the color lies within the sRGB gamut, and so can be
+    /// converted to [`Rgb`] without clipping.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Oklab::new(0.6, 0.05, -0.1).is_displayable());
+    /// assert!(!Oklab::new(0.85, -0.3, 0.2).is_displayable());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_displayable(&self) -> bool {
+        self.in_gamut::<Srgb>()
+    }
+
+    /// Converts the color to [`Rgb`], returning an [`OutOfGamut`] error
+    /// holding the clamped color if it lies outside of the sRGB gamut.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    /// [`OutOfGamut`]: gamut/struct.OutOfGamut.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklab::new(0.85, -0.3, 0.2);
+    ///
+    /// let clamped = Rgb::from(color);
+    /// assert_eq!(color.checked_rgb().unwrap_err().clamped(), clamped);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn checked_rgb(&self) -> Result<Rgb, OutOfGamut> {
+        checked_rgb(*self, linear_srgb(*self))
+    }
+}
+
+
+impl fmt::Display for Oklab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Oklab conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[f32; 3]> for Oklab {
+    fn from(components: [f32; 3]) -> Self {
+        let span = span!(Level::DEBUG, "Oklab::from<[f32; 3]>");
+        let _enter = span.enter();
+
+        Oklab {
+            l: components[0],
+            a: components[1],
+            b: components[2],
+        }
+    }
+}
+
+impl From<Rgb> for Oklab {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Oklab::from<Rgb>");
+        let _enter = span.enter();
+
+        from_linear_srgb(rgb.linear_ratios())
+    }
+}
+
+impl From<Color> for Oklab {
+    fn from(color: Color) -> Self {
+        let span = span!(Level::DEBUG, "Oklab::from<Color>");
+        let _enter = span.enter();
+
+        Oklab::from(Rgb::from(color))
+    }
+}
+
+impl From<Oklab> for Rgb {
+    fn from(oklab: Oklab) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Oklab>");
+        let _enter = span.enter();
+
+        Rgb::from_linear_ratios(linear_srgb(oklab))
+    }
+}
+
+impl From<Oklab> for Color {
+    fn from(oklab: Oklab) -> Self {
+        let span = span!(Level::DEBUG, "Color::from<Oklab>");
+        let _enter = span.enter();
+
+        Color::from(Rgb::from(oklab))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the linear sRGB ratios of the given color, which are outside of
+/// [0, 1] if the color is out of the sRGB gamut.
+#[allow(clippy::excessive_precision)]
+pub(in crate) fn linear_srgb(oklab: Oklab) -> [f32; 3] {
+    let [l, m, s] = lms_from_oklab(oklab).map(|c| c * c * c);
+    [
+         4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+}
+
+/// Returns the `Oklab` color for the given linear sRGB ratios.
+pub(in crate) fn from_linear_srgb(rgb: [f32; 3]) -> Oklab {
+    oklab_from_lms(lms_from_linear_srgb(rgb).map(f32::cbrt))
+}
+
+/// Returns the LMS cone responses for the given linear sRGB ratios.
+#[inline]
+#[allow(clippy::excessive_precision)]
+pub(in crate) fn lms_from_linear_srgb([r, g, b]: [f32; 3]) -> [f32; 3] {
+    [
+        0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b,
+        0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b,
+        0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b,
+    ]
+}
+
+/// Returns the `Oklab` color for the given cube roots of the LMS cone
+/// responses.
+#[inline]
+#[allow(clippy::excessive_precision)]
+pub(in crate) fn oklab_from_lms([l, m, s]: [f32; 3]) -> Oklab {
+    Oklab {
+        l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    }
+}
+
+/// Returns the cube roots of the LMS cone responses for the given color.
+#[inline]
+#[allow(clippy::excessive_precision)]
+fn lms_from_oklab(oklab: Oklab) -> [f32; 3] {
+    [
+        oklab.l + 0.3963377774 * oklab.a + 0.2158037573 * oklab.b,
+        oklab.l - 0.1055613458 * oklab.a - 0.0638541728 * oklab.b,
+        oklab.l - 0.0894841775 * oklab.a - 1.2914855480 * oklab.b,
+    ]
+}
@@ -0,0 +1,452 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 96-bit Oklch color space.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::angle;
+use crate::Color;
+use crate::color_space::oklab::linear_srgb;
+use crate::gamut::checked_rgb;
+use crate::gamut::linear_in_gamut;
+use crate::gamut::OutOfGamut;
+use crate::Oklab;
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::utility::lerp_f32;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Oklch
+////////////////////////////////////////////////////////////////////////////////
+/// An Oklch color, the cylindrical form of [`Oklab`].
+///
+/// The `l` component is the same lightness as in [`Oklab`], the `c`
+/// component is the chroma, or distance from the neutral axis, and the `h`
+/// component is the hue angle in degrees, within [0, 360).
+///
+/// [`Oklab`]: struct.Oklab.html
+///
+/// # Layout
+///
+/// `Oklch` is `#[repr(C)]`, with the `l`, `c`, and `h` components stored as
+/// consecutive `f32` values.
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Oklch {
+    /// The lightness component.
+    pub l: f32,
+    /// The chroma component.
+    pub c: f32,
+    /// The hue component, in degrees.
+    pub h: f32,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Oklch>() == 12);
+const _: () = assert!(align_of::<Oklch>() == 4);
+
+
+impl Oklch {
+    /// Constructs a new `Oklch` color. The hue is wrapped into the range
+    /// [0, 360).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hue is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklch::new(0.5, 0.125, -30.0);
+    ///
+    /// assert_eq!(color.h, 330.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        assert!(h.is_finite());
+        Oklch { l, c, h: angle::wrap(h) }
+    }
+
+    /// Returns an array containing the `[L, C, h]` components.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklch::new(0.5, 0.125, 30.0);
+    ///
+    /// assert_eq!(color.components(), [0.5, 0.125, 30.0]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn components(&self) -> [f32; 3] {
+        [self.l, self.c, self.h]
+    }
+
+    /// Sets the hue component of the color in degrees, wrapping it into the
+    /// range [0, 360).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hue is not finite.
+    pub fn set_hue(&mut self, hue: f32) {
+        assert!(hue.is_finite());
+        self.h = angle::wrap(hue);
+    }
+
+    /// Returns the color with its hue rotated by the given number of degrees,
+    /// wrapping into the range [0, 360). Negative rotations are clockwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degrees` is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklch::new(0.5, 0.1, 300.0);
+    ///
+    /// assert_eq!(color.rotate_hue(90.0).h, 30.0);
+    /// assert_eq!(color.rotate_hue(-330.0).h, 330.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn rotate_hue(&self, degrees: f32) -> Self {
+        let mut color = *self;
+        color.set_hue(self.h + degrees);
+        color
+    }
+
+    /// Returns the memory representation of the color as a byte array in
+    /// native byte order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklch::new(0.5, 0.125, 30.0);
+    ///
+    /// assert_eq!(Oklch::from_ne_bytes(color.to_ne_bytes()), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_ne_bytes(&self) -> [u8; 12] {
+        let [a, b, c] = self.components();
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&a.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&b.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&c.to_ne_bytes());
+        bytes
+    }
+
+    /// Constructs a new `Oklch` color from its memory representation as a
+    /// byte array in native byte order.
+    pub fn from_ne_bytes(bytes: [u8; 12]) -> Self {
+        let component = |i: usize| {
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[i..i + 4]);
+            f32::from_ne_bytes(word)
+        };
+        Oklch { l: component(0), c: component(4), h: component(8) }
+    }
+
+    /// Performs a component-wise linear interpolation between given colors,
+    /// returning the color located at the ratio given by `amount`, which is
+    /// clamped between 1 and 0.
+    ///
+    /// The hue is interpolated directly between the two angles. Use the
+    /// [`Interpolate`] implementation to follow the shorter arc, or to
+    /// interpolate in rectangular form.
+    ///
+    /// [`Interpolate`]: gradient/trait.Interpolate.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Oklch::new(0.25, 0.0625, 20.0);
+    /// let color_b = Oklch::new(0.75, 0.1875, 60.0);
+    ///
+    /// let lerp_color = Oklch::linear_interpolate(color_a, color_b, 0.5);
+    ///
+    /// assert_eq!(lerp_color, Oklch::new(0.5, 0.125, 40.0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn linear_interpolate<C, D>(start: C, end: D, amount: f32) -> Self
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+    {
+        let s = start.into();
+        let e = end.into();
+        Oklch {
+            l: lerp_f32(s.l, e.l, amount),
+            c: lerp_f32(s.c, e.c, amount),
+            h: lerp_f32(s.h, e.h, amount),
+        }
+    }
+
+    /// Returns true if the color lies within the gamut of the given
+    /// [`RgbSpace`].
+    ///
+    /// [`RgbSpace`]: rgb_space/trait.RgbSpace.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::rgb_space::Rec2020;
+    /// # use color::rgb_space::Srgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklch::new(0.85, 0.35, 145.0);
+    ///
+    /// assert!(!color.in_gamut::<Srgb>());
+    /// assert!(color.in_gamut::<Rec2020>());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn in_gamut<S>(&self) -> bool where S: RgbSpace {
+        Oklab::from(*self).in_gamut::<S>()
+    }
+
+    /// Returns true if the color lies within the sRGB gamut, and so can be
+    /// converted to [`Rgb`] without clipping.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Oklch::new(0.6, 0.1, 270.0).is_displayable());
+    /// assert!(!Oklch::new(0.85, 0.35, 145.0).is_displayable());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_displayable(&self) -> bool {
+        self.in_gamut::<Srgb>()
+    }
+
+    /// Converts the color to [`Rgb`], returning an [`OutOfGamut`] error
+    /// holding the clamped color if it lies outside of the sRGB gamut.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    /// [`OutOfGamut`]: gamut/struct.OutOfGamut.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Oklch::new(0.85, 0.35, 145.0);
+    ///
+    /// let clamped = Rgb::from(color);
+    /// assert_eq!(color.checked_rgb().unwrap_err().clamped(), clamped);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn checked_rgb(&self) -> Result<Rgb, OutOfGamut> {
+        checked_rgb(*self, linear_srgb(Oklab::from(*self)))
+    }
+}
+
+
+impl fmt::Display for Oklch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Oklch conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[f32; 3]> for Oklch {
+    fn from(components: [f32; 3]) -> Self {
+        let span = span!(Level::DEBUG, "Oklch::from<[f32; 3]>");
+        let _enter = span.enter();
+
+        Oklch::new(components[0], components[1], components[2])
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(lab: Oklab) -> Self {
+        let span = span!(Level::DEBUG, "Oklch::from<Oklab>");
+        let _enter = span.enter();
+
+        let c = lab.a.hypot(lab.b);
+        let h = if c == 0.0 {
+            0.0
+        } else {
+            angle::wrap(lab.b.atan2(lab.a).to_degrees())
+        };
+        Oklch { l: lab.l, c, h }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(lch: Oklch) -> Self {
+        let span = span!(Level::DEBUG, "Oklab::from<Oklch>");
+        let _enter = span.enter();
+
+        let (sin, cos) = lch.h.to_radians().sin_cos();
+        Oklab::new(lch.l, lch.c * cos, lch.c * sin)
+    }
+}
+
+impl From<Rgb> for Oklch {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Oklch::from<Rgb>");
+        let _enter = span.enter();
+
+        Oklch::from(Oklab::from(rgb))
+    }
+}
+
+impl From<Color> for Oklch {
+    fn from(color: Color) -> Self {
+        let span = span!(Level::DEBUG, "Oklch::from<Color>");
+        let _enter = span.enter();
+
+        Oklch::from(Rgb::from(color))
+    }
+}
+
+impl From<Oklch> for Rgb {
+    fn from(lch: Oklch) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Oklch>");
+        let _enter = span.enter();
+
+        Rgb::from(Oklab::from(lch))
+    }
+}
+
+impl From<Oklch> for Color {
+    fn from(lch: Oklch) -> Self {
+        let span = span!(Level::DEBUG, "Color::from<Oklch>");
+        let _enter = span.enter();
+
+        Color::from(Rgb::from(lch))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Converts the color to `Rgb`, first reducing its chroma to the largest
+/// chroma within sRGB if it is out of gamut. The lightness and hue are
+/// preserved.
+pub(in crate) fn gamut_map(oklch: Oklch) -> Rgb {
+    let in_gamut = |chroma: f32| {
+        let oklab = Oklab::from(Oklch { c: chroma, ..oklch });
+        linear_in_gamut(linear_srgb(oklab))
+    };
+
+    let mut chroma = oklch.c;
+    if !in_gamut(chroma) {
+        let (mut low, mut high) = (0.0, chroma);
+        for _ in 0..24 {
+            let mid = (low + high) / 2.0;
+            if in_gamut(mid) { low = mid; } else { high = mid; }
+        }
+        chroma = low;
+    }
+    Rgb::from(Oklch { c: chroma, ..oklch })
+}
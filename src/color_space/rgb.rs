@@ -13,29 +13,87 @@
 
 // Local imports.
 use crate::Cmyk;
+use crate::color_space::luma::GrayscaleMethod;
+use crate::color_space::luma::Luma;
+use crate::contrast::contrast_ratio;
 use crate::Hsl;
 use crate::Hsv;
+use crate::color_space::oklch::gamut_map;
+use crate::lossless::widen;
+use crate::metric::Metric;
+use crate::Oklab;
+use crate::Oklch;
+use crate::packed::ChannelOrder;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::trace::event;
+use crate::trace::Level;
+use crate::trace::span;
 use crate::utility::cerp_u8;
 use crate::utility::clamped;
 use crate::utility::distance;
+use crate::utility::lerp_f32;
 use crate::utility::lerp_u8;
+use crate::utility::linear_to_srgb_octet;
+use crate::utility::matrix_transform;
+use crate::utility::srgb_octet_to_linear;
 use crate::Xyz;
 
 // External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
 #[cfg(feature = "serde")]
 use serde::Deserialize;
 #[cfg(feature = "serde")]
 use serde::Serialize;
-use tracing::Level;
-use tracing::event;
-use tracing::span;
 
 
 // Standard library imports.
 use std::convert::From;
 use std::fmt;
 use std::f32;
-use std::u8;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The number of steps taken through the value range by
+/// `Rgb::from_seed_with` when searching for sufficient contrast.
+const SEED_CONTRAST_STEPS: u32 = 64;
+
+/// The channel weights of full sepia toning, as used by the CSS `sepia()`
+/// filter. Each row gives the weights of the red, green, and blue input
+/// channels for one output channel.
+pub(in crate) const SEPIA_WEIGHTS: [[f32; 3]; 3] = [
+    [0.393, 0.769, 0.189],
+    [0.349, 0.686, 0.168],
+    [0.272, 0.534, 0.131],
+];
+
+/// The Oklch hue of the warmest colors, an orange. The coolest colors have
+/// the opposite hue, a cyan blue.
+const WARM_HUE: f32 = 55.0;
+
+/// The Oklch chroma at and above which a color's warmth is not reduced.
+const WARMTH_FULL_CHROMA: f32 = 0.1;
+
+/// The Oklch chroma at and above which `Rgb::vibrance` has no effect.
+const VIBRANCE_FULL_CHROMA: f32 = 0.2;
+
+/// The Oklch hue of typical skin tones, which `Rgb::vibrance` protects.
+const SKIN_HUE: f32 = 50.0;
+
+/// The number of degrees of hue either side of `SKIN_HUE` over which skin
+/// tone protection fades out.
+const SKIN_HUE_WIDTH: f32 = 30.0;
+
+/// The fraction of the vibrance adjustment withheld from skin tones.
+const SKIN_PROTECTION: f32 = 0.7;
+
+/// The warmth beyond which a color is classified as warm or cool.
+pub(in crate) const WARMTH_THRESHOLD: f32 = 0.1;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -50,12 +108,88 @@ use std::u8;
 pub struct RgbHexCodeParseError;
 
 
+////////////////////////////////////////////////////////////////////////////////
+// SeedOptions
+////////////////////////////////////////////////////////////////////////////////
+/// Constraints on the colors generated by [`Rgb::from_seed_with`].
+///
+/// [`Rgb::from_seed_with`]: struct.Rgb.html#method.from_seed_with
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SeedOptions {
+    /// The range of HSV saturations to choose from.
+    saturation: [f32; 2],
+    /// The range of HSV values to choose from.
+    value: [f32; 2],
+    /// The background color and the minimum contrast ratio against it.
+    contrast: Option<(Rgb, f32)>,
+}
+
+impl SeedOptions {
+    /// Constructs a new `SeedOptions` with the default saturation and value
+    /// ranges and no contrast requirement.
+    pub fn new() -> Self {
+        SeedOptions {
+            saturation: [0.45, 0.8],
+            value: [0.6, 0.9],
+            contrast: None,
+        }
+    }
+
+    /// Returns the options with the given range of HSV saturations. The
+    /// bounds are clamped between 0 and 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn with_saturation(mut self, min: f32, max: f32) -> Self {
+        self.saturation = unit_range(min, max);
+        self
+    }
+
+    /// Returns the options with the given range of HSV values, which control
+    /// the lightness of the generated colors. The bounds are clamped between
+    /// 0 and 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn with_value(mut self, min: f32, max: f32) -> Self {
+        self.value = unit_range(min, max);
+        self
+    }
+
+    /// Returns the options with a minimum contrast ratio against the given
+    /// background. Generated colors are moved toward whichever end of the
+    /// value range contrasts more with the background, and then toward the
+    /// minimum saturation, until the ratio is met. If the ranges do not allow
+    /// the ratio to be met, the closest color within them is used.
+    pub fn with_contrast(mut self, background: Rgb, ratio: f32) -> Self {
+        self.contrast = Some((background, ratio));
+        self
+    }
+}
+
+impl Default for SeedOptions {
+    fn default() -> Self {
+        SeedOptions::new()
+    }
+}
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // Rgb
 ////////////////////////////////////////////////////////////////////////////////
 /// The encoded RGB color.
+///
+/// # Layout
+///
+/// `Rgb` is `#[repr(C)]` with three `u8` components in `[r, g, b]` order. It
+/// has a size of 3 bytes and an alignment of 1 byte. This layout is guaranteed
+/// to remain stable.
 #[derive(Debug, PartialOrd, PartialEq, Eq, Hash, Ord, Clone, Copy, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
 pub struct Rgb {
     /// The red component.
     pub r: u8,
@@ -65,9 +199,945 @@ pub struct Rgb {
     pub b: u8,
 }
 
+// Layout guarantees.
+const _: () = assert!(size_of::<Rgb>() == 3);
+const _: () = assert!(align_of::<Rgb>() == 1);
+
+
+impl Rgb {
+    /// The black color.
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+
+    /// The white color.
+    pub const WHITE: Rgb = Rgb::new(u8::MAX, u8::MAX, u8::MAX);
+
+    /// Constructs a new `Rgb` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(127, 255, 64);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Rgb {r: red, g: green, b: blue}
+    }
+
+    /// Constructs a new `Rgb` color by parsing a hex code.
+    ///
+    /// Both three and six digit variations are acceptable, and the longest will
+    /// be used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::from_hex_code("#a1b2c3");
+    /// let color_short = Rgb::from_hex_code("#abc");
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_hex_code(hex: &str) -> Result<Rgb, RgbHexCodeParseError> {
+        if !hex.starts_with("#") || hex.len() < 4 {
+            return Err(RgbHexCodeParseError);
+        }
+
+        Rgb::from_hex_code_6(&hex[1..])
+            .or_else(|_| Rgb::from_hex_code_3(&hex[1..]))
+    }
+
+    fn from_hex_code_6(hex: &str) -> Result<Rgb, RgbHexCodeParseError> {
+        if hex.len() != 6 {
+            return Err(RgbHexCodeParseError);
+        }
+
+        match u32::from_str_radix(&hex[0..6], 16) {
+            Ok(v) => Ok(Rgb::from(v)),
+            Err(_) => Err(RgbHexCodeParseError),
+        }
+    }
+
+    fn from_hex_code_3(hex: &str) -> Result<Rgb, RgbHexCodeParseError> {
+        if hex.len() != 3 {
+            return Err(RgbHexCodeParseError);
+        }
+
+        match u32::from_str_radix(&hex[0..3], 16) {
+            Ok(v) => {
+                // Expand three digits into six.
+                let mut expanded = 0;
+                expanded |= v & 0x00F;
+                expanded |= (v & 0x00F) << 4;
+                expanded |= (v & 0x0F0) << 4;
+                expanded |= (v & 0x0F0) << 8;
+                expanded |= (v & 0xF00) << 8;
+                expanded |= (v & 0xF00) << 12;
+                Ok(Rgb::from(expanded))
+            }
+            Err(_) => Err(RgbHexCodeParseError),
+        }
+    }
+
+    /// Constructs a new `Rgb` color deterministically from a string, such as
+    /// a username or ID, using the default [`SeedOptions`].
+    ///
+    /// The same string always produces the same color, including across
+    /// versions of this crate and platforms.
+    ///
+    /// [`SeedOptions`]: struct.SeedOptions.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::from_seed("skyschermer");
+    ///
+    /// assert_eq!(color, Rgb::from_seed("skyschermer"));
+    /// assert_ne!(color, Rgb::from_seed("skyschermer2"));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_seed(seed: &str) -> Self {
+        Rgb::from_seed_with(seed, &SeedOptions::default())
+    }
+
+    /// Constructs a new `Rgb` color deterministically from a string, within
+    /// the constraints of the given [`SeedOptions`].
+    ///
+    /// [`SeedOptions`]: struct.SeedOptions.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::SeedOptions;
+    /// # use color::contrast::contrast_ratio;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let white = Rgb::new(255, 255, 255);
+    /// let options = SeedOptions::new()
+    ///     .with_value(0.3, 0.9)
+    ///     .with_contrast(white, 4.5);
+    ///
+    /// for name in ["alice", "bob", "carol"] {
+    ///     let color = Rgb::from_seed_with(name, &options);
+    ///     assert!(contrast_ratio(color, white) >= 4.5);
+    /// }
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_seed_with(seed: &str, options: &SeedOptions) -> Self {
+        let hash = fnv1a(seed.as_bytes());
+        let unit = |shift: u32| ((hash >> shift) & 0xFFFF) as f32 / 65535.0;
+
+        let hue = unit(0) * 360.0;
+        let [s_min, s_max] = options.saturation;
+        let [v_min, v_max] = options.value;
+        let saturation = lerp_f32(s_min, s_max, unit(16));
+        let value = lerp_f32(v_min, v_max, unit(32));
+        let color = |saturation, value| {
+            Rgb::from(Hsv::new(hue, saturation, value))
+        };
+
+        let (background, ratio) = match options.contrast {
+            Some(contrast) => contrast,
+            None           => return color(saturation, value),
+        };
+        let end = if contrast_ratio(color(saturation, v_min), background)
+            > contrast_ratio(color(saturation, v_max), background)
+        {
+            v_min
+        } else {
+            v_max
+        };
+
+        // Move the value toward the end of its range first, then reduce the
+        // saturation, which helps when a saturated hue is too dark.
+        let steps = (0..=SEED_CONTRAST_STEPS)
+            .map(|i| i as f32 / SEED_CONTRAST_STEPS as f32);
+        steps.clone()
+            .map(|amount| color(saturation, lerp_f32(value, end, amount)))
+            .chain(steps.map(|amount| {
+                color(lerp_f32(saturation, s_min, amount), end)
+            }))
+            .find(|&c| contrast_ratio(c, background) >= ratio)
+            .unwrap_or_else(|| color(s_min, end))
+    }
+
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(127, 255, 64);
+    /// 
+    /// assert_eq!(color.red(), 127);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn red(&self) -> u8 {
+        self.r
+    }
+    
+    /// Returns the green component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(127, 255, 64);
+    /// 
+    /// assert_eq!(color.green(), 255);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn green(&self) -> u8 {
+        self.g
+    }
+    
+    /// Returns the blue component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(127, 255, 64);
+    /// 
+    /// assert_eq!(color.blue(), 64);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn blue(&self) -> u8 {
+        self.b
+    }
+    
+    /// Sets the red component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut color = Rgb::new(127, 255, 64);
+    /// 
+    /// color.set_red(15);
+    /// 
+    /// assert_eq!(color, Rgb {r: 15, g: 255, b: 64});
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn set_red(&mut self, value: u8) {
+        self.r = value;
+    }
+    
+    /// Sets the green component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut color = Rgb::new(127, 255, 64);
+    /// 
+    /// color.set_green(15);
+    /// 
+    /// assert_eq!(color, Rgb {r: 127, g: 15, b: 64});
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn set_green(&mut self, value: u8) {
+        self.g = value;
+    }
+
+
+    /// Sets the blue component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut color = Rgb::new(127, 255, 64);
+    /// 
+    /// color.set_blue(15);
+    /// 
+    /// assert_eq!(color, Rgb {r: 127, g: 255, b: 15});
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn set_blue(&mut self, value: u8) {
+        self.b = value;
+    }
+
+    /// Returns an array containing the `[R, G, B]` component octets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb {r: 127, g: 255, b: 64};
+    ///
+    /// let octets = color.octets();
+    ///
+    /// assert_eq!(octets, [127u8, 255u8, 64u8]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn octets(&self) -> [u8; 3] {
+        [self.r, self.g, self.b]
+    }
+
+    /// Returns the memory representation of the color as a byte array,
+    /// matching the `#[repr(C)]` layout of `Rgb`. This is equivalent to
+    /// [`octets`], and is provided for uniformity with the floating point
+    /// color types.
+    ///
+    /// [`octets`]: struct.Rgb.html#method.octets
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(127, 255, 64);
+    ///
+    /// let bytes = color.to_ne_bytes();
+    ///
+    /// assert_eq!(Rgb::from_ne_bytes(bytes), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn to_ne_bytes(&self) -> [u8; 3] {
+        self.octets()
+    }
+
+    /// Constructs a new `Rgb` color from its memory representation as a byte
+    /// array.
+    pub const fn from_ne_bytes(bytes: [u8; 3]) -> Self {
+        Rgb::new(bytes[0], bytes[1], bytes[2])
+    }
+
+    /// Returns a view of the given bytes as a slice of `Rgb` colors, without
+    /// copying. Each color is 3 consecutive bytes in `[R, G, B]` order, as
+    /// in decoded image data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bytes` is not a multiple of 3.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let bytes = [255, 0, 0, 0, 128, 255];
+    ///
+    /// let colors = Rgb::from_bytes(&bytes);
+    ///
+    /// assert_eq!(colors.len(), 2);
+    /// assert_eq!(Rgb::as_bytes(colors), &bytes[..]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn from_bytes(bytes: &[u8]) -> &[Rgb] {
+        bytemuck::cast_slice(bytes)
+    }
+
+    /// Returns a mutable view of the given bytes as a slice of `Rgb` colors,
+    /// without copying, so decoded pixels can be modified in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bytes` is not a multiple of 3.
+    #[cfg(feature = "bytemuck")]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> &mut [Rgb] {
+        bytemuck::cast_slice_mut(bytes)
+    }
+
+    /// Returns a view of the given bytes as a slice of `Rgb` colors, without
+    /// copying, as [`from_bytes`] does.
+    ///
+    /// [`from_bytes`]: #method.from_bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PodCastError`] if the length of `bytes` is not a multiple
+    /// of 3.
+    ///
+    /// [`PodCastError`]: https://docs.rs/bytemuck/1/bytemuck/enum.PodCastError.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let bytes = [255, 0, 0, 0, 128, 255];
+    ///
+    /// assert_eq!(Rgb::try_from_bytes(&bytes).map(|c| c.len()), Ok(2));
+    /// assert!(Rgb::try_from_bytes(&bytes[1..]).is_err());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn try_from_bytes(bytes: &[u8])
+        -> Result<&[Rgb], bytemuck::PodCastError>
+    {
+        bytemuck::try_cast_slice(bytes)
+    }
+
+    /// Returns a mutable view of the given bytes as a slice of `Rgb` colors,
+    /// without copying, as [`from_bytes_mut`] does.
+    ///
+    /// [`from_bytes_mut`]: #method.from_bytes_mut
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PodCastError`] if the length of `bytes` is not a multiple
+    /// of 3.
+    ///
+    /// [`PodCastError`]: https://docs.rs/bytemuck/1/bytemuck/enum.PodCastError.html
+    #[cfg(feature = "bytemuck")]
+    pub fn try_from_bytes_mut(bytes: &mut [u8])
+        -> Result<&mut [Rgb], bytemuck::PodCastError>
+    {
+        bytemuck::try_cast_slice_mut(bytes)
+    }
+
+    /// Returns a view of the given colors as bytes, without copying.
+    #[cfg(feature = "bytemuck")]
+    pub fn as_bytes(colors: &[Rgb]) -> &[u8] {
+        bytemuck::cast_slice(colors)
+    }
+
+    /// Returns an array containing the `[R, G, B]` component ratios.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb {r: 127, g: 255, b: 64};
+    ///
+    /// let ratios = color.ratios();
+    ///
+    /// assert_eq!(ratios, [0.49803922f32, 1.0f32, 0.2509804f32]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn ratios(&self) -> [f32; 3] {
+        let max = u8::MAX as f32;
+        [
+            self.r as f32 / max,
+            self.g as f32 / max, 
+            self.b as f32 / max,
+        ]
+    }
+
+    /// Returns an array containing the `[R, G, B]` linear light ratios of
+    /// the color, decoded by the sRGB transfer function.
+    ///
+    /// Each channel is decoded with a precomputed lookup table, rather than
+    /// evaluating the transfer function.
+    ///
+    /// Linear light is returned as an array rather than as a separate color
+    /// type because it is only an intermediate step: the ratios feed directly
+    /// into the matrices of [`RgbSpace`] and the other color space
+    /// conversions, and are converted back with [`Rgb::from_linear_ratios`].
+    ///
+    /// [`RgbSpace`]: rgb_space/trait.RgbSpace.html
+    /// [`Rgb::from_linear_ratios`]: #method.from_linear_ratios
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(0, 188, 255);
+    ///
+    /// let [r, g, b] = color.linear_ratios();
+    ///
+    /// assert_eq!(r, 0.0);
+    /// assert!((g - 0.5).abs() < 0.005);
+    /// assert_eq!(b, 1.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn linear_ratios(&self) -> [f32; 3] {
+        self.octets().map(srgb_octet_to_linear)
+    }
+
+    /// Constructs a new `Rgb` color from the given `[R, G, B]` linear light
+    /// ratios, encoded by the sRGB transfer function and rounded to the
+    /// nearest octet. The ratios are clamped between 0 and 1.
+    ///
+    /// Each channel is encoded with a precomputed lookup table, rather than
+    /// evaluating the transfer function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(12, 188, 255);
+    ///
+    /// assert_eq!(Rgb::from_linear_ratios(color.linear_ratios()), color);
+    /// assert_eq!(
+    ///     Rgb::from_linear_ratios([-1.0, 0.5, 2.0]),
+    ///     Rgb::new(0, 188, 255));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_linear_ratios(linear: [f32; 3]) -> Self {
+        let [r, g, b] = linear.map(linear_to_srgb_octet);
+        Rgb::new(r, g, b)
+    }
+
+    /// Returns the `Rgb` hex code of the color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb {r: 127, g: 255, b: 64};
+    ///
+    /// assert_eq!(color.hex(), 0x7FFF40);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn hex(&self) -> u32 {
+        (self.r as u32) << 16 | (self.g as u32) << 8 | (self.b as u32)
+    }
+
+    /// Constructs a new `Rgb` color from its hex code, ignoring the high
+    /// byte. This is the `const` form of `Rgb::from(u32)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// const TOMATO: Rgb = Rgb::from_hex(0xFF6347);
+    ///
+    /// assert_eq!(TOMATO, Rgb::new(255, 99, 71));
+    /// assert_eq!(TOMATO.hex(), 0xFF6347);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn from_hex(hex: u32) -> Self {
+        Rgb::new((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
+    /// Returns the color packed into a `u32` in the given channel order,
+    /// with a fully opaque alpha. Unlike [`hex`], the alpha byte is always
+    /// set, as expected by most graphics APIs.
+    ///
+    /// [`hex`]: #method.hex
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::packed::ChannelOrder;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(0x7F, 0xFF, 0x40);
+    ///
+    /// assert_eq!(color.to_u32(ChannelOrder::Argb), 0xFF7FFF40);
+    /// assert_eq!(color.to_u32(ChannelOrder::Abgr), 0xFF40FF7F);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn to_u32(&self, order: ChannelOrder) -> u32 {
+        order.pack([self.r, self.g, self.b, u8::MAX])
+    }
+
+    /// Constructs a new `Rgb` color from a `u32` packed in the given channel
+    /// order, discarding the alpha.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::packed::ChannelOrder;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::from_u32(0x40FF7F00, ChannelOrder::Bgra);
+    ///
+    /// assert_eq!(color, Rgb::new(0x7F, 0xFF, 0x40));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn from_u32(packed: u32, order: ChannelOrder) -> Self {
+        let [r, g, b, _] = order.unpack(packed);
+        Rgb::new(r, g, b)
+    }
+
+    /// Returns the color with each channel inverted, as in a photographic
+    /// negative. Inverting twice returns the original color.
+    ///
+    /// Inversion changes the lightness of most colors. To find the opposite
+    /// hue at the same lightness, use [`complement`].
+    ///
+    /// [`complement`]: #method.complement
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(255, 200, 10);
+    ///
+    /// assert_eq!(color.invert(), Rgb::new(0, 55, 245));
+    /// assert_eq!(color.invert().invert(), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn invert(&self) -> Self {
+        Rgb::new(u8::MAX - self.r, u8::MAX - self.g, u8::MAX - self.b)
+    }
+
+    /// Returns the perceptual complement of the color, with its [`Oklch`] hue
+    /// rotated by 180 degrees. The lightness is preserved, and the chroma is
+    /// reduced if needed to stay within sRGB.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(200, 120, 40);
+    ///
+    /// let complement = Oklch::from(color.complement());
+    /// let original = Oklch::from(color);
+    ///
+    /// assert!((complement.l - original.l).abs() < 0.01);
+    /// assert!((complement.h - (original.h + 180.0) % 360.0).abs() < 1.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn complement(&self) -> Self {
+        gamut_map(Oklch::from(*self).rotate_hue(180.0))
+    }
+
+    /// Returns the warmth of the color, between -1 for the coolest colors and
+    /// 1 for the warmest.
+    ///
+    /// Warmth follows the [`Oklch`] hue, peaking at orange and bottoming out
+    /// at cyan blue, with greens and purples near zero. It is scaled down for
+    /// colors with little chroma, so grays are neutral.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Rgb::new(255, 128, 0).warmth() > 0.9);
+    /// assert!(Rgb::new(0, 140, 255).warmth() < -0.8);
+    /// assert!(Rgb::new(128, 128, 128).warmth().abs() < 0.01);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn warmth(&self) -> f32 {
+        let oklch = Oklch::from(*self);
+        let strength = (oklch.c / WARMTH_FULL_CHROMA).min(1.0);
+        (oklch.h - WARM_HUE).to_radians().cos() * strength
+    }
+
+    /// Returns true if the color is warm, with a [`warmth`] above 0.1.
+    ///
+    /// [`warmth`]: #method.warmth
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Rgb::new(220, 40, 40).is_warm());
+    /// assert!(!Rgb::new(40, 40, 220).is_warm());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_warm(&self) -> bool {
+        self.warmth() > WARMTH_THRESHOLD
+    }
+
+    /// Returns true if the color is cool, with a [`warmth`] below -0.1.
+    ///
+    /// [`warmth`]: #method.warmth
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Rgb::new(40, 40, 220).is_cool());
+    /// assert!(!Rgb::new(120, 120, 120).is_cool());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_cool(&self) -> bool {
+        self.warmth() < -WARMTH_THRESHOLD
+    }
+
+    /// Returns the gray level of the color using the given
+    /// [`GrayscaleMethod`].
+    ///
+    /// [`GrayscaleMethod`]: enum.GrayscaleMethod.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::GrayscaleMethod;
+    /// # use color::Luma;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(255, 128, 0);
+    ///
+    /// let luma = color.to_luma(GrayscaleMethod::Rec601);
+    ///
+    /// assert_eq!(luma, Luma::new(151));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_luma(&self, method: GrayscaleMethod) -> Luma {
+        Luma::from_rgb(*self, method)
+    }
+
+    /// Returns the gray `Rgb` color with the gray level of the color using
+    /// the given [`GrayscaleMethod`].
+    ///
+    /// [`GrayscaleMethod`]: enum.GrayscaleMethod.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::GrayscaleMethod;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(255, 128, 0);
+    ///
+    /// let gray = color.to_grayscale(GrayscaleMethod::Average);
+    ///
+    /// assert_eq!(gray, Rgb::new(128, 128, 128));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_grayscale(&self, method: GrayscaleMethod) -> Self {
+        Rgb::from(self.to_luma(method))
+    }
 
-impl Rgb {
-    /// Constructs a new `Rgb` color.
+    /// Returns the color with a sepia tone applied by the given amount,
+    /// between 0 for the original color and 1 for full sepia. This matches
+    /// the CSS `sepia()` filter.
     ///
     /// # Example
     ///
@@ -76,7 +1146,10 @@ impl Rgb {
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb::new(127, 255, 64);
+    /// let color = Rgb::new(128, 128, 128);
+    ///
+    /// assert_eq!(color.sepia(1.0), Rgb::new(173, 154, 120));
+    /// assert_eq!(color.sepia(0.0), color);
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -85,14 +1158,28 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn new(red: u8, green: u8, blue: u8) -> Self {
-        Rgb {r: red, g: green, b: blue}
+    pub fn sepia(&self, amount: f32) -> Self {
+        let a = clamped(amount, 0.0, 1.0);
+        let [r, g, b] = self.octets().map(f32::from);
+        let tone = |[wr, wg, wb]: [f32; 3], v: f32| {
+            lerp_f32(v, wr * r + wg * g + wb * b, a)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Rgb::new(
+            tone(SEPIA_WEIGHTS[0], r),
+            tone(SEPIA_WEIGHTS[1], g),
+            tone(SEPIA_WEIGHTS[2], b))
     }
 
-    /// Constructs a new `Rgb` color by parsing a hex code.
+    /// Returns the color remapped onto the two given colors by its gray
+    /// level, so black becomes `dark`, white becomes `light`, and the grays
+    /// between are interpolated in [`Oklab`].
     ///
-    /// Both three and six digit variations are acceptable, and the longest will
-    /// be used.
+    /// The gray level uses the default [`GrayscaleMethod`].
+    ///
+    /// [`Oklab`]: struct.Oklab.html
+    /// [`GrayscaleMethod`]: enum.GrayscaleMethod.html
     ///
     /// # Example
     ///
@@ -101,8 +1188,11 @@ impl Rgb {
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb::from_hex_code("#a1b2c3");
-    /// let color_short = Rgb::from_hex_code("#abc");
+    /// let navy = Rgb::new(20, 30, 90);
+    /// let peach = Rgb::new(255, 200, 160);
+    ///
+    /// assert_eq!(Rgb::new(0, 0, 0).duotone(navy, peach), navy);
+    /// assert_eq!(Rgb::new(255, 255, 255).duotone(navy, peach), peach);
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -111,48 +1201,110 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn from_hex_code(hex: &str) -> Result<Rgb, RgbHexCodeParseError> {
-        if !hex.starts_with("#") || hex.len() < 4 {
-            return Err(RgbHexCodeParseError);
-        }
-
-        Rgb::from_hex_code_6(&hex[1..])
-            .or_else(|_| Rgb::from_hex_code_3(&hex[1..]))
+    pub fn duotone<C, D>(&self, dark: C, light: D) -> Self
+        where
+            C: Into<Oklab> + Sized,
+            D: Into<Oklab> + Sized,
+    {
+        let amount = f32::from(self.to_luma(GrayscaleMethod::default()).y)
+            / 255.0;
+        Rgb::from(Oklab::linear_interpolate(dark, light, amount))
     }
 
-    fn from_hex_code_6(hex: &str) -> Result<Rgb, RgbHexCodeParseError> {
-        if hex.len() != 6 {
-            return Err(RgbHexCodeParseError);
-        }
-
-        match u32::from_str_radix(&hex[0..6], 16) {
-            Ok(v) => Ok(Rgb::from(v)),
-            Err(_) => Err(RgbHexCodeParseError),
-        }
+    /// Returns the color with the given gamma adjustment applied in linear
+    /// light. Each linear channel is raised to the power of `1 / gamma`, so
+    /// a gamma above 1 brightens the midtones and a gamma below 1 darkens
+    /// them, while black and white are unchanged.
+    ///
+    /// The color is decoded from sRGB before the adjustment and re-encoded
+    /// after it, which avoids the hue shifts and banding of adjusting the
+    /// encoded values directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gamma` is not a positive finite number.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(128, 64, 32);
+    ///
+    /// let brighter = color.adjust_gamma(2.2);
+    ///
+    /// assert!(brighter.r > color.r && brighter.b > color.b);
+    /// assert_eq!(color.adjust_gamma(1.0), color);
+    /// assert_eq!(Rgb::new(0, 255, 0).adjust_gamma(2.2), Rgb::new(0, 255, 0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn adjust_gamma(&self, gamma: f32) -> Self {
+        assert!(gamma > 0.0 && gamma.is_finite(),
+            "gamma must be a positive finite number");
+        self.map_linear(|v| v.powf(1.0 / gamma))
     }
 
-    fn from_hex_code_3(hex: &str) -> Result<Rgb, RgbHexCodeParseError> {
-        if hex.len() != 3 {
-            return Err(RgbHexCodeParseError);
-        }
+    /// Returns the color with its exposure adjusted by the given number of
+    /// stops. Each stop doubles or halves the linear light intensity, as on
+    /// a camera, and channels pushed past white are clipped.
+    ///
+    /// The color is decoded from sRGB before the adjustment and re-encoded
+    /// after it, so the result matches a physical change in exposure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ev` is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gray = Rgb::new(128, 128, 128);
+    ///
+    /// // One stop doubles the light, which is not double the encoded value.
+    /// assert_eq!(gray.adjust_exposure(1.0), Rgb::new(176, 176, 176));
+    /// assert_eq!(gray.adjust_exposure(-1.0), Rgb::new(92, 92, 92));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn adjust_exposure(&self, ev: f32) -> Self {
+        assert!(ev.is_finite(), "exposure adjustment must be finite");
+        let scale = ev.exp2();
+        self.map_linear(|v| v * scale)
+    }
 
-        match u32::from_str_radix(&hex[0..3], 16) {
-            Ok(v) => {
-                // Expand three digits into six.
-                let mut expanded = 0;
-                expanded |= v & 0x00F;
-                expanded |= (v & 0x00F) << 4;
-                expanded |= (v & 0x0F0) << 4;
-                expanded |= (v & 0x0F0) << 8;
-                expanded |= (v & 0xF00) << 8;
-                expanded |= (v & 0xF00) << 12;
-                Ok(Rgb::from(expanded))
-            }
-            Err(_) => Err(RgbHexCodeParseError),
-        }
+    /// Returns the color with the given function applied to each channel in
+    /// linear light.
+    fn map_linear<F>(&self, f: F) -> Self where F: Fn(f32) -> f32 {
+        Rgb::from_linear_ratios(self.linear_ratios().map(f))
     }
 
-    /// Returns the red component.
+    /// Returns the color with the given brightness delta added to each
+    /// channel ratio. A delta of 1 turns every color white and a delta of -1
+    /// turns every color black.
+    ///
+    /// Like the brightness control of most image editors, the adjustment is
+    /// applied to the encoded channels, not in linear light.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delta` is not finite.
     ///
     /// # Example
     ///
@@ -161,9 +1313,10 @@ impl Rgb {
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb::new(127, 255, 64);
-    /// 
-    /// assert_eq!(color.red(), 127);
+    /// let color = Rgb::new(128, 64, 240);
+    ///
+    /// assert_eq!(color.adjust_brightness(0.2), Rgb::new(179, 115, 255));
+    /// assert_eq!(color.adjust_brightness(-0.2), Rgb::new(77, 13, 189));
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -172,11 +1325,24 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn red(&self) -> u8 {
-        self.r
+    pub fn adjust_brightness(&self, delta: f32) -> Self {
+        assert!(delta.is_finite(), "brightness adjustment must be finite");
+        self.map_ratios(|v| v + delta)
     }
-    
-    /// Returns the green component.
+
+    /// Returns the color with its contrast scaled by the given factor about
+    /// a pivot of 0.5, the middle of the encoded channel range.
+    ///
+    /// A factor greater than 1 pushes channels away from the pivot, and a
+    /// factor less than 1 pulls them toward it. A factor of 0 turns every
+    /// color mid gray. To scale the contrast of an image about its mean, use
+    /// [`adjust::adjust_contrast`].
+    ///
+    /// [`adjust::adjust_contrast`]: adjust/fn.adjust_contrast.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is negative or not finite.
     ///
     /// # Example
     ///
@@ -185,9 +1351,10 @@ impl Rgb {
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb::new(127, 255, 64);
-    /// 
-    /// assert_eq!(color.green(), 255);
+    /// let color = Rgb::new(255, 128, 0);
+    ///
+    /// assert_eq!(color.adjust_contrast(0.5), Rgb::new(191, 128, 64));
+    /// assert_eq!(color.adjust_contrast(1.0), color);
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -196,11 +1363,40 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn green(&self) -> u8 {
-        self.g
+    pub fn adjust_contrast(&self, factor: f32) -> Self {
+        self.adjust_contrast_about(factor, 0.5)
     }
-    
-    /// Returns the blue component.
+
+    /// Returns the color with its contrast scaled by the given factor about
+    /// the given pivot channel ratio.
+    pub(in crate) fn adjust_contrast_about(&self, factor: f32, pivot: f32)
+        -> Self
+    {
+        assert!(factor >= 0.0 && factor.is_finite(),
+            "contrast factor must be a non-negative finite number");
+        self.map_ratios(|v| (v - pivot) * factor + pivot)
+    }
+
+    /// Returns the color with the given function applied to each encoded
+    /// channel ratio.
+    fn map_ratios<F>(&self, f: F) -> Self where F: Fn(f32) -> f32 {
+        let [r, g, b] = self.ratios().map(|ratio| {
+            (f(ratio).clamp(0.0, 1.0) * u8::MAX as f32).round() as u8
+        });
+        Rgb::new(r, g, b)
+    }
+
+    /// Returns the color with its [`Hsv`] hue rotated by the given number of
+    /// degrees. Negative rotations are clockwise. To rotate a perceptual hue
+    /// instead, rotate an [`Oklch`] color, or use [`HueSpace::rotate`].
+    ///
+    /// [`Hsv`]: struct.Hsv.html
+    /// [`Oklch`]: struct.Oklch.html
+    /// [`HueSpace::rotate`]: harmony/enum.HueSpace.html#method.rotate
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degrees` is not finite.
     ///
     /// # Example
     ///
@@ -209,9 +1405,11 @@ impl Rgb {
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb::new(127, 255, 64);
-    /// 
-    /// assert_eq!(color.blue(), 64);
+    /// let color = Rgb::new(255, 0, 0);
+    ///
+    /// assert_eq!(color.rotate_hue(120.0), Rgb::new(0, 255, 0));
+    /// assert_eq!(color.rotate_hue(-120.0), Rgb::new(0, 0, 255));
+    /// assert_eq!(color.rotate_hue(720.0), color);
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -220,24 +1418,30 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn blue(&self) -> u8 {
-        self.b
+    pub fn rotate_hue(&self, degrees: f32) -> Self {
+        Rgb::from(Hsv::from(*self).rotate_hue(degrees))
     }
-    
-    /// Sets the red component.
+
+    /// Returns the color with its [`Oklch`] lightness increased by `amount`,
+    /// clamped between 0 and 1. The hue is preserved, and the chroma is reduced
+    /// if needed to stay within sRGB.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
     ///
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
+    /// # use color::Oklch;
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let mut color = Rgb::new(127, 255, 64);
-    /// 
-    /// color.set_red(15);
-    /// 
-    /// assert_eq!(color, Rgb {r: 15, g: 255, b: 64});
+    /// let color = Rgb::new(200, 60, 40);
+    ///
+    /// let lighter = color.lighten(0.1);
+    ///
+    /// assert!(Oklch::from(lighter).l > Oklch::from(color).l);
+    /// assert_eq!(color.lighten(1.0), Rgb::new(255, 255, 255));
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -246,24 +1450,32 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn set_red(&mut self, value: u8) {
-        self.r = value;
+    pub fn lighten(&self, amount: f32) -> Self {
+        let oklch = Oklch::from(*self);
+        let l = clamped(oklch.l + amount, 0.0, 1.0);
+        gamut_map(Oklch { l, ..oklch })
     }
-    
-    /// Sets the green component.
+
+    /// Returns the color with its [`Oklch`] lightness decreased by `amount`,
+    /// clamped between 0 and 1. The hue is preserved, and the chroma is reduced
+    /// if needed to stay within sRGB.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
     ///
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
+    /// # use color::Oklch;
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let mut color = Rgb::new(127, 255, 64);
-    /// 
-    /// color.set_green(15);
-    /// 
-    /// assert_eq!(color, Rgb {r: 127, g: 15, b: 64});
+    /// let color = Rgb::new(200, 60, 40);
+    ///
+    /// let darker = color.darken(0.1);
+    ///
+    /// assert!(Oklch::from(darker).l < Oklch::from(color).l);
+    /// assert_eq!(color.darken(1.0), Rgb::new(0, 0, 0));
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -272,25 +1484,62 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn set_green(&mut self, value: u8) {
-        self.g = value;
+    pub fn darken(&self, amount: f32) -> Self {
+        let oklch = Oklch::from(*self);
+        let l = clamped(oklch.l - amount, 0.0, 1.0);
+        gamut_map(Oklch { l, ..oklch })
     }
 
+    /// Returns the color with its [`Oklch`] lightness moved toward 1 by the
+    /// given ratio of the remaining distance. The hue is preserved, and the
+    /// chroma is reduced if needed to stay within sRGB.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb::new(0, 0, 0);
+    ///
+    /// assert_eq!(color.lighten_relative(0.0), Rgb::new(0, 0, 0));
+    /// assert_eq!(color.lighten_relative(1.0), Rgb::new(255, 255, 255));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn lighten_relative(&self, ratio: f32) -> Self {
+        let oklch = Oklch::from(*self);
+        let l = clamped(oklch.l + (1.0 - oklch.l) * ratio, 0.0, 1.0);
+        gamut_map(Oklch { l, ..oklch })
+    }
 
-    /// Sets the blue component.
+    /// Returns the color with its [`Oklch`] lightness reduced by the given
+    /// ratio. The hue is preserved, and the chroma is reduced if needed to stay
+    /// within sRGB.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
     ///
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
+    /// # use color::Oklch;
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let mut color = Rgb::new(127, 255, 64);
-    /// 
-    /// color.set_blue(15);
-    /// 
-    /// assert_eq!(color, Rgb {r: 127, g: 255, b: 15});
+    /// let color = Rgb::new(255, 255, 255);
+    ///
+    /// assert_eq!(color.darken_relative(0.0), Rgb::new(255, 255, 255));
+    /// assert_eq!(color.darken_relative(1.0), Rgb::new(0, 0, 0));
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -299,24 +1548,32 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn set_blue(&mut self, value: u8) {
-        self.b = value;
+    pub fn darken_relative(&self, ratio: f32) -> Self {
+        let oklch = Oklch::from(*self);
+        let l = clamped(oklch.l * (1.0 - ratio), 0.0, 1.0);
+        gamut_map(Oklch { l, ..oklch })
     }
 
-    /// Returns an array containing the `[R, G, B]` component octets.
+    /// Returns the color with its [`Oklch`] chroma scaled up by the given
+    /// ratio, so a ratio of 0.5 gives 1.5 times the chroma. The lightness and
+    /// hue are preserved, and the chroma is reduced if needed to stay within
+    /// sRGB.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
     ///
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
+    /// # use color::Oklch;
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb {r: 127, g: 255, b: 64};
+    /// let color = Rgb::new(160, 110, 100);
     ///
-    /// let octets = color.octets();
+    /// let vivid = color.saturate(0.5);
     ///
-    /// assert_eq!(octets, [127u8, 255u8, 64u8]);
+    /// assert!(Oklch::from(vivid).c > Oklch::from(color).c);
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -325,24 +1582,39 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn octets(&self) -> [u8; 3] {
-        [self.r, self.g, self.b]
+    pub fn saturate(&self, ratio: f32) -> Self {
+        let oklch = Oklch::from(*self);
+        let c = (oklch.c * (1.0 + ratio)).max(0.0);
+        gamut_map(Oklch { c, ..oklch })
     }
 
-    /// Returns an array containing the `[R, G, B]` component ratios.
+    /// Returns the color with its vibrance adjusted by the given amount.
+    ///
+    /// Like [`saturate`], this scales the [`Oklch`] chroma, but the scale
+    /// is weighted toward muted colors: a color with no chroma is scaled by
+    /// `1 + amount`, and colors nearer a chroma of 0.2 are scaled less, down to
+    /// no change. Skin tone hues are also protected, receiving as little as
+    /// 30% of the adjustment, so that portraits are not oversaturated. Negative
+    /// amounts reduce the vibrance. The lightness and hue are preserved, and
+    /// the chroma is reduced if needed to stay within sRGB.
+    ///
+    /// [`saturate`]: #method.saturate
+    /// [`Oklch`]: struct.Oklch.html
     ///
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
+    /// # use color::Oklch;
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb {r: 127, g: 255, b: 64};
+    /// let muted = Rgb::new(110, 130, 150);
+    /// let vivid = Rgb::new(20, 90, 230);
     ///
-    /// let ratios = color.ratios();
+    /// let gain = |c: Rgb| Oklch::from(c.vibrance(0.5)).c / Oklch::from(c).c;
     ///
-    /// assert_eq!(ratios, [0.49803922f32, 1.0f32, 0.2509804f32]);
+    /// assert!(gain(muted) > gain(vivid));
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -351,27 +1623,40 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn ratios(&self) -> [f32; 3] {
-        let max = u8::MAX as f32;
-        [
-            self.r as f32 / max,
-            self.g as f32 / max, 
-            self.b as f32 / max,
-        ]
+    pub fn vibrance(&self, amount: f32) -> Self {
+        let oklch = Oklch::from(*self);
+        let weight = (1.0 - oklch.c / VIBRANCE_FULL_CHROMA).clamp(0.0, 1.0);
+
+        let d = (oklch.h - SKIN_HUE).rem_euclid(360.0);
+        let skin = (1.0 - d.min(360.0 - d) / SKIN_HUE_WIDTH).max(0.0);
+        let protection = SKIN_PROTECTION * skin * skin * (3.0 - 2.0 * skin);
+
+        let scale = 1.0 + amount * weight * (1.0 - protection);
+        let c = (oklch.c * scale).max(0.0);
+        gamut_map(Oklch { c, ..oklch })
     }
 
-    /// Returns the `Rgb` hex code of the color.
+    /// Returns the color with its [`Oklch`] chroma scaled down by the given
+    /// ratio, so a ratio of 1 gives a gray of the same lightness. The
+    /// lightness and hue are preserved.
+    ///
+    /// [`Oklch`]: struct.Oklch.html
     ///
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
+    /// # use color::Oklch;
     /// # use color::Rgb;
     /// # fn example() -> Result<(), Box<dyn Error>> {
     /// # //-------------------------------------------------------------------
-    /// let color = Rgb {r: 127, g: 255, b: 64};
+    /// let color = Rgb::new(160, 110, 100);
     ///
-    /// assert_eq!(color.hex(), 0x7FFF40);
+    /// let muted = color.desaturate(0.5);
+    ///
+    /// assert!(Oklch::from(muted).c < Oklch::from(color).c);
+    /// let gray = Rgb::new(255, 0, 0).desaturate(1.0);
+    /// assert_eq!(gray, Rgb::new(136, 136, 136));
     /// # //-------------------------------------------------------------------
     /// #     Ok(())
     /// # }
@@ -380,8 +1665,10 @@ impl Rgb {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn hex(&self) -> u32 {
-        (self.r as u32) << 16 | (self.g as u32) << 8 | (self.b as u32)
+    pub fn desaturate(&self, ratio: f32) -> Self {
+        let oklch = Oklch::from(*self);
+        let c = (oklch.c * (1.0 - ratio)).max(0.0);
+        gamut_map(Oklch { c, ..oklch })
     }
 
     /// Performs an `Rgb` component-wise linear interpolation between given 
@@ -505,6 +1792,47 @@ impl Rgb {
 
         (r*r + g*g + b*b).sqrt()
     }
+
+    /// Returns the distance between the given colors, as measured by the
+    /// given [`Metric`].
+    ///
+    /// [`Metric`]: metric/trait.Metric.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklab;
+    /// # use color::Rgb;
+    /// # use color::metric::DeltaE;
+    /// # use color::metric::Euclidean;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color_a = Rgb {r: 127, g: 255, b: 64};
+    /// let color_b = Rgb {r: 15, g: 144, b: 99};
+    ///
+    /// assert_eq!(
+    ///     Rgb::distance_with(color_a, color_b, Euclidean::Rgb),
+    ///     Rgb::distance(color_a, color_b));
+    /// assert_eq!(
+    ///     Rgb::distance_with(color_a, Oklab::from(color_a), DeltaE::Ciede2000),
+    ///     0.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn distance_with<C, D, M>(start: C, end: D, metric: M) -> f32
+        where
+            C: Into<Self> + Sized,
+            D: Into<Self> + Sized,
+            M: Metric,
+    {
+        metric.distance(start.into(), end.into())
+    }
 }
 
 
@@ -540,11 +1868,7 @@ impl From<u32> for Rgb {
 
         event!(Level::TRACE, "hex={:0X}", hex);
         
-        let rgb = Rgb {
-            r: ((hex & 0xFF0000) >> 16) as u8,
-            g: ((hex & 0x00FF00) >> 8) as u8,
-            b: ((hex & 0x0000FF)) as u8,
-        };
+        let rgb = Rgb::from_hex(hex);
 
         event!(Level::TRACE, "Rgb={:?}", rgb);
         rgb
@@ -619,6 +1943,16 @@ impl From<Rgb> for [f32; 4] {
     }
 }
 
+/// Converts the color to 16-bit channel values, scaling each octet by 257.
+impl From<Rgb> for [u16; 3] {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "[u16; 3]::from<Rgb>");
+        let _enter = span.enter();
+
+        rgb.octets().map(widen)
+    }
+}
+
 impl From<Cmyk> for Rgb {
     fn from(cmyk: Cmyk) -> Self {
         let span = span!(Level::DEBUG, "Rgb::from<Cmyk>");
@@ -645,6 +1979,7 @@ impl From<Cmyk> for Rgb {
 
 
 impl From<Hsl> for Rgb {
+    #[allow(clippy::manual_range_contains)]
     fn from(hsl: Hsl) -> Self {
         let span = span!(Level::DEBUG, "Rgb::from<Hsl>");
         let _enter = span.enter();
@@ -684,6 +2019,7 @@ impl From<Hsl> for Rgb {
 }
 
 impl From<Hsv> for Rgb {
+    #[allow(clippy::manual_range_contains)]
     fn from(hsv: Hsv) -> Self {
         let span = span!(Level::DEBUG, "Rgb::from<Hsv>");
         let _enter = span.enter();
@@ -730,19 +2066,33 @@ impl From<Xyz> for Rgb {
 
         event!(Level::TRACE, "Xyz {{ x={}, y={}, z={} }}", x, y, z);
 
-        let ri = x *  3.2404542 + y * -1.5371385 + z * -0.4985314;
-        let gi = x * -0.9692660 + y *  1.8760108 + z *  0.0415560;
-        let bi = x *  0.0556434 + y * -0.2040259 + z *  1.0572252;
+        let [ri, gi, bi] = matrix_transform(
+            Srgb::LINEAR_FROM_XYZ,
+            [x, y, z]);
 
         event!(Level::TRACE, "ri={}, gi={}, bi={}", ri, gi, bi);
 
-        let rgb = Rgb {
-            r: (ri * u8::MAX as f32) as u8,
-            g: (gi * u8::MAX as f32) as u8,
-            b: (bi * u8::MAX as f32) as u8,
-        };
+        let rgb = Rgb::from_linear_ratios([ri, gi, bi]);
 
         event!(Level::TRACE, "Rgb={:?}", rgb);
         rgb
     }
 }
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the 64-bit FNV-1a hash of the given bytes. This is used instead of
+/// the standard library hashers because its output is stable.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// Returns the given range clamped between 0 and 1.
+fn unit_range(min: f32, max: f32) -> [f32; 2] {
+    assert!(min <= max);
+    [min.clamp(0.0, 1.0), max.clamp(0.0, 1.0)]
+}
@@ -0,0 +1,304 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 48-bit RGB color space.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::xyz64::from_linear_srgb;
+use crate::color_space::xyz64::linear_srgb;
+use crate::lossless::widen;
+use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::utility::linear_to_srgb_f64;
+use crate::utility::srgb_to_linear_f64;
+use crate::Xyz;
+use crate::Xyz64;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rgb48
+////////////////////////////////////////////////////////////////////////////////
+/// The encoded RGB color with 16 bits per channel.
+///
+/// `Rgb48` holds the same sRGB encoded colors as [`Rgb`] with 256 times the
+/// precision, as stored by 16-bit PNG and TIFF images and scanners. Widening
+/// an [`Rgb`] color replicates each octet into both bytes of the channel, so
+/// black and white stay black and white, and narrowing rounds to the nearest
+/// octet.
+///
+/// [`Rgb`]: struct.Rgb.html
+///
+/// # Layout
+///
+/// `Rgb48` is `#[repr(C)]` with three `u16` components in `[r, g, b]`
+/// order. It has a size of 6 bytes and an alignment of 2 bytes. This layout
+/// is guaranteed to remain stable.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Hash, Ord, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Rgb48 {
+    /// The red component.
+    pub r: u16,
+    /// The green component.
+    pub g: u16,
+    /// The blue component.
+    pub b: u16,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Rgb48>() == 6);
+const _: () = assert!(align_of::<Rgb48>() == 2);
+
+
+impl Rgb48 {
+    /// Constructs a new `Rgb48` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb48;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb48::new(0x1234, 0xABCD, 0xFFFF);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(red: u16, green: u16, blue: u16) -> Self {
+        Rgb48 { r: red, g: green, b: blue }
+    }
+
+    /// Returns an array containing the `[R, G, B]` channels.
+    pub const fn channels(&self) -> [u16; 3] {
+        [self.r, self.g, self.b]
+    }
+
+    /// Returns an array containing the `[R, G, B]` ratios.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb48;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb48::new(0, 0x8000, 0xFFFF);
+    ///
+    /// assert_eq!(color.ratios(), [0.0, 32768.0 / 65535.0, 1.0]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn ratios(&self) -> [f32; 3] {
+        let max = u16::MAX as f32;
+        [
+            self.r as f32 / max,
+            self.g as f32 / max,
+            self.b as f32 / max,
+        ]
+    }
+
+    /// Constructs a new `Rgb48` color from the given `[R, G, B]` ratios,
+    /// which are clamped between 0 and 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb48;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb48::from_ratios([0.0, 0.5, 1.5]);
+    ///
+    /// assert_eq!(color, Rgb48::new(0, 0x8000, 0xFFFF));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_ratios(ratios: [f32; 3]) -> Self {
+        let [r, g, b] = ratios.map(|v| {
+            (v.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+        });
+        Rgb48::new(r, g, b)
+    }
+
+    /// Returns the color narrowed to 8 bits per channel, rounding each
+    /// channel to the nearest octet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::Rgb48;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgb48::from(Rgb::new(12, 200, 255));
+    ///
+    /// assert_eq!(color, Rgb48::new(0x0C0C, 0xC8C8, 0xFFFF));
+    /// assert_eq!(color.rgb(), Rgb::new(12, 200, 255));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn rgb(&self) -> Rgb {
+        let [r, g, b] = self.channels().map(narrow);
+        Rgb::new(r, g, b)
+    }
+}
+
+
+impl fmt::Display for Rgb48 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+impl fmt::UpperHex for Rgb48 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "#{:04X}{:04X}{:04X}", self.r, self.g, self.b)
+    }
+}
+
+
+impl fmt::LowerHex for Rgb48 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "#{:04x}{:04x}{:04x}", self.r, self.g, self.b)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rgb48 conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[u16; 3]> for Rgb48 {
+    fn from(channels: [u16; 3]) -> Self {
+        let span = span!(Level::DEBUG, "Rgb48::from<[u16; 3]>");
+        let _enter = span.enter();
+
+        let [r, g, b] = channels;
+        Rgb48::new(r, g, b)
+    }
+}
+
+impl From<Rgb48> for [u16; 3] {
+    fn from(rgb: Rgb48) -> Self {
+        let span = span!(Level::DEBUG, "[u16; 3]::from<Rgb48>");
+        let _enter = span.enter();
+
+        rgb.channels()
+    }
+}
+
+impl From<Rgb> for Rgb48 {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Rgb48::from<Rgb>");
+        let _enter = span.enter();
+
+        let [r, g, b] = rgb.octets().map(widen);
+        Rgb48::new(r, g, b)
+    }
+}
+
+/// Converts the color to `Rgb`, rounding each channel to the nearest octet.
+impl From<Rgb48> for Rgb {
+    fn from(rgb: Rgb48) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Rgb48>");
+        let _enter = span.enter();
+
+        rgb.rgb()
+    }
+}
+
+impl From<Rgb48> for Xyz64 {
+    fn from(rgb: Rgb48) -> Self {
+        let span = span!(Level::DEBUG, "Xyz64::from<Rgb48>");
+        let _enter = span.enter();
+
+        from_linear_srgb(rgb.channels().map(|v| {
+            srgb_to_linear_f64(f64::from(v) / f64::from(u16::MAX))
+        }))
+    }
+}
+
+impl From<Xyz64> for Rgb48 {
+    fn from(xyz: Xyz64) -> Self {
+        let span = span!(Level::DEBUG, "Rgb48::from<Xyz64>");
+        let _enter = span.enter();
+
+        let [r, g, b] = linear_srgb(xyz).map(|v| {
+            let encoded = linear_to_srgb_f64(v.max(0.0)).clamp(0.0, 1.0);
+            (encoded * f64::from(u16::MAX)).round() as u16
+        });
+        Rgb48::new(r, g, b)
+    }
+}
+
+impl From<Rgb48> for Xyz {
+    fn from(rgb: Rgb48) -> Self {
+        let span = span!(Level::DEBUG, "Xyz::from<Rgb48>");
+        let _enter = span.enter();
+
+        Xyz::from(Xyz64::from(rgb))
+    }
+}
+
+impl From<Xyz> for Rgb48 {
+    fn from(xyz: Xyz) -> Self {
+        let span = span!(Level::DEBUG, "Rgb48::from<Xyz>");
+        let _enter = span.enter();
+
+        Rgb48::from(Xyz64::from(xyz))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Narrows the given 16-bit channel to the nearest octet.
+pub(in crate) fn narrow(channel: u16) -> u8 {
+    ((u32::from(channel) + 128) / 257) as u8
+}
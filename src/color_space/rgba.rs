@@ -0,0 +1,439 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 32-bit RGB color space with a straight alpha channel.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Color;
+use crate::packed::ChannelOrder;
+use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rgba
+////////////////////////////////////////////////////////////////////////////////
+/// The encoded RGB color with an alpha channel.
+///
+/// The color channels are not premultiplied by the alpha. An alpha of 0 is
+/// fully transparent and an alpha of 255 is fully opaque. Converting an `Rgba`
+/// color to [`Rgb`] discards the alpha; use [`Rgba::over`] to composite it
+/// onto a background instead.
+///
+/// [`Rgb`]: struct.Rgb.html
+/// [`Rgba::over`]: struct.Rgba.html#method.over
+///
+/// # Layout
+///
+/// `Rgba` is `#[repr(C)]` with four `u8` components in `[r, g, b, a]` order.
+/// It has a size of 4 bytes and an alignment of 1 byte. This layout is
+/// guaranteed to remain stable.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Hash, Ord, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Rgba {
+    /// The red component.
+    pub r: u8,
+    /// The green component.
+    pub g: u8,
+    /// The blue component.
+    pub b: u8,
+    /// The alpha component.
+    pub a: u8,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Rgba>() == 4);
+const _: () = assert!(align_of::<Rgba>() == 1);
+
+
+impl Rgba {
+    /// The fully transparent color.
+    pub const TRANSPARENT: Rgba = Rgba::new(0, 0, 0, 0);
+
+    /// The opaque black color.
+    pub const BLACK: Rgba = Rgba::new(0, 0, 0, u8::MAX);
+
+    /// The opaque white color.
+    pub const WHITE: Rgba = Rgba::new(u8::MAX, u8::MAX, u8::MAX, u8::MAX);
+
+    /// Constructs a new `Rgba` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgba::new(127, 255, 64, 128);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Rgba { r: red, g: green, b: blue, a: alpha }
+    }
+
+    /// Constructs a new `Rgba` color from the given color and alpha.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::Rgba;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgba::from_rgb(Rgb::new(10, 20, 30), 40);
+    ///
+    /// assert_eq!(color, Rgba::new(10, 20, 30, 40));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn from_rgb(rgb: Rgb, alpha: u8) -> Self {
+        Rgba::new(rgb.r, rgb.g, rgb.b, alpha)
+    }
+
+    /// Returns the color without its alpha.
+    pub const fn rgb(&self) -> Rgb {
+        Rgb::new(self.r, self.g, self.b)
+    }
+
+    /// Returns the alpha as a ratio between 0 and 1.
+    pub fn alpha(&self) -> f32 {
+        self.a as f32 / u8::MAX as f32
+    }
+
+    /// Returns an array containing the `[R, G, B, A]` octets.
+    pub const fn octets(&self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// Returns a view of the given bytes as a slice of `Rgba` colors, without
+    /// copying. Each color is 4 consecutive bytes in `[R, G, B, A]` order, as
+    /// in decoded image data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bytes` is not a multiple of 4.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let bytes = [255, 0, 0, 255, 0, 128, 255, 64];
+    ///
+    /// let colors = Rgba::from_bytes(&bytes);
+    ///
+    /// assert_eq!(colors.len(), 2);
+    /// assert_eq!(Rgba::as_bytes(colors), &bytes[..]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn from_bytes(bytes: &[u8]) -> &[Rgba] {
+        bytemuck::cast_slice(bytes)
+    }
+
+    /// Returns a mutable view of the given bytes as a slice of `Rgba` colors,
+    /// without copying, so decoded pixels can be modified in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bytes` is not a multiple of 4.
+    #[cfg(feature = "bytemuck")]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> &mut [Rgba] {
+        bytemuck::cast_slice_mut(bytes)
+    }
+
+    /// Returns a view of the given bytes as a slice of `Rgba` colors, without
+    /// copying, as [`from_bytes`] does.
+    ///
+    /// [`from_bytes`]: #method.from_bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PodCastError`] if the length of `bytes` is not a multiple
+    /// of 4.
+    ///
+    /// [`PodCastError`]: https://docs.rs/bytemuck/1/bytemuck/enum.PodCastError.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let bytes = [255, 0, 0, 255, 0, 128, 255, 64];
+    ///
+    /// assert_eq!(Rgba::try_from_bytes(&bytes).map(|c| c.len()), Ok(2));
+    /// assert!(Rgba::try_from_bytes(&bytes[1..]).is_err());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn try_from_bytes(bytes: &[u8])
+        -> Result<&[Rgba], bytemuck::PodCastError>
+    {
+        bytemuck::try_cast_slice(bytes)
+    }
+
+    /// Returns a mutable view of the given bytes as a slice of `Rgba` colors,
+    /// without copying, as [`from_bytes_mut`] does.
+    ///
+    /// [`from_bytes_mut`]: #method.from_bytes_mut
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PodCastError`] if the length of `bytes` is not a multiple
+    /// of 4.
+    ///
+    /// [`PodCastError`]: https://docs.rs/bytemuck/1/bytemuck/enum.PodCastError.html
+    #[cfg(feature = "bytemuck")]
+    pub fn try_from_bytes_mut(bytes: &mut [u8])
+        -> Result<&mut [Rgba], bytemuck::PodCastError>
+    {
+        bytemuck::try_cast_slice_mut(bytes)
+    }
+
+    /// Returns a view of the given colors as bytes, without copying.
+    #[cfg(feature = "bytemuck")]
+    pub fn as_bytes(colors: &[Rgba]) -> &[u8] {
+        bytemuck::cast_slice(colors)
+    }
+
+    /// Returns an array containing the `[R, G, B, A]` ratios.
+    pub fn ratios(&self) -> [f32; 4] {
+        let max = u8::MAX as f32;
+        [
+            self.r as f32 / max,
+            self.g as f32 / max,
+            self.b as f32 / max,
+            self.a as f32 / max,
+        ]
+    }
+
+    /// Returns true if the color is fully opaque.
+    pub const fn is_opaque(&self) -> bool {
+        self.a == u8::MAX
+    }
+
+    /// Returns the color packed into a `u32` in the given channel order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # use color::packed::ChannelOrder;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgba::new(0x11, 0x22, 0x33, 0xFF);
+    ///
+    /// assert_eq!(color.to_u32(ChannelOrder::Argb), 0xFF112233);
+    /// assert_eq!(color.to_u32(ChannelOrder::Bgra), 0x332211FF);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn to_u32(&self, order: ChannelOrder) -> u32 {
+        order.pack(self.octets())
+    }
+
+    /// Constructs a new `Rgba` color from a `u32` packed in the given channel
+    /// order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # use color::packed::ChannelOrder;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgba::from_u32(0x11223344, ChannelOrder::Rgba);
+    ///
+    /// assert_eq!(color, Rgba::new(0x11, 0x22, 0x33, 0x44));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn from_u32(packed: u32, order: ChannelOrder) -> Self {
+        let [r, g, b, a] = order.unpack(packed);
+        Rgba::new(r, g, b, a)
+    }
+
+    /// Returns the color composited over the given opaque background using
+    /// the source-over operator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::Rgba;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgba::new(255, 0, 0, 128);
+    ///
+    /// assert_eq!(color.over(Rgb::new(0, 0, 255)), Rgb::new(128, 0, 127));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn over(&self, background: Rgb) -> Rgb {
+        let alpha = self.alpha();
+        let [r, g, b] = [
+            (self.r, background.r),
+            (self.g, background.g),
+            (self.b, background.b),
+        ].map(|(s, d)| {
+            (s as f32 * alpha + d as f32 * (1.0 - alpha)).round() as u8
+        });
+        Rgb::new(r, g, b)
+    }
+}
+
+
+impl fmt::Display for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+impl fmt::UpperHex for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+    }
+}
+
+
+impl fmt::LowerHex for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rgba conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[u8; 4]> for Rgba {
+    fn from(octets: [u8; 4]) -> Self {
+        let span = span!(Level::DEBUG, "Rgba::from<[u8; 4]>");
+        let _enter = span.enter();
+
+        let [r, g, b, a] = octets;
+        Rgba::new(r, g, b, a)
+    }
+}
+
+impl From<Rgba> for [u8; 4] {
+    fn from(rgba: Rgba) -> Self {
+        let span = span!(Level::DEBUG, "[u8; 4]::from<Rgba>");
+        let _enter = span.enter();
+
+        rgba.octets()
+    }
+}
+
+/// Converts the color to a fully opaque `Rgba` color.
+impl From<Rgb> for Rgba {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Rgba::from<Rgb>");
+        let _enter = span.enter();
+
+        Rgba::from_rgb(rgb, u8::MAX)
+    }
+}
+
+/// Converts the color to `Rgb`, discarding the alpha.
+impl From<Rgba> for Rgb {
+    fn from(rgba: Rgba) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Rgba>");
+        let _enter = span.enter();
+
+        rgba.rgb()
+    }
+}
+
+/// Converts the color to a fully opaque `Rgba` color.
+impl From<Color> for Rgba {
+    fn from(color: Color) -> Self {
+        let span = span!(Level::DEBUG, "Rgba::from<Color>");
+        let _enter = span.enter();
+
+        Rgba::from(Rgb::from(color))
+    }
+}
+
+/// Converts the color to `Color`, discarding the alpha.
+impl From<Rgba> for Color {
+    fn from(rgba: Rgba) -> Self {
+        let span = span!(Level::DEBUG, "Color::from<Rgba>");
+        let _enter = span.enter();
+
+        Color::from(rgba.rgb())
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 64-bit RGB color space with a straight alpha channel.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::rgb48::narrow;
+use crate::lossless::widen;
+use crate::Rgb;
+use crate::Rgb48;
+use crate::Rgba;
+use crate::trace::Level;
+use crate::trace::span;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rgba64
+////////////////////////////////////////////////////////////////////////////////
+/// The encoded RGB color with an alpha channel and 16 bits per channel.
+///
+/// This is the 16-bit form of [`Rgba`]. The color channels are not
+/// premultiplied by the alpha. An alpha of 0 is fully transparent and an
+/// alpha of 65535 is fully opaque.
+///
+/// [`Rgba`]: struct.Rgba.html
+///
+/// # Layout
+///
+/// `Rgba64` is `#[repr(C)]` with four `u16` components in `[r, g, b, a]`
+/// order. It has a size of 8 bytes and an alignment of 2 bytes. This layout
+/// is guaranteed to remain stable.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Hash, Ord, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Rgba64 {
+    /// The red component.
+    pub r: u16,
+    /// The green component.
+    pub g: u16,
+    /// The blue component.
+    pub b: u16,
+    /// The alpha component.
+    pub a: u16,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Rgba64>() == 8);
+const _: () = assert!(align_of::<Rgba64>() == 2);
+
+
+impl Rgba64 {
+    /// Constructs a new `Rgba64` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgba64::new(0x1234, 0xABCD, 0xFFFF, 0x8000);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(red: u16, green: u16, blue: u16, alpha: u16) -> Self {
+        Rgba64 { r: red, g: green, b: blue, a: alpha }
+    }
+
+    /// Constructs a new `Rgba64` color from an [`Rgb48`] color and an alpha.
+    ///
+    /// [`Rgb48`]: struct.Rgb48.html
+    pub const fn from_rgb48(rgb: Rgb48, alpha: u16) -> Self {
+        Rgba64::new(rgb.r, rgb.g, rgb.b, alpha)
+    }
+
+    /// Returns the color without its alpha.
+    pub const fn rgb48(&self) -> Rgb48 {
+        Rgb48::new(self.r, self.g, self.b)
+    }
+
+    /// Returns the alpha as a ratio between 0 and 1.
+    pub fn alpha(&self) -> f32 {
+        self.a as f32 / u16::MAX as f32
+    }
+
+    /// Returns an array containing the `[R, G, B, A]` channels.
+    pub const fn channels(&self) -> [u16; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// Returns an array containing the `[R, G, B, A]` ratios.
+    pub fn ratios(&self) -> [f32; 4] {
+        let max = u16::MAX as f32;
+        [
+            self.r as f32 / max,
+            self.g as f32 / max,
+            self.b as f32 / max,
+            self.a as f32 / max,
+        ]
+    }
+
+    /// Returns true if the color is fully opaque.
+    pub const fn is_opaque(&self) -> bool {
+        self.a == u16::MAX
+    }
+
+    /// Returns the color narrowed to 8 bits per channel, rounding each
+    /// channel to the nearest octet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgba;
+    /// # use color::Rgba64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Rgba64::from(Rgba::new(12, 200, 255, 128));
+    ///
+    /// assert_eq!(color, Rgba64::new(0x0C0C, 0xC8C8, 0xFFFF, 0x8080));
+    /// assert_eq!(color.rgba(), Rgba::new(12, 200, 255, 128));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn rgba(&self) -> Rgba {
+        let [r, g, b, a] = self.channels().map(narrow);
+        Rgba::new(r, g, b, a)
+    }
+}
+
+
+impl fmt::Display for Rgba64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+impl fmt::UpperHex for Rgba64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "#{:04X}{:04X}{:04X}{:04X}",
+            self.r, self.g, self.b, self.a)
+    }
+}
+
+
+impl fmt::LowerHex for Rgba64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "#{:04x}{:04x}{:04x}{:04x}",
+            self.r, self.g, self.b, self.a)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rgba64 conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[u16; 4]> for Rgba64 {
+    fn from(channels: [u16; 4]) -> Self {
+        let span = span!(Level::DEBUG, "Rgba64::from<[u16; 4]>");
+        let _enter = span.enter();
+
+        let [r, g, b, a] = channels;
+        Rgba64::new(r, g, b, a)
+    }
+}
+
+impl From<Rgba64> for [u16; 4] {
+    fn from(rgba: Rgba64) -> Self {
+        let span = span!(Level::DEBUG, "[u16; 4]::from<Rgba64>");
+        let _enter = span.enter();
+
+        rgba.channels()
+    }
+}
+
+impl From<Rgba> for Rgba64 {
+    fn from(rgba: Rgba) -> Self {
+        let span = span!(Level::DEBUG, "Rgba64::from<Rgba>");
+        let _enter = span.enter();
+
+        let [r, g, b, a] = rgba.octets().map(widen);
+        Rgba64::new(r, g, b, a)
+    }
+}
+
+/// Converts the color to `Rgba`, rounding each channel to the nearest octet.
+impl From<Rgba64> for Rgba {
+    fn from(rgba: Rgba64) -> Self {
+        let span = span!(Level::DEBUG, "Rgba::from<Rgba64>");
+        let _enter = span.enter();
+
+        rgba.rgba()
+    }
+}
+
+/// Converts the color to a fully opaque `Rgba64` color.
+impl From<Rgb48> for Rgba64 {
+    fn from(rgb: Rgb48) -> Self {
+        let span = span!(Level::DEBUG, "Rgba64::from<Rgb48>");
+        let _enter = span.enter();
+
+        Rgba64::from_rgb48(rgb, u16::MAX)
+    }
+}
+
+/// Converts the color to `Rgb48`, discarding the alpha.
+impl From<Rgba64> for Rgb48 {
+    fn from(rgba: Rgba64) -> Self {
+        let span = span!(Level::DEBUG, "Rgb48::from<Rgba64>");
+        let _enter = span.enter();
+
+        rgba.rgb48()
+    }
+}
+
+/// Converts the color to a fully opaque `Rgba64` color.
+impl From<Rgb> for Rgba64 {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Rgba64::from<Rgb>");
+        let _enter = span.enter();
+
+        Rgba64::from(Rgb48::from(rgb))
+    }
+}
+
+/// Converts the color to `Rgb`, discarding the alpha.
+impl From<Rgba64> for Rgb {
+    fn from(rgba: Rgba64) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Rgba64>");
+        let _enter = span.enter();
+
+        rgba.rgb48().rgb()
+    }
+}
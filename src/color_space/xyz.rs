@@ -13,20 +13,33 @@
 
 // Local imports.
 use crate::Cmyk;
+use crate::gamut::checked_rgb;
+use crate::gamut::in_gamut;
+use crate::gamut::linear_srgb_from_xyz;
+use crate::gamut::OutOfGamut;
 use crate::Hsl;
 use crate::Hsv;
+use crate::illuminant::adaptation_matrix;
+use crate::illuminant::AdaptationMethod;
+use crate::illuminant::Illuminant;
 use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::trace::Level;
+use crate::trace::span;
 use crate::utility::cerp_f32;
-use crate::utility::clamped;
 use crate::utility::lerp_f32;
+use crate::utility::matrix_transform;
 
 // External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
 #[cfg(feature = "serde")]
 use serde::Deserialize;
 #[cfg(feature = "serde")]
 use serde::Serialize;
-use tracing::Level;
-use tracing::span;
 
 
 // Standard library imports.
@@ -39,9 +52,22 @@ use std::f32;
 ////////////////////////////////////////////////////////////////////////////////
 // Xyz
 ////////////////////////////////////////////////////////////////////////////////
-/// The encoded XYZ color.
+/// The CIE XYZ color.
+///
+/// Colors converted from [`Rgb`] are relative to the D65 white point of
+/// sRGB, with white having a luminance `y` of 1.
+///
+/// [`Rgb`]: struct.Rgb.html
+///
+/// # Layout
+///
+/// `Xyz` is `#[repr(C)]` with three `f32` components in `[x, y, z]` order. It
+/// has a size of 12 bytes and an alignment of 4 bytes. This layout is
+/// guaranteed to remain stable.
 #[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
 pub struct Xyz {
     /// The x component.
     pub x: f32,
@@ -51,9 +77,14 @@ pub struct Xyz {
     pub z: f32,
 }
 
+// Layout guarantees.
+const _: () = assert!(size_of::<Xyz>() == 12);
+const _: () = assert!(align_of::<Xyz>() == 4);
+
 
 impl Xyz {
-    /// Constructs a new `Xyz` color.
+    /// Constructs a new `Xyz` color. The components are not clamped, as the
+    /// `x` and `z` components of colors such as the D65 white point exceed 1.
     ///
     /// # Example
     ///
@@ -71,12 +102,8 @@ impl Xyz {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
-        let mut xyz = Xyz {x: 0.0, y: 0.0, z: 0.0};
-        xyz.set_x(x);
-        xyz.set_y(y);
-        xyz.set_z(z);
-        xyz
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Xyz { x, y, z }
     }
 
     /// Returns the x component.
@@ -151,7 +178,7 @@ impl Xyz {
         self.z
     }
 
-    /// Sets the x component as a ratio.
+    /// Sets the x component.
     ///
     /// # Example
     ///
@@ -174,10 +201,10 @@ impl Xyz {
     /// # }
     /// ```
     pub fn set_x(&mut self, x: f32) {
-        self.x = clamped(x, 0.0, 1.0);
+        self.x = x;
     }
 
-    /// Sets the y component as a ratio.
+    /// Sets the y component.
     ///
     /// # Example
     ///
@@ -199,10 +226,10 @@ impl Xyz {
     /// #     example().unwrap();
     /// # }
     pub fn set_y(&mut self, y: f32) {
-        self.y = clamped(y, 0.0, 1.0);
+        self.y = y;
     }
 
-    /// Sets the z component as a ratio.
+    /// Sets the z component.
     ///
     /// # Example
     ///
@@ -224,7 +251,7 @@ impl Xyz {
     /// #     example().unwrap();
     /// # }
     pub fn set_z(&mut self, z: f32) {
-        self.z = clamped(z, 0.0, 1.0);
+        self.z = z;
     }
 
     /// Returns an array containing the `[X, Y, Z]` components.
@@ -249,10 +276,54 @@ impl Xyz {
     /// #     example().unwrap();
     /// # }
     /// ```
-    pub fn components(&self) -> [f32; 3] {
+    pub const fn components(&self) -> [f32; 3] {
         [self.x, self.y, self.z]
     }
 
+    /// Returns the memory representation of the color as a byte array in
+    /// native byte order, matching the `#[repr(C)]` layout of `Xyz`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Xyz::new(0.24, 0.68, 0.91);
+    ///
+    /// let bytes = color.to_ne_bytes();
+    ///
+    /// assert_eq!(Xyz::from_ne_bytes(bytes), color);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn to_ne_bytes(&self) -> [u8; 12] {
+        let [a, b, c] = self.components();
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&a.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&b.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&c.to_ne_bytes());
+        bytes
+    }
+
+    /// Constructs a new `Xyz` color from its memory representation as a byte
+    /// array in native byte order. The components are used as given, so
+    /// converting a color to bytes and back is exact.
+    pub fn from_ne_bytes(bytes: [u8; 12]) -> Self {
+        let component = |i: usize| {
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[i..i + 4]);
+            f32::from_ne_bytes(word)
+        };
+        Xyz::from([component(0), component(4), component(8)])
+    }
+
     /// Performs a component-wise linear interpolation between given colors,
     /// returning the color located at the ratio given by `amount`, which is
     /// clamped between 1 and 0.
@@ -373,6 +444,175 @@ impl Xyz {
 
         (x*x + y*y + z*z).sqrt()
     }
+
+    /// Returns the color as seen under the `to` illuminant, given that it is
+    /// referenced to the `from` illuminant, using the given chromatic
+    /// adaptation method. This is needed to convert colors correctly between
+    /// D65 display spaces and D50 print spaces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz;
+    /// # use color::illuminant::AdaptationMethod;
+    /// # use color::illuminant::Illuminant;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let white = Illuminant::D65.white_point();
+    ///
+    /// let adapted = white.adapt(
+    ///     Illuminant::D65,
+    ///     Illuminant::D50,
+    ///     AdaptationMethod::Bradford);
+    ///
+    /// assert!(Xyz::distance(adapted, Illuminant::D50.white_point()) < 1e-4);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn adapt(
+        &self,
+        from: Illuminant,
+        to: Illuminant,
+        method: AdaptationMethod)
+        -> Self
+    {
+        if from == to { return *self; }
+        let matrix = adaptation_matrix(
+            from.white_point(),
+            to.white_point(),
+            method);
+        let [x, y, z] = matrix_transform(matrix, self.components());
+        Xyz { x, y, z }
+    }
+
+    /// Constructs an `Xyz` color by applying the sRGB matrix directly to the
+    /// encoded ratios of the given [`Rgb`] color, without linearizing them.
+    ///
+    /// This reproduces the conversion from [`Rgb`] used before version
+    /// 0.3.0, which gives incorrect XYZ values. It is provided for
+    /// compatibility with stored values computed by that conversion.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    #[deprecated(since = "0.3.0",
+        note = "this conversion is incorrect; use `Xyz::from` instead")]
+    pub fn from_encoded_rgb(rgb: Rgb) -> Self {
+        let [x, y, z] = matrix_transform(
+            Srgb::XYZ_FROM_LINEAR,
+            rgb.ratios());
+        Xyz { x, y, z }
+    }
+
+    /// Returns the [`Rgb`] color given by applying the inverse sRGB matrix
+    /// to the components and using the results directly as encoded ratios.
+    ///
+    /// This reproduces the conversion to [`Rgb`] used before version 0.3.0,
+    /// and is the inverse of [`Xyz::from_encoded_rgb`].
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    /// [`Xyz::from_encoded_rgb`]: #method.from_encoded_rgb
+    #[deprecated(since = "0.3.0",
+        note = "this conversion is incorrect; use `Rgb::from` instead")]
+    pub fn to_encoded_rgb(&self) -> Rgb {
+        let [r, g, b] = matrix_transform(
+            Srgb::LINEAR_FROM_XYZ,
+            self.components());
+        Rgb::new(
+            (r * u8::MAX as f32) as u8,
+            (g * u8::MAX as f32) as u8,
+            (b * u8::MAX as f32) as u8)
+    }
+
+    /// Returns true if the color lies within the gamut of the given
+    /// [`RgbSpace`].
+    ///
+    /// [`RgbSpace`]: rgb_space/trait.RgbSpace.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz;
+    /// # use color::rgb_space::Rec2020;
+    /// # use color::rgb_space::Srgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Xyz { x: 0.30, y: 0.65, z: 0.08 };
+    ///
+    /// assert!(!color.in_gamut::<Srgb>());
+    /// assert!(color.in_gamut::<Rec2020>());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn in_gamut<S>(&self) -> bool where S: RgbSpace {
+        in_gamut::<S>(*self)
+    }
+
+    /// Returns true if the color lies within the sRGB gamut, and so can be
+    /// converted to [`Rgb`] without clipping.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert!(Xyz::new(0.3, 0.3, 0.3).is_displayable());
+    /// assert!(!Xyz { x: 0.30, y: 0.65, z: 0.08 }.is_displayable());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn is_displayable(&self) -> bool {
+        self.in_gamut::<Srgb>()
+    }
+
+    /// Converts the color to [`Rgb`], returning an [`OutOfGamut`] error
+    /// holding the clamped color if it lies outside of the sRGB gamut.
+    ///
+    /// [`Rgb`]: struct.Rgb.html
+    /// [`OutOfGamut`]: gamut/struct.OutOfGamut.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz;
+    /// # use color::Rgb;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Xyz { x: 0.30, y: 0.65, z: 0.08 };
+    ///
+    /// let clamped = Rgb::from(color);
+    /// assert_eq!(color.checked_rgb().unwrap_err().clamped(), clamped);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn checked_rgb(&self) -> Result<Rgb, OutOfGamut> {
+        checked_rgb(*self, linear_srgb_from_xyz(self.components()))
+    }
 }
 
 
@@ -432,13 +672,11 @@ impl From<Rgb> for Xyz {
         let span = span!(Level::DEBUG, "Xyz::from<Rgb>");
         let _enter = span.enter();
         
-        let m = rgb.ratios(); 
+        let [x, y, z] = matrix_transform(
+            Srgb::XYZ_FROM_LINEAR,
+            rgb.linear_ratios());
 
-        Xyz {
-            x: m[0] * 0.4124564 + m[1] * 0.3575761 + m[2] * 0.1804375,
-            y: m[0] * 0.2126729 + m[1] * 0.7151522 + m[2] * 0.0721750,
-            z: m[0] * 0.0193339 + m[1] * 0.1191920 + m[2] * 0.9503041,
-        }
+        Xyz { x, y, z }
     }
 }
 
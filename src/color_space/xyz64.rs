@@ -0,0 +1,285 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines a 192-bit double precision XYZ color space.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::illuminant::adaptation_matrix_f64;
+use crate::illuminant::AdaptationMethod;
+use crate::illuminant::Illuminant;
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::inverse_f64;
+use crate::rgb_space::Srgb;
+use crate::rgb_space::xyz_from_linear_matrix_f64;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::utility::linear_to_srgb_f64;
+use crate::utility::matrix_transform;
+use crate::utility::srgb_to_linear_f64;
+use crate::Xyz;
+
+// External library imports.
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "bytemuck")]
+use bytemuck::Zeroable;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The double precision matrix converting linear sRGB ratios to `Xyz64`
+/// components.
+const SRGB_XYZ_FROM_LINEAR: [[f64; 3]; 3]
+    = xyz_from_linear_matrix_f64(Srgb::PRIMARIES, Srgb::WHITE_POINT);
+
+/// The double precision matrix converting `Xyz64` components to linear sRGB
+/// ratios.
+const SRGB_LINEAR_FROM_XYZ: [[f64; 3]; 3] = inverse_f64(SRGB_XYZ_FROM_LINEAR);
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Xyz64
+////////////////////////////////////////////////////////////////////////////////
+/// The CIE XYZ color with double precision components.
+///
+/// This is the double precision form of [`Xyz`], for colorimetric chains of
+/// adaptation, [`Lab64`] conversion, and color differences whose
+/// accumulated `f32` rounding error is visible in tolerance testing. Colors
+/// converted from [`Rgb`] are relative to the D65 white point of sRGB, with
+/// white having a luminance `y` of 1.
+///
+/// [`Xyz`]: struct.Xyz.html
+/// [`Lab64`]: struct.Lab64.html
+/// [`Rgb`]: struct.Rgb.html
+///
+/// # Layout
+///
+/// `Xyz64` is `#[repr(C)]` with three `f64` components in `[x, y, z]`
+/// order.
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bytemuck", derive(Pod, Zeroable))]
+#[repr(C)]
+pub struct Xyz64 {
+    /// The x component.
+    pub x: f64,
+    /// The y component.
+    pub y: f64,
+    /// The z component.
+    pub z: f64,
+}
+
+// Layout guarantees.
+const _: () = assert!(size_of::<Xyz64>() == 24);
+const _: () = assert!(align_of::<Xyz64>() == 8);
+
+
+impl Xyz64 {
+    /// Constructs a new `Xyz64` color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Xyz64::new(0.24, 0.68, 0.91);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Xyz64 { x, y, z }
+    }
+
+    /// Returns an array containing the `[x, y, z]` components.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz64;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Xyz64::new(0.24, 0.68, 0.91);
+    ///
+    /// assert_eq!(color.components(), [0.24, 0.68, 0.91]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub const fn components(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Returns the color adapted from the `from` illuminant to the `to`
+    /// illuminant using the given chromatic adaptation transform, computed
+    /// in double precision.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Xyz64;
+    /// # use color::illuminant::AdaptationMethod;
+    /// # use color::illuminant::Illuminant;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = Xyz64::new(0.35, 0.40, 0.12);
+    ///
+    /// let d50 = color.adapt(
+    ///     Illuminant::D65,
+    ///     Illuminant::D50,
+    ///     AdaptationMethod::Bradford);
+    /// let d65 = d50.adapt(
+    ///     Illuminant::D50,
+    ///     Illuminant::D65,
+    ///     AdaptationMethod::Bradford);
+    ///
+    /// assert!((d65.x - color.x).abs() < 1e-12);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn adapt(
+        &self,
+        from: Illuminant,
+        to: Illuminant,
+        method: AdaptationMethod)
+        -> Self
+    {
+        if from == to { return *self; }
+        let matrix = adaptation_matrix_f64(
+            white_point(from),
+            white_point(to),
+            method);
+        let [x, y, z] = matrix_transform(matrix, self.components());
+        Xyz64 { x, y, z }
+    }
+}
+
+
+impl fmt::Display for Xyz64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Xyz64 conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<[f64; 3]> for Xyz64 {
+    fn from(components: [f64; 3]) -> Self {
+        let span = span!(Level::DEBUG, "Xyz64::from<[f64; 3]>");
+        let _enter = span.enter();
+
+        Xyz64 {
+            x: components[0],
+            y: components[1],
+            z: components[2],
+        }
+    }
+}
+
+impl From<Xyz> for Xyz64 {
+    fn from(xyz: Xyz) -> Self {
+        let span = span!(Level::DEBUG, "Xyz64::from<Xyz>");
+        let _enter = span.enter();
+
+        Xyz64 {
+            x: f64::from(xyz.x),
+            y: f64::from(xyz.y),
+            z: f64::from(xyz.z),
+        }
+    }
+}
+
+impl From<Xyz64> for Xyz {
+    fn from(xyz: Xyz64) -> Self {
+        let span = span!(Level::DEBUG, "Xyz::from<Xyz64>");
+        let _enter = span.enter();
+
+        Xyz {
+            x: xyz.x as f32,
+            y: xyz.y as f32,
+            z: xyz.z as f32,
+        }
+    }
+}
+
+impl From<Rgb> for Xyz64 {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "Xyz64::from<Rgb>");
+        let _enter = span.enter();
+
+        from_linear_srgb(rgb.ratios()
+            .map(|v| srgb_to_linear_f64(f64::from(v))))
+    }
+}
+
+impl From<Xyz64> for Rgb {
+    fn from(xyz: Xyz64) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<Xyz64>");
+        let _enter = span.enter();
+
+        let [r, g, b] = linear_srgb(xyz).map(|v| {
+            let encoded = linear_to_srgb_f64(v.max(0.0)).clamp(0.0, 1.0);
+            (encoded * f64::from(u8::MAX)).round() as u8
+        });
+        Rgb::new(r, g, b)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the double precision components of the given illuminant's white
+/// point, with a luminance of 1.
+pub(in crate) fn white_point(illuminant: Illuminant) -> [f64; 3] {
+    let [x, y] = illuminant.chromaticity().map(f64::from);
+    [x / y, 1.0, (1.0 - x - y) / y]
+}
+
+/// Returns the `Xyz64` color of the given linear sRGB ratios.
+pub(in crate) fn from_linear_srgb(linear: [f64; 3]) -> Xyz64 {
+    Xyz64::from(matrix_transform(SRGB_XYZ_FROM_LINEAR, linear))
+}
+
+/// Returns the linear sRGB ratios of the given color, which are outside of
+/// [0, 1] if the color is out of the sRGB gamut.
+pub(in crate) fn linear_srgb(xyz: Xyz64) -> [f64; 3] {
+    matrix_transform(SRGB_LINEAR_FROM_XYZ, xyz.components())
+}
@@ -0,0 +1,197 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines the reversible YCoCg-R color encoding.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Color;
+use crate::Rgb;
+use crate::trace::Level;
+use crate::trace::span;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::convert::From;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// YCoCgR
+////////////////////////////////////////////////////////////////////////////////
+/// A YCoCg-R color, the lifting-based reversible form of YCoCg.
+///
+/// The `y` component is the luma, and the `co` and `cg` components are the
+/// orange and green chroma offsets. The conversion from [`Rgb`] uses only
+/// integer additions and shifts, and converting back recovers the original
+/// color exactly. The chroma components need 9 bits each, ranging from -255
+/// to 255.
+///
+/// `YCoCgR` colors can only be constructed from an [`Rgb`] color, so every
+/// value converts back exactly.
+///
+/// [`Rgb`]: struct.Rgb.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct YCoCgR {
+    /// The luma component.
+    pub(in crate) y: u8,
+    /// The orange chroma component.
+    pub(in crate) co: i16,
+    /// The green chroma component.
+    pub(in crate) cg: i16,
+}
+
+
+impl YCoCgR {
+    /// Returns the luma component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::YCoCgR;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = YCoCgR::from(Rgb::new(200, 100, 50));
+    ///
+    /// assert_eq!(color.y(), 112);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    /// Returns the orange chroma component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::YCoCgR;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = YCoCgR::from(Rgb::new(200, 100, 50));
+    ///
+    /// assert_eq!(color.co(), 150);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn co(&self) -> i16 {
+        self.co
+    }
+
+    /// Returns the green chroma component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::YCoCgR;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let color = YCoCgR::from(Rgb::new(200, 100, 50));
+    ///
+    /// assert_eq!(color.cg(), -25);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn cg(&self) -> i16 {
+        self.cg
+    }
+
+    /// Returns an array containing the `[Y, Co, Cg]` components.
+    pub const fn components(&self) -> [i16; 3] {
+        [self.y as i16, self.co, self.cg]
+    }
+}
+
+
+impl fmt::Display for YCoCgR {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// YCoCgR conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<Rgb> for YCoCgR {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "YCoCgR::from<Rgb>");
+        let _enter = span.enter();
+
+        let [r, g, b] = rgb.octets().map(i16::from);
+        let co = r - b;
+        let t = b + (co >> 1);
+        let cg = g - t;
+        let y = t + (cg >> 1);
+        YCoCgR { y: y as u8, co, cg }
+    }
+}
+
+impl From<Color> for YCoCgR {
+    fn from(color: Color) -> Self {
+        let span = span!(Level::DEBUG, "YCoCgR::from<Color>");
+        let _enter = span.enter();
+
+        YCoCgR::from(Rgb::from(color))
+    }
+}
+
+impl From<YCoCgR> for Rgb {
+    fn from(ycocg: YCoCgR) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<YCoCgR>");
+        let _enter = span.enter();
+
+        // Values not produced from an `Rgb` (such as deserialized ones) are
+        // clamped into range.
+        let t = ycocg.y as i16 - (ycocg.cg >> 1);
+        let g = ycocg.cg + t;
+        let b = t - (ycocg.co >> 1);
+        let r = b + ycocg.co;
+        let [r, g, b] = [r, g, b].map(|c| c.clamp(0, u8::MAX as i16) as u8);
+        Rgb::new(r, g, b)
+    }
+}
+
+impl From<YCoCgR> for Color {
+    fn from(ycocg: YCoCgR) -> Self {
+        let span = span!(Level::DEBUG, "Color::from<YCoCgR>");
+        let _enter = span.enter();
+
+        Color::from(Rgb::from(ycocg))
+    }
+}
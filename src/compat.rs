@@ -0,0 +1,874 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Compatibility deserialization for stored color data.
+//!
+//! The derived `Deserialize` implementations only accept the current
+//! serialized form of each color. The [`Compat`] wrapper accepts a wider set
+//! of representations, so that data written by older versions of this crate
+//! or by the [palette](https://crates.io/crates/palette) crate can still be
+//! read:
+//!
+//! | Type   | Accepted representations |
+//! | ------ | ------------------------ |
+//! | `Rgb`  | `{r, g, b}` or `{red, green, blue}` maps, `[r, g, b]` arrays, `"#rrggbb"` or `"#rgb"` hex codes, and packed `0xRRGGBB` integers. |
+//! | `Cmyk` | `{c, m, y, k}` or `{cyan, magenta, yellow, key}` maps, `[c, m, y, k]` arrays, and packed `0xCCMMYYKK` integers. |
+//! | `Hsl`  | `{h, s, l}` or `{hue, saturation, lightness}` maps, and `[h, s, l]` arrays. |
+//! | `Hsv`  | `{h, s, v}` or `{hue, saturation, value}` maps, and `[h, s, v]` arrays. |
+//! | `Xyz`  | `{x, y, z}` or `{X, Y, Z}` maps, and `[x, y, z]` arrays. |
+//! | `Color`| Any `Rgb` representation. |
+//!
+//! Integer channels of `Rgb` and `Cmyk` are read as octets, while floating
+//! point channels are read as ratios between 0 and 1, matching the output of
+//! palette's floating point `Srgb` type. Unknown fields, such as an `alpha`
+//! field, are ignored.
+//!
+//! For messier inputs, the [`Lenient`] wrapper additionally clamps
+//! out-of-range values, accepts percentages and unit suffixes, and records a
+//! [`Warning`] for every value it had to adjust. `Xyz` components are
+//! unbounded, so both wrappers read them as plain numbers, without rescaling
+//! or clamping.
+//!
+//! [`Compat`]: struct.Compat.html
+//! [`Lenient`]: struct.Lenient.html
+//! [`Warning`]: struct.Warning.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Cmyk;
+use crate::Color;
+use crate::Hsl;
+use crate::Hsv;
+use crate::Rgb;
+use crate::Xyz;
+
+// External library imports.
+use serde::de::Error;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+// Standard library imports.
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Compat
+////////////////////////////////////////////////////////////////////////////////
+/// A wrapper which deserializes a color from any of its accepted
+/// representations, and serializes it in the current representation.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::compat::Compat;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors: Vec<Compat<Rgb>> = serde_json::from_str(r##"[
+///     {"r": 255, "g": 0, "b": 0},
+///     {"red": 0.0, "green": 1.0, "blue": 0.0, "alpha": 0.5},
+///     "#0000ff"
+/// ]"##)?;
+///
+/// assert_eq!(colors[0].0, Rgb::new(255, 0, 0));
+/// assert_eq!(colors[1].0, Rgb::new(0, 255, 0));
+/// assert_eq!(colors[2].0, Rgb::new(0, 0, 255));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct Compat<T>(pub T);
+
+impl<T> Serialize for Compat<T> where T: Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// deserialize
+////////////////////////////////////////////////////////////////////////////////
+/// Deserializes a color from any of its accepted representations.
+///
+/// This is intended for use with serde's `deserialize_with` attribute.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Hsl;
+/// # use serde::Deserialize;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// #[derive(Deserialize)]
+/// struct Theme {
+///     #[serde(deserialize_with = "color::compat::deserialize")]
+///     accent: Hsl,
+/// }
+///
+/// let theme: Theme = serde_json::from_str(
+///     r#"{"accent": {"hue": 120.0, "saturation": 0.5, "lightness": 0.25}}"#)?;
+///
+/// assert_eq!(theme.accent, Hsl::new(120.0, 0.5, 0.25));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        Compat<T>: Deserialize<'de>,
+{
+    Compat::<T>::deserialize(deserializer).map(|compat| compat.0)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Channel
+////////////////////////////////////////////////////////////////////////////////
+/// An octet channel value, which may be stored as an integer octet or a
+/// floating point ratio.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Channel {
+    /// An integer octet.
+    Octet(u64),
+    /// A floating point ratio.
+    Ratio(f64),
+}
+
+impl Channel {
+    /// Returns the channel as an octet, or an error if it is out of range.
+    fn octet<E>(self) -> Result<u8, E> where E: Error {
+        match self {
+            Channel::Octet(v) if v <= u8::MAX as u64 => Ok(v as u8),
+            Channel::Octet(v) => Err(E::custom(
+                format!("channel value {} out of range", v))),
+            Channel::Ratio(v) if (0.0..=1.0).contains(&v) => {
+                Ok((v * u8::MAX as f64).round() as u8)
+            },
+            Channel::Ratio(v) => Err(E::custom(
+                format!("channel ratio {} out of range", v))),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rgb
+////////////////////////////////////////////////////////////////////////////////
+/// The accepted representations of an `Rgb` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RgbRepr {
+    /// A map of channels.
+    Fields {
+        #[serde(alias = "red")]
+        r: Channel,
+        #[serde(alias = "green")]
+        g: Channel,
+        #[serde(alias = "blue")]
+        b: Channel,
+    },
+    /// An array of channels.
+    Array([Channel; 3]),
+    /// A hex code.
+    Hex(String),
+    /// A packed integer.
+    Packed(u32),
+}
+
+impl<'de> Deserialize<'de> for Compat<Rgb> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let rgb = match RgbRepr::deserialize(deserializer)? {
+            RgbRepr::Fields { r, g, b } | RgbRepr::Array([r, g, b]) => {
+                Rgb::new(r.octet()?, g.octet()?, b.octet()?)
+            },
+            RgbRepr::Hex(hex) => Rgb::from_hex_code(&hex)
+                .map_err(|_| D::Error::custom(
+                    format!("invalid hex code {:?}", hex)))?,
+            RgbRepr::Packed(hex) if hex <= 0xFFFFFF => Rgb::from(hex),
+            RgbRepr::Packed(hex) => return Err(D::Error::custom(
+                format!("packed color {:#X} out of range", hex))),
+        };
+        Ok(Compat(rgb))
+    }
+}
+
+impl<'de> Deserialize<'de> for Compat<Color> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        Compat::<Rgb>::deserialize(deserializer)
+            .map(|compat| Compat(Color::from(compat.0)))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Cmyk
+////////////////////////////////////////////////////////////////////////////////
+/// The accepted representations of a `Cmyk` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CmykRepr {
+    /// A map of channels.
+    Fields {
+        #[serde(alias = "cyan")]
+        c: Channel,
+        #[serde(alias = "magenta")]
+        m: Channel,
+        #[serde(alias = "yellow")]
+        y: Channel,
+        #[serde(alias = "key", alias = "black")]
+        k: Channel,
+    },
+    /// An array of channels.
+    Array([Channel; 4]),
+    /// A packed integer.
+    Packed(u32),
+}
+
+impl<'de> Deserialize<'de> for Compat<Cmyk> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let cmyk = match CmykRepr::deserialize(deserializer)? {
+            CmykRepr::Fields { c, m, y, k } | CmykRepr::Array([c, m, y, k]) => {
+                Cmyk::new(c.octet()?, m.octet()?, y.octet()?, k.octet()?)
+            },
+            CmykRepr::Packed(hex) => Cmyk::from(hex),
+        };
+        Ok(Compat(cmyk))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Hsl
+////////////////////////////////////////////////////////////////////////////////
+/// The accepted representations of an `Hsl` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HslRepr {
+    /// A map of components.
+    Fields {
+        #[serde(alias = "hue")]
+        h: f32,
+        #[serde(alias = "saturation")]
+        s: f32,
+        #[serde(alias = "lightness")]
+        l: f32,
+    },
+    /// An array of components.
+    Array([f32; 3]),
+}
+
+impl<'de> Deserialize<'de> for Compat<Hsl> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let [h, s, l] = match HslRepr::deserialize(deserializer)? {
+            HslRepr::Fields { h, s, l } => [h, s, l],
+            HslRepr::Array(components) => components,
+        };
+        Ok(Compat(Hsl::new(finite(h)?, finite(s)?, finite(l)?)))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Hsv
+////////////////////////////////////////////////////////////////////////////////
+/// The accepted representations of an `Hsv` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HsvRepr {
+    /// A map of components.
+    Fields {
+        #[serde(alias = "hue")]
+        h: f32,
+        #[serde(alias = "saturation")]
+        s: f32,
+        #[serde(alias = "value")]
+        v: f32,
+    },
+    /// An array of components.
+    Array([f32; 3]),
+}
+
+impl<'de> Deserialize<'de> for Compat<Hsv> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let [h, s, v] = match HsvRepr::deserialize(deserializer)? {
+            HsvRepr::Fields { h, s, v } => [h, s, v],
+            HsvRepr::Array(components) => components,
+        };
+        Ok(Compat(Hsv::new(finite(h)?, finite(s)?, finite(v)?)))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Xyz
+////////////////////////////////////////////////////////////////////////////////
+/// The accepted representations of an `Xyz` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum XyzRepr {
+    /// A map of components.
+    Fields {
+        #[serde(alias = "X")]
+        x: f32,
+        #[serde(alias = "Y")]
+        y: f32,
+        #[serde(alias = "Z")]
+        z: f32,
+    },
+    /// An array of components.
+    Array([f32; 3]),
+}
+
+impl<'de> Deserialize<'de> for Compat<Xyz> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let [x, y, z] = match XyzRepr::deserialize(deserializer)? {
+            XyzRepr::Fields { x, y, z } => [x, y, z],
+            XyzRepr::Array(components) => components,
+        };
+        Ok(Compat(Xyz::from([finite(x)?, finite(y)?, finite(z)?])))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given component, or an error if it is not finite.
+fn finite<E>(value: f32) -> Result<f32, E> where E: Error {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(E::custom(format!("component {} is not finite", value)))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lenient
+////////////////////////////////////////////////////////////////////////////////
+/// A wrapper which deserializes a color leniently, adjusting values which
+/// would otherwise be rejected and recording a [`Warning`] for each.
+///
+/// In addition to the representations accepted by [`Compat`], each component
+/// may be given as a number or as a string with an optional unit suffix:
+///
+/// | Component        | Interpretation |
+/// | ---------------- | -------------- |
+/// | Octet channels   | Integers are octets, decimals up to 1 are ratios, and `%` values are percentages. Out of range values are clamped. |
+/// | Ratio components | Values up to 1 are ratios, values up to 100 or with a `%` suffix are percentages. Out of range values are clamped. |
+/// | Hue components   | Values are degrees, or may have a `deg`, `rad`, `turn`, or `%` suffix. Values are wrapped into [0, 360). |
+///
+/// [`Compat`]: struct.Compat.html
+/// [`Warning`]: struct.Warning.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Hsl;
+/// # use color::compat::Lenient;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let hsl: Lenient<Hsl> = serde_json::from_str(
+///     r#"{"hue": "0.5turn", "saturation": "40%", "lightness": 120}"#)?;
+///
+/// assert_eq!(hsl.value, Hsl::new(180.0, 0.4, 1.0));
+/// assert_eq!(hsl.warnings.len(), 1);
+/// assert_eq!(hsl.warnings[0].field, "l");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Lenient<T> {
+    /// The deserialized value.
+    pub value: T,
+    /// The adjustments made while deserializing the value.
+    pub warnings: Vec<Warning>,
+}
+
+impl<T> Lenient<T> {
+    /// Returns true if the value was deserialized without adjustment.
+    pub fn is_exact(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl<T> Serialize for Lenient<T> where T: Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Warning
+////////////////////////////////////////////////////////////////////////////////
+/// An adjustment made to a component during lenient deserialization.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Warning {
+    /// The name of the adjusted component.
+    pub field: &'static str,
+    /// The adjustment made.
+    pub adjustment: Adjustment,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self.adjustment {
+            Adjustment::Clamped { original } => write!(f,
+                "{} value {} was out of range and has been clamped",
+                self.field, original),
+            Adjustment::Rescaled { original } => write!(f,
+                "{} value {} was interpreted as a percentage",
+                self.field, original),
+            Adjustment::Wrapped { original } => write!(f,
+                "{} angle {} has been wrapped into [0, 360)",
+                self.field, original),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Adjustment
+////////////////////////////////////////////////////////////////////////////////
+/// The kind of adjustment recorded by a [`Warning`].
+///
+/// [`Warning`]: struct.Warning.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Adjustment {
+    /// The value was outside of the valid range and was clamped.
+    Clamped {
+        /// The original value.
+        original: f64,
+    },
+    /// The value was too large to be a ratio, and was interpreted as a
+    /// percentage without an explicit `%` suffix.
+    Rescaled {
+        /// The original value.
+        original: f64,
+    },
+    /// The angle was outside of [0, 360) and was wrapped.
+    Wrapped {
+        /// The original value.
+        original: f64,
+    },
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Value
+////////////////////////////////////////////////////////////////////////////////
+/// A leniently parsed component value.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Value {
+    /// An integer.
+    Integer(i64),
+    /// A decimal number.
+    Decimal(f64),
+    /// A number with an optional unit suffix.
+    Text(String),
+}
+
+/// The unit of a parsed component value.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Unit {
+    /// An integer with no unit.
+    Integer,
+    /// A decimal with no unit.
+    Decimal,
+    /// A percentage.
+    Percent,
+    /// An angle in degrees.
+    Degrees,
+    /// An angle in radians.
+    Radians,
+    /// An angle in turns.
+    Turns,
+}
+
+impl Value {
+    /// Returns the numeric value and unit, or an error message.
+    fn parse(self) -> Result<(f64, Unit), String> {
+        let (value, unit) = match self {
+            Value::Integer(v) => (v as f64, Unit::Integer),
+            Value::Decimal(v) => (v, Unit::Decimal),
+            Value::Text(text) => {
+                let text = text.trim();
+                let (number, unit) = [
+                    ("%",    Unit::Percent),
+                    ("deg",  Unit::Degrees),
+                    ("°",    Unit::Degrees),
+                    ("rad",  Unit::Radians),
+                    ("turn", Unit::Turns),
+                ]
+                    .iter()
+                    .find_map(|&(suffix, unit)| text
+                        .strip_suffix(suffix)
+                        .map(|number| (number, unit)))
+                    .unwrap_or((text, Unit::Decimal));
+                let number = number.trim();
+                let value = number.parse::<f64>()
+                    .map_err(|_| format!("invalid component {:?}", text))?;
+                let unit = match unit {
+                    Unit::Decimal if !number.contains(['.', 'e', 'E'])
+                        => Unit::Integer,
+                    _ => unit,
+                };
+                (value, unit)
+            },
+        };
+
+        if value.is_finite() {
+            Ok((value, unit))
+        } else {
+            Err(format!("component {} is not finite", value))
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Collector
+////////////////////////////////////////////////////////////////////////////////
+/// Converts lenient component values while collecting warnings.
+#[derive(Debug, Default)]
+struct Collector {
+    /// The warnings collected so far.
+    warnings: Vec<Warning>,
+}
+
+impl Collector {
+    /// Records a warning for the given field.
+    fn warn(&mut self, field: &'static str, adjustment: Adjustment) {
+        self.warnings.push(Warning { field, adjustment });
+    }
+
+    /// Returns the value clamped to the given range, recording a warning if
+    /// it was adjusted.
+    fn clamp(&mut self, field: &'static str, value: f64, max: f64) -> f64 {
+        if value < 0.0 || value > max {
+            self.warn(field, Adjustment::Clamped { original: value });
+        }
+        value.clamp(0.0, max)
+    }
+
+    /// Converts a value into an octet channel.
+    fn octet(&mut self, field: &'static str, value: Value)
+        -> Result<u8, String>
+    {
+        let max = u8::MAX as f64;
+        let octet = match value.parse()? {
+            (v, Unit::Percent) => self.clamp(field, v, 100.0) / 100.0 * max,
+            (v, Unit::Decimal) if (0.0..=1.0).contains(&v) => v * max,
+            (v, Unit::Decimal) | (v, Unit::Integer) => self.clamp(field, v, max),
+            (_, unit) => return Err(format!(
+                "unexpected unit {:?} for {}", unit, field)),
+        };
+        Ok(octet.round() as u8)
+    }
+
+    /// Converts a value into a ratio component.
+    fn ratio(&mut self, field: &'static str, value: Value)
+        -> Result<f32, String>
+    {
+        let ratio = match value.parse()? {
+            (v, Unit::Percent) => self.clamp(field, v, 100.0) / 100.0,
+            (v, Unit::Decimal) | (v, Unit::Integer) if v > 1.0 && v <= 100.0 => {
+                self.warn(field, Adjustment::Rescaled { original: v });
+                v / 100.0
+            },
+            (v, Unit::Decimal) | (v, Unit::Integer) => self.clamp(field, v, 1.0),
+            (_, unit) => return Err(format!(
+                "unexpected unit {:?} for {}", unit, field)),
+        };
+        Ok(ratio as f32)
+    }
+
+    /// Converts a value into an unbounded component, which is neither
+    /// rescaled nor clamped.
+    fn component(&mut self, field: &'static str, value: Value)
+        -> Result<f32, String>
+    {
+        match value.parse()? {
+            (v, Unit::Decimal) | (v, Unit::Integer) => {
+                let component = v as f32;
+                if component.is_finite() {
+                    Ok(component)
+                } else {
+                    Err(format!("component {} is not finite", v))
+                }
+            },
+            (_, unit) => Err(format!(
+                "unexpected unit {:?} for {}", unit, field)),
+        }
+    }
+
+    /// Converts a value into a hue in degrees.
+    fn hue(&mut self, field: &'static str, value: Value)
+        -> Result<f32, String>
+    {
+        let (original, unit) = value.parse()?;
+        let degrees = match unit {
+            Unit::Percent => original / 100.0 * 360.0,
+            Unit::Radians => original.to_degrees(),
+            Unit::Turns   => original * 360.0,
+            _             => original,
+        };
+        if !(0.0..360.0).contains(&degrees) {
+            self.warn(field, Adjustment::Wrapped { original });
+        }
+        Ok(degrees.rem_euclid(360.0) as f32)
+    }
+}
+
+/// Deserializes a lenient representation and converts it using a collector.
+fn lenient<'de, D, R, T, F>(deserializer: D, convert: F)
+    -> Result<Lenient<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        R: Deserialize<'de>,
+        F: FnOnce(R, &mut Collector) -> Result<T, String>,
+{
+    let repr = R::deserialize(deserializer)?;
+    let mut collector = Collector::default();
+    let value = convert(repr, &mut collector).map_err(D::Error::custom)?;
+    Ok(Lenient { value, warnings: collector.warnings })
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lenient representations
+////////////////////////////////////////////////////////////////////////////////
+/// The lenient representations of an `Rgb` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LenientRgbRepr {
+    /// A map of channels.
+    Fields {
+        #[serde(alias = "red", alias = "R")]
+        r: Value,
+        #[serde(alias = "green", alias = "G")]
+        g: Value,
+        #[serde(alias = "blue", alias = "B")]
+        b: Value,
+    },
+    /// An array of channels.
+    Array([Value; 3]),
+    /// A hex code.
+    Hex(String),
+}
+
+impl<'de> Deserialize<'de> for Lenient<Rgb> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        lenient(deserializer, |repr, c: &mut Collector| match repr {
+            LenientRgbRepr::Fields { r, g, b }
+                | LenientRgbRepr::Array([r, g, b]) => Ok(Rgb::new(
+                    c.octet("r", r)?,
+                    c.octet("g", g)?,
+                    c.octet("b", b)?)),
+            LenientRgbRepr::Hex(hex) => {
+                let hex = hex.trim();
+                let hex = if hex.starts_with('#') {
+                    hex.to_owned()
+                } else {
+                    format!("#{}", hex)
+                };
+                Rgb::from_hex_code(&hex)
+                    .map_err(|_| format!("invalid hex code {:?}", hex))
+            },
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Lenient<Color> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        Lenient::<Rgb>::deserialize(deserializer).map(|rgb| Lenient {
+            value: Color::from(rgb.value),
+            warnings: rgb.warnings,
+        })
+    }
+}
+
+/// The lenient representations of a `Cmyk` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LenientCmykRepr {
+    /// A map of channels.
+    Fields {
+        #[serde(alias = "cyan", alias = "C")]
+        c: Value,
+        #[serde(alias = "magenta", alias = "M")]
+        m: Value,
+        #[serde(alias = "yellow", alias = "Y")]
+        y: Value,
+        #[serde(alias = "key", alias = "black", alias = "K")]
+        k: Value,
+    },
+    /// An array of channels.
+    Array([Value; 4]),
+}
+
+impl<'de> Deserialize<'de> for Lenient<Cmyk> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        lenient(deserializer, |repr, c: &mut Collector| {
+            let (cv, mv, yv, kv) = match repr {
+                LenientCmykRepr::Fields { c, m, y, k } => (c, m, y, k),
+                LenientCmykRepr::Array([c, m, y, k]) => (c, m, y, k),
+            };
+            Ok(Cmyk::new(
+                c.octet("c", cv)?,
+                c.octet("m", mv)?,
+                c.octet("y", yv)?,
+                c.octet("k", kv)?))
+        })
+    }
+}
+
+/// The lenient representations of an `Hsl` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LenientHslRepr {
+    /// A map of components.
+    Fields {
+        #[serde(alias = "hue", alias = "H")]
+        h: Value,
+        #[serde(alias = "saturation", alias = "S")]
+        s: Value,
+        #[serde(alias = "lightness", alias = "L")]
+        l: Value,
+    },
+    /// An array of components.
+    Array([Value; 3]),
+}
+
+impl<'de> Deserialize<'de> for Lenient<Hsl> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        lenient(deserializer, |repr, c: &mut Collector| {
+            let (h, s, l) = match repr {
+                LenientHslRepr::Fields { h, s, l } => (h, s, l),
+                LenientHslRepr::Array([h, s, l]) => (h, s, l),
+            };
+            Ok(Hsl::new(c.hue("h", h)?, c.ratio("s", s)?, c.ratio("l", l)?))
+        })
+    }
+}
+
+/// The lenient representations of an `Hsv` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LenientHsvRepr {
+    /// A map of components.
+    Fields {
+        #[serde(alias = "hue", alias = "H")]
+        h: Value,
+        #[serde(alias = "saturation", alias = "S")]
+        s: Value,
+        #[serde(alias = "value", alias = "brightness", alias = "V")]
+        v: Value,
+    },
+    /// An array of components.
+    Array([Value; 3]),
+}
+
+impl<'de> Deserialize<'de> for Lenient<Hsv> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        lenient(deserializer, |repr, c: &mut Collector| {
+            let (h, s, v) = match repr {
+                LenientHsvRepr::Fields { h, s, v } => (h, s, v),
+                LenientHsvRepr::Array([h, s, v]) => (h, s, v),
+            };
+            Ok(Hsv::new(c.hue("h", h)?, c.ratio("s", s)?, c.ratio("v", v)?))
+        })
+    }
+}
+
+/// The lenient representations of an `Xyz` color.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LenientXyzRepr {
+    /// A map of components.
+    Fields {
+        #[serde(alias = "X")]
+        x: Value,
+        #[serde(alias = "Y")]
+        y: Value,
+        #[serde(alias = "Z")]
+        z: Value,
+    },
+    /// An array of components.
+    Array([Value; 3]),
+}
+
+impl<'de> Deserialize<'de> for Lenient<Xyz> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        lenient(deserializer, |repr, c: &mut Collector| {
+            let (x, y, z) = match repr {
+                LenientXyzRepr::Fields { x, y, z } => (x, y, z),
+                LenientXyzRepr::Array([x, y, z]) => (x, y, z),
+            };
+            Ok(Xyz::from([
+                c.component("x", x)?,
+                c.component("y", y)?,
+                c.component("z", z)?,
+            ]))
+        })
+    }
+}
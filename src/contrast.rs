@@ -0,0 +1,203 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Contrast measurement and solving.
+//!
+//! Contrast is measured using the [WCAG 2] definition of relative luminance
+//! and contrast ratio.
+//!
+//! [WCAG 2]: https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Rgb;
+use crate::utility::clamped;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The minimum contrast ratio for large text at WCAG level AA.
+pub const AA_LARGE: f32 = 3.0;
+
+/// The minimum contrast ratio for normal text at WCAG level AA.
+pub const AA: f32 = 4.5;
+
+/// The minimum contrast ratio for normal text at WCAG level AAA.
+pub const AAA: f32 = 7.0;
+
+/// The number of bisection steps used by the solvers. This is sufficient to
+/// resolve an opacity well below the precision of an 8-bit channel.
+const SOLVER_ITERATIONS: usize = 24;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// relative_luminance
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the relative luminance of the given color, between 0 and 1.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::contrast::relative_luminance;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(relative_luminance(Rgb::new(0, 0, 0)), 0.0);
+/// assert_eq!(relative_luminance(Rgb::new(255, 255, 255)), 1.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn relative_luminance<C>(color: C) -> f32 where C: Into<Rgb> {
+    let [r, g, b] = color.into().linear_ratios();
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// contrast_ratio
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the contrast ratio between the given colors, between 1 and 21.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::contrast::contrast_ratio;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let black = Rgb::new(0, 0, 0);
+/// let white = Rgb::new(255, 255, 255);
+///
+/// assert!(contrast_ratio(black, white) > 20.99);
+/// assert_eq!(contrast_ratio(black, white), contrast_ratio(white, black));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn contrast_ratio<C, D>(a: C, b: D) -> f32
+    where
+        C: Into<Rgb>,
+        D: Into<Rgb>,
+{
+    let la = relative_luminance(a);
+    let lb = relative_luminance(b);
+    let (hi, lo) = if la > lb { (la, lb) } else { (lb, la) };
+    (hi + 0.05) / (lo + 0.05)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// composite
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the color produced by drawing `overlay` over `background` with the
+/// given opacity, which is clamped between 0 and 1.
+///
+/// Compositing is performed on the encoded channel values, matching the
+/// behavior of typical renderers.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::contrast::composite;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let black = Rgb::new(0, 0, 0);
+/// let white = Rgb::new(255, 255, 255);
+///
+/// assert_eq!(composite(white, black, 0.5), Rgb::new(128, 128, 128));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn composite(background: Rgb, overlay: Rgb, opacity: f32) -> Rgb {
+    let a = clamped(opacity, 0.0, 1.0);
+    let channel = |b: u8, o: u8| {
+        (b as f32 * (1.0 - a) + o as f32 * a).round() as u8
+    };
+    Rgb::new(
+        channel(background.r, overlay.r),
+        channel(background.g, overlay.g),
+        channel(background.b, overlay.b))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// solve_scrim_opacity
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the minimum opacity of a `scrim` color composited over
+/// `background` for which `text` reaches the `target` contrast ratio.
+///
+/// Returns `None` if the target cannot be reached even with an opaque scrim.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::contrast::AA;
+/// # use color::contrast::composite;
+/// # use color::contrast::contrast_ratio;
+/// # use color::contrast::solve_scrim_opacity;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let text = Rgb::new(255, 255, 255);
+/// let background = Rgb::new(200, 200, 200);
+/// let scrim = Rgb::new(0, 0, 0);
+///
+/// let opacity = solve_scrim_opacity(text, background, scrim, AA).unwrap();
+/// let backdrop = composite(background, scrim, opacity);
+///
+/// assert!(contrast_ratio(text, backdrop) >= AA);
+/// assert!(opacity < 1.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn solve_scrim_opacity(text: Rgb, background: Rgb, scrim: Rgb, target: f32)
+    -> Option<f32>
+{
+    let reaches = |opacity: f32| {
+        contrast_ratio(text, composite(background, scrim, opacity)) >= target
+    };
+
+    if reaches(0.0) { return Some(0.0); }
+    if !reaches(1.0) { return None; }
+
+    // Contrast is monotonic in opacity when the scrim lies on the far side of
+    // the background from the text, so bisect for the smallest opacity.
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..SOLVER_ITERATIONS {
+        let mid = (lo + hi) / 2.0;
+        if reaches(mid) { hi = mid; } else { lo = mid; }
+    }
+    Some(hi)
+}
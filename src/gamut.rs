@@ -0,0 +1,375 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Gamut checking.
+//!
+//! The unbounded color spaces [`Xyz`], [`Lab`], [`Lch`], [`Oklab`], and
+//! [`Oklch`] can represent colors which no [`Rgb`] color can display. Each
+//! provides `in_gamut` to check whether a color fits within an [`RgbSpace`],
+//! `is_displayable` to check whether it fits within sRGB, and `checked_rgb`
+//! to convert it to [`Rgb`] while reporting any clipping as an
+//! [`OutOfGamut`] error instead of silently clamping.
+//!
+//! Out of gamut colors can be brought into sRGB without flattening saturated
+//! regions using a [`GamutCompression`].
+//!
+//! [`Xyz`]: ../struct.Xyz.html
+//! [`Lab`]: ../struct.Lab.html
+//! [`Lch`]: ../struct.Lch.html
+//! [`Oklab`]: ../struct.Oklab.html
+//! [`Oklch`]: ../struct.Oklch.html
+//! [`Rgb`]: ../struct.Rgb.html
+//! [`RgbSpace`]: ../rgb_space/trait.RgbSpace.html
+//! [`OutOfGamut`]: struct.OutOfGamut.html
+//! [`GamutCompression`]: struct.GamutCompression.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::oklch::gamut_map;
+use crate::illuminant::AdaptationMethod;
+use crate::illuminant::Illuminant;
+use crate::Oklch;
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::utility::matrix_transform;
+use crate::Xyz;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::error::Error;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The distance outside of [0, 1] a linear RGB ratio may fall and still be
+/// considered in gamut, allowing for rounding error in conversions.
+pub const GAMUT_TOLERANCE: f32 = 1e-4;
+
+/// An upper bound on the `Oklch` chroma of the supported RGB spaces.
+const MAX_CHROMA: f32 = 0.5;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// OutOfGamut
+////////////////////////////////////////////////////////////////////////////////
+/// An error returned when a color is converted to [`Rgb`] but lies outside
+/// of the sRGB gamut.
+///
+/// The error holds the clamped color which the conversion would otherwise
+/// have returned, so it can still be used after reporting the clipping.
+///
+/// [`Rgb`]: ../struct.Rgb.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OutOfGamut {
+    /// The color with its channels clamped to the sRGB gamut.
+    clamped: Rgb,
+    /// The largest distance of a linear channel ratio outside of [0, 1].
+    excess: f32,
+}
+
+impl OutOfGamut {
+    /// Returns the color with its channels clamped to the sRGB gamut.
+    pub fn clamped(&self) -> Rgb {
+        self.clamped
+    }
+
+    /// Returns the largest distance of a linear channel ratio outside of
+    /// [0, 1], which measures how far out of gamut the color is.
+    pub fn excess(&self) -> f32 {
+        self.excess
+    }
+}
+
+impl fmt::Display for OutOfGamut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "color is outside of the sRGB gamut by {}", self.excess)
+    }
+}
+
+impl Error for OutOfGamut {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// in_gamut
+////////////////////////////////////////////////////////////////////////////////
+/// Returns true if the given [`Xyz`] color, relative to the D65 white point,
+/// lies within the gamut of the given [`RgbSpace`]. Colors are adapted to
+/// the white point of the space first if it is not D65.
+///
+/// [`Xyz`]: ../struct.Xyz.html
+/// [`RgbSpace`]: ../rgb_space/trait.RgbSpace.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::Xyz;
+/// # use color::gamut::in_gamut;
+/// # use color::rgb_space::DisplayP3;
+/// # use color::rgb_space::Srgb;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let red = Xyz::from(Rgb::new(255, 0, 0));
+///
+/// assert!(in_gamut::<Srgb>(red));
+/// assert!(in_gamut::<DisplayP3>(red));
+///
+/// // A saturated green outside of sRGB, but within Display P3.
+/// let green = Xyz { x: 0.30, y: 0.65, z: 0.08 };
+///
+/// assert!(!in_gamut::<Srgb>(green));
+/// assert!(in_gamut::<DisplayP3>(green));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn in_gamut<S>(xyz: Xyz) -> bool where S: RgbSpace {
+    let white = S::WHITE_POINT;
+    let xyz = if white == Illuminant::D65 {
+        xyz
+    } else {
+        xyz.adapt(Illuminant::D65, white, AdaptationMethod::Bradford)
+    };
+    linear_in_gamut(matrix_transform(S::LINEAR_FROM_XYZ, xyz.components()))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GamutCompression
+////////////////////////////////////////////////////////////////////////////////
+/// A soft gamut compression, which maps colors into the sRGB gamut while
+/// preserving their [`Oklch`] lightness and hue.
+///
+/// Hard clipping maps every out of gamut chroma to the gamut boundary, so
+/// gradients through saturated regions of wide gamut content flatten into
+/// bands of identical color. Soft compression leaves chroma below the
+/// `threshold` fraction of the sRGB boundary unchanged, and smoothly
+/// compresses the rest of the range, up to `limit` times the boundary, into
+/// the outer portion of the gamut, so distinct colors remain distinct.
+///
+/// [`Oklch`]: ../struct.Oklch.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Oklch;
+/// # use color::gamut::GamutCompression;
+/// # use color::rgb_space::DisplayP3;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let compression = GamutCompression::default();
+///
+/// // Two Display P3 greens which clip to the same sRGB color.
+/// let a = Oklch::new(0.85, 0.26, 145.0);
+/// let b = Oklch::new(0.85, 0.30, 145.0);
+///
+/// let a = compression.compress_from::<DisplayP3, _>(a);
+/// let b = compression.compress_from::<DisplayP3, _>(b);
+///
+/// assert!(a.is_displayable() && b.is_displayable());
+/// assert!(a.c < b.c);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GamutCompression {
+    /// The fraction of the sRGB chroma boundary below which colors are left
+    /// unchanged.
+    threshold: f32,
+    /// The multiple of the sRGB chroma boundary which is compressed onto the
+    /// boundary.
+    limit: f32,
+}
+
+impl GamutCompression {
+    /// Constructs a new `GamutCompression` from the fraction of the sRGB
+    /// chroma boundary below which colors are unchanged, and the multiple of
+    /// the boundary which is compressed onto it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not within (0, 1) or `limit` is not greater
+    /// than 1.
+    pub fn new(threshold: f32, limit: f32) -> Self {
+        assert!(threshold > 0.0 && threshold < 1.0,
+            "gamut compression threshold must be within (0, 1)");
+        assert!(limit > 1.0, "gamut compression limit must be greater than 1");
+        GamutCompression { threshold, limit }
+    }
+
+    /// Returns the fraction of the sRGB chroma boundary below which colors
+    /// are left unchanged.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Returns the multiple of the sRGB chroma boundary which is compressed
+    /// onto the boundary.
+    pub fn limit(&self) -> f32 {
+        self.limit
+    }
+
+    /// Returns the given color with its chroma compressed into the sRGB
+    /// gamut. Chroma beyond the `limit` is clipped to the boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::gamut::GamutCompression;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let compression = GamutCompression::new(0.75, 1.5);
+    ///
+    /// // Muted colors are unchanged.
+    /// let muted = Oklch::new(0.6, 0.02, 30.0);
+    /// assert_eq!(compression.compress(muted), muted);
+    ///
+    /// let vivid = compression.compress(Oklch::new(0.6, 0.4, 30.0));
+    /// assert!(vivid.is_displayable());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn compress<C>(&self, color: C) -> Oklch where C: Into<Oklch> {
+        let oklch = color.into();
+        self.compress_chroma(oklch, self.limit)
+    }
+
+    /// Returns the given color with its chroma compressed into the sRGB
+    /// gamut, using the chroma boundary of the source [`RgbSpace`] at the
+    /// color's lightness and hue as the limit. This compresses the whole
+    /// source gamut into sRGB without clipping.
+    ///
+    /// [`RgbSpace`]: ../rgb_space/trait.RgbSpace.html
+    pub fn compress_from<S, C>(&self, color: C) -> Oklch
+        where
+            S: RgbSpace,
+            C: Into<Oklch>,
+    {
+        let oklch = color.into();
+        let boundary = max_chroma::<Srgb>(oklch);
+        let limit = if boundary > 0.0 {
+            (max_chroma::<S>(oklch) / boundary).max(self.limit)
+        } else {
+            self.limit
+        };
+        self.compress_chroma(oklch, limit)
+    }
+
+    /// Converts the given color to [`Rgb`], compressing its chroma into the
+    /// sRGB gamut.
+    ///
+    /// [`Rgb`]: ../struct.Rgb.html
+    pub fn to_rgb<C>(&self, color: C) -> Rgb where C: Into<Oklch> {
+        gamut_map(self.compress(color))
+    }
+
+    /// Returns the given color with its chroma compressed using the given
+    /// limit.
+    fn compress_chroma(&self, oklch: Oklch, limit: f32) -> Oklch {
+        let boundary = max_chroma::<Srgb>(oklch);
+        let t = self.threshold;
+        let d = if boundary > 0.0 { oklch.c / boundary } else { 1.0 };
+        if d <= t { return oklch; }
+
+        // Compress [t, limit] onto [t, 1] with a curve which is continuous
+        // and has a unit slope at the threshold.
+        let scale = (limit - t) * (1.0 - t) / (limit - 1.0);
+        let compressed = (t + (d - t) / (1.0 + (d - t) / scale)).min(1.0);
+        Oklch { c: compressed * boundary, ..oklch }
+    }
+}
+
+impl Default for GamutCompression {
+    fn default() -> Self {
+        GamutCompression { threshold: 0.8, limit: 1.5 }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns true if the given linear RGB ratios are within [0, 1], allowing
+/// for rounding error.
+pub(in crate) fn linear_in_gamut(linear: [f32; 3]) -> bool {
+    excess(linear) <= GAMUT_TOLERANCE
+}
+
+/// Returns the largest distance of the given linear RGB ratios outside of
+/// [0, 1].
+fn excess(linear: [f32; 3]) -> f32 {
+    linear.iter().fold(0.0f32, |e, &v| e.max(-v).max(v - 1.0))
+}
+
+/// Returns the given color converted to `Rgb`, or an [`OutOfGamut`] error
+/// holding the converted color if its linear sRGB ratios are out of gamut.
+///
+/// [`OutOfGamut`]: struct.OutOfGamut.html
+pub(in crate) fn checked_rgb<C>(color: C, linear: [f32; 3])
+    -> Result<Rgb, OutOfGamut>
+    where C: Into<Rgb>
+{
+    let rgb = color.into();
+    if linear_in_gamut(linear) {
+        Ok(rgb)
+    } else {
+        Err(OutOfGamut { clamped: rgb, excess: excess(linear) })
+    }
+}
+
+/// Returns the linear sRGB ratios of the given [`Xyz`] components.
+///
+/// [`Xyz`]: ../struct.Xyz.html
+pub(in crate) fn linear_srgb_from_xyz(xyz: [f32; 3]) -> [f32; 3] {
+    matrix_transform(Srgb::LINEAR_FROM_XYZ, xyz)
+}
+
+/// Returns the largest chroma at the lightness and hue of the given color
+/// which lies within the gamut of the given [`RgbSpace`].
+///
+/// [`RgbSpace`]: ../rgb_space/trait.RgbSpace.html
+pub(in crate) fn max_chroma<S>(oklch: Oklch) -> f32 where S: RgbSpace {
+    let in_gamut = |c: f32| Oklch { c, ..oklch }.in_gamut::<S>();
+    let (mut low, mut high) = (0.0, MAX_CHROMA);
+    if !in_gamut(low) { return 0.0; }
+    if in_gamut(high) { return high; }
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if in_gamut(mid) { low = mid; } else { high = mid; }
+    }
+    low
+}
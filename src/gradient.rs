@@ -0,0 +1,1586 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Color gradients.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::angle;
+use crate::Cmyk;
+use crate::Color;
+use crate::Hsl;
+use crate::Hsv;
+use crate::Lab;
+use crate::Lch;
+use crate::Luma;
+use crate::Oklab;
+use crate::Oklch;
+use crate::palette::Palette;
+use crate::render::preview;
+use crate::render::AnsiMode;
+use crate::render::PREVIEW_WIDTH;
+use crate::render::write_swatch_row;
+use crate::Rgb;
+use crate::Rgb48;
+use crate::Rgba;
+use crate::Rgba64;
+use crate::utility::clamped;
+use crate::utility::monotone_cerp_f32;
+use crate::Xyz;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::io;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Interpolate
+////////////////////////////////////////////////////////////////////////////////
+/// A color type which can be interpolated in its own color space.
+///
+/// In addition to linear interpolation, implementors expose their components
+/// as a vector of coordinates, which allows smooth curves to be fit through
+/// sequences of colors.
+///
+/// Colors with an alpha channel, such as [`Rgba`], are interpolated with
+/// their color channels premultiplied by the alpha, as CSS `color-mix()`
+/// does, so that a transparent color contributes no hue to the result.
+///
+/// `Xyz64` and `Lab64` don't implement `Interpolate`, since the coordinates
+/// are single precision and would discard the precision those types exist
+/// for; convert them to [`Xyz`] or [`Lab`] to interpolate. `YCoCgR` doesn't
+/// either, since it can only be constructed from an [`Rgb`] color and is a
+/// linear transform of it, so interpolating the `Rgb` colors gives the same
+/// result.
+///
+/// [`Lab`]: ../struct.Lab.html
+/// [`Rgb`]: ../struct.Rgb.html
+/// [`Rgba`]: ../struct.Rgba.html
+/// [`Xyz`]: ../struct.Xyz.html
+pub trait Interpolate: Copy {
+    /// The coordinate vector of the color.
+    type Coordinates: AsRef<[f32]> + AsMut<[f32]> + Copy;
+
+    /// The index of the coordinate holding a hue angle in degrees, if any.
+    /// Hue coordinates are interpolated along the shorter arc between
+    /// angles.
+    const HUE_INDEX: Option<usize> = None;
+
+    /// The index of the coordinate holding the chroma paired with the hue
+    /// coordinate, if any. Colors with both a chroma and a hue coordinate can
+    /// be interpolated in [`InterpolationForm::Rectangular`] form.
+    ///
+    /// [`InterpolationForm::Rectangular`]: enum.InterpolationForm.html#variant.Rectangular
+    const CHROMA_INDEX: Option<usize> = None;
+
+    /// Returns the color located at the ratio given by `amount` between
+    /// `start` and `end`. `amount` is clamped between 0 and 1.
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self;
+
+    /// Returns the color located at the ratio given by `amount` between
+    /// `start` and `end`, interpolating in the given form. `amount` is clamped
+    /// between 0 and 1.
+    ///
+    /// The form is ignored unless the color has both a chroma and a hue
+    /// coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::gradient::Interpolate;
+    /// # use color::gradient::InterpolationForm;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let red = Oklch::new(0.6, 0.2, 30.0);
+    /// let cyan = Oklch::new(0.6, 0.2, 210.0);
+    ///
+    /// let polar = Oklch::interpolate_in(red, cyan, 0.5, InterpolationForm::Polar);
+    /// let rectangular = Oklch::interpolate_in(
+    ///     red, cyan, 0.5, InterpolationForm::Rectangular);
+    ///
+    /// assert_eq!(polar.c, 0.2);
+    /// assert!(rectangular.c < 1e-6);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    fn interpolate_in(
+        start: Self,
+        end: Self,
+        amount: f32,
+        form: InterpolationForm)
+        -> Self
+    {
+        if !form.is_rectangular::<Self>() {
+            return Self::interpolate(start, end, amount);
+        }
+        let amount = clamped(amount, 0.0, 1.0);
+        let s = to_form::<Self>(start.to_coordinates(), form);
+        let e = to_form::<Self>(end.to_coordinates(), form);
+        let mut coordinates = s;
+        for (i, value) in coordinates.as_mut().iter_mut().enumerate() {
+            *value += (e.as_ref()[i] - *value) * amount;
+        }
+        Self::from_coordinates(from_form::<Self>(coordinates, form))
+    }
+
+    /// Returns the mix of the colors `a` and `b` containing the proportion of
+    /// `a` given by `weight`, as the Sass `mix()` function and CSS
+    /// `color-mix()` do. `weight` is clamped between 0 and 1, and a weight of
+    /// 0.5 mixes the colors evenly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Interpolate;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let red = Rgb::new(255, 0, 0);
+    /// let blue = Rgb::new(0, 0, 255);
+    ///
+    /// assert_eq!(Rgb::mix(red, blue, 0.75), Rgb::new(191, 0, 63));
+    /// assert_eq!(Rgb::mix(red, blue, 1.0), red);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    fn mix(a: Self, b: Self, weight: f32) -> Self {
+        Self::interpolate(a, b, 1.0 - clamped(weight, 0.0, 1.0))
+    }
+
+    /// Returns the coordinates of the color.
+    fn to_coordinates(self) -> Self::Coordinates;
+
+    /// Constructs a color from the given coordinates, which are clamped to
+    /// the valid range of each component.
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self;
+}
+
+impl Interpolate for Rgb {
+    type Coordinates = [f32; 3];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        Rgb::linear_interpolate(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        [self.r as f32, self.g as f32, self.b as f32]
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        let [r, g, b] = coordinates;
+        Rgb::new(octet(r), octet(g), octet(b))
+    }
+}
+
+impl Interpolate for Rgba {
+    type Coordinates = [f32; 4];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        interpolate_coordinates(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        premultiply(
+            [self.r as f32, self.g as f32, self.b as f32, self.a as f32],
+            u8::MAX as f32)
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        let [r, g, b, a] = unpremultiply(coordinates, u8::MAX as f32);
+        Rgba::new(octet(r), octet(g), octet(b), octet(a))
+    }
+}
+
+impl Interpolate for Luma {
+    type Coordinates = [f32; 1];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        interpolate_coordinates(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        [self.y as f32]
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        Luma::new(octet(coordinates[0]))
+    }
+}
+
+impl Interpolate for Rgb48 {
+    type Coordinates = [f32; 3];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        interpolate_coordinates(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        [self.r as f32, self.g as f32, self.b as f32]
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        let [r, g, b] = coordinates;
+        Rgb48::new(hextet(r), hextet(g), hextet(b))
+    }
+}
+
+impl Interpolate for Rgba64 {
+    type Coordinates = [f32; 4];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        interpolate_coordinates(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        premultiply(
+            [self.r as f32, self.g as f32, self.b as f32, self.a as f32],
+            u16::MAX as f32)
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        let [r, g, b, a] = unpremultiply(coordinates, u16::MAX as f32);
+        Rgba64::new(hextet(r), hextet(g), hextet(b), hextet(a))
+    }
+}
+
+impl Interpolate for Cmyk {
+    type Coordinates = [f32; 4];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        Cmyk::linear_interpolate(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        [self.c as f32, self.m as f32, self.y as f32, self.k as f32]
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        let [c, m, y, k] = coordinates;
+        Cmyk::new(octet(c), octet(m), octet(y), octet(k))
+    }
+}
+
+impl Interpolate for Hsl {
+    type Coordinates = [f32; 3];
+
+    const HUE_INDEX: Option<usize> = Some(0);
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        let mut end = end;
+        end.h = start.h + angle::difference(start.h, end.h);
+        let mut color = Hsl::linear_interpolate(start, end, amount);
+        color.set_hue(color.h);
+        color
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        self.components()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        Hsl::from(coordinates)
+    }
+}
+
+impl Interpolate for Hsv {
+    type Coordinates = [f32; 3];
+
+    const HUE_INDEX: Option<usize> = Some(0);
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        let mut end = end;
+        end.h = start.h + angle::difference(start.h, end.h);
+        let mut color = Hsv::linear_interpolate(start, end, amount);
+        color.set_hue(color.h);
+        color
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        self.components()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        Hsv::from(coordinates)
+    }
+}
+
+impl Interpolate for Xyz {
+    type Coordinates = [f32; 3];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        Xyz::linear_interpolate(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        self.components()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        Xyz::from(coordinates)
+    }
+}
+
+impl Interpolate for Lab {
+    type Coordinates = [f32; 3];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        Lab::linear_interpolate(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        self.components()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        Lab::from(coordinates)
+    }
+}
+
+impl Interpolate for Oklab {
+    type Coordinates = [f32; 3];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        Oklab::linear_interpolate(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        self.components()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        Oklab::from(coordinates)
+    }
+}
+
+impl Interpolate for Lch {
+    type Coordinates = [f32; 3];
+
+    const HUE_INDEX: Option<usize> = Some(2);
+    const CHROMA_INDEX: Option<usize> = Some(1);
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        let mut end = end;
+        end.h = start.h + angle::difference(start.h, end.h);
+        let mut color = Lch::linear_interpolate(start, end, amount);
+        color.set_hue(color.h);
+        color
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        self.components()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        let [l, c, h] = coordinates;
+        Lch::new(l, c.max(0.0), h)
+    }
+}
+
+impl Interpolate for Oklch {
+    type Coordinates = [f32; 3];
+
+    const HUE_INDEX: Option<usize> = Some(2);
+    const CHROMA_INDEX: Option<usize> = Some(1);
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        let mut end = end;
+        end.h = start.h + angle::difference(start.h, end.h);
+        let mut color = Oklch::linear_interpolate(start, end, amount);
+        color.set_hue(color.h);
+        color
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        self.components()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        let [l, c, h] = coordinates;
+        Oklch::new(l, c.max(0.0), h)
+    }
+}
+
+impl Interpolate for Color {
+    type Coordinates = [f32; 3];
+
+    fn interpolate(start: Self, end: Self, amount: f32) -> Self {
+        Color::rgb_linear_interpolate(start, end, amount)
+    }
+
+    fn to_coordinates(self) -> Self::Coordinates {
+        Rgb::from(self).to_coordinates()
+    }
+
+    fn from_coordinates(coordinates: Self::Coordinates) -> Self {
+        Color::from(Rgb::from_coordinates(coordinates))
+    }
+}
+
+/// Converts an octet coordinate to a `u8`, rounding and clamping.
+fn octet(value: f32) -> u8 {
+    clamped(value.round(), 0.0, 255.0) as u8
+}
+
+/// Converts a 16-bit coordinate to a `u16`, rounding and clamping.
+fn hextet(value: f32) -> u16 {
+    clamped(value.round(), 0.0, 65535.0) as u16
+}
+
+/// Returns the color located at the ratio given by `amount` between `start`
+/// and `end`, interpolating each of their coordinates linearly.
+fn interpolate_coordinates<C>(start: C, end: C, amount: f32) -> C
+    where C: Interpolate
+{
+    let amount = clamped(amount, 0.0, 1.0);
+    let end = end.to_coordinates();
+    let mut coordinates = start.to_coordinates();
+    for (value, e) in coordinates.as_mut().iter_mut().zip(end.as_ref()) {
+        *value += (e - *value) * amount;
+    }
+    C::from_coordinates(coordinates)
+}
+
+/// Returns the given color channels and alpha with the color channels
+/// multiplied by the alpha, where `max` is the alpha of an opaque color.
+fn premultiply(channels: [f32; 4], max: f32) -> [f32; 4] {
+    let [r, g, b, a] = channels;
+    let alpha = a / max;
+    [r * alpha, g * alpha, b * alpha, a]
+}
+
+/// Returns the given premultiplied color channels and alpha with the color
+/// channels divided by the alpha, where `max` is the alpha of an opaque
+/// color. The color channels of a transparent color are 0.
+fn unpremultiply(channels: [f32; 4], max: f32) -> [f32; 4] {
+    let [r, g, b, a] = channels;
+    let a = clamped(a, 0.0, max);
+    if a == 0.0 { return [0.0; 4]; }
+    let alpha = a / max;
+    [r / alpha, g / alpha, b / alpha, a]
+}
+
+/// Mixes the given colors in the color space `S`.
+fn mix_in<S>(a: Rgb, b: Rgb, weight: f32) -> Rgb
+    where S: Interpolate + From<Rgb> + Into<Rgb>
+{
+    S::mix(S::from(a), S::from(b), weight).into()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Interpolation
+////////////////////////////////////////////////////////////////////////////////
+/// The method used to interpolate between the stops of a [`Gradient`].
+///
+/// [`Gradient`]: struct.Gradient.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Interpolation {
+    /// Adjacent stops are linearly interpolated using the color space's
+    /// [`Interpolate::interpolate`] function.
+    ///
+    /// [`Interpolate::interpolate`]: trait.Interpolate.html#tymethod.interpolate
+    #[default]
+    Linear,
+    /// A Catmull-Rom spline is fit through the stops. Each segment is a cubic
+    /// Hermite curve whose slopes are derived from the neighboring stops, so
+    /// the curve passes through every stop without corners.
+    CatmullRom,
+    /// A monotone cubic spline is fit through the stops. The slopes at each
+    /// stop are limited using the Fritsch-Carlson method, so each segment
+    /// stays between the coordinates of its two stops and never overshoots,
+    /// at the cost of flattening the curve at local extremes.
+    MonotoneCubic,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// InterpolationForm
+////////////////////////////////////////////////////////////////////////////////
+/// The coordinate form used to interpolate colors with chroma and hue
+/// components, such as [`Lch`] and [`Oklch`].
+///
+/// Interpolating the hue of a low-chroma color can swing through many
+/// unrelated hues, since the hue of a nearly neutral color is unstable.
+/// Rectangular interpolation avoids this by passing through the neutral axis
+/// instead.
+///
+/// [`Lch`]: ../struct.Lch.html
+/// [`Oklch`]: ../struct.Oklch.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InterpolationForm {
+    /// Chroma and hue are interpolated as separate coordinates, with the hue
+    /// following the shorter arc between angles.
+    #[default]
+    Polar,
+    /// Chroma and hue are converted to the equivalent rectangular
+    /// coordinates, as in [`Lab`] or [`Oklab`], before interpolating.
+    ///
+    /// [`Lab`]: ../struct.Lab.html
+    /// [`Oklab`]: ../struct.Oklab.html
+    Rectangular,
+}
+
+impl InterpolationForm {
+    /// Returns true if colors of type `C` are interpolated in rectangular form.
+    fn is_rectangular<C>(self) -> bool where C: Interpolate {
+        self == InterpolationForm::Rectangular
+            && C::HUE_INDEX.is_some()
+            && C::CHROMA_INDEX.is_some()
+    }
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// MixSpace
+////////////////////////////////////////////////////////////////////////////////
+/// The color space in which [`mix`] mixes colors.
+///
+/// [`mix`]: fn.mix.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MixSpace {
+    /// The encoded sRGB color space.
+    Srgb,
+    /// The HSL color model, with hues mixed along the shorter arc.
+    Hsl,
+    /// The HSV color model, with hues mixed along the shorter arc.
+    Hsv,
+    /// The CIE XYZ color space, which mixes light physically.
+    Xyz,
+    /// The CIE L*a*b* color space.
+    Lab,
+    /// The CIE LCh color space, with hues mixed along the shorter arc.
+    Lch,
+    /// The Oklab color space. This is the default of CSS `color-mix()`.
+    #[default]
+    Oklab,
+    /// The Oklch color space, with hues mixed along the shorter arc.
+    Oklch,
+}
+
+/// Returns the mix of the colors `a` and `b` containing the proportion of `a`
+/// given by `weight`, mixed in the given [`MixSpace`]. This is equivalent to
+/// the CSS `color-mix()` function. `weight` is clamped between 0 and 1, and a
+/// weight of 0.5 mixes the colors evenly.
+///
+/// To mix colors within their own color space, use [`Interpolate::mix`].
+///
+/// [`MixSpace`]: enum.MixSpace.html
+/// [`Interpolate::mix`]: trait.Interpolate.html#method.mix
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::gradient::MixSpace;
+/// # use color::gradient::mix;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let red = Rgb::new(255, 0, 0);
+/// let blue = Rgb::new(0, 0, 255);
+///
+/// assert_eq!(mix(red, blue, 0.5, MixSpace::Srgb), Rgb::new(127, 0, 127));
+/// assert_eq!(mix(red, blue, 0.5, MixSpace::Oklab), Rgb::new(140, 83, 162));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn mix<C, D>(a: C, b: D, weight: f32, space: MixSpace) -> Rgb
+    where
+        C: Into<Rgb>,
+        D: Into<Rgb>,
+{
+    let (a, b) = (a.into(), b.into());
+    match space {
+        MixSpace::Srgb  => Rgb::mix(a, b, weight),
+        MixSpace::Hsl   => mix_in::<Hsl>(a, b, weight),
+        MixSpace::Hsv   => mix_in::<Hsv>(a, b, weight),
+        MixSpace::Xyz   => mix_in::<Xyz>(a, b, weight),
+        MixSpace::Lab   => mix_in::<Lab>(a, b, weight),
+        MixSpace::Lch   => mix_in::<Lch>(a, b, weight),
+        MixSpace::Oklab => mix_in::<Oklab>(a, b, weight),
+        MixSpace::Oklch => mix_in::<Oklch>(a, b, weight),
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Stop
+////////////////////////////////////////////////////////////////////////////////
+/// A color placed at a fixed position along a [`Gradient`].
+///
+/// [`Gradient`]: struct.Gradient.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stop<C> {
+    /// The position of the stop, between 0 and 1.
+    pub position: f32,
+    /// The color of the stop.
+    pub color: C,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Gradient
+////////////////////////////////////////////////////////////////////////////////
+/// A sequence of color stops which are interpolated to produce a continuous
+/// range of colors over the interval [0, 1].
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GradientRepr<C>"))]
+pub struct Gradient<C> {
+    /// The color stops, ordered by position.
+    stops: Vec<Stop<C>>,
+    /// The interpolation method used between stops.
+    #[cfg_attr(feature = "serde", serde(default))]
+    interpolation: Interpolation,
+    /// The coordinate form used to interpolate chroma and hue.
+    #[cfg_attr(feature = "serde", serde(default))]
+    form: InterpolationForm,
+    /// Whether the gradient wraps around from its end back to its start.
+    #[cfg_attr(feature = "serde", serde(default))]
+    cyclic: bool,
+}
+
+impl<C> Gradient<C> where C: Interpolate {
+    /// Constructs a new `Gradient` from `start` to `end`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(255, 255, 255));
+    ///
+    /// assert_eq!(gradient.color(0.5), Rgb::new(127, 127, 127));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn new(start: C, end: C) -> Self {
+        Gradient {
+            stops: vec![
+                Stop { position: 0.0, color: start },
+                Stop { position: 1.0, color: end },
+            ],
+            interpolation: Interpolation::default(),
+            form: InterpolationForm::default(),
+            cyclic: false,
+        }
+    }
+
+    /// Constructs a new `Gradient` with the given colors evenly spaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `colors` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::from_colors(vec![
+    ///     Rgb::new(255, 0, 0),
+    ///     Rgb::new(0, 255, 0),
+    ///     Rgb::new(0, 0, 255),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.color(0.5), Rgb::new(0, 255, 0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_colors<I>(colors: I) -> Self where I: IntoIterator<Item=C> {
+        let colors: Vec<C> = colors.into_iter().collect();
+        assert!(!colors.is_empty());
+
+        let last = (colors.len() - 1).max(1) as f32;
+        Gradient {
+            stops: colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| Stop { position: i as f32 / last, color })
+                .collect(),
+            interpolation: Interpolation::default(),
+            form: InterpolationForm::default(),
+            cyclic: false,
+        }
+    }
+
+    /// Constructs a new cyclic `Gradient` with the given colors evenly spaced
+    /// around it. The last color blends back into the first, so no color is
+    /// repeated at the ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `colors` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Hsv;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::cyclic_from_colors(vec![
+    ///     Hsv::new(0.0, 1.0, 1.0),
+    ///     Hsv::new(120.0, 1.0, 1.0),
+    ///     Hsv::new(240.0, 1.0, 1.0),
+    /// ]);
+    ///
+    /// assert_eq!(gradient.color(5.0 / 6.0).hue(), 300.0);
+    /// assert_eq!(gradient.color(1.5).hue(), 180.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn cyclic_from_colors<I>(colors: I) -> Self
+        where I: IntoIterator<Item=C>
+    {
+        let colors: Vec<C> = colors.into_iter().collect();
+        assert!(!colors.is_empty());
+
+        let count = colors.len() as f32;
+        Gradient {
+            stops: colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| Stop { position: i as f32 / count, color })
+                .collect(),
+            interpolation: Interpolation::default(),
+            form: InterpolationForm::default(),
+            cyclic: true,
+        }
+    }
+
+    /// Inserts a color stop at the given position, which is clamped between
+    /// 0 and 1. A stop inserted at the same position as an existing stop is
+    /// placed after it, producing a hard transition.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(0, 0, 0));
+    /// gradient.add_stop(0.5, Rgb::new(255, 255, 255));
+    ///
+    /// assert_eq!(gradient.color(0.5), Rgb::new(255, 255, 255));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn add_stop(&mut self, position: f32, color: C) {
+        let position = clamped(position, 0.0, 1.0);
+        let index = self.stops
+            .iter()
+            .position(|stop| stop.position > position)
+            .unwrap_or(self.stops.len());
+        self.stops.insert(index, Stop { position, color });
+    }
+
+    /// Returns the gradient with the given interpolation method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # use color::gradient::Interpolation;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::from_colors(vec![
+    ///     Rgb::new(0, 0, 0),
+    ///     Rgb::new(40, 40, 40),
+    ///     Rgb::new(200, 200, 200),
+    /// ]).with_interpolation(Interpolation::CatmullRom);
+    ///
+    /// assert_eq!(gradient.color(0.5), Rgb::new(40, 40, 40));
+    /// assert_eq!(gradient.color(0.75), Rgb::new(113, 113, 113));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the interpolation method used between stops.
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Returns the interpolation method used between stops.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Returns the gradient with the given coordinate form for interpolating
+    /// chroma and hue.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Lch;
+    /// # use color::gradient::Gradient;
+    /// # use color::gradient::InterpolationForm;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::new(
+    ///         Lch::new(50.0, 40.0, 0.0),
+    ///         Lch::new(50.0, 40.0, 180.0))
+    ///     .with_form(InterpolationForm::Rectangular);
+    ///
+    /// assert!(gradient.color(0.5).c < 1e-3);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn with_form(mut self, form: InterpolationForm) -> Self {
+        self.form = form;
+        self
+    }
+
+    /// Sets the coordinate form for interpolating chroma and hue.
+    pub fn set_form(&mut self, form: InterpolationForm) {
+        self.form = form;
+    }
+
+    /// Returns the coordinate form for interpolating chroma and hue.
+    pub fn form(&self) -> InterpolationForm {
+        self.form
+    }
+
+    /// Returns the gradient with the given cyclic behavior. A cyclic gradient
+    /// wraps positions into the interval [0, 1) and blends from its last stop
+    /// back into its first, making it suitable for periodic data such as
+    /// angles, phases, or times of day.
+    pub fn with_cyclic(mut self, cyclic: bool) -> Self {
+        self.cyclic = cyclic;
+        self
+    }
+
+    /// Sets whether the gradient is cyclic.
+    pub fn set_cyclic(&mut self, cyclic: bool) {
+        self.cyclic = cyclic;
+    }
+
+    /// Returns true if the gradient is cyclic.
+    pub fn is_cyclic(&self) -> bool {
+        self.cyclic
+    }
+
+    /// Returns the color stops of the gradient, ordered by position.
+    pub fn stops(&self) -> &[Stop<C>] {
+        &self.stops[..]
+    }
+
+    /// Returns the color located at the given position along the gradient.
+    /// The position is clamped between 0 and 1, or wrapped into that interval
+    /// if the gradient is cyclic. A NaN position is treated as 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::new(Rgb::new(0, 100, 0), Rgb::new(200, 100, 0));
+    ///
+    /// assert_eq!(gradient.color(0.25), Rgb::new(50, 100, 0));
+    /// assert_eq!(gradient.color(2.0), Rgb::new(200, 100, 0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn color(&self, position: f32) -> C {
+        let position = if position.is_nan() {
+            0.0
+        } else if self.cyclic {
+            position.rem_euclid(1.0)
+        } else {
+            clamped(position, 0.0, 1.0)
+        };
+
+        let first = self.stops[0];
+        let last = self.stops[self.stops.len() - 1];
+        let index = if position < first.position {
+            if !self.cyclic { return first.color; }
+            -1
+        } else if position >= last.position {
+            if !self.cyclic { return last.color; }
+            self.stops.len() as isize - 1
+        } else {
+            self.stops
+                .windows(2)
+                .position(|pair| position < pair[1].position)
+                .expect("position within stops") as isize
+        };
+
+        let a = self.stop_at(index);
+        let b = self.stop_at(index + 1);
+        let width = b.position - a.position;
+        if width <= 0.0 { return b.color; }
+        let amount = (position - a.position) / width;
+
+        match self.interpolation {
+            Interpolation::Linear
+                => C::interpolate_in(a.color, b.color, amount, self.form),
+            Interpolation::CatmullRom
+                => self.cubic_segment(index, amount, false),
+            Interpolation::MonotoneCubic
+                => self.cubic_segment(index, amount, true),
+        }
+    }
+
+    /// Returns the stop at the given index. For cyclic gradients, the index
+    /// wraps around and the position is offset by the number of wraps;
+    /// otherwise the index is clamped to the first or last stop.
+    fn stop_at(&self, index: isize) -> Stop<C> {
+        let count = self.stops.len() as isize;
+        if self.cyclic {
+            let mut stop = self.stops[index.rem_euclid(count) as usize];
+            stop.position += index.div_euclid(count) as f32;
+            stop
+        } else {
+            self.stops[index.clamp(0, count - 1) as usize]
+        }
+    }
+
+    /// Returns the color at `amount` along the Catmull-Rom or monotone cubic
+    /// segment starting at the given stop index.
+    fn cubic_segment(&self, index: isize, amount: f32, monotone: bool) -> C {
+        let prev = self.stop_at(index - 1);
+        let a = self.stop_at(index);
+        let b = self.stop_at(index + 1);
+        let next = self.stop_at(index + 2);
+
+        // Unwrap hue coordinates so that each is within a half turn of its
+        // neighbor, so the curve follows the shorter arcs between stops.
+        let form = self.form;
+        let coordinates = |stop: Stop<C>| {
+            to_form::<C>(stop.color.to_coordinates(), form)
+        };
+        let pa = coordinates(a);
+        let pb = unwrap_hue::<C>(pa, coordinates(b), form);
+        let pprev = unwrap_hue::<C>(pa, coordinates(prev), form);
+        let pnext = unwrap_hue::<C>(pb, coordinates(next), form);
+
+        let width = b.position - a.position;
+        let mut coordinates = pa;
+        for (i, value) in coordinates.as_mut().iter_mut().enumerate() {
+            let (prev_value, end) = (pprev.as_ref()[i], pb.as_ref()[i]);
+            let next_value = pnext.as_ref()[i];
+            if monotone {
+                let before_width = a.position - prev.position;
+                let after_width = next.position - b.position;
+                let before = slope(prev_value, *value, before_width);
+                let within = slope(*value, end, width);
+                let after = slope(end, next_value, after_width);
+                let start_slope = monotone_slope(
+                    before, before_width, within, width);
+                let end_slope = monotone_slope(
+                    within, width, after, after_width);
+                *value = monotone_cerp_f32(
+                    *value,
+                    end,
+                    start_slope * width,
+                    end_slope * width,
+                    amount);
+            } else {
+                let start_slope = slope(
+                    prev_value, end, b.position - prev.position);
+                let end_slope = slope(
+                    *value, next_value, next.position - a.position);
+                *value = hermite(
+                    *value,
+                    end,
+                    start_slope * width,
+                    end_slope * width,
+                    amount);
+            }
+        }
+        C::from_coordinates(from_form::<C>(coordinates, form))
+    }
+
+    /// Returns an iterator over `count` colors evenly spaced along the
+    /// gradient, including both ends. For cyclic gradients, the end is
+    /// excluded, as it is the same as the start.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(200, 0, 0));
+    ///
+    /// let mut steps = gradient.steps(3);
+    ///
+    /// assert_eq!(steps.next(), Some(Rgb::new(0, 0, 0)));
+    /// assert_eq!(steps.next(), Some(Rgb::new(100, 0, 0)));
+    /// assert_eq!(steps.next(), Some(Rgb::new(200, 0, 0)));
+    /// assert_eq!(steps.next(), None);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn steps(&self, count: usize) -> Steps<'_, C> {
+        Steps { gradient: self, front: 0, back: count, count }
+    }
+
+    /// Returns a vector of `count` colors evenly spaced along the gradient,
+    /// including both ends. For cyclic gradients, the end is excluded, as it
+    /// is the same as the start.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(0, 0, 100));
+    ///
+    /// assert_eq!(gradient.quantize(5), vec![
+    ///     Rgb::new(0, 0, 0),
+    ///     Rgb::new(0, 0, 25),
+    ///     Rgb::new(0, 0, 50),
+    ///     Rgb::new(0, 0, 75),
+    ///     Rgb::new(0, 0, 100),
+    /// ]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn quantize(&self, count: usize) -> Vec<C> {
+        self.steps(count).collect()
+    }
+}
+
+impl<C> Gradient<C> where C: Interpolate + Into<Rgb> {
+    /// Writes a truecolor preview of the gradient, followed by a swatch row
+    /// with the position and hex code of each stop.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let gradient = Gradient::new(Rgb::new(255, 0, 0), Rgb::new(0, 0, 255));
+    ///
+    /// let mut out = Vec::new();
+    /// gradient.print_preview(&mut out)?;
+    /// let out = String::from_utf8(out)?;
+    ///
+    /// assert_eq!(out.lines().count(), 3);
+    /// assert!(out.contains("#ff0000 0.00"));
+    /// assert!(out.contains("#0000ff 1.00"));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn print_preview<W>(&self, out: &mut W) -> io::Result<()>
+        where W: io::Write + ?Sized
+    {
+        writeln!(out, "{}", preview(self, PREVIEW_WIDTH, AnsiMode::TrueColor))?;
+        for stop in self.stops.iter() {
+            let label = format!("{:.2}", stop.position);
+            write_swatch_row(out, stop.color.into(), &label)?;
+        }
+        Ok(())
+    }
+}
+
+impl Gradient<Rgb> {
+    /// Constructs a linear `Gradient` with at most `max_stops` stops which
+    /// approximates evenly spaced samples of a color ramp, such as a row of
+    /// pixels taken from an image.
+    ///
+    /// Stops are placed at the first and last samples, and then at whichever
+    /// sample the gradient approximates worst, until every sample is within
+    /// `tolerance` ΔE*00 of the gradient or there are `max_stops` stops. Each
+    /// stop takes the exact color of its sample. A `max_stops` below 2 is
+    /// treated as 2.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let ramp = Gradient::from_colors(vec![
+    ///     Rgb::new(0, 0, 80),
+    ///     Rgb::new(200, 40, 40),
+    ///     Rgb::new(255, 240, 200),
+    /// ]);
+    /// let samples = ramp.quantize(65);
+    ///
+    /// let fitted = Gradient::fit(&samples, 8, 1.0);
+    ///
+    /// assert_eq!(fitted.stops().len(), 3);
+    /// assert_eq!(fitted.color(0.5), Rgb::new(200, 40, 40));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn fit(samples: &[Rgb], max_stops: usize, tolerance: f32) -> Self {
+        assert!(!samples.is_empty());
+        let last = samples.len() - 1;
+        let position = |i: usize| if last == 0 { 0.0 } else {
+            i as f32 / last as f32
+        };
+
+        let mut gradient = Gradient::new(samples[0], samples[last]);
+        let mut used = vec![false; samples.len()];
+        used[0] = true;
+        used[last] = true;
+        while gradient.stops.len() < max_stops.max(2) {
+            let worst = samples.iter()
+                .enumerate()
+                .filter(|&(i, _)| !used[i])
+                .map(|(i, &c)| {
+                    (i, Lab::delta_e(c, gradient.color(position(i))))
+                })
+                .fold(None, |worst: Option<(usize, f32)>, next| match worst {
+                    Some(w) if w.1 >= next.1 => Some(w),
+                    _                        => Some(next),
+                });
+            match worst {
+                Some((i, error)) if error > tolerance => {
+                    used[i] = true;
+                    gradient.add_stop(position(i), samples[i]);
+                },
+                _ => break,
+            }
+        }
+        gradient
+    }
+
+    /// Constructs the smoothest `Gradient` between two colors which passes
+    /// only through colors of the given palette, such as a shading ramp for a
+    /// fixed hardware palette. The endpoints are snapped to their nearest
+    /// palette colors, and the palette colors along the way become evenly
+    /// spaced stops.
+    ///
+    /// Each palette color is linked to its `neighbors` nearest colors in
+    /// Oklab, and the path with the lowest sum of squared step distances is
+    /// chosen. Squaring the distances favors several small steps over one
+    /// large jump, and only colors lying between two others can shorten a
+    /// path, so the ramp does not wander away from its endpoints.
+    ///
+    /// Returns `None` if the palette is empty or the endpoints are not
+    /// connected by the neighbor links. Raising `neighbors` connects more
+    /// colors, at the cost of allowing larger steps.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::gradient::Gradient;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palette: Palette = vec![
+    ///     ("black", Rgb::new(0, 0, 0)),
+    ///     ("navy", Rgb::new(30, 30, 90)),
+    ///     ("green", Rgb::new(40, 160, 60)),
+    ///     ("blue", Rgb::new(60, 80, 200)),
+    ///     ("sky", Rgb::new(150, 190, 255)),
+    ///     ("white", Rgb::new(255, 255, 255)),
+    /// ].into_iter().collect();
+    ///
+    /// let ramp = Gradient::through_palette(
+    ///         &palette,
+    ///         Rgb::new(5, 5, 5),
+    ///         Rgb::new(250, 250, 250),
+    ///         3)
+    ///     .expect("connected palette");
+    ///
+    /// let colors: Vec<Rgb> = ramp.stops().iter().map(|s| s.color).collect();
+    /// assert_eq!(colors, vec![
+    ///     Rgb::new(0, 0, 0),
+    ///     Rgb::new(30, 30, 90),
+    ///     Rgb::new(60, 80, 200),
+    ///     Rgb::new(150, 190, 255),
+    ///     Rgb::new(255, 255, 255),
+    /// ]);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn through_palette<C, D>(
+        palette: &Palette,
+        start: C,
+        end: D,
+        neighbors: usize)
+        -> Option<Self>
+        where
+            C: Into<Rgb>,
+            D: Into<Rgb>,
+    {
+        let colors: Vec<Oklab> = palette.colors().map(Oklab::from).collect();
+        let nearest = |color: Rgb| {
+            let color = Oklab::from(color);
+            (0..colors.len()).min_by(|&a, &b| {
+                Oklab::distance(color, colors[a])
+                    .total_cmp(&Oklab::distance(color, colors[b]))
+            })
+        };
+        let source = nearest(start.into())?;
+        let target = nearest(end.into())?;
+
+        let mut links = vec![Vec::new(); colors.len()];
+        for (i, &color) in colors.iter().enumerate() {
+            let mut others: Vec<usize> = (0..colors.len())
+                .filter(|&j| j != i)
+                .collect();
+            others.sort_by(|&a, &b| {
+                Oklab::distance(color, colors[a])
+                    .total_cmp(&Oklab::distance(color, colors[b]))
+            });
+            for &j in others.iter().take(neighbors) {
+                links[i].push(j);
+                links[j].push(i);
+            }
+        }
+
+        // Dijkstra's algorithm, scanning for the nearest unvisited color as
+        // palettes are small.
+        let mut cost = vec![f32::INFINITY; colors.len()];
+        let mut previous = vec![None; colors.len()];
+        let mut visited = vec![false; colors.len()];
+        cost[source] = 0.0;
+        while let Some(i) = (0..colors.len())
+            .filter(|&i| !visited[i] && cost[i].is_finite())
+            .min_by(|&a, &b| cost[a].total_cmp(&cost[b]))
+        {
+            if i == target { break; }
+            visited[i] = true;
+            for &j in links[i].iter() {
+                let step = Oklab::distance(colors[i], colors[j]);
+                let next = cost[i] + step * step;
+                if next < cost[j] {
+                    cost[j] = next;
+                    previous[j] = Some(i);
+                }
+            }
+        }
+        if !cost[target].is_finite() { return None; }
+
+        let mut path = vec![target];
+        while let Some(i) = previous[*path.last().expect("nonempty path")] {
+            path.push(i);
+        }
+        path.reverse();
+
+        let swatches = palette.swatches();
+        if path.len() == 1 {
+            let color = swatches[source].color;
+            return Some(Gradient::new(color, color));
+        }
+        Some(Gradient::from_colors(path.into_iter().map(|i| swatches[i].color)))
+    }
+}
+
+
+/// Returns the slope between two values separated by `run`, or zero if the run
+/// is empty.
+fn slope(start: f32, end: f32, run: f32) -> f32 {
+    if run > 0.0 { (end - start) / run } else { 0.0 }
+}
+
+/// Returns the slope at a stop for a monotone cubic spline, given the slopes
+/// and widths of the segments before and after it.
+///
+/// The slope is zero at local extremes, and otherwise a weighted harmonic mean
+/// of the neighboring slopes, following Fritsch and Butland. At the ends of a
+/// gradient, where one segment is empty, the other segment's slope is used.
+fn monotone_slope(before: f32, before_width: f32, after: f32, after_width: f32)
+    -> f32
+{
+    if before_width <= 0.0 { return after; }
+    if after_width <= 0.0 { return before; }
+    if before * after <= 0.0 { return 0.0; }
+
+    let total = before_width + after_width;
+    3.0 * total / (
+        (total + after_width) / before + (total + before_width) / after)
+}
+
+/// Returns the `target` coordinates with any hue coordinate replaced by an
+/// equivalent angle within a half turn of the `reference` hue. Rectangular
+/// coordinates are returned unchanged.
+fn unwrap_hue<C>(
+    reference: C::Coordinates,
+    mut target: C::Coordinates,
+    form: InterpolationForm)
+    -> C::Coordinates
+    where C: Interpolate
+{
+    if form.is_rectangular::<C>() { return target; }
+    if let Some(i) = C::HUE_INDEX {
+        let from = reference.as_ref()[i];
+        let to = target.as_ref()[i];
+        target.as_mut()[i] = from + angle::difference(from, to);
+    }
+    target
+}
+
+/// Converts polar coordinates into the given form, replacing the chroma and hue
+/// coordinates with the equivalent rectangular coordinates if required.
+fn to_form<C>(mut coordinates: C::Coordinates, form: InterpolationForm)
+    -> C::Coordinates
+    where C: Interpolate
+{
+    if let (true, Some(c), Some(h))
+        = (form.is_rectangular::<C>(), C::CHROMA_INDEX, C::HUE_INDEX)
+    {
+        let chroma = coordinates.as_ref()[c];
+        let (sin, cos) = coordinates.as_ref()[h].to_radians().sin_cos();
+        coordinates.as_mut()[c] = chroma * cos;
+        coordinates.as_mut()[h] = chroma * sin;
+    }
+    coordinates
+}
+
+/// Converts coordinates in the given form back into polar coordinates.
+fn from_form<C>(mut coordinates: C::Coordinates, form: InterpolationForm)
+    -> C::Coordinates
+    where C: Interpolate
+{
+    if let (true, Some(c), Some(h))
+        = (form.is_rectangular::<C>(), C::CHROMA_INDEX, C::HUE_INDEX)
+    {
+        let x = coordinates.as_ref()[c];
+        let y = coordinates.as_ref()[h];
+        coordinates.as_mut()[c] = x.hypot(y);
+        coordinates.as_mut()[h] = y.atan2(x).to_degrees();
+    }
+    coordinates
+}
+
+/// Evaluates the cubic Hermite curve between `start` and `end` with the given
+/// tangents at the ratio given by `amount`.
+fn hermite(start: f32, end: f32, start_slope: f32, end_slope: f32, amount: f32)
+    -> f32
+{
+    let a = amount;
+    let a2 = a * a;
+    let a3 = a2 * a;
+
+    (2.0*a3 - 3.0*a2 + 1.0) * start
+        + (a3 - 2.0*a2 + a) * start_slope
+        + (-2.0*a3 + 3.0*a2) * end
+        + (a3 - a2) * end_slope
+}
+
+/// The unvalidated serialized form of a [`Gradient`].
+///
+/// [`Gradient`]: struct.Gradient.html
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct GradientRepr<C> {
+    stops: Vec<Stop<C>>,
+    #[serde(default)]
+    interpolation: Interpolation,
+    #[serde(default)]
+    form: InterpolationForm,
+    #[serde(default)]
+    cyclic: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<C> TryFrom<GradientRepr<C>> for Gradient<C> {
+    type Error = String;
+
+    fn try_from(repr: GradientRepr<C>) -> Result<Self, Self::Error> {
+        let GradientRepr { stops, interpolation, form, cyclic } = repr;
+        if stops.is_empty() {
+            return Err("gradient has no stops".to_owned());
+        }
+        if let Some(stop) = stops.iter()
+            .find(|stop| !(0.0..=1.0).contains(&stop.position))
+        {
+            return Err(format!(
+                "stop position {} is not between 0 and 1", stop.position));
+        }
+        if stops.windows(2).any(|pair| pair[0].position > pair[1].position) {
+            return Err("gradient stops are not ordered by position".to_owned());
+        }
+        Ok(Gradient { stops, interpolation, form, cyclic })
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Steps
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over evenly spaced colors of a [`Gradient`].
+///
+/// This struct is created by the [`steps`] method on [`Gradient`].
+///
+/// [`Gradient`]: struct.Gradient.html
+/// [`steps`]: struct.Gradient.html#method.steps
+#[derive(Debug, Clone)]
+pub struct Steps<'g, C> {
+    /// The gradient to sample.
+    gradient: &'g Gradient<C>,
+    /// The index of the next step from the front.
+    front: usize,
+    /// The index after the next step from the back.
+    back: usize,
+    /// The total number of steps.
+    count: usize,
+}
+
+impl<'g, C> Steps<'g, C> where C: Interpolate {
+    /// Returns the gradient position of the given step.
+    fn step_position(&self, index: usize) -> f32 {
+        if self.gradient.cyclic {
+            index as f32 / self.count as f32
+        } else if self.count <= 1 {
+            0.0
+        } else {
+            index as f32 / (self.count - 1) as f32
+        }
+    }
+}
+
+impl<'g, C> Iterator for Steps<'g, C> where C: Interpolate {
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        if self.front >= self.back { return None; }
+        let color = self.gradient.color(self.step_position(self.front));
+        self.front += 1;
+        Some(color)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'g, C> DoubleEndedIterator for Steps<'g, C> where C: Interpolate {
+    fn next_back(&mut self) -> Option<C> {
+        if self.front >= self.back { return None; }
+        self.back -= 1;
+        Some(self.gradient.color(self.step_position(self.back)))
+    }
+}
+
+impl<'g, C> ExactSizeIterator for Steps<'g, C> where C: Interpolate {}
@@ -0,0 +1,477 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Color harmony scheme generation.
+//!
+//! Each function returns a set of colors related to a base color by rotating
+//! its hue, or, for [`monochromatic`], by changing its lightness. The
+//! [`HueSpace`] determines which color wheel the hue rotation happens on. The
+//! base color is always the first color in the returned set, except for
+//! [`monochromatic`], which is ordered from dark to light. For an unbounded
+//! number of distinct colors, see [`GoldenHues`].
+//!
+//! [`GoldenHues`]: struct.GoldenHues.html
+//! [`HueSpace`]: enum.HueSpace.html
+//! [`monochromatic`]: fn.monochromatic.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::angle;
+use crate::Hsv;
+use crate::Oklch;
+use crate::Rgb;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// Corresponding `(rgb, ryb)` hue angles on the HSV and artist's color wheels,
+/// for red, orange, yellow, green, blue, purple, and red again.
+const RYB_WHEEL: [(f32, f32); 7] = [
+    (0.0, 0.0),
+    (30.0, 60.0),
+    (60.0, 120.0),
+    (120.0, 180.0),
+    (240.0, 240.0),
+    (280.0, 300.0),
+    (360.0, 360.0),
+];
+
+/// The golden angle in degrees, `360 / φ²`. Successive multiples of it are
+/// spread as evenly as possible around the circle, no matter how many are
+/// taken.
+pub const GOLDEN_ANGLE: f32 = 137.507_76;
+
+/// The lightness offsets used by [`monochromatic`], from dark to light.
+///
+/// [`monochromatic`]: fn.monochromatic.html
+const MONOCHROMATIC_STEPS: [f32; 5] = [-0.3, -0.15, 0.0, 0.15, 0.3];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// HueSpace
+////////////////////////////////////////////////////////////////////////////////
+/// The color wheel used to rotate hues when generating a harmony.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HueSpace {
+    /// Rotate the hue of the HSV color model. Fast, but hue steps are not
+    /// perceptually even.
+    #[default]
+    Hsv,
+    /// Rotate the hue of the Oklch color space, preserving perceived
+    /// lightness and chroma. Rotated colors may be clipped to the sRGB gamut.
+    Oklch,
+    /// Rotate the hue on the traditional red-yellow-blue artist's color
+    /// wheel, so that red complements green and yellow complements purple.
+    Ryb,
+}
+
+impl HueSpace {
+    /// Returns the given color with its hue rotated by the given number of
+    /// degrees in this hue space.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::harmony::HueSpace;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let red = Rgb::new(255, 0, 0);
+    ///
+    /// assert_eq!(HueSpace::Hsv.rotate(red, 180.0), Rgb::new(0, 255, 255));
+    /// assert_eq!(HueSpace::Ryb.rotate(red, 180.0), Rgb::new(0, 255, 0));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn rotate<C>(self, color: C, degrees: f32) -> Rgb
+        where C: Into<Rgb>
+    {
+        let color = color.into();
+        match self {
+            HueSpace::Hsv => color.rotate_hue(degrees),
+            HueSpace::Oklch => {
+                Rgb::from(Oklch::from(color).rotate_hue(degrees))
+            },
+            HueSpace::Ryb => {
+                let mut hsv = Hsv::from(color);
+                let ryb = ryb_from_rgb_hue(hsv.hue());
+                hsv.set_hue(rgb_from_ryb_hue(angle::wrap(ryb + degrees)));
+                Rgb::from(hsv)
+            },
+        }
+    }
+
+    /// Returns the given color with its lightness shifted by the given
+    /// amount. HSV value is used for the `Hsv` and `Ryb` hue spaces, and
+    /// Oklch lightness for the `Oklch` hue space.
+    fn lighten(self, color: Rgb, amount: f32) -> Rgb {
+        match self {
+            HueSpace::Hsv | HueSpace::Ryb => {
+                let mut hsv = Hsv::from(color);
+                hsv.set_value(hsv.value() + amount);
+                Rgb::from(hsv)
+            },
+            HueSpace::Oklch => {
+                let mut oklch = Oklch::from(color);
+                oklch.l = (oklch.l + amount).clamp(0.0, 1.0);
+                Rgb::from(oklch)
+            },
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// complementary
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the base color and the color opposite it on the color wheel.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::harmony::HueSpace;
+/// # use color::harmony::complementary;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors = complementary(Rgb::new(255, 255, 0), HueSpace::Hsv);
+///
+/// assert_eq!(colors, [Rgb::new(255, 255, 0), Rgb::new(0, 0, 255)]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn complementary<C>(base: C, space: HueSpace) -> [Rgb; 2]
+    where C: Into<Rgb>
+{
+    rotations(base.into(), space, [0.0, 180.0])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// split_complementary
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the base color and the two colors adjacent to its complement,
+/// 150 and 210 degrees away.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::harmony::HueSpace;
+/// # use color::harmony::split_complementary;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors = split_complementary(Rgb::new(255, 0, 0), HueSpace::Hsv);
+///
+/// assert_eq!(colors[1], Rgb::new(0, 255, 127));
+/// assert_eq!(colors[2], Rgb::new(0, 127, 255));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn split_complementary<C>(base: C, space: HueSpace) -> [Rgb; 3]
+    where C: Into<Rgb>
+{
+    rotations(base.into(), space, [0.0, 150.0, 210.0])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// analogous
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the base color and its two neighbors, 30 degrees to either side.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::harmony::HueSpace;
+/// # use color::harmony::analogous;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors = analogous(Rgb::new(255, 0, 0), HueSpace::Hsv);
+///
+/// assert_eq!(colors[1], Rgb::new(255, 0, 127));
+/// assert_eq!(colors[2], Rgb::new(255, 127, 0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn analogous<C>(base: C, space: HueSpace) -> [Rgb; 3]
+    where C: Into<Rgb>
+{
+    rotations(base.into(), space, [0.0, -30.0, 30.0])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// triadic
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the base color and two colors evenly spaced around the color
+/// wheel from it.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::harmony::HueSpace;
+/// # use color::harmony::triadic;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors = triadic(Rgb::new(255, 0, 0), HueSpace::Ryb);
+///
+/// assert_eq!(colors, [
+///     Rgb::new(255, 0, 0),
+///     Rgb::new(255, 255, 0),
+///     Rgb::new(0, 0, 255),
+/// ]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn triadic<C>(base: C, space: HueSpace) -> [Rgb; 3]
+    where C: Into<Rgb>
+{
+    rotations(base.into(), space, [0.0, 120.0, 240.0])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// tetradic
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the base color and three colors forming a square with it on the
+/// color wheel.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::harmony::HueSpace;
+/// # use color::harmony::tetradic;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors = tetradic(Rgb::new(255, 0, 0), HueSpace::Hsv);
+///
+/// assert_eq!(colors[2], Rgb::new(0, 255, 255));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn tetradic<C>(base: C, space: HueSpace) -> [Rgb; 4]
+    where C: Into<Rgb>
+{
+    rotations(base.into(), space, [0.0, 90.0, 180.0, 270.0])
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// monochromatic
+////////////////////////////////////////////////////////////////////////////////
+/// Returns five shades and tints of the base color, ordered from dark to
+/// light, with the base color in the middle.
+///
+/// The lightness used is HSV value for the `Hsv` and `Ryb` hue spaces, and
+/// Oklch lightness for the `Oklch` hue space. Lightness is clamped, so
+/// adjacent colors may be equal for very dark or light base colors.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::harmony::HueSpace;
+/// # use color::harmony::monochromatic;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let base = Rgb::new(0, 0, 128);
+/// let colors = monochromatic(base, HueSpace::Hsv);
+///
+/// assert_eq!(colors[2], base);
+/// assert_eq!(colors[0], Rgb::new(0, 0, 51));
+/// assert_eq!(colors[4], Rgb::new(0, 0, 204));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn monochromatic<C>(base: C, space: HueSpace) -> [Rgb; 5]
+    where C: Into<Rgb>
+{
+    let base = base.into();
+    MONOCHROMATIC_STEPS.map(|amount| if amount == 0.0 {
+        base
+    } else {
+        space.lighten(base, amount)
+    })
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GoldenHues
+////////////////////////////////////////////////////////////////////////////////
+/// An infinite iterator over colors of fixed HSV saturation and value, with
+/// each hue stepped by the [`GOLDEN_ANGLE`] from the last.
+///
+/// Every prefix of the sequence is well spread around the hue circle, so it
+/// can assign distinct colors to any number of categories without knowing
+/// the count in advance.
+///
+/// [`GOLDEN_ANGLE`]: constant.GOLDEN_ANGLE.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Hsv;
+/// # use color::harmony::GoldenHues;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors: Vec<_> = GoldenHues::new(0.0, 0.5, 0.95).take(3).collect();
+///
+/// assert_eq!(colors[0], Hsv::new(0.0, 0.5, 0.95).into());
+/// assert_eq!(colors[1], Hsv::new(137.5, 0.5, 0.95).into());
+/// assert_eq!(colors[2], Hsv::new(275.0, 0.5, 0.95).into());
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GoldenHues {
+    /// The hue of the first color.
+    start: f32,
+    /// The saturation of each color.
+    saturation: f32,
+    /// The value of each color.
+    value: f32,
+    /// The index of the next color.
+    index: u32,
+}
+
+impl GoldenHues {
+    /// Constructs a new `GoldenHues` iterator starting at the given hue, with
+    /// the given saturation and value. Saturation and value are clamped
+    /// between 0 and 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hue is not finite.
+    pub fn new(start: f32, saturation: f32, value: f32) -> Self {
+        assert!(start.is_finite());
+        GoldenHues {
+            start: angle::wrap(start),
+            saturation: saturation.clamp(0.0, 1.0),
+            value: value.clamp(0.0, 1.0),
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for GoldenHues {
+    type Item = Rgb;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Hues are computed from the index rather than accumulated, so that
+        // rounding error does not build up along the sequence.
+        let turns = (self.index as f64 * GOLDEN_ANGLE as f64) % 360.0;
+        let hue = self.start + turns as f32;
+        self.index = self.index.wrapping_add(1);
+        Some(Rgb::from(Hsv::new(hue, self.saturation, self.value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the base color rotated by each of the given angles, keeping the
+/// base color exact for a rotation of zero.
+fn rotations<const N: usize>(base: Rgb, space: HueSpace, degrees: [f32; N])
+    -> [Rgb; N]
+{
+    degrees.map(|d| if d == 0.0 { base } else { space.rotate(base, d) })
+}
+
+/// Converts an HSV hue angle into the corresponding RYB hue angle.
+fn ryb_from_rgb_hue(hue: f32) -> f32 {
+    map_wheel(hue, |(rgb, ryb)| (rgb, ryb))
+}
+
+/// Converts an RYB hue angle into the corresponding HSV hue angle.
+fn rgb_from_ryb_hue(hue: f32) -> f32 {
+    map_wheel(hue, |(rgb, ryb)| (ryb, rgb))
+}
+
+/// Piecewise-linearly maps a hue angle between the wheels of `RYB_WHEEL`,
+/// with `pick` selecting the `(from, to)` angles of each entry.
+fn map_wheel<F>(hue: f32, pick: F) -> f32
+    where F: Fn((f32, f32)) -> (f32, f32)
+{
+    let hue = angle::wrap(hue);
+    for pair in RYB_WHEEL.windows(2) {
+        let (from_a, to_a) = pick(pair[0]);
+        let (from_b, to_b) = pick(pair[1]);
+        if hue <= from_b {
+            let t = (hue - from_a) / (from_b - from_a);
+            return angle::wrap(to_a + t * (to_b - to_a));
+        }
+    }
+    hue
+}
@@ -0,0 +1,1652 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! ICC color profiles.
+//!
+//! An [`IccProfile`] is parsed from an ICC v2 or v4 profile. Matrix/TRC
+//! profiles expose their colorants and tone curves, and LUT-based profiles
+//! expose their `AToB` and `BToA` transforms as a [`Pipeline`] of stages for
+//! each rendering intent. Tags not needed to describe or apply the profile
+//! are ignored.
+//!
+//! An [`IccTransform`] converts device colors from one profile to another,
+//! such as from an RGB display to a CMYK press, and [`convert_icc`] and
+//! [`convert_icc_buffer`] apply a transform once.
+//!
+//! [`IccProfile`]: struct.IccProfile.html
+//! [`Pipeline`]: struct.Pipeline.html
+//! [`IccTransform`]: struct.IccTransform.html
+//! [`convert_icc`]: fn.convert_icc.html
+//! [`convert_icc_buffer`]: fn.convert_icc_buffer.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::lab::lab_f;
+use crate::color_space::lab::lab_f_inverse;
+use crate::illuminant::Illuminant;
+use crate::utility::matrix_inverse;
+use crate::utility::matrix_transform;
+use crate::Xyz;
+
+// Standard library imports.
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::Read;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The size of the profile header in bytes.
+const HEADER_SIZE: usize = 128;
+
+/// The profile file signature, found at byte 36 of the header.
+const SIGNATURE: &[u8; 4] = b"acsp";
+
+/// The `AToB` tag signatures, indexed by rendering intent.
+const A_TO_B_TAGS: [&[u8; 4]; 3] = [b"A2B0", b"A2B1", b"A2B2"];
+
+/// The `BToA` tag signatures, indexed by rendering intent.
+const B_TO_A_TAGS: [&[u8; 4]; 3] = [b"B2A0", b"B2A1", b"B2A2"];
+
+/// The number of bisection steps used to invert a tone curve, enough to
+/// reach the precision of an `f32` ratio.
+const INVERSE_ITERATIONS: usize = 24;
+
+/// The ratio of the largest PCS XYZ value to the largest encoded value in a
+/// lookup table, as XYZ is encoded as a `u1Fixed15Number`.
+const PCS_XYZ_SCALE: f32 = 65535.0 / 32768.0;
+
+/// The ratio of the largest legacy 16-bit PCS L*a*b* value to the largest
+/// encoded value, used by `lut16Type` tables.
+const PCS_LAB_LEGACY_SCALE: f32 = 65535.0 / 65280.0;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// IccError
+////////////////////////////////////////////////////////////////////////////////
+/// An error which can be returned while reading an ICC profile.
+#[derive(Debug)]
+pub enum IccError {
+    /// An error occurred in the underlying reader.
+    Io(io::Error),
+    /// The data does not contain the ICC profile signature.
+    InvalidSignature,
+    /// The profile has an unsupported major version.
+    UnsupportedVersion(u8),
+    /// A required tag has an unsupported type, given by its signature.
+    UnsupportedTagType([u8; 4]),
+    /// The profile has no transform between its device space and the PCS
+    /// in the required direction.
+    NoTransform,
+    /// The data is malformed.
+    Malformed(&'static str),
+}
+
+impl fmt::Display for IccError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            IccError::Io(e) => write!(f, "ICC profile i/o error: {}", e),
+            IccError::InvalidSignature => write!(f,
+                "invalid ICC profile signature"),
+            IccError::UnsupportedVersion(v) => write!(f,
+                "unsupported ICC profile version {}", v),
+            IccError::UnsupportedTagType(t) => write!(f,
+                "unsupported ICC tag type {:?}", String::from_utf8_lossy(t)),
+            IccError::NoTransform => write!(f,
+                "ICC profile has no transform in the required direction"),
+            IccError::Malformed(msg) => write!(f,
+                "malformed ICC profile: {}", msg),
+        }
+    }
+}
+
+impl Error for IccError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IccError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for IccError {
+    fn from(error: io::Error) -> Self {
+        IccError::Io(error)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ProfileClass
+////////////////////////////////////////////////////////////////////////////////
+/// The class of device or transform described by an ICC profile.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ProfileClass {
+    /// An input device, such as a scanner or camera.
+    Input,
+    /// A display device, such as a monitor.
+    Display,
+    /// An output device, such as a printer or press.
+    Output,
+    /// A transform directly between two device spaces.
+    DeviceLink,
+    /// A transform between a color space and the PCS.
+    ColorSpace,
+    /// An effect applied within the PCS.
+    Abstract,
+    /// A list of named colors.
+    NamedColor,
+}
+
+impl ProfileClass {
+    /// Returns the profile class with the given signature.
+    fn from_signature(signature: [u8; 4]) -> Option<Self> {
+        use ProfileClass::*;
+        match &signature {
+            b"scnr" => Some(Input),
+            b"mntr" => Some(Display),
+            b"prtr" => Some(Output),
+            b"link" => Some(DeviceLink),
+            b"spac" => Some(ColorSpace),
+            b"abst" => Some(Abstract),
+            b"nmcl" => Some(NamedColor),
+            _       => None,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ColorSpaceSignature
+////////////////////////////////////////////////////////////////////////////////
+/// The color space of the data or PCS of an ICC profile.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ColorSpaceSignature {
+    /// CIE XYZ.
+    Xyz,
+    /// CIE L*a*b*.
+    Lab,
+    /// RGB.
+    Rgb,
+    /// Grayscale.
+    Gray,
+    /// CMYK.
+    Cmyk,
+    /// CMY.
+    Cmy,
+    /// Another color space, given by its signature.
+    Other([u8; 4]),
+}
+
+impl ColorSpaceSignature {
+    /// Returns the color space with the given signature.
+    fn from_signature(signature: [u8; 4]) -> Self {
+        use ColorSpaceSignature::*;
+        match &signature {
+            b"XYZ " => Xyz,
+            b"Lab " => Lab,
+            b"RGB " => Rgb,
+            b"GRAY" => Gray,
+            b"CMYK" => Cmyk,
+            b"CMY " => Cmy,
+            _       => Other(signature),
+        }
+    }
+
+    /// Returns the number of channels of the color space, or `None` if it is
+    /// not known.
+    pub fn channels(&self) -> Option<usize> {
+        use ColorSpaceSignature::*;
+        match self {
+            Gray => Some(1),
+            Xyz | Lab | Rgb | Cmy => Some(3),
+            Cmyk => Some(4),
+            // Generic spaces of 2 to 15 channels are named `2CLR` to `FCLR`.
+            Other([n, b'C', b'L', b'R']) => (*n as char)
+                .to_digit(16)
+                .filter(|&n| n >= 2)
+                .map(|n| n as usize),
+            Other(_) => None,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RenderingIntent
+////////////////////////////////////////////////////////////////////////////////
+/// The rendering intent of a color transform, which determines how colors
+/// outside of the destination gamut are handled.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum RenderingIntent {
+    /// Compresses the whole gamut to preserve the relationships between
+    /// colors, as for photographs.
+    #[default]
+    Perceptual,
+    /// Preserves in-gamut colors relative to the media white point, and
+    /// clips the rest.
+    RelativeColorimetric,
+    /// Preserves saturation at the expense of accuracy, as for charts.
+    Saturation,
+    /// Preserves in-gamut colors exactly, including the media white point,
+    /// as for proofing.
+    AbsoluteColorimetric,
+}
+
+impl RenderingIntent {
+    /// Returns the rendering intent with the given header value.
+    fn from_value(value: u32) -> Option<Self> {
+        use RenderingIntent::*;
+        match value {
+            0 => Some(Perceptual),
+            1 => Some(RelativeColorimetric),
+            2 => Some(Saturation),
+            3 => Some(AbsoluteColorimetric),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the `AToB` and `BToA` tags for the intent.
+    /// Absolute colorimetric transforms use the relative colorimetric tags.
+    fn tag_index(self) -> usize {
+        match self {
+            RenderingIntent::Perceptual           => 0,
+            RenderingIntent::RelativeColorimetric => 1,
+            RenderingIntent::Saturation           => 2,
+            RenderingIntent::AbsoluteColorimetric => 1,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ToneCurve
+////////////////////////////////////////////////////////////////////////////////
+/// A one-dimensional curve mapping channel ratios between 0 and 1.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ToneCurve {
+    /// The identity curve.
+    Identity,
+    /// A pure power function with the given exponent.
+    Gamma(f32),
+    /// A table of evenly spaced samples, interpolated linearly.
+    Table(Vec<f32>),
+    /// An ICC parametric curve of the given function type, from 0 to 4,
+    /// with its parameters in the order `[g, a, b, c, d, e, f]`.
+    Parametric {
+        /// The function type.
+        function: u16,
+        /// The function parameters, with unused parameters set to 0.
+        params: [f32; 7],
+    },
+}
+
+impl ToneCurve {
+    /// Returns the value of the curve at the given ratio. The input and
+    /// output are clamped between 0 and 1.
+    pub fn value(&self, ratio: f32) -> f32 {
+        let x = ratio.clamp(0.0, 1.0);
+        let y = match self {
+            ToneCurve::Identity     => x,
+            ToneCurve::Gamma(gamma) => x.powf(*gamma),
+            ToneCurve::Table(table) => match table.len() {
+                0 => x,
+                1 => table[0],
+                n => {
+                    let position = x * (n - 1) as f32;
+                    let i = (position.floor() as usize).min(n - 2);
+                    let t = position - i as f32;
+                    table[i] + (table[i + 1] - table[i]) * t
+                },
+            },
+            ToneCurve::Parametric { function, params } => {
+                let [g, a, b, c, d, e, f] = *params;
+                let power = |v: f32| if v > 0.0 { v.powf(g) } else { 0.0 };
+                match function {
+                    0 => power(x),
+                    1 => if x >= -b / a { power(a * x + b) } else { 0.0 },
+                    2 => if x >= -b / a { power(a * x + b) + c } else { c },
+                    3 => if x >= d { power(a * x + b) } else { c * x },
+                    _ => if x >= d { power(a * x + b) + e } else { c * x + f },
+                }
+            },
+        };
+        y.clamp(0.0, 1.0)
+    }
+
+    /// Returns the ratio at which the curve has the given value, assuming
+    /// the curve is increasing. Values outside of the range of the curve
+    /// give the nearest endpoint.
+    pub fn inverse_value(&self, value: f32) -> f32 {
+        let y = value.clamp(0.0, 1.0);
+        match self {
+            ToneCurve::Identity     => y,
+            ToneCurve::Gamma(gamma) => y.powf(1.0 / gamma),
+            _ => {
+                // Curves are monotonic but may be flat in places, so the
+                // inverse is found by bisection.
+                let (mut low, mut high) = (0.0f32, 1.0f32);
+                for _ in 0..INVERSE_ITERATIONS {
+                    let mid = 0.5 * (low + high);
+                    if self.value(mid) < y { low = mid; } else { high = mid; }
+                }
+                0.5 * (low + high)
+            },
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Clut
+////////////////////////////////////////////////////////////////////////////////
+/// A multidimensional color lookup table, interpolated multilinearly.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Clut {
+    /// The number of grid points along each input dimension.
+    grid_points: Vec<usize>,
+    /// The number of output channels.
+    output_channels: usize,
+    /// The output ratios of each grid point, with the first input varying
+    /// slowest.
+    values: Vec<f32>,
+}
+
+impl Clut {
+    /// Returns the number of grid points along each input dimension.
+    pub fn grid_points(&self) -> &[usize] {
+        &self.grid_points
+    }
+
+    /// Returns the number of output channels.
+    pub fn output_channels(&self) -> usize {
+        self.output_channels
+    }
+
+    /// Returns the interpolated outputs for the given input ratios.
+    pub fn value(&self, input: &[f32]) -> Vec<f32> {
+        let n = self.grid_points.len();
+        let m = self.output_channels;
+
+        // The lower grid index, fraction, and stride of each dimension.
+        let mut cells = Vec::with_capacity(n);
+        let mut stride = m;
+        for d in (0..n).rev() {
+            let points = self.grid_points[d];
+            let x = input.get(d).copied().unwrap_or(0.0).clamp(0.0, 1.0);
+            let position = x * (points - 1) as f32;
+            let i = (position.floor() as usize).min(points.saturating_sub(2));
+            cells.push((i, position - i as f32, stride));
+            stride *= points;
+        }
+        cells.reverse();
+
+        let mut output = vec![0.0; m];
+        for corner in 0..(1usize << n) {
+            let mut weight = 1.0;
+            let mut offset = 0;
+            for (d, &(i, t, stride)) in cells.iter().enumerate() {
+                let upper = (corner >> (n - 1 - d)) & 1 == 1;
+                weight *= if upper { t } else { 1.0 - t };
+                offset += if upper { i + 1 } else { i } * stride;
+            }
+            // Skipping empty corners also avoids indexing past the end of
+            // a dimension with a single grid point.
+            if weight == 0.0 { continue; }
+            for (o, v) in output.iter_mut()
+                .zip(&self.values[offset..offset + m])
+            {
+                *o += weight * v;
+            }
+        }
+        output
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Stage
+////////////////////////////////////////////////////////////////////////////////
+/// A processing stage of a [`Pipeline`].
+///
+/// [`Pipeline`]: struct.Pipeline.html
+#[derive(Debug, PartialEq, Clone)]
+pub enum Stage {
+    /// A curve applied to each channel.
+    Curves(Vec<ToneCurve>),
+    /// A 3×3 matrix followed by an offset, applied to three channels.
+    Matrix {
+        /// The matrix.
+        matrix: [[f32; 3]; 3],
+        /// The offset added after the matrix.
+        offset: [f32; 3],
+    },
+    /// A multidimensional lookup table.
+    Clut(Clut),
+}
+
+impl Stage {
+    /// Applies the stage to the given channel ratios.
+    fn apply(&self, input: Vec<f32>) -> Vec<f32> {
+        match self {
+            Stage::Curves(curves) => input
+                .iter()
+                .zip(curves.iter())
+                .map(|(&v, curve)| curve.value(v))
+                .collect(),
+            Stage::Matrix { matrix, offset } => {
+                let v = [0, 1, 2].map(|i| input.get(i).copied()
+                    .unwrap_or(0.0));
+                let t = matrix_transform(*matrix, v);
+                (0..3).map(|i| t[i] + offset[i]).collect()
+            },
+            Stage::Clut(clut) => clut.value(&input),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// LutKind
+////////////////////////////////////////////////////////////////////////////////
+/// The tag type a [`Pipeline`] was read from, which determines how PCS
+/// values are encoded.
+///
+/// [`Pipeline`]: struct.Pipeline.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LutKind {
+    /// An 8-bit `lut8Type` table.
+    Lut8,
+    /// A 16-bit `lut16Type` table, which uses the legacy 16-bit L*a*b*
+    /// encoding.
+    Lut16,
+    /// An `lutAToBType` transform.
+    AToB,
+    /// An `lutBToAType` transform.
+    BToA,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Pipeline
+////////////////////////////////////////////////////////////////////////////////
+/// A transform of an ICC profile, made of a sequence of stages operating on
+/// channel ratios between 0 and 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pipeline {
+    /// The tag type the pipeline was read from.
+    kind: LutKind,
+    /// The number of input channels.
+    input_channels: usize,
+    /// The number of output channels.
+    output_channels: usize,
+    /// The stages of the pipeline, in the order they are applied.
+    stages: Vec<Stage>,
+}
+
+impl Pipeline {
+    /// Returns the tag type the pipeline was read from.
+    pub fn kind(&self) -> LutKind {
+        self.kind
+    }
+
+    /// Returns the number of input channels.
+    pub fn input_channels(&self) -> usize {
+        self.input_channels
+    }
+
+    /// Returns the number of output channels.
+    pub fn output_channels(&self) -> usize {
+        self.output_channels
+    }
+
+    /// Returns the stages of the pipeline, in the order they are applied.
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// Returns the result of applying the pipeline to the given encoded
+    /// channel ratios.
+    pub fn apply(&self, input: &[f32]) -> Vec<f32> {
+        let mut values: Vec<f32> = input.to_vec();
+        values.resize(self.input_channels, 0.0);
+        for stage in &self.stages {
+            values = stage.apply(values);
+        }
+        values.resize(self.output_channels, 0.0);
+        values
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// MatrixTrc
+////////////////////////////////////////////////////////////////////////////////
+/// The colorants and tone reproduction curves of a matrix/TRC RGB profile.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatrixTrc {
+    /// The PCS XYZ values of the red, green, and blue colorants.
+    pub colorants: [Xyz; 3],
+    /// The curves converting the red, green, and blue channels to linear
+    /// light.
+    pub curves: [ToneCurve; 3],
+}
+
+impl MatrixTrc {
+    /// Returns the matrix converting linear RGB ratios to PCS XYZ.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        let [r, g, b] = self.colorants;
+        [
+            [r.x, g.x, b.x],
+            [r.y, g.y, b.y],
+            [r.z, g.z, b.z],
+        ]
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// IccProfile
+////////////////////////////////////////////////////////////////////////////////
+/// A parsed ICC v2 or v4 color profile.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::icc::ColorSpaceSignature;
+/// # use color::icc::IccProfile;
+/// # use color::icc::ProfileClass;
+/// # use color::icc::RenderingIntent;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // A minimal version 4 display profile header with no tags.
+/// let mut data = vec![0; 132];
+/// data[0..4].copy_from_slice(&132u32.to_be_bytes());
+/// data[8] = 4;
+/// data[12..16].copy_from_slice(b"mntr");
+/// data[16..20].copy_from_slice(b"GRAY");
+/// data[20..24].copy_from_slice(b"XYZ ");
+/// data[36..40].copy_from_slice(b"acsp");
+///
+/// let profile = IccProfile::from_bytes(&data)?;
+///
+/// assert_eq!(profile.version(), (4, 0));
+/// assert_eq!(profile.class(), ProfileClass::Display);
+/// assert_eq!(profile.color_space(), ColorSpaceSignature::Gray);
+/// assert_eq!(profile.rendering_intent(), RenderingIntent::Perceptual);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct IccProfile {
+    /// The major and minor version.
+    version: (u8, u8),
+    /// The profile class.
+    class: ProfileClass,
+    /// The color space of the device data.
+    color_space: ColorSpaceSignature,
+    /// The profile connection space.
+    pcs: ColorSpaceSignature,
+    /// The default rendering intent.
+    rendering_intent: RenderingIntent,
+    /// The illuminant of the PCS.
+    pcs_illuminant: Xyz,
+    /// The media white point tag.
+    media_white_point: Option<Xyz>,
+    /// The chromatic adaptation tag.
+    chromatic_adaptation: Option<[[f32; 3]; 3]>,
+    /// The profile description tag.
+    description: Option<String>,
+    /// The colorant and TRC tags of an RGB profile.
+    matrix_trc: Option<MatrixTrc>,
+    /// The gray TRC tag of a grayscale profile.
+    gray_trc: Option<ToneCurve>,
+    /// The `AToB` tags, indexed by rendering intent.
+    a_to_b: [Option<Pipeline>; 3],
+    /// The `BToA` tags, indexed by rendering intent.
+    b_to_a: [Option<Pipeline>; 3],
+}
+
+impl IccProfile {
+    /// Reads an ICC profile from the given reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IccError`] if the data cannot be read or is not a valid
+    /// ICC profile.
+    ///
+    /// [`IccError`]: enum.IccError.html
+    pub fn read<R>(reader: &mut R) -> Result<Self, IccError>
+        where R: Read
+    {
+        let mut data = Vec::new();
+        let _ = reader.read_to_end(&mut data)?;
+        IccProfile::from_bytes(&data)
+    }
+
+    /// Parses an ICC profile from the given bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IccError`] if the data is not a valid ICC profile.
+    ///
+    /// [`IccError`]: enum.IccError.html
+    pub fn from_bytes(data: &[u8]) -> Result<Self, IccError> {
+        if data.len() < HEADER_SIZE + 4 {
+            return Err(IccError::Malformed("truncated header"));
+        }
+        if &data[36..40] != SIGNATURE {
+            return Err(IccError::InvalidSignature);
+        }
+        let size = read_u32(data, 0)? as usize;
+        if size > data.len() || size < HEADER_SIZE + 4 {
+            return Err(IccError::Malformed("invalid profile size"));
+        }
+        let data = &data[..size];
+
+        let major = data[8];
+        if major != 2 && major != 4 {
+            return Err(IccError::UnsupportedVersion(major));
+        }
+        let version = (major, data[9] >> 4);
+        let class = ProfileClass::from_signature(read_signature(data, 12)?)
+            .ok_or(IccError::Malformed("unknown profile class"))?;
+        let color_space = ColorSpaceSignature::from_signature(
+            read_signature(data, 16)?);
+        let pcs = ColorSpaceSignature::from_signature(
+            read_signature(data, 20)?);
+        if class != ProfileClass::DeviceLink
+            && pcs != ColorSpaceSignature::Xyz
+            && pcs != ColorSpaceSignature::Lab
+        {
+            return Err(IccError::Malformed("invalid connection space"));
+        }
+        let rendering_intent = RenderingIntent::from_value(
+                read_u32(data, 64)? & 0xFFFF)
+            .ok_or(IccError::Malformed("invalid rendering intent"))?;
+        let pcs_illuminant = read_xyz_number(data, 68)?;
+
+        let tags = read_tag_table(data)?;
+        let tag = |signature: &[u8; 4]| tags
+            .iter()
+            .find(|(s, _)| s == signature)
+            .map(|(_, bytes)| *bytes);
+
+        let media_white_point = tag(b"wtpt").map(read_xyz_type).transpose()?;
+        let chromatic_adaptation = tag(b"chad")
+            .map(read_matrix_type)
+            .transpose()?;
+        let description = tag(b"desc").and_then(read_text_type);
+
+        let colorants = [b"rXYZ", b"gXYZ", b"bXYZ"].map(tag);
+        let curves = [b"rTRC", b"gTRC", b"bTRC"].map(tag);
+        let matrix_trc = match (colorants, curves) {
+            ([Some(r), Some(g), Some(b)], [Some(rc), Some(gc), Some(bc)]) => {
+                Some(MatrixTrc {
+                    colorants: [
+                        read_xyz_type(r)?,
+                        read_xyz_type(g)?,
+                        read_xyz_type(b)?,
+                    ],
+                    curves: [
+                        read_curve_type(rc)?.0,
+                        read_curve_type(gc)?.0,
+                        read_curve_type(bc)?.0,
+                    ],
+                })
+            },
+            _ => None,
+        };
+        let gray_trc = match tag(b"kTRC") {
+            Some(bytes) => Some(read_curve_type(bytes)?.0),
+            None        => None,
+        };
+
+        let mut a_to_b = [None, None, None];
+        let mut b_to_a = [None, None, None];
+        for i in 0..3 {
+            if let Some(bytes) = tag(A_TO_B_TAGS[i]) {
+                a_to_b[i] = Some(read_lut_type(bytes)?);
+            }
+            if let Some(bytes) = tag(B_TO_A_TAGS[i]) {
+                b_to_a[i] = Some(read_lut_type(bytes)?);
+            }
+        }
+
+        Ok(IccProfile {
+            version,
+            class,
+            color_space,
+            pcs,
+            rendering_intent,
+            pcs_illuminant,
+            media_white_point,
+            chromatic_adaptation,
+            description,
+            matrix_trc,
+            gray_trc,
+            a_to_b,
+            b_to_a,
+        })
+    }
+
+    /// Returns the major and minor version of the profile.
+    pub fn version(&self) -> (u8, u8) {
+        self.version
+    }
+
+    /// Returns the profile class.
+    pub fn class(&self) -> ProfileClass {
+        self.class
+    }
+
+    /// Returns the color space of the device data.
+    pub fn color_space(&self) -> ColorSpaceSignature {
+        self.color_space
+    }
+
+    /// Returns the profile connection space, which is the output color space
+    /// of a device link.
+    pub fn pcs(&self) -> ColorSpaceSignature {
+        self.pcs
+    }
+
+    /// Returns the default rendering intent of the profile.
+    pub fn rendering_intent(&self) -> RenderingIntent {
+        self.rendering_intent
+    }
+
+    /// Returns the illuminant of the profile connection space, which is D50
+    /// for conforming profiles.
+    pub fn pcs_illuminant(&self) -> Xyz {
+        self.pcs_illuminant
+    }
+
+    /// Returns the media white point tag, if present.
+    pub fn media_white_point(&self) -> Option<Xyz> {
+        self.media_white_point
+    }
+
+    /// Returns the matrix adapting the device white point to the PCS
+    /// illuminant, if present.
+    pub fn chromatic_adaptation(&self) -> Option<[[f32; 3]; 3]> {
+        self.chromatic_adaptation
+    }
+
+    /// Returns the white point of the device, undoing any recorded
+    /// adaptation to the PCS illuminant.
+    pub fn white_point(&self) -> Xyz {
+        let white = self.media_white_point.unwrap_or(self.pcs_illuminant);
+        match self.chromatic_adaptation {
+            Some(chad) => {
+                let [x, y, z] = matrix_transform(
+                    matrix_inverse(chad),
+                    white.components());
+                Xyz { x, y, z }
+            },
+            None => white,
+        }
+    }
+
+    /// Returns the media white point relative to the PCS illuminant, which
+    /// scales colors for the absolute colorimetric intent.
+    fn absolute_white(&self) -> Xyz {
+        self.media_white_point
+            .unwrap_or_else(|| Illuminant::D50.white_point())
+    }
+
+    /// Returns the profile description, if present.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the colorants and curves of a matrix/TRC RGB profile.
+    pub fn matrix_trc(&self) -> Option<&MatrixTrc> {
+        self.matrix_trc.as_ref()
+    }
+
+    /// Returns the tone curve of a grayscale TRC profile.
+    pub fn gray_trc(&self) -> Option<&ToneCurve> {
+        self.gray_trc.as_ref()
+    }
+
+    /// Returns the `[x, y]` chromaticities of the red, green, and blue
+    /// primaries of a matrix/TRC profile, undoing any recorded adaptation to
+    /// the PCS illuminant.
+    pub fn primaries(&self) -> Option<[[f32; 2]; 3]> {
+        let matrix_trc = self.matrix_trc.as_ref()?;
+        let unadapt = self.chromatic_adaptation.map(matrix_inverse);
+        Some(matrix_trc.colorants.map(|c| {
+            let [x, y, z] = match unadapt {
+                Some(m) => matrix_transform(m, c.components()),
+                None    => c.components(),
+            };
+            let sum = x + y + z;
+            [x / sum, y / sum]
+        }))
+    }
+
+    /// Returns the transform from device data to the PCS for the given
+    /// rendering intent, falling back to the perceptual transform as the
+    /// ICC specification requires.
+    pub fn a_to_b(&self, intent: RenderingIntent) -> Option<&Pipeline> {
+        self.a_to_b[intent.tag_index()].as_ref()
+            .or_else(|| self.a_to_b[0].as_ref())
+    }
+
+    /// Returns the transform from the PCS to device data for the given
+    /// rendering intent, falling back to the perceptual transform as the
+    /// ICC specification requires.
+    pub fn b_to_a(&self, intent: RenderingIntent) -> Option<&Pipeline> {
+        self.b_to_a[intent.tag_index()].as_ref()
+            .or_else(|| self.b_to_a[0].as_ref())
+    }
+
+    /// Returns the rendering intents the profile provides distinct
+    /// transforms for. Matrix/TRC profiles support every intent.
+    pub fn supported_intents(&self) -> Vec<RenderingIntent> {
+        use RenderingIntent::*;
+        [Perceptual, RelativeColorimetric, Saturation, AbsoluteColorimetric]
+            .iter()
+            .copied()
+            .filter(|&intent| {
+                let i = intent.tag_index();
+                self.matrix_trc.is_some()
+                    || self.gray_trc.is_some()
+                    || self.a_to_b[i].is_some()
+                    || self.b_to_a[i].is_some()
+            })
+            .collect()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// IccTransform
+////////////////////////////////////////////////////////////////////////////////
+/// A color transform from the device space of one ICC profile to that of
+/// another.
+///
+/// Colors are connected through D50 relative XYZ. For
+/// [`RenderingIntent::AbsoluteColorimetric`], colors are also scaled by the
+/// ratio of the media white points, so that paper colors are simulated.
+///
+/// [`RenderingIntent::AbsoluteColorimetric`]:
+/// enum.RenderingIntent.html#variant.AbsoluteColorimetric
+#[derive(Debug, PartialEq, Clone)]
+pub struct IccTransform {
+    /// The conversion from source device values to the PCS.
+    source: Connection,
+    /// The conversion from the PCS to target device values.
+    target: Connection,
+    /// The scale applied to the PCS XYZ components between the profiles.
+    white_scale: [f32; 3],
+}
+
+impl IccTransform {
+    /// Constructs a transform from the device space of the `source` profile
+    /// to that of the `target` profile, using the given rendering intent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IccError::NoTransform`] if either profile lacks a transform
+    /// in the required direction, as is the case for device link, abstract,
+    /// and named color profiles.
+    ///
+    /// [`IccError::NoTransform`]: enum.IccError.html#variant.NoTransform
+    pub fn new(
+        source: &IccProfile,
+        target: &IccProfile,
+        intent: RenderingIntent)
+        -> Result<Self, IccError>
+    {
+        let white_scale = match intent {
+            RenderingIntent::AbsoluteColorimetric => {
+                let s = source.absolute_white();
+                let t = target.absolute_white();
+                [s.x / t.x, s.y / t.y, s.z / t.z]
+            },
+            _ => [1.0; 3],
+        };
+        Ok(IccTransform {
+            source: Connection::to_pcs(source, intent)?,
+            target: Connection::from_pcs(target, intent)?,
+            white_scale,
+        })
+    }
+
+    /// Returns the number of input channels.
+    pub fn input_channels(&self) -> usize {
+        self.source.channels()
+    }
+
+    /// Returns the number of output channels.
+    pub fn output_channels(&self) -> usize {
+        self.target.channels()
+    }
+
+    /// Returns the given device color of the source profile, as channel
+    /// ratios, converted to a device color of the target profile.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of channels of `color` is not the number of
+    /// input channels of the transform.
+    pub fn apply(&self, color: &[f32]) -> Vec<f32> {
+        assert!(color.len() == self.input_channels(),
+            "color must have one value per input channel");
+        let pcs = self.source.apply(color);
+        let pcs = [0, 1, 2].map(|i| pcs[i] * self.white_scale[i]);
+        self.target.apply(&pcs)
+    }
+
+    /// Returns the given buffer of interleaved source device colors
+    /// converted to interleaved target device colors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `pixels` is not a multiple of the number of
+    /// input channels of the transform.
+    pub fn apply_buffer(&self, pixels: &[f32]) -> Vec<f32> {
+        let channels = self.input_channels();
+        assert!(pixels.len().is_multiple_of(channels),
+            "buffer length must be a multiple of the input channels");
+        let mut output = Vec::with_capacity(
+            pixels.len() / channels * self.output_channels());
+        for color in pixels.chunks_exact(channels) {
+            output.extend(self.apply(color));
+        }
+        output
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// convert_icc
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given device color of the `source` profile, as channel
+/// ratios, converted to a device color of the `target` profile with the
+/// given rendering intent.
+///
+/// To convert many colors, construct an [`IccTransform`] once or use
+/// [`convert_icc_buffer`].
+///
+/// [`IccTransform`]: struct.IccTransform.html
+/// [`convert_icc_buffer`]: fn.convert_icc_buffer.html
+///
+/// # Errors
+///
+/// Returns an [`IccError`] if either profile lacks a transform in the
+/// required direction.
+///
+/// [`IccError`]: enum.IccError.html
+///
+/// # Panics
+///
+/// Panics if the number of channels of `color` does not match the source
+/// profile.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::icc::IccProfile;
+/// # use color::icc::RenderingIntent;
+/// # use color::icc::convert_icc;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// # // Builds a grayscale profile with a gamma tone curve.
+/// # let gray_profile = |gamma: f32| {
+/// #     let mut data = vec![0; 160];
+/// #     data[0..4].copy_from_slice(&160u32.to_be_bytes());
+/// #     data[8] = 4;
+/// #     data[12..16].copy_from_slice(b"mntr");
+/// #     data[16..20].copy_from_slice(b"GRAY");
+/// #     data[20..24].copy_from_slice(b"XYZ ");
+/// #     data[36..40].copy_from_slice(b"acsp");
+/// #     data[128..132].copy_from_slice(&1u32.to_be_bytes());
+/// #     data[132..136].copy_from_slice(b"kTRC");
+/// #     data[136..140].copy_from_slice(&144u32.to_be_bytes());
+/// #     data[140..144].copy_from_slice(&14u32.to_be_bytes());
+/// #     data[144..148].copy_from_slice(b"curv");
+/// #     data[152..156].copy_from_slice(&1u32.to_be_bytes());
+/// #     let gamma = (gamma * 256.0) as u16;
+/// #     data[156..158].copy_from_slice(&gamma.to_be_bytes());
+/// #     IccProfile::from_bytes(&data)
+/// # };
+/// let linear = gray_profile(1.0)?;
+/// let display = gray_profile(2.0)?;
+///
+/// let gray = convert_icc(&linear, &display,
+///     RenderingIntent::RelativeColorimetric, &[0.25])?;
+///
+/// assert!((gray[0] - 0.5).abs() < 0.0001);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn convert_icc(
+    source: &IccProfile,
+    target: &IccProfile,
+    intent: RenderingIntent,
+    color: &[f32])
+    -> Result<Vec<f32>, IccError>
+{
+    Ok(IccTransform::new(source, target, intent)?.apply(color))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// convert_icc_buffer
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given buffer of interleaved device colors of the `source`
+/// profile converted to interleaved device colors of the `target` profile
+/// with the given rendering intent.
+///
+/// # Errors
+///
+/// Returns an [`IccError`] if either profile lacks a transform in the
+/// required direction.
+///
+/// [`IccError`]: enum.IccError.html
+///
+/// # Panics
+///
+/// Panics if the length of `pixels` is not a multiple of the number of
+/// channels of the source profile.
+pub fn convert_icc_buffer(
+    source: &IccProfile,
+    target: &IccProfile,
+    intent: RenderingIntent,
+    pixels: &[f32])
+    -> Result<Vec<f32>, IccError>
+{
+    Ok(IccTransform::new(source, target, intent)?.apply_buffer(pixels))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Connection
+////////////////////////////////////////////////////////////////////////////////
+/// One side of an [`IccTransform`], converting between device values and
+/// D50 relative PCS XYZ.
+///
+/// [`IccTransform`]: struct.IccTransform.html
+#[derive(Debug, PartialEq, Clone)]
+enum Connection {
+    /// A lookup table pipeline with an encoded PCS.
+    Lut {
+        /// The pipeline.
+        pipeline: Pipeline,
+        /// The PCS of the pipeline.
+        pcs: ColorSpaceSignature,
+        /// The PCS white point.
+        white: Xyz,
+        /// Whether the pipeline converts to the PCS.
+        to_pcs: bool,
+    },
+    /// Matrix/TRC conversion of RGB values.
+    MatrixTrc {
+        /// The matrix converting linear RGB to PCS XYZ, or its inverse.
+        matrix: [[f32; 3]; 3],
+        /// The tone curves.
+        curves: [ToneCurve; 3],
+        /// Whether the conversion is to the PCS.
+        to_pcs: bool,
+    },
+    /// Gray TRC conversion of gray values.
+    GrayTrc {
+        /// The tone curve.
+        curve: ToneCurve,
+        /// The PCS white point.
+        white: Xyz,
+        /// Whether the conversion is to the PCS.
+        to_pcs: bool,
+    },
+}
+
+impl Connection {
+    /// Returns the conversion of the given profile from device values to
+    /// the PCS.
+    fn to_pcs(profile: &IccProfile, intent: RenderingIntent)
+        -> Result<Self, IccError>
+    {
+        Connection::new(profile, profile.a_to_b(intent), true)
+    }
+
+    /// Returns the conversion of the given profile from the PCS to device
+    /// values.
+    fn from_pcs(profile: &IccProfile, intent: RenderingIntent)
+        -> Result<Self, IccError>
+    {
+        Connection::new(profile, profile.b_to_a(intent), false)
+    }
+
+    /// Returns the conversion of the given profile in the given direction,
+    /// preferring the given pipeline.
+    fn new(profile: &IccProfile, pipeline: Option<&Pipeline>, to_pcs: bool)
+        -> Result<Self, IccError>
+    {
+        match profile.class {
+            ProfileClass::DeviceLink
+                | ProfileClass::Abstract
+                | ProfileClass::NamedColor => return Err(IccError::NoTransform),
+            _ => (),
+        }
+        // The ICC specification requires a D50 PCS, whatever the header
+        // states.
+        let white = Illuminant::D50.white_point();
+        if let Some(pipeline) = pipeline {
+            return Ok(Connection::Lut {
+                pipeline: pipeline.clone(),
+                pcs: profile.pcs,
+                white,
+                to_pcs,
+            });
+        }
+        if let Some(matrix_trc) = &profile.matrix_trc {
+            let matrix = matrix_trc.matrix();
+            return Ok(Connection::MatrixTrc {
+                matrix: if to_pcs { matrix } else { matrix_inverse(matrix) },
+                curves: matrix_trc.curves.clone(),
+                to_pcs,
+            });
+        }
+        if let Some(curve) = &profile.gray_trc {
+            return Ok(Connection::GrayTrc {
+                curve: curve.clone(),
+                white,
+                to_pcs,
+            });
+        }
+        Err(IccError::NoTransform)
+    }
+
+    /// Returns the number of device channels.
+    fn channels(&self) -> usize {
+        match self {
+            Connection::Lut { pipeline, to_pcs, .. } => if *to_pcs {
+                pipeline.input_channels
+            } else {
+                pipeline.output_channels
+            },
+            Connection::MatrixTrc { .. } => 3,
+            Connection::GrayTrc { .. }   => 1,
+        }
+    }
+
+    /// Converts device values to PCS XYZ components, or PCS XYZ components
+    /// to device values.
+    fn apply(&self, input: &[f32]) -> Vec<f32> {
+        match self {
+            Connection::Lut { pipeline, pcs, white, to_pcs: true } => {
+                let encoded = pipeline.apply(input);
+                decode_pcs(&encoded, *pcs, pipeline.kind, *white).to_vec()
+            },
+            Connection::Lut { pipeline, pcs, white, to_pcs: false } => {
+                let xyz = [input[0], input[1], input[2]];
+                pipeline.apply(&encode_pcs(xyz, *pcs, pipeline.kind, *white))
+            },
+            Connection::MatrixTrc { matrix, curves, to_pcs: true } => {
+                let linear = [0, 1, 2].map(|i| curves[i].value(input[i]));
+                matrix_transform(*matrix, linear).to_vec()
+            },
+            Connection::MatrixTrc { matrix, curves, to_pcs: false } => {
+                let xyz = [input[0], input[1], input[2]];
+                let linear = matrix_transform(*matrix, xyz);
+                (0..3).map(|i| curves[i].inverse_value(linear[i])).collect()
+            },
+            Connection::GrayTrc { curve, white, to_pcs: true } => {
+                let y = curve.value(input[0]);
+                vec![white.x * y, white.y * y, white.z * y]
+            },
+            Connection::GrayTrc { curve, white, to_pcs: false } => {
+                vec![curve.inverse_value(input[1] / white.y)]
+            },
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the D50 relative PCS XYZ components of the given encoded PCS
+/// values of a pipeline of the given kind.
+fn decode_pcs(
+    encoded: &[f32],
+    pcs: ColorSpaceSignature,
+    kind: LutKind,
+    white: Xyz)
+    -> [f32; 3]
+{
+    let v = [0, 1, 2].map(|i| encoded.get(i).copied().unwrap_or(0.0));
+    if pcs != ColorSpaceSignature::Lab {
+        return v.map(|c| c * PCS_XYZ_SCALE);
+    }
+    let scale = if kind == LutKind::Lut16 { PCS_LAB_LEGACY_SCALE } else { 1.0 };
+    let l = v[0] * scale * 100.0;
+    let a = v[1] * scale * 255.0 - 128.0;
+    let b = v[2] * scale * 255.0 - 128.0;
+
+    let fy = (l + 16.0) / 116.0;
+    [
+        lab_f_inverse(fy + a / 500.0) * white.x,
+        lab_f_inverse(fy) * white.y,
+        lab_f_inverse(fy - b / 200.0) * white.z,
+    ]
+}
+
+/// Returns the encoded PCS values for a pipeline of the given kind of the
+/// given D50 relative PCS XYZ components.
+fn encode_pcs(
+    xyz: [f32; 3],
+    pcs: ColorSpaceSignature,
+    kind: LutKind,
+    white: Xyz)
+    -> [f32; 3]
+{
+    if pcs != ColorSpaceSignature::Lab {
+        return xyz.map(|c| c / PCS_XYZ_SCALE);
+    }
+    let scale = if kind == LutKind::Lut16 { PCS_LAB_LEGACY_SCALE } else { 1.0 };
+    let fx = lab_f(xyz[0] / white.x);
+    let fy = lab_f(xyz[1] / white.y);
+    let fz = lab_f(xyz[2] / white.z);
+    [
+        (116.0 * fy - 16.0) / 100.0 / scale,
+        (500.0 * (fx - fy) + 128.0) / 255.0 / scale,
+        (200.0 * (fy - fz) + 128.0) / 255.0 / scale,
+    ]
+}
+
+/// Returns the bytes at the given offset and length, or an error if they are
+/// out of bounds.
+fn read_bytes(data: &[u8], offset: usize, len: usize)
+    -> Result<&[u8], IccError>
+{
+    offset.checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(IccError::Malformed("unexpected end of data"))
+}
+
+/// Reads a big-endian `u16` at the given offset.
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, IccError> {
+    let bytes = read_bytes(data, offset, 2)?;
+    Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// Reads a big-endian `u32` at the given offset.
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, IccError> {
+    let bytes = read_bytes(data, offset, 4)?;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Reads a four byte signature at the given offset.
+fn read_signature(data: &[u8], offset: usize) -> Result<[u8; 4], IccError> {
+    let bytes = read_bytes(data, offset, 4)?;
+    Ok([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Reads an `s15Fixed16Number` at the given offset.
+fn read_s15_fixed16(data: &[u8], offset: usize) -> Result<f32, IccError> {
+    Ok(read_u32(data, offset)? as i32 as f32 / 65536.0)
+}
+
+/// Reads an `XYZNumber` at the given offset.
+fn read_xyz_number(data: &[u8], offset: usize) -> Result<Xyz, IccError> {
+    Ok(Xyz {
+        x: read_s15_fixed16(data, offset)?,
+        y: read_s15_fixed16(data, offset + 4)?,
+        z: read_s15_fixed16(data, offset + 8)?,
+    })
+}
+
+/// The signature and data of a tag.
+type Tag<'a> = ([u8; 4], &'a [u8]);
+
+/// Reads the tag table, returning the signature and data of each tag.
+fn read_tag_table(data: &[u8]) -> Result<Vec<Tag<'_>>, IccError> {
+    let count = read_u32(data, HEADER_SIZE)? as usize;
+    let mut tags = Vec::new();
+    for i in 0..count {
+        let entry = HEADER_SIZE + 4 + i * 12;
+        let signature = read_signature(data, entry)?;
+        let offset = read_u32(data, entry + 4)? as usize;
+        let size = read_u32(data, entry + 8)? as usize;
+        tags.push((signature, read_bytes(data, offset, size)?));
+    }
+    Ok(tags)
+}
+
+/// Returns an error unless the tag has the given type signature.
+fn expect_type(tag: &[u8], signature: &[u8; 4]) -> Result<(), IccError> {
+    let actual = read_signature(tag, 0)?;
+    if &actual == signature {
+        Ok(())
+    } else {
+        Err(IccError::UnsupportedTagType(actual))
+    }
+}
+
+/// Reads an `XYZType` tag holding a single value.
+fn read_xyz_type(tag: &[u8]) -> Result<Xyz, IccError> {
+    expect_type(tag, b"XYZ ")?;
+    read_xyz_number(tag, 8)
+}
+
+/// Reads an `s15Fixed16ArrayType` tag holding a 3×3 matrix.
+fn read_matrix_type(tag: &[u8]) -> Result<[[f32; 3]; 3], IccError> {
+    expect_type(tag, b"sf32")?;
+    let mut matrix = [[0.0; 3]; 3];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = read_s15_fixed16(tag, 8 + (i * 3 + j) * 4)?;
+        }
+    }
+    Ok(matrix)
+}
+
+/// Reads the text of a `textDescriptionType` or `multiLocalizedUnicodeType`
+/// tag, using the first localization of the latter. Returns `None` if the
+/// tag is malformed or of another type.
+fn read_text_type(tag: &[u8]) -> Option<String> {
+    match &read_signature(tag, 0).ok()? {
+        b"desc" => {
+            let len = read_u32(tag, 8).ok()? as usize;
+            let bytes = read_bytes(tag, 12, len).ok()?;
+            let text = bytes.split(|&b| b == 0).next().unwrap_or(&[]);
+            Some(String::from_utf8_lossy(text).into_owned())
+        },
+        b"mluc" => {
+            if read_u32(tag, 8).ok()? == 0 { return None; }
+            let len = read_u32(tag, 20).ok()? as usize;
+            let offset = read_u32(tag, 24).ok()? as usize;
+            let bytes = read_bytes(tag, offset, len).ok()?;
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        },
+        _ => None,
+    }
+}
+
+/// Reads a `curveType` or `parametricCurveType` element, returning the curve
+/// and its size in bytes.
+fn read_curve_type(tag: &[u8]) -> Result<(ToneCurve, usize), IccError> {
+    match &read_signature(tag, 0)? {
+        b"curv" => {
+            let count = read_u32(tag, 8)? as usize;
+            let curve = match count {
+                0 => ToneCurve::Identity,
+                1 => ToneCurve::Gamma(read_u16(tag, 12)? as f32 / 256.0),
+                _ => {
+                    let bytes = read_bytes(tag, 12, count * 2)?;
+                    ToneCurve::Table(bytes
+                        .chunks_exact(2)
+                        .map(|c| u16::from_be_bytes([c[0], c[1]]) as f32
+                            / u16::MAX as f32)
+                        .collect())
+                },
+            };
+            Ok((curve, 12 + count * 2))
+        },
+        b"para" => {
+            let function = read_u16(tag, 8)?;
+            let count = match function {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => return Err(IccError::Malformed(
+                    "unknown parametric curve type")),
+            };
+            let mut params = [0.0; 7];
+            for (i, p) in params.iter_mut().take(count).enumerate() {
+                *p = read_s15_fixed16(tag, 12 + i * 4)?;
+            }
+            let curve = if function == 0 {
+                ToneCurve::Gamma(params[0])
+            } else {
+                ToneCurve::Parametric { function, params }
+            };
+            Ok((curve, 12 + count * 4))
+        },
+        other => Err(IccError::UnsupportedTagType(*other)),
+    }
+}
+
+/// Reads the given number of consecutive curve elements, each padded to a
+/// four byte boundary.
+fn read_curves(tag: &[u8], offset: usize, count: usize)
+    -> Result<Vec<ToneCurve>, IccError>
+{
+    let mut curves = Vec::with_capacity(count);
+    let mut position = offset;
+    for _ in 0..count {
+        let element = tag.get(position..)
+            .ok_or(IccError::Malformed("unexpected end of data"))?;
+        let (curve, size) = read_curve_type(element)?;
+        curves.push(curve);
+        position += (size + 3) & !3;
+    }
+    Ok(curves)
+}
+
+/// Reads the given number of tables of `u8` or `u16` samples as curves.
+fn read_tables(
+    tag: &[u8],
+    offset: usize,
+    count: usize,
+    entries: usize,
+    precision: usize)
+    -> Result<Vec<ToneCurve>, IccError>
+{
+    let values = read_samples(tag, offset, count * entries, precision)?;
+    Ok(values.chunks(entries.max(1))
+        .map(|table| ToneCurve::Table(table.to_vec()))
+        .collect())
+}
+
+/// Reads the given number of `u8` or `u16` samples as ratios.
+fn read_samples(tag: &[u8], offset: usize, count: usize, precision: usize)
+    -> Result<Vec<f32>, IccError>
+{
+    let bytes = read_bytes(tag, offset, count * precision)?;
+    Ok(match precision {
+        1 => bytes.iter().map(|&b| b as f32 / u8::MAX as f32).collect(),
+        _ => bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]) as f32
+                / u16::MAX as f32)
+            .collect(),
+    })
+}
+
+/// Reads a 3×3 matrix stored as nine `s15Fixed16Number` values, optionally
+/// followed by three offsets.
+fn read_matrix(tag: &[u8], offset: usize, offsets: bool)
+    -> Result<Stage, IccError>
+{
+    let mut matrix = [[0.0; 3]; 3];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = read_s15_fixed16(tag, offset + (i * 3 + j) * 4)?;
+        }
+    }
+    let mut translation = [0.0; 3];
+    if offsets {
+        for (i, v) in translation.iter_mut().enumerate() {
+            *v = read_s15_fixed16(tag, offset + 36 + i * 4)?;
+        }
+    }
+    Ok(Stage::Matrix { matrix, offset: translation })
+}
+
+/// Builds a color lookup table, checking that no dimension is empty.
+fn make_clut(grid_points: Vec<usize>, output_channels: usize, values: Vec<f32>)
+    -> Result<Clut, IccError>
+{
+    if grid_points.contains(&0) {
+        return Err(IccError::Malformed("empty lookup table"));
+    }
+    Ok(Clut { grid_points, output_channels, values })
+}
+
+/// Returns the number of samples in a lookup table with the given grid
+/// points and output channels.
+fn clut_samples(grid_points: &[usize], output_channels: usize)
+    -> Result<usize, IccError>
+{
+    grid_points.iter()
+        .try_fold(output_channels, |n, &g| n.checked_mul(g))
+        .ok_or(IccError::Malformed("lookup table too large"))
+}
+
+/// Reads a `lut8Type`, `lut16Type`, `lutAToBType`, or `lutBToAType` tag.
+fn read_lut_type(tag: &[u8]) -> Result<Pipeline, IccError> {
+    let signature = read_signature(tag, 0)?;
+    let inputs = *tag.get(8).ok_or(IccError::Malformed("truncated lut"))?
+        as usize;
+    let outputs = *tag.get(9).ok_or(IccError::Malformed("truncated lut"))?
+        as usize;
+    if inputs == 0 || outputs == 0 || inputs > 15 || outputs > 15 {
+        return Err(IccError::Malformed("invalid lut channel count"));
+    }
+
+    match &signature {
+        b"mft1" | b"mft2" => {
+            let lut8 = &signature == b"mft1";
+            let grid = *tag.get(10)
+                .ok_or(IccError::Malformed("truncated lut"))? as usize;
+            let matrix = read_matrix(tag, 12, false)?;
+            let (precision, input_entries, output_entries, mut offset) =
+                if lut8 {
+                    (1, 256, 256, 48)
+                } else {
+                    (2, read_u16(tag, 48)? as usize,
+                        read_u16(tag, 50)? as usize, 52)
+                };
+
+            let mut stages = Vec::new();
+            let identity = Stage::Matrix {
+                matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                offset: [0.0; 3],
+            };
+            if inputs == 3 && matrix != identity { stages.push(matrix); }
+
+            let curves = read_tables(
+                tag, offset, inputs, input_entries, precision)?;
+            offset += inputs * input_entries * precision;
+            stages.push(Stage::Curves(curves));
+
+            let grid_points = vec![grid; inputs];
+            let samples = clut_samples(&grid_points, outputs)?;
+            let values = read_samples(tag, offset, samples, precision)?;
+            offset += samples * precision;
+            stages.push(Stage::Clut(make_clut(grid_points, outputs, values)?));
+
+            let curves = read_tables(
+                tag, offset, outputs, output_entries, precision)?;
+            stages.push(Stage::Curves(curves));
+
+            Ok(Pipeline {
+                kind: if lut8 { LutKind::Lut8 } else { LutKind::Lut16 },
+                input_channels: inputs,
+                output_channels: outputs,
+                stages,
+            })
+        },
+
+        b"mAB " | b"mBA " => {
+            let a_to_b = &signature == b"mAB ";
+            let b_offset = read_u32(tag, 12)? as usize;
+            let matrix_offset = read_u32(tag, 16)? as usize;
+            let m_offset = read_u32(tag, 20)? as usize;
+            let clut_offset = read_u32(tag, 24)? as usize;
+            let a_offset = read_u32(tag, 28)? as usize;
+
+            // The A curves are on the device side and the B curves on the
+            // PCS side, so the channel counts depend on the direction.
+            let (a_channels, b_channels) = if a_to_b {
+                (inputs, outputs)
+            } else {
+                (outputs, inputs)
+            };
+            if b_offset == 0 {
+                return Err(IccError::Malformed("missing B curves"));
+            }
+            let b = Stage::Curves(read_curves(tag, b_offset, b_channels)?);
+            let m = match m_offset {
+                0 => None,
+                _ => Some(Stage::Curves(read_curves(tag, m_offset, 3)?)),
+            };
+            let matrix = match matrix_offset {
+                0 => None,
+                _ => Some(read_matrix(tag, matrix_offset, true)?),
+            };
+            let a = match a_offset {
+                0 => None,
+                _ => Some(Stage::Curves(
+                    read_curves(tag, a_offset, a_channels)?)),
+            };
+            let clut = match clut_offset {
+                0 => None,
+                _ => {
+                    let grid_points: Vec<usize> = read_bytes(
+                            tag, clut_offset, inputs)?
+                        .iter()
+                        .map(|&g| g as usize)
+                        .collect();
+                    let precision = *tag.get(clut_offset + 16)
+                        .ok_or(IccError::Malformed("truncated lut"))?
+                        as usize;
+                    if precision != 1 && precision != 2 {
+                        return Err(IccError::Malformed(
+                            "invalid lookup table precision"));
+                    }
+                    let samples = clut_samples(&grid_points, outputs)?;
+                    let values = read_samples(
+                        tag, clut_offset + 20, samples, precision)?;
+                    Some(Stage::Clut(make_clut(grid_points, outputs, values)?))
+                },
+            };
+
+            let stages = if a_to_b {
+                vec![a, clut, m, matrix, Some(b)]
+            } else {
+                vec![Some(b), matrix, m, clut, a]
+            };
+            Ok(Pipeline {
+                kind: if a_to_b { LutKind::AToB } else { LutKind::BToA },
+                input_channels: inputs,
+                output_channels: outputs,
+                stages: stages.into_iter().flatten().collect(),
+            })
+        },
+
+        _ => Err(IccError::UnsupportedTagType(signature)),
+    }
+}
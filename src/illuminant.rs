@@ -0,0 +1,298 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Standard illuminants and their white points.
+//!
+//! An [`Illuminant`] is a standard light source defined by the CIE. Its white
+//! point is the color of a perfectly white surface lit by it, and is the
+//! reference for converting between [`Xyz`] and spaces such as [`Lab`]. The
+//! sRGB color space is defined relative to [`Illuminant::D65`], while print
+//! workflows commonly use [`Illuminant::D50`].
+//!
+//! Colors are re-referenced from one white point to another by a chromatic
+//! adaptation transform, such as with [`Xyz::adapt`], which models the way
+//! the eye adjusts to a change of illuminant.
+//!
+//! [`Illuminant`]: enum.Illuminant.html
+//! [`Illuminant::D65`]: enum.Illuminant.html#variant.D65
+//! [`Illuminant::D50`]: enum.Illuminant.html#variant.D50
+//! [`Xyz`]: ../struct.Xyz.html
+//! [`Xyz::adapt`]: ../struct.Xyz.html#method.adapt
+//! [`Lab`]: ../struct.Lab.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::utility::matrix_inverse;
+use crate::utility::matrix_product;
+use crate::utility::matrix_transform;
+use crate::utility::MatrixElement;
+use crate::Xyz;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The Bradford matrix converting CIE XYZ to sharpened cone responses.
+const BRADFORD: [[f32; 3]; 3] = [
+    [ 0.8951,  0.2664, -0.1614],
+    [-0.7502,  1.7135,  0.0367],
+    [ 0.0389, -0.0685,  1.0296],
+];
+
+/// The Hunt-Pointer-Estévez matrix converting CIE XYZ to cone responses,
+/// normalized to D65.
+const VON_KRIES: [[f32; 3]; 3] = [
+    [ 0.40024, 0.70760, -0.08081],
+    [-0.22630, 1.16532,  0.04570],
+    [ 0.0,     0.0,      0.91822],
+];
+
+/// The CIECAM02 matrix converting CIE XYZ to sharpened cone responses.
+const CAT02: [[f32; 3]; 3] = [
+    [ 0.7328, 0.4296, -0.1624],
+    [-0.7036, 1.6975,  0.0061],
+    [ 0.0030, 0.0136,  0.9834],
+];
+
+/// The CAM16 matrix converting CIE XYZ to sharpened cone responses.
+const CAT16: [[f32; 3]; 3] = [
+    [ 0.401288, 0.650173, -0.051461],
+    [-0.250268, 1.204414,  0.045854],
+    [-0.002079, 0.048952,  0.953127],
+];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Illuminant
+////////////////////////////////////////////////////////////////////////////////
+/// A CIE standard illuminant.
+///
+/// Chromaticities are those of the CIE 1931 2° standard observer.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::illuminant::Illuminant;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(Illuminant::D65.chromaticity(), [0.31271, 0.32902]);
+///
+/// let white = Illuminant::D50.white_point();
+/// assert_eq!(white.y, 1.0);
+/// assert!((white.x - 0.9642).abs() < 0.001);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Illuminant {
+    /// Incandescent tungsten light, at about 2856 K.
+    A,
+    /// Average daylight, an obsolete predecessor of D65, at about 6774 K.
+    C,
+    /// Horizon daylight, at about 5003 K. This is the reference white of ICC
+    /// profiles and print workflows.
+    D50,
+    /// Mid-morning or mid-afternoon daylight, at about 5503 K.
+    D55,
+    /// Noon daylight, at about 6504 K. This is the reference white of sRGB
+    /// and most display color spaces.
+    #[default]
+    D65,
+    /// North sky daylight, at about 7504 K.
+    D75,
+    /// The equal energy illuminant, a theoretical source with equal power at
+    /// every wavelength.
+    E,
+    /// Daylight fluorescent light, at about 6430 K.
+    F1,
+    /// Cool white fluorescent light, at about 4230 K.
+    F2,
+    /// White fluorescent light, at about 3450 K.
+    F3,
+    /// Warm white fluorescent light, at about 2940 K.
+    F4,
+    /// Daylight fluorescent light, at about 6350 K.
+    F5,
+    /// Lite white fluorescent light, at about 4150 K.
+    F6,
+    /// Broadband D65 simulator fluorescent light, at about 6500 K.
+    F7,
+    /// Broadband D50 simulator fluorescent light, at about 5000 K.
+    F8,
+    /// Broadband cool white deluxe fluorescent light, at about 4150 K.
+    F9,
+    /// Narrowband fluorescent light, at about 5000 K.
+    F10,
+    /// Narrowband fluorescent light, at about 4000 K.
+    F11,
+    /// Narrowband fluorescent light, at about 3000 K.
+    F12,
+}
+
+impl Illuminant {
+    /// Returns the `[x, y]` chromaticity of the illuminant.
+    pub const fn chromaticity(self) -> [f32; 2] {
+        use Illuminant::*;
+        match self {
+            A   => [0.44757, 0.40745],
+            C   => [0.31006, 0.31616],
+            D50 => [0.34567, 0.35850],
+            D55 => [0.33242, 0.34743],
+            D65 => [0.31271, 0.32902],
+            D75 => [0.29902, 0.31485],
+            E   => [1.0 / 3.0, 1.0 / 3.0],
+            F1  => [0.31310, 0.33727],
+            F2  => [0.37208, 0.37529],
+            F3  => [0.40910, 0.39430],
+            F4  => [0.44018, 0.40329],
+            F5  => [0.31379, 0.34531],
+            F6  => [0.37790, 0.38835],
+            F7  => [0.31292, 0.32933],
+            F8  => [0.34588, 0.35875],
+            F9  => [0.37417, 0.37281],
+            F10 => [0.34609, 0.35986],
+            F11 => [0.38052, 0.37713],
+            F12 => [0.43695, 0.40441],
+        }
+    }
+
+    /// Returns the white point of the illuminant, normalized to a luminance
+    /// `y` of 1.
+    pub const fn white_point(self) -> Xyz {
+        let [x, y] = self.chromaticity();
+        Xyz { x: x / y, y: 1.0, z: (1.0 - x - y) / y }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// AdaptationMethod
+////////////////////////////////////////////////////////////////////////////////
+/// A chromatic adaptation transform, used to re-reference colors from one
+/// white point to another.
+///
+/// Each method converts colors to a space of cone responses, scales each
+/// response by the ratio of the target and source white points, and converts
+/// back. They differ only in their cone response matrices.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AdaptationMethod {
+    /// The Bradford transform, used by ICC profiles and most color
+    /// management systems.
+    #[default]
+    Bradford,
+    /// The von Kries transform, using the Hunt-Pointer-Estévez cone
+    /// responses.
+    VonKries,
+    /// The transform of the CIECAM02 color appearance model.
+    Cat02,
+    /// The transform of the CAM16 color appearance model, which corrects
+    /// problems of CAT02 with highly saturated colors.
+    Cat16,
+}
+
+impl AdaptationMethod {
+    /// Returns the matrix converting CIE XYZ to cone responses.
+    fn cone_matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            AdaptationMethod::Bradford => BRADFORD,
+            AdaptationMethod::VonKries => VON_KRIES,
+            AdaptationMethod::Cat02    => CAT02,
+            AdaptationMethod::Cat16    => CAT16,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// adaptation_matrix
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the matrix adapting [`Xyz`] colors from the given source white
+/// point to the given target white point, using the given method.
+///
+/// The white points need not be those of standard illuminants, so this can
+/// adapt colors to a white point estimated from an image.
+///
+/// [`Xyz`]: ../struct.Xyz.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::illuminant::AdaptationMethod;
+/// # use color::illuminant::Illuminant;
+/// # use color::illuminant::adaptation_matrix;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let matrix = adaptation_matrix(
+///     Illuminant::D65.white_point(),
+///     Illuminant::D50.white_point(),
+///     AdaptationMethod::Bradford);
+///
+/// assert!((matrix[0][0] - 1.0479).abs() < 0.001);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn adaptation_matrix(source: Xyz, target: Xyz, method: AdaptationMethod)
+    -> [[f32; 3]; 3]
+{
+    cone_adaptation(source.components(), target.components(), method)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the matrix adapting XYZ components from the given source white
+/// point to the given target white point in double precision.
+pub(in crate) fn adaptation_matrix_f64(
+    source: [f64; 3],
+    target: [f64; 3],
+    method: AdaptationMethod)
+    -> [[f64; 3]; 3]
+{
+    cone_adaptation(source, target, method)
+}
+
+/// Returns the matrix adapting XYZ components from the given source white
+/// point to the given target white point.
+fn cone_adaptation<T>(source: [T; 3], target: [T; 3], method: AdaptationMethod)
+    -> [[T; 3]; 3]
+    where T: MatrixElement
+{
+    let cone = method.cone_matrix().map(|row| row.map(T::from));
+    let s = matrix_transform(cone, source);
+    let t = matrix_transform(cone, target);
+    let zero = T::default();
+    let scale = [
+        [t[0] / s[0], zero, zero],
+        [zero, t[1] / s[1], zero],
+        [zero, zero, t[2] / s[2]],
+    ];
+    matrix_product(matrix_inverse(cone), matrix_product(scale, cone))
+}
@@ -0,0 +1,28 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Interoperability with other crates.
+//!
+//! Each integration is enabled by the feature of the same name, and consists
+//! of conversion impls between this crate's colors and the other crate's
+//! types, along with any helpers listed below.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Internal modules.
+#[cfg(feature = "approx")]
+pub mod approx;
+#[cfg(feature = "image")]
+pub mod image;
+#[cfg(feature = "palette")]
+pub mod palette;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "quickcheck")]
+pub mod quickcheck;
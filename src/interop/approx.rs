@@ -0,0 +1,109 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Approximate equality using the [approx](https://crates.io/crates/approx)
+//! crate.
+//!
+//! The floating point colors implement `AbsDiffEq`, `RelativeEq`, and
+//! `UlpsEq`, comparing each component with the given tolerance. Hue
+//! components are compared as numbers, so hues just above 0 and just below
+//! 360 degrees are not approximately equal.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # use color::Lab;
+//! # use color::Rgb;
+//! # use approx::assert_relative_eq;
+//! # fn example() -> Result<(), Box<dyn Error>> {
+//! # //-------------------------------------------------------------------
+//! let lab = Lab::new(53.24, 80.09, 67.2);
+//!
+//! assert_relative_eq!(Lab::from(Rgb::new(255, 0, 0)), lab, epsilon = 0.01);
+//! # //-------------------------------------------------------------------
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     example().unwrap();
+//! # }
+//! ```
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Hsl;
+use crate::Hsv;
+use crate::Lab;
+use crate::Lab64;
+use crate::Lch;
+use crate::Oklab;
+use crate::Oklch;
+use crate::Xyz;
+use crate::Xyz64;
+
+// External library imports.
+use ::approx::AbsDiffEq;
+use ::approx::RelativeEq;
+use ::approx::UlpsEq;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Approximate equality
+////////////////////////////////////////////////////////////////////////////////
+macro_rules! impl_approx_eq {
+    ($t:ty, $f:ty, [$($c:ident),*]) => {
+        impl AbsDiffEq for $t {
+            type Epsilon = $f;
+
+            fn default_epsilon() -> $f {
+                <$f>::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: $f) -> bool {
+                $(self.$c.abs_diff_eq(&other.$c, epsilon))&&*
+            }
+        }
+
+        impl RelativeEq for $t {
+            fn default_max_relative() -> $f {
+                <$f>::default_max_relative()
+            }
+
+            fn relative_eq(&self, other: &Self, epsilon: $f, max_relative: $f)
+                -> bool
+            {
+                $(self.$c.relative_eq(&other.$c, epsilon, max_relative))&&*
+            }
+        }
+
+        impl UlpsEq for $t {
+            fn default_max_ulps() -> u32 {
+                <$f>::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: $f, max_ulps: u32)
+                -> bool
+            {
+                $(self.$c.ulps_eq(&other.$c, epsilon, max_ulps))&&*
+            }
+        }
+    };
+}
+
+impl_approx_eq!(Hsl, f32, [h, s, l]);
+impl_approx_eq!(Hsv, f32, [h, s, v]);
+impl_approx_eq!(Lab, f32, [l, a, b]);
+impl_approx_eq!(Lch, f32, [l, c, h]);
+impl_approx_eq!(Oklab, f32, [l, a, b]);
+impl_approx_eq!(Oklch, f32, [l, c, h]);
+impl_approx_eq!(Xyz, f32, [x, y, z]);
+impl_approx_eq!(Lab64, f64, [l, a, b]);
+impl_approx_eq!(Xyz64, f64, [x, y, z]);
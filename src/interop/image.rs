@@ -0,0 +1,312 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Interoperability with the [image](https://crates.io/crates/image) crate.
+//!
+//! Colors convert to and from the matching `image` pixel types:
+//!
+//! | Color    | Pixel             |
+//! | -------- | ----------------- |
+//! | `Rgb`    | `image::Rgb<u8>`  |
+//! | `Rgba`   | `image::Rgba<u8>` |
+//! | `Luma`   | `image::Luma<u8>` |
+//! | `Rgb48`  | `image::Rgb<u16>` |
+//! | `Rgba64` | `image::Rgba<u16>`|
+//!
+//! The functions in this module run palette extraction and quantization
+//! directly on a `DynamicImage`, in any of its pixel formats.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Luma;
+use crate::metric::Metric;
+use crate::palette::Palette;
+use crate::quantize::palette_indices;
+use crate::Rgb;
+use crate::Rgb48;
+use crate::Rgba;
+use crate::Rgba64;
+use crate::trace::Level;
+use crate::trace::span;
+
+// External library imports.
+use ::image::DynamicImage;
+use ::image::RgbImage;
+use ::image::RgbaImage;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Pixel conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<::image::Rgb<u8>> for Rgb {
+    fn from(pixel: ::image::Rgb<u8>) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<image::Rgb<u8>>");
+        let _enter = span.enter();
+
+        Rgb::from(pixel.0)
+    }
+}
+
+impl From<Rgb> for ::image::Rgb<u8> {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "image::Rgb<u8>::from<Rgb>");
+        let _enter = span.enter();
+
+        ::image::Rgb(rgb.octets())
+    }
+}
+
+impl From<::image::Rgba<u8>> for Rgba {
+    fn from(pixel: ::image::Rgba<u8>) -> Self {
+        let span = span!(Level::DEBUG, "Rgba::from<image::Rgba<u8>>");
+        let _enter = span.enter();
+
+        Rgba::from(pixel.0)
+    }
+}
+
+impl From<Rgba> for ::image::Rgba<u8> {
+    fn from(rgba: Rgba) -> Self {
+        let span = span!(Level::DEBUG, "image::Rgba<u8>::from<Rgba>");
+        let _enter = span.enter();
+
+        ::image::Rgba(rgba.octets())
+    }
+}
+
+impl From<::image::Luma<u8>> for Luma {
+    fn from(pixel: ::image::Luma<u8>) -> Self {
+        let span = span!(Level::DEBUG, "Luma::from<image::Luma<u8>>");
+        let _enter = span.enter();
+
+        Luma::new(pixel.0[0])
+    }
+}
+
+impl From<Luma> for ::image::Luma<u8> {
+    fn from(luma: Luma) -> Self {
+        let span = span!(Level::DEBUG, "image::Luma<u8>::from<Luma>");
+        let _enter = span.enter();
+
+        ::image::Luma([luma.y])
+    }
+}
+
+impl From<::image::Rgb<u16>> for Rgb48 {
+    fn from(pixel: ::image::Rgb<u16>) -> Self {
+        let span = span!(Level::DEBUG, "Rgb48::from<image::Rgb<u16>>");
+        let _enter = span.enter();
+
+        let [r, g, b] = pixel.0;
+        Rgb48::new(r, g, b)
+    }
+}
+
+impl From<Rgb48> for ::image::Rgb<u16> {
+    fn from(rgb: Rgb48) -> Self {
+        let span = span!(Level::DEBUG, "image::Rgb<u16>::from<Rgb48>");
+        let _enter = span.enter();
+
+        ::image::Rgb([rgb.r, rgb.g, rgb.b])
+    }
+}
+
+impl From<::image::Rgba<u16>> for Rgba64 {
+    fn from(pixel: ::image::Rgba<u16>) -> Self {
+        let span = span!(Level::DEBUG, "Rgba64::from<image::Rgba<u16>>");
+        let _enter = span.enter();
+
+        let [r, g, b, a] = pixel.0;
+        Rgba64::new(r, g, b, a)
+    }
+}
+
+impl From<Rgba64> for ::image::Rgba<u16> {
+    fn from(rgba: Rgba64) -> Self {
+        let span = span!(Level::DEBUG, "image::Rgba<u16>::from<Rgba64>");
+        let _enter = span.enter();
+
+        ::image::Rgba([rgba.r, rgba.g, rgba.b, rgba.a])
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// rgb_pixels
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the pixels of the given image as `Rgb` colors, in row-major order.
+///
+/// Images in other pixel formats are converted to 8-bit RGB first, and any
+/// alpha channel is discarded.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::interop::image::rgb_pixels;
+/// # use image::DynamicImage;
+/// # use image::GrayImage;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let gray = GrayImage::from_raw(2, 1, vec![0, 200]).unwrap();
+///
+/// let pixels = rgb_pixels(&DynamicImage::ImageLuma8(gray));
+///
+/// assert_eq!(pixels, [Rgb::new(0, 0, 0), Rgb::new(200, 200, 200)]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn rgb_pixels(image: &DynamicImage) -> Vec<Rgb> {
+    let span = span!(Level::DEBUG, "rgb_pixels");
+    let _enter = span.enter();
+
+    match image {
+        DynamicImage::ImageRgb8(buffer) => buffer
+            .pixels()
+            .map(|&pixel| Rgb::from(pixel))
+            .collect(),
+        _ => image
+            .to_rgb8()
+            .pixels()
+            .map(|&pixel| Rgb::from(pixel))
+            .collect(),
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// extract_palette
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the palette extracted from the pixels of the given image by the
+/// given extraction function, such as [`median_cut`] or [`k_means`].
+///
+/// The pixels are read as by [`rgb_pixels`].
+///
+/// [`median_cut`]: ../../quantize/fn.median_cut.html
+/// [`k_means`]: ../../quantize/fn.k_means.html
+/// [`rgb_pixels`]: fn.rgb_pixels.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::interop::image::extract_palette;
+/// # use color::quantize::median_cut;
+/// # use image::DynamicImage;
+/// # use image::RgbImage;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let buffer = RgbImage::from_fn(4, 4, |x, _| if x < 2 {
+///     image::Rgb([250, 10, 10])
+/// } else {
+///     image::Rgb([10, 10, 250])
+/// });
+///
+/// let palette = extract_palette(
+///     &DynamicImage::ImageRgb8(buffer),
+///     |pixels| median_cut(pixels, 2));
+///
+/// assert_eq!(palette.len(), 2);
+/// assert_eq!(palette.get("#fa0a0a"), Some(Rgb::new(250, 10, 10)));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn extract_palette<F>(image: &DynamicImage, extract: F) -> Palette
+    where F: FnOnce(&[Rgb]) -> Palette
+{
+    let span = span!(Level::DEBUG, "extract_palette");
+    let _enter = span.enter();
+
+    extract(&rgb_pixels(image))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// quantize
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a copy of the given image with each pixel replaced by the nearest
+/// color in `palette`, as measured by the given [`Metric`].
+///
+/// The result is an 8-bit RGBA image if the given image has an alpha channel,
+/// in which case the alpha of each pixel is kept, and an 8-bit RGB image
+/// otherwise. Returns an unmodified copy if the palette is empty.
+///
+/// [`Metric`]: ../../metric/trait.Metric.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::interop::image::quantize;
+/// # use color::metric::Euclidean;
+/// # use color::palette::Palette;
+/// # use image::DynamicImage;
+/// # use image::RgbImage;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let buffer = RgbImage::from_raw(2, 1, vec![240, 20, 10, 20, 10, 240])
+///     .unwrap();
+/// let mut palette = Palette::new();
+/// let _ = palette.insert("red", Rgb::new(255, 0, 0));
+/// let _ = palette.insert("blue", Rgb::new(0, 0, 255));
+///
+/// let quantized = quantize(
+///     &DynamicImage::ImageRgb8(buffer),
+///     &palette,
+///     Euclidean::Oklab);
+///
+/// assert_eq!(quantized.as_bytes(), &[255, 0, 0, 0, 0, 255]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn quantize<M>(image: &DynamicImage, palette: &Palette, metric: M)
+    -> DynamicImage
+    where M: Metric
+{
+    let span = span!(Level::DEBUG, "quantize");
+    let _enter = span.enter();
+
+    if palette.is_empty() { return image.clone(); }
+
+    let colors: Vec<Rgb> = palette.colors().collect();
+    let indices = palette_indices(&rgb_pixels(image), palette, metric);
+    let (width, height) = (image.width(), image.height());
+
+    if image.color().has_alpha() {
+        let mut buffer: RgbaImage = image.to_rgba8();
+        for (pixel, &index) in buffer.pixels_mut().zip(indices.iter()) {
+            *pixel = Rgba::from_rgb(colors[index], pixel.0[3]).into();
+        }
+        DynamicImage::ImageRgba8(buffer)
+    } else {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let index = indices[(y as usize) * (width as usize) + x as usize];
+            colors[index].into()
+        }))
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Interoperability with the [palette](https://crates.io/crates/palette)
+//! crate.
+//!
+//! Colors convert to and from the matching `palette` types:
+//!
+//! | Color   | palette type              |
+//! | ------- | ------------------------- |
+//! | `Rgb`   | `Srgb<u8>`, `Srgb<f32>`   |
+//! | `Rgba`  | `Srgba<u8>`               |
+//! | `Xyz`   | `Xyz<D65, f32>`           |
+//! | `Lab`   | `Lab<D65, f32>`           |
+//! | `Lch`   | `Lch<D65, f32>`           |
+//! | `Oklab` | `Oklab<f32>`              |
+//!
+//! Both crates use the same component ranges for these types, so the
+//! components are copied unchanged, except that `Srgb<f32>` components are
+//! ratios between 0 and 1. Converting `Srgb<f32>` to `Rgb` rounds to the
+//! nearest octet, as `palette`'s own `into_format` does.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # use color::Lab;
+//! # use color::Rgb;
+//! # fn example() -> Result<(), Box<dyn Error>> {
+//! # //-------------------------------------------------------------------
+//! let srgb: palette::Srgb<u8> = Rgb::new(255, 99, 71).into();
+//! assert_eq!(srgb, palette::Srgb::new(255, 99, 71));
+//!
+//! let lab = Lab::new(50.0, 20.0, -30.0);
+//! let other: palette::Lab = lab.into();
+//! assert_eq!(Lab::from(other), lab);
+//! # //-------------------------------------------------------------------
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     example().unwrap();
+//! # }
+//! ```
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Lab;
+use crate::Lch;
+use crate::Oklab;
+use crate::Rgb;
+use crate::Rgba;
+use crate::trace::Level;
+use crate::trace::span;
+use crate::Xyz;
+
+// External library imports.
+use ::palette::white_point::D65;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Srgb conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<::palette::Srgb<u8>> for Rgb {
+    fn from(color: ::palette::Srgb<u8>) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<palette::Srgb<u8>>");
+        let _enter = span.enter();
+
+        Rgb::new(color.red, color.green, color.blue)
+    }
+}
+
+impl From<Rgb> for ::palette::Srgb<u8> {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "palette::Srgb<u8>::from<Rgb>");
+        let _enter = span.enter();
+
+        ::palette::Srgb::new(rgb.r, rgb.g, rgb.b)
+    }
+}
+
+impl From<::palette::Srgb<f32>> for Rgb {
+    fn from(color: ::palette::Srgb<f32>) -> Self {
+        let span = span!(Level::DEBUG, "Rgb::from<palette::Srgb<f32>>");
+        let _enter = span.enter();
+
+        Rgb::from(color.into_format::<u8>())
+    }
+}
+
+impl From<Rgb> for ::palette::Srgb<f32> {
+    fn from(rgb: Rgb) -> Self {
+        let span = span!(Level::DEBUG, "palette::Srgb<f32>::from<Rgb>");
+        let _enter = span.enter();
+
+        let [r, g, b] = rgb.ratios();
+        ::palette::Srgb::new(r, g, b)
+    }
+}
+
+impl From<::palette::Srgba<u8>> for Rgba {
+    fn from(color: ::palette::Srgba<u8>) -> Self {
+        let span = span!(Level::DEBUG, "Rgba::from<palette::Srgba<u8>>");
+        let _enter = span.enter();
+
+        Rgba::new(color.red, color.green, color.blue, color.alpha)
+    }
+}
+
+impl From<Rgba> for ::palette::Srgba<u8> {
+    fn from(rgba: Rgba) -> Self {
+        let span = span!(Level::DEBUG, "palette::Srgba<u8>::from<Rgba>");
+        let _enter = span.enter();
+
+        ::palette::Srgba::new(rgba.r, rgba.g, rgba.b, rgba.a)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Xyz conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<::palette::Xyz<D65, f32>> for Xyz {
+    fn from(color: ::palette::Xyz<D65, f32>) -> Self {
+        let span = span!(Level::DEBUG, "Xyz::from<palette::Xyz>");
+        let _enter = span.enter();
+
+        Xyz::new(color.x, color.y, color.z)
+    }
+}
+
+impl From<Xyz> for ::palette::Xyz<D65, f32> {
+    fn from(xyz: Xyz) -> Self {
+        let span = span!(Level::DEBUG, "palette::Xyz::from<Xyz>");
+        let _enter = span.enter();
+
+        ::palette::Xyz::new(xyz.x, xyz.y, xyz.z)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lab conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<::palette::Lab<D65, f32>> for Lab {
+    fn from(color: ::palette::Lab<D65, f32>) -> Self {
+        let span = span!(Level::DEBUG, "Lab::from<palette::Lab>");
+        let _enter = span.enter();
+
+        Lab::new(color.l, color.a, color.b)
+    }
+}
+
+impl From<Lab> for ::palette::Lab<D65, f32> {
+    fn from(lab: Lab) -> Self {
+        let span = span!(Level::DEBUG, "palette::Lab::from<Lab>");
+        let _enter = span.enter();
+
+        ::palette::Lab::new(lab.l, lab.a, lab.b)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lch conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<::palette::Lch<D65, f32>> for Lch {
+    fn from(color: ::palette::Lch<D65, f32>) -> Self {
+        let span = span!(Level::DEBUG, "Lch::from<palette::Lch>");
+        let _enter = span.enter();
+
+        Lch::new(color.l, color.chroma, color.hue.into_positive_degrees())
+    }
+}
+
+impl From<Lch> for ::palette::Lch<D65, f32> {
+    fn from(lch: Lch) -> Self {
+        let span = span!(Level::DEBUG, "palette::Lch::from<Lch>");
+        let _enter = span.enter();
+
+        ::palette::Lch::new(lch.l, lch.c, lch.h)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Oklab conversions
+////////////////////////////////////////////////////////////////////////////////
+impl From<::palette::Oklab<f32>> for Oklab {
+    fn from(color: ::palette::Oklab<f32>) -> Self {
+        let span = span!(Level::DEBUG, "Oklab::from<palette::Oklab>");
+        let _enter = span.enter();
+
+        Oklab::new(color.l, color.a, color.b)
+    }
+}
+
+impl From<Oklab> for ::palette::Oklab<f32> {
+    fn from(oklab: Oklab) -> Self {
+        let span = span!(Level::DEBUG, "palette::Oklab::from<Oklab>");
+        let _enter = span.enter();
+
+        ::palette::Oklab::new(oklab.l, oklab.a, oklab.b)
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Property testing strategies using the
+//! [proptest](https://crates.io/crates/proptest) crate.
+//!
+//! Every color type implements `Arbitrary`, generating only valid colors:
+//!
+//! | Colors | Generated values |
+//! | ------ | ---------------- |
+//! | `Rgb`, `Rgba`, `Rgb48`, `Rgba64`, `Cmyk`, `Luma` | Any components. |
+//! | `Hsl`, `Hsv` | Hues in [0, 360), and other components in [0, 1]. |
+//! | `Xyz`, `Xyz64`, `Lab`, `Lab64`, `Lch`, `Oklab`, `Oklch`, `YCoCgR`, `Color` | Conversions of arbitrary `Rgb` colors, so always finite and within the sRGB gamut. |
+//!
+//! # Example
+//!
+//! ```rust
+//! # use color::Oklab;
+//! # use color::Rgb;
+//! # use proptest::prelude::*;
+//! proptest! {
+//!     fn oklab_round_trip(rgb in any::<Rgb>()) {
+//!         prop_assert_eq!(Rgb::from(Oklab::from(rgb)), rgb);
+//!     }
+//! }
+//! # fn main() { oklab_round_trip(); }
+//! ```
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Cmyk;
+use crate::Color;
+use crate::Hsl;
+use crate::Hsv;
+use crate::Lab;
+use crate::Lab64;
+use crate::Lch;
+use crate::Luma;
+use crate::Oklab;
+use crate::Oklch;
+use crate::Rgb;
+use crate::Rgb48;
+use crate::Rgba;
+use crate::Rgba64;
+use crate::Xyz;
+use crate::Xyz64;
+use crate::YCoCgR;
+
+// External library imports.
+use ::proptest::arbitrary::any;
+use ::proptest::arbitrary::Arbitrary;
+use ::proptest::strategy::BoxedStrategy;
+use ::proptest::strategy::Strategy;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Component strategies
+////////////////////////////////////////////////////////////////////////////////
+impl Arbitrary for Rgb {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Rgb>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u8; 3]>().prop_map(Rgb::from).boxed()
+    }
+}
+
+impl Arbitrary for Rgba {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Rgba>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u8; 4]>().prop_map(Rgba::from).boxed()
+    }
+}
+
+impl Arbitrary for Rgb48 {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Rgb48>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u16; 3]>().prop_map(|[r, g, b]| Rgb48::new(r, g, b)).boxed()
+    }
+}
+
+impl Arbitrary for Rgba64 {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Rgba64>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u16; 4]>()
+            .prop_map(|[r, g, b, a]| Rgba64::new(r, g, b, a))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Cmyk {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Cmyk>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u8; 4]>()
+            .prop_map(|[c, m, y, k]| Cmyk::new(c, m, y, k))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Luma {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Luma>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<u8>().prop_map(Luma::new).boxed()
+    }
+}
+
+impl Arbitrary for Hsl {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Hsl>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0.0f32..360.0, 0.0f32..=1.0, 0.0f32..=1.0)
+            .prop_map(|(h, s, l)| Hsl::new(h, s, l))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Hsv {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Hsv>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0.0f32..360.0, 0.0f32..=1.0, 0.0f32..=1.0)
+            .prop_map(|(h, s, v)| Hsv::new(h, s, v))
+            .boxed()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Converted strategies
+////////////////////////////////////////////////////////////////////////////////
+macro_rules! impl_arbitrary_from_rgb {
+    ($($t:ty),*) => {
+        $(
+            impl Arbitrary for $t {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<$t>;
+
+                fn arbitrary_with(_: ()) -> Self::Strategy {
+                    any::<Rgb>().prop_map(<$t>::from).boxed()
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_from_rgb!(
+    Xyz, Xyz64, Lab, Lab64, Lch, Oklab, Oklch, YCoCgR, Color);
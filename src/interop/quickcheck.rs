@@ -0,0 +1,190 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Property testing generators using the
+//! [quickcheck](https://crates.io/crates/quickcheck) crate.
+//!
+//! Every color type implements `Arbitrary`, generating and shrinking to only
+//! valid colors:
+//!
+//! | Colors | Generated values |
+//! | ------ | ---------------- |
+//! | `Rgb`, `Rgba`, `Rgb48`, `Rgba64`, `Cmyk`, `Luma` | Any components. |
+//! | `Hsl`, `Hsv` | Hues in [0, 360), and other components in [0, 1]. |
+//! | `Xyz`, `Xyz64`, `Lab`, `Lab64`, `Lch`, `Oklab`, `Oklch`, `YCoCgR`, `Color` | Conversions of arbitrary `Rgb` colors, so always finite and within the sRGB gamut. |
+//!
+//! # Example
+//!
+//! ```rust
+//! # use color::Oklab;
+//! # use color::Rgb;
+//! # use quickcheck::quickcheck;
+//! fn oklab_round_trip(rgb: Rgb) -> bool {
+//!     Rgb::from(Oklab::from(rgb)) == rgb
+//! }
+//!
+//! quickcheck(oklab_round_trip as fn(Rgb) -> bool);
+//! ```
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Cmyk;
+use crate::Color;
+use crate::Hsl;
+use crate::Hsv;
+use crate::Lab;
+use crate::Lab64;
+use crate::Lch;
+use crate::Luma;
+use crate::Oklab;
+use crate::Oklch;
+use crate::Rgb;
+use crate::Rgb48;
+use crate::Rgba;
+use crate::Rgba64;
+use crate::Xyz;
+use crate::Xyz64;
+use crate::YCoCgR;
+
+// External library imports.
+use ::quickcheck::Arbitrary;
+use ::quickcheck::Gen;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Component generators
+////////////////////////////////////////////////////////////////////////////////
+impl Arbitrary for Rgb {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Rgb::from(<[u8; 3]>::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new(self.octets().shrink().map(Rgb::from))
+    }
+}
+
+impl Arbitrary for Rgba {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Rgba::from(<[u8; 4]>::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new(self.octets().shrink().map(Rgba::from))
+    }
+}
+
+impl Arbitrary for Rgb48 {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Rgb48::new(u16::arbitrary(g), u16::arbitrary(g), u16::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new([self.r, self.g, self.b]
+            .shrink()
+            .map(|[r, g, b]| Rgb48::new(r, g, b)))
+    }
+}
+
+impl Arbitrary for Rgba64 {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Rgba64::new(
+            u16::arbitrary(g),
+            u16::arbitrary(g),
+            u16::arbitrary(g),
+            u16::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new([self.r, self.g, self.b, self.a]
+            .shrink()
+            .map(|[r, g, b, a]| Rgba64::new(r, g, b, a)))
+    }
+}
+
+impl Arbitrary for Cmyk {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let [c, m, y, k] = <[u8; 4]>::arbitrary(g);
+        Cmyk::new(c, m, y, k)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new([self.c, self.m, self.y, self.k]
+            .shrink()
+            .map(|[c, m, y, k]| Cmyk::new(c, m, y, k)))
+    }
+}
+
+impl Arbitrary for Luma {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Luma::new(u8::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new(self.y.shrink().map(Luma::new))
+    }
+}
+
+impl Arbitrary for Hsl {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Hsl::new(arbitrary_hue(g), arbitrary_ratio(g), arbitrary_ratio(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new((self.h, self.s, self.l)
+            .shrink()
+            .map(|(h, s, l)| Hsl::new(h, s, l)))
+    }
+}
+
+impl Arbitrary for Hsv {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Hsv::new(arbitrary_hue(g), arbitrary_ratio(g), arbitrary_ratio(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        Box::new((self.h, self.s, self.v)
+            .shrink()
+            .map(|(h, s, v)| Hsv::new(h, s, v)))
+    }
+}
+
+/// Returns an arbitrary hue in degrees, within [0, 360).
+fn arbitrary_hue(g: &mut Gen) -> f32 {
+    f32::from(u16::arbitrary(g)) / 65536.0 * 360.0
+}
+
+/// Returns an arbitrary ratio, within [0, 1].
+fn arbitrary_ratio(g: &mut Gen) -> f32 {
+    f32::from(u16::arbitrary(g)) / f32::from(u16::MAX)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Converted generators
+////////////////////////////////////////////////////////////////////////////////
+macro_rules! impl_arbitrary_from_rgb {
+    ($($t:ty),*) => {
+        $(
+            impl Arbitrary for $t {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    <$t>::from(Rgb::arbitrary(g))
+                }
+
+                fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+                    Box::new(Rgb::from(*self).shrink().map(<$t>::from))
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_from_rgb!(
+    Xyz, Xyz64, Lab, Lab64, Lch, Oklab, Oklch, YCoCgR, Color);
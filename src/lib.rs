@@ -12,14 +12,27 @@
 //! | Feature | Description |
 //! | ------- | ----------- |
 //! | "serde" | Enables serialization and deserialization of data using [serde](https://crates.io/crates/serde). |
-//! | "parse" | Enables FromStr implementations for colors. |
+//! | "parse" | Enables parsing CSS colors through the FromStr implementations of `Rgb` and `Rgba`. |
+//! | "naming" | Enables locale-aware basic color term naming in the `naming` module. |
+//! | "icc" | Enables ICC profile parsing and transforms in the `icc` module. |
+//! | "json" | Enables the JSON palette format. Implies "serde". |
+//! | "rayon" | Enables the `par_` bulk conversion and palette extraction functions using [rayon](https://crates.io/crates/rayon). |
+//! | "bytemuck" | Enables zero-copy byte views of color slices using [bytemuck](https://crates.io/crates/bytemuck). |
+//! | "approx" | Enables approximate equality of floating point colors using [approx](https://crates.io/crates/approx). |
+//! | "image" | Enables conversions to and from [image](https://crates.io/crates/image) pixels, and image quantization helpers in the `interop::image` module. |
+//! | "palette" | Enables conversions to and from [palette](https://crates.io/crates/palette) colors. |
+//! | "proptest" | Enables generating valid colors for property tests using [proptest](https://crates.io/crates/proptest). |
+//! | "quickcheck" | Enables generating valid colors for property tests using [quickcheck](https://crates.io/crates/quickcheck). |
+//! | "rand" | Enables sampling random colors using [rand](https://crates.io/crates/rand) distributions. |
+//! | "wasm" | Enables JavaScript bindings using [wasm-bindgen](https://crates.io/crates/wasm-bindgen) in the `wasm` module. Implies "parse". |
+//! | "tracing" | Enables instrumentation of conversions using [tracing](https://crates.io/crates/tracing). |
+//! | "bench" | Enables the benchmark suite, run with `cargo bench --features bench`. |
 //!
 //! Only the "parse" feature is enabled by default.
 ////////////////////////////////////////////////////////////////////////////////
 #![warn(anonymous_parameters)]
 #![warn(bad_style)]
 #![warn(bare_trait_objects)]
-#![warn(const_err)]
 #![warn(dead_code)]
 #![warn(elided_lifetimes_in_paths)]
 #![warn(improper_ctypes)]
@@ -32,7 +45,6 @@
 #![warn(overflowing_literals)]
 #![warn(path_statements)]
 #![warn(patterns_in_fns_without_body)]
-#![warn(private_in_public)]
 #![warn(rust_2018_idioms)]
 #![warn(trivial_casts)]
 #![warn(trivial_numeric_casts)]
@@ -49,11 +61,50 @@
 
 
 // Internal modules.
+pub mod adjust;
+pub mod analysis;
+pub mod angle;
+pub mod backend;
+pub mod blend;
+pub mod bulk;
+pub mod color_map;
+pub mod color_matrix;
+#[cfg(feature = "serde")]
+pub mod compat;
+pub mod contrast;
+pub mod gamut;
+pub mod gradient;
+pub mod harmony;
+#[cfg(feature = "icc")]
+pub mod icc;
+pub mod illuminant;
+pub mod interop;
+pub mod lossless;
+pub mod material;
+pub mod math;
+pub mod metric;
+#[cfg(feature = "naming")]
+pub mod naming;
+pub mod opaque;
+pub mod packed;
+pub mod palette;
+pub mod quantize;
+pub mod random;
+pub mod render;
+pub mod rgb_space;
+pub mod short_code;
+pub mod tags;
+pub mod theme;
+pub mod tone_map;
+pub mod transfer;
 pub mod utility;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 mod color_space;
 mod color;
 #[cfg(feature = "parse")]
 mod parse;
+mod trace;
 
 #[cfg(test)]
 mod test;
@@ -62,8 +113,23 @@ mod test;
 pub use color_space::cmyk::Cmyk;
 pub use color_space::hsl::Hsl;
 pub use color_space::hsv::Hsv;
+pub use color_space::lab::Lab;
+pub use color_space::lab64::Lab64;
+pub use color_space::lch::Lch;
+pub use color_space::luma::GrayscaleMethod;
+pub use color_space::luma::Luma;
+pub use color_space::oklab::Oklab;
+pub use color_space::oklch::Oklch;
 pub use color_space::rgb::Rgb;
+pub use color_space::rgb48::Rgb48;
+pub use color_space::rgba::Rgba;
+pub use color_space::rgba64::Rgba64;
+pub use color_space::rgb::SeedOptions;
 pub use color_space::xyz::Xyz;
+pub use color_space::xyz64::Xyz64;
+pub use color_space::ycocg::YCoCgR;
+#[cfg(feature = "parse")]
+pub use parse::CssColorParseError;
 pub use crate::color::Color;
 
 
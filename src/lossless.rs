@@ -0,0 +1,160 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Integer-exact conversions.
+//!
+//! Most conversions in this crate pass through floating point and may be off
+//! by one octet after a round trip. The [`Lossless`] marker trait is
+//! implemented only for conversions which are exact, so code which must not
+//! lose information, such as archival tools, can use [`convert`] to have the
+//! compiler reject any other path.
+//!
+//! [`Lossless`]: trait.Lossless.html
+//! [`convert`]: fn.convert.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Color;
+use crate::Luma;
+use crate::Rgb;
+use crate::Rgb48;
+use crate::Rgba;
+use crate::Rgba64;
+use crate::YCoCgR;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Lossless
+////////////////////////////////////////////////////////////////////////////////
+/// A marker for conversions into `T` which lose no information.
+///
+/// Implementing this trait asserts that the `Into<T>` conversion is
+/// injective and involves no rounding, so the original value can always be
+/// recovered exactly.
+pub trait Lossless<T>: Into<T> {}
+
+impl Lossless<YCoCgR> for Rgb {}
+impl Lossless<Rgb> for YCoCgR {}
+impl Lossless<Color> for Rgb {}
+impl Lossless<Rgb> for Color {}
+impl Lossless<Rgb> for [u8; 3] {}
+impl Lossless<[u16; 3]> for Rgb {}
+impl Lossless<Rgb48> for Rgb {}
+impl Lossless<Rgba64> for Rgba {}
+impl Lossless<Rgba64> for Rgb {}
+impl Lossless<Rgb> for Luma {}
+impl Lossless<u8> for Luma {}
+impl Lossless<Luma> for u8 {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// convert
+////////////////////////////////////////////////////////////////////////////////
+/// Converts a value using only a conversion marked as [`Lossless`].
+///
+/// [`Lossless`]: trait.Lossless.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::YCoCgR;
+/// # use color::lossless::convert;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let rgb = Rgb::new(12, 200, 99);
+///
+/// let ycocg: YCoCgR = convert(rgb);
+///
+/// assert_eq!(convert::<_, Rgb>(ycocg), rgb);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+///
+/// Lossy conversions do not compile:
+///
+/// ```compile_fail
+/// # use color::Hsl;
+/// # use color::Rgb;
+/// # use color::lossless::convert;
+/// let hsl: Hsl = convert(Rgb::new(12, 200, 99));
+/// ```
+pub fn convert<T, U>(value: T) -> U where T: Lossless<U> {
+    value.into()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// widen
+////////////////////////////////////////////////////////////////////////////////
+/// Scales an 8-bit channel value to 16 bits by multiplying by 257, which maps
+/// 0 to 0 and 255 to 65535 exactly.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::lossless::widen;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(widen(0x00), 0x0000);
+/// assert_eq!(widen(0xAB), 0xABAB);
+/// assert_eq!(widen(0xFF), 0xFFFF);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub const fn widen(octet: u8) -> u16 {
+    octet as u16 * 257
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// narrow
+////////////////////////////////////////////////////////////////////////////////
+/// Scales a 16-bit channel value to 8 bits, if it can be done exactly. This
+/// is the inverse of [`widen`].
+///
+/// [`widen`]: fn.widen.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::lossless::narrow;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(narrow(0xABAB), Some(0xAB));
+/// assert_eq!(narrow(0xABAC), None);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub const fn narrow(value: u16) -> Option<u8> {
+    if value.is_multiple_of(257) {
+        Some((value / 257) as u8)
+    } else {
+        None
+    }
+}
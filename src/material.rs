@@ -0,0 +1,365 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Material Design 3 style tonal palettes and color schemes.
+//!
+//! Material Design describes colors by hue, chroma, and tone (HCT), where
+//! tone is the CIE L* lightness. This module uses [`Lch`] for hue and chroma
+//! in place of the CAM16 model used by Material, so tones match Material's
+//! exactly while hues and chromas are close approximations. Colors which
+//! cannot be shown in sRGB at a requested chroma are reduced in chroma,
+//! keeping their hue and tone.
+//!
+//! [`Lch`]: ../struct.Lch.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::angle;
+use crate::color_space::lab::linear_srgb;
+use crate::Lab;
+use crate::Lch;
+use crate::Rgb;
+use crate::utility::clamped;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TonalPalette
+////////////////////////////////////////////////////////////////////////////////
+/// A range of tones sharing a hue and chroma.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Lab;
+/// # use color::Rgb;
+/// # use color::material::TonalPalette;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let blues = TonalPalette::new(270.0, 40.0);
+///
+/// assert_eq!(blues.tone(0.0), Rgb::new(0, 0, 0));
+/// assert_eq!(blues.tone(100.0), Rgb::new(255, 255, 255));
+/// assert!((Lab::from(blues.tone(40.0)).l - 40.0).abs() < 0.5);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TonalPalette {
+    /// The hue, in degrees.
+    hue: f32,
+    /// The target chroma.
+    chroma: f32,
+}
+
+impl TonalPalette {
+    /// The tones included in a Material tonal palette.
+    pub const TONES: [u8; 13] = [
+        0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 99, 100,
+    ];
+
+    /// Constructs a new `TonalPalette` with the given hue in degrees and
+    /// target chroma.
+    pub fn new(hue: f32, chroma: f32) -> Self {
+        TonalPalette { hue: angle::wrap(hue), chroma: chroma.max(0.0) }
+    }
+
+    /// Constructs a new `TonalPalette` with the hue and chroma of the given
+    /// color.
+    pub fn from_color<C>(color: C) -> Self where C: Into<Rgb> {
+        let lch = Lch::from(color.into());
+        TonalPalette::new(lch.h, lch.c)
+    }
+
+    /// Returns the hue of the palette, in degrees.
+    pub fn hue(&self) -> f32 {
+        self.hue
+    }
+
+    /// Returns the target chroma of the palette.
+    pub fn chroma(&self) -> f32 {
+        self.chroma
+    }
+
+    /// Returns the color with the given tone, which is clamped between 0 and
+    /// 100. The chroma is reduced if needed to keep the color within sRGB, so
+    /// tone 0 is always black and tone 100 is always white.
+    pub fn tone(&self, tone: f32) -> Rgb {
+        let tone = clamped(tone, 0.0, 100.0);
+        if tone <= 0.0 { return Rgb::new(0, 0, 0); }
+        if tone >= 100.0 { return Rgb::new(255, 255, 255); }
+        let in_gamut = |chroma: f32| {
+            linear_srgb(Lab::from(Lch::new(tone, chroma, self.hue)))
+                .iter()
+                .all(|&v| (-1e-4..=1.0 + 1e-4).contains(&v))
+        };
+
+        let mut chroma = self.chroma;
+        if !in_gamut(chroma) {
+            let (mut low, mut high) = (0.0, chroma);
+            for _ in 0..24 {
+                let mid = (low + high) / 2.0;
+                if in_gamut(mid) { low = mid; } else { high = mid; }
+            }
+            chroma = low;
+        }
+        Rgb::from(Lch::new(tone, chroma, self.hue))
+    }
+
+    /// Returns the colors of the palette at each of the standard [`TONES`].
+    ///
+    /// [`TONES`]: #associatedconstant.TONES
+    pub fn tones(&self) -> [Rgb; 13] {
+        TonalPalette::TONES.map(|tone| self.tone(tone as f32))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// CorePalettes
+////////////////////////////////////////////////////////////////////////////////
+/// The tonal palettes derived from a seed color, from which the roles of a
+/// [`Scheme`] are chosen.
+///
+/// [`Scheme`]: struct.Scheme.html
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CorePalettes {
+    /// The palette for primary, prominent components.
+    pub primary: TonalPalette,
+    /// The palette for less prominent components.
+    pub secondary: TonalPalette,
+    /// The palette for contrasting accents.
+    pub tertiary: TonalPalette,
+    /// The palette for surfaces and backgrounds.
+    pub neutral: TonalPalette,
+    /// The palette for surface variants and outlines.
+    pub neutral_variant: TonalPalette,
+    /// The palette for errors.
+    pub error: TonalPalette,
+}
+
+impl CorePalettes {
+    /// Returns the core palettes for the given seed color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::material::CorePalettes;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palettes = CorePalettes::from_seed(Rgb::new(103, 80, 164));
+    ///
+    /// assert!(palettes.primary.chroma() >= 48.0);
+    /// assert_eq!(palettes.secondary.hue(), palettes.primary.hue());
+    /// assert_eq!(palettes.neutral.chroma(), 4.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_seed<C>(seed: C) -> Self where C: Into<Rgb> {
+        let seed = Lch::from(seed.into());
+        CorePalettes {
+            primary: TonalPalette::new(seed.h, seed.c.max(48.0)),
+            secondary: TonalPalette::new(seed.h, 16.0),
+            tertiary: TonalPalette::new(seed.h + 60.0, 24.0),
+            neutral: TonalPalette::new(seed.h, 4.0),
+            neutral_variant: TonalPalette::new(seed.h, 8.0),
+            error: TonalPalette::new(25.0, 84.0),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Scheme
+////////////////////////////////////////////////////////////////////////////////
+/// A set of colors assigned to the roles of a Material Design 3 theme.
+///
+/// Each `on_` color is intended for text and icons drawn over the role of the
+/// same name.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::material::Scheme;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let seed = Rgb::new(103, 80, 164);
+///
+/// let light = Scheme::light(seed);
+/// let dark = Scheme::dark(seed);
+///
+/// assert_eq!(light.on_primary, Rgb::new(255, 255, 255));
+/// assert_eq!(light.primary_container, dark.on_primary_container);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scheme {
+    /// The primary color.
+    pub primary: Rgb,
+    /// The color for content over the primary color.
+    pub on_primary: Rgb,
+    /// The primary container color.
+    pub primary_container: Rgb,
+    /// The color for content over the primary container color.
+    pub on_primary_container: Rgb,
+    /// The secondary color.
+    pub secondary: Rgb,
+    /// The color for content over the secondary color.
+    pub on_secondary: Rgb,
+    /// The secondary container color.
+    pub secondary_container: Rgb,
+    /// The color for content over the secondary container color.
+    pub on_secondary_container: Rgb,
+    /// The tertiary color.
+    pub tertiary: Rgb,
+    /// The color for content over the tertiary color.
+    pub on_tertiary: Rgb,
+    /// The tertiary container color.
+    pub tertiary_container: Rgb,
+    /// The color for content over the tertiary container color.
+    pub on_tertiary_container: Rgb,
+    /// The error color.
+    pub error: Rgb,
+    /// The color for content over the error color.
+    pub on_error: Rgb,
+    /// The error container color.
+    pub error_container: Rgb,
+    /// The color for content over the error container color.
+    pub on_error_container: Rgb,
+    /// The background color.
+    pub background: Rgb,
+    /// The color for content over the background color.
+    pub on_background: Rgb,
+    /// The surface color.
+    pub surface: Rgb,
+    /// The color for content over the surface color.
+    pub on_surface: Rgb,
+    /// The surface variant color.
+    pub surface_variant: Rgb,
+    /// The color for content over the surface variant color.
+    pub on_surface_variant: Rgb,
+    /// The outline color.
+    pub outline: Rgb,
+}
+
+impl Scheme {
+    /// Returns the light scheme for the given seed color.
+    pub fn light<C>(seed: C) -> Self where C: Into<Rgb> {
+        Scheme::light_from_palettes(&CorePalettes::from_seed(seed))
+    }
+
+    /// Returns the dark scheme for the given seed color.
+    pub fn dark<C>(seed: C) -> Self where C: Into<Rgb> {
+        Scheme::dark_from_palettes(&CorePalettes::from_seed(seed))
+    }
+
+    /// Returns the light scheme for the given core palettes.
+    pub fn light_from_palettes(palettes: &CorePalettes) -> Self {
+        let CorePalettes {
+            primary: p,
+            secondary: s,
+            tertiary: t,
+            neutral: n,
+            neutral_variant: nv,
+            error: e,
+        } = *palettes;
+        Scheme {
+            primary: p.tone(40.0),
+            on_primary: p.tone(100.0),
+            primary_container: p.tone(90.0),
+            on_primary_container: p.tone(10.0),
+            secondary: s.tone(40.0),
+            on_secondary: s.tone(100.0),
+            secondary_container: s.tone(90.0),
+            on_secondary_container: s.tone(10.0),
+            tertiary: t.tone(40.0),
+            on_tertiary: t.tone(100.0),
+            tertiary_container: t.tone(90.0),
+            on_tertiary_container: t.tone(10.0),
+            error: e.tone(40.0),
+            on_error: e.tone(100.0),
+            error_container: e.tone(90.0),
+            on_error_container: e.tone(10.0),
+            background: n.tone(99.0),
+            on_background: n.tone(10.0),
+            surface: n.tone(99.0),
+            on_surface: n.tone(10.0),
+            surface_variant: nv.tone(90.0),
+            on_surface_variant: nv.tone(30.0),
+            outline: nv.tone(50.0),
+        }
+    }
+
+    /// Returns the dark scheme for the given core palettes.
+    pub fn dark_from_palettes(palettes: &CorePalettes) -> Self {
+        let CorePalettes {
+            primary: p,
+            secondary: s,
+            tertiary: t,
+            neutral: n,
+            neutral_variant: nv,
+            error: e,
+        } = *palettes;
+        Scheme {
+            primary: p.tone(80.0),
+            on_primary: p.tone(20.0),
+            primary_container: p.tone(30.0),
+            on_primary_container: p.tone(90.0),
+            secondary: s.tone(80.0),
+            on_secondary: s.tone(20.0),
+            secondary_container: s.tone(30.0),
+            on_secondary_container: s.tone(90.0),
+            tertiary: t.tone(80.0),
+            on_tertiary: t.tone(20.0),
+            tertiary_container: t.tone(30.0),
+            on_tertiary_container: t.tone(90.0),
+            error: e.tone(80.0),
+            on_error: e.tone(20.0),
+            error_container: e.tone(30.0),
+            on_error_container: e.tone(90.0),
+            background: n.tone(10.0),
+            on_background: n.tone(90.0),
+            surface: n.tone(10.0),
+            on_surface: n.tone(90.0),
+            surface_variant: nv.tone(30.0),
+            on_surface_variant: nv.tone(80.0),
+            outline: nv.tone(60.0),
+        }
+    }
+}
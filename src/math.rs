@@ -0,0 +1,228 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Scalar math functions used by the color types.
+//!
+//! These are the same functions the crate uses internally, so code built on
+//! them interpolates, clamps, and compares values exactly as the color types
+//! do.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Standard library imports.
+use std::f32;
+
+// Re-exports.
+pub use crate::utility::cerp_f32 as cerp;
+pub use crate::utility::cerp_u8;
+pub use crate::utility::clamped;
+pub use crate::utility::lerp_f32 as lerp;
+pub use crate::utility::lerp_u8;
+pub use crate::utility::monotone_cerp_f32 as monotone_cerp;
+pub use crate::utility::monotone_cerp_u8;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The relative tolerance used by [`utility::nearly_equal`].
+///
+/// [`utility::nearly_equal`]: ../utility/fn.nearly_equal.html
+pub const DEFAULT_EPSILON: f32 = f32::EPSILON;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// nearly_equal
+////////////////////////////////////////////////////////////////////////////////
+/// Returns true if the given [`f32`] values are equal within the relative
+/// tolerance `epsilon`, taking into account infinites.
+///
+/// Values very near zero are compared with an absolute tolerance of `epsilon`
+/// times the smallest normal `f32`, as their relative error is not
+/// meaningful.
+///
+/// [`f32`]: https://doc.rust-lang.org/std/primitive.f32.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::math::nearly_equal;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert!(!nearly_equal(100.0, 100.01, 1e-6));
+/// assert!(nearly_equal(100.0, 100.01, 1e-4));
+///
+/// // NANs do not compare nearly equal:
+/// assert!(!nearly_equal(f32::NAN, f32::NAN, 1.0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn nearly_equal(a: f32, b: f32, epsilon: f32) -> bool {
+    let abs_a = a.abs();
+    let abs_b = b.abs();
+    let diff = (a - b).abs();
+
+    if a == b { // Shortcut, handles infinities.
+        true
+    } else if a == 0.0 || b == 0.0 || diff < f32::MIN_POSITIVE {
+        // a or b is zero or both are extremely close to it
+        // relative error is less meaningful here
+        diff < (epsilon * f32::MIN_POSITIVE)
+    } else { // Use relative error.
+        (diff / f32::min(abs_a + abs_b, f32::MAX)) < epsilon
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// inverse_lerp
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the ratio at which `value` lies between `start` and `end`, clamped
+/// between 0 and 1. This is the inverse of [`lerp`].
+///
+/// Returns 0 if `start` and `end` are equal.
+///
+/// [`lerp`]: fn.lerp.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::math::inverse_lerp;
+/// # use color::math::lerp;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(inverse_lerp(10.0, 20.0, 12.5), 0.25);
+/// assert_eq!(inverse_lerp(20.0, 10.0, 12.5), 0.75);
+/// assert_eq!(inverse_lerp(10.0, 20.0, 40.0), 1.0);
+///
+/// assert_eq!(lerp(10.0, 20.0, inverse_lerp(10.0, 20.0, 12.5)), 12.5);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn inverse_lerp(start: f32, end: f32, value: f32) -> f32 {
+    if start == end { return 0.0; }
+    clamped((value - start) / (end - start), 0.0, 1.0)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// remap
+////////////////////////////////////////////////////////////////////////////////
+/// Maps `value` from the range between `from_start` and `from_end` onto the
+/// range between `to_start` and `to_end`. Values outside of the source range
+/// are clamped to it.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::math::remap;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(remap(50.0, 0.0, 100.0, 0.0, 1.0), 0.5);
+/// assert_eq!(remap(0.25, 0.0, 1.0, 255.0, 0.0), 191.25);
+/// assert_eq!(remap(-5.0, 0.0, 100.0, 0.0, 1.0), 0.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn remap(
+    value: f32,
+    from_start: f32,
+    from_end: f32,
+    to_start: f32,
+    to_end: f32)
+    -> f32
+{
+    lerp(to_start, to_end, inverse_lerp(from_start, from_end, value))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// smoothstep
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a smooth Hermite step from 0 to 1 as `value` moves from `edge_start`
+/// to `edge_end`. The step has zero slope at both edges.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::math::smoothstep;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(smoothstep(0.0, 1.0, -1.0), 0.0);
+/// assert_eq!(smoothstep(0.0, 1.0, 0.25), 0.15625);
+/// assert_eq!(smoothstep(0.0, 1.0, 0.5), 0.5);
+/// assert_eq!(smoothstep(0.0, 1.0, 2.0), 1.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn smoothstep(edge_start: f32, edge_end: f32, value: f32) -> f32 {
+    let t = inverse_lerp(edge_start, edge_end, value);
+    t * t * (3.0 - 2.0 * t)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// smootherstep
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a smooth step from 0 to 1 as `value` moves from `edge_start` to
+/// `edge_end`. Unlike [`smoothstep`], the step also has zero curvature at both
+/// edges.
+///
+/// [`smoothstep`]: fn.smoothstep.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::math::smootherstep;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(smootherstep(0.0, 1.0, 0.25), 0.103515625);
+/// assert_eq!(smootherstep(0.0, 1.0, 0.5), 0.5);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn smootherstep(edge_start: f32, edge_end: f32, value: f32) -> f32 {
+    let t = inverse_lerp(edge_start, edge_end, value);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
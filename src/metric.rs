@@ -0,0 +1,288 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Color distance metrics.
+//!
+//! The [`Metric`] trait is accepted wherever the crate compares colors by
+//! distance, such as [`Rgb::distance_with`], [`nearest_in`],
+//! [`Palette::dedup_by`], and [`palette_indices`], which maps the pixels of
+//! an image onto a quantized palette, so one choice of metric can be used
+//! consistently. Any `Fn(Rgb, Rgb) -> f32` closure is also a metric.
+//!
+//! The palette extraction algorithms don't take a metric. [`median_cut`] and
+//! [`octree`] divide the RGB cube without measuring distances, and
+//! [`KMeans`] measures distances in its cluster space because the mean of a
+//! cluster only minimizes Euclidean distances. The crate doesn't dither yet.
+//!
+//! [`KMeans`]: ../quantize/struct.KMeans.html
+//! [`Metric`]: trait.Metric.html
+//! [`Palette::dedup_by`]: ../palette/struct.Palette.html#method.dedup_by
+//! [`Rgb::distance_with`]: ../struct.Rgb.html#method.distance_with
+//! [`median_cut`]: ../quantize/fn.median_cut.html
+//! [`nearest_in`]: ../palette/fn.nearest_in.html
+//! [`octree`]: ../quantize/fn.octree.html
+//! [`palette_indices`]: ../quantize/fn.palette_indices.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Lab;
+use crate::Oklab;
+use crate::Rgb;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Metric
+////////////////////////////////////////////////////////////////////////////////
+/// A measure of the difference between two colors.
+///
+/// Distances are non-negative, and zero for identical colors. Larger
+/// distances indicate more different colors, but the scale of the distance
+/// depends on the metric.
+pub trait Metric {
+    /// Returns the distance between the given colors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::metric::Euclidean;
+    /// # use color::metric::Metric;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let a = Rgb::new(0, 0, 0);
+    /// let b = Rgb::new(30, 40, 0);
+    ///
+    /// assert_eq!(Euclidean::Rgb.distance(a, b), 50.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    fn distance(&self, a: Rgb, b: Rgb) -> f32;
+}
+
+impl<F> Metric for F where F: Fn(Rgb, Rgb) -> f32 {
+    fn distance(&self, a: Rgb, b: Rgb) -> f32 {
+        self(a, b)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Euclidean
+////////////////////////////////////////////////////////////////////////////////
+/// The straight-line distance between colors in a given color space.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Euclidean {
+    /// Distance between sRGB octets, from 0 to about 441.7. Cheap, but
+    /// poorly matched to perception.
+    Rgb,
+    /// Distance between linear sRGB ratios, from 0 to about 1.73.
+    LinearRgb,
+    /// Distance in CIE L*a*b*, also known as ΔE*76.
+    Lab,
+    /// Distance in Oklab.
+    #[default]
+    Oklab,
+}
+
+impl Metric for Euclidean {
+    fn distance(&self, a: Rgb, b: Rgb) -> f32 {
+        match self {
+            Euclidean::Rgb => {
+                let [a, b] = [a, b].map(|c| c.octets().map(|v| v as f32));
+                euclidean(a, b)
+            },
+            Euclidean::LinearRgb => {
+                let [a, b] = [a, b].map(|c| c.linear_ratios());
+                euclidean(a, b)
+            },
+            Euclidean::Lab   => Lab::distance(a, b),
+            Euclidean::Oklab => Oklab::distance(a, b),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Weighted
+////////////////////////////////////////////////////////////////////////////////
+/// The straight-line distance between sRGB octets, with each channel's
+/// difference scaled by a weight.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::metric::Metric;
+/// # use color::metric::Weighted;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let green_heavy = Weighted::new(1.0, 4.0, 1.0);
+///
+/// let black = Rgb::new(0, 0, 0);
+/// assert_eq!(green_heavy.distance(black, Rgb::new(0, 10, 0)), 20.0);
+/// assert_eq!(green_heavy.distance(black, Rgb::new(10, 0, 0)), 10.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Weighted {
+    /// The weights of the red, green, and blue channels.
+    pub weights: [f32; 3],
+}
+
+impl Weighted {
+    /// Weights approximating the relative luminance of each channel, as in
+    /// ITU-R BT.601.
+    pub const LUMA: Weighted = Weighted { weights: [0.299, 0.587, 0.114] };
+
+    /// Constructs a new `Weighted` metric with the given channel weights.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any weight is negative or not finite.
+    pub fn new(red: f32, green: f32, blue: f32) -> Self {
+        let weights = [red, green, blue];
+        assert!(weights.iter().all(|w| w.is_finite() && *w >= 0.0));
+        Weighted { weights }
+    }
+}
+
+impl Metric for Weighted {
+    fn distance(&self, a: Rgb, b: Rgb) -> f32 {
+        let (a, b) = (a.octets(), b.octets());
+        (0..3)
+            .map(|i| {
+                let d = a[i] as f32 - b[i] as f32;
+                self.weights[i] * d * d
+            })
+            .sum::<f32>()
+            .sqrt()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Redmean
+////////////////////////////////////////////////////////////////////////////////
+/// The "redmean" distance between sRGB octets, which weights the channel
+/// differences by the mean red level of the two colors.
+///
+/// This is far cheaper than [`DeltaE`], needing no color space conversion, and
+/// tracks perceived difference noticeably better than unweighted RGB
+/// distance, making it a good choice for performance-sensitive nearest-color
+/// lookups. Distances range from 0 to about 765.
+///
+/// [`DeltaE`]: enum.DeltaE.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::metric::Metric;
+/// # use color::metric::Redmean;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let black = Rgb::new(0, 0, 0);
+///
+/// assert_eq!(Redmean.distance(black, Rgb::new(0, 10, 0)), 20.0);
+/// assert!(Redmean.distance(black, Rgb::new(0, 0, 255)) >
+///     Redmean.distance(black, Rgb::new(255, 0, 0)));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Redmean;
+
+impl Metric for Redmean {
+    fn distance(&self, a: Rgb, b: Rgb) -> f32 {
+        let (a, b) = (a.octets(), b.octets());
+        let mean = (a[0] as f32 + b[0] as f32) / 2.0;
+        let [dr, dg, db] = [0, 1, 2].map(|i| a[i] as f32 - b[i] as f32);
+        ((2.0 + mean / 256.0) * dr * dr
+            + 4.0 * dg * dg
+            + (2.0 + (255.0 - mean) / 256.0) * db * db)
+            .sqrt()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DeltaE
+////////////////////////////////////////////////////////////////////////////////
+/// The CIE color difference formulas, measured in CIE L*a*b*.
+///
+/// A difference of about 1 is the smallest that most observers notice.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeltaE {
+    /// ΔE*76, the distance in L*a*b*. See [`Lab::distance`].
+    ///
+    /// [`Lab::distance`]: ../struct.Lab.html#method.distance
+    Cie76,
+    /// ΔE*94, using the graphic arts weights. See [`Lab::delta_e_94`].
+    ///
+    /// [`Lab::delta_e_94`]: ../struct.Lab.html#method.delta_e_94
+    Cie94,
+    /// ΔE*00, the most accurate and most expensive. See [`Lab::delta_e`].
+    ///
+    /// [`Lab::delta_e`]: ../struct.Lab.html#method.delta_e
+    #[default]
+    Ciede2000,
+}
+
+impl Metric for DeltaE {
+    fn distance(&self, a: Rgb, b: Rgb) -> f32 {
+        match self {
+            DeltaE::Cie76     => Lab::distance(a, b),
+            DeltaE::Cie94     => Lab::delta_e_94(a, b),
+            DeltaE::Ciede2000 => Lab::delta_e(a, b),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the euclidean distance between two points.
+fn euclidean(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
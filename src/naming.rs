@@ -0,0 +1,295 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Locale-aware naming of colors using basic color terms.
+//!
+//! Colors are named with one of the eleven basic color terms identified by
+//! Berlin and Kay, which have direct translations in most languages. The term
+//! for a color is the one whose prototype is nearest in [`Oklab`].
+//!
+//! [`Oklab`]: ../struct.Oklab.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Oklab;
+use crate::Rgb;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Locale
+////////////////////////////////////////////////////////////////////////////////
+/// A language in which colors can be named.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Locale {
+    /// English.
+    #[default]
+    English,
+    /// Spanish.
+    Spanish,
+    /// French.
+    French,
+    /// German.
+    German,
+    /// Italian.
+    Italian,
+    /// Portuguese.
+    Portuguese,
+    /// Russian.
+    Russian,
+    /// Japanese.
+    Japanese,
+    /// Chinese.
+    Chinese,
+}
+
+impl Locale {
+    /// Returns the locale for the given BCP 47 language tag, such as `"en"` or
+    /// `"pt-BR"`, using only its primary language subtag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::naming::Locale;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert_eq!(Locale::from_tag("es-MX"), Some(Locale::Spanish));
+    /// assert_eq!(Locale::from_tag("zh_Hans"), Some(Locale::Chinese));
+    /// assert_eq!(Locale::from_tag("tlh"), None);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag
+            .split(['-', '_'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match &language[..] {
+            "en" => Some(Locale::English),
+            "es" => Some(Locale::Spanish),
+            "fr" => Some(Locale::French),
+            "de" => Some(Locale::German),
+            "it" => Some(Locale::Italian),
+            "pt" => Some(Locale::Portuguese),
+            "ru" => Some(Locale::Russian),
+            "ja" => Some(Locale::Japanese),
+            "zh" => Some(Locale::Chinese),
+            _    => None,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// BasicColor
+////////////////////////////////////////////////////////////////////////////////
+/// One of the eleven basic color terms.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BasicColor {
+    /// Black.
+    Black,
+    /// White.
+    White,
+    /// Red.
+    Red,
+    /// Green.
+    Green,
+    /// Yellow.
+    Yellow,
+    /// Blue.
+    Blue,
+    /// Brown.
+    Brown,
+    /// Purple.
+    Purple,
+    /// Pink.
+    Pink,
+    /// Orange.
+    Orange,
+    /// Gray.
+    Gray,
+}
+
+impl BasicColor {
+    /// All of the basic color terms.
+    pub const ALL: [BasicColor; 11] = [
+        BasicColor::Black,
+        BasicColor::White,
+        BasicColor::Red,
+        BasicColor::Green,
+        BasicColor::Yellow,
+        BasicColor::Blue,
+        BasicColor::Brown,
+        BasicColor::Purple,
+        BasicColor::Pink,
+        BasicColor::Orange,
+        BasicColor::Gray,
+    ];
+
+    /// Returns the prototypical color for the term.
+    pub fn prototype(self) -> Rgb {
+        match self {
+            BasicColor::Black  => Rgb::new(0, 0, 0),
+            BasicColor::White  => Rgb::new(255, 255, 255),
+            BasicColor::Red    => Rgb::new(200, 30, 35),
+            BasicColor::Green  => Rgb::new(50, 160, 60),
+            BasicColor::Yellow => Rgb::new(245, 220, 40),
+            BasicColor::Blue   => Rgb::new(40, 80, 210),
+            BasicColor::Brown  => Rgb::new(130, 80, 40),
+            BasicColor::Purple => Rgb::new(130, 50, 160),
+            BasicColor::Pink   => Rgb::new(245, 160, 190),
+            BasicColor::Orange => Rgb::new(245, 135, 30),
+            BasicColor::Gray   => Rgb::new(128, 128, 128),
+        }
+    }
+
+    /// Returns the term whose prototype is nearest to the given color in
+    /// Oklab.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::naming::BasicColor;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert_eq!(BasicColor::nearest(Rgb::new(255, 165, 0)), BasicColor::Orange);
+    /// assert_eq!(BasicColor::nearest(Rgb::new(139, 69, 19)), BasicColor::Brown);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn nearest<C>(color: C) -> Self where C: Into<Oklab> {
+        let color = color.into();
+        BasicColor::ALL
+            .iter()
+            .copied()
+            .map(|term| (term, Oklab::distance(color, term.prototype())))
+            .fold((BasicColor::Black, f32::INFINITY), |nearest, next| {
+                if next.1 < nearest.1 { next } else { nearest }
+            })
+            .0
+    }
+
+    /// Returns the name of the term in the given locale.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::naming::BasicColor;
+    /// # use color::naming::Locale;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// assert_eq!(BasicColor::Green.name(Locale::German), "grün");
+    /// assert_eq!(BasicColor::Green.name(Locale::Japanese), "緑");
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn name(self, locale: Locale) -> &'static str {
+        let names = match locale {
+            Locale::English => [
+                "black", "white", "red", "green", "yellow", "blue",
+                "brown", "purple", "pink", "orange", "gray",
+            ],
+            Locale::Spanish => [
+                "negro", "blanco", "rojo", "verde", "amarillo", "azul",
+                "marrón", "morado", "rosa", "naranja", "gris",
+            ],
+            Locale::French => [
+                "noir", "blanc", "rouge", "vert", "jaune", "bleu",
+                "marron", "violet", "rose", "orange", "gris",
+            ],
+            Locale::German => [
+                "schwarz", "weiß", "rot", "grün", "gelb", "blau",
+                "braun", "lila", "rosa", "orange", "grau",
+            ],
+            Locale::Italian => [
+                "nero", "bianco", "rosso", "verde", "giallo", "blu",
+                "marrone", "viola", "rosa", "arancione", "grigio",
+            ],
+            Locale::Portuguese => [
+                "preto", "branco", "vermelho", "verde", "amarelo", "azul",
+                "marrom", "roxo", "rosa", "laranja", "cinza",
+            ],
+            Locale::Russian => [
+                "чёрный", "белый", "красный", "зелёный", "жёлтый", "синий",
+                "коричневый", "фиолетовый", "розовый", "оранжевый", "серый",
+            ],
+            Locale::Japanese => [
+                "黒", "白", "赤", "緑", "黄色", "青",
+                "茶色", "紫", "ピンク", "オレンジ", "灰色",
+            ],
+            Locale::Chinese => [
+                "黑色", "白色", "红色", "绿色", "黄色", "蓝色",
+                "棕色", "紫色", "粉色", "橙色", "灰色",
+            ],
+        };
+        names[self as usize]
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// describe
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the name of the basic color term nearest to the given color in the
+/// given locale.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::naming::Locale;
+/// # use color::naming::describe;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let cobalt = Rgb::new(30, 90, 220);
+///
+/// assert_eq!(describe(cobalt, Locale::English), "blue");
+/// assert_eq!(describe(cobalt, Locale::French), "bleu");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn describe<C>(color: C, locale: Locale) -> &'static str
+    where C: Into<Oklab>
+{
+    BasicColor::nearest(color).name(locale)
+}
@@ -0,0 +1,220 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Utilities for treating colors as opaque tokens.
+//!
+//! Some applications use colors to encode sensitive categories, such as
+//! security classifications. The [`ConstantTimeEq`] trait compares such colors
+//! without data-dependent timing, and the [`Redacted`] wrapper prevents them
+//! from being written to logs by accident.
+//!
+//! [`ConstantTimeEq`]: trait.ConstantTimeEq.html
+//! [`Redacted`]: struct.Redacted.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Cmyk;
+use crate::Color;
+use crate::Hsl;
+use crate::Hsv;
+use crate::Lab;
+use crate::Lch;
+use crate::Oklab;
+use crate::Oklch;
+use crate::Rgb;
+use crate::Xyz;
+
+// Standard library imports.
+use std::fmt;
+use std::hint::black_box;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// constant_time_eq
+////////////////////////////////////////////////////////////////////////////////
+/// Returns true if the given byte slices are equal, taking the same time
+/// regardless of where or whether they differ.
+///
+/// Only the lengths of the slices may affect the running time.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::opaque::constant_time_eq;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert!(constant_time_eq(b"secret", b"secret"));
+/// assert!(!constant_time_eq(b"secret", b"secreT"));
+/// assert!(!constant_time_eq(b"secret", b"secrets"));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() { return false; }
+
+    let difference = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| black_box(acc | (x ^ y)));
+    black_box(difference) == 0
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ConstantTimeEq
+////////////////////////////////////////////////////////////////////////////////
+/// Equality comparison which does not leak the compared values through
+/// timing.
+///
+/// Floating point colors are compared by their bit patterns, so `0.0` and
+/// `-0.0` are considered different and a `NaN` component equals itself.
+pub trait ConstantTimeEq {
+    /// Returns true if the values are equal, taking the same time regardless
+    /// of where or whether they differ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::opaque::ConstantTimeEq;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let secret = Rgb::new(200, 30, 30);
+    ///
+    /// assert!(secret.ct_eq(&Rgb::new(200, 30, 30)));
+    /// assert!(!secret.ct_eq(&Rgb::new(200, 30, 31)));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    fn ct_eq(&self, other: &Self) -> bool;
+}
+
+macro_rules! impl_constant_time_eq {
+    ($($t:ty),*) => {
+        $(
+            impl ConstantTimeEq for $t {
+                fn ct_eq(&self, other: &Self) -> bool {
+                    constant_time_eq(&self.to_ne_bytes(), &other.to_ne_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_constant_time_eq!(Rgb, Cmyk, Hsl, Hsv, Xyz, Lab, Lch, Oklab, Oklch);
+
+impl ConstantTimeEq for Color {
+    fn ct_eq(&self, other: &Self) -> bool {
+        Rgb::from(*self).ct_eq(&Rgb::from(*other))
+    }
+}
+
+impl<T> ConstantTimeEq for [T] where T: ConstantTimeEq {
+    fn ct_eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() { return false; }
+
+        self.iter()
+            .zip(other.iter())
+            .fold(true, |acc, (a, b)| black_box(acc & a.ct_eq(b)))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Redacted
+////////////////////////////////////////////////////////////////////////////////
+/// A wrapper which hides its value from `Debug` and `Display` output, and
+/// compares in constant time.
+///
+/// The wrapped value can only be read through [`expose`] or [`into_inner`],
+/// which makes every place a sensitive color is used explicit.
+///
+/// [`expose`]: #method.expose
+/// [`into_inner`]: #method.into_inner
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::opaque::Redacted;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let level = Redacted::new(Rgb::new(200, 30, 30));
+///
+/// assert_eq!(format!("{}", level), "<redacted>");
+/// assert_eq!(format!("{:?}", level), "Redacted(<redacted>)");
+/// assert_eq!(level, Redacted::new(Rgb::new(200, 30, 30)));
+/// assert_eq!(*level.expose(), Rgb::new(200, 30, 30));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Redacted<T>(T);
+
+impl<T> Redacted<T> {
+    /// Constructs a new `Redacted` wrapper around the given value.
+    pub fn new(value: T) -> Self {
+        Redacted(value)
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Redacted<T> {
+    fn from(value: T) -> Self {
+        Redacted(value)
+    }
+}
+
+impl<T> PartialEq for Redacted<T> where T: ConstantTimeEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<T> Eq for Redacted<T> where T: ConstantTimeEq {}
+
+impl<T> fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Redacted(<redacted>)")
+    }
+}
+
+impl<T> fmt::Display for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "<redacted>")
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Packed low bit depth pixel formats.
+//!
+//! Embedded displays, framebuffers, and retro graphics hardware store colors
+//! in fewer than 8 bits per channel, packed into a single integer. Encoding
+//! rounds each channel to the nearest representable level, and decoding
+//! expands each channel by bit replication, so the lowest level decodes to 0
+//! and the highest to 255, rather than the darker results of shifting alone.
+//!
+//! Full depth colors are packed into a `u32` with an explicit
+//! [`ChannelOrder`], using [`Rgba::to_u32`] and [`Rgba::from_u32`].
+//!
+//! [`ChannelOrder`]: enum.ChannelOrder.html
+//! [`Rgba::to_u32`]: ../struct.Rgba.html#method.to_u32
+//! [`Rgba::from_u32`]: ../struct.Rgba.html#method.from_u32
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Rgb;
+use crate::Rgba;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ChannelOrder
+////////////////////////////////////////////////////////////////////////////////
+/// The order of the channels of a color packed into a `u32`, from the most
+/// significant byte to the least significant.
+///
+/// The order describes the integer value, not its bytes in memory. For
+/// example, `Argb` is `0xAARRGGBB`, as used by Win32 `DIB` and Cairo
+/// `ARGB32` pixels, which are stored as `[B, G, R, A]` bytes on little
+/// endian machines.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgba;
+/// # use color::packed::ChannelOrder;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let color = Rgba::new(0x11, 0x22, 0x33, 0x44);
+///
+/// assert_eq!(color.to_u32(ChannelOrder::Argb), 0x44112233);
+/// assert_eq!(color.to_u32(ChannelOrder::Rgba), 0x11223344);
+/// assert_eq!(color.to_u32(ChannelOrder::Abgr), 0x44332211);
+/// assert_eq!(color.to_u32(ChannelOrder::Bgra), 0x33221144);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ChannelOrder {
+    /// Alpha in the high byte, then red, green, and blue: `0xAARRGGBB`.
+    #[default]
+    Argb,
+    /// Red in the high byte, then green, blue, and alpha: `0xRRGGBBAA`.
+    Rgba,
+    /// Alpha in the high byte, then blue, green, and red: `0xAABBGGRR`.
+    Abgr,
+    /// Blue in the high byte, then green, red, and alpha: `0xBBGGRRAA`.
+    Bgra,
+}
+
+impl ChannelOrder {
+    /// Returns the bit shifts of the red, green, blue, and alpha channels.
+    const fn shifts(self) -> [u32; 4] {
+        use ChannelOrder::*;
+        match self {
+            Argb => [16, 8, 0, 24],
+            Rgba => [24, 16, 8, 0],
+            Abgr => [0, 8, 16, 24],
+            Bgra => [8, 16, 24, 0],
+        }
+    }
+
+    /// Returns the given `[R, G, B, A]` octets packed in this order.
+    pub(in crate) const fn pack(self, octets: [u8; 4]) -> u32 {
+        let [r, g, b, a] = self.shifts();
+        (octets[0] as u32) << r
+            | (octets[1] as u32) << g
+            | (octets[2] as u32) << b
+            | (octets[3] as u32) << a
+    }
+
+    /// Returns the `[R, G, B, A]` octets of the given value packed in this
+    /// order.
+    pub(in crate) const fn unpack(self, packed: u32) -> [u8; 4] {
+        let [r, g, b, a] = self.shifts();
+        [
+            (packed >> r) as u8,
+            (packed >> g) as u8,
+            (packed >> b) as u8,
+            (packed >> a) as u8,
+        ]
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RGB565
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given color packed as RGB565, with 5 bits of red in the high
+/// bits, 6 bits of green, and 5 bits of blue in the low bits.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::packed::decode_rgb565;
+/// # use color::packed::encode_rgb565;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(encode_rgb565(Rgb::new(255, 0, 0)), 0xF800);
+/// assert_eq!(encode_rgb565(Rgb::new(255, 255, 255)), 0xFFFF);
+///
+/// // Decoding replicates the high bits, so full intensity is 255.
+/// assert_eq!(decode_rgb565(0xFFFF), Rgb::new(255, 255, 255));
+/// assert_eq!(decode_rgb565(0x8410), Rgb::new(132, 130, 132));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn encode_rgb565(color: Rgb) -> u16 {
+    let r = quantize(color.r, 5);
+    let g = quantize(color.g, 6);
+    let b = quantize(color.b, 5);
+    r << 11 | g << 5 | b
+}
+
+/// Returns the color of the given RGB565 packed value.
+pub fn decode_rgb565(packed: u16) -> Rgb {
+    Rgb::new(
+        expand(packed >> 11, 5),
+        expand(packed >> 5 & 0x3F, 6),
+        expand(packed & 0x1F, 5))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ARGB1555
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given color packed as ARGB1555, with a 1 bit alpha in the high
+/// bit, followed by 5 bits each of red, green, and blue. Alphas of 128 or
+/// more are opaque.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgba;
+/// # use color::packed::decode_argb1555;
+/// # use color::packed::encode_argb1555;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let packed = encode_argb1555(Rgba::new(0, 255, 0, 200));
+///
+/// assert_eq!(packed, 0x83E0);
+/// assert_eq!(decode_argb1555(packed), Rgba::new(0, 255, 0, 255));
+/// assert_eq!(decode_argb1555(0x03E0), Rgba::new(0, 255, 0, 0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn encode_argb1555(color: Rgba) -> u16 {
+    let a = u16::from(color.a >= 128);
+    let r = quantize(color.r, 5);
+    let g = quantize(color.g, 5);
+    let b = quantize(color.b, 5);
+    a << 15 | r << 10 | g << 5 | b
+}
+
+/// Returns the color of the given ARGB1555 packed value.
+pub fn decode_argb1555(packed: u16) -> Rgba {
+    Rgba::new(
+        expand(packed >> 10 & 0x1F, 5),
+        expand(packed >> 5 & 0x1F, 5),
+        expand(packed & 0x1F, 5),
+        if packed & 0x8000 == 0 { 0 } else { u8::MAX })
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RGB332
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given color packed as RGB332, with 3 bits of red in the high
+/// bits, 3 bits of green, and 2 bits of blue in the low bits.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::packed::decode_rgb332;
+/// # use color::packed::encode_rgb332;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let packed = encode_rgb332(Rgb::new(255, 128, 0));
+///
+/// assert_eq!(packed, 0b111_100_00);
+/// assert_eq!(decode_rgb332(packed), Rgb::new(255, 146, 0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn encode_rgb332(color: Rgb) -> u8 {
+    let r = quantize(color.r, 3);
+    let g = quantize(color.g, 3);
+    let b = quantize(color.b, 2);
+    (r << 5 | g << 2 | b) as u8
+}
+
+/// Returns the color of the given RGB332 packed value.
+pub fn decode_rgb332(packed: u8) -> Rgb {
+    let packed = u16::from(packed);
+    Rgb::new(
+        expand(packed >> 5, 3),
+        expand(packed >> 2 & 0x7, 3),
+        expand(packed & 0x3, 2))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the nearest level of the given octet with the given number of
+/// bits.
+fn quantize(octet: u8, bits: u32) -> u16 {
+    let max = (1u32 << bits) - 1;
+    ((u32::from(octet) * max + 127) / 255) as u16
+}
+
+/// Returns the octet of the given level with the given number of bits,
+/// expanded by replicating its bits into the low bits of the octet.
+fn expand(level: u16, bits: u32) -> u8 {
+    let mut value = u32::from(level) << (8 - bits);
+    let mut filled = bits;
+    while filled < 8 {
+        value |= value >> filled;
+        filled *= 2;
+    }
+    value as u8
+}
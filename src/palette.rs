@@ -0,0 +1,1651 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Ordered collections of named colors.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Internal modules.
+pub mod io;
+
+// Local imports.
+use crate::color_space::oklch::gamut_map;
+use crate::color_space::rgb::WARMTH_THRESHOLD;
+use crate::Lab;
+use crate::metric::DeltaE;
+use crate::metric::Metric;
+use crate::Oklab;
+use crate::Oklch;
+use crate::render::write_swatch_row;
+use crate::Rgb;
+use crate::utility::clamped;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::fmt;
+use std::iter::FromIterator;
+use std::slice;
+use std::vec;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The matching distance, in ΔE*00, at which [`Palette::similarity`] falls to
+/// `1/e`.
+///
+/// [`Palette::similarity`]: struct.Palette.html#method.similarity
+const SIMILARITY_SCALE: f32 = 10.0;
+
+/// The number of levels per channel of the sRGB gamut samples used by
+/// [`Palette::gamut_coverage`].
+///
+/// [`Palette::gamut_coverage`]: struct.Palette.html#method.gamut_coverage
+const GAMUT_LEVELS: u16 = 16;
+
+/// The Oklch chroma below which a color is treated as neutral by
+/// [`Palette::neon`], which leaves it unsaturated.
+///
+/// [`Palette::neon`]: struct.Palette.html#method.neon
+const NEUTRAL_CHROMA: f32 = 0.02;
+
+/// The Oklch lightness range onto which [`Palette::pastel`] maps colors.
+///
+/// [`Palette::pastel`]: struct.Palette.html#method.pastel
+const PASTEL_LIGHTNESS: [f32; 2] = [0.82, 0.95];
+
+/// The factor by which [`Palette::pastel`] scales the Oklch chroma.
+///
+/// [`Palette::pastel`]: struct.Palette.html#method.pastel
+const PASTEL_CHROMA_SCALE: f32 = 0.25;
+
+/// The Oklch lightness range onto which [`Palette::neon`] maps colors.
+///
+/// [`Palette::neon`]: struct.Palette.html#method.neon
+const NEON_LIGHTNESS: [f32; 2] = [0.6, 0.85];
+
+/// The Oklch lightness range onto which [`Palette::muted`] maps colors.
+///
+/// [`Palette::muted`]: struct.Palette.html#method.muted
+const MUTED_LIGHTNESS: [f32; 2] = [0.4, 0.7];
+
+/// The factor by which [`Palette::muted`] scales the Oklch chroma.
+///
+/// [`Palette::muted`]: struct.Palette.html#method.muted
+const MUTED_CHROMA_SCALE: f32 = 0.3;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Swatch
+////////////////////////////////////////////////////////////////////////////////
+/// A named color within a [`Palette`].
+///
+/// [`Palette`]: struct.Palette.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Swatch {
+    /// The name of the color.
+    pub name: String,
+    /// The color.
+    pub color: Rgb,
+}
+
+impl Swatch {
+    /// Constructs a new `Swatch`.
+    pub fn new<S>(name: S, color: Rgb) -> Self where S: Into<String> {
+        Swatch { name: name.into(), color }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Palette
+////////////////////////////////////////////////////////////////////////////////
+/// An ordered set of named colors.
+///
+/// Each name appears in the palette at most once. Swatches keep the order in
+/// which they were first inserted. A palette may also have a name and an
+/// author, which are preserved by formats that support them.
+///
+/// The `serde` representation of a palette is a list of its swatches. See
+/// [`Palette::to_json`] for a documented format which includes the palette
+/// metadata.
+///
+/// [`Palette::to_json`]: #method.to_json
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::palette::Palette;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut palette = Palette::new();
+/// palette.insert("ink", Rgb::new(20, 20, 30));
+/// palette.insert("paper", Rgb::new(250, 245, 235));
+///
+/// assert_eq!(palette.get("paper"), Some(Rgb::new(250, 245, 235)));
+///
+/// let names: Vec<&str> = palette.iter().map(|s| &s.name[..]).collect();
+/// assert_eq!(names, ["ink", "paper"]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde",
+    serde(from = "Vec<Swatch>", into = "Vec<Swatch>"))]
+pub struct Palette {
+    /// The palette name.
+    name: Option<String>,
+    /// The palette author.
+    author: Option<String>,
+    /// The palette swatches, in order.
+    swatches: Vec<Swatch>,
+}
+
+impl Palette {
+    /// Constructs a new empty `Palette`.
+    pub fn new() -> Self {
+        Palette { name: None, author: None, swatches: Vec::new() }
+    }
+
+    /// Constructs the 216 color web-safe palette, whose channels are each one
+    /// of `00`, `33`, `66`, `99`, `cc`, or `ff`. Swatches are named by their
+    /// hex code, ordered by red, then green, then blue.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palette = Palette::web_safe();
+    ///
+    /// assert_eq!(palette.len(), 216);
+    /// assert_eq!(palette.get("#ff9933"), Some(Rgb::new(255, 153, 51)));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn web_safe() -> Self {
+        let mut palette = Palette::new();
+        palette.set_name(Some("Web Safe"));
+        for r in 0..6u8 {
+            for g in 0..6u8 {
+                for b in 0..6u8 {
+                    let color = Rgb::new(r * 0x33, g * 0x33, b * 0x33);
+                    let _ = palette.insert(
+                        io::fallback_name(String::new(), color),
+                        color);
+                }
+            }
+        }
+        palette
+    }
+
+    /// Returns the palette name.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets the palette name.
+    pub fn set_name<S>(&mut self, name: Option<S>) where S: Into<String> {
+        self.name = name.map(Into::into);
+    }
+
+    /// Returns the palette author.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Sets the palette author.
+    pub fn set_author<S>(&mut self, author: Option<S>) where S: Into<String> {
+        self.author = author.map(Into::into);
+    }
+
+    /// Returns the number of swatches in the palette.
+    pub fn len(&self) -> usize {
+        self.swatches.len()
+    }
+
+    /// Returns true if the palette contains no swatches.
+    pub fn is_empty(&self) -> bool {
+        self.swatches.is_empty()
+    }
+
+    /// Inserts a named color into the palette. If the name is already present,
+    /// its color is replaced without changing its position, and the previous
+    /// color is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut palette = Palette::new();
+    ///
+    /// assert_eq!(palette.insert("accent", Rgb::new(255, 0, 0)), None);
+    /// assert_eq!(palette.insert("accent", Rgb::new(0, 0, 255)),
+    ///     Some(Rgb::new(255, 0, 0)));
+    /// assert_eq!(palette.len(), 1);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn insert<S>(&mut self, name: S, color: Rgb) -> Option<Rgb>
+        where S: Into<String>
+    {
+        let name = name.into();
+        match self.position(&name) {
+            Some(index) => {
+                let previous = self.swatches[index].color;
+                self.swatches[index].color = color;
+                Some(previous)
+            },
+            None => {
+                self.swatches.push(Swatch { name, color });
+                None
+            },
+        }
+    }
+
+    /// Removes the named color from the palette, returning its color.
+    pub fn remove(&mut self, name: &str) -> Option<Rgb> {
+        self.position(name)
+            .map(|index| self.swatches.remove(index).color)
+    }
+
+    /// Returns the color with the given name.
+    pub fn get(&self, name: &str) -> Option<Rgb> {
+        self.position(name).map(|index| self.swatches[index].color)
+    }
+
+    /// Returns true if the palette contains a color with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// Returns the index of the color with the given name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.swatches.iter().position(|swatch| swatch.name == name)
+    }
+
+    /// Returns the palette swatches, in order.
+    pub fn swatches(&self) -> &[Swatch] {
+        &self.swatches[..]
+    }
+
+    /// Returns an iterator over the palette swatches, in order.
+    pub fn iter(&self) -> slice::Iter<'_, Swatch> {
+        self.swatches.iter()
+    }
+
+    /// Returns an iterator over the palette colors, in order.
+    pub fn colors(&self) -> impl Iterator<Item=Rgb> + '_ {
+        self.swatches.iter().map(|swatch| swatch.color)
+    }
+
+    /// Writes a truecolor swatch row for each color, labelled with its hex
+    /// code and name, preceded by the palette name if it has one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut palette: Palette = vec![
+    ///     ("tomato", Rgb::new(255, 99, 71)),
+    ///     ("teal", Rgb::new(0, 128, 128)),
+    /// ].into_iter().collect();
+    /// palette.set_name(Some("Demo"));
+    ///
+    /// let mut out = Vec::new();
+    /// palette.print_preview(&mut out)?;
+    /// let out = String::from_utf8(out)?;
+    ///
+    /// assert!(out.starts_with("Demo\n"));
+    /// assert!(out.contains("#ff6347 tomato\n"));
+    /// assert!(out.contains("#008080 teal\n"));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn print_preview<W>(&self, out: &mut W) -> std::io::Result<()>
+        where W: std::io::Write + ?Sized
+    {
+        if let Some(name) = self.name() {
+            writeln!(out, "{}", name)?;
+        }
+        for swatch in self.swatches.iter() {
+            write_swatch_row(out, swatch.color, &swatch.name)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the palette in the JSON palette format.
+    ///
+    /// The format is an object with the following fields:
+    ///
+    /// | Field      | Description |
+    /// | ---------- | ----------- |
+    /// | `version`  | The format version, currently `1`. |
+    /// | `name`     | The palette name, or `null`. |
+    /// | `author`   | The palette author, or `null`. |
+    /// | `swatches` | An array of swatch objects, in palette order. |
+    ///
+    /// Each swatch object has a `name` field and one or more color fields:
+    ///
+    /// | Field   | Description |
+    /// | ------- | ----------- |
+    /// | `hex`   | A `"#rrggbb"` hex code. |
+    /// | `rgb`   | An `[r, g, b]` array of octets. |
+    /// | `hsl`   | An `[h, s, l]` array with the hue in degrees. |
+    /// | `oklab` | An `[l, a, b]` array of Oklab components. |
+    ///
+    /// All color fields are written, but only one is required when reading,
+    /// and they are read in the order listed. As the `hex` field is exact,
+    /// palettes round trip without loss.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut palette = Palette::new();
+    /// palette.set_name(Some("Sunset"));
+    /// palette.insert("ember", Rgb::new(230, 80, 40));
+    ///
+    /// let json = palette.to_json();
+    /// assert!(json.contains(r##""hex": "#e65028""##));
+    ///
+    /// assert_eq!(Palette::from_json(&json)?, palette);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> String {
+        let mut json = Vec::new();
+        io::json::write(self, &mut json)
+            .expect("write palette JSON to memory");
+        String::from_utf8(json).expect("valid UTF-8 palette JSON")
+    }
+
+    /// Parses a palette in the JSON palette format described by
+    /// [`Palette::to_json`].
+    ///
+    /// [`Palette::to_json`]: #method.to_json
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PaletteError`] if the text is not a valid JSON palette.
+    ///
+    /// [`PaletteError`]: io/enum.PaletteError.html
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, io::PaletteError> {
+        io::json::read(json.as_bytes())
+    }
+
+    /// Removes every swatch whose color is within `threshold` of an earlier
+    /// swatch, as measured by [`Lab::delta_e`]. The first of each group of
+    /// similar colors is retained.
+    ///
+    /// [`Lab::delta_e`]: ../struct.Lab.html#method.delta_e
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut palette: Palette = vec![
+    ///     ("red", Rgb::new(255, 0, 0)),
+    ///     ("scarlet", Rgb::new(252, 2, 1)),
+    ///     ("blue", Rgb::new(0, 0, 255)),
+    /// ].into_iter().collect();
+    ///
+    /// palette.dedup(2.0);
+    ///
+    /// assert!(palette.contains("red"));
+    /// assert!(!palette.contains("scarlet"));
+    /// assert_eq!(palette.len(), 2);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn dedup(&mut self, threshold: f32) {
+        self.dedup_by(threshold, DeltaE::Ciede2000);
+    }
+
+    /// Removes every swatch whose color is within `threshold` of an earlier
+    /// swatch, as measured by the given [`Metric`]. The first of each group of
+    /// similar colors is retained.
+    ///
+    /// [`Metric`]: ../metric/trait.Metric.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::metric::Euclidean;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut palette: Palette = vec![
+    ///     ("gray", Rgb::new(128, 128, 128)),
+    ///     ("grey", Rgb::new(130, 128, 128)),
+    ///     ("silver", Rgb::new(192, 192, 192)),
+    /// ].into_iter().collect();
+    ///
+    /// palette.dedup_by(5.0, Euclidean::Rgb);
+    ///
+    /// assert!(!palette.contains("grey"));
+    /// assert_eq!(palette.len(), 2);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn dedup_by<M>(&mut self, threshold: f32, metric: M) where M: Metric {
+        let mut kept: Vec<Rgb> = Vec::with_capacity(self.swatches.len());
+        self.swatches.retain(|swatch| {
+            let unique = kept
+                .iter()
+                .all(|&other| metric.distance(other, swatch.color) > threshold);
+            if unique { kept.push(swatch.color); }
+            unique
+        });
+    }
+
+    /// Returns a similarity score between 0 and 1 for the colors of two
+    /// palettes, ignoring their names and order. Identical palettes score 1,
+    /// and the score falls toward 0 as their [`match_distance`] grows. A
+    /// matching distance of 2 ΔE*00, which is barely noticeable, scores about
+    /// 0.82.
+    ///
+    /// [`match_distance`]: #method.match_distance
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let theme: Palette = vec![
+    ///     ("primary", Rgb::new(37, 99, 235)),
+    ///     ("danger", Rgb::new(220, 38, 38)),
+    /// ].into_iter().collect();
+    /// let tweaked: Palette = vec![
+    ///     ("error", Rgb::new(222, 40, 36)),
+    ///     ("brand", Rgb::new(37, 99, 235)),
+    /// ].into_iter().collect();
+    ///
+    /// assert_eq!(theme.similarity(&theme), 1.0);
+    /// assert!(theme.similarity(&tweaked) > 0.9);
+    /// assert!(theme.similarity(&Palette::web_safe()) < 0.5);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn similarity(&self, other: &Palette) -> f32 {
+        (-self.match_distance(other) / SIMILARITY_SCALE).exp()
+    }
+
+    /// Returns the mean ΔE*00 between the colors of two palettes when each
+    /// color is matched to a distinct color of the other palette, choosing
+    /// the matching with the lowest total cost. Colors left over in the
+    /// larger palette are compared with their nearest color in the smaller
+    /// one.
+    ///
+    /// Returns 0 if both palettes are empty, and infinity if only one is.
+    pub fn match_distance(&self, other: &Palette) -> f32 {
+        self.match_distance_by(other, DeltaE::Ciede2000)
+    }
+
+    /// Returns the mean distance between the colors of two palettes, as
+    /// measured by the given [`Metric`], when each color is matched to a
+    /// distinct color of the other palette, choosing the matching with the
+    /// lowest total cost. Colors left over in the larger palette are compared
+    /// with their nearest color in the smaller one.
+    ///
+    /// Returns 0 if both palettes are empty, and infinity if only one is.
+    ///
+    /// [`Metric`]: ../metric/trait.Metric.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::metric::Euclidean;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let a: Palette = vec![
+    ///     ("black", Rgb::new(0, 0, 0)),
+    ///     ("maroon", Rgb::new(100, 0, 0)),
+    /// ].into_iter().collect();
+    /// let b: Palette = vec![
+    ///     ("dark red", Rgb::new(90, 0, 0)),
+    ///     ("red", Rgb::new(200, 0, 0)),
+    /// ].into_iter().collect();
+    ///
+    /// // Pairing "maroon" with its nearest color would leave "black" with
+    /// // "red", for a total of 210. The optimal matching totals 190.
+    /// assert_eq!(a.match_distance_by(&b, Euclidean::Rgb), 95.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn match_distance_by<M>(&self, other: &Palette, metric: M) -> f32
+        where M: Metric
+    {
+        let a: Vec<Rgb> = self.colors().collect();
+        let b: Vec<Rgb> = other.colors().collect();
+        if a.is_empty() || b.is_empty() {
+            return if a.len() == b.len() { 0.0 } else { f32::INFINITY };
+        }
+
+        let (pairs, _) = optimal_matching(&a, &b, &metric);
+        let mut matched_a = vec![false; a.len()];
+        let mut matched_b = vec![false; b.len()];
+        let mut total = 0.0;
+        for &(i, j) in pairs.iter() {
+            matched_a[i] = true;
+            matched_b[j] = true;
+            total += metric.distance(a[i], b[j]);
+        }
+        let leftover = |colors: &[Rgb], matched: &[bool], others: &[Rgb]| {
+            colors.iter()
+                .zip(matched.iter())
+                .filter(|(_, &m)| !m)
+                .map(|(&c, _)| others.iter()
+                    .map(|&o| metric.distance(c, o))
+                    .fold(f32::INFINITY, f32::min))
+                .sum::<f32>()
+        };
+        total += leftover(&a, &matched_a, &b);
+        total += leftover(&b, &matched_b, &a);
+        total / a.len().max(b.len()) as f32
+    }
+
+    /// Returns how well the palette covers the given target colors, such as
+    /// the pixels of an image, as the ΔE*00 from each target to its nearest
+    /// palette color. See [`coverage_by`].
+    ///
+    /// [`coverage_by`]: #method.coverage_by
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # use color::quantize::KMeans;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let pixels: Vec<Rgb> = (0..=255)
+    ///     .map(|v| Rgb::new(v, 255 - v, 128))
+    ///     .collect();
+    ///
+    /// let small = KMeans::new(2).extract(&pixels);
+    /// let large = KMeans::new(8).extract(&pixels);
+    ///
+    /// assert!(large.coverage(pixels.iter().copied()).mean
+    ///     < small.coverage(pixels.iter().copied()).mean);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn coverage<I>(&self, targets: I) -> Coverage
+        where I: IntoIterator<Item=Rgb>
+    {
+        self.coverage_by(targets, DeltaE::Ciede2000)
+    }
+
+    /// Returns how well the palette covers the given target colors, as the
+    /// distance from each target to its nearest palette color, measured by
+    /// the given [`Metric`].
+    ///
+    /// If there are no targets, every statistic is zero. If the palette is
+    /// empty, every statistic is infinite.
+    ///
+    /// [`Metric`]: ../metric/trait.Metric.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::metric::Euclidean;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palette: Palette = vec![
+    ///     ("black", Rgb::new(0, 0, 0)),
+    ///     ("white", Rgb::new(255, 255, 255)),
+    /// ].into_iter().collect();
+    /// let targets = vec![
+    ///     Rgb::new(0, 0, 0),
+    ///     Rgb::new(0, 0, 30),
+    ///     Rgb::new(255, 255, 205),
+    /// ];
+    ///
+    /// let coverage = palette.coverage_by(targets, Euclidean::Rgb);
+    ///
+    /// assert_eq!(coverage.mean, 80.0 / 3.0);
+    /// assert_eq!(coverage.max, 50.0);
+    /// assert_eq!(coverage.worst, Some(Rgb::new(255, 255, 205)));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn coverage_by<I, M>(&self, targets: I, metric: M) -> Coverage
+        where
+            I: IntoIterator<Item=Rgb>,
+            M: Metric,
+    {
+        let mut coverage = Coverage::default();
+        let mut total = 0.0;
+        let mut count = 0usize;
+        for target in targets {
+            let distance = self.colors()
+                .map(|color| metric.distance(target, color))
+                .fold(f32::INFINITY, f32::min);
+            if coverage.worst.is_none() || distance > coverage.max {
+                coverage.max = distance;
+                coverage.worst = Some(target);
+            }
+            total += distance;
+            count += 1;
+        }
+        if count > 0 { coverage.mean = total / count as f32; }
+        coverage
+    }
+
+    /// Returns how well the palette covers the whole sRGB gamut, sampled as
+    /// a grid of 16 levels per channel, measured in ΔE*00.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let coverage = Palette::web_safe().gamut_coverage();
+    ///
+    /// assert!(coverage.mean < 5.0);
+    /// assert!(coverage.max < 15.0);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn gamut_coverage(&self) -> Coverage {
+        let level = |i: u16| (i * 255 / (GAMUT_LEVELS - 1)) as u8;
+        let samples = (0..GAMUT_LEVELS).flat_map(|r| {
+            (0..GAMUT_LEVELS).flat_map(move |g| {
+                (0..GAMUT_LEVELS)
+                    .map(move |b| Rgb::new(level(r), level(g), level(b)))
+            })
+        });
+        self.coverage(samples)
+    }
+
+    /// Returns statistics of the [`warmth`] of the palette colors.
+    ///
+    /// [`warmth`]: ../struct.Rgb.html#method.warmth
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let autumn: Palette = vec![
+    ///     ("rust", Rgb::new(183, 65, 14)),
+    ///     ("gold", Rgb::new(218, 165, 32)),
+    ///     ("bark", Rgb::new(90, 60, 40)),
+    ///     ("sky", Rgb::new(120, 160, 200)),
+    /// ].into_iter().collect();
+    ///
+    /// let warmth = autumn.warmth();
+    ///
+    /// assert_eq!((warmth.warm, warmth.cool, warmth.neutral), (3, 1, 0));
+    /// assert!(warmth.mean > 0.3);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn warmth(&self) -> WarmthSummary {
+        let mut summary = WarmthSummary::default();
+        let mut total = 0.0;
+        for color in self.colors() {
+            let warmth = color.warmth();
+            total += warmth;
+            if warmth > WARMTH_THRESHOLD {
+                summary.warm += 1;
+            } else if warmth < -WARMTH_THRESHOLD {
+                summary.cool += 1;
+            } else {
+                summary.neutral += 1;
+            }
+        }
+        if !self.is_empty() { summary.mean = total / self.len() as f32; }
+        summary
+    }
+
+    /// Returns a pastel rendition of the palette, with light colors of low
+    /// chroma.
+    ///
+    /// The transform is done in [`Oklch`]. Each hue is preserved, and the
+    /// lightness and chroma are compressed into narrower ranges, so the
+    /// ordering of colors by lightness and by chroma is preserved as well.
+    /// Swatch names and palette metadata are kept.
+    ///
+    /// [`Oklch`]: ../struct.Oklch.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palette: Palette = vec![
+    ///     ("red", Rgb::new(220, 30, 40)),
+    ///     ("navy", Rgb::new(20, 30, 110)),
+    /// ].into_iter().collect();
+    ///
+    /// let pastel = palette.pastel();
+    ///
+    /// for color in pastel.colors() {
+    ///     let oklch = Oklch::from(color);
+    ///     assert!(oklch.l > 0.8 && oklch.c < 0.1);
+    /// }
+    /// assert!(pastel.get("navy").is_some());
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn pastel(&self) -> Self {
+        self.map_oklch(|oklch| Oklch {
+            l: remap(oklch.l, PASTEL_LIGHTNESS),
+            c: oklch.c * PASTEL_CHROMA_SCALE,
+            ..oklch
+        })
+    }
+
+    /// Returns a neon rendition of the palette, with bright colors at the
+    /// highest chroma the sRGB gamut allows. Neutral colors are lightened
+    /// but left unsaturated.
+    ///
+    /// The transform is done in [`Oklch`]. Each hue is preserved, and the
+    /// lightness is compressed into a narrower range, so the ordering of
+    /// colors by lightness is preserved as well. Swatch names and palette
+    /// metadata are kept.
+    ///
+    /// [`Oklch`]: ../struct.Oklch.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palette: Palette = vec![
+    ///     ("rose", Rgb::new(190, 120, 130)),
+    ///     ("gray", Rgb::new(128, 128, 128)),
+    /// ].into_iter().collect();
+    ///
+    /// let neon = palette.neon();
+    ///
+    /// let rose = Oklch::from(neon.get("rose").unwrap());
+    /// assert!(rose.c > 1.5 * Oklch::from(Rgb::new(190, 120, 130)).c);
+    /// assert!(Oklch::from(neon.get("gray").unwrap()).c < 0.01);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn neon(&self) -> Self {
+        self.map_oklch(|oklch| Oklch {
+            l: remap(oklch.l, NEON_LIGHTNESS),
+            // The chroma is reduced to the gamut boundary when mapped.
+            c: if oklch.c < NEUTRAL_CHROMA { oklch.c } else { 1.0 },
+            ..oklch
+        })
+    }
+
+    /// Returns a muted rendition of the palette, with mid-lightness colors
+    /// of reduced chroma.
+    ///
+    /// The transform is done in [`Oklch`]. Each hue is preserved, and the
+    /// lightness and chroma are compressed into narrower ranges, so the
+    /// ordering of colors by lightness and by chroma is preserved as well.
+    /// Swatch names and palette metadata are kept.
+    ///
+    /// [`Oklch`]: ../struct.Oklch.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Oklch;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palette: Palette = vec![
+    ///     ("lime", Rgb::new(50, 230, 40)),
+    ///     ("black", Rgb::new(0, 0, 0)),
+    /// ].into_iter().collect();
+    ///
+    /// let muted = palette.muted();
+    ///
+    /// for color in muted.colors() {
+    ///     let oklch = Oklch::from(color);
+    ///     assert!(oklch.l > 0.35 && oklch.l < 0.75 && oklch.c < 0.1);
+    /// }
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn muted(&self) -> Self {
+        self.map_oklch(|oklch| Oklch {
+            l: remap(oklch.l, MUTED_LIGHTNESS),
+            c: oklch.c * MUTED_CHROMA_SCALE,
+            ..oklch
+        })
+    }
+
+    /// Returns a copy of the palette with each color transformed in
+    /// [`Oklch`] and mapped back into the sRGB gamut.
+    ///
+    /// [`Oklch`]: ../struct.Oklch.html
+    fn map_oklch<F>(&self, f: F) -> Self where F: Fn(Oklch) -> Oklch {
+        let mut palette = self.clone();
+        for swatch in palette.swatches.iter_mut() {
+            swatch.color = gamut_map(f(Oklch::from(swatch.color)));
+        }
+        palette
+    }
+
+    /// Returns the differences between this palette and a later revision of
+    /// it, matching swatches by name. Added swatches are listed in the order
+    /// of the `other` palette, and removed and changed swatches in the order
+    /// of this palette.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let old: Palette = vec![
+    ///     ("primary", Rgb::new(37, 99, 235)),
+    ///     ("danger", Rgb::new(220, 38, 38)),
+    ///     ("muted", Rgb::new(100, 116, 139)),
+    /// ].into_iter().collect();
+    /// let new: Palette = vec![
+    ///     ("primary", Rgb::new(29, 78, 216)),
+    ///     ("danger", Rgb::new(220, 38, 38)),
+    ///     ("success", Rgb::new(22, 163, 74)),
+    /// ].into_iter().collect();
+    ///
+    /// let diff = old.diff(&new);
+    ///
+    /// assert_eq!(diff.added[0].name, "success");
+    /// assert_eq!(diff.removed[0].name, "muted");
+    /// assert_eq!(diff.changed[0].name, "primary");
+    /// assert!(diff.changed[0].delta_e > 5.0);
+    /// assert_eq!(diff.to_string(), "\
+    ///     + success #16a34a\n\
+    ///     - muted #64748b\n\
+    ///     ~ primary #2563eb -> #1d4ed8 (ΔE 7.13)\n");
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn diff(&self, other: &Palette) -> PaletteDiff {
+        let added = other.swatches.iter()
+            .filter(|swatch| !self.contains(&swatch.name))
+            .cloned()
+            .collect();
+        let removed = self.swatches.iter()
+            .filter(|swatch| !other.contains(&swatch.name))
+            .cloned()
+            .collect();
+        let changed = self.swatches.iter()
+            .filter_map(|swatch| other.get(&swatch.name)
+                .filter(|&new| new != swatch.color)
+                .map(|new| SwatchChange {
+                    name: swatch.name.clone(),
+                    old: swatch.color,
+                    new,
+                    delta_e: Lab::delta_e(swatch.color, new),
+                }))
+            .collect();
+        PaletteDiff { added, removed, changed }
+    }
+
+    /// Returns a mapping from each color of this palette to a color of a
+    /// `new` palette, as measured by ΔE*00. See [`migration_to_by`].
+    ///
+    /// [`migration_to_by`]: #method.migration_to_by
+    pub fn migration_to(&self, new: &Palette) -> Migration {
+        self.migration_to_by(new, DeltaE::Ciede2000)
+    }
+
+    /// Returns a mapping from each color of this palette to a color of a
+    /// `new` palette, as measured by the given [`Metric`].
+    ///
+    /// Colors are mapped to distinct colors of the new palette, choosing the
+    /// mapping with the lowest total distance, so two old roles are not
+    /// merged into one new color while another goes unused. If this palette
+    /// has more colors than the new one, the colors left over are mapped to
+    /// their nearest new color. If the new palette is empty, the migration
+    /// is empty.
+    ///
+    /// [`Metric`]: ../metric/trait.Metric.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::metric::Euclidean;
+    /// # use color::palette::Palette;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let old: Palette = vec![
+    ///     ("text", Rgb::new(50, 50, 50)),
+    ///     ("border", Rgb::new(60, 60, 60)),
+    /// ].into_iter().collect();
+    /// let new: Palette = vec![
+    ///     ("gray-900", Rgb::new(20, 20, 20)),
+    ///     ("gray-700", Rgb::new(55, 55, 55)),
+    ///     ("gray-300", Rgb::new(200, 200, 200)),
+    /// ].into_iter().collect();
+    ///
+    /// let migration = old.migration_to_by(&new, Euclidean::Rgb);
+    ///
+    /// // Both old colors are nearest to "gray-700", but only one can use it.
+    /// assert_eq!(migration.get("text").unwrap().name, "gray-900");
+    /// assert_eq!(migration.get("border").unwrap().name, "gray-700");
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn migration_to_by<M>(&self, new: &Palette, metric: M) -> Migration
+        where M: Metric
+    {
+        let old_colors: Vec<Rgb> = self.colors().collect();
+        let new_colors: Vec<Rgb> = new.colors().collect();
+        if new_colors.is_empty() { return Migration::default(); }
+
+        let (pairs, _) = optimal_matching(&old_colors, &new_colors, &metric);
+        let mut targets: Vec<Option<usize>> = vec![None; old_colors.len()];
+        for (i, j) in pairs {
+            targets[i] = Some(j);
+        }
+
+        let mut entries = Vec::with_capacity(self.swatches.len());
+        let mut total_cost = 0.0;
+        for (swatch, target) in self.swatches.iter().zip(targets) {
+            let (j, distance) = match target {
+                Some(j) => (j, metric.distance(swatch.color, new_colors[j])),
+                None => new_colors.iter()
+                    .map(|&c| metric.distance(swatch.color, c))
+                    .enumerate()
+                    .fold((0, f32::INFINITY), |nearest, next| {
+                        if next.1 < nearest.1 { next } else { nearest }
+                    }),
+            };
+            total_cost += distance;
+            entries.push(MigrationEntry {
+                old: swatch.clone(),
+                new: new.swatches[j].clone(),
+                distance,
+            });
+        }
+        Migration { entries, total_cost }
+    }
+
+    /// Returns the palette at the ratio given by `amount` between the
+    /// `start` and `end` palettes, for animating a transition between them.
+    /// `amount` is clamped between 0 and 1.
+    ///
+    /// Swatches are paired using the given [`SwatchMatching`], and each pair
+    /// is interpolated in [`Oklab`]. A swatch left without a partner, such as
+    /// when the palettes differ in size, is interpolated toward or from the
+    /// nearest color of the other palette, and is only included while it is
+    /// visible: swatches of `start` are dropped at an `amount` of 1, and
+    /// swatches of `end` are absent at an `amount` of 0. So the result is
+    /// `start` at 0 and `end` at 1.
+    ///
+    /// Below an `amount` of 0.5, the result has the names, metadata, and
+    /// swatch order of `start`, followed by any swatches only in `end`.
+    /// Otherwise it has those of `end`, followed by any swatches only in
+    /// `start`. If two swatches of the result have the same name, the later
+    /// one replaces the color of the earlier.
+    ///
+    /// [`SwatchMatching`]: enum.SwatchMatching.html
+    /// [`Oklab`]: ../struct.Oklab.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # use color::palette::SwatchMatching;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let day: Palette = vec![
+    ///     ("sky", Rgb::new(135, 206, 235)),
+    ///     ("sun", Rgb::new(255, 215, 0)),
+    /// ].into_iter().collect();
+    /// let night: Palette = vec![
+    ///     ("sky", Rgb::new(25, 25, 80)),
+    ///     ("sun", Rgb::new(200, 200, 220)),
+    /// ].into_iter().collect();
+    ///
+    /// let by_name = SwatchMatching::Name;
+    /// let dusk = Palette::interpolate(&day, &night, 0.5, by_name);
+    ///
+    /// let sky = dusk.get("sky").unwrap();
+    /// assert!(sky.b < 235 && sky.b > 80);
+    /// assert_eq!(Palette::interpolate(&day, &night, 1.0, by_name), night);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn interpolate(
+        start: &Palette,
+        end: &Palette,
+        amount: f32,
+        matching: SwatchMatching)
+        -> Palette
+    {
+        let amount = clamped(amount, 0.0, 1.0);
+        let start_colors: Vec<Rgb> = start.colors().collect();
+        let end_colors: Vec<Rgb> = end.colors().collect();
+
+        let mut partners: Vec<Option<usize>> = vec![None; start.len()];
+        match matching {
+            SwatchMatching::Name => {
+                for (i, swatch) in start.swatches.iter().enumerate() {
+                    partners[i] = end.position(&swatch.name);
+                }
+            },
+            SwatchMatching::Optimal => {
+                let (pairs, _) = optimal_matching(
+                    &start_colors,
+                    &end_colors,
+                    &DeltaE::Ciede2000);
+                for (i, j) in pairs {
+                    partners[i] = Some(j);
+                }
+            },
+        }
+        let mut matched = vec![false; end.len()];
+        for &j in partners.iter().flatten() {
+            matched[j] = true;
+        }
+
+        let nearest = |color: Rgb, colors: &[Rgb]| colors.iter()
+            .copied()
+            .min_by(|&a, &b| Oklab::distance(color, a)
+                .total_cmp(&Oklab::distance(color, b)))
+            .unwrap_or(color);
+        let lerp = |a: Rgb, b: Rgb| {
+            Rgb::from(Oklab::linear_interpolate(a, b, amount))
+        };
+
+        let ends_first = amount >= 0.5;
+        // Each entry holds the sort key, name, and color of a swatch.
+        let mut entries: Vec<((bool, usize), &str, Rgb)> = Vec::new();
+        for (i, swatch) in start.swatches.iter().enumerate() {
+            match partners[i] {
+                Some(j) => {
+                    let (key, name) = if ends_first {
+                        (j, &end.swatches[j].name)
+                    } else {
+                        (i, &swatch.name)
+                    };
+                    entries.push(((false, key), name,
+                        lerp(swatch.color, end.swatches[j].color)));
+                },
+                None if amount < 1.0 => {
+                    let target = nearest(swatch.color, &end_colors);
+                    entries.push(((ends_first, i), &swatch.name,
+                        lerp(swatch.color, target)));
+                },
+                None => (),
+            }
+        }
+        for (j, swatch) in end.swatches.iter().enumerate() {
+            if matched[j] || amount <= 0.0 { continue; }
+            let source = nearest(swatch.color, &start_colors);
+            entries.push(((!ends_first, j), &swatch.name,
+                lerp(source, swatch.color)));
+        }
+        entries.sort_by_key(|&(key, _, _)| key);
+
+        let metadata = if ends_first { end } else { start };
+        let mut palette: Palette = entries.into_iter()
+            .map(|(_, name, color)| (name, color))
+            .collect();
+        palette.name = metadata.name.clone();
+        palette.author = metadata.author.clone();
+        palette
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// nearest_in
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the index and color of the swatch in `palette` nearest to the given
+/// color, as measured by the given [`Metric`], or `None` if the palette is
+/// empty. The first swatch is returned if several are equally near.
+///
+/// [`Metric`]: ../metric/trait.Metric.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::metric::DeltaE;
+/// # use color::palette::Palette;
+/// # use color::palette::nearest_in;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let palette = Palette::web_safe();
+///
+/// let (index, snapped) = nearest_in(
+///         Rgb::new(250, 140, 60),
+///         &palette,
+///         DeltaE::Ciede2000)
+///     .expect("nonempty palette");
+///
+/// assert_eq!(snapped, Rgb::new(255, 153, 51));
+/// assert_eq!(palette.swatches()[index].name, "#ff9933");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn nearest_in<C, M>(color: C, palette: &Palette, metric: M)
+    -> Option<(usize, Rgb)>
+    where
+        C: Into<Rgb>,
+        M: Metric,
+{
+    let color = color.into();
+    palette
+        .colors()
+        .enumerate()
+        .map(|(i, candidate)| (i, candidate, metric.distance(color, candidate)))
+        .fold(None, |nearest: Option<(usize, Rgb, f32)>, next| match nearest {
+            Some(n) if n.2 <= next.2 => Some(n),
+            _                        => Some(next),
+        })
+        .map(|(i, candidate, _)| (i, candidate))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Coverage
+////////////////////////////////////////////////////////////////////////////////
+/// How well a [`Palette`] covers a set of target colors, as returned by
+/// [`Palette::coverage`].
+///
+/// [`Palette`]: struct.Palette.html
+/// [`Palette::coverage`]: struct.Palette.html#method.coverage
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Coverage {
+    /// The mean distance from a target to its nearest palette color.
+    pub mean: f32,
+    /// The largest distance from a target to its nearest palette color.
+    pub max: f32,
+    /// The target farthest from every palette color, or `None` if there are
+    /// no targets.
+    pub worst: Option<Rgb>,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// WarmthSummary
+////////////////////////////////////////////////////////////////////////////////
+/// Statistics of the warmth of the colors of a [`Palette`], as returned by
+/// [`Palette::warmth`].
+///
+/// [`Palette`]: struct.Palette.html
+/// [`Palette::warmth`]: struct.Palette.html#method.warmth
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WarmthSummary {
+    /// The mean warmth, between -1 and 1, or 0 for an empty palette.
+    pub mean: f32,
+    /// The number of warm colors.
+    pub warm: usize,
+    /// The number of cool colors.
+    pub cool: usize,
+    /// The number of colors which are neither warm nor cool.
+    pub neutral: usize,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PaletteDiff
+////////////////////////////////////////////////////////////////////////////////
+/// The differences between two revisions of a [`Palette`], as returned by
+/// [`Palette::diff`].
+///
+/// The `Display` implementation writes a plain text changelog, with one line
+/// per difference.
+///
+/// [`Palette`]: struct.Palette.html
+/// [`Palette::diff`]: struct.Palette.html#method.diff
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PaletteDiff {
+    /// The swatches whose names appear only in the later revision.
+    pub added: Vec<Swatch>,
+    /// The swatches whose names appear only in the earlier revision.
+    pub removed: Vec<Swatch>,
+    /// The swatches whose colors differ between the revisions.
+    pub changed: Vec<SwatchChange>,
+}
+
+impl PaletteDiff {
+    /// Returns true if the revisions have the same named colors.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for PaletteDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        for swatch in self.added.iter() {
+            writeln!(f, "+ {} {:x}", swatch.name, swatch.color)?;
+        }
+        for swatch in self.removed.iter() {
+            writeln!(f, "- {} {:x}", swatch.name, swatch.color)?;
+        }
+        for change in self.changed.iter() {
+            writeln!(f, "~ {} {:x} -> {:x} (ΔE {:.2})",
+                change.name, change.old, change.new, change.delta_e)?;
+        }
+        Ok(())
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SwatchChange
+////////////////////////////////////////////////////////////////////////////////
+/// A named color which differs between two revisions of a [`Palette`].
+///
+/// [`Palette`]: struct.Palette.html
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwatchChange {
+    /// The name of the color.
+    pub name: String,
+    /// The color in the earlier revision.
+    pub old: Rgb,
+    /// The color in the later revision.
+    pub new: Rgb,
+    /// The difference between the colors, in ΔE*00.
+    pub delta_e: f32,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Migration
+////////////////////////////////////////////////////////////////////////////////
+/// A mapping from the colors of one [`Palette`] to those of another, as
+/// returned by [`Palette::migration_to`].
+///
+/// [`Palette`]: struct.Palette.html
+/// [`Palette::migration_to`]: struct.Palette.html#method.migration_to
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Migration {
+    /// The mapping of each old swatch, in the order of the old palette.
+    pub entries: Vec<MigrationEntry>,
+    /// The sum of the distances of all entries.
+    pub total_cost: f32,
+}
+
+impl Migration {
+    /// Returns the new swatch that the old swatch with the given name maps
+    /// to.
+    pub fn get(&self, old_name: &str) -> Option<&Swatch> {
+        self.entries.iter()
+            .find(|entry| entry.old.name == old_name)
+            .map(|entry| &entry.new)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// MigrationEntry
+////////////////////////////////////////////////////////////////////////////////
+/// The mapping of one swatch within a [`Migration`].
+///
+/// [`Migration`]: struct.Migration.html
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigrationEntry {
+    /// The swatch of the old palette.
+    pub old: Swatch,
+    /// The swatch of the new palette it maps to.
+    pub new: Swatch,
+    /// The distance between their colors.
+    pub distance: f32,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SwatchMatching
+////////////////////////////////////////////////////////////////////////////////
+/// The method used by [`Palette::interpolate`] to pair the swatches of two
+/// palettes.
+///
+/// [`Palette::interpolate`]: struct.Palette.html#method.interpolate
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SwatchMatching {
+    /// Swatches with the same name are paired, as when the names are roles
+    /// such as "background" or "accent".
+    #[default]
+    Name,
+    /// Swatches are paired to minimize the total ΔE*00 between paired colors,
+    /// regardless of their names.
+    Optimal,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Palette conversions
+////////////////////////////////////////////////////////////////////////////////
+impl<S> FromIterator<(S, Rgb)> for Palette where S: Into<String> {
+    fn from_iter<I>(iter: I) -> Self where I: IntoIterator<Item=(S, Rgb)> {
+        let mut palette = Palette::new();
+        palette.extend(iter);
+        palette
+    }
+}
+
+impl<S> Extend<(S, Rgb)> for Palette where S: Into<String> {
+    fn extend<I>(&mut self, iter: I) where I: IntoIterator<Item=(S, Rgb)> {
+        for (name, color) in iter {
+            let _ = self.insert(name, color);
+        }
+    }
+}
+
+impl FromIterator<Swatch> for Palette {
+    fn from_iter<I>(iter: I) -> Self where I: IntoIterator<Item=Swatch> {
+        iter.into_iter()
+            .map(|swatch| (swatch.name, swatch.color))
+            .collect()
+    }
+}
+
+impl From<Vec<Swatch>> for Palette {
+    fn from(swatches: Vec<Swatch>) -> Self {
+        swatches.into_iter().collect()
+    }
+}
+
+impl From<Palette> for Vec<Swatch> {
+    fn from(palette: Palette) -> Self {
+        palette.swatches
+    }
+}
+
+impl IntoIterator for Palette {
+    type Item = Swatch;
+    type IntoIter = vec::IntoIter<Swatch>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.swatches.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Palette {
+    type Item = &'a Swatch;
+    type IntoIter = slice::Iter<'a, Swatch>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.swatches.iter()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the pairs of indices `(i, j)` matching colors of `a` with distinct
+/// colors of `b` with the lowest total distance, along with that total. Every
+/// color of the smaller slice is matched.
+fn optimal_matching<M>(a: &[Rgb], b: &[Rgb], metric: &M)
+    -> (Vec<(usize, usize)>, f32)
+    where M: Metric
+{
+    let transpose = a.len() > b.len();
+    let (rows, cols) = if transpose { (b, a) } else { (a, b) };
+    let costs: Vec<Vec<f64>> = rows.iter()
+        .map(|&r| cols.iter()
+            .map(|&c| {
+                let d = if transpose {
+                    metric.distance(c, r)
+                } else {
+                    metric.distance(r, c)
+                };
+                // Non-finite costs would stall the search, so they are
+                // treated as merely very large.
+                if d.is_finite() { d as f64 } else { 1e30 }
+            })
+            .collect())
+        .collect();
+
+    let assigned = min_cost_assignment(&costs, cols.len());
+    let total = assigned.iter()
+        .enumerate()
+        .map(|(row, &col)| costs[row][col])
+        .sum::<f64>() as f32;
+    let pairs = assigned.into_iter()
+        .enumerate()
+        .map(|(row, col)| if transpose { (col, row) } else { (row, col) })
+        .collect();
+    (pairs, total)
+}
+
+/// Returns the column assigned to each row of a cost matrix with no more
+/// rows than columns, minimizing the total cost. This is the Hungarian
+/// algorithm with potentials, running in O(rows² × cols) time.
+fn min_cost_assignment(costs: &[Vec<f64>], cols: usize) -> Vec<usize> {
+    let rows = costs.len();
+    debug_assert!(rows <= cols);
+
+    // Rows and columns are numbered from 1, with column 0 as a sentinel.
+    let mut u = vec![0.0; rows + 1];
+    let mut v = vec![0.0; cols + 1];
+    let mut row_of = vec![0; cols + 1];
+    let mut way = vec![0; cols + 1];
+    for row in 1..=rows {
+        row_of[0] = row;
+        let mut col = 0;
+        let mut min = vec![f64::INFINITY; cols + 1];
+        let mut used = vec![false; cols + 1];
+        loop {
+            used[col] = true;
+            let current = row_of[col];
+            let mut delta = f64::INFINITY;
+            let mut next = 0;
+            for j in 1..=cols {
+                if used[j] { continue; }
+                let reduced = costs[current - 1][j - 1] - u[current] - v[j];
+                if reduced < min[j] {
+                    min[j] = reduced;
+                    way[j] = col;
+                }
+                if min[j] < delta {
+                    delta = min[j];
+                    next = j;
+                }
+            }
+            for j in 0..=cols {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min[j] -= delta;
+                }
+            }
+            col = next;
+            if row_of[col] == 0 { break; }
+        }
+        while col != 0 {
+            let previous = way[col];
+            row_of[col] = row_of[previous];
+            col = previous;
+        }
+    }
+
+    let mut assigned = vec![0; rows];
+    for j in 1..=cols {
+        if row_of[j] != 0 { assigned[row_of[j] - 1] = j - 1; }
+    }
+    assigned
+}
+
+/// Maps a value between 0 and 1 linearly onto the given range.
+fn remap(value: f32, [low, high]: [f32; 2]) -> f32 {
+    low + clamped(value, 0.0, 1.0) * (high - low)
+}
@@ -0,0 +1,197 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Reading and writing palette files.
+//!
+//! Each supported format has its own module with `read` and `write`
+//! functions operating on any `std::io` reader or writer.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Internal modules.
+pub mod aco;
+pub mod act;
+pub mod ase;
+pub mod hex;
+#[cfg(feature = "json")]
+pub mod json;
+
+// Local imports.
+use crate::Cmyk;
+use crate::Rgb;
+
+// Standard library imports.
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ColorModel
+////////////////////////////////////////////////////////////////////////////////
+/// The color model used to store colors in a palette file.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum ColorModel {
+    /// Colors are stored as RGB components.
+    #[default]
+    Rgb,
+    /// Colors are stored as CMYK components.
+    Cmyk,
+    /// Colors are stored as CIE L*a*b* components.
+    Lab,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PaletteError
+////////////////////////////////////////////////////////////////////////////////
+/// An error which can be returned while reading or writing a palette file.
+#[derive(Debug)]
+pub enum PaletteError {
+    /// An error occurred in the underlying reader or writer.
+    Io(io::Error),
+    /// The data does not begin with the signature of the expected format.
+    InvalidSignature,
+    /// The file uses an unsupported version of the format.
+    UnsupportedVersion(u32),
+    /// A color is stored with an unsupported color model.
+    UnsupportedColorModel(String),
+    /// A swatch name is not valid UTF-16, or is too long to be stored.
+    InvalidName,
+    /// The data is malformed.
+    Malformed(&'static str),
+    /// The data is not valid JSON, or does not match the palette schema.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            PaletteError::Io(e) => write!(f, "palette i/o error: {}", e),
+            PaletteError::InvalidSignature => write!(f,
+                "invalid palette file signature"),
+            PaletteError::UnsupportedVersion(v) => write!(f,
+                "unsupported palette file version {}", v),
+            PaletteError::UnsupportedColorModel(m) => write!(f,
+                "unsupported color model {:?}", m),
+            PaletteError::InvalidName => write!(f, "invalid swatch name"),
+            PaletteError::Malformed(msg) => write!(f,
+                "malformed palette file: {}", msg),
+            #[cfg(feature = "json")]
+            PaletteError::Json(e) => write!(f, "palette JSON error: {}", e),
+        }
+    }
+}
+
+impl Error for PaletteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PaletteError::Io(e) => Some(e),
+            #[cfg(feature = "json")]
+            PaletteError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PaletteError {
+    fn from(error: io::Error) -> Self {
+        PaletteError::Io(error)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for PaletteError {
+    fn from(error: serde_json::Error) -> Self {
+        PaletteError::Json(error)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Reads a big-endian `u16`.
+pub(in crate) fn read_u16<R>(reader: &mut R) -> Result<u16, PaletteError>
+    where R: Read
+{
+    let mut bytes = [0; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_be_bytes(bytes))
+}
+
+/// Reads a big-endian `u32`.
+pub(in crate) fn read_u32<R>(reader: &mut R) -> Result<u32, PaletteError>
+    where R: Read
+{
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_be_bytes(bytes))
+}
+
+/// Reads a big-endian `f32`.
+pub(in crate) fn read_f32<R>(reader: &mut R) -> Result<f32, PaletteError>
+    where R: Read
+{
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(f32::from_be_bytes(bytes))
+}
+
+/// Reads `len` big-endian UTF-16 code units, dropping a trailing nul.
+pub(in crate) fn read_utf16<R>(reader: &mut R, len: usize)
+    -> Result<String, PaletteError>
+    where R: Read
+{
+    let mut units = Vec::with_capacity(len);
+    for _ in 0..len {
+        units.push(read_u16(reader)?);
+    }
+    if units.last() == Some(&0) { let _ = units.pop(); }
+    String::from_utf16(&units).map_err(|_| PaletteError::InvalidName)
+}
+
+/// Returns the big-endian UTF-16 code units of a name, with a trailing nul.
+pub(in crate) fn utf16_units(name: &str) -> Vec<u16> {
+    name.encode_utf16().chain(Some(0)).collect()
+}
+
+/// Writes a sequence of big-endian `u16` values.
+pub(in crate) fn write_u16s<W>(writer: &mut W, values: &[u16])
+    -> Result<(), PaletteError>
+    where W: Write
+{
+    for value in values {
+        writer.write_all(&value.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+/// Returns the name to use for a swatch read without one.
+pub(in crate) fn fallback_name(name: String, color: Rgb) -> String {
+    if name.is_empty() {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    } else {
+        name
+    }
+}
+
+/// Converts CMYK ink ratios into an `Rgb` color.
+pub(in crate) fn rgb_from_cmyk_ratios(ratios: [f32; 4]) -> Rgb {
+    let [c, m, y, k] = ratios.map(octet);
+    Rgb::from(Cmyk::new(c, m, y, k))
+}
+
+/// Converts a ratio into an octet, rounding and clamping it.
+pub(in crate) fn octet(ratio: f32) -> u8 {
+    (ratio.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8
+}
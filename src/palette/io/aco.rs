@@ -0,0 +1,254 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Photoshop color swatch (`.aco`) palette files.
+//!
+//! Files are written with both the version 1 section, which is read by older
+//! applications, and the version 2 section, which adds swatch names. Swatches
+//! stored with HSB, CMYK, L*a*b*, or grayscale components are converted to RGB
+//! when reading.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Cmyk;
+use crate::Hsv;
+use crate::Lab;
+use crate::palette::io::ColorModel;
+use crate::palette::io::PaletteError;
+use crate::palette::io::fallback_name;
+use crate::palette::io::read_u16;
+use crate::palette::io::read_u32;
+use crate::palette::io::read_utf16;
+use crate::palette::io::rgb_from_cmyk_ratios;
+use crate::palette::io::utf16_units;
+use crate::palette::io::write_u16s;
+use crate::palette::Palette;
+use crate::Rgb;
+
+// Standard library imports.
+use std::convert::TryFrom;
+use std::io::Read;
+use std::io::Write;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The color space identifier for RGB colors.
+const RGB: u16 = 0;
+
+/// The color space identifier for HSB colors.
+const HSB: u16 = 1;
+
+/// The color space identifier for CMYK colors.
+const CMYK: u16 = 2;
+
+/// The color space identifier for L*a*b* colors.
+const LAB: u16 = 7;
+
+/// The color space identifier for grayscale colors.
+const GRAYSCALE: u16 = 8;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// read
+////////////////////////////////////////////////////////////////////////////////
+/// Reads a palette from Photoshop color swatch data.
+///
+/// If the data contains a version 2 section, the swatch names are read from
+/// it. Otherwise, and for swatches without a name, the swatches are named by
+/// their hex code.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be read or is not a valid
+/// color swatch file.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::palette::Palette;
+/// # use color::palette::io::ColorModel;
+/// # use color::palette::io::aco;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut palette = Palette::new();
+/// palette.insert("Coral", Rgb::new(255, 127, 80));
+///
+/// let mut data = Vec::new();
+/// aco::write(&palette, ColorModel::Rgb, &mut data)?;
+///
+/// assert_eq!(aco::read(&data[..])?, palette);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn read<R>(mut reader: R) -> Result<Palette, PaletteError>
+    where R: Read
+{
+    let mut data = Vec::new();
+    let _ = reader.read_to_end(&mut data)?;
+    let mut data = &data[..];
+
+    let version = read_u16(&mut data)?;
+    if version != 1 && version != 2 {
+        return Err(PaletteError::UnsupportedVersion(version as u32));
+    }
+
+    let mut swatches = read_section(&mut data, version == 2)?;
+    if version == 1 && !data.is_empty() {
+        let version = read_u16(&mut data)?;
+        if version != 2 {
+            return Err(PaletteError::UnsupportedVersion(version as u32));
+        }
+        swatches = read_section(&mut data, true)?;
+    }
+
+    Ok(swatches
+        .into_iter()
+        .map(|(name, color)| (fallback_name(name, color), color))
+        .collect())
+}
+
+/// Reads the swatches of a file section, following the version number.
+fn read_section(data: &mut &[u8], named: bool)
+    -> Result<Vec<(String, Rgb)>, PaletteError>
+{
+    let count = read_u16(data)?;
+    let mut swatches = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let space = read_u16(data)?;
+        let mut w = [0; 4];
+        for value in w.iter_mut() {
+            *value = read_u16(data)?;
+        }
+        let color = read_color(space, w)?;
+
+        let name = if named {
+            let len = read_u32(data)? as usize;
+            if len > data.len() / 2 {
+                return Err(PaletteError::Malformed("swatch name too long"));
+            }
+            read_utf16(data, len)?
+        } else {
+            String::new()
+        };
+        swatches.push((name, color));
+    }
+    Ok(swatches)
+}
+
+/// Converts the raw values of a swatch into an `Rgb` color.
+fn read_color(space: u16, w: [u16; 4]) -> Result<Rgb, PaletteError> {
+    let ratio = |value: u16| value as f32 / u16::MAX as f32;
+    let octet = |value: u16| ((value as u32 * 255 + 32767) / 65535) as u8;
+    match space {
+        RGB => Ok(Rgb::new(octet(w[0]), octet(w[1]), octet(w[2]))),
+        HSB => Ok(Rgb::from(Hsv::new(
+            ratio(w[0]) * 360.0,
+            ratio(w[1]),
+            ratio(w[2])))),
+        // CMYK values are stored inverted, with zero representing full ink.
+        CMYK => Ok(rgb_from_cmyk_ratios([
+            1.0 - ratio(w[0]),
+            1.0 - ratio(w[1]),
+            1.0 - ratio(w[2]),
+            1.0 - ratio(w[3]),
+        ])),
+        LAB => Ok(Rgb::from(Lab::new(
+            w[0] as f32 / 100.0,
+            w[1] as i16 as f32 / 100.0,
+            w[2] as i16 as f32 / 100.0))),
+        GRAYSCALE => {
+            let gray = (255.0 * (1.0 - w[0] as f32 / 10000.0))
+                .clamp(0.0, 255.0)
+                .round() as u8;
+            Ok(Rgb::new(gray, gray, gray))
+        },
+        _ => Err(PaletteError::UnsupportedColorModel(space.to_string())),
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// write
+////////////////////////////////////////////////////////////////////////////////
+/// Writes a palette as Photoshop color swatch data, storing each color using
+/// the given color model.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be written, or if the
+/// palette has more swatches than the format can store.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+pub fn write<W>(palette: &Palette, model: ColorModel, mut writer: W)
+    -> Result<(), PaletteError>
+    where W: Write
+{
+    let count = u16::try_from(palette.len())
+        .map_err(|_| PaletteError::Malformed("too many swatches"))?;
+
+    let records: Vec<[u16; 5]> = palette
+        .colors()
+        .map(|color| color_record(color, model))
+        .collect();
+
+    write_u16s(&mut writer, &[1, count])?;
+    for record in &records {
+        write_u16s(&mut writer, record)?;
+    }
+
+    write_u16s(&mut writer, &[2, count])?;
+    for (record, swatch) in records.iter().zip(palette) {
+        let units = utf16_units(&swatch.name);
+        write_u16s(&mut writer, record)?;
+        writer.write_all(&(units.len() as u32).to_be_bytes())?;
+        write_u16s(&mut writer, &units)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the color space and raw values used to store a color.
+fn color_record(color: Rgb, model: ColorModel) -> [u16; 5] {
+    let scale = |ratio: f32| (ratio * u16::MAX as f32).round() as u16;
+    match model {
+        ColorModel::Rgb => [
+            RGB,
+            color.r as u16 * 257,
+            color.g as u16 * 257,
+            color.b as u16 * 257,
+            0,
+        ],
+        ColorModel::Cmyk => {
+            let [c, m, y, k] = Cmyk::from(color).ratios();
+            [CMYK, scale(1.0 - c), scale(1.0 - m), scale(1.0 - y), scale(1.0 - k)]
+        },
+        ColorModel::Lab => {
+            let lab = Lab::from(color);
+            [
+                LAB,
+                (lab.l * 100.0).round().clamp(0.0, 10000.0) as u16,
+                (lab.a * 100.0).round().clamp(-12800.0, 12700.0) as i16 as u16,
+                (lab.b * 100.0).round().clamp(-12800.0, 12700.0) as i16 as u16,
+                0,
+            ]
+        },
+    }
+}
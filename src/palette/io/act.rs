@@ -0,0 +1,141 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Adobe color table (`.act`) palette files.
+//!
+//! A color table is a 768-byte table of 256 RGB colors, optionally followed
+//! by the number of colors in use and the index of the transparent color.
+//! Color tables do not store names, so swatches are named by their hex code
+//! and repeated colors are merged.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::palette::io::PaletteError;
+use crate::palette::io::fallback_name;
+use crate::palette::io::write_u16s;
+use crate::palette::Palette;
+use crate::Rgb;
+
+// Standard library imports.
+use std::convert::TryFrom;
+use std::io::Read;
+use std::io::Write;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The maximum number of colors in a color table.
+pub const MAX_COLORS: usize = 256;
+
+/// The size of the color table in bytes.
+const TABLE_LEN: usize = MAX_COLORS * 3;
+
+/// The transparent color index indicating that no color is transparent.
+const NO_TRANSPARENCY: u16 = 0xFFFF;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// read
+////////////////////////////////////////////////////////////////////////////////
+/// Reads a palette from an Adobe color table.
+///
+/// If the table includes a color count, only the colors in use are read.
+/// Otherwise all 256 colors are read.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be read or is not a valid
+/// color table.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::palette::Palette;
+/// # use color::palette::io::act;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let palette: Palette = vec![
+///     ("#ff0000", Rgb::new(255, 0, 0)),
+///     ("#00ff00", Rgb::new(0, 255, 0)),
+/// ].into_iter().collect();
+///
+/// let mut data = Vec::new();
+/// act::write(&palette, &mut data)?;
+///
+/// assert_eq!(data.len(), 772);
+/// assert_eq!(act::read(&data[..])?, palette);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn read<R>(mut reader: R) -> Result<Palette, PaletteError>
+    where R: Read
+{
+    let mut data = Vec::with_capacity(TABLE_LEN + 4);
+    let _ = reader.read_to_end(&mut data)?;
+
+    let count = match data.len() {
+        TABLE_LEN => MAX_COLORS,
+        len if len == TABLE_LEN + 4 => {
+            let count = u16::from_be_bytes([data[TABLE_LEN], data[TABLE_LEN + 1]]);
+            (count as usize).min(MAX_COLORS)
+        },
+        _ => return Err(PaletteError::Malformed("invalid color table size")),
+    };
+
+    Ok(data[..count * 3]
+        .chunks_exact(3)
+        .map(|rgb| {
+            let color = Rgb::new(rgb[0], rgb[1], rgb[2]);
+            (fallback_name(String::new(), color), color)
+        })
+        .collect())
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// write
+////////////////////////////////////////////////////////////////////////////////
+/// Writes a palette as an Adobe color table, including the color count.
+/// Unused table entries are filled with black.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be written, or if the
+/// palette has more than [`MAX_COLORS`] colors.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+/// [`MAX_COLORS`]: constant.MAX_COLORS.html
+pub fn write<W>(palette: &Palette, mut writer: W) -> Result<(), PaletteError>
+    where W: Write
+{
+    if palette.len() > MAX_COLORS {
+        return Err(PaletteError::Malformed("too many colors for color table"));
+    }
+
+    let mut table = [0; TABLE_LEN];
+    for (entry, color) in table.chunks_exact_mut(3).zip(palette.colors()) {
+        entry.copy_from_slice(&color.octets());
+    }
+    writer.write_all(&table)?;
+
+    let count = u16::try_from(palette.len())
+        .map_err(|_| PaletteError::Malformed("too many colors for color table"))?;
+    write_u16s(&mut writer, &[count, NO_TRANSPARENCY])
+}
@@ -0,0 +1,225 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Adobe Swatch Exchange (`.ase`) palette files.
+//!
+//! Swatch groups are flattened when reading, and swatches stored with CMYK,
+//! L*a*b*, or grayscale components are converted to RGB.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Cmyk;
+use crate::Lab;
+use crate::palette::io::ColorModel;
+use crate::palette::io::PaletteError;
+use crate::palette::io::fallback_name;
+use crate::palette::io::octet;
+use crate::palette::io::read_f32;
+use crate::palette::io::read_u16;
+use crate::palette::io::read_u32;
+use crate::palette::io::read_utf16;
+use crate::palette::io::rgb_from_cmyk_ratios;
+use crate::palette::io::utf16_units;
+use crate::palette::io::write_u16s;
+use crate::palette::Palette;
+use crate::Rgb;
+
+// Standard library imports.
+use std::convert::TryFrom;
+use std::io::Read;
+use std::io::Write;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The file signature.
+const SIGNATURE: &[u8; 4] = b"ASEF";
+
+/// The block type of a group start.
+const GROUP_START: u16 = 0xC001;
+
+/// The block type of a group end.
+const GROUP_END: u16 = 0xC002;
+
+/// The block type of a color entry.
+const COLOR_ENTRY: u16 = 0x0001;
+
+/// The color type of a normal (process) color.
+const NORMAL_COLOR: u16 = 2;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// read
+////////////////////////////////////////////////////////////////////////////////
+/// Reads a palette from Adobe Swatch Exchange data.
+///
+/// Swatches without a name are named by their hex code.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be read or is not a valid
+/// version 1 swatch exchange file.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::palette::Palette;
+/// # use color::palette::io::ColorModel;
+/// # use color::palette::io::ase;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut palette = Palette::new();
+/// palette.insert("Coral", Rgb::new(255, 127, 80));
+///
+/// let mut data = Vec::new();
+/// ase::write(&palette, ColorModel::Rgb, &mut data)?;
+///
+/// assert_eq!(ase::read(&data[..])?, palette);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn read<R>(mut reader: R) -> Result<Palette, PaletteError>
+    where R: Read
+{
+    let mut signature = [0; 4];
+    reader.read_exact(&mut signature)?;
+    if &signature != SIGNATURE {
+        return Err(PaletteError::InvalidSignature);
+    }
+
+    let major = read_u16(&mut reader)?;
+    let _minor = read_u16(&mut reader)?;
+    if major != 1 {
+        return Err(PaletteError::UnsupportedVersion(major as u32));
+    }
+
+    let mut palette = Palette::new();
+    let block_count = read_u32(&mut reader)?;
+    for _ in 0..block_count {
+        let block_type = read_u16(&mut reader)?;
+        let len = read_u32(&mut reader)? as usize;
+        let mut block = vec![0; len];
+        reader.read_exact(&mut block)?;
+
+        if block_type == COLOR_ENTRY {
+            let (name, color) = read_color_entry(&block[..])?;
+            let _ = palette.insert(fallback_name(name, color), color);
+        } else if block_type != GROUP_START && block_type != GROUP_END {
+            return Err(PaletteError::Malformed("unknown block type"));
+        }
+    }
+
+    Ok(palette)
+}
+
+/// Reads the name and color of a color entry block.
+fn read_color_entry(mut block: &[u8]) -> Result<(String, Rgb), PaletteError> {
+    let name_len = read_u16(&mut block)? as usize;
+    let name = read_utf16(&mut block, name_len)?;
+
+    let mut model = [0; 4];
+    block.read_exact(&mut model)?;
+
+    let color = match &model {
+        b"RGB " => {
+            let r = read_f32(&mut block)?;
+            let g = read_f32(&mut block)?;
+            let b = read_f32(&mut block)?;
+            Rgb::new(octet(r), octet(g), octet(b))
+        },
+        b"CMYK" => {
+            let mut ratios = [0.0; 4];
+            for ratio in ratios.iter_mut() {
+                *ratio = read_f32(&mut block)?;
+            }
+            rgb_from_cmyk_ratios(ratios)
+        },
+        b"LAB " => {
+            let l = read_f32(&mut block)?;
+            let a = read_f32(&mut block)?;
+            let b = read_f32(&mut block)?;
+            Rgb::from(Lab::new(l * 100.0, a, b))
+        },
+        b"Gray" => {
+            let gray = octet(read_f32(&mut block)?);
+            Rgb::new(gray, gray, gray)
+        },
+        _ => return Err(PaletteError::UnsupportedColorModel(
+            String::from_utf8_lossy(&model).into_owned())),
+    };
+
+    Ok((name, color))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// write
+////////////////////////////////////////////////////////////////////////////////
+/// Writes a palette as Adobe Swatch Exchange data, storing each color using
+/// the given color model.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be written, or if a swatch
+/// name is too long to be stored.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+pub fn write<W>(palette: &Palette, model: ColorModel, mut writer: W)
+    -> Result<(), PaletteError>
+    where W: Write
+{
+    let block_count = u32::try_from(palette.len())
+        .map_err(|_| PaletteError::Malformed("too many swatches"))?;
+
+    writer.write_all(SIGNATURE)?;
+    write_u16s(&mut writer, &[1, 0])?;
+    writer.write_all(&block_count.to_be_bytes())?;
+
+    for swatch in palette {
+        let units = utf16_units(&swatch.name);
+        let name_len = u16::try_from(units.len())
+            .map_err(|_| PaletteError::InvalidName)?;
+
+        let mut block = Vec::new();
+        write_u16s(&mut block, &[name_len])?;
+        write_u16s(&mut block, &units)?;
+        let (tag, components): (&[u8; 4], Vec<f32>) = match model {
+            ColorModel::Rgb => (b"RGB ", swatch.color.ratios().to_vec()),
+            ColorModel::Cmyk => {
+                (b"CMYK", Cmyk::from(swatch.color).ratios().to_vec())
+            },
+            ColorModel::Lab => {
+                let lab = Lab::from(swatch.color);
+                (b"LAB ", vec![lab.l / 100.0, lab.a, lab.b])
+            },
+        };
+        block.extend_from_slice(tag);
+        for component in components {
+            block.extend_from_slice(&component.to_be_bytes());
+        }
+        write_u16s(&mut block, &[NORMAL_COLOR])?;
+
+        write_u16s(&mut writer, &[COLOR_ENTRY])?;
+        writer.write_all(&(block.len() as u32).to_be_bytes())?;
+        writer.write_all(&block)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Plain text hex code (`.hex`) palette files.
+//!
+//! Hex palettes list one six-digit hex code per line, as produced by
+//! [Lospec](https://lospec.com/palette-list) and many pixel art tools. Hex
+//! palettes do not store names, so swatches are named by their hex code and
+//! repeated colors are merged.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::palette::io::PaletteError;
+use crate::palette::io::fallback_name;
+use crate::palette::Palette;
+use crate::Rgb;
+
+// Standard library imports.
+use std::io::Read;
+use std::io::Write;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// read
+////////////////////////////////////////////////////////////////////////////////
+/// Reads a palette from a list of hex codes.
+///
+/// Each line may optionally start with a `#`. Blank lines and lines starting
+/// with `;` or `//` are ignored.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be read, is not valid UTF-8,
+/// or contains an invalid hex code.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::palette::io::hex;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let palette = hex::read("; pico-8\n000000\n#1d2b53\n\n7E2553\n".as_bytes())?;
+///
+/// let colors: Vec<Rgb> = palette.colors().collect();
+/// assert_eq!(colors, [
+///     Rgb::new(0x00, 0x00, 0x00),
+///     Rgb::new(0x1d, 0x2b, 0x53),
+///     Rgb::new(0x7e, 0x25, 0x53),
+/// ]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn read<R>(mut reader: R) -> Result<Palette, PaletteError>
+    where R: Read
+{
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text)?;
+
+    let mut palette = Palette::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') || line.starts_with("//") {
+            continue;
+        }
+
+        let digits = line.strip_prefix('#').unwrap_or(line);
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(PaletteError::Malformed("invalid hex code"));
+        }
+        let hex = u32::from_str_radix(digits, 16)
+            .map_err(|_| PaletteError::Malformed("invalid hex code"))?;
+
+        let color = Rgb::from(hex);
+        let _ = palette.insert(fallback_name(String::new(), color), color);
+    }
+    Ok(palette)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// write
+////////////////////////////////////////////////////////////////////////////////
+/// Writes a palette as a list of lowercase hex codes without a leading `#`,
+/// one per line.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be written.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+pub fn write<W>(palette: &Palette, mut writer: W) -> Result<(), PaletteError>
+    where W: Write
+{
+    for color in palette.colors() {
+        writeln!(writer, "{:02x}{:02x}{:02x}", color.r, color.g, color.b)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! JSON palette files.
+//!
+//! The format is described by [`Palette::to_json`].
+//!
+//! [`Palette::to_json`]: ../../struct.Palette.html#method.to_json
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Hsl;
+use crate::Oklab;
+use crate::palette::io::PaletteError;
+use crate::palette::Palette;
+use crate::palette::Swatch;
+use crate::Rgb;
+
+// External library imports.
+use serde::Deserialize;
+use serde::Serialize;
+
+// Standard library imports.
+use std::io::Read;
+use std::io::Write;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The current version of the JSON palette format.
+pub const VERSION: u32 = 1;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Document
+////////////////////////////////////////////////////////////////////////////////
+/// The top level object of a JSON palette.
+#[derive(Serialize, Deserialize)]
+struct Document {
+    /// The format version.
+    version: u32,
+    /// The palette name.
+    #[serde(default)]
+    name: Option<String>,
+    /// The palette author.
+    #[serde(default)]
+    author: Option<String>,
+    /// The palette swatches.
+    swatches: Vec<SwatchObject>,
+}
+
+/// A swatch object of a JSON palette.
+#[derive(Serialize, Deserialize)]
+struct SwatchObject {
+    /// The swatch name.
+    name: String,
+    /// The color as a hex code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hex: Option<String>,
+    /// The color as RGB octets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rgb: Option<[u8; 3]>,
+    /// The color as HSL components.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hsl: Option<[f32; 3]>,
+    /// The color as Oklab components.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oklab: Option<[f32; 3]>,
+}
+
+impl SwatchObject {
+    /// Constructs a swatch object with every color field.
+    fn new(swatch: &Swatch) -> Self {
+        let color = swatch.color;
+        let hsl = Hsl::from(color);
+        SwatchObject {
+            name: swatch.name.clone(),
+            hex: Some(format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)),
+            rgb: Some(color.octets()),
+            hsl: Some([hsl.hue(), hsl.saturation(), hsl.lightness()]),
+            oklab: Some(Oklab::from(color).components()),
+        }
+    }
+
+    /// Returns the color given by the first color field present.
+    fn color(&self) -> Result<Rgb, PaletteError> {
+        if let Some(hex) = &self.hex {
+            Rgb::from_hex_code(hex)
+                .map_err(|_| PaletteError::Malformed("invalid hex code"))
+        } else if let Some([r, g, b]) = self.rgb {
+            Ok(Rgb::new(r, g, b))
+        } else if let Some([h, s, l]) = self.hsl {
+            Ok(Rgb::from(Hsl::new(h, s, l)))
+        } else if let Some(oklab) = self.oklab {
+            Ok(Rgb::from(Oklab::from(oklab)))
+        } else {
+            Err(PaletteError::Malformed("swatch has no color"))
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// read
+////////////////////////////////////////////////////////////////////////////////
+/// Reads a palette from JSON palette data.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be read or is not a valid
+/// JSON palette.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+pub fn read<R>(reader: R) -> Result<Palette, PaletteError>
+    where R: Read
+{
+    let document: Document = serde_json::from_reader(reader)?;
+    if document.version != VERSION {
+        return Err(PaletteError::UnsupportedVersion(document.version));
+    }
+
+    let mut palette = Palette::new();
+    palette.set_name(document.name);
+    palette.set_author(document.author);
+    for swatch in &document.swatches {
+        let _ = palette.insert(swatch.name.clone(), swatch.color()?);
+    }
+    Ok(palette)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// write
+////////////////////////////////////////////////////////////////////////////////
+/// Writes a palette as pretty-printed JSON palette data.
+///
+/// # Errors
+///
+/// Returns a [`PaletteError`] if the data cannot be written.
+///
+/// [`PaletteError`]: ../enum.PaletteError.html
+pub fn write<W>(palette: &Palette, writer: W) -> Result<(), PaletteError>
+    where W: Write
+{
+    let document = Document {
+        version: VERSION,
+        name: palette.name().map(String::from),
+        author: palette.author().map(String::from),
+        swatches: palette.iter().map(SwatchObject::new).collect(),
+    };
+    serde_json::to_writer_pretty(writer, &document)?;
+    Ok(())
+}
@@ -10,3 +10,419 @@
 //! Parsing functions for color values.
 //!
 ////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Hsl;
+use crate::Rgb;
+use crate::Rgba;
+use crate::trace::Level;
+use crate::trace::span;
+
+// Standard library imports.
+use std::error::Error;
+use std::f32::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The CSS named colors, sorted by name.
+const CSS_NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue",            0xF0F8FF),
+    ("antiquewhite",         0xFAEBD7),
+    ("aqua",                 0x00FFFF),
+    ("aquamarine",           0x7FFFD4),
+    ("azure",                0xF0FFFF),
+    ("beige",                0xF5F5DC),
+    ("bisque",               0xFFE4C4),
+    ("black",                0x000000),
+    ("blanchedalmond",       0xFFEBCD),
+    ("blue",                 0x0000FF),
+    ("blueviolet",           0x8A2BE2),
+    ("brown",                0xA52A2A),
+    ("burlywood",            0xDEB887),
+    ("cadetblue",            0x5F9EA0),
+    ("chartreuse",           0x7FFF00),
+    ("chocolate",            0xD2691E),
+    ("coral",                0xFF7F50),
+    ("cornflowerblue",       0x6495ED),
+    ("cornsilk",             0xFFF8DC),
+    ("crimson",              0xDC143C),
+    ("cyan",                 0x00FFFF),
+    ("darkblue",             0x00008B),
+    ("darkcyan",             0x008B8B),
+    ("darkgoldenrod",        0xB8860B),
+    ("darkgray",             0xA9A9A9),
+    ("darkgreen",            0x006400),
+    ("darkgrey",             0xA9A9A9),
+    ("darkkhaki",            0xBDB76B),
+    ("darkmagenta",          0x8B008B),
+    ("darkolivegreen",       0x556B2F),
+    ("darkorange",           0xFF8C00),
+    ("darkorchid",           0x9932CC),
+    ("darkred",              0x8B0000),
+    ("darksalmon",           0xE9967A),
+    ("darkseagreen",         0x8FBC8F),
+    ("darkslateblue",        0x483D8B),
+    ("darkslategray",        0x2F4F4F),
+    ("darkslategrey",        0x2F4F4F),
+    ("darkturquoise",        0x00CED1),
+    ("darkviolet",           0x9400D3),
+    ("deeppink",             0xFF1493),
+    ("deepskyblue",          0x00BFFF),
+    ("dimgray",              0x696969),
+    ("dimgrey",              0x696969),
+    ("dodgerblue",           0x1E90FF),
+    ("firebrick",            0xB22222),
+    ("floralwhite",          0xFFFAF0),
+    ("forestgreen",          0x228B22),
+    ("fuchsia",              0xFF00FF),
+    ("gainsboro",            0xDCDCDC),
+    ("ghostwhite",           0xF8F8FF),
+    ("gold",                 0xFFD700),
+    ("goldenrod",            0xDAA520),
+    ("gray",                 0x808080),
+    ("green",                0x008000),
+    ("greenyellow",          0xADFF2F),
+    ("grey",                 0x808080),
+    ("honeydew",             0xF0FFF0),
+    ("hotpink",              0xFF69B4),
+    ("indianred",            0xCD5C5C),
+    ("indigo",               0x4B0082),
+    ("ivory",                0xFFFFF0),
+    ("khaki",                0xF0E68C),
+    ("lavender",             0xE6E6FA),
+    ("lavenderblush",        0xFFF0F5),
+    ("lawngreen",            0x7CFC00),
+    ("lemonchiffon",         0xFFFACD),
+    ("lightblue",            0xADD8E6),
+    ("lightcoral",           0xF08080),
+    ("lightcyan",            0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray",            0xD3D3D3),
+    ("lightgreen",           0x90EE90),
+    ("lightgrey",            0xD3D3D3),
+    ("lightpink",            0xFFB6C1),
+    ("lightsalmon",          0xFFA07A),
+    ("lightseagreen",        0x20B2AA),
+    ("lightskyblue",         0x87CEFA),
+    ("lightslategray",       0x778899),
+    ("lightslategrey",       0x778899),
+    ("lightsteelblue",       0xB0C4DE),
+    ("lightyellow",          0xFFFFE0),
+    ("lime",                 0x00FF00),
+    ("limegreen",            0x32CD32),
+    ("linen",                0xFAF0E6),
+    ("magenta",              0xFF00FF),
+    ("maroon",               0x800000),
+    ("mediumaquamarine",     0x66CDAA),
+    ("mediumblue",           0x0000CD),
+    ("mediumorchid",         0xBA55D3),
+    ("mediumpurple",         0x9370DB),
+    ("mediumseagreen",       0x3CB371),
+    ("mediumslateblue",      0x7B68EE),
+    ("mediumspringgreen",    0x00FA9A),
+    ("mediumturquoise",      0x48D1CC),
+    ("mediumvioletred",      0xC71585),
+    ("midnightblue",         0x191970),
+    ("mintcream",            0xF5FFFA),
+    ("mistyrose",            0xFFE4E1),
+    ("moccasin",             0xFFE4B5),
+    ("navajowhite",          0xFFDEAD),
+    ("navy",                 0x000080),
+    ("oldlace",              0xFDF5E6),
+    ("olive",                0x808000),
+    ("olivedrab",            0x6B8E23),
+    ("orange",               0xFFA500),
+    ("orangered",            0xFF4500),
+    ("orchid",               0xDA70D6),
+    ("palegoldenrod",        0xEEE8AA),
+    ("palegreen",            0x98FB98),
+    ("paleturquoise",        0xAFEEEE),
+    ("palevioletred",        0xDB7093),
+    ("papayawhip",           0xFFEFD5),
+    ("peachpuff",            0xFFDAB9),
+    ("peru",                 0xCD853F),
+    ("pink",                 0xFFC0CB),
+    ("plum",                 0xDDA0DD),
+    ("powderblue",           0xB0E0E6),
+    ("purple",               0x800080),
+    ("rebeccapurple",        0x663399),
+    ("red",                  0xFF0000),
+    ("rosybrown",            0xBC8F8F),
+    ("royalblue",            0x4169E1),
+    ("saddlebrown",          0x8B4513),
+    ("salmon",               0xFA8072),
+    ("sandybrown",           0xF4A460),
+    ("seagreen",             0x2E8B57),
+    ("seashell",             0xFFF5EE),
+    ("sienna",               0xA0522D),
+    ("silver",               0xC0C0C0),
+    ("skyblue",              0x87CEEB),
+    ("slateblue",            0x6A5ACD),
+    ("slategray",            0x708090),
+    ("slategrey",            0x708090),
+    ("snow",                 0xFFFAFA),
+    ("springgreen",          0x00FF7F),
+    ("steelblue",            0x4682B4),
+    ("tan",                  0xD2B48C),
+    ("teal",                 0x008080),
+    ("thistle",              0xD8BFD8),
+    ("tomato",               0xFF6347),
+    ("turquoise",            0x40E0D0),
+    ("violet",               0xEE82EE),
+    ("wheat",                0xF5DEB3),
+    ("white",                0xFFFFFF),
+    ("whitesmoke",           0xF5F5F5),
+    ("yellow",               0xFFFF00),
+    ("yellowgreen",          0x9ACD32),
+];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// CssColorParseError
+////////////////////////////////////////////////////////////////////////////////
+/// An error which can be returned while parsing a CSS color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CssColorParseError {
+    /// A hex code has an invalid digit or number of digits.
+    InvalidHexCode,
+    /// A color function is not `rgb`, `rgba`, `hsl`, or `hsla`.
+    UnknownFunction,
+    /// A color function has the wrong number of arguments, or an argument
+    /// with an invalid number or unit.
+    InvalidArguments,
+    /// The value is not a known color name.
+    UnknownName,
+}
+
+impl fmt::Display for CssColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            CssColorParseError::InvalidHexCode => write!(f,
+                "invalid CSS hex color"),
+            CssColorParseError::UnknownFunction => write!(f,
+                "unknown CSS color function"),
+            CssColorParseError::InvalidArguments => write!(f,
+                "invalid CSS color function arguments"),
+            CssColorParseError::UnknownName => write!(f,
+                "unknown CSS color name"),
+        }
+    }
+}
+
+impl Error for CssColorParseError {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// FromStr
+////////////////////////////////////////////////////////////////////////////////
+/// Parses a CSS color value:
+///
+/// | Syntax | Example |
+/// | ------ | ------- |
+/// | Hex codes with 3, 4, 6, or 8 digits | `#f63`, `#ff6347cc` |
+/// | `rgb()` and `rgba()` with numbers or percentages | `rgb(255, 99, 71)`, `rgb(100% 39% 28% / 0.8)` |
+/// | `hsl()` and `hsla()` with `deg`, `grad`, `rad`, or `turn` hues | `hsl(9deg 100% 64%)`, `hsla(9, 100%, 64%, 80%)` |
+/// | Named colors and `transparent` | `tomato`, `RebeccaPurple` |
+///
+/// Both the comma separated and space separated function syntaxes are
+/// accepted, and out-of-range components are clamped.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgba;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let tomato = Rgba::new(255, 99, 71, 255);
+///
+/// assert_eq!("#ff6347".parse::<Rgba>()?, tomato);
+/// assert_eq!("rgb(255 99 71)".parse::<Rgba>()?, tomato);
+/// assert_eq!("Tomato".parse::<Rgba>()?, tomato);
+/// assert_eq!("rgba(255, 99, 71, 50%)".parse::<Rgba>()?.a, 128);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+impl FromStr for Rgba {
+    type Err = CssColorParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let span = span!(Level::DEBUG, "Rgba::from_str");
+        let _enter = span.enter();
+
+        let text = text.trim().to_ascii_lowercase();
+        if let Some(hex) = text.strip_prefix('#') {
+            parse_hex(hex)
+        } else if let Some((name, arguments)) = split_function(&text) {
+            parse_function(name, arguments)
+        } else if text == "transparent" {
+            Ok(Rgba::new(0, 0, 0, 0))
+        } else {
+            CSS_NAMED_COLORS
+                .binary_search_by_key(&text.as_str(), |&(name, _)| name)
+                .map(|i| Rgba::from_rgb(Rgb::from(CSS_NAMED_COLORS[i].1), 255))
+                .map_err(|_| CssColorParseError::UnknownName)
+        }
+    }
+}
+
+/// Parses a CSS color value as for `Rgba`, discarding the alpha.
+impl FromStr for Rgb {
+    type Err = CssColorParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.parse::<Rgba>().map(Rgb::from)
+    }
+}
+
+/// Parses the digits of a CSS hex color.
+fn parse_hex(hex: &str) -> Result<Rgba, CssColorParseError> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(CssColorParseError::InvalidHexCode);
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16)
+        .expect("hex digit");
+    let octet = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16)
+        .expect("hex digits");
+
+    match hex.len() {
+        3 => Ok(Rgba::new(digit(0) * 17, digit(1) * 17, digit(2) * 17, 255)),
+        4 => Ok(Rgba::new(
+            digit(0) * 17,
+            digit(1) * 17,
+            digit(2) * 17,
+            digit(3) * 17)),
+        6 => Ok(Rgba::new(octet(0), octet(2), octet(4), 255)),
+        8 => Ok(Rgba::new(octet(0), octet(2), octet(4), octet(6))),
+        _ => Err(CssColorParseError::InvalidHexCode),
+    }
+}
+
+/// Splits a CSS function into its name and the text between its parentheses.
+fn split_function(text: &str) -> Option<(&str, &str)> {
+    let (name, rest) = text.split_once('(')?;
+    let arguments = rest.strip_suffix(')')?;
+    Some((name.trim_end(), arguments))
+}
+
+/// Parses the arguments of a CSS color function.
+fn parse_function(name: &str, arguments: &str)
+    -> Result<Rgba, CssColorParseError>
+{
+    let (components, alpha) = split_arguments(arguments)
+        .ok_or(CssColorParseError::InvalidArguments)?;
+    let alpha = match alpha {
+        Some(alpha) => parse_alpha(alpha)
+            .ok_or(CssColorParseError::InvalidArguments)?,
+        None        => 255,
+    };
+
+    let rgb = match name {
+        "rgb" | "rgba" => {
+            let mut octets = [0; 3];
+            for (octet, component) in octets.iter_mut().zip(&components) {
+                *octet = parse_channel(component)
+                    .ok_or(CssColorParseError::InvalidArguments)?;
+            }
+            Rgb::from(octets)
+        },
+        "hsl" | "hsla" => {
+            let hue = parse_hue(components[0]);
+            let saturation = parse_ratio(components[1]);
+            let lightness = parse_ratio(components[2]);
+            match (hue, saturation, lightness) {
+                (Some(h), Some(s), Some(l)) => Rgb::from(Hsl::new(h, s, l)),
+                _ => return Err(CssColorParseError::InvalidArguments),
+            }
+        },
+        _ => return Err(CssColorParseError::UnknownFunction),
+    };
+    Ok(Rgba::from_rgb(rgb, alpha))
+}
+
+/// Splits the arguments of a CSS color function into its three components
+/// and optional alpha, using either the comma or space separated syntax.
+fn split_arguments(arguments: &str) -> Option<([&str; 3], Option<&str>)> {
+    let (components, alpha): (Vec<&str>, Option<&str>) = if arguments
+        .contains(',')
+    {
+        let mut parts: Vec<&str> = arguments.split(',').map(str::trim).collect();
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    } else {
+        let (components, alpha) = match arguments.split_once('/') {
+            Some((components, alpha)) => (components, Some(alpha.trim())),
+            None                      => (arguments, None),
+        };
+        (components.split_whitespace().collect(), alpha)
+    };
+
+    match components.as_slice() {
+        &[a, b, c] => Some(([a, b, c], alpha)),
+        _          => None,
+    }
+}
+
+/// Parses a finite CSS number, or a percentage as a ratio.
+fn parse_number(text: &str) -> Option<(f32, bool)> {
+    let (number, percentage) = match text.strip_suffix('%') {
+        Some(number) => (number, true),
+        None         => (text, false),
+    };
+    let value: f32 = number.parse().ok()?;
+    if !value.is_finite() { return None; }
+
+    if percentage {
+        Some((value / 100.0, true))
+    } else {
+        Some((value, false))
+    }
+}
+
+/// Parses an RGB channel given as a number or percentage.
+fn parse_channel(text: &str) -> Option<u8> {
+    let value = match parse_number(text)? {
+        (ratio, true)   => ratio * 255.0,
+        (number, false) => number,
+    };
+    Some(value.clamp(0.0, 255.0).round() as u8)
+}
+
+/// Parses an alpha given as a number or percentage.
+fn parse_alpha(text: &str) -> Option<u8> {
+    let (ratio, _) = parse_number(text)?;
+    Some((ratio.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Parses an HSL saturation or lightness, given as a percentage or a number
+/// of percent.
+fn parse_ratio(text: &str) -> Option<f32> {
+    match parse_number(text)? {
+        (ratio, true)   => Some(ratio),
+        (number, false) => Some(number / 100.0),
+    }
+}
+
+/// Parses a hue in degrees, given as a number or an angle with a unit.
+fn parse_hue(text: &str) -> Option<f32> {
+    let units = [("deg", 1.0), ("grad", 0.9), ("rad", 180.0 / PI), ("turn", 360.0)];
+    // `grad` is checked before `rad`, which it ends with.
+    let (number, scale) = units
+        .iter()
+        .find_map(|&(unit, scale)| text.strip_suffix(unit).map(|n| (n, scale)))
+        .unwrap_or((text, 1.0));
+
+    match parse_number(number)? {
+        (value, false) => Some(value * scale).filter(|h| h.is_finite()),
+        (_, true)      => None,
+    }
+}
@@ -0,0 +1,1231 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Color quantization for extracting palettes from pixel data.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::bulk::Job;
+#[cfg(feature = "rayon")]
+use crate::bulk::par_map;
+#[cfg(feature = "rayon")]
+use crate::bulk::PARALLEL_CHUNK;
+use crate::bulk::Progress;
+use crate::Lab;
+use crate::metric::Metric;
+use crate::Oklab;
+use crate::palette::io::fallback_name;
+use crate::palette::nearest_in;
+use crate::palette::Palette;
+use crate::Rgb;
+use crate::utility::unit_random;
+
+// External library imports.
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::collections::BTreeMap;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// median_cut
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a palette of at most `n` colors representing the given pixels,
+/// using the median cut algorithm.
+///
+/// The pixels are repeatedly divided at the median of the channel with the
+/// widest range, until there are `n` groups or no group can be divided
+/// further. Each group contributes its average color, named by its hex code,
+/// and the swatches are ordered from the most to the least common.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::quantize::median_cut;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut pixels = vec![Rgb::new(250, 10, 10); 4];
+/// pixels.extend(vec![Rgb::new(10, 10, 250); 4]);
+///
+/// let palette = median_cut(&pixels, 2);
+///
+/// assert_eq!(palette.len(), 2);
+/// assert_eq!(palette.get("#fa0a0a"), Some(Rgb::new(250, 10, 10)));
+/// assert_eq!(palette.get("#0a0afa"), Some(Rgb::new(10, 10, 250)));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn median_cut(pixels: &[Rgb], n: usize) -> Palette {
+    if pixels.is_empty() || n == 0 { return Palette::new(); }
+
+    let boxes = cut_boxes(pixels, n, widest_channel, |colors, channel| {
+        colors.sort_unstable_by_key(|&color| channel_key(color, channel));
+    });
+    to_palette(boxes.iter().map(|b| (b.len() as u64, average(b))).collect())
+}
+
+/// Returns a palette of at most `n` colors representing the given pixels,
+/// using the median cut algorithm with the pixels of each box scanned and
+/// sorted in parallel.
+///
+/// The result is the same as [`median_cut`].
+///
+/// [`median_cut`]: fn.median_cut.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::quantize::median_cut;
+/// # use color::quantize::par_median_cut;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels: Vec<Rgb> = (0..100_000u32)
+///     .map(|v| Rgb::new(v as u8, (v >> 8) as u8, (v >> 16) as u8))
+///     .collect();
+///
+/// assert_eq!(par_median_cut(&pixels, 16), median_cut(&pixels, 16));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_median_cut(pixels: &[Rgb], n: usize) -> Palette {
+    if pixels.is_empty() || n == 0 { return Palette::new(); }
+
+    let boxes = cut_boxes(pixels, n, par_widest_channel, |colors, channel| {
+        colors.par_sort_unstable_by_key(|&color| channel_key(color, channel));
+    });
+    to_palette(boxes.iter().map(|b| (b.len() as u64, par_average(b))).collect())
+}
+
+/// Divides the given pixels into at most `n` boxes by repeatedly splitting
+/// the box with the widest channel range at its median, using the given
+/// functions to find the widest channel of a box and to sort a box along a
+/// channel.
+fn cut_boxes<W, S>(pixels: &[Rgb], n: usize, widest: W, sort: S)
+    -> Vec<Vec<Rgb>>
+    where
+        W: Fn(&[Rgb]) -> (usize, u8),
+        S: Fn(&mut [Rgb], usize),
+{
+    let mut boxes = vec![(pixels.to_vec(), widest(pixels))];
+    while boxes.len() < n {
+        let index = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, (_, range)))| *range > 0)
+            .max_by_key(|(_, (_, (_, range)))| *range)
+            .map(|(i, _)| i);
+        let index = match index {
+            Some(index) => index,
+            None        => break,
+        };
+
+        let (mut lower, (channel, _)) = boxes.swap_remove(index);
+        sort(&mut lower, channel);
+        let upper = lower.split_off(lower.len() / 2);
+        let (lower_widest, upper_widest) = (widest(&lower), widest(&upper));
+        boxes.push((lower, lower_widest));
+        boxes.push((upper, upper_widest));
+    }
+
+    boxes.into_iter().map(|(b, _)| b).collect()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// octree
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a palette of at most `n` colors representing the given pixels,
+/// using octree quantization.
+///
+/// Each pixel is inserted into a tree which divides the RGB cube into eight
+/// octants per level. Whenever the tree holds more than `n` colors, the
+/// deepest, least populated branch is merged into a single color. Memory use
+/// is bounded by `n` rather than by the number of distinct pixels, and each
+/// pixel is visited only once, which makes this faster than [`median_cut`]
+/// on large images at some cost in accuracy. Swatches are named by their hex
+/// code and ordered from the most to the least common.
+///
+/// [`median_cut`]: fn.median_cut.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::quantize::octree;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut pixels = vec![Rgb::new(250, 10, 10); 6];
+/// pixels.extend(vec![Rgb::new(10, 10, 250); 3]);
+///
+/// let palette = octree(&pixels, 2);
+///
+/// let colors: Vec<Rgb> = palette.colors().collect();
+/// assert_eq!(colors, [Rgb::new(250, 10, 10), Rgb::new(10, 10, 250)]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn octree(pixels: &[Rgb], n: usize) -> Palette {
+    if pixels.is_empty() || n == 0 { return Palette::new(); }
+
+    to_palette(build_octree(pixels, n)
+        .leaf_sums()
+        .into_iter()
+        .map(|(count, sum)| (count, mean(sum, count)))
+        .collect())
+}
+
+/// Returns a palette of at most `n` colors representing the given pixels,
+/// using octree quantization with the pixels divided into chunks processed
+/// in parallel.
+///
+/// A separate tree is built for each chunk, and the leaves of those trees
+/// are then merged into a single tree in order. Because the least populated
+/// branches are merged within each chunk before the trees are combined, the
+/// result may differ slightly from [`octree`], but the same pixels always
+/// produce the same palette.
+///
+/// [`octree`]: fn.octree.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::quantize::par_octree;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut pixels = vec![Rgb::new(250, 10, 10); 60_000];
+/// pixels.extend(vec![Rgb::new(10, 10, 250); 30_000]);
+///
+/// let palette = par_octree(&pixels, 2);
+///
+/// let colors: Vec<Rgb> = palette.colors().collect();
+/// assert_eq!(colors, [Rgb::new(250, 10, 10), Rgb::new(10, 10, 250)]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_octree(pixels: &[Rgb], n: usize) -> Palette {
+    if pixels.is_empty() || n == 0 { return Palette::new(); }
+
+    let chunks: Vec<Vec<(u64, [u64; 3])>> = pixels
+        .par_chunks(PARALLEL_CHUNK)
+        .map(|chunk| build_octree(chunk, n).leaf_sums())
+        .collect();
+
+    let mut tree = Octree::new();
+    for (count, sum) in chunks.into_iter().flatten() {
+        tree.insert_cluster(mean(sum, count), count, sum);
+        while tree.leaves > n {
+            tree.reduce();
+        }
+    }
+    to_palette(tree
+        .leaf_sums()
+        .into_iter()
+        .map(|(count, sum)| (count, mean(sum, count)))
+        .collect())
+}
+
+/// Returns an octree of the given pixels with at most `n` leaves.
+fn build_octree(pixels: &[Rgb], n: usize) -> Octree {
+    let mut tree = Octree::new();
+    for &pixel in pixels {
+        tree.insert(pixel);
+        while tree.leaves > n {
+            tree.reduce();
+        }
+    }
+    tree
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// k_means
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a palette of at most `n` colors representing the given pixels,
+/// using k-means clustering in Oklab with the default [`KMeans`] settings.
+///
+/// [`KMeans`]: struct.KMeans.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::quantize::k_means;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut pixels = vec![Rgb::new(250, 10, 10); 6];
+/// pixels.extend(vec![Rgb::new(10, 10, 250); 3]);
+///
+/// let palette = k_means(&pixels, 2);
+///
+/// let colors: Vec<Rgb> = palette.colors().collect();
+/// assert_eq!(colors, [Rgb::new(250, 10, 10), Rgb::new(10, 10, 250)]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn k_means(pixels: &[Rgb], n: usize) -> Palette {
+    KMeans::new(n).extract(pixels)
+}
+
+/// Returns a palette of at most `n` colors representing the given pixels,
+/// using k-means clustering in Oklab with the default [`KMeans`] settings,
+/// with each pass over the pixels divided into parallel chunks.
+///
+/// The result is the same as [`k_means`].
+///
+/// [`KMeans`]: struct.KMeans.html
+/// [`k_means`]: fn.k_means.html
+#[cfg(feature = "rayon")]
+pub fn par_k_means(pixels: &[Rgb], n: usize) -> Palette {
+    KMeans::new(n).par_extract(pixels)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ClusterSpace
+////////////////////////////////////////////////////////////////////////////////
+/// The perceptual color space in which [`KMeans`] measures distances and
+/// averages colors.
+///
+/// [`KMeans`]: struct.KMeans.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ClusterSpace {
+    /// CIE L*a*b*.
+    Lab,
+    /// Oklab.
+    #[default]
+    Oklab,
+}
+
+impl ClusterSpace {
+    /// Returns the coordinates of the color in the space.
+    fn point(self, color: Rgb) -> [f32; 3] {
+        match self {
+            ClusterSpace::Lab   => Lab::from(color).components(),
+            ClusterSpace::Oklab => Oklab::from(color).components(),
+        }
+    }
+
+    /// Returns the color at the given coordinates in the space.
+    fn color_at(self, point: [f32; 3]) -> Rgb {
+        match self {
+            ClusterSpace::Lab   => Rgb::from(Lab::from(point)),
+            ClusterSpace::Oklab => Rgb::from(Oklab::from(point)),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Seeding
+////////////////////////////////////////////////////////////////////////////////
+/// The method used by [`KMeans`] to choose the initial cluster centers.
+///
+/// [`KMeans`]: struct.KMeans.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Seeding {
+    /// The k-means++ method, which picks each center at random with a
+    /// probability proportional to its squared distance from the centers
+    /// already chosen. The same seed always produces the same palette.
+    KMeansPlusPlus {
+        /// The seed for the pseudo-random number generator.
+        seed: u64,
+    },
+    /// The centers are the distinct pixel colors at evenly spaced positions
+    /// in RGB order.
+    Uniform,
+}
+
+impl Default for Seeding {
+    fn default() -> Self {
+        Seeding::KMeansPlusPlus { seed: 0 }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// KMeans
+////////////////////////////////////////////////////////////////////////////////
+/// A k-means clustering palette extractor.
+///
+/// Pixels are grouped into clusters around `k` centers in a perceptual
+/// [`ClusterSpace`], refining the centers until no pixel changes cluster or
+/// the iteration limit is reached. This tends to find more representative
+/// dominant colors than [`median_cut`], at a higher cost.
+///
+/// [`ClusterSpace`]: enum.ClusterSpace.html
+/// [`median_cut`]: fn.median_cut.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::quantize::ClusterSpace;
+/// # use color::quantize::KMeans;
+/// # use color::quantize::Seeding;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels = vec![
+///     Rgb::new(240, 30, 30), Rgb::new(250, 20, 40),
+///     Rgb::new(30, 30, 240), Rgb::new(40, 20, 250),
+/// ];
+///
+/// let palette = KMeans::new(2)
+///     .with_iterations(8)
+///     .with_space(ClusterSpace::Lab)
+///     .with_seeding(Seeding::Uniform)
+///     .extract(&pixels);
+///
+/// assert_eq!(palette.len(), 2);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KMeans {
+    /// The number of clusters.
+    k: usize,
+    /// The maximum number of refinement iterations.
+    iterations: usize,
+    /// The space in which colors are clustered.
+    space: ClusterSpace,
+    /// The method used to choose the initial centers.
+    seeding: Seeding,
+}
+
+impl KMeans {
+    /// The default maximum number of refinement iterations.
+    pub const DEFAULT_ITERATIONS: usize = 16;
+
+    /// Constructs a new `KMeans` extractor for `k` colors.
+    pub fn new(k: usize) -> Self {
+        KMeans {
+            k,
+            iterations: KMeans::DEFAULT_ITERATIONS,
+            space: ClusterSpace::default(),
+            seeding: Seeding::default(),
+        }
+    }
+
+    /// Returns the extractor with the given maximum number of refinement
+    /// iterations.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Returns the extractor with the given cluster space.
+    pub fn with_space(mut self, space: ClusterSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Returns the extractor with the given seeding method.
+    pub fn with_seeding(mut self, seeding: Seeding) -> Self {
+        self.seeding = seeding;
+        self
+    }
+
+    /// Returns a palette of at most `k` colors representing the given pixels.
+    /// Swatches are named by their hex code and ordered from the most to the
+    /// least common.
+    pub fn extract(&self, pixels: &[Rgb]) -> Palette {
+        self.job(pixels).run()
+    }
+
+    /// Returns a palette of at most `k` colors representing the given pixels,
+    /// with each pass over the pixels divided into parallel chunks.
+    ///
+    /// The result is the same as [`extract`].
+    ///
+    /// [`extract`]: #method.extract
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::quantize::KMeans;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let pixels: Vec<Rgb> = (0..100_000u32)
+    ///     .map(|v| Rgb::new(v as u8, (v >> 8) as u8, (v >> 16) as u8))
+    ///     .collect();
+    /// let kmeans = KMeans::new(8).with_iterations(4);
+    ///
+    /// assert_eq!(kmeans.par_extract(&pixels), kmeans.extract(&pixels));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_extract(&self, pixels: &[Rgb]) -> Palette {
+        let mut job = self.job(pixels);
+        if let KMeansStage::Done(palette) = &mut job.stage {
+            return palette.take().unwrap_or_default();
+        }
+
+        let counts = pixels
+            .par_chunks(PARALLEL_CHUNK)
+            .fold(BTreeMap::new, |mut counts, chunk| {
+                for &pixel in chunk {
+                    *counts.entry(pixel).or_insert(0) += 1;
+                }
+                counts
+            })
+            .reduce(BTreeMap::new, |mut a, mut b| {
+                if a.len() < b.len() { std::mem::swap(&mut a, &mut b); }
+                for (color, count) in b {
+                    *a.entry(color).or_insert(0) += count;
+                }
+                a
+            });
+        job.seed(counts);
+
+        while let KMeansStage::Assigning = job.stage {
+            let centers = &job.centers;
+            job.changed = job.points
+                .par_iter()
+                .zip(job.assignments.par_iter_mut())
+                .with_min_len(PARALLEL_CHUNK)
+                .map(|((point, _), assignment)| {
+                    let nearest = nearest_center(centers, *point).0;
+                    let changed = *assignment != nearest;
+                    *assignment = nearest;
+                    changed
+                })
+                .reduce(|| false, |a, b| a || b);
+            job.update_centers();
+        }
+
+        let k = job.centers.len();
+        let centers = &job.centers;
+        let totals = job.points
+            .par_chunks(PARALLEL_CHUNK)
+            .fold(|| vec![0; k], |mut totals, chunk| {
+                for (point, count) in chunk {
+                    totals[nearest_center(centers, *point).0] += count;
+                }
+                totals
+            })
+            .reduce(|| vec![0; k], |mut a, b| {
+                for (a, b) in a.iter_mut().zip(b) { *a += b; }
+                a
+            });
+        job.palette(totals)
+    }
+
+    /// Returns a resumable [`Job`] which extracts the same palette as
+    /// [`extract`], a chunk at a time.
+    ///
+    /// [`Job`]: ../bulk/trait.Job.html
+    /// [`extract`]: #method.extract
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::bulk::Job;
+    /// # use color::quantize::KMeans;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let pixels: Vec<Rgb> = (0..=255)
+    ///     .map(|v| Rgb::new(v, 0, 255 - v))
+    ///     .collect();
+    /// let kmeans = KMeans::new(4);
+    ///
+    /// let mut job = kmeans.job(&pixels);
+    /// while !job.process_chunk(64).is_complete() {}
+    ///
+    /// assert_eq!(job.finish(), Some(kmeans.extract(&pixels)));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn job<'a>(&self, pixels: &'a [Rgb]) -> KMeansJob<'a> {
+        let stage = if pixels.is_empty() || self.k == 0 {
+            KMeansStage::Done(Some(Palette::new()))
+        } else {
+            KMeansStage::Counting(BTreeMap::new())
+        };
+        KMeansJob {
+            config: *self,
+            pixels,
+            stage,
+            points: Vec::new(),
+            centers: Vec::new(),
+            assignments: Vec::new(),
+            iteration: 0,
+            changed: false,
+            next: 0,
+            done: 0,
+        }
+    }
+}
+
+impl Default for KMeans {
+    fn default() -> Self {
+        KMeans::new(8)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// KMeansJob
+////////////////////////////////////////////////////////////////////////////////
+/// A resumable [`KMeans`] palette extraction, returned by [`KMeans::job`].
+///
+/// [`KMeans`]: struct.KMeans.html
+/// [`KMeans::job`]: struct.KMeans.html#method.job
+#[derive(Debug)]
+pub struct KMeansJob<'a> {
+    /// The extractor's configuration.
+    config: KMeans,
+    /// The pixels to extract a palette from.
+    pixels: &'a [Rgb],
+    /// The current stage of the extraction.
+    stage: KMeansStage,
+    /// Each distinct color's coordinates and pixel count.
+    points: Vec<([f32; 3], u64)>,
+    /// The cluster centers.
+    centers: Vec<[f32; 3]>,
+    /// The cluster of each point.
+    assignments: Vec<usize>,
+    /// The current refinement iteration.
+    iteration: usize,
+    /// Whether any point has changed cluster in the current iteration.
+    changed: bool,
+    /// The index of the next pixel or point to process in the current stage.
+    next: usize,
+    /// The units of work done so far.
+    done: usize,
+}
+
+/// The stages of a [`KMeansJob`].
+///
+/// [`KMeansJob`]: struct.KMeansJob.html
+#[derive(Debug)]
+enum KMeansStage {
+    /// Counting the pixels of each distinct color.
+    Counting(BTreeMap<Rgb, u64>),
+    /// Assigning each point to its nearest center.
+    Assigning,
+    /// Counting the pixels nearest to each final center.
+    Totaling(Vec<u64>),
+    /// Complete, holding the palette until it is taken by `finish`.
+    Done(Option<Palette>),
+}
+
+impl<'a> KMeansJob<'a> {
+    /// Seeds the centers once the distinct colors are counted.
+    fn seed(&mut self, counts: BTreeMap<Rgb, u64>) {
+        let space = self.config.space;
+        self.points = counts
+            .into_iter()
+            .map(|(color, count)| (space.point(color), count))
+            .collect();
+        let k = self.config.k.min(self.points.len());
+        self.centers = match self.config.seeding {
+            Seeding::KMeansPlusPlus { seed } => {
+                seed_plus_plus(&self.points, k, seed)
+            },
+            Seeding::Uniform => (0..k)
+                .map(|i| self.points[i * self.points.len() / k].0)
+                .collect(),
+        };
+        self.assignments = vec![usize::MAX; self.points.len()];
+        self.stage = if self.config.iterations == 0 {
+            KMeansStage::Totaling(vec![0; k])
+        } else {
+            KMeansStage::Assigning
+        };
+    }
+
+    /// Moves each center to the mean of its points after an assignment pass,
+    /// or moves on to totaling if the clusters are stable or the iteration
+    /// limit is reached.
+    fn update_centers(&mut self) {
+        let k = self.centers.len();
+        if !self.changed {
+            self.stage = KMeansStage::Totaling(vec![0; k]);
+            return;
+        }
+
+        let mut sums = vec![([0.0f64; 3], 0u64); k];
+        for (&cluster, (point, count)) in self.assignments
+            .iter()
+            .zip(&self.points)
+        {
+            let (sum, total) = &mut sums[cluster];
+            for (s, &p) in sum.iter_mut().zip(point) {
+                *s += p as f64 * *count as f64;
+            }
+            *total += count;
+        }
+        for (center, (sum, total)) in self.centers.iter_mut().zip(&sums) {
+            if *total > 0 {
+                *center = sum.map(|s| (s / *total as f64) as f32);
+            }
+        }
+
+        self.changed = false;
+        self.iteration += 1;
+        if self.iteration >= self.config.iterations {
+            self.stage = KMeansStage::Totaling(vec![0; k]);
+        }
+    }
+
+    /// Returns the palette of the final centers, given the number of pixels
+    /// nearest to each.
+    fn palette(&mut self, totals: Vec<u64>) -> Palette {
+        let space = self.config.space;
+        let centers = std::mem::take(&mut self.centers);
+        to_palette(centers
+            .into_iter()
+            .zip(totals)
+            .filter(|&(_, total)| total > 0)
+            .map(|(center, total)| (total, space.color_at(center)))
+            .collect())
+    }
+}
+
+impl<'a> Job for KMeansJob<'a> {
+    type Output = Palette;
+
+    fn process_chunk(&mut self, budget: usize) -> Progress {
+        let mut budget = budget;
+        while budget > 0 {
+            let stage = std::mem::replace(
+                &mut self.stage,
+                KMeansStage::Assigning);
+            match stage {
+                KMeansStage::Counting(mut counts) => {
+                    let end = self.next
+                        .saturating_add(budget)
+                        .min(self.pixels.len());
+                    for &pixel in &self.pixels[self.next..end] {
+                        *counts.entry(pixel).or_insert(0) += 1;
+                    }
+                    budget -= end - self.next;
+                    self.done += end - self.next;
+                    self.next = end;
+                    if end == self.pixels.len() {
+                        self.next = 0;
+                        self.seed(counts);
+                    } else {
+                        self.stage = KMeansStage::Counting(counts);
+                    }
+                },
+
+                KMeansStage::Assigning => {
+                    let end = self.next
+                        .saturating_add(budget)
+                        .min(self.points.len());
+                    for i in self.next..end {
+                        let nearest = nearest_center(
+                            &self.centers,
+                            self.points[i].0).0;
+                        self.changed |= self.assignments[i] != nearest;
+                        self.assignments[i] = nearest;
+                    }
+                    budget -= end - self.next;
+                    self.done += end - self.next;
+                    self.next = end;
+                    if end == self.points.len() {
+                        self.next = 0;
+                        self.update_centers();
+                    }
+                },
+
+                KMeansStage::Totaling(mut totals) => {
+                    let end = self.next
+                        .saturating_add(budget)
+                        .min(self.points.len());
+                    for (point, count) in &self.points[self.next..end] {
+                        totals[nearest_center(&self.centers, *point).0]
+                            += count;
+                    }
+                    budget -= end - self.next;
+                    self.done += end - self.next;
+                    self.next = end;
+                    if end < self.points.len() {
+                        self.stage = KMeansStage::Totaling(totals);
+                        continue;
+                    }
+                    let palette = self.palette(totals);
+                    self.stage = KMeansStage::Done(Some(palette));
+                },
+
+                stage @ KMeansStage::Done(_) => {
+                    self.stage = stage;
+                    break;
+                },
+            }
+        }
+        self.progress()
+    }
+
+    fn progress(&self) -> Progress {
+        let passes = self.config.iterations + 1;
+        let total = match &self.stage {
+            KMeansStage::Counting(_) => {
+                // Every pixel may be a distinct color.
+                self.pixels.len() * (passes + 1)
+            },
+            KMeansStage::Assigning => {
+                let remaining = passes - self.iteration;
+                self.done - self.next + remaining * self.points.len()
+            },
+            KMeansStage::Totaling(_) => {
+                self.done - self.next + self.points.len()
+            },
+            KMeansStage::Done(_) => self.done,
+        };
+        Progress { done: self.done, total }
+    }
+
+    fn finish(self) -> Option<Self::Output> {
+        match self.stage {
+            KMeansStage::Done(palette) => palette,
+            _ => None,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// palette_indices
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the index of the nearest swatch in `palette` for each of the given
+/// pixels, as measured by the given [`Metric`]. This maps an image onto an
+/// extracted palette.
+///
+/// Returns an empty vector if the palette is empty.
+///
+/// [`Metric`]: ../metric/trait.Metric.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::metric::Euclidean;
+/// # use color::quantize::median_cut;
+/// # use color::quantize::palette_indices;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels = vec![
+///     Rgb::new(250, 10, 10), Rgb::new(10, 10, 250),
+///     Rgb::new(240, 20, 10), Rgb::new(20, 10, 240),
+/// ];
+/// let palette = median_cut(&pixels, 2);
+///
+/// let indices = palette_indices(&pixels, &palette, Euclidean::Oklab);
+///
+/// assert_eq!(indices[0], indices[2]);
+/// assert_eq!(indices[1], indices[3]);
+/// assert_ne!(indices[0], indices[1]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn palette_indices<M>(pixels: &[Rgb], palette: &Palette, metric: M)
+    -> Vec<usize>
+    where M: Metric
+{
+    if palette.is_empty() { return Vec::new(); }
+
+    // Images repeat colors heavily, so each distinct color is searched once.
+    let mut cache = BTreeMap::new();
+    pixels
+        .iter()
+        .map(|&pixel| *cache.entry(pixel).or_insert_with(|| {
+            nearest_in(pixel, palette, |a, b| metric.distance(a, b))
+                .expect("nonempty palette")
+                .0
+        }))
+        .collect()
+}
+
+/// Returns the index of the nearest swatch in `palette` for each of the given
+/// pixels, divided between threads.
+///
+/// The result is the same as [`palette_indices`].
+///
+/// [`palette_indices`]: fn.palette_indices.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::metric::Euclidean;
+/// # use color::quantize::median_cut;
+/// # use color::quantize::palette_indices;
+/// # use color::quantize::par_palette_indices;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let pixels: Vec<Rgb> = (0..100_000u32)
+///     .map(|v| Rgb::new(v as u8, (v >> 8) as u8, (v >> 16) as u8))
+///     .collect();
+/// let palette = median_cut(&pixels, 16);
+///
+/// assert_eq!(
+///     par_palette_indices(&pixels, &palette, Euclidean::Oklab),
+///     palette_indices(&pixels, &palette, Euclidean::Oklab));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_palette_indices<M>(pixels: &[Rgb], palette: &Palette, metric: M)
+    -> Vec<usize>
+    where M: Metric + Sync
+{
+    if palette.is_empty() { return Vec::new(); }
+
+    let metric = &metric;
+    par_map(pixels, |chunk| {
+        palette_indices(chunk, palette, |a, b| metric.distance(a, b))
+    })
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Octree
+////////////////////////////////////////////////////////////////////////////////
+/// The number of levels below the root of an `Octree`, one per bit of each
+/// channel.
+const OCTREE_DEPTH: usize = 8;
+
+/// A node of an `Octree`.
+#[derive(Debug, Clone, Default)]
+struct OctreeNode {
+    /// The indices of the child nodes.
+    children: [Option<usize>; 8],
+    /// Whether the node is a leaf holding a color.
+    leaf: bool,
+    /// The number of pixels within the node.
+    count: u64,
+    /// The sum of the channels of the pixels within the node.
+    sum: [u64; 3],
+}
+
+/// A color octree, with nodes stored in an arena.
+#[derive(Debug, Clone)]
+struct Octree {
+    /// The nodes of the tree. The root is at index 0.
+    nodes: Vec<OctreeNode>,
+    /// The indices of the branch nodes at each level.
+    branches: [Vec<usize>; OCTREE_DEPTH],
+    /// The number of leaf nodes.
+    leaves: usize,
+}
+
+impl Octree {
+    /// Constructs a new `Octree` with an empty root.
+    fn new() -> Self {
+        let mut branches: [Vec<usize>; OCTREE_DEPTH] = Default::default();
+        branches[0].push(0);
+        Octree { nodes: vec![OctreeNode::default()], branches, leaves: 0 }
+    }
+
+    /// Adds a pixel to the tree, stopping at the first leaf on its path.
+    fn insert(&mut self, pixel: Rgb) {
+        self.insert_cluster(pixel, 1, pixel.octets().map(u64::from));
+    }
+
+    /// Adds a cluster of `count` pixels with the given channel sums to the
+    /// tree, following the path of the given color and stopping at the first
+    /// leaf on its path.
+    fn insert_cluster(&mut self, color: Rgb, count: u64, sums: [u64; 3]) {
+        let octets = color.octets();
+        let mut node = 0;
+        for level in 0..=OCTREE_DEPTH {
+            self.nodes[node].count += count;
+            for (sum, &v) in self.nodes[node].sum.iter_mut().zip(&sums) {
+                *sum += v;
+            }
+            if self.nodes[node].leaf { return; }
+
+            let shift = 7 - level;
+            let octant = (((octets[0] >> shift) & 1) << 2
+                | ((octets[1] >> shift) & 1) << 1
+                | ((octets[2] >> shift) & 1)) as usize;
+            node = match self.nodes[node].children[octant] {
+                Some(child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    let leaf = level + 1 == OCTREE_DEPTH;
+                    self.nodes.push(OctreeNode { leaf, ..Default::default() });
+                    self.nodes[node].children[octant] = Some(child);
+                    if leaf {
+                        self.leaves += 1;
+                    } else {
+                        self.branches[level + 1].push(child);
+                    }
+                    child
+                },
+            };
+        }
+    }
+
+    /// Merges the least populated branch at the deepest level into a leaf.
+    fn reduce(&mut self) {
+        let level = match self.branches.iter().rposition(|b| !b.is_empty()) {
+            Some(level) => level,
+            None        => return,
+        };
+        let nodes = &self.nodes;
+        let (position, _) = self.branches[level]
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &node)| nodes[node].count)
+            .expect("nonempty branch level");
+        let node = self.branches[level].swap_remove(position);
+
+        // Every child of a branch at the deepest level is a leaf, whose
+        // pixels are already counted in the branch.
+        let children = std::mem::take(&mut self.nodes[node].children);
+        self.leaves -= children.iter().flatten().count();
+        self.leaves += 1;
+        self.nodes[node].leaf = true;
+    }
+
+    /// Returns the pixel count and channel sums of each leaf.
+    fn leaf_sums(&self) -> Vec<(u64, [u64; 3])> {
+        let mut clusters = Vec::with_capacity(self.leaves);
+        self.collect_leaves(0, &mut clusters);
+        clusters
+    }
+
+    /// Appends the pixel count and channel sums of each leaf below the given
+    /// node.
+    fn collect_leaves(&self, node: usize, clusters: &mut Vec<(u64, [u64; 3])>) {
+        let node = &self.nodes[node];
+        if node.leaf {
+            clusters.push((node.count, node.sum));
+            return;
+        }
+        for &child in node.children.iter().flatten() {
+            self.collect_leaves(child, clusters);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the minimum and maximum of each channel among the given colors.
+fn channel_bounds(colors: &[Rgb]) -> ([u8; 3], [u8; 3]) {
+    let mut min = [u8::MAX; 3];
+    let mut max = [u8::MIN; 3];
+    for color in colors {
+        for (c, &v) in color.octets().iter().enumerate() {
+            min[c] = min[c].min(v);
+            max[c] = max[c].max(v);
+        }
+    }
+    (min, max)
+}
+
+/// Returns the index of the channel with the widest range between the given
+/// channel bounds, along with that range.
+fn widest_in_bounds((min, max): ([u8; 3], [u8; 3])) -> (usize, u8) {
+    (0..3)
+        .map(|c| (c, max[c].saturating_sub(min[c])))
+        .fold((0, 0), |widest, next| if next.1 > widest.1 { next } else { widest })
+}
+
+/// Returns the index of the channel with the widest range among the given
+/// colors, along with that range.
+fn widest_channel(colors: &[Rgb]) -> (usize, u8) {
+    widest_in_bounds(channel_bounds(colors))
+}
+
+/// Returns the index of the channel with the widest range among the given
+/// colors, along with that range, scanning the colors in parallel.
+#[cfg(feature = "rayon")]
+fn par_widest_channel(colors: &[Rgb]) -> (usize, u8) {
+    let empty = || ([u8::MAX; 3], [u8::MIN; 3]);
+    widest_in_bounds(colors
+        .par_chunks(PARALLEL_CHUNK)
+        .map(channel_bounds)
+        .reduce(empty, |(min_a, max_a), (min_b, max_b)| {
+            let mut bounds = (min_a, max_a);
+            for c in 0..3 {
+                bounds.0[c] = min_a[c].min(min_b[c]);
+                bounds.1[c] = max_a[c].max(max_b[c]);
+            }
+            bounds
+        }))
+}
+
+/// Returns the key sorting colors along the given channel. Ties are broken
+/// by the remaining channels, so that every sort yields the same order.
+fn channel_key(color: Rgb, channel: usize) -> (u8, [u8; 3]) {
+    let octets = color.octets();
+    (octets[channel], octets)
+}
+
+/// Returns the channel sums of the given colors.
+fn channel_sums(colors: &[Rgb]) -> [u64; 3] {
+    let mut sum = [0u64; 3];
+    for color in colors {
+        for (c, &v) in color.octets().iter().enumerate() {
+            sum[c] += v as u64;
+        }
+    }
+    sum
+}
+
+/// Returns the average of the given colors, rounded to the nearest octet.
+fn average(colors: &[Rgb]) -> Rgb {
+    mean(channel_sums(colors), colors.len() as u64)
+}
+
+/// Returns the average of the given colors, rounded to the nearest octet,
+/// summing the colors in parallel.
+#[cfg(feature = "rayon")]
+fn par_average(colors: &[Rgb]) -> Rgb {
+    let sum = colors
+        .par_chunks(PARALLEL_CHUNK)
+        .map(channel_sums)
+        .reduce(|| [0; 3], |a, b| [a[0] + b[0], a[1] + b[1], a[2] + b[2]]);
+    mean(sum, colors.len() as u64)
+}
+
+/// Returns the index of the center nearest to the given point, along with the
+/// squared distance to it.
+fn nearest_center(centers: &[[f32; 3]], point: [f32; 3]) -> (usize, f32) {
+    centers
+        .iter()
+        .map(|center| {
+            center.iter().zip(&point).map(|(c, p)| (c - p) * (c - p)).sum()
+        })
+        .enumerate()
+        .fold((0, f32::INFINITY), |nearest, next| {
+            if next.1 < nearest.1 { next } else { nearest }
+        })
+}
+
+/// Returns `k` initial centers chosen from the weighted points using the
+/// k-means++ method.
+fn seed_plus_plus(points: &[([f32; 3], u64)], k: usize, seed: u64)
+    -> Vec<[f32; 3]>
+{
+    let mut state = seed;
+    let mut centers = Vec::with_capacity(k);
+    let mut weights: Vec<f64> = points
+        .iter()
+        .map(|&(_, count)| count as f64)
+        .collect();
+    while centers.len() < k {
+        let total: f64 = weights.iter().sum();
+        let index = if total > 0.0 {
+            let mut target = unit_random(&mut state) * total;
+            weights
+                .iter()
+                .position(|&w| { target -= w; target < 0.0 })
+                .unwrap_or(points.len() - 1)
+        } else {
+            // Every point coincides with a center.
+            centers.len()
+        };
+        centers.push(points[index].0);
+        for (weight, &(point, count)) in weights.iter_mut().zip(points) {
+            let distance = nearest_center(&centers, point).1 as f64;
+            *weight = distance * count as f64;
+        }
+    }
+    centers
+}
+
+/// Returns the color with the given channel sums divided by `count`, rounded
+/// to the nearest octet.
+fn mean(sum: [u64; 3], count: u64) -> Rgb {
+    let [r, g, b] = sum.map(|s| ((s + count / 2) / count) as u8);
+    Rgb::new(r, g, b)
+}
+
+/// Returns a palette of the given colors with their pixel counts, named by
+/// their hex codes and ordered from the most to the least common.
+fn to_palette(mut clusters: Vec<(u64, Rgb)>) -> Palette {
+    clusters.sort_by_key(|&(count, _)| std::cmp::Reverse(count));
+    clusters
+        .into_iter()
+        .map(|(_, color)| (fallback_name(String::new(), color), color))
+        .collect()
+}
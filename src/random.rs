@@ -0,0 +1,683 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Constrained, reproducible random color generation.
+//!
+//! A [`RandomColorBuilder`] describes the colors to generate: a range of hues,
+//! a range of saturations, and a minimum perceptual lightness, which keeps
+//! the colors pleasant and legible. The same seed always produces the same
+//! sequence of colors, including across platforms, which makes generated
+//! colors suitable for assigning to dynamically created entities, such as
+//! chart series or map layers.
+//!
+//! With the "rand" feature, colors can also be sampled from any `rand`
+//! generator. The `Standard` distribution is uniform over each space: over
+//! the components of the integer spaces, over the hue, saturation, and
+//! value or lightness of `Hsv` and `Hsl`, and over the volume of the sRGB
+//! gamut in `Oklab`. `Uniform` samples the box between two `Rgb`, `Rgba`, or
+//! `Oklab` colors, and [`UniformHue`] samples hues with a fixed saturation
+//! and value.
+//!
+//! [`RandomColorBuilder`]: struct.RandomColorBuilder.html
+//! [`UniformHue`]: struct.UniformHue.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::angle;
+#[cfg(feature = "rand")]
+use crate::Cmyk;
+#[cfg(feature = "rand")]
+use crate::Hsl;
+use crate::Hsv;
+#[cfg(feature = "rand")]
+use crate::Luma;
+use crate::Oklab;
+use crate::Rgb;
+#[cfg(feature = "rand")]
+use crate::Rgb48;
+#[cfg(feature = "rand")]
+use crate::Rgba;
+#[cfg(feature = "rand")]
+use crate::Rgba64;
+#[cfg(feature = "rand")]
+use crate::rgb_space::Srgb;
+use crate::utility::lerp_f32;
+use crate::utility::unit_random;
+
+// External library imports.
+#[cfg(feature = "rand")]
+use rand::distributions::Distribution;
+#[cfg(feature = "rand")]
+use rand::distributions::Standard;
+#[cfg(feature = "rand")]
+use rand::distributions::uniform::SampleBorrow;
+#[cfg(feature = "rand")]
+use rand::distributions::uniform::SampleUniform;
+#[cfg(feature = "rand")]
+use rand::distributions::uniform::UniformFloat;
+#[cfg(feature = "rand")]
+use rand::distributions::uniform::UniformInt;
+#[cfg(feature = "rand")]
+use rand::distributions::uniform::UniformSampler;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The number of bisection steps used to find the lowest HSV value or highest
+/// saturation meeting the minimum lightness.
+const LIGHTNESS_SEARCH_STEPS: usize = 16;
+
+/// The bounds of the `a` component of the sRGB gamut in `Oklab`.
+#[cfg(feature = "rand")]
+const OKLAB_SRGB_A: [f32; 2] = [-0.24, 0.28];
+
+/// The bounds of the `b` component of the sRGB gamut in `Oklab`.
+#[cfg(feature = "rand")]
+const OKLAB_SRGB_B: [f32; 2] = [-0.32, 0.2];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RandomColorBuilder
+////////////////////////////////////////////////////////////////////////////////
+/// Constraints on generated random colors.
+///
+/// Each color has a hue chosen uniformly from the hue range, and an HSV
+/// saturation chosen uniformly from the saturation range. Its HSV value is
+/// then chosen uniformly between the lowest value meeting the minimum
+/// [`Oklab`] lightness and full value. If a hue and saturation cannot reach
+/// the minimum lightness, such as a saturated blue, the saturation is reduced
+/// toward the minimum of its range until it does. If the ranges do not allow
+/// the lightness to be met, the lightest color within them is used.
+///
+/// [`Oklab`]: ../struct.Oklab.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Oklab;
+/// # use color::random::RandomColorBuilder;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let builder = RandomColorBuilder::new()
+///     .with_hue(90.0, 270.0)
+///     .with_saturation(0.5, 0.8)
+///     .with_min_lightness(0.6)
+///     .with_seed(42);
+///
+/// let colors: Vec<_> = builder.build().take(8).collect();
+///
+/// // The same seed always produces the same colors.
+/// assert_eq!(colors, builder.build().take(8).collect::<Vec<_>>());
+/// for color in colors {
+///     assert!(Oklab::from(color).l >= 0.6);
+/// }
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RandomColorBuilder {
+    /// The start and end of the hue range, counterclockwise in degrees.
+    hue: [f32; 2],
+    /// The range of HSV saturations.
+    saturation: [f32; 2],
+    /// The minimum Oklab lightness.
+    min_lightness: f32,
+    /// The seed for the pseudo-random number generator.
+    seed: u64,
+}
+
+impl RandomColorBuilder {
+    /// Constructs a new `RandomColorBuilder` for colors of any hue, with a
+    /// moderate to high saturation and a minimum lightness of 0.55.
+    pub fn new() -> Self {
+        RandomColorBuilder {
+            hue: [0.0, 360.0],
+            saturation: [0.4, 0.85],
+            min_lightness: 0.55,
+            seed: 0,
+        }
+    }
+
+    /// Returns the builder with the given hue range, from `start`
+    /// counterclockwise to `end` in degrees. If `end` is less than `start`
+    /// after wrapping, the range passes through 0, so a range from 330 to 30
+    /// selects reds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either bound is not finite.
+    pub fn with_hue(mut self, start: f32, end: f32) -> Self {
+        assert!(start.is_finite() && end.is_finite(),
+            "hue range bounds must be finite");
+        let start = angle::wrap(start);
+        let mut span = angle::wrap(end) - start;
+        if span < 0.0 || (span == 0.0 && end != start) { span += 360.0; }
+        self.hue = [start, start + span];
+        self
+    }
+
+    /// Returns the builder with the given range of HSV saturations. The
+    /// bounds are clamped between 0 and 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn with_saturation(mut self, min: f32, max: f32) -> Self {
+        assert!(min <= max, "minimum saturation exceeds maximum");
+        self.saturation = [min.clamp(0.0, 1.0), max.clamp(0.0, 1.0)];
+        self
+    }
+
+    /// Returns the builder with the given minimum [`Oklab`] lightness,
+    /// clamped between 0 and 1.
+    ///
+    /// [`Oklab`]: ../struct.Oklab.html
+    pub fn with_min_lightness(mut self, min_lightness: f32) -> Self {
+        self.min_lightness = min_lightness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the builder with the given seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns an endless iterator over random colors meeting the
+    /// constraints, starting from the builder's seed.
+    pub fn build(&self) -> RandomColors {
+        RandomColors { builder: *self, state: self.seed }
+    }
+
+    /// Returns the random color for the given key, such as an entity ID.
+    ///
+    /// Unlike [`build`], which produces a sequence, each key's color is
+    /// independent of the others, so colors can be assigned in any order.
+    ///
+    /// [`build`]: #method.build
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::random::RandomColorBuilder;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let builder = RandomColorBuilder::new().with_seed(7);
+    ///
+    /// let second = builder.color_for(2);
+    /// let first = builder.color_for(1);
+    ///
+    /// assert_eq!(builder.color_for(2), second);
+    /// assert_ne!(first, second);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn color_for(&self, key: u64) -> Rgb {
+        // Spread the keys across the generator's state space, so nearby keys
+        // don't share outputs.
+        let mut state = self.seed ^ key.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        self.color(&mut state)
+    }
+
+    /// Returns a random color meeting the constraints, advancing the given
+    /// state.
+    fn color(&self, state: &mut u64) -> Rgb {
+        let [h_start, h_end] = self.hue;
+        let [s_min, s_max] = self.saturation;
+        let hue = angle::wrap(
+            lerp_f32(h_start, h_end, unit_random(state) as f32));
+        let saturation = lerp_f32(s_min, s_max, unit_random(state) as f32);
+        let amount = unit_random(state) as f32;
+
+        let lightness = |s, v| {
+            Oklab::from(Rgb::from(Hsv::new(hue, s, v))).l
+        };
+        let color = |s, v| Rgb::from(Hsv::new(hue, s, v));
+
+        if lightness(saturation, 1.0) < self.min_lightness {
+            // Find the highest saturation which meets the lightness at full
+            // value.
+            if lightness(s_min, 1.0) < self.min_lightness {
+                return color(s_min, 1.0);
+            }
+            let (mut low, mut high) = (s_min, saturation);
+            for _ in 0..LIGHTNESS_SEARCH_STEPS {
+                let mid = (low + high) / 2.0;
+                if lightness(mid, 1.0) >= self.min_lightness {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            return color(low, 1.0);
+        }
+
+        // Find the lowest value which meets the lightness.
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..LIGHTNESS_SEARCH_STEPS {
+            let mid = (low + high) / 2.0;
+            if lightness(saturation, mid) >= self.min_lightness {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        color(saturation, lerp_f32(high, 1.0, amount))
+    }
+}
+
+impl Default for RandomColorBuilder {
+    fn default() -> Self {
+        RandomColorBuilder::new()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RandomColors
+////////////////////////////////////////////////////////////////////////////////
+/// An endless iterator over random colors, returned by
+/// [`RandomColorBuilder::build`].
+///
+/// [`RandomColorBuilder::build`]: struct.RandomColorBuilder.html#method.build
+#[derive(Debug, Clone)]
+pub struct RandomColors {
+    /// The constraints on the colors.
+    builder: RandomColorBuilder,
+    /// The state of the pseudo-random number generator.
+    state: u64,
+}
+
+impl Iterator for RandomColors {
+    type Item = Rgb;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.builder.color(&mut self.state))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Standard distributions
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(feature = "rand")]
+impl Distribution<Rgb> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgb {
+        Rgb::new(rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Rgba> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgba {
+        Rgba::new(rng.gen(), rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Rgb48> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgb48 {
+        Rgb48::new(rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Rgba64> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgba64 {
+        Rgba64::new(rng.gen(), rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Cmyk> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Cmyk {
+        Cmyk::new(rng.gen(), rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Luma> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Luma {
+        Luma::new(rng.gen())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Hsv> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Hsv {
+        Hsv::new(rng.gen_range(0.0..360.0), rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Hsl> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Hsl {
+        Hsl::new(rng.gen_range(0.0..360.0), rng.gen(), rng.gen())
+    }
+}
+
+/// Samples uniformly over the volume of the sRGB gamut in `Oklab`, by
+/// rejecting samples from its bounding box which are out of gamut.
+#[cfg(feature = "rand")]
+impl Distribution<Oklab> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Oklab {
+        loop {
+            let color = Oklab::new(
+                rng.gen(),
+                rng.gen_range(OKLAB_SRGB_A[0]..OKLAB_SRGB_A[1]),
+                rng.gen_range(OKLAB_SRGB_B[0]..OKLAB_SRGB_B[1]));
+            if color.in_gamut::<Srgb>() { return color; }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// UniformHue
+////////////////////////////////////////////////////////////////////////////////
+/// A distribution of colors with a uniformly random hue, and a fixed HSV
+/// saturation and value.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Hsv;
+/// # use color::random::UniformHue;
+/// # use rand::Rng;
+/// # use rand::SeedableRng;
+/// # use rand::rngs::StdRng;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut rng = StdRng::seed_from_u64(42);
+///
+/// let color: Hsv = rng.sample(UniformHue::new(0.8, 0.9));
+///
+/// assert_eq!(color.saturation(), 0.8);
+/// assert_eq!(color.value(), 0.9);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UniformHue {
+    /// The HSV saturation of the colors.
+    saturation: f32,
+    /// The HSV value of the colors.
+    value: f32,
+}
+
+#[cfg(feature = "rand")]
+impl UniformHue {
+    /// Constructs a new `UniformHue` distribution with the given HSV
+    /// saturation and value, which are clamped to the range [0, 1].
+    pub fn new(saturation: f32, value: f32) -> Self {
+        let hsv = Hsv::new(0.0, saturation, value);
+        UniformHue {
+            saturation: hsv.saturation(),
+            value: hsv.value(),
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Hsv> for UniformHue {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Hsv {
+        Hsv::new(rng.gen_range(0.0..360.0), self.saturation, self.value)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Rgb> for UniformHue {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgb {
+        Rgb::from(Distribution::<Hsv>::sample(self, rng))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Uniform samplers
+////////////////////////////////////////////////////////////////////////////////
+/// The `Uniform` sampler for `Rgb` colors, which samples each component
+/// uniformly between the components of the bounds.
+///
+/// # Panics
+///
+/// Constructing the sampler panics if any component of the low bound is not
+/// less than the matching component of the high bound, or greater for an
+/// inclusive range.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use rand::Rng;
+/// # use rand::SeedableRng;
+/// # use rand::distributions::Uniform;
+/// # use rand::rngs::StdRng;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let mut rng = StdRng::seed_from_u64(42);
+/// let reds = Uniform::new_inclusive(Rgb::new(128, 0, 0), Rgb::new(255, 64, 64));
+///
+/// let color = rng.sample(reds);
+///
+/// assert!(color.r >= 128 && color.g <= 64 && color.b <= 64);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy)]
+pub struct UniformRgb {
+    /// The sampler for the red component.
+    r: UniformInt<u8>,
+    /// The sampler for the green component.
+    g: UniformInt<u8>,
+    /// The sampler for the blue component.
+    b: UniformInt<u8>,
+}
+
+#[cfg(feature = "rand")]
+impl SampleUniform for Rgb {
+    type Sampler = UniformRgb;
+}
+
+#[cfg(feature = "rand")]
+impl UniformSampler for UniformRgb {
+    type X = Rgb;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+        where
+            B1: SampleBorrow<Rgb> + Sized,
+            B2: SampleBorrow<Rgb> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        UniformRgb {
+            r: UniformInt::new(low.r, high.r),
+            g: UniformInt::new(low.g, high.g),
+            b: UniformInt::new(low.b, high.b),
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+        where
+            B1: SampleBorrow<Rgb> + Sized,
+            B2: SampleBorrow<Rgb> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        UniformRgb {
+            r: UniformInt::new_inclusive(low.r, high.r),
+            g: UniformInt::new_inclusive(low.g, high.g),
+            b: UniformInt::new_inclusive(low.b, high.b),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgb {
+        Rgb::new(self.r.sample(rng), self.g.sample(rng), self.b.sample(rng))
+    }
+}
+
+/// The `Uniform` sampler for `Rgba` colors, which samples each component
+/// uniformly between the components of the bounds.
+///
+/// # Panics
+///
+/// Constructing the sampler panics if any component of the low bound is not
+/// less than the matching component of the high bound, or greater for an
+/// inclusive range.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy)]
+pub struct UniformRgba {
+    /// The sampler for the red component.
+    r: UniformInt<u8>,
+    /// The sampler for the green component.
+    g: UniformInt<u8>,
+    /// The sampler for the blue component.
+    b: UniformInt<u8>,
+    /// The sampler for the alpha component.
+    a: UniformInt<u8>,
+}
+
+#[cfg(feature = "rand")]
+impl SampleUniform for Rgba {
+    type Sampler = UniformRgba;
+}
+
+#[cfg(feature = "rand")]
+impl UniformSampler for UniformRgba {
+    type X = Rgba;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+        where
+            B1: SampleBorrow<Rgba> + Sized,
+            B2: SampleBorrow<Rgba> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        UniformRgba {
+            r: UniformInt::new(low.r, high.r),
+            g: UniformInt::new(low.g, high.g),
+            b: UniformInt::new(low.b, high.b),
+            a: UniformInt::new(low.a, high.a),
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+        where
+            B1: SampleBorrow<Rgba> + Sized,
+            B2: SampleBorrow<Rgba> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        UniformRgba {
+            r: UniformInt::new_inclusive(low.r, high.r),
+            g: UniformInt::new_inclusive(low.g, high.g),
+            b: UniformInt::new_inclusive(low.b, high.b),
+            a: UniformInt::new_inclusive(low.a, high.a),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rgba {
+        Rgba::new(
+            self.r.sample(rng),
+            self.g.sample(rng),
+            self.b.sample(rng),
+            self.a.sample(rng))
+    }
+}
+
+/// The `Uniform` sampler for `Oklab` colors, which samples each component
+/// uniformly between the components of the bounds. Unlike the `Standard`
+/// distribution, the samples are not limited to the sRGB gamut.
+///
+/// # Panics
+///
+/// Constructing the sampler panics if any component of the bounds is not
+/// finite, or if any component of the low bound is not less than the
+/// matching component of the high bound, or greater for an inclusive range.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy)]
+pub struct UniformOklab {
+    /// The sampler for the lightness component.
+    l: UniformFloat<f32>,
+    /// The sampler for the a component.
+    a: UniformFloat<f32>,
+    /// The sampler for the b component.
+    b: UniformFloat<f32>,
+}
+
+#[cfg(feature = "rand")]
+impl SampleUniform for Oklab {
+    type Sampler = UniformOklab;
+}
+
+#[cfg(feature = "rand")]
+impl UniformSampler for UniformOklab {
+    type X = Oklab;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+        where
+            B1: SampleBorrow<Oklab> + Sized,
+            B2: SampleBorrow<Oklab> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        UniformOklab {
+            l: UniformFloat::new(low.l, high.l),
+            a: UniformFloat::new(low.a, high.a),
+            b: UniformFloat::new(low.b, high.b),
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+        where
+            B1: SampleBorrow<Oklab> + Sized,
+            B2: SampleBorrow<Oklab> + Sized,
+    {
+        let (low, high) = (low.borrow(), high.borrow());
+        UniformOklab {
+            l: UniformFloat::new_inclusive(low.l, high.l),
+            a: UniformFloat::new_inclusive(low.a, high.a),
+            b: UniformFloat::new_inclusive(low.b, high.b),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Oklab {
+        Oklab::new(self.l.sample(rng), self.a.sample(rng), self.b.sample(rng))
+    }
+}
@@ -0,0 +1,648 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Plain text and terminal rendering of colors.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_map::ColorMap;
+use crate::Oklab;
+use crate::palette::Palette;
+use crate::Rgb;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::io;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The full block character used to draw swatches.
+pub const FULL_BLOCK: char = '█';
+
+/// The left half block character used to draw previews.
+pub const LEFT_HALF_BLOCK: char = '▌';
+
+/// The ANSI escape sequence which resets all text attributes.
+pub const RESET: &str = "\x1b[0m";
+
+/// The colored square emoji and their typical rendered colors.
+const EMOJI: [(&str, Rgb); 9] = [
+    ("🟥", Rgb { r: 221, g: 46,  b: 68  }),
+    ("🟧", Rgb { r: 244, g: 144, b: 12  }),
+    ("🟨", Rgb { r: 253, g: 203, b: 88  }),
+    ("🟩", Rgb { r: 120, g: 177, b: 89  }),
+    ("🟦", Rgb { r: 85,  g: 172, b: 238 }),
+    ("🟪", Rgb { r: 170, g: 142, b: 214 }),
+    ("🟫", Rgb { r: 193, g: 105, b: 79  }),
+    ("⬛", Rgb { r: 49,  g: 55,  b: 61  }),
+    ("⬜", Rgb { r: 230, g: 231, b: 232 }),
+];
+
+/// The xterm colors of the 16 standard ANSI colors.
+const ANSI_16: [Rgb; 16] = [
+    Rgb { r: 0,   g: 0,   b: 0   },
+    Rgb { r: 205, g: 0,   b: 0   },
+    Rgb { r: 0,   g: 205, b: 0   },
+    Rgb { r: 205, g: 205, b: 0   },
+    Rgb { r: 0,   g: 0,   b: 238 },
+    Rgb { r: 205, g: 0,   b: 205 },
+    Rgb { r: 0,   g: 205, b: 205 },
+    Rgb { r: 229, g: 229, b: 229 },
+    Rgb { r: 127, g: 127, b: 127 },
+    Rgb { r: 255, g: 0,   b: 0   },
+    Rgb { r: 0,   g: 255, b: 0   },
+    Rgb { r: 255, g: 255, b: 0   },
+    Rgb { r: 92,  g: 92,  b: 255 },
+    Rgb { r: 255, g: 0,   b: 255 },
+    Rgb { r: 0,   g: 255, b: 255 },
+    Rgb { r: 255, g: 255, b: 255 },
+];
+
+/// The channel levels of the 6×6×6 color cube of the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The width of the preview line written by the `print_preview` methods.
+pub(in crate) const PREVIEW_WIDTH: usize = 48;
+
+/// The width of each swatch written by the `print_preview` methods.
+const PREVIEW_SWATCH_WIDTH: usize = 4;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// emoji
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the colored square emoji nearest to the given color.
+///
+/// Differences in lightness are given half weight, as emoji are rendered with
+/// widely varying lightness across platforms and their hue is what readers
+/// notice.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::render::emoji;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(emoji(Rgb::new(255, 0, 0)), "🟥");
+/// assert_eq!(emoji(Rgb::new(0, 90, 200)), "🟦");
+/// assert_eq!(emoji(Rgb::new(0, 0, 0)), "⬛");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn emoji<C>(color: C) -> &'static str where C: Into<Rgb> {
+    let target = Oklab::from(color.into());
+    EMOJI
+        .iter()
+        .map(|&(emoji, rgb)| {
+            let rgb = Oklab::from(rgb);
+            let l = (target.l - rgb.l) / 2.0;
+            let a = target.a - rgb.a;
+            let b = target.b - rgb.b;
+            (emoji, (l*l + a*a + b*b).sqrt())
+        })
+        .fold(("⬛", f32::INFINITY), |nearest, next| {
+            if next.1 < nearest.1 { next } else { nearest }
+        })
+        .0
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// AnsiMode
+////////////////////////////////////////////////////////////////////////////////
+/// The color support of a terminal.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AnsiMode {
+    /// 24-bit truecolor escape sequences.
+    #[default]
+    TrueColor,
+    /// The xterm 256-color palette.
+    Ansi256,
+    /// The 16 standard ANSI colors.
+    Ansi16,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ansi_foreground
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the ANSI escape sequence which sets the foreground color to the
+/// given color, or the nearest color supported by the given mode.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::render::AnsiMode;
+/// # use color::render::ansi_foreground;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let orange = Rgb::new(255, 135, 0);
+///
+/// assert_eq!(ansi_foreground(orange, AnsiMode::TrueColor), "\x1b[38;2;255;135;0m");
+/// assert_eq!(ansi_foreground(orange, AnsiMode::Ansi256), "\x1b[38;5;208m");
+/// assert_eq!(ansi_foreground(orange, AnsiMode::Ansi16), "\x1b[91m");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn ansi_foreground<C>(color: C, mode: AnsiMode) -> String
+    where C: Into<Rgb>
+{
+    ansi_escape(color.into(), mode, false)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ansi_background
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the ANSI escape sequence which sets the background color to the
+/// given color, or the nearest color supported by the given mode.
+pub fn ansi_background<C>(color: C, mode: AnsiMode) -> String
+    where C: Into<Rgb>
+{
+    ansi_escape(color.into(), mode, true)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ansi_swatch
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a swatch of the given color drawn as `width` block characters
+/// between a foreground escape sequence and a reset sequence.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::render::AnsiMode;
+/// # use color::render::ansi_swatch;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let swatch = ansi_swatch(Rgb::new(255, 0, 0), AnsiMode::TrueColor, 2);
+///
+/// assert_eq!(swatch, "\x1b[38;2;255;0;0m██\x1b[0m");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn ansi_swatch<C>(color: C, mode: AnsiMode, width: usize) -> String
+    where C: Into<Rgb>
+{
+    let mut swatch = ansi_foreground(color, mode);
+    swatch.extend(std::iter::repeat_n(FULL_BLOCK, width));
+    swatch.push_str(RESET);
+    swatch
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// preview
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a single line preview of a [`ColorMap`], such as a [`Gradient`],
+/// drawn as `width` half block characters.
+///
+/// Each character shows two samples, using the foreground color for its left
+/// half and the background color for its right half. Escape sequences are
+/// only emitted when a color changes, and the line ends with a reset sequence.
+///
+/// [`ColorMap`]: ../color_map/trait.ColorMap.html
+/// [`Gradient`]: ../gradient/struct.Gradient.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::gradient::Gradient;
+/// # use color::render::AnsiMode;
+/// # use color::render::preview;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(255, 255, 255));
+///
+/// let line = preview(&gradient, 1, AnsiMode::TrueColor);
+///
+/// assert_eq!(line, "\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m▌\x1b[0m");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn preview<M>(map: &M, width: usize, mode: AnsiMode) -> String
+    where M: ColorMap + ?Sized
+{
+    let count = 2 * width;
+    let last = count.saturating_sub(1).max(1) as f32;
+    let samples: Vec<Rgb> = (0..count)
+        .map(|i| map.map(i as f32 / last))
+        .collect();
+    half_block_line(&samples, mode)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// preview_palette
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a single line preview of a [`Palette`], drawn as `width` half
+/// block characters with each color given an equal share of the width.
+///
+/// If the palette has more colors than half blocks, some colors will not be
+/// shown.
+///
+/// [`Palette`]: ../palette/struct.Palette.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::palette::Palette;
+/// # use color::render::AnsiMode;
+/// # use color::render::preview_palette;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let palette: Palette = vec![
+///     ("red", Rgb::new(255, 0, 0)),
+///     ("blue", Rgb::new(0, 0, 255)),
+/// ].into_iter().collect();
+///
+/// let line = preview_palette(&palette, 2, AnsiMode::TrueColor);
+///
+/// assert_eq!(line, "\x1b[38;2;255;0;0m\x1b[48;2;255;0;0m▌\
+///     \x1b[38;2;0;0;255m\x1b[48;2;0;0;255m▌\x1b[0m");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn preview_palette(palette: &Palette, width: usize, mode: AnsiMode)
+    -> String
+{
+    if palette.is_empty() { return String::new(); }
+
+    let colors = palette.swatches();
+    let count = 2 * width;
+    let samples: Vec<Rgb> = (0..count)
+        .map(|i| colors[i * colors.len() / count].color)
+        .collect();
+    half_block_line(&samples, mode)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TextSplit
+////////////////////////////////////////////////////////////////////////////////
+/// The way [`text_colors`] spreads a color map over text.
+///
+/// [`text_colors`]: fn.text_colors.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextSplit {
+    /// The color map spans the whole text, with a color for each character.
+    #[default]
+    Character,
+    /// The color map spans the words of the text, with one color for each
+    /// word. Whitespace takes the color of the preceding word.
+    Word,
+    /// The color map spans each line of the text separately, with a color
+    /// for each character. Each line break takes the color of the character
+    /// before it.
+    Line,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// text_colors
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a color from the given [`ColorMap`], such as a [`Gradient`], for
+/// each character of the given text, spread according to the given
+/// [`TextSplit`].
+///
+/// The first character or word of each span is given the start of the map
+/// and the last is given the end. Every character is assumed to be one
+/// column wide; use [`glyph_colors`] to account for wide or zero-width
+/// characters.
+///
+/// [`ColorMap`]: ../color_map/trait.ColorMap.html
+/// [`Gradient`]: ../gradient/struct.Gradient.html
+/// [`TextSplit`]: enum.TextSplit.html
+/// [`glyph_colors`]: fn.glyph_colors.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::gradient::Gradient;
+/// # use color::render::TextSplit;
+/// # use color::render::text_colors;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(200, 0, 0));
+///
+/// let colors = text_colors(&gradient, "hello", TextSplit::Character);
+/// assert_eq!(colors[0], Rgb::new(0, 0, 0));
+/// assert_eq!(colors[2], Rgb::new(100, 0, 0));
+/// assert_eq!(colors[4], Rgb::new(200, 0, 0));
+///
+/// let colors = text_colors(&gradient, "hi there", TextSplit::Word);
+/// assert_eq!(colors[1], Rgb::new(0, 0, 0));
+/// assert_eq!(colors[2], Rgb::new(0, 0, 0));
+/// assert_eq!(colors[3], Rgb::new(200, 0, 0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn text_colors<M>(map: &M, text: &str, split: TextSplit) -> Vec<Rgb>
+    where M: ColorMap + ?Sized
+{
+    match split {
+        TextSplit::Character => {
+            let count = text.chars().count();
+            (0..count).map(|i| map.map(span_position(i, count))).collect()
+        },
+        TextSplit::Word => {
+            let count = text.split_whitespace().count();
+            let mut colors = Vec::with_capacity(text.len());
+            let mut word: usize = 0;
+            let mut in_word = false;
+            for c in text.chars() {
+                if c.is_whitespace() {
+                    if in_word { word += 1; }
+                    in_word = false;
+                } else {
+                    in_word = true;
+                }
+                let index = if in_word { word } else { word.saturating_sub(1) };
+                colors.push(map.map(span_position(index, count)));
+            }
+            colors
+        },
+        TextSplit::Line => {
+            let mut colors = Vec::with_capacity(text.len());
+            for line in text.split_inclusive('\n') {
+                let content = line.strip_suffix('\n').unwrap_or(line);
+                let count = content.chars().count();
+                colors.extend((0..count)
+                    .map(|i| map.map(span_position(i, count))));
+                if content.len() < line.len() {
+                    let before = count.saturating_sub(1);
+                    colors.push(map.map(span_position(before, count)));
+                }
+            }
+            colors
+        },
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// glyph_colors
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a color from the given [`ColorMap`], such as a [`Gradient`], for
+/// each glyph of the given display widths, in columns.
+///
+/// Each glyph is colored by the column where it starts, with the first glyph
+/// given the start of the map and the last given the end, so wide glyphs
+/// advance the colors further than narrow ones and zero-width glyphs share
+/// the color of the next glyph.
+///
+/// [`ColorMap`]: ../color_map/trait.ColorMap.html
+/// [`Gradient`]: ../gradient/struct.Gradient.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::gradient::Gradient;
+/// # use color::render::glyph_colors;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(0, 0, 200));
+///
+/// let colors = glyph_colors(&gradient, &[2, 1, 1]);
+///
+/// assert_eq!(colors, [
+///     Rgb::new(0, 0, 0),
+///     Rgb::new(0, 0, 133),
+///     Rgb::new(0, 0, 200),
+/// ]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn glyph_colors<M>(map: &M, widths: &[usize]) -> Vec<Rgb>
+    where M: ColorMap + ?Sized
+{
+    let last = widths.iter().rev().skip(1).sum::<usize>();
+    let mut column = 0;
+    widths
+        .iter()
+        .map(|&width| {
+            let t = if last == 0 { 0.0 } else {
+                column as f32 / last as f32
+            };
+            column += width;
+            map.map(t)
+        })
+        .collect()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ansi_text
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given text with each character colored by [`text_colors`],
+/// using foreground escape sequences for the given [`AnsiMode`].
+///
+/// Escape sequences are only emitted when a color changes, and the text ends
+/// with a reset sequence.
+///
+/// [`text_colors`]: fn.text_colors.html
+/// [`AnsiMode`]: enum.AnsiMode.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::gradient::Gradient;
+/// # use color::render::AnsiMode;
+/// # use color::render::TextSplit;
+/// # use color::render::ansi_text;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let gradient = Gradient::new(Rgb::new(255, 0, 0), Rgb::new(0, 0, 255));
+///
+/// let text = ansi_text(&gradient, "ok", TextSplit::Character,
+///     AnsiMode::TrueColor);
+///
+/// assert_eq!(text, "\x1b[38;2;255;0;0mo\x1b[38;2;0;0;255mk\x1b[0m");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn ansi_text<M>(map: &M, text: &str, split: TextSplit, mode: AnsiMode)
+    -> String
+    where M: ColorMap + ?Sized
+{
+    let mut out = String::with_capacity(text.len() * 4);
+    let mut current = None;
+    for (c, color) in text.chars().zip(text_colors(map, text, split)) {
+        if current != Some(color) && !c.is_whitespace() {
+            out.push_str(&ansi_escape(color, mode, false));
+            current = Some(color);
+        }
+        out.push(c);
+    }
+    out.push_str(RESET);
+    out
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Draws pairs of samples as half block characters.
+fn half_block_line(samples: &[Rgb], mode: AnsiMode) -> String {
+    if samples.is_empty() { return String::new(); }
+
+    let mut line = String::new();
+    let mut current: Option<(Rgb, Rgb)> = None;
+    for pair in samples.chunks_exact(2) {
+        let (left, right) = (pair[0], pair[1]);
+        if current.map(|c| c.0) != Some(left) {
+            line.push_str(&ansi_escape(left, mode, false));
+        }
+        if current.map(|c| c.1) != Some(right) {
+            line.push_str(&ansi_escape(right, mode, true));
+        }
+        current = Some((left, right));
+        line.push(LEFT_HALF_BLOCK);
+    }
+    line.push_str(RESET);
+    line
+}
+
+/// Writes a truecolor swatch row followed by the color's hex code and the
+/// given label.
+pub(in crate) fn write_swatch_row<W>(out: &mut W, color: Rgb, label: &str)
+    -> io::Result<()>
+    where W: io::Write + ?Sized
+{
+    let swatch = ansi_swatch(color, AnsiMode::TrueColor, PREVIEW_SWATCH_WIDTH);
+    if label.is_empty() {
+        writeln!(out, "{} {:x}", swatch, color)
+    } else {
+        writeln!(out, "{} {:x} {}", swatch, color, label)
+    }
+}
+
+/// Returns the escape sequence selecting the given color.
+fn ansi_escape(color: Rgb, mode: AnsiMode, background: bool) -> String {
+    match mode {
+        AnsiMode::TrueColor => format!("\x1b[{};2;{};{};{}m",
+            if background { 48 } else { 38 }, color.r, color.g, color.b),
+        AnsiMode::Ansi256 => format!("\x1b[{};5;{}m",
+            if background { 48 } else { 38 }, ansi_256_index(color)),
+        AnsiMode::Ansi16 => {
+            let index = nearest_index(color, ANSI_16.iter().copied()) as u8;
+            let base = if background { 40 } else { 30 };
+            let code = if index < 8 { base + index } else { base + 60 + index - 8 };
+            format!("\x1b[{}m", code)
+        },
+    }
+}
+
+/// Returns the index of the nearest color in the xterm 256-color palette,
+/// considering only the color cube and the grayscale ramp.
+fn ansi_256_index(color: Rgb) -> u8 {
+    let level = |c: u8| (0..CUBE_LEVELS.len())
+        .min_by_key(|&i| (CUBE_LEVELS[i] as i16 - c as i16).abs())
+        .unwrap_or(0);
+    let (r, g, b) = (level(color.r), level(color.g), level(color.b));
+    let cube = Rgb::new(CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    let mean = (color.r as u16 + color.g as u16 + color.b as u16) / 3;
+    let gray_step = ((mean.saturating_sub(3)) / 10).min(23) as u8;
+    let gray_level = 8 + gray_step * 10;
+    let gray = Rgb::new(gray_level, gray_level, gray_level);
+
+    if Oklab::distance(color, gray) < Oklab::distance(color, cube) {
+        232 + gray_step
+    } else {
+        (16 + 36 * r + 6 * g + b) as u8
+    }
+}
+
+/// Returns the index of the color nearest to the target in Oklab.
+fn nearest_index<I>(target: Rgb, colors: I) -> usize
+    where I: Iterator<Item=Rgb>
+{
+    let target = Oklab::from(target);
+    colors
+        .map(|c| Oklab::distance(target, c))
+        .enumerate()
+        .fold((0, f32::INFINITY), |nearest, next| {
+            if next.1 < nearest.1 { next } else { nearest }
+        })
+        .0
+}
+
+/// Returns the position of the item at the given index of a span of `count`
+/// items, from 0 for the first item to 1 for the last.
+fn span_position(index: usize, count: usize) -> f32 {
+    if count <= 1 { 0.0 } else { index as f32 / (count - 1) as f32 }
+}
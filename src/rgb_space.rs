@@ -0,0 +1,290 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! RGB color spaces defined by their primaries and white point.
+//!
+//! An [`RgbSpace`] is defined by the chromaticities of its red, green, and
+//! blue primaries and by its white point. The matrices converting between its
+//! linear RGB ratios and [`Xyz`] are derived from these at compile time, so a
+//! new space is added by stating its primaries rather than copying
+//! precomputed constants.
+//!
+//! [`RgbSpace`]: trait.RgbSpace.html
+//! [`Xyz`]: ../struct.Xyz.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::illuminant::Illuminant;
+use crate::transfer::TransferFunction;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RgbSpace
+////////////////////////////////////////////////////////////////////////////////
+/// An RGB color space, defined by its primaries, white point, and transfer
+/// function.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::rgb_space::RgbSpace;
+/// # use color::rgb_space::Srgb;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let m = Srgb::XYZ_FROM_LINEAR;
+///
+/// assert!((m[0][0] - 0.4124).abs() < 0.0001);
+/// assert!((m[1][1] - 0.7152).abs() < 0.0001);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub trait RgbSpace {
+    /// The CIE 1931 `[x, y]` chromaticities of the red, green, and blue
+    /// primaries.
+    const PRIMARIES: [[f32; 2]; 3];
+
+    /// The white point of the space, which is the color of equal linear red,
+    /// green, and blue ratios.
+    const WHITE_POINT: Illuminant;
+
+    /// The transfer function encoding the linear RGB ratios of the space.
+    const TRANSFER_FUNCTION: TransferFunction;
+
+    /// The matrix converting linear RGB ratios to [`Xyz`] components, such
+    /// that RGB white maps to the white point with a luminance of 1.
+    ///
+    /// [`Xyz`]: ../struct.Xyz.html
+    const XYZ_FROM_LINEAR: [[f32; 3]; 3]
+        = xyz_from_linear_matrix(Self::PRIMARIES, Self::WHITE_POINT);
+
+    /// The matrix converting [`Xyz`] components to linear RGB ratios, which
+    /// are outside of [0, 1] for colors out of the gamut of the space.
+    ///
+    /// [`Xyz`]: ../struct.Xyz.html
+    const LINEAR_FROM_XYZ: [[f32; 3]; 3]
+        = inverse_f32(Self::XYZ_FROM_LINEAR);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Srgb
+////////////////////////////////////////////////////////////////////////////////
+/// The sRGB color space, the standard for the web and most displays, and the
+/// space of [`Rgb`] colors.
+///
+/// [`Rgb`]: ../struct.Rgb.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Srgb;
+
+impl RgbSpace for Srgb {
+    const PRIMARIES: [[f32; 2]; 3] =
+        [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]];
+    const WHITE_POINT: Illuminant = Illuminant::D65;
+    const TRANSFER_FUNCTION: TransferFunction = TransferFunction::Srgb;
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DisplayP3
+////////////////////////////////////////////////////////////////////////////////
+/// The Display P3 color space, using the DCI-P3 primaries with a D65 white
+/// point, as used by Apple displays and the CSS `display-p3` color space.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DisplayP3;
+
+impl RgbSpace for DisplayP3 {
+    const PRIMARIES: [[f32; 2]; 3] =
+        [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]];
+    const WHITE_POINT: Illuminant = Illuminant::D65;
+    const TRANSFER_FUNCTION: TransferFunction = TransferFunction::Srgb;
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// AdobeRgb
+////////////////////////////////////////////////////////////////////////////////
+/// The Adobe RGB (1998) color space, which extends the sRGB gamut toward
+/// green and cyan for print workflows.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdobeRgb;
+
+impl RgbSpace for AdobeRgb {
+    const PRIMARIES: [[f32; 2]; 3] =
+        [[0.64, 0.33], [0.21, 0.71], [0.15, 0.06]];
+    const WHITE_POINT: Illuminant = Illuminant::D65;
+    const TRANSFER_FUNCTION: TransferFunction
+        = TransferFunction::Gamma(563.0 / 256.0);
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rec2020
+////////////////////////////////////////////////////////////////////////////////
+/// The ITU-R BT.2020 color space, used for UHD and HDR video.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rec2020;
+
+impl RgbSpace for Rec2020 {
+    const PRIMARIES: [[f32; 2]; 3] =
+        [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]];
+    const WHITE_POINT: Illuminant = Illuminant::D65;
+    const TRANSFER_FUNCTION: TransferFunction = TransferFunction::Rec709;
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// xyz_from_linear_matrix
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the matrix converting linear RGB ratios to [`Xyz`] components for
+/// the RGB space with the given `[x, y]` primary chromaticities and white
+/// point.
+///
+/// [`Xyz`]: ../struct.Xyz.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::illuminant::Illuminant;
+/// # use color::rgb_space::xyz_from_linear_matrix;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // The CIE RGB color space.
+/// let m = xyz_from_linear_matrix(
+///     [[0.7347, 0.2653], [0.2738, 0.7174], [0.1666, 0.0089]],
+///     Illuminant::E);
+///
+/// // The luminance of white is 1.
+/// assert!((m[1][0] + m[1][1] + m[1][2] - 1.0).abs() < 0.0001);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub const fn xyz_from_linear_matrix(
+    primaries: [[f32; 2]; 3],
+    white: Illuminant)
+    -> [[f32; 3]; 3]
+{
+    primaries_matrix_f32(primaries, white.white_point().components())
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the matrix converting linear RGB ratios to [`Xyz`] components in
+/// double precision, for the RGB space with the given `[x, y]` primary
+/// chromaticities and white point.
+///
+/// [`Xyz`]: ../struct.Xyz.html
+pub(in crate) const fn xyz_from_linear_matrix_f64(
+    primaries: [[f32; 2]; 3],
+    white: Illuminant)
+    -> [[f64; 3]; 3]
+{
+    let [x, y] = white.chromaticity();
+    let (x, y) = (x as f64, y as f64);
+    primaries_matrix_f64(primaries, [x / y, 1.0, (1.0 - x - y) / y])
+}
+
+/// Generates const functions computing the 3×3 matrices of an RGB space in
+/// the given precision. The operations are ordered as in the generic matrix
+/// functions of the `utility` module, so the results are identical.
+macro_rules! space_matrix_fns {
+    ($inverse:ident, $primaries_matrix:ident, $t:ty) => {
+        /// Returns the inverse of the given invertible 3×3 matrix.
+        pub(in crate) const fn $inverse(m: [[$t; 3]; 3]) -> [[$t; 3]; 3] {
+            const fn cofactor(m: [[$t; 3]; 3], i: usize, j: usize) -> $t {
+                let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+                let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+                m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+            }
+
+            let determinant = m[0][0] * cofactor(m, 0, 0)
+                + m[0][1] * cofactor(m, 0, 1)
+                + m[0][2] * cofactor(m, 0, 2);
+            let mut inverse = [[0.0; 3]; 3];
+            let mut i = 0;
+            while i < 3 {
+                let mut j = 0;
+                while j < 3 {
+                    inverse[i][j] = cofactor(m, j, i) / determinant;
+                    j += 1;
+                }
+                i += 1;
+            }
+            inverse
+        }
+
+        /// Returns the matrix converting linear RGB ratios to XYZ components
+        /// for the given primary chromaticities and white point components.
+        #[allow(trivial_numeric_casts)]
+        const fn $primaries_matrix(primaries: [[f32; 2]; 3], white: [$t; 3])
+            -> [[$t; 3]; 3]
+        {
+            // The columns of the unscaled matrix are the primaries with unit
+            // luminance.
+            let mut m = [[0.0; 3]; 3];
+            let mut j = 0;
+            while j < 3 {
+                let [x, y] = primaries[j];
+                let (x, y) = (x as $t, y as $t);
+                m[0][j] = x / y;
+                m[1][j] = 1.0;
+                m[2][j] = (1.0 - x - y) / y;
+                j += 1;
+            }
+
+            // Scale each primary so that their sum is the white point.
+            let inverse = $inverse(m);
+            let mut scale = [0.0; 3];
+            let mut i = 0;
+            while i < 3 {
+                let row = inverse[i];
+                scale[i] = row[0] * white[0] + row[1] * white[1]
+                    + row[2] * white[2];
+                i += 1;
+            }
+            let mut i = 0;
+            while i < 3 {
+                let mut j = 0;
+                while j < 3 {
+                    m[i][j] *= scale[j];
+                    j += 1;
+                }
+                i += 1;
+            }
+            m
+        }
+    };
+}
+
+space_matrix_fns!(inverse_f32, primaries_matrix_f32, f32);
+space_matrix_fns!(inverse_f64, primaries_matrix_f64, f64);
@@ -0,0 +1,259 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Compact, human-typeable short codes for colors and small palettes.
+//!
+//! A short code is the RGB octets of one or more colors followed by a CRC-8
+//! checksum, encoded in [Crockford's base32] and split into groups of four
+//! characters. The alphabet avoids easily confused letters, and decoding is
+//! case-insensitive and accepts `I` and `L` for `1` and `O` for `0`, so codes
+//! can be read aloud or typed by hand. A single color encodes to seven
+//! characters, such as `ZZZZ-Y3R` for white.
+//!
+//! [Crockford's base32]: https://www.crockford.com/base32.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Rgb;
+
+// Standard library imports.
+use std::error::Error;
+use std::fmt;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The maximum number of colors in a short code.
+pub const MAX_COLORS: usize = 32;
+
+/// The Crockford base32 alphabet.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// The number of characters in each group of a short code.
+const GROUP_LEN: usize = 4;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ShortCodeError
+////////////////////////////////////////////////////////////////////////////////
+/// An error which can be returned while decoding a short code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortCodeError {
+    /// The code contains a character outside of the base32 alphabet.
+    InvalidCharacter(char),
+    /// The code has a length which does not encode a whole number of colors.
+    InvalidLength,
+    /// The checksum does not match, indicating a mistyped code.
+    ChecksumMismatch,
+}
+
+impl fmt::Display for ShortCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            ShortCodeError::InvalidCharacter(c) => write!(f,
+                "invalid character {:?} in short code", c),
+            ShortCodeError::InvalidLength => write!(f,
+                "invalid short code length"),
+            ShortCodeError::ChecksumMismatch => write!(f,
+                "short code checksum mismatch"),
+        }
+    }
+}
+
+impl Error for ShortCodeError {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// encode
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the short code for the given colors.
+///
+/// # Panics
+///
+/// Panics if `colors` is empty or has more than [`MAX_COLORS`] colors.
+///
+/// [`MAX_COLORS`]: constant.MAX_COLORS.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::short_code::decode;
+/// # use color::short_code::encode;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let colors = vec![Rgb::new(255, 99, 71), Rgb::new(20, 24, 40)];
+///
+/// let code = encode(colors.iter().copied());
+///
+/// assert_eq!(code, "ZXHM-E50R-51S0");
+/// assert_eq!(decode(&code.to_lowercase())?, colors);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn encode<I>(colors: I) -> String where I: IntoIterator<Item=Rgb> {
+    let mut bytes: Vec<u8> = colors
+        .into_iter()
+        .flat_map(|color| color.octets())
+        .collect();
+    assert!(!bytes.is_empty());
+    assert!(bytes.len() <= MAX_COLORS * 3);
+    bytes.push(crc8(&bytes));
+
+    let mut code = String::new();
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    let push = |code: &mut String, symbol: u16| {
+        if code.len() % (GROUP_LEN + 1) == GROUP_LEN { code.push('-'); }
+        code.push(ALPHABET[symbol as usize & 0x1F] as char);
+    };
+    for byte in bytes {
+        buffer = (buffer << 8) | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            push(&mut code, buffer >> bits);
+        }
+    }
+    if bits > 0 {
+        push(&mut code, buffer << (5 - bits));
+    }
+    code
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// encode_color
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the short code for a single color.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::short_code::decode_color;
+/// # use color::short_code::encode_color;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let code = encode_color(Rgb::new(255, 99, 71));
+///
+/// assert_eq!(code.len(), 8);
+/// assert_eq!(decode_color(&code)?, Rgb::new(255, 99, 71));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn encode_color<C>(color: C) -> String where C: Into<Rgb> {
+    encode(Some(color.into()))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// decode
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the colors encoded by the given short code.
+///
+/// Hyphens and whitespace are ignored.
+///
+/// # Errors
+///
+/// Returns a [`ShortCodeError`] if the code is invalid or mistyped.
+///
+/// [`ShortCodeError`]: enum.ShortCodeError.html
+pub fn decode(code: &str) -> Result<Vec<Rgb>, ShortCodeError> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for c in code.chars().filter(|&c| c != '-' && !c.is_whitespace()) {
+        buffer = (buffer << 5) | symbol_value(c)? as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    // Any leftover bits must be zero padding.
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(ShortCodeError::InvalidLength);
+    }
+    if bytes.len() < 4 || bytes.len() % 3 != 1 {
+        return Err(ShortCodeError::InvalidLength);
+    }
+
+    let checksum = bytes.pop();
+    if checksum != Some(crc8(&bytes)) {
+        return Err(ShortCodeError::ChecksumMismatch);
+    }
+
+    Ok(bytes
+        .chunks_exact(3)
+        .map(|rgb| Rgb::new(rgb[0], rgb[1], rgb[2]))
+        .collect())
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// decode_color
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the single color encoded by the given short code.
+///
+/// # Errors
+///
+/// Returns a [`ShortCodeError`] if the code is invalid or mistyped, or if it
+/// encodes more than one color.
+///
+/// [`ShortCodeError`]: enum.ShortCodeError.html
+pub fn decode_color(code: &str) -> Result<Rgb, ShortCodeError> {
+    match decode(code)?[..] {
+        [color] => Ok(color),
+        _ => Err(ShortCodeError::InvalidLength),
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the value of a base32 symbol, accepting Crockford's aliases.
+fn symbol_value(c: char) -> Result<u8, ShortCodeError> {
+    let upper = match c.to_ascii_uppercase() {
+        'O' => '0',
+        'I' | 'L' => '1',
+        upper => upper,
+    };
+    ALPHABET
+        .iter()
+        .position(|&symbol| symbol as char == upper)
+        .map(|value| value as u8)
+        .ok_or(ShortCodeError::InvalidCharacter(c))
+}
+
+/// Returns the CRC-8 checksum of the given bytes, using the polynomial
+/// `x^8 + x^2 + x + 1`.
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 }
+        })
+    })
+}
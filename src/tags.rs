@@ -0,0 +1,300 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Rule-based tagging of colors with descriptive labels.
+//!
+//! A [`TagRule`] assigns a tag, such as "pastel" or "earthy", to every color
+//! whose [`Oklch`] lightness, chroma, and hue fall within its ranges. A
+//! [`Tagger`] holds a set of rules, starting from a small default set which
+//! applications can extend or replace, so that colors and palettes are
+//! labelled consistently.
+//!
+//! [`TagRule`]: struct.TagRule.html
+//! [`Tagger`]: struct.Tagger.html
+//! [`Oklch`]: ../struct.Oklch.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Oklch;
+use crate::palette::Palette;
+use crate::Rgb;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// Standard library imports.
+use std::cmp::Reverse;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The rules of the default [`Tagger`], as the tag followed by the Oklch
+/// lightness, chroma, and optional hue ranges.
+///
+/// [`Tagger`]: struct.Tagger.html
+#[allow(clippy::type_complexity)]
+const DEFAULT_RULES: [(&str, [f32; 2], [f32; 2], Option<[f32; 2]>); 8] = [
+    ("neutral", [0.0, 1.0],  [0.0, 0.02],   None),
+    ("dark",    [0.0, 0.3],  [0.0, 1.0],    None),
+    ("light",   [0.9, 1.0],  [0.0, 1.0],    None),
+    ("pastel",  [0.8, 1.0],  [0.02, 0.1],   None),
+    ("muted",   [0.3, 0.8],  [0.02, 0.06],  None),
+    ("vivid",   [0.0, 1.0],  [0.15, 1.0],   None),
+    ("neon",    [0.6, 1.0],  [0.2, 1.0],    None),
+    ("earthy",  [0.25, 0.65], [0.03, 0.13], Some([30.0, 100.0])),
+];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TagRule
+////////////////////////////////////////////////////////////////////////////////
+/// A rule assigning a tag to colors within given [`Oklch`] ranges.
+///
+/// Each range is inclusive. A new rule matches every color until its ranges
+/// are narrowed.
+///
+/// [`Oklch`]: ../struct.Oklch.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::tags::TagRule;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let ocean = TagRule::new("ocean")
+///     .with_chroma(0.05, 1.0)
+///     .with_hue(200.0, 260.0);
+///
+/// assert!(ocean.matches(Rgb::new(0, 105, 148)));
+/// assert!(!ocean.matches(Rgb::new(148, 105, 0)));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TagRule {
+    /// The tag assigned to matching colors.
+    tag: String,
+    /// The range of Oklch lightness.
+    lightness: [f32; 2],
+    /// The range of Oklch chroma.
+    chroma: [f32; 2],
+    /// The range of Oklch hue, in degrees, or `None` to match any hue.
+    hue: Option<[f32; 2]>,
+}
+
+impl TagRule {
+    /// Constructs a new `TagRule` assigning the given tag to every color.
+    pub fn new<S>(tag: S) -> Self where S: Into<String> {
+        TagRule {
+            tag: tag.into(),
+            lightness: [0.0, 1.0],
+            chroma: [0.0, f32::INFINITY],
+            hue: None,
+        }
+    }
+
+    /// Returns the rule restricted to the given range of Oklch lightness.
+    pub fn with_lightness(mut self, min: f32, max: f32) -> Self {
+        self.lightness = [min, max];
+        self
+    }
+
+    /// Returns the rule restricted to the given range of Oklch chroma.
+    pub fn with_chroma(mut self, min: f32, max: f32) -> Self {
+        self.chroma = [min, max];
+        self
+    }
+
+    /// Returns the rule restricted to the given range of Oklch hue, in
+    /// degrees. The range runs counterclockwise from `start` to `end`, so it
+    /// wraps through 0 if `start` is greater than `end`.
+    pub fn with_hue(mut self, start: f32, end: f32) -> Self {
+        self.hue = Some([start, end]);
+        self
+    }
+
+    /// Returns the tag assigned by the rule.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns true if the rule matches the given color.
+    pub fn matches<C>(&self, color: C) -> bool where C: Into<Rgb> {
+        let oklch = Oklch::from(color.into());
+        let within = |[min, max]: [f32; 2], v: f32| min <= v && v <= max;
+
+        within(self.lightness, oklch.l)
+            && within(self.chroma, oklch.c)
+            && match self.hue {
+                None => true,
+                Some([start, end]) if start <= end => {
+                    within([start, end], oklch.h)
+                },
+                Some([start, end]) => oklch.h >= start || oklch.h <= end,
+            }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Tagger
+////////////////////////////////////////////////////////////////////////////////
+/// A set of [`TagRule`]s used to tag colors and palettes.
+///
+/// The default tagger has rules for the tags "neutral", "dark", "light",
+/// "pastel", "muted", "vivid", "neon", and "earthy".
+///
+/// [`TagRule`]: struct.TagRule.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::tags::TagRule;
+/// # use color::tags::Tagger;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let tagger = Tagger::default()
+///     .with_rule(TagRule::new("ocean").with_chroma(0.05, 1.0)
+///         .with_hue(200.0, 260.0));
+///
+/// assert_eq!(tagger.tags(Rgb::new(255, 209, 220)), ["light", "pastel"]);
+/// assert_eq!(tagger.tags(Rgb::new(57, 255, 20)), ["vivid", "neon"]);
+/// assert_eq!(tagger.tags(Rgb::new(0, 105, 148)), ["ocean"]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tagger {
+    /// The tagging rules, in order.
+    rules: Vec<TagRule>,
+}
+
+impl Tagger {
+    /// Constructs a new `Tagger` with no rules.
+    pub fn new() -> Self {
+        Tagger { rules: Vec::new() }
+    }
+
+    /// Returns the tagger with the given rule added after the existing
+    /// rules.
+    pub fn with_rule(mut self, rule: TagRule) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    /// Adds the given rule after the existing rules.
+    pub fn add_rule(&mut self, rule: TagRule) {
+        self.rules.push(rule);
+    }
+
+    /// Removes every rule assigning the given tag.
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.rules.retain(|rule| rule.tag != tag);
+    }
+
+    /// Returns the tagging rules, in order.
+    pub fn rules(&self) -> &[TagRule] {
+        &self.rules[..]
+    }
+
+    /// Returns the tags of the given color, in the order of the rules. Each
+    /// tag is listed once, even if several of its rules match.
+    pub fn tags<C>(&self, color: C) -> Vec<&str> where C: Into<Rgb> {
+        let color = color.into();
+        let mut tags: Vec<&str> = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.matches(color)) {
+            if !tags.contains(&rule.tag()) { tags.push(rule.tag()); }
+        }
+        tags
+    }
+
+    /// Returns each tag of the palette colors with the number of colors it
+    /// applies to, most common first, and otherwise in the order of the
+    /// rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::palette::Palette;
+    /// # use color::tags::Tagger;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let palette: Palette = vec![
+    ///     ("clay", Rgb::new(150, 100, 60)),
+    ///     ("moss", Rgb::new(110, 110, 60)),
+    ///     ("bark", Rgb::new(60, 40, 30)),
+    /// ].into_iter().collect();
+    ///
+    /// let tagger = Tagger::default();
+    /// let tags = tagger.palette_tags(&palette);
+    ///
+    /// assert_eq!(tags[0], ("earthy", 2));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn palette_tags(&self, palette: &Palette) -> Vec<(&str, usize)> {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for color in palette.colors() {
+            for tag in self.tags(color) {
+                match counts.iter_mut().find(|(t, _)| *t == tag) {
+                    Some((_, count)) => *count += 1,
+                    None             => counts.push((tag, 1)),
+                }
+            }
+        }
+        counts.sort_by_key(|&(_, count)| Reverse(count));
+        counts
+    }
+}
+
+impl Default for Tagger {
+    fn default() -> Self {
+        let rules = DEFAULT_RULES
+            .iter()
+            .map(|&(tag, [l0, l1], [c0, c1], hue)| {
+                let rule = TagRule::new(tag)
+                    .with_lightness(l0, l1)
+                    .with_chroma(c0, c1);
+                match hue {
+                    Some([start, end]) => rule.with_hue(start, end),
+                    None               => rule,
+                }
+            })
+            .collect();
+        Tagger { rules }
+    }
+}
@@ -12,9 +12,36 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Local imports.
+use crate::analysis::detect_background;
+use crate::analysis::legibility;
+use crate::analysis::Region;
 use crate::Cmyk;
+use crate::color_map::ColorMap;
+use crate::color_map::Domain;
+use crate::color_map::LookupTable;
+use crate::contrast::AA;
+use crate::gradient::Gradient;
+use crate::gradient::Interpolation;
 use crate::Hsl;
 use crate::Hsv;
+use crate::Lab;
+use crate::Lch;
+use crate::Oklab;
+use crate::Oklch;
+use crate::opaque::ConstantTimeEq;
+use crate::opaque::Redacted;
+use crate::palette::Palette;
+use crate::render::AnsiMode;
+use crate::render::ansi_background;
+use crate::render::ansi_foreground;
+use crate::render::emoji;
+use crate::render::preview;
+use crate::render::preview_palette;
+use crate::palette::io::ColorModel;
+use crate::palette::io::aco;
+use crate::palette::io::act;
+use crate::palette::io::ase;
+use crate::palette::io::hex;
 use crate::Rgb;
 use crate::utility::close;
 
@@ -486,3 +513,4085 @@ fn color_conversions_navy() {
     assert!(close(navy_hsv.saturation(), 1.0, UNIT));
     assert!(close(navy_hsv.value(), 0.5, UNIT));
 }
+
+/// Tests color map domain normalization.
+#[test]
+fn color_map_domain_normalize() {
+    let linear = Domain::linear(0.0, 10.0);
+    assert!(close(linear.normalize(2.5), 0.25, UNIT));
+    assert!(close(linear.normalize(-5.0), 0.0, UNIT));
+    assert!(close(linear.normalize(15.0), 1.0, UNIT));
+
+    let log = Domain::log(1.0, 1000.0);
+    assert!(close(log.normalize(10.0), 1.0 / 3.0, UNIT));
+    assert!(close(log.normalize(0.0), 0.0, UNIT));
+
+    let symlog = Domain::symlog(-1000.0, 1000.0, 1.0);
+    assert!(close(symlog.normalize(0.0), 0.5, UNIT));
+    assert!(close(
+        symlog.normalize(-10.0),
+        1.0 - symlog.normalize(10.0),
+        UNIT));
+}
+
+/// Tests color map lookup.
+#[test]
+fn color_map_lookup() {
+    let colors = vec![
+        Rgb::new(0, 0, 0),
+        Rgb::new(127, 127, 127),
+        Rgb::new(255, 255, 255),
+    ];
+    let table = LookupTable::new(colors.clone());
+    assert_eq!(table.map(0.0), colors[0]);
+    assert_eq!(table.map(0.3), colors[1]);
+    assert_eq!(table.map(0.8), colors[2]);
+
+    let gradient = Gradient::from_colors(colors);
+    assert_eq!(gradient.map(0.25), Rgb::new(63, 63, 63));
+    assert_eq!(gradient.map(1.0), Rgb::new(255, 255, 255));
+}
+
+/// Tests background detection on a busy border.
+#[test]
+fn analysis_detect_background_busy() {
+    let black = Rgb::new(0, 0, 0);
+    let white = Rgb::new(255, 255, 255);
+    let pixels = vec![
+        black, white, black, white,
+        white, black, white, black,
+    ];
+
+    let background = detect_background(&pixels, 4, 8.0).unwrap();
+    assert!(!background.is_uniform());
+    let matte = background.matte;
+    assert!(matte.r == matte.g && matte.g == matte.b);
+    assert!(matte.r >= 127 && matte.r <= 128);
+
+    assert_eq!(detect_background(&[], 4, 8.0), None);
+}
+
+/// Tests gradient step iteration.
+#[test]
+fn gradient_steps() {
+    let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(100, 100, 100));
+
+    assert_eq!(gradient.steps(0).count(), 0);
+    assert_eq!(gradient.quantize(1), vec![Rgb::new(0, 0, 0)]);
+
+    let steps = gradient.steps(11);
+    assert_eq!(steps.len(), 11);
+
+    let forward: Vec<Rgb> = gradient.steps(11).collect();
+    let mut backward: Vec<Rgb> = gradient.steps(11).rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(forward[10], Rgb::new(100, 100, 100));
+}
+
+/// Tests legibility analysis over a region which is already legible.
+#[test]
+fn analysis_legibility_without_scrim() {
+    let dark = Rgb::new(10, 10, 10);
+    let pixels = vec![dark; 16];
+    let region = Region { x: 1, y: 1, width: 10, height: 10 };
+
+    let result = legibility(&pixels, 4, region, Rgb::new(255, 255, 255), AA)
+        .unwrap();
+    assert!(result.is_legible());
+    assert_eq!(result.worst_color, dark);
+    assert!(close(result.worst_contrast, result.mean_contrast, UNIT));
+
+    let empty = Region { x: 4, y: 0, width: 2, height: 2 };
+    assert_eq!(legibility(&pixels, 4, empty, dark, AA), None);
+}
+
+/// Tests that Catmull-Rom gradients pass through every stop.
+#[test]
+fn gradient_catmull_rom_through_stops() {
+    let colors = vec![
+        Hsv::new(10.0, 0.2, 0.9),
+        Hsv::new(60.0, 0.5, 0.7),
+        Hsv::new(90.0, 0.9, 0.4),
+        Hsv::new(200.0, 0.6, 0.6),
+        Hsv::new(250.0, 0.3, 0.8),
+    ];
+    let gradient = Gradient::from_colors(colors.clone())
+        .with_interpolation(Interpolation::CatmullRom);
+
+    for (i, color) in colors.iter().enumerate() {
+        let sample = gradient.color(i as f32 / 4.0);
+        assert!(close(sample.hue(), color.hue(), UNIT));
+        assert!(close(sample.saturation(), color.saturation(), UNIT));
+        assert!(close(sample.value(), color.value(), UNIT));
+    }
+}
+
+/// Tests that cyclic gradients are continuous across the wrap point.
+#[test]
+fn gradient_cyclic_wraps() {
+    let gradient = Gradient::cyclic_from_colors(vec![
+        Hsl::new(330.0, 0.8, 0.5),
+        Hsl::new(90.0, 0.8, 0.5),
+        Hsl::new(210.0, 0.8, 0.5),
+    ]).with_interpolation(Interpolation::CatmullRom);
+
+    let before = gradient.color(0.999);
+    let after = gradient.color(1.001);
+    assert!(close(before.hue(), 330.0, 1.0));
+    assert!(close(after.hue(), 330.0, 1.0));
+    assert!(close(gradient.color(-0.25).hue(), gradient.color(0.75).hue(), UNIT));
+
+    let steps = gradient.quantize(3);
+    assert_eq!(steps.len(), 3);
+    assert!(close(steps[2].hue(), 210.0, UNIT));
+}
+
+/// Tests that a NaN gradient position gives the color at position 0.
+#[test]
+fn gradient_nan_position() {
+    let linear = Gradient::new(Rgb::new(0, 100, 0), Rgb::new(200, 100, 0));
+    assert_eq!(linear.color(f32::NAN), Rgb::new(0, 100, 0));
+
+    let cyclic = Gradient::cyclic_from_colors(vec![
+        Hsl::new(330.0, 0.8, 0.5),
+        Hsl::new(90.0, 0.8, 0.5),
+    ]).with_interpolation(Interpolation::CatmullRom);
+    assert_eq!(cyclic.color(f32::NAN), cyclic.color(0.0));
+}
+
+/// Tests compatibility deserialization of alternate representations.
+#[test]
+#[cfg(feature = "serde")]
+fn compat_deserialize_representations() {
+    use crate::compat::Compat;
+
+    let hsv: Compat<Hsv> = serde_json::from_str(
+        r#"{"hue": -60.0, "saturation": 0.5, "value": 1.0}"#).unwrap();
+    assert_eq!(hsv.0, Hsv::new(300.0, 0.5, 1.0));
+
+    let cmyk: Compat<Cmyk> = serde_json::from_str("[0, 255, 64, 10]").unwrap();
+    assert_eq!(cmyk.0, Cmyk::new(0, 255, 64, 10));
+
+    let rgb: Compat<Rgb> = serde_json::from_str("1193046").unwrap();
+    assert_eq!(rgb.0, Rgb::from(0x123456));
+
+    let current = serde_json::to_string(&Rgb::new(1, 2, 3)).unwrap();
+    let rgb: Compat<Rgb> = serde_json::from_str(&current).unwrap();
+    assert_eq!(rgb.0, Rgb::new(1, 2, 3));
+
+    let white = crate::Xyz::from([0.9505, 1.0, 1.089]);
+    let current = serde_json::to_string(&white).unwrap();
+    let xyz: Compat<crate::Xyz> = serde_json::from_str(&current).unwrap();
+    assert_eq!(xyz.0, white);
+
+    assert!(serde_json::from_str::<Compat<Rgb>>("[256, 0, 0]").is_err());
+    assert!(serde_json::from_str::<Compat<Rgb>>("[1.5, 0, 0]").is_err());
+    assert!(serde_json::from_str::<Compat<Rgb>>(r##""#12345""##).is_err());
+}
+
+/// Tests lenient deserialization of messy inputs.
+#[test]
+#[cfg(feature = "serde")]
+fn compat_lenient_deserialize() {
+    use crate::compat::Adjustment;
+    use crate::compat::Lenient;
+
+    let rgb: Lenient<Rgb> = serde_json::from_str(
+        r#"{"red": "100%", "green": 0.5, "blue": 300}"#).unwrap();
+    assert_eq!(rgb.value, Rgb::new(255, 128, 255));
+    assert_eq!(rgb.warnings.len(), 1);
+    assert_eq!(rgb.warnings[0].field, "b");
+    assert_eq!(rgb.warnings[0].adjustment,
+        Adjustment::Clamped { original: 300.0 });
+
+    let hsv: Lenient<Hsv> = serde_json::from_str(r#"[-90, 50, "25 %"]"#)
+        .unwrap();
+    assert_eq!(hsv.value, Hsv::new(270.0, 0.5, 0.25));
+    assert_eq!(hsv.warnings.len(), 2);
+
+    let rgb: Lenient<Rgb> = serde_json::from_str(r#"" ff8000 ""#).unwrap();
+    assert_eq!(rgb.value, Rgb::new(255, 128, 0));
+    assert!(rgb.is_exact());
+
+    let xyz: Lenient<crate::Xyz> = serde_json::from_str(
+        r#"{"X": 95.047, "Y": 100, "Z": 108.883}"#).unwrap();
+    assert_eq!(xyz.value, crate::Xyz::from([95.047, 100.0, 108.883]));
+    assert!(xyz.is_exact());
+
+    assert!(serde_json::from_str::<Lenient<crate::Xyz>>(r#"["50%", 1, 1]"#)
+        .is_err());
+    assert!(serde_json::from_str::<Lenient<Rgb>>(r#"["red", 0, 0]"#).is_err());
+    assert!(serde_json::from_str::<Lenient<Hsl>>(r#"[0, "1rad", 0]"#).is_err());
+}
+
+/// Tests Lab conversions and CIEDE2000 color differences.
+#[test]
+fn lab_conversions_and_delta_e() {
+    let lab = Lab::from(Rgb::new(255, 0, 0));
+    assert!(close(lab.l, 53.24, 0.01));
+    assert!(close(lab.a, 80.09, 0.01));
+    assert!(close(lab.b, 67.20, 0.01));
+
+    for &rgb in &[Rgb::new(0, 0, 0), Rgb::new(12, 200, 99), Rgb::new(255, 255, 255)] {
+        assert_eq!(Rgb::from(Lab::from(rgb)), rgb);
+    }
+
+    // Reference pairs from Sharma, Wu, and Dalal (2005).
+    let de = Lab::delta_e(
+        Lab::new(50.0, 2.6772, -79.7751),
+        Lab::new(50.0, 0.0, -82.7485));
+    assert!(close(de, 2.0425, 0.0001));
+    let de = Lab::delta_e(
+        Lab::new(50.0, 2.5, 0.0),
+        Lab::new(73.0, 25.0, -18.0));
+    assert!(close(de, 27.1492, 0.0001));
+}
+
+/// Tests palette insertion order, lookup, and ΔE deduplication.
+#[test]
+fn palette_insert_and_dedup() {
+    let mut palette = Palette::new();
+    assert_eq!(palette.insert("a", Rgb::new(10, 10, 10)), None);
+    assert_eq!(palette.insert("b", Rgb::new(200, 0, 0)), None);
+    assert_eq!(palette.insert("c", Rgb::new(11, 10, 10)), None);
+    assert_eq!(palette.insert("a", Rgb::new(12, 10, 10)),
+        Some(Rgb::new(10, 10, 10)));
+    assert_eq!(palette.position("a"), Some(0));
+
+    palette.dedup(1.0);
+    let names: Vec<&str> = palette.iter().map(|s| &s.name[..]).collect();
+    assert_eq!(names, ["a", "b"]);
+
+    assert_eq!(palette.remove("a"), Some(Rgb::new(12, 10, 10)));
+    assert_eq!(palette.get("a"), None);
+    assert_eq!(palette.len(), 1);
+}
+
+/// Tests palette serialization round trips and merges duplicate names.
+#[test]
+#[cfg(feature = "serde")]
+fn palette_serde() {
+    let palette: Palette = vec![
+        ("ink", Rgb::new(1, 2, 3)),
+        ("paper", Rgb::new(250, 250, 250)),
+    ].into_iter().collect();
+
+    let json = serde_json::to_string(&palette).unwrap();
+    assert_eq!(serde_json::from_str::<Palette>(&json).unwrap(), palette);
+
+    let duplicated: Palette = serde_json::from_str(r#"[
+        {"name": "ink", "color": {"r": 1, "g": 2, "b": 3}},
+        {"name": "ink", "color": {"r": 4, "g": 5, "b": 6}}
+    ]"#).unwrap();
+    assert_eq!(duplicated.len(), 1);
+    assert_eq!(duplicated.get("ink"), Some(Rgb::new(4, 5, 6)));
+}
+
+/// Tests constant-time comparison and redacted formatting.
+#[test]
+fn opaque_constant_time_eq_and_redacted() {
+    let a = [Rgb::new(1, 2, 3), Rgb::new(4, 5, 6)];
+    let b = [Rgb::new(1, 2, 3), Rgb::new(4, 5, 7)];
+    assert!(a[..].ct_eq(&a[..]));
+    assert!(!a[..].ct_eq(&b[..]));
+    assert!(!a[..].ct_eq(&a[..1]));
+
+    assert!(Hsl::new(120.0, 0.5, 0.5).ct_eq(&Hsl::new(120.0, 0.5, 0.5)));
+    assert!(!Lab::new(0.0, 0.0, 0.0).ct_eq(&Lab::new(-0.0, 0.0, 0.0)));
+
+    let secret = Redacted::new(Hsv::new(10.0, 0.2, 0.3));
+    assert_eq!(format!("{} {:?}", secret, secret), "<redacted> Redacted(<redacted>)");
+    assert_ne!(secret, Redacted::from(Hsv::new(10.0, 0.2, 0.4)));
+}
+
+/// Tests Adobe Swatch Exchange round trips and group handling.
+#[test]
+fn palette_io_ase() {
+    let palette: Palette = vec![
+        ("Ink", Rgb::new(20, 24, 40)),
+        ("Tomato", Rgb::new(255, 99, 71)),
+    ].into_iter().collect();
+
+    for &model in &[ColorModel::Rgb, ColorModel::Cmyk, ColorModel::Lab] {
+        let mut data = Vec::new();
+        ase::write(&palette, model, &mut data).unwrap();
+        let read = ase::read(&data[..]).unwrap();
+        assert_eq!(read.len(), 2);
+        for (a, b) in read.iter().zip(palette.iter()) {
+            assert_eq!(a.name, b.name);
+            assert!(Rgb::distance(a.color, b.color) <= 2.0);
+        }
+    }
+
+    // A group containing one unnamed grayscale swatch.
+    let mut data = b"ASEF\x00\x01\x00\x00\x00\x00\x00\x03".to_vec();
+    data.extend_from_slice(b"\xC0\x01\x00\x00\x00\x06\x00\x02\x00G\x00\x00");
+    data.extend_from_slice(b"\x00\x01\x00\x00\x00\x0E\x00\x01\x00\x00Gray");
+    data.extend_from_slice(&0.5f32.to_be_bytes());
+    data.extend_from_slice(b"\x00\x02");
+    data.extend_from_slice(b"\xC0\x02\x00\x00\x00\x00");
+    let read = ase::read(&data[..]).unwrap();
+    assert_eq!(read.get("#808080"), Some(Rgb::new(128, 128, 128)));
+
+    assert!(ase::read(&b"8BPS"[..]).is_err());
+}
+
+/// Tests Photoshop color swatch round trips and version 1 files.
+#[test]
+fn palette_io_aco() {
+    let palette: Palette = vec![
+        ("Ink", Rgb::new(20, 24, 40)),
+        ("Tomato", Rgb::new(255, 99, 71)),
+    ].into_iter().collect();
+
+    for &model in &[ColorModel::Rgb, ColorModel::Cmyk, ColorModel::Lab] {
+        let mut data = Vec::new();
+        aco::write(&palette, model, &mut data).unwrap();
+        let read = aco::read(&data[..]).unwrap();
+        assert_eq!(read.len(), 2);
+        for (a, b) in read.iter().zip(palette.iter()) {
+            assert_eq!(a.name, b.name);
+            assert!(Rgb::distance(a.color, b.color) <= 2.0);
+        }
+    }
+
+    // A version 1 file with an RGB and an HSB swatch.
+    let data = [
+        0, 1, 0, 2,
+        0, 0, 0xFF, 0xFF, 0x80, 0x80, 0, 0, 0, 0,
+        0, 1, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0,
+    ];
+    let read = aco::read(&data[..]).unwrap();
+    let colors: Vec<Rgb> = read.colors().collect();
+    assert_eq!(colors, [Rgb::new(255, 128, 0), Rgb::new(255, 0, 0)]);
+}
+
+/// Tests Oklab conversions against reference values.
+#[test]
+fn oklab_conversions() {
+    let white = Oklab::from(Rgb::new(255, 255, 255));
+    assert!(close(white.l, 1.0, 0.001));
+    assert!(close(white.a, 0.0, 0.001));
+    assert!(close(white.b, 0.0, 0.001));
+
+    let red = Oklab::from(Rgb::new(255, 0, 0));
+    assert!(close(red.l, 0.6280, 0.001));
+    assert!(close(red.a, 0.2249, 0.001));
+    assert!(close(red.b, 0.1258, 0.001));
+
+    for &rgb in &[Rgb::new(0, 0, 0), Rgb::new(12, 200, 99), Rgb::new(255, 0, 255)] {
+        assert_eq!(Rgb::from(Oklab::from(rgb)), rgb);
+    }
+}
+
+/// Tests basic color term lookup across locales.
+#[test]
+#[cfg(feature = "naming")]
+fn naming_basic_color_terms() {
+    use crate::naming::BasicColor;
+    use crate::naming::Locale;
+    use crate::naming::describe;
+
+    for &term in &BasicColor::ALL {
+        assert_eq!(BasicColor::nearest(term.prototype()), term);
+    }
+
+    assert_eq!(describe(Rgb::new(255, 0, 0), Locale::Spanish), "rojo");
+    assert_eq!(describe(Rgb::new(255, 192, 203), Locale::English), "pink");
+    assert_eq!(describe(Rgb::new(128, 0, 128), Locale::Italian), "viola");
+    assert_eq!(describe(Rgb::new(250, 250, 250), Locale::Russian), "белый");
+    assert_eq!(describe(Rgb::new(0, 128, 0), Locale::Chinese), "绿色");
+    assert_eq!(Locale::from_tag("PT-br"), Some(Locale::Portuguese));
+}
+
+/// Tests Adobe color table and hex list palette formats.
+#[test]
+fn palette_io_act_and_hex() {
+    let palette: Palette = vec![
+        ("Ink", Rgb::new(20, 24, 40)),
+        ("Tomato", Rgb::new(255, 99, 71)),
+    ].into_iter().collect();
+
+    // A bare 768-byte table holds 256 colors; the black padding is merged.
+    let mut data = Vec::new();
+    act::write(&palette, &mut data).unwrap();
+    let read = act::read(&data[..768]).unwrap();
+    assert_eq!(read.len(), 3);
+    assert_eq!(read.get("#ff6347"), Some(Rgb::new(255, 99, 71)));
+    assert!(act::read(&data[..700]).is_err());
+
+    let mut text = Vec::new();
+    hex::write(&palette, &mut text).unwrap();
+    assert_eq!(text, b"141828\nff6347\n");
+    let colors: Vec<Rgb> = hex::read(&text[..]).unwrap().colors().collect();
+    assert_eq!(colors, [Rgb::new(20, 24, 40), Rgb::new(255, 99, 71)]);
+
+    assert!(hex::read(&b"+12345\n"[..]).is_err());
+    assert!(hex::read(&b"fff\n"[..]).is_err());
+}
+
+/// Tests emoji and ANSI swatch rendering.
+#[test]
+fn render_emoji_and_ansi() {
+    assert_eq!(emoji(Rgb::new(250, 250, 250)), "⬜");
+    assert_eq!(emoji(Rgb::new(0, 160, 0)), "🟩");
+    assert_eq!(emoji(Rgb::new(120, 60, 30)), "🟫");
+    assert_eq!(emoji(Rgb::new(128, 0, 128)), "🟪");
+
+    assert_eq!(ansi_foreground(Rgb::new(255, 0, 0), AnsiMode::Ansi256), "\x1b[38;5;196m");
+    assert_eq!(ansi_foreground(Rgb::new(128, 128, 128), AnsiMode::Ansi256), "\x1b[38;5;244m");
+    assert_eq!(ansi_background(Rgb::new(255, 255, 255), AnsiMode::Ansi16), "\x1b[107m");
+    assert_eq!(ansi_background(Rgb::new(0, 0, 10), AnsiMode::Ansi16), "\x1b[40m");
+}
+
+/// Tests half block previews of color maps and palettes.
+#[test]
+fn render_previews() {
+    let map = LookupTable::new(vec![Rgb::new(9, 9, 9)]);
+    assert_eq!(preview(&map, 3, AnsiMode::TrueColor),
+        "\x1b[38;2;9;9;9m\x1b[48;2;9;9;9m▌▌▌\x1b[0m");
+    assert_eq!(preview(&map, 0, AnsiMode::TrueColor), "");
+
+    let palette: Palette = vec![("ink", Rgb::new(1, 2, 3))].into_iter().collect();
+    assert_eq!(preview_palette(&palette, 1, AnsiMode::Ansi256),
+        "\x1b[38;5;232m\x1b[48;5;232m▌\x1b[0m");
+    assert_eq!(preview_palette(&Palette::new(), 4, AnsiMode::TrueColor), "");
+}
+
+/// Tests the JSON palette format.
+#[test]
+#[cfg(feature = "json")]
+fn palette_json() {
+    let mut palette: Palette = vec![
+        ("ink", Rgb::new(20, 24, 40)),
+        ("paper", Rgb::new(250, 245, 235)),
+    ].into_iter().collect();
+    palette.set_name(Some("Notebook"));
+    palette.set_author(Some("A. Person"));
+
+    let read = Palette::from_json(&palette.to_json()).unwrap();
+    assert_eq!(read, palette);
+    assert_eq!(read.author(), Some("A. Person"));
+
+    let read = Palette::from_json(r#"{
+        "version": 1,
+        "swatches": [
+            {"name": "a", "rgb": [1, 2, 3]},
+            {"name": "b", "hsl": [0.0, 1.0, 0.5]},
+            {"name": "c", "oklab": [1.0, 0.0, 0.0]}
+        ]
+    }"#).unwrap();
+    let colors: Vec<Rgb> = read.colors().collect();
+    assert_eq!(colors, [
+        Rgb::new(1, 2, 3),
+        Rgb::new(255, 0, 0),
+        Rgb::new(255, 255, 255),
+    ]);
+    assert_eq!(read.name(), None);
+
+    assert!(Palette::from_json(r#"{"version": 2, "swatches": []}"#).is_err());
+    assert!(Palette::from_json(
+        r#"{"version": 1, "swatches": [{"name": "x"}]}"#).is_err());
+}
+
+/// Tests short code round trips, aliases, and error detection.
+#[test]
+fn short_code_round_trip_and_errors() {
+    use crate::short_code::ShortCodeError;
+    use crate::short_code::decode;
+    use crate::short_code::decode_color;
+    use crate::short_code::encode;
+    use crate::short_code::encode_color;
+
+    assert_eq!(encode_color(Rgb::new(255, 255, 255)), "ZZZZ-Y3R");
+    assert_eq!(decode_color("zzzz y3r"), Ok(Rgb::new(255, 255, 255)));
+
+    let colors: Vec<Rgb> = (0..32u8)
+        .map(|i| Rgb::new(i, i.wrapping_mul(37), 255 - i))
+        .collect();
+    assert_eq!(decode(&encode(colors.iter().copied())), Ok(colors));
+
+    // Crockford aliases for 0 and 1.
+    let code = encode_color(Rgb::new(0, 0, 0));
+    let aliased = code.replace('0', "o").replace('1', "l");
+    assert_eq!(decode_color(&aliased), Ok(Rgb::new(0, 0, 0)));
+
+    assert_eq!(decode_color("ZZZY-Y3R"), Err(ShortCodeError::ChecksumMismatch));
+    assert_eq!(decode_color("ZZZZ-Y3"), Err(ShortCodeError::InvalidLength));
+    assert_eq!(decode_color("ZZZZ-Y3U"), Err(ShortCodeError::InvalidCharacter('U')));
+    assert_eq!(decode_color("ZXHM-E50R-51S0"), Err(ShortCodeError::InvalidLength));
+}
+
+/// Tests median cut palette extraction.
+#[test]
+fn quantize_median_cut() {
+    use crate::quantize::median_cut;
+
+    let clusters = [
+        Rgb::new(250, 0, 0),
+        Rgb::new(0, 250, 0),
+        Rgb::new(0, 0, 250),
+        Rgb::new(250, 250, 250),
+    ];
+    let pixels: Vec<Rgb> = clusters
+        .iter()
+        .flat_map(|&c| std::iter::repeat_n(c, 16))
+        .collect();
+
+    let palette = median_cut(&pixels, 4);
+    assert_eq!(palette.len(), 4);
+    assert!(clusters.iter().all(|&c| palette.colors().any(|p| p == c)));
+    assert_eq!(median_cut(&pixels, 1).colors().collect::<Vec<_>>(),
+        [Rgb::new(125, 125, 125)]);
+
+    // Never more colors than there are distinct pixels.
+    let flat = vec![Rgb::new(1, 2, 3); 10];
+    let palette = median_cut(&flat, 8);
+    assert_eq!(palette.colors().collect::<Vec<_>>(), [Rgb::new(1, 2, 3)]);
+
+    assert!(median_cut(&[], 4).is_empty());
+    assert!(median_cut(&pixels, 0).is_empty());
+}
+
+/// Tests LCh conversions and polar versus rectangular interpolation.
+#[test]
+fn lch_interpolation_forms() {
+    use crate::gradient::Interpolate;
+    use crate::gradient::InterpolationForm;
+
+    for &rgb in &[Rgb::new(255, 0, 0), Rgb::new(30, 140, 200), Rgb::new(9, 9, 9)] {
+        assert_eq!(Rgb::from(Lch::from(rgb)), rgb);
+        assert_eq!(Rgb::from(Oklch::from(rgb)), rgb);
+    }
+    let lch = Lch::from(Lab::new(50.0, 0.0, -40.0));
+    assert!(close(lch.c, 40.0, 0.001));
+    assert!(close(lch.h, 270.0, 0.001));
+
+    // Polar interpolation follows the shorter arc, keeping chroma.
+    let start = Oklch::new(0.7, 0.1, 350.0);
+    let end = Oklch::new(0.7, 0.1, 30.0);
+    let mid = Oklch::interpolate_in(start, end, 0.5, InterpolationForm::Polar);
+    assert!(close(mid.h, 10.0, 0.001));
+    assert!(close(mid.c, 0.1, 0.001));
+
+    // Rectangular interpolation matches Oklab interpolation.
+    let mid = Oklch::interpolate_in(start, end, 0.5, InterpolationForm::Rectangular);
+    let lab = Oklab::linear_interpolate(Oklab::from(start), Oklab::from(end), 0.5);
+    assert!(close(mid.c, lab.chroma(), 0.0001));
+    assert!(close(mid.h, 10.0, 0.001));
+
+    // The form is ignored for spaces without chroma and hue.
+    let a = Rgb::new(0, 0, 0);
+    let b = Rgb::new(200, 100, 0);
+    assert_eq!(
+        Rgb::interpolate_in(a, b, 0.5, InterpolationForm::Rectangular),
+        Rgb::interpolate(a, b, 0.5));
+
+    let gradient = Gradient::from_colors(vec![
+            Lch::new(60.0, 50.0, 0.0),
+            Lch::new(60.0, 1.0, 90.0),
+            Lch::new(60.0, 50.0, 180.0),
+        ])
+        .with_interpolation(Interpolation::CatmullRom)
+        .with_form(InterpolationForm::Rectangular);
+    assert_eq!(gradient.form(), InterpolationForm::Rectangular);
+    assert!(gradient.color(0.5).c < 1.001);
+    assert!(close(gradient.color(0.0).h, 0.0, 0.01));
+}
+
+/// Tests octree palette extraction.
+#[test]
+fn quantize_octree() {
+    use crate::quantize::octree;
+
+    let pixels: Vec<Rgb> = (0..4096u32)
+        .map(|i| Rgb::new((i * 7) as u8, (i * 13) as u8, (i / 16) as u8))
+        .collect();
+    for &n in &[1, 8, 64] {
+        let palette = octree(&pixels, n);
+        assert!(palette.len() <= n);
+        assert!(!palette.is_empty());
+    }
+    assert_eq!(octree(&pixels, 1).len(), 1);
+
+    let flat = vec![Rgb::new(1, 2, 3); 10];
+    assert_eq!(octree(&flat, 8).colors().collect::<Vec<_>>(), [Rgb::new(1, 2, 3)]);
+
+    assert!(octree(&[], 4).is_empty());
+    assert!(octree(&pixels, 0).is_empty());
+}
+
+/// Tests k-means palette extraction.
+#[test]
+fn quantize_k_means() {
+    use crate::quantize::ClusterSpace;
+    use crate::quantize::KMeans;
+    use crate::quantize::Seeding;
+    use crate::quantize::k_means;
+
+    let clusters = [
+        Rgb::new(220, 40, 40),
+        Rgb::new(40, 180, 60),
+        Rgb::new(50, 60, 210),
+    ];
+    let pixels: Vec<Rgb> = clusters
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &c)| (0..(10 - i as u8 * 3)).map(move |d| {
+            Rgb::new(c.r.saturating_add(d), c.g, c.b.saturating_sub(d))
+        }))
+        .collect();
+
+    for &space in &[ClusterSpace::Lab, ClusterSpace::Oklab] {
+        for &seeding in &[Seeding::Uniform, Seeding::KMeansPlusPlus { seed: 7 }] {
+            let palette = KMeans::new(3)
+                .with_space(space)
+                .with_seeding(seeding)
+                .extract(&pixels);
+            assert_eq!(palette.len(), 3);
+            for (color, expected) in palette.colors().zip(&clusters) {
+                assert!(Oklab::distance(color, *expected) < 0.03);
+            }
+        }
+    }
+
+    // The same seed gives the same palette.
+    let seeded = KMeans::new(2).with_seeding(Seeding::KMeansPlusPlus { seed: 3 });
+    assert_eq!(seeded.extract(&pixels), seeded.extract(&pixels));
+
+    assert_eq!(k_means(&[Rgb::new(1, 2, 3); 4], 3).len(), 1);
+    assert!(k_means(&[], 3).is_empty());
+}
+
+/// Tests that monotone cubic gradients never overshoot their stops.
+#[test]
+fn gradient_monotone_cubic() {
+    use crate::utility::cerp_f32;
+    use crate::utility::monotone_cerp_f32;
+
+    assert!(cerp_f32(0.0, 1.0, 6.0, 0.0, 0.5) > 1.0);
+    assert!(close(monotone_cerp_f32(0.0, 1.0, 0.0, 0.0, 0.5), 0.5, 0.0001));
+    assert_eq!(monotone_cerp_f32(2.0, 2.0, 5.0, -5.0, 0.5), 2.0);
+
+    let stops = vec![
+        Rgb::new(0, 0, 0),
+        Rgb::new(10, 10, 10),
+        Rgb::new(240, 240, 240),
+        Rgb::new(250, 250, 250),
+    ];
+    let catmull_rom = Gradient::from_colors(stops.clone())
+        .with_interpolation(Interpolation::CatmullRom);
+    let monotone = Gradient::from_colors(stops)
+        .with_interpolation(Interpolation::MonotoneCubic);
+
+    let overshoots = (0..=300)
+        .map(|i| catmull_rom.color(i as f32 / 300.0).r)
+        .collect::<Vec<_>>()
+        .windows(2)
+        .any(|pair| pair[1] < pair[0]);
+    assert!(overshoots);
+
+    let samples: Vec<u8> = (0..=300)
+        .map(|i| monotone.color(i as f32 / 300.0).r)
+        .collect();
+    assert!(samples.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(monotone.color(1.0 / 3.0), Rgb::new(10, 10, 10));
+}
+
+/// Tests slope estimation for cubic curves.
+#[test]
+fn utility_slope_estimation() {
+    use crate::utility::cardinal_slopes;
+    use crate::utility::cubic_curve_f32;
+    use crate::utility::finite_difference_slopes;
+
+    assert!(finite_difference_slopes(&[]).is_empty());
+    assert_eq!(finite_difference_slopes(&[3.0]), [0.0]);
+    assert_eq!(cardinal_slopes(&[3.0], 0.0), [0.0]);
+    assert_eq!(cardinal_slopes(&[0.0, 2.0, 1.0], 1.0), [0.0, 0.0, 0.0]);
+
+    // A line is reproduced exactly by finite difference slopes.
+    let line = [1.0, 3.0, 5.0, 7.0, 9.0];
+    let slopes = finite_difference_slopes(&line);
+    for i in 0..=20 {
+        let amount = i as f32 / 20.0;
+        assert!(close(cubic_curve_f32(&line, &slopes, amount), 1.0 + 8.0 * amount, 0.0001));
+    }
+
+    // The curve passes through every value.
+    let values = [0.2, 0.9, 0.1, 0.6];
+    let slopes = cardinal_slopes(&values, 0.3);
+    for (i, &value) in values.iter().enumerate() {
+        assert!(close(cubic_curve_f32(&values, &slopes, i as f32 / 3.0), value, 0.0001));
+    }
+}
+
+/// Tests the public math helpers.
+#[test]
+fn math_remap_and_steps() {
+    use crate::math::inverse_lerp;
+    use crate::math::lerp;
+    use crate::math::nearly_equal;
+    use crate::math::remap;
+    use crate::math::smootherstep;
+    use crate::math::smoothstep;
+
+    assert!(nearly_equal(1.0, 1.0 + f32::EPSILON, 1e-6));
+    assert!(!nearly_equal(1.0, 1.001, 1e-6));
+    assert!(nearly_equal(f32::INFINITY, f32::INFINITY, 0.0));
+
+    for &value in &[0.0, 0.2, 0.5, 0.9, 1.0] {
+        assert!(close(inverse_lerp(3.0, 7.0, lerp(3.0, 7.0, value)), value, 1e-6));
+    }
+    assert_eq!(inverse_lerp(2.0, 2.0, 5.0), 0.0);
+    assert_eq!(remap(15.0, 10.0, 20.0, 100.0, 200.0), 150.0);
+    assert_eq!(remap(25.0, 10.0, 20.0, 100.0, 200.0), 200.0);
+
+    assert_eq!(smoothstep(2.0, 4.0, 3.0), 0.5);
+    assert_eq!(smoothstep(4.0, 2.0, 3.5), 0.15625);
+    assert_eq!(smootherstep(0.0, 1.0, 1.5), 1.0);
+    assert!(smootherstep(0.0, 1.0, 0.1) < smoothstep(0.0, 1.0, 0.1));
+}
+
+/// Tests snapping colors to the web-safe palette.
+#[test]
+fn palette_web_safe_nearest_in() {
+    use crate::metric::Euclidean;
+    use crate::palette::nearest_in;
+
+    let palette = Palette::web_safe();
+    assert_eq!(palette.len(), 216);
+    assert!(palette.colors().all(|c| c.octets().iter().all(|v| v % 0x33 == 0)));
+
+    let euclidean = |a: Rgb, b: Rgb| {
+        a.octets().iter().zip(b.octets().iter())
+            .map(|(&x, &y)| (x as f32 - y as f32).powi(2))
+            .sum::<f32>()
+    };
+    assert_eq!(nearest_in(Rgb::new(20, 40, 230), &palette, euclidean),
+        Some((11, Rgb::new(0, 51, 255))));
+    assert_eq!(nearest_in(Rgb::new(0, 51, 255), &palette, Euclidean::Oklab),
+        Some((11, Rgb::new(0, 51, 255))));
+
+    // Ties go to the first swatch.
+    let ties: Palette = vec![("a", Rgb::new(0, 0, 0)), ("b", Rgb::new(0, 0, 0))]
+        .into_iter()
+        .collect();
+    assert_eq!(nearest_in(Rgb::new(9, 9, 9), &ties, euclidean).map(|n| n.0), Some(0));
+    assert_eq!(nearest_in(Rgb::new(9, 9, 9), &Palette::new(), euclidean), None);
+}
+
+/// Tests hue angle utilities.
+#[test]
+fn angle_wrap_difference_mean_lerp() {
+    use crate::angle::Arc;
+    use crate::angle::difference;
+    use crate::angle::lerp;
+    use crate::angle::mean;
+    use crate::angle::wrap;
+
+    assert_eq!(wrap(-1e-9), 0.0);
+    assert_eq!(wrap(720.0 + 45.0), 45.0);
+    assert_eq!(difference(180.0, 0.0), 180.0);
+    assert_eq!(difference(90.0, 300.0), -150.0);
+
+    assert!(close(mean(vec![0.0, 90.0]).unwrap(), 45.0, 1e-4));
+    assert!(close(mean(vec![200.0]).unwrap(), 200.0, 1e-4));
+
+    assert_eq!(lerp(10.0, 50.0, 0.25, Arc::Shorter), 20.0);
+    assert_eq!(lerp(10.0, 50.0, 0.5, Arc::Increasing), 30.0);
+    assert_eq!(lerp(50.0, 10.0, 0.5, Arc::Increasing), 210.0);
+    assert_eq!(lerp(10.0, 50.0, 0.5, Arc::Longer), 210.0);
+    assert_eq!(lerp(50.0, 50.0, 0.5, Arc::Longer), 50.0);
+    assert_eq!(lerp(50.0, 50.0, 0.5, Arc::Decreasing), 50.0);
+    assert_eq!(lerp(10.0, 50.0, 2.0, Arc::Shorter), 50.0);
+
+    // Color types wrap their hues with the same rules.
+    let mut hsl = Hsl::new(0.0, 0.5, 0.5);
+    hsl.set_hue(-30.0);
+    assert_eq!(hsl.h, 330.0);
+    assert_eq!(Lch::new(50.0, 20.0, 400.0).h, 40.0);
+}
+
+/// Tests Material tonal palettes and schemes.
+#[test]
+fn material_tonal_palettes() {
+    use crate::material::CorePalettes;
+    use crate::material::Scheme;
+    use crate::material::TonalPalette;
+
+    let seed = Rgb::new(0, 120, 200);
+    let palettes = CorePalettes::from_seed(seed);
+    assert!(close(palettes.tertiary.hue(),
+        crate::angle::wrap(Lch::from(seed).h + 60.0), 0.001));
+
+    // Every tone has the requested lightness, even for very saturated hues.
+    for palette in &[palettes.primary, palettes.error, TonalPalette::new(140.0, 200.0)] {
+        for (&tone, color) in TonalPalette::TONES.iter().zip(palette.tones().iter()) {
+            assert!(close(Lab::from(*color).l, tone as f32, 0.6));
+        }
+    }
+
+    // Neutral tones are nearly gray.
+    let neutral = Lch::from(palettes.neutral.tone(50.0));
+    assert!(neutral.c < 5.0);
+
+    let light = Scheme::light(seed);
+    let dark = Scheme::dark(seed);
+    assert!(Lab::from(light.primary).l < Lab::from(dark.primary).l);
+    assert_eq!(light.surface, palettes.neutral.tone(99.0));
+    assert_eq!(dark.outline, palettes.neutral_variant.tone(60.0));
+    assert_eq!(Scheme::light_from_palettes(&palettes), light);
+}
+
+/// Tests the built-in distance metrics.
+#[test]
+fn metric_builtins() {
+    use crate::metric::DeltaE;
+    use crate::metric::Euclidean;
+    use crate::metric::Metric;
+    use crate::metric::Redmean;
+    use crate::metric::Weighted;
+    use crate::palette::nearest_in;
+    use crate::quantize::palette_indices;
+
+    let a = Rgb::new(200, 60, 40);
+    let b = Rgb::new(190, 80, 60);
+    let metrics: Vec<Box<dyn Metric>> = vec![
+        Box::new(Euclidean::Rgb),
+        Box::new(Euclidean::LinearRgb),
+        Box::new(Euclidean::Lab),
+        Box::new(Euclidean::Oklab),
+        Box::new(Weighted::LUMA),
+        Box::new(Redmean),
+        Box::new(DeltaE::Cie76),
+        Box::new(DeltaE::Cie94),
+        Box::new(DeltaE::Ciede2000),
+    ];
+    for metric in &metrics {
+        assert_eq!(metric.distance(a, a), 0.0);
+        assert!(metric.distance(a, b) > 0.0);
+    }
+    assert_eq!(Euclidean::Lab.distance(a, b), DeltaE::Cie76.distance(a, b));
+    assert_eq!(Rgb::distance_with(a, b, Euclidean::Rgb), Rgb::distance(a, b));
+    assert_eq!(
+        Rgb::distance_with(a, Oklab::from(b), Redmean),
+        Redmean.distance(a, Rgb::from(Oklab::from(b))));
+    assert!(close(Euclidean::LinearRgb.distance(Rgb::new(0, 0, 0), Rgb::new(255, 255, 255)),
+        3f32.sqrt(), 1e-5));
+
+    // CIE94 reduces the weight of chroma differences in saturated colors.
+    let vivid = Lab::new(50.0, 60.0, 0.0);
+    let duller = Lab::new(50.0, 50.0, 0.0);
+    assert!(Lab::delta_e_94(vivid, duller) < Lab::distance(vivid, duller));
+
+    // The metric changes which swatch is nearest.
+    let palette: Palette = vec![
+        ("dark", Rgb::new(40, 40, 40)),
+        ("green", Rgb::new(0, 120, 0)),
+    ].into_iter().collect();
+    let color = Rgb::new(0, 80, 0);
+    assert_eq!(nearest_in(color, &palette, Weighted::new(1.0, 0.0, 1.0)).map(|n| n.0), Some(1));
+    assert_eq!(nearest_in(color, &palette, Weighted::new(0.0, 1.0, 0.0)).map(|n| n.0), Some(0));
+
+    let mut similar: Palette = vec![("a", a), ("b", b)].into_iter().collect();
+    similar.dedup_by(30.0, Euclidean::Rgb);
+    assert_eq!(similar.len(), 1);
+
+    assert_eq!(palette_indices(&[color, Rgb::new(30, 30, 30), color], &palette, DeltaE::Cie94),
+        [1, 0, 1]);
+    assert!(palette_indices(&[color], &Palette::new(), DeltaE::Cie94).is_empty());
+}
+
+/// Tests harmony generation in each hue space.
+#[test]
+fn harmony_schemes() {
+    use crate::harmony::*;
+
+    let red = Rgb::new(255, 0, 0);
+    for space in [HueSpace::Hsv, HueSpace::Oklch, HueSpace::Ryb] {
+        assert_eq!(complementary(red, space)[0], red);
+        assert_eq!(split_complementary(red, space)[0], red);
+        assert_eq!(analogous(red, space)[0], red);
+        assert_eq!(triadic(red, space)[0], red);
+        assert_eq!(tetradic(red, space)[0], red);
+        assert_eq!(monochromatic(red, space)[2], red);
+        assert_eq!(space.rotate(red, 360.0), red);
+    }
+
+    // RYB complements are the traditional painter's pairs.
+    assert_eq!(complementary(red, HueSpace::Ryb)[1], Rgb::new(0, 255, 0));
+    let yellow = Rgb::new(255, 255, 0);
+    let purple = complementary(yellow, HueSpace::Ryb)[1];
+    assert!(Hsv::from(purple).hue() > 270.0);
+
+    // Oklch rotation preserves lightness.
+    let base = Rgb::new(120, 90, 200);
+    let [_, b, c] = triadic(base, HueSpace::Oklch);
+    let l = Oklch::from(base).l;
+    assert!(close(Oklch::from(b).l, l, 0.05));
+    assert!(close(Oklch::from(c).l, l, 0.05));
+
+    // Monochromatic sets are ordered from dark to light.
+    let mono = monochromatic(base, HueSpace::Oklch);
+    for pair in mono.windows(2) {
+        assert!(Oklch::from(pair[0]).l < Oklch::from(pair[1]).l);
+    }
+}
+
+/// Tests that the lossless conversions round trip exactly.
+#[test]
+fn lossless_round_trips() {
+    use crate::lossless::convert;
+    use crate::lossless::narrow;
+    use crate::lossless::widen;
+    use crate::YCoCgR;
+
+    let channels = (0..=255u8).step_by(3).chain(Some(255));
+    for r in channels.clone() {
+        for g in channels.clone() {
+            for b in channels.clone() {
+                let rgb = Rgb::new(r, g, b);
+                let ycocg: YCoCgR = convert(rgb);
+                assert_eq!(convert::<_, Rgb>(ycocg), rgb);
+            }
+        }
+    }
+
+    for octet in 0..=255u8 {
+        assert_eq!(narrow(widen(octet)), Some(octet));
+    }
+    let wide: [u16; 3] = convert(Rgb::new(0, 128, 255));
+    assert_eq!(wide, [0, 0x8080, 0xFFFF]);
+    assert_eq!(narrow(1), None);
+}
+
+/// Tests that golden-angle hues are well spread.
+#[test]
+fn harmony_golden_hues() {
+    use crate::harmony::GoldenHues;
+
+    let colors: Vec<Rgb> = GoldenHues::new(30.0, 0.6, 0.9).take(12).collect();
+    let mut hues: Vec<f32> = colors.iter().map(|&c| Hsv::from(c).hue()).collect();
+    assert!(close(hues[0], 30.0, 0.5));
+
+    // No two of the first 12 hues are closer than a third of an even split.
+    hues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for pair in hues.windows(2) {
+        assert!(pair[1] - pair[0] > 10.0);
+    }
+
+    let far = GoldenHues::new(0.0, 0.6, 0.9).nth(100_000);
+    assert!(far.is_some());
+}
+
+/// Tests deterministic seeded colors.
+#[test]
+fn rgb_from_seed() {
+    use crate::contrast::contrast_ratio;
+    use crate::SeedOptions;
+
+    // The hash is stable, so seeded colors must never change.
+    assert_eq!(Rgb::from_seed("skyschermer"), Rgb::new(169, 188, 99));
+    assert_eq!(Rgb::from_seed(""), Rgb::new(199, 177, 73));
+
+    let options = SeedOptions::new()
+        .with_saturation(0.5, 0.5)
+        .with_value(0.25, 0.75);
+    for i in 0..100 {
+        let hsv = Hsv::from(Rgb::from_seed_with(&i.to_string(), &options));
+        assert!(close(hsv.saturation(), 0.5, 0.02));
+        assert!(hsv.value() > 0.24 && hsv.value() < 0.76);
+    }
+
+    for background in [Rgb::new(255, 255, 255), Rgb::new(20, 20, 20)] {
+        let options = SeedOptions::new()
+            .with_value(0.0, 1.0)
+            .with_contrast(background, 4.5);
+        for i in 0..100 {
+            let color = Rgb::from_seed_with(&format!("user{}", i), &options);
+            assert!(contrast_ratio(color, background) >= 4.5);
+        }
+    }
+}
+
+/// Tests that the conversion backends agree.
+#[test]
+fn backend_agreement() {
+    use crate::backend::Backend;
+    use crate::backend::ConversionBackend;
+
+    let pixels: Vec<Rgb> = (0..=255u8)
+        .flat_map(|v| [Rgb::new(v, 0, 0), Rgb::new(v, v, v), Rgb::new(17, v, 255 - v)])
+        .collect();
+    let mut reference = vec![Oklab::default(); pixels.len()];
+    Backend::Scalar.rgb_to_oklab(&pixels, &mut reference);
+    for (pixel, oklab) in pixels.iter().zip(reference.iter()) {
+        assert_eq!(Oklab::from(*pixel), *oklab);
+    }
+
+    for backend in [Backend::Scalar, Backend::Lut, Backend::Batched] {
+        let mut oklab = vec![Oklab::default(); pixels.len()];
+        backend.rgb_to_oklab(&pixels, &mut oklab);
+        for (a, b) in oklab.iter().zip(reference.iter()) {
+            assert!(close(a.l, b.l, 1e-5), "{:?}", backend);
+            assert!(close(a.a, b.a, 1e-5), "{:?}", backend);
+            assert!(close(a.b, b.b, 1e-5), "{:?}", backend);
+        }
+
+        let mut rgb = vec![Rgb::default(); pixels.len()];
+        backend.oklab_to_rgb(&oklab, &mut rgb);
+        assert_eq!(rgb, pixels, "{:?}", backend);
+    }
+}
+
+/// Tests that derived themes meet their contrast targets for varied brands.
+#[test]
+fn theme_variants_contrast() {
+    use crate::contrast::contrast_ratio;
+    use crate::contrast::AA;
+    use crate::contrast::AAA;
+    use crate::theme::ThemeVariants;
+
+    let brands = [
+        Rgb::new(0x63, 0x66, 0xF1),
+        Rgb::new(0xFF, 0xEB, 0x3B),
+        Rgb::new(0x00, 0x00, 0x00),
+        Rgb::new(0xFF, 0xFF, 0xFF),
+        Rgb::new(0x10, 0xB9, 0x81),
+    ];
+    for brand in brands {
+        let themes = ThemeVariants::from_brand(brand);
+        for theme in [themes.light, themes.dark] {
+            assert!(contrast_ratio(theme.on_background, theme.background) >= AAA);
+            assert!(contrast_ratio(theme.on_surface, theme.surface) >= AAA);
+            assert!(contrast_ratio(theme.on_surface, theme.surface_raised) >= AA);
+            assert!(contrast_ratio(theme.on_surface_muted, theme.surface) >= AA);
+            assert!(contrast_ratio(theme.on_accent, theme.accent) >= AA);
+            assert!(contrast_ratio(theme.on_accent_subtle, theme.accent_subtle) >= AA);
+        }
+        let light = Oklch::from(themes.light.background).l;
+        let dark = Oklch::from(themes.dark.background).l;
+        assert!(light > 0.95 && dark < 0.25);
+    }
+}
+
+/// Tests that chunked jobs match their one-shot equivalents.
+#[test]
+fn bulk_jobs_resume() {
+    use crate::bulk::Convert;
+    use crate::bulk::Job;
+    use crate::quantize::KMeans;
+    use std::time::Duration;
+
+    let pixels: Vec<Rgb> = (0..2000u32)
+        .map(|i| Rgb::new((i * 7) as u8, (i * 13) as u8, (i / 8) as u8))
+        .collect();
+
+    let mut job = Convert::<Rgb, Hsl>::new(&pixels);
+    assert!(job.progress().fraction() == 0.0);
+    let progress = job.process_chunk(500);
+    assert_eq!(progress.done, 500);
+    assert!(job.finish().is_none());
+
+    let mut job = Convert::<Rgb, Hsl>::new(&pixels);
+    while !job.process_for(Duration::from_millis(1)).is_complete() {}
+    let expected: Vec<Hsl> = pixels.iter().map(|&c| Hsl::from(c)).collect();
+    assert_eq!(job.finish(), Some(expected));
+
+    let kmeans = KMeans::new(6).with_iterations(5);
+    let expected = kmeans.extract(&pixels);
+    for budget in [1, 7, 100, 5000] {
+        let mut job = kmeans.job(&pixels);
+        let mut last = job.progress();
+        while !last.is_complete() {
+            let next = job.process_chunk(budget);
+            assert!(next.done > last.done);
+            assert!(next.done - last.done <= budget);
+            last = next;
+        }
+        assert_eq!(job.finish().as_ref(), Some(&expected));
+    }
+
+    assert!(kmeans.job(&[]).progress().is_complete());
+    assert_eq!(kmeans.job(&[]).finish().map(|p| p.len()), Some(0));
+}
+
+
+/// Tests that previews write one labelled truecolor row per color.
+#[test]
+fn print_preview_rows() {
+    let palette: Palette = vec![
+        ("black", Rgb::new(0, 0, 0)),
+        ("white", Rgb::new(255, 255, 255)),
+    ].into_iter().collect();
+    let mut out = Vec::new();
+    palette.print_preview(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out,
+        "\x1b[38;2;0;0;0m████\x1b[0m #000000 black\n\
+        \x1b[38;2;255;255;255m████\x1b[0m #ffffff white\n");
+
+    let mut gradient = Gradient::new(
+        Rgb::new(0, 0, 0),
+        Rgb::new(255, 255, 255));
+    gradient.add_stop(0.25, Rgb::new(255, 0, 0));
+    let mut out = Vec::new();
+    gradient.print_preview(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], preview(&gradient, 48, AnsiMode::TrueColor));
+    assert!(lines[2].ends_with(" #ff0000 0.25"));
+
+    let map: &dyn ColorMap = &LookupTable::new(vec![Rgb::new(1, 2, 3)]);
+    let mut out = Vec::new();
+    map.print_preview(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.lines().count(), 6);
+    assert!(out.lines().skip(1).all(|line| line.contains(" #010203 ")));
+}
+
+
+/// Tests that shade scales keep the reference color and a stable hue, with
+/// lightness decreasing across the steps.
+#[test]
+fn theme_shades() {
+    use crate::theme::Shades;
+    use crate::theme::SHADE_STEPS;
+
+    for &reference in &[
+        Rgb::new(0x3B, 0x82, 0xF6),
+        Rgb::new(0xEF, 0x44, 0x44),
+        Rgb::new(0xFD, 0xE0, 0x47),
+        Rgb::new(0x14, 0x53, 0x2D),
+        Rgb::new(0xF0, 0xF9, 0xFF),
+    ] {
+        let shades = Shades::from_reference(reference);
+        let step = shades.reference_step();
+        assert_eq!(shades.get(step), Some(reference));
+        assert_eq!(shades.get(42), None);
+        assert_eq!(shades.iter().count(), SHADE_STEPS.len());
+
+        let base = Oklch::from(reference);
+        let lightness: Vec<f32> = shades.colors().iter()
+            .map(|&c| Oklch::from(c).l)
+            .collect();
+        assert!(lightness.windows(2).all(|w| w[0] > w[1]), "{:?}", lightness);
+
+        for (_, color) in shades.iter() {
+            let oklch = Oklch::from(color);
+            if oklch.c > 0.03 {
+                let difference = crate::angle::difference(base.h, oklch.h);
+                assert!(difference.abs() < 4.0, "{} vs {}", base.h, oklch.h);
+            }
+        }
+    }
+
+    let gray = Shades::from_reference(Rgb::new(128, 128, 128));
+    for (_, color) in gray.iter() {
+        assert!(Oklch::from(color).c < 0.01);
+    }
+}
+
+
+/// Tests palette matching distances and similarity scores.
+#[test]
+fn palette_similarity() {
+    use crate::metric::Euclidean;
+    use crate::metric::Metric;
+
+    let gray = |v| Rgb::new(v, v, v);
+    let a: Palette = vec![("a", gray(10)), ("b", gray(50)), ("c", gray(90))]
+        .into_iter()
+        .collect();
+    let b: Palette = vec![("x", gray(95)), ("y", gray(12)), ("z", gray(48))]
+        .into_iter()
+        .collect();
+
+    let distance = a.match_distance_by(&b, Euclidean::Rgb);
+    let expected = (2.0 + 2.0 + 5.0) * 3f32.sqrt() / 3.0;
+    assert!(close(distance, expected, 0.001), "{}", distance);
+    assert!(close(distance, b.match_distance_by(&a, Euclidean::Rgb), 0.001));
+
+    // A leftover color is compared with its nearest color.
+    let mut c = a.clone();
+    assert_eq!(c.insert("d", gray(0)), None);
+    let distance = c.match_distance_by(&a, Euclidean::Rgb);
+    assert!(close(distance, 10.0 * 3f32.sqrt() / 4.0, 0.001), "{}", distance);
+
+    assert_eq!(a.similarity(&a), 1.0);
+    assert!(a.similarity(&b) > a.similarity(&Palette::web_safe()));
+    assert_eq!(Palette::new().match_distance(&Palette::new()), 0.0);
+    assert_eq!(a.similarity(&Palette::new()), 0.0);
+
+    // The optimal matching beats every permutation.
+    let colors: Vec<Rgb> = (0..6u32)
+        .map(|i| Rgb::from(i.wrapping_mul(0x9E37_79B9) >> 8))
+        .collect();
+    let swatches = |colors: &[Rgb]| colors.iter()
+        .map(|&c| (format!("{:x}", c), c))
+        .collect::<Palette>();
+    let (left, right) = (swatches(&colors[..3]), swatches(&colors[3..]));
+    let best = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]]
+        .iter()
+        .map(|p| (0..3)
+            .map(|i| Euclidean::Rgb.distance(colors[i], colors[3 + p[i]]))
+            .sum::<f32>() / 3.0)
+        .fold(f32::INFINITY, f32::min);
+    let distance = left.match_distance_by(&right, Euclidean::Rgb);
+    assert!(close(distance, best, 0.001), "{} vs {}", distance, best);
+}
+
+
+/// Tests palette diffs by swatch name.
+#[test]
+fn palette_diff() {
+    let old: Palette = vec![
+        ("a", Rgb::new(10, 10, 10)),
+        ("b", Rgb::new(20, 20, 20)),
+        ("c", Rgb::new(30, 30, 30)),
+    ].into_iter().collect();
+    let new: Palette = vec![
+        ("d", Rgb::new(40, 40, 40)),
+        ("c", Rgb::new(30, 30, 31)),
+        ("a", Rgb::new(10, 10, 10)),
+    ].into_iter().collect();
+
+    assert!(old.diff(&old).is_empty());
+    assert_eq!(old.diff(&old).to_string(), "");
+
+    let diff = old.diff(&new);
+    assert!(!diff.is_empty());
+    let names = |swatches: &[crate::palette::Swatch]| swatches.iter()
+        .map(|s| s.name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names(&diff.added), ["d"]);
+    assert_eq!(names(&diff.removed), ["b"]);
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].name, "c");
+    assert_eq!(diff.changed[0].old, Rgb::new(30, 30, 30));
+    assert_eq!(diff.changed[0].new, Rgb::new(30, 30, 31));
+    assert!(diff.changed[0].delta_e > 0.0 && diff.changed[0].delta_e < 2.0);
+
+    // Diffs are reversible.
+    let reverse = new.diff(&old);
+    assert_eq!(reverse.added, diff.removed);
+    assert_eq!(reverse.removed, diff.added);
+}
+
+
+/// Tests that palette migrations use an optimal assignment and map leftover
+/// colors to their nearest color.
+#[test]
+fn palette_migration() {
+    use crate::metric::Euclidean;
+
+    let gray = |v| Rgb::new(v, v, v);
+    let old: Palette = vec![("a", gray(100)), ("b", gray(0)), ("c", gray(95))]
+        .into_iter()
+        .collect();
+    let new: Palette = vec![("x", gray(90)), ("y", gray(200))]
+        .into_iter()
+        .collect();
+
+    let migration = old.migration_to_by(&new, Euclidean::Rgb);
+    assert_eq!(migration.entries.len(), 3);
+    let target = |name| migration.get(name).unwrap().name.clone();
+    // Only two colors can be matched, and "b" is left over.
+    assert_eq!(target("b"), "x");
+    assert_eq!(target("a"), "y");
+    assert_eq!(target("c"), "x");
+    assert_eq!(migration.get("z"), None);
+
+    let total: f32 = migration.entries.iter().map(|e| e.distance).sum();
+    assert!(close(migration.total_cost, total, 0.001));
+    assert!(close(total, (90.0 + 100.0 + 5.0) * 3f32.sqrt(), 0.01));
+
+    assert!(old.migration_to(&Palette::new()).entries.is_empty());
+    assert_eq!(old.migration_to(&old).total_cost, 0.0);
+}
+
+
+/// Tests absolute and relative lightening and darkening.
+#[test]
+fn lighten_and_darken() {
+    let hsl = Hsl::new(210.0, 0.6, 0.4);
+    assert!(close(hsl.lighten(0.2).lightness(), 0.6, 0.0001));
+    assert!(close(hsl.darken(0.2).lightness(), 0.2, 0.0001));
+    assert!(close(hsl.lighten_relative(0.5).lightness(), 0.7, 0.0001));
+    assert!(close(hsl.darken_relative(0.5).lightness(), 0.2, 0.0001));
+    assert_eq!(hsl.lighten(0.2).hue(), hsl.hue());
+    assert_eq!(hsl.darken(0.2).saturation(), hsl.saturation());
+
+    let hsv = Hsv::new(210.0, 0.6, 0.4);
+    assert!(close(hsv.lighten(0.2).value(), 0.6, 0.0001));
+    assert!(close(hsv.darken_relative(0.25).value(), 0.3, 0.0001));
+    assert_eq!(hsv.darken(1.0).value(), 0.0);
+
+    for &color in &[
+        Rgb::new(200, 60, 40),
+        Rgb::new(30, 144, 255),
+        Rgb::new(120, 120, 120),
+        Rgb::new(255, 255, 0),
+    ] {
+        let base = Oklch::from(color);
+        for &step in &[0.05, 0.1, 0.2] {
+            let lighter = Oklch::from(color.lighten(step));
+            let darker = Oklch::from(color.darken(step));
+            assert!(lighter.l >= base.l - 0.005, "{:?}", color);
+            assert!(darker.l <= base.l + 0.005, "{:?}", color);
+            for shifted in [lighter, darker] {
+                if shifted.c > 0.03 && base.c > 0.03 {
+                    let difference = crate::angle::difference(base.h, shifted.h);
+                    assert!(difference.abs() < 3.0, "{:?}", color);
+                }
+            }
+        }
+        assert!(Oklch::from(color.lighten_relative(0.5)).l > base.l);
+        assert!(Oklch::from(color.darken_relative(0.5)).l < base.l);
+    }
+}
+
+
+/// Tests that fitted gradients stay within tolerance using few stops.
+#[test]
+fn gradient_fit() {
+    let ramp = Gradient::from_colors(vec![
+        Rgb::new(10, 20, 30),
+        Rgb::new(250, 20, 30),
+        Rgb::new(250, 220, 30),
+        Rgb::new(0, 0, 0),
+    ]);
+    let samples = ramp.quantize(97);
+
+    let fitted = Gradient::fit(&samples, 10, 3.0);
+    assert_eq!(fitted.stops().len(), 4);
+    let last = (samples.len() - 1) as f32;
+    for (i, &sample) in samples.iter().enumerate() {
+        let error = Lab::delta_e(sample, fitted.color(i as f32 / last));
+        assert!(error <= 3.0, "{} at {}", error, i);
+    }
+
+    // Smooth curves need more stops, up to the limit.
+    let curve: Vec<Rgb> = (0..100)
+        .map(|i| {
+            let v = ((i as f32 / 99.0) * std::f32::consts::PI).sin();
+            Rgb::new((v * 255.0) as u8, 0, 255 - (v * 255.0) as u8)
+        })
+        .collect();
+    assert_eq!(Gradient::fit(&curve, 5, 0.1).stops().len(), 5);
+    assert!(Gradient::fit(&curve, 64, 2.0).stops().len() > 5);
+    assert_eq!(Gradient::fit(&curve, 0, 2.0).stops().len(), 2);
+
+    let single = Gradient::fit(&[Rgb::new(1, 2, 3)], 4, 1.0);
+    assert_eq!(single.color(0.7), Rgb::new(1, 2, 3));
+}
+
+
+/// Tests saturation adjustments on the hue-based types and perceptual chroma
+/// scaling on `Rgb`.
+#[test]
+fn saturate_and_desaturate() {
+    let hsl = Hsl::new(30.0, 0.4, 0.5);
+    assert!(close(hsl.saturate(0.3).saturation(), 0.7, 0.0001));
+    assert!(close(hsl.desaturate(0.3).saturation(), 0.1, 0.0001));
+    assert_eq!(hsl.saturate(2.0).saturation(), 1.0);
+    assert_eq!(hsl.desaturate(0.3).lightness(), hsl.lightness());
+
+    let hsv = Hsv::new(30.0, 0.4, 0.5);
+    assert!(close(hsv.saturate(0.3).saturation(), 0.7, 0.0001));
+    assert_eq!(hsv.desaturate(2.0).saturation(), 0.0);
+    assert_eq!(hsv.desaturate(0.3).value(), hsv.value());
+
+    for &color in &[
+        Rgb::new(180, 90, 70),
+        Rgb::new(70, 130, 180),
+        Rgb::new(120, 160, 90),
+    ] {
+        let base = Oklch::from(color);
+        let vivid = Oklch::from(color.saturate(0.4));
+        let muted = Oklch::from(color.desaturate(0.4));
+        assert!(vivid.c > base.c && muted.c < base.c, "{:?}", color);
+        assert!(close(vivid.l, base.l, 0.01) && close(muted.l, base.l, 0.01));
+        assert!(crate::angle::difference(base.h, vivid.h).abs() < 3.0);
+        assert!(crate::angle::difference(base.h, muted.h).abs() < 3.0);
+        assert!(Oklch::from(color.desaturate(1.0)).c < 0.002);
+    }
+}
+
+
+/// Tests hue rotation with wrap-around in every hue-bearing space.
+#[test]
+fn rotate_hue_wraps() {
+    for &degrees in &[0.0, 45.0, -45.0, 360.0, 725.0, -1080.0] {
+        let expected = crate::angle::wrap(350.0 + degrees);
+        let hsl = Hsl::new(350.0, 0.5, 0.5).rotate_hue(degrees);
+        let hsv = Hsv::new(350.0, 0.5, 0.5).rotate_hue(degrees);
+        let lch = Lch::new(50.0, 30.0, 350.0).rotate_hue(degrees);
+        let oklch = Oklch::new(0.5, 0.1, 350.0).rotate_hue(degrees);
+        for hue in [hsl.hue(), hsv.hue(), lch.h, oklch.h] {
+            assert!(close(hue, expected, 0.001), "{} vs {}", hue, expected);
+            assert!((0.0..360.0).contains(&hue));
+        }
+        assert_eq!(hsl.saturation(), 0.5);
+        assert_eq!(oklch.l, 0.5);
+    }
+
+    let color = Rgb::new(200, 60, 40);
+    assert!(Rgb::distance(color.rotate_hue(360.0), color) < 2.0);
+    let round_trip = color.rotate_hue(90.0).rotate_hue(-90.0);
+    assert!(Rgb::distance(round_trip, color) < 4.0, "{:?}", round_trip);
+    assert!(close(
+        Hsv::from(color.rotate_hue(180.0)).hue(),
+        crate::angle::wrap(Hsv::from(color).hue() + 180.0),
+        1.0));
+}
+
+
+/// Tests palette-constrained gradients.
+#[test]
+fn gradient_through_palette() {
+    let palette = Palette::web_safe();
+    let ramp = Gradient::through_palette(
+            &palette,
+            Rgb::new(0, 0, 0),
+            Rgb::new(255, 204, 153),
+            6)
+        .unwrap();
+    let stops = ramp.stops();
+    assert!(stops.len() > 3);
+    assert_eq!(stops[0].color, Rgb::new(0, 0, 0));
+    assert_eq!(stops[stops.len() - 1].color, Rgb::new(255, 204, 153));
+    assert!(stops.iter().all(|s| palette.colors().any(|c| c == s.color)));
+    let lightness: Vec<f32> = stops.iter()
+        .map(|s| Oklab::from(s.color).l)
+        .collect();
+    assert!(lightness.windows(2).all(|w| w[1] > w[0] - 0.02),
+        "{:?}", lightness);
+
+    let step = |a: Rgb, b: Rgb| Oklab::distance(a, b);
+    let largest = stops.windows(2)
+        .map(|w| step(w[0].color, w[1].color))
+        .fold(0.0, f32::max);
+    assert!(largest < step(Rgb::new(0, 0, 0), Rgb::new(255, 204, 153)) / 2.0);
+
+    let same = Gradient::through_palette(
+        &palette, Rgb::new(1, 1, 1), Rgb::new(2, 2, 2), 6).unwrap();
+    assert_eq!(same.color(0.5), Rgb::new(0, 0, 0));
+
+    assert!(Gradient::through_palette(
+        &palette, Rgb::new(0, 0, 0), Rgb::new(255, 255, 255), 0).is_none());
+    assert!(Gradient::through_palette(
+        &Palette::new(), Rgb::new(0, 0, 0), Rgb::new(9, 9, 9), 4).is_none());
+}
+
+
+/// Tests palette coverage statistics.
+#[test]
+fn palette_coverage() {
+    use crate::metric::Euclidean;
+
+    let palette: Palette = vec![("gray", Rgb::new(100, 100, 100))]
+        .into_iter()
+        .collect();
+    let targets = vec![Rgb::new(100, 100, 100), Rgb::new(100, 100, 160)];
+    let coverage = palette.coverage_by(targets.clone(), Euclidean::Rgb);
+    assert_eq!(coverage.mean, 30.0);
+    assert_eq!(coverage.max, 60.0);
+    assert_eq!(coverage.worst, Some(Rgb::new(100, 100, 160)));
+
+    let empty = palette.coverage(Vec::new());
+    assert_eq!((empty.mean, empty.max, empty.worst), (0.0, 0.0, None));
+
+    let uncovered = Palette::new().coverage(targets);
+    assert!(uncovered.mean.is_infinite() && uncovered.max.is_infinite());
+
+    // Larger palettes cover the gamut better.
+    let web_safe = Palette::web_safe().gamut_coverage();
+    let grays = palette.gamut_coverage();
+    assert!(web_safe.mean < grays.mean && web_safe.max < grays.max);
+}
+
+
+/// Tests channel inversion and perceptual complements.
+#[test]
+fn invert_and_complement() {
+    for &color in &[
+        Rgb::new(0, 0, 0),
+        Rgb::new(12, 200, 99),
+        Rgb::new(255, 255, 255),
+    ] {
+        assert_eq!(color.invert().invert(), color);
+        let [r, g, b] = color.invert().octets();
+        assert_eq!([r, g, b], color.octets().map(|c| 255 - c));
+    }
+    let cmyk = Cmyk { c: 1, m: 2, y: 3, k: 4 };
+    assert_eq!(cmyk.invert(), Cmyk { c: 254, m: 253, y: 252, k: 251 });
+
+    for &color in &[
+        Rgb::new(200, 60, 40),
+        Rgb::new(30, 144, 255),
+        Rgb::new(120, 160, 90),
+    ] {
+        let original = Oklch::from(color);
+        let complement = Oklch::from(color.complement());
+        assert!(close(complement.l, original.l, 0.01), "{:?}", color);
+        let turn = crate::angle::difference(original.h, complement.h);
+        assert!(close(turn.abs(), 180.0, 2.0), "{:?} {}", color, turn);
+    }
+    let gray = Rgb::new(128, 128, 128);
+    assert!(Rgb::distance(gray.complement(), gray) < 2.0);
+}
+
+
+/// Tests grayscale conversion methods and the `Luma` type.
+#[test]
+fn grayscale_methods() {
+    use crate::GrayscaleMethod;
+    use crate::lossless::convert;
+    use crate::Luma;
+
+    let methods = [
+        GrayscaleMethod::Rec709,
+        GrayscaleMethod::Rec601,
+        GrayscaleMethod::Average,
+        GrayscaleMethod::Lightness,
+        GrayscaleMethod::Desaturation,
+    ];
+    for &method in &methods {
+        for v in (0..=255).step_by(17) {
+            let gray = Rgb::new(v, v, v);
+            assert_eq!(gray.to_luma(method), Luma::new(v), "{:?}", method);
+            assert_eq!(gray.to_grayscale(method), gray);
+        }
+        let [r, g, b] = Rgb::new(200, 30, 90).to_grayscale(method).octets();
+        assert!(r == g && g == b);
+    }
+
+    let color = Rgb::new(10, 200, 250);
+    assert_eq!(color.to_luma(GrayscaleMethod::Rec709).y, 163);
+    assert_eq!(color.to_luma(GrayscaleMethod::Rec601).y, 149);
+    assert_eq!(color.to_luma(GrayscaleMethod::Average).y, 153);
+    assert_eq!(color.to_luma(GrayscaleMethod::Lightness).y, 130);
+    let desaturated = color.to_grayscale(GrayscaleMethod::Desaturation);
+    assert!(close(Oklab::from(desaturated).l, Oklab::from(color).l, 0.005));
+
+    assert_eq!(Luma::from(color), color.to_luma(GrayscaleMethod::default()));
+    let rgb: Rgb = convert(Luma::new(77));
+    assert_eq!(rgb, Rgb::new(77, 77, 77));
+    assert_eq!(u8::from(Luma::from(9u8)), 9);
+}
+
+
+/// Tests warm and cool classification.
+#[test]
+fn warmth_classification() {
+    let warm = [
+        Rgb::new(255, 0, 0),
+        Rgb::new(255, 128, 0),
+        Rgb::new(255, 220, 0),
+        Rgb::new(160, 82, 45),
+    ];
+    let cool = [
+        Rgb::new(0, 0, 255),
+        Rgb::new(0, 200, 255),
+        Rgb::new(70, 130, 180),
+    ];
+    for color in warm {
+        assert!(color.is_warm() && !color.is_cool(), "{:?}", color);
+    }
+    for color in cool {
+        assert!(color.is_cool() && !color.is_warm(), "{:?}", color);
+    }
+    for v in [0, 128, 255] {
+        let gray = Rgb::new(v, v, v);
+        assert!(!gray.is_warm() && !gray.is_cool());
+    }
+    for color in warm.iter().chain(cool.iter()) {
+        assert!((-1.0..=1.0).contains(&color.warmth()));
+    }
+    assert!(Rgb::new(255, 128, 0).warmth() > Rgb::new(255, 0, 255).warmth());
+
+    let palette: Palette = warm.iter()
+        .chain(cool.iter())
+        .map(|&c| (format!("{:x}", c), c))
+        .chain(std::iter::once(("gray".to_string(), Rgb::new(9, 9, 9))))
+        .collect();
+    let summary = palette.warmth();
+    assert_eq!((summary.warm, summary.cool, summary.neutral), (4, 3, 1));
+    let mean = palette.colors().map(|c| c.warmth()).sum::<f32>() / 8.0;
+    assert!(close(summary.mean, mean, 0.0001));
+    assert_eq!(Palette::new().warmth().mean, 0.0);
+}
+
+/// Tests tagging colors with the default and custom rules.
+#[test]
+fn tag_rules() {
+    use crate::tags::TagRule;
+    use crate::tags::Tagger;
+
+    let tagger = Tagger::default();
+    assert_eq!(tagger.tags(Rgb::new(128, 128, 128)), ["neutral"]);
+    assert_eq!(tagger.tags(Rgb::new(10, 10, 10)), ["neutral", "dark"]);
+    assert!(tagger.tags(Rgb::new(150, 100, 50)).contains(&"earthy"));
+    assert!(tagger.tags(Rgb::new(57, 255, 20)).contains(&"neon"));
+    assert!(tagger.tags(Rgb::new(255, 209, 220)).contains(&"pastel"));
+
+    // Hue ranges wrap through zero.
+    let reds = TagRule::new("red").with_chroma(0.1, 1.0).with_hue(350.0, 40.0);
+    assert!(reds.matches(Rgb::new(255, 0, 0)));
+    assert!(reds.matches(Rgb::new(255, 0, 80)));
+    assert!(!reds.matches(Rgb::new(0, 255, 0)));
+
+    // Duplicate tags are listed once, and can be removed together.
+    let mut tagger = Tagger::new()
+        .with_rule(TagRule::new("any"))
+        .with_rule(TagRule::new("any").with_lightness(0.5, 1.0));
+    assert_eq!(tagger.tags(Rgb::new(255, 255, 255)), ["any"]);
+    tagger.remove_tag("any");
+    assert!(tagger.rules().is_empty());
+    assert!(tagger.tags(Rgb::new(255, 255, 255)).is_empty());
+
+    let palette: Palette = vec![
+        ("a", Rgb::new(128, 128, 128)),
+        ("b", Rgb::new(10, 10, 10)),
+        ("c", Rgb::new(57, 255, 20)),
+    ].into_iter().collect();
+    let tagger = Tagger::default();
+    let tags = tagger.palette_tags(&palette);
+    assert_eq!(tags[0], ("neutral", 2));
+    assert!(tags.contains(&("neon", 1)));
+}
+
+/// Tests sepia toning and duotone remapping.
+#[test]
+fn sepia_and_duotone() {
+    let color = Rgb::new(30, 144, 255);
+    assert_eq!(color.sepia(0.0), color);
+    assert_eq!(color.sepia(-1.0), color);
+    assert_eq!(color.sepia(2.0), color.sepia(1.0));
+    let [r, g, b] = Rgb::new(100, 100, 100).sepia(1.0).octets();
+    assert!(r > g && g > b);
+    assert_eq!(Rgb::new(255, 255, 255).sepia(1.0), Rgb::new(255, 255, 239));
+
+    let dark = Rgb::new(40, 0, 80);
+    let light = Rgb::new(255, 230, 120);
+    assert_eq!(Rgb::new(0, 0, 0).duotone(dark, light), dark);
+    assert_eq!(Rgb::new(255, 255, 255).duotone(dark, light), light);
+    // Brighter inputs map closer to the light color.
+    let mut last = Oklab::from(dark).l;
+    for v in (0..=255).step_by(15) {
+        let l = Oklab::from(Rgb::new(v, v, v).duotone(dark, light)).l;
+        assert!(l >= last - 0.001);
+        last = l;
+    }
+    // Colors with equal gray levels map to the same color.
+    let gray = Rgb::new(0, 255, 0).to_grayscale(Default::default());
+    assert_eq!(
+        Rgb::new(0, 255, 0).duotone(dark, light),
+        gray.duotone(dark, light));
+}
+
+/// Tests mixing colors within their own space and in a chosen space.
+#[test]
+fn mix_weights_and_spaces() {
+    use crate::gradient::Interpolate;
+    use crate::gradient::MixSpace;
+    use crate::gradient::mix;
+
+    let a = Rgb::new(200, 40, 20);
+    let b = Rgb::new(20, 120, 240);
+    // The weight is the proportion of the first color.
+    assert_eq!(Rgb::mix(a, b, 1.0), a);
+    assert_eq!(Rgb::mix(a, b, 0.0), b);
+    assert_eq!(Rgb::mix(a, b, 2.0), a);
+    assert_eq!(Rgb::mix(a, b, -1.0), b);
+    assert_eq!(Rgb::mix(a, b, 0.3), Rgb::interpolate(a, b, 0.7));
+
+    let oklch = Oklch::mix(Oklch::new(0.6, 0.1, 350.0),
+        Oklch::new(0.6, 0.1, 30.0), 0.5);
+    assert!(close(oklch.h, 10.0, 0.001));
+
+    // The endpoints are only as exact as the round trip through the space.
+    for space in [
+        MixSpace::Srgb, MixSpace::Hsl, MixSpace::Hsv, MixSpace::Xyz,
+        MixSpace::Lab, MixSpace::Lch, MixSpace::Oklab, MixSpace::Oklch,
+    ] {
+        let (start, end) = (mix(a, a, 0.5, space), mix(b, b, 0.5, space));
+        assert!(Rgb::distance(mix(a, b, 1.0, space), start) <= 2.0);
+        assert!(Rgb::distance(mix(a, b, 0.0, space), end) <= 2.0);
+    }
+    assert_eq!(
+        mix(a, b, 0.25, MixSpace::default()),
+        Rgb::from(Oklab::mix(Oklab::from(a), Oklab::from(b), 0.25)));
+}
+
+/// Tests mixing colors with alpha in premultiplied form, and mixing the
+/// 16-bit and grayscale colors.
+#[test]
+fn mix_alpha_and_deep_colors() {
+    use crate::gradient::Interpolate;
+    use crate::Luma;
+    use crate::Rgb48;
+    use crate::Rgba;
+    use crate::Rgba64;
+
+    // A transparent color contributes no hue to the mix.
+    let red = Rgba::new(255, 0, 0, 255);
+    let clear = Rgba::new(0, 0, 255, 0);
+    assert_eq!(Rgba::mix(red, clear, 0.5), Rgba::new(255, 0, 0, 128));
+    assert_eq!(Rgba::mix(red, clear, 1.0), red);
+    assert_eq!(Rgba::mix(clear, clear, 0.5), Rgba::new(0, 0, 0, 0));
+    assert_eq!(
+        Rgba::mix(Rgba::new(255, 0, 0, 255), Rgba::new(0, 0, 255, 255), 0.5),
+        Rgba::new(128, 0, 128, 255));
+
+    assert_eq!(
+        Rgba64::mix(
+            Rgba64::new(65535, 0, 0, 65535),
+            Rgba64::new(0, 0, 65535, 0),
+            0.5),
+        Rgba64::new(65535, 0, 0, 32768));
+    assert_eq!(
+        Rgb48::interpolate(Rgb48::new(0, 0, 0), Rgb48::new(1000, 2000, 65535), 0.5),
+        Rgb48::new(500, 1000, 32768));
+    assert_eq!(Luma::mix(Luma::new(0), Luma::new(200), 0.25), Luma::new(150));
+}
+
+/// Tests the pastel, neon, and muted palette renditions.
+#[test]
+fn palette_renditions() {
+    use crate::angle;
+
+    let mut palette: Palette = vec![
+        ("red", Rgb::new(220, 30, 40)),
+        ("teal", Rgb::new(0, 128, 128)),
+        ("ochre", Rgb::new(204, 119, 34)),
+        ("violet", Rgb::new(120, 60, 200)),
+        ("dark", Rgb::new(40, 20, 30)),
+    ].into_iter().collect();
+    palette.set_name(Some("base"));
+
+    let originals: Vec<Oklch> = palette.colors().map(Oklch::from).collect();
+    for rendition in [palette.pastel(), palette.neon(), palette.muted()] {
+        assert_eq!(rendition.name(), Some("base"));
+        assert_eq!(rendition.len(), palette.len());
+        let colors: Vec<Oklch> = rendition.colors().map(Oklch::from).collect();
+        for (old, new) in originals.iter().zip(colors.iter()) {
+            let hue_shift = angle::difference(old.h, new.h).abs();
+            assert!(hue_shift < 3.0, "{:?} -> {:?}", old, new);
+        }
+        // Lightness order is preserved.
+        for i in 0..colors.len() {
+            for j in 0..colors.len() {
+                if originals[i].l + 0.05 < originals[j].l {
+                    assert!(colors[i].l < colors[j].l);
+                }
+            }
+        }
+    }
+
+    let neon = palette.neon();
+    for (old, new) in originals.iter().zip(neon.colors().map(Oklch::from)) {
+        assert!(new.c > old.c || new.c > 0.15, "{:?} -> {:?}", old, new);
+    }
+    let pastel = palette.pastel();
+    assert!(pastel.colors().all(|c| Oklch::from(c).l > 0.8));
+    let muted = palette.muted();
+    for (old, new) in originals.iter().zip(muted.colors().map(Oklch::from)) {
+        assert!(new.c < old.c);
+    }
+    assert!(Palette::new().pastel().is_empty());
+}
+
+/// Tests the separable and non-separable blend modes and alpha compositing.
+#[test]
+fn blend_modes() {
+    use crate::blend::BlendMode;
+    use crate::Rgba;
+
+    let white = Rgb::new(255, 255, 255);
+    let black = Rgb::new(0, 0, 0);
+    let colors = [
+        Rgb::new(200, 100, 50),
+        Rgb::new(12, 180, 240),
+        Rgb::new(128, 128, 128),
+    ];
+    for &c in &colors {
+        assert_eq!(BlendMode::Normal.blend(black, c), c);
+        assert_eq!(BlendMode::Multiply.blend(c, white), c);
+        assert_eq!(BlendMode::Screen.blend(c, black), c);
+        assert_eq!(BlendMode::Difference.blend(c, c), black);
+        assert_eq!(BlendMode::Exclusion.blend(c, black), c);
+        assert_eq!(BlendMode::Darken.blend(c, white), c);
+        assert_eq!(BlendMode::Lighten.blend(c, black), c);
+        assert_eq!(BlendMode::ColorDodge.blend(c, black), c);
+        assert_eq!(BlendMode::ColorBurn.blend(c, white), c);
+        assert_eq!(
+            BlendMode::Overlay.blend(c, colors[1]),
+            BlendMode::HardLight.blend(colors[1], c));
+        // A gray source leaves the backdrop unchanged under soft light.
+        let soft = BlendMode::SoftLight.blend(c, Rgb::new(128, 128, 128));
+        assert!(Rgb::distance(soft, c) <= 2.0, "{:?}", soft);
+    }
+
+    // Non-separable modes.
+    let gray = Rgb::new(128, 128, 128);
+    let red = Rgb::new(255, 0, 0);
+    let lum = |c: Rgb| {
+        let [r, g, b] = c.ratios();
+        0.3 * r + 0.59 * g + 0.11 * b
+    };
+    assert!(close(lum(BlendMode::Color.blend(gray, red)), lum(gray), 0.01));
+    assert!(close(lum(BlendMode::Hue.blend(colors[0], colors[1])),
+        lum(colors[0]), 0.01));
+    assert!(close(lum(BlendMode::Luminosity.blend(red, gray)), lum(gray),
+        0.01));
+    assert_eq!(BlendMode::Saturation.blend(red, gray), Rgb::new(77, 77, 77));
+    assert_eq!(BlendMode::Luminosity.blend(gray, red), Rgb::new(77, 77, 77));
+    assert!(!BlendMode::Hue.is_separable());
+    assert!(BlendMode::SoftLight.is_separable());
+
+    // Alpha compositing.
+    let backdrop = Rgba::new(0, 0, 255, 255);
+    let clear = Rgba::new(255, 0, 0, 0);
+    assert_eq!(BlendMode::Screen.blend_rgba(backdrop, clear), backdrop);
+    let half = Rgba::new(255, 0, 0, 128);
+    assert_eq!(
+        BlendMode::Normal.blend_rgba(backdrop, half),
+        Rgba::from(half.over(backdrop.rgb())));
+    let none = BlendMode::Multiply.blend_rgba(clear, Rgba::new(0, 0, 0, 0));
+    assert_eq!(none.a, 0);
+    let opaque = BlendMode::Multiply.blend_rgba(
+        Rgba::from(colors[0]), Rgba::from(colors[1]));
+    assert_eq!(opaque.rgb(), BlendMode::Multiply.blend(colors[0], colors[1]));
+    assert!(opaque.is_opaque());
+}
+
+/// Tests interpolating palettes by name and by optimal assignment, and
+/// interpolating themes.
+#[test]
+fn palette_and_theme_interpolation() {
+    use crate::palette::SwatchMatching;
+    use crate::theme::ThemeVariants;
+
+    let mut start: Palette = vec![
+        ("bg", Rgb::new(250, 250, 250)),
+        ("fg", Rgb::new(20, 20, 20)),
+        ("old", Rgb::new(200, 0, 0)),
+    ].into_iter().collect();
+    start.set_name(Some("light"));
+    let mut end: Palette = vec![
+        ("fg", Rgb::new(235, 235, 235)),
+        ("bg", Rgb::new(18, 18, 24)),
+        ("new", Rgb::new(0, 180, 0)),
+    ].into_iter().collect();
+    end.set_name(Some("dark"));
+
+    for matching in [SwatchMatching::Name, SwatchMatching::Optimal] {
+        assert_eq!(Palette::interpolate(&start, &end, 0.0, matching), start);
+        assert_eq!(Palette::interpolate(&start, &end, 1.0, matching), end);
+        assert_eq!(Palette::interpolate(&start, &end, -1.0, matching), start);
+    }
+
+    // By name, roles keep their identity and unmatched swatches fade in and
+    // out, ordered after the matched ones of the nearer palette.
+    let early = Palette::interpolate(&start, &end, 0.25, SwatchMatching::Name);
+    let names: Vec<&str> = early.iter().map(|s| &s.name[..]).collect();
+    assert_eq!(names, ["bg", "fg", "old", "new"]);
+    assert_eq!(early.name(), Some("light"));
+    let bg = early.get("bg").unwrap();
+    assert!(bg.r < 250 && bg.r > 18);
+    let late = Palette::interpolate(&start, &end, 0.75, SwatchMatching::Name);
+    let names: Vec<&str> = late.iter().map(|s| &s.name[..]).collect();
+    assert_eq!(names, ["fg", "bg", "new", "old"]);
+    assert_eq!(late.name(), Some("dark"));
+
+    // By optimal assignment, each color moves only a short way.
+    let optimal = Palette::interpolate(
+        &start, &end, 0.4, SwatchMatching::Optimal);
+    let bg = optimal.get("bg").unwrap();
+    assert!(bg.r > 230, "{:?}", bg);
+
+    // Unmatched swatches move toward their nearest counterpart.
+    let empty = Palette::new();
+    assert_eq!(
+        Palette::interpolate(&start, &empty, 0.5, SwatchMatching::Name)
+            .colors()
+            .collect::<Vec<_>>(),
+        start.colors().collect::<Vec<_>>());
+    assert!(Palette::interpolate(&start, &empty, 1.0, SwatchMatching::Name)
+        .is_empty());
+
+    let variants = ThemeVariants::from_brand(Rgb::new(0x63, 0x66, 0xF1));
+    assert_eq!(variants.interpolate(0.0), variants.light);
+    assert_eq!(variants.interpolate(1.0), variants.dark);
+    let mut last = Oklab::from(variants.light.background).l;
+    for i in 1..=10 {
+        let theme = variants.interpolate(i as f32 / 10.0);
+        let l = Oklab::from(theme.background).l;
+        assert!(l <= last + 0.001);
+        last = l;
+    }
+}
+
+/// Tests spreading a gradient over text by character, word, and line.
+#[test]
+fn text_gradient_colors() {
+    use crate::render::TextSplit;
+    use crate::render::ansi_text;
+    use crate::render::glyph_colors;
+    use crate::render::text_colors;
+
+    let black = Rgb::new(0, 0, 0);
+    let blue = Rgb::new(0, 0, 200);
+    let gradient = Gradient::new(black, blue);
+
+    // One color per character, including multibyte ones.
+    let colors = text_colors(&gradient, "héllo wörld", TextSplit::Character);
+    assert_eq!(colors.len(), 11);
+    assert_eq!((colors[0], colors[10]), (black, blue));
+    assert!(colors.windows(2).all(|w| w[0].b <= w[1].b));
+    assert_eq!(text_colors(&gradient, "x", TextSplit::Character), [black]);
+    assert!(text_colors(&gradient, "", TextSplit::Word).is_empty());
+
+    // Words share a color, and whitespace follows the preceding word.
+    let colors = text_colors(&gradient, "  one two  three", TextSplit::Word);
+    assert_eq!(colors.len(), 16);
+    assert_eq!(&colors[0..6], &[black; 6]);
+    assert_eq!(&colors[6..11], &[Rgb::new(0, 0, 100); 5]);
+    assert_eq!(&colors[11..], &[blue; 5]);
+
+    // Each line spans the whole gradient.
+    let colors = text_colors(&gradient, "abc\n\nxy\n", TextSplit::Line);
+    assert_eq!(colors, [black, Rgb::new(0, 0, 100), blue, blue,
+        black, black, blue, blue]);
+
+    // Glyph widths advance the gradient by columns.
+    assert_eq!(glyph_colors(&gradient, &[1, 0, 1]), [black, blue, blue]);
+    assert_eq!(glyph_colors(&gradient, &[3]), [black]);
+    assert!(glyph_colors(&gradient, &[]).is_empty());
+
+    // Escapes are only emitted when a visible character changes color.
+    let text = ansi_text(&gradient, "a b", TextSplit::Word, AnsiMode::Ansi16);
+    assert_eq!(text.matches('\x1b').count(), 3);
+    assert!(text.ends_with(crate::render::RESET));
+}
+
+/// Tests gamma and exposure adjustments in linear light.
+#[test]
+fn gamma_and_exposure() {
+    let colors = [
+        Rgb::new(0, 0, 0),
+        Rgb::new(255, 255, 255),
+        Rgb::new(200, 100, 50),
+        Rgb::new(12, 180, 240),
+    ];
+    for &c in &colors {
+        assert_eq!(c.adjust_gamma(1.0), c);
+        assert_eq!(c.adjust_exposure(0.0), c);
+        let [r, g, b] = c.adjust_gamma(2.0).octets();
+        assert!(r >= c.r && g >= c.g && b >= c.b);
+        let [r, g, b] = c.adjust_gamma(0.5).octets();
+        assert!(r <= c.r && g <= c.g && b <= c.b);
+    }
+    // Exposure scales linear light, so a stop up and a stop down round trip.
+    let c = Rgb::new(120, 90, 60);
+    assert!(Rgb::distance(c.adjust_exposure(1.0).adjust_exposure(-1.0), c)
+        <= 1.0);
+    assert_eq!(c.adjust_exposure(30.0), Rgb::new(255, 255, 255));
+    assert_eq!(c.adjust_exposure(-30.0), Rgb::new(0, 0, 0));
+    // Exposure preserves the ratios of linear channels, unlike scaling the
+    // encoded values.
+    let linear = |c: Rgb| c.ratios().map(crate::utility::srgb_to_linear);
+    let [r0, g0, _] = linear(c);
+    let [r1, g1, _] = linear(c.adjust_exposure(-2.0));
+    assert!(close(r0 / g0, r1 / g1, 0.05));
+}
+
+/// Tests extracting a gradient ramp from a pixel buffer along each axis.
+#[test]
+fn extract_image_ramp() {
+    use crate::analysis::Axis;
+    use crate::analysis::extract_ramp;
+
+    // A 16x3 image fading from black to white, with noise across rows.
+    let width = 16;
+    let mut pixels = Vec::new();
+    for y in 0..3u8 {
+        for x in 0..width as u8 {
+            let v = x * 17;
+            pixels.push(Rgb::new(v, v, v.saturating_add(y)));
+        }
+    }
+    let ramp = extract_ramp(&pixels, width, Axis::Horizontal).unwrap();
+    assert!(ramp.stops().len() <= 16);
+    assert!(Rgb::distance(ramp.color(0.0), Rgb::new(0, 0, 1)) <= 1.0);
+    assert!(Rgb::distance(ramp.color(1.0), Rgb::new(255, 255, 255)) <= 1.0);
+    let steps: Vec<Rgb> = ramp.steps(8).collect();
+    assert!(steps.windows(2).all(|w| w[0].g <= w[1].g));
+
+    // Averaging is done in linear light, so black and white average to a
+    // lighter gray than the encoded midpoint.
+    let black = Rgb::new(0, 0, 0);
+    let white = Rgb::new(255, 255, 255);
+    let pixels = vec![black, white, black, white];
+    let ramp = extract_ramp(&pixels, 2, Axis::Vertical).unwrap();
+    assert_eq!(ramp.color(0.0), Rgb::new(188, 188, 188));
+    assert_eq!(ramp.stops().len(), 2);
+
+    assert!(extract_ramp(&[], 4, Axis::Vertical).is_none());
+}
+
+/// Tests brightness and contrast adjustments of colors and pixel buffers.
+#[test]
+fn brightness_and_contrast() {
+    use crate::adjust::ContrastPivot;
+    use crate::adjust::adjust_brightness;
+    use crate::adjust::adjust_contrast;
+
+    let color = Rgb::new(30, 128, 220);
+    assert_eq!(color.adjust_brightness(0.0), color);
+    assert_eq!(color.adjust_brightness(1.0), Rgb::new(255, 255, 255));
+    assert_eq!(color.adjust_brightness(-1.0), Rgb::new(0, 0, 0));
+    assert_eq!(color.adjust_contrast(1.0), color);
+    assert_eq!(color.adjust_contrast(0.0), Rgb::new(128, 128, 128));
+    let stronger = color.adjust_contrast(1.5);
+    assert!(stronger.r < color.r && stronger.b > color.b);
+
+    // Slices match the per-color adjustments about the midpoint.
+    let mut pixels = vec![color, Rgb::new(200, 10, 90)];
+    let expected: Vec<Rgb> = pixels
+        .iter()
+        .map(|p| p.adjust_brightness(-0.1).adjust_contrast(2.0))
+        .collect();
+    adjust_brightness(&mut pixels, -0.1);
+    adjust_contrast(&mut pixels, 2.0, ContrastPivot::Midpoint);
+    assert_eq!(pixels, expected);
+
+    // Pivoting about the mean keeps the mean of a light image.
+    let mut light = vec![Rgb::new(200, 200, 200), Rgb::new(240, 240, 240)];
+    adjust_contrast(&mut light, 0.5, ContrastPivot::Mean);
+    assert_eq!(light, [Rgb::new(210, 210, 210), Rgb::new(230, 230, 230)]);
+
+    let mut empty: Vec<Rgb> = Vec::new();
+    adjust_contrast(&mut empty, 2.0, ContrastPivot::Mean);
+    assert!(empty.is_empty());
+}
+
+/// Tests recoloring pixels from a source palette onto a target palette.
+#[test]
+fn palette_recolor() {
+    use crate::adjust::recolor;
+    use crate::adjust::recolor_soft;
+    use crate::metric::Euclidean;
+
+    let source: Palette = vec![
+        ("red", Rgb::new(255, 0, 0)),
+        ("green", Rgb::new(0, 255, 0)),
+        ("blue", Rgb::new(0, 0, 255)),
+    ].into_iter().collect();
+    let target: Palette = vec![
+        ("red", Rgb::new(0, 0, 0)),
+        ("green", Rgb::new(128, 128, 128)),
+        ("blue", Rgb::new(255, 255, 255)),
+    ].into_iter().collect();
+
+    let original = vec![
+        Rgb::new(250, 20, 10),
+        Rgb::new(10, 200, 40),
+        Rgb::new(30, 30, 220),
+        Rgb::new(255, 0, 0),
+    ];
+    let mut pixels = original.clone();
+    recolor(&mut pixels, &source, &target, Euclidean::Oklab);
+    assert_eq!(pixels, [
+        Rgb::new(0, 0, 0),
+        Rgb::new(128, 128, 128),
+        Rgb::new(255, 255, 255),
+        Rgb::new(0, 0, 0),
+    ]);
+
+    // Soft blending keeps exact matches and pulls toward the nearest target,
+    // approaching the hard mapping as the falloff grows.
+    let mut soft = original.clone();
+    recolor_soft(&mut soft, &source, &target, Euclidean::Oklab, 2.0);
+    assert_eq!(soft[3], Rgb::new(0, 0, 0));
+    assert!(soft[0].r < 128 && soft[2].r > 128);
+    let mut sharp = original.clone();
+    recolor_soft(&mut sharp, &source, &target, Euclidean::Oklab, 50.0);
+    for (s, p) in sharp.iter().zip(pixels.iter()) {
+        assert!(Rgb::distance(*s, *p) <= 2.0);
+    }
+
+    // An empty source palette leaves the pixels unchanged.
+    let mut unchanged = original.clone();
+    recolor(&mut unchanged, &Palette::new(), &Palette::new(),
+        Euclidean::Oklab);
+    recolor_soft(&mut unchanged, &Palette::new(), &Palette::new(),
+        Euclidean::Oklab, 2.0);
+    assert_eq!(unchanged, original);
+}
+
+/// Tests color matrix constructors, application, and composition.
+#[test]
+fn color_matrix_transforms() {
+    use crate::color_matrix::ColorMatrix;
+    use crate::Rgba;
+
+    let colors = [
+        Rgba::new(200, 80, 40, 255),
+        Rgba::new(12, 160, 230, 128),
+        Rgba::new(0, 0, 0, 0),
+    ];
+    for &c in colors.iter() {
+        assert_eq!(ColorMatrix::identity().apply(c), c);
+        assert_eq!(ColorMatrix::default().apply(c), c);
+        assert_eq!(ColorMatrix::saturation(1.0).apply(c), c);
+        assert_eq!(ColorMatrix::hue_rotate(0.0).apply(c), c);
+        assert_eq!(ColorMatrix::sepia(0.0).apply(c), c);
+        assert_eq!(ColorMatrix::sepia(0.6).apply(c).rgb(), c.rgb().sepia(0.6));
+        // The alpha is preserved by the color filters.
+        assert_eq!(ColorMatrix::hue_rotate(77.0).apply(c).a, c.a);
+    }
+
+    // Composition applies the right operand first.
+    let swap = ColorMatrix::new([
+        [0.0, 0.0, 1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0, 0.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0, 0.0],
+    ]);
+    let brighten = ColorMatrix::new([
+        [1.0, 0.0, 0.0, 0.0, 0.2],
+        [0.0, 1.0, 0.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0, 0.0],
+    ]);
+    let c = Rgba::new(0, 0, 100, 255);
+    assert_eq!((brighten * swap).apply(c), Rgba::new(151, 0, 0, 255));
+    assert_eq!((swap * brighten).apply(c), Rgba::new(100, 0, 51, 255));
+    assert_eq!(ColorMatrix::identity() * swap, swap);
+    assert_eq!(swap * ColorMatrix::identity(), swap);
+    let twice = ColorMatrix::hue_rotate(60.0) * ColorMatrix::hue_rotate(60.0);
+    let once = ColorMatrix::hue_rotate(120.0);
+    for (a, b) in twice.rows().iter().zip(once.rows().iter()) {
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| close(*x, *y, 0.01)));
+    }
+}
+
+/// Tests selective color replacement with tolerance and feathering.
+#[test]
+fn selective_color_replacement() {
+    use crate::adjust::replace_color;
+
+    let target = Rgb::new(40, 90, 200);
+    let replacement = Rgb::new(200, 60, 40);
+    let near = Rgb::new(50, 95, 190);
+    let edge = Rgb::new(90, 110, 170);
+    let far = Rgb::new(240, 230, 20);
+    let original = vec![target, near, edge, far];
+
+    // With no softness the selection has a hard edge.
+    let mut hard = original.clone();
+    replace_color(&mut hard, target, replacement, 0.05, 0.0);
+    assert_eq!(hard[0], replacement);
+    assert!(hard[1].r > 150 && hard[1].b < 100);
+    assert_eq!(hard[2], edge);
+    assert_eq!(hard[3], far);
+
+    // Feathering partially shifts colors just beyond the tolerance.
+    let mut soft = original.clone();
+    replace_color(&mut soft, target, replacement, 0.05, 0.2);
+    assert_eq!(soft[0], replacement);
+    assert_ne!(soft[2], edge);
+    assert!(Rgb::distance(soft[2], replacement)
+        > Rgb::distance(soft[1], replacement));
+    assert_eq!(soft[3], far);
+
+    // Replacing a color with itself changes nothing.
+    let mut same = original.clone();
+    replace_color(&mut same, target, target, 0.5, 0.5);
+    assert_eq!(same, original);
+}
+
+/// Tests masking pixels by hue band and applying masked adjustments.
+#[test]
+fn hue_masks() {
+    use crate::adjust::adjust_brightness;
+    use crate::adjust::apply_masked;
+    use crate::adjust::mask_by_hue;
+
+    // Sweep the full hue circle at a fixed lightness and chroma.
+    let pixels: Vec<Rgb> = (0..36)
+        .map(|i| Rgb::from(Oklch::new(0.7, 0.12, i as f32 * 10.0)))
+        .collect();
+    let hue = |c: Rgb| Oklch::from(c).h;
+
+    // A band wrapping through 0 selects the reds.
+    let mask = mask_by_hue(&pixels, [340.0, 40.0], 0.02);
+    for (&pixel, &weight) in pixels.iter().zip(mask.iter()) {
+        let h = hue(pixel);
+        if h >= 342.0 || h <= 38.0 {
+            assert_eq!(weight, 1.0);
+        } else if (52.0..=328.0).contains(&h) {
+            assert_eq!(weight, 0.0);
+        } else {
+            assert!((0.0..=1.0).contains(&weight));
+        }
+    }
+    // The falloff is smooth rather than a hard edge.
+    assert!(mask.iter().any(|&w| w > 0.0 && w < 1.0));
+
+    // Grays are excluded regardless of hue.
+    let grays = [Rgb::new(0, 0, 0), Rgb::new(128, 128, 128)];
+    assert_eq!(mask_by_hue(&grays, [0.0, 360.0], 0.02), [0.0, 0.0]);
+    assert!(mask_by_hue(&pixels, [0.0, 360.0], 0.02)
+        .iter()
+        .all(|&w| w == 1.0));
+
+    // Masked adjustments leave unselected pixels alone.
+    let mut edited = pixels.clone();
+    apply_masked(&mut edited, &mask, |c| {
+        let mut single = [c];
+        adjust_brightness(&mut single, 0.2);
+        single[0]
+    });
+    for ((e, p), &w) in edited.iter().zip(pixels.iter()).zip(mask.iter()) {
+        if w == 0.0 { assert_eq!(e, p); }
+        if w == 1.0 { assert_eq!(*e, p.adjust_brightness(0.2)); }
+    }
+}
+
+/// Tests white balance adjustment by chromatic adaptation.
+#[test]
+fn white_balance_shifts() {
+    use crate::adjust::white_balance;
+
+    let original = vec![
+        Rgb::new(128, 128, 128),
+        Rgb::new(200, 200, 200),
+        Rgb::new(60, 120, 200),
+        Rgb::new(210, 90, 40),
+    ];
+
+    // No shift leaves the pixels nearly unchanged.
+    let mut same = original.clone();
+    white_balance(&mut same, 0.0, 0.0);
+    for (a, b) in same.iter().zip(original.iter()) {
+        assert!(Rgb::distance(*a, *b) <= 2.0);
+    }
+
+    // Temperature shifts warm or cool the neutral grays.
+    let mut warm = original.clone();
+    white_balance(&mut warm, 40.0, 0.0);
+    assert!(warm[0].r > warm[0].g && warm[0].g > warm[0].b);
+    let mut cool = original.clone();
+    white_balance(&mut cool, -40.0, 0.0);
+    assert!(cool[0].b > cool[0].g && cool[0].g > cool[0].r);
+
+    // Tint shifts move the grays toward magenta or green.
+    let mut magenta = original.clone();
+    white_balance(&mut magenta, 0.0, 0.01);
+    assert!(magenta[0].g < magenta[0].r && magenta[0].g < magenta[0].b);
+    let mut green = original.clone();
+    white_balance(&mut green, 0.0, -0.01);
+    assert!(green[0].g > green[0].r && green[0].g > green[0].b);
+
+    // A warm cast is removed by cooling, bringing the grays back toward
+    // neutral.
+    let mut cast = warm.clone();
+    white_balance(&mut cast, -40.0, 0.0);
+    let spread = |c: Rgb| c.r.max(c.g).max(c.b) - c.r.min(c.g).min(c.b);
+    assert!(spread(cast[0]) < spread(warm[0]));
+
+    // Black images are unchanged.
+    let mut black = vec![Rgb::new(0, 0, 0); 4];
+    white_balance(&mut black, 40.0, 0.01);
+    assert_eq!(black, [Rgb::new(0, 0, 0); 4]);
+}
+
+/// Tests levels and curves adjustments and their compiled lookup tables.
+#[test]
+fn levels_and_curves() {
+    use crate::adjust::Curve;
+    use crate::adjust::Levels;
+    use crate::adjust::Lut;
+
+    let colors = [
+        Rgb::new(0, 0, 0),
+        Rgb::new(12, 130, 250),
+        Rgb::new(77, 180, 33),
+        Rgb::new(255, 255, 255),
+    ];
+
+    // Defaults are the identity.
+    for &c in colors.iter() {
+        assert_eq!(Levels::default().apply(c), c);
+        assert_eq!(Levels::default().lut().apply(c), c);
+        assert_eq!(Curve::default().lut().apply(c), c);
+        assert_eq!(Lut::default().apply(c), c);
+    }
+
+    // Gamma brightens or darkens the midtones but keeps the end points.
+    let bright = Levels { gamma: 2.0, ..Levels::default() }.table();
+    let dark = Levels { gamma: 0.5, ..Levels::default() }.table();
+    assert_eq!((bright[0], bright[255]), (0, 255));
+    assert!(bright[128] > 128 && dark[128] < 128);
+
+    // Curves pass through their control points and are monotone when the
+    // points are.
+    let curve = Curve::new()
+        .with_point(0.2, 0.1)
+        .with_point(0.5, 0.6)
+        .with_point(0.9, 0.95);
+    assert!(close(curve.value(0.5), 0.6, 0.001));
+    assert!(close(curve.value(0.2), 0.1, 0.001));
+    let table = curve.table();
+    assert!(table.windows(2).all(|w| w[0] <= w[1]));
+
+    // Curves do not overshoot between control points.
+    let steep = Curve::new().with_point(0.1, 0.9).with_point(0.9, 1.0);
+    assert!((0..=100).all(|i| steep.value(i as f32 / 100.0) <= 1.0));
+    assert!(steep.table().windows(2).all(|w| w[0] <= w[1]));
+
+    // Points can be replaced and removed.
+    let mut edited = curve.clone();
+    edited.add_point(0.5, 0.5);
+    assert_eq!(edited.points().len(), 5);
+    edited.remove_point(0.5);
+    edited.remove_point(0.2);
+    edited.remove_point(0.9);
+    assert_eq!(edited, Curve::new());
+
+    // Compiled tables match direct application and compose in order.
+    let levels = Levels { black: 0.1, white: 0.9, gamma: 1.2 };
+    let combined = levels.lut().then(&curve.lut());
+    let mut pixels = colors.to_vec();
+    combined.apply_pixels(&mut pixels);
+    for (p, &c) in pixels.iter().zip(colors.iter()) {
+        assert_eq!(*p, curve.apply(levels.apply(c)));
+    }
+}
+
+/// Tests split-toning of shadows and highlights.
+#[test]
+fn split_toning() {
+    use crate::adjust::SplitTone;
+
+    let grays: Vec<Rgb> = (0..=8).map(|i| {
+        let v = (i * 255 / 8) as u8;
+        Rgb::new(v, v, v)
+    }).collect();
+
+    // No saturation is the identity.
+    for &c in grays.iter() {
+        assert_eq!(SplitTone::default().apply(c), c);
+    }
+
+    let split = SplitTone {
+        shadow_hue: 240.0,
+        shadow_saturation: 0.5,
+        highlight_hue: 60.0,
+        highlight_saturation: 0.5,
+        balance: 0.0,
+    };
+    let dark = split.apply(Rgb::new(50, 50, 50));
+    let light = split.apply(Rgb::new(210, 210, 210));
+    assert!(dark.b > dark.r && dark.b > dark.g);
+    assert!(light.r > light.b && light.g > light.b);
+    assert_eq!(split.apply(Rgb::new(0, 0, 0)), Rgb::new(0, 0, 0));
+
+    // Toning preserves the luminance of the colors.
+    let luma = crate::contrast::relative_luminance::<Rgb>;
+    assert!(close(luma(dark), luma(Rgb::new(50, 50, 50)), 0.01));
+    assert!(close(luma(light), luma(Rgb::new(210, 210, 210)), 0.01));
+
+    // The balance shifts which tint dominates the midtones.
+    let mid = Rgb::new(128, 128, 128);
+    let shadows = SplitTone { balance: -1.0, ..split }.apply(mid);
+    let highlights = SplitTone { balance: 1.0, ..split }.apply(mid);
+    assert!(shadows.b > shadows.r);
+    assert!(highlights.r > highlights.b);
+
+    let mut pixels = grays.clone();
+    split.apply_pixels(&mut pixels);
+    for (p, &c) in pixels.iter().zip(grays.iter()) {
+        assert_eq!(*p, split.apply(c));
+    }
+}
+
+/// Tests lift, gamma, and gain color grading.
+#[test]
+fn lift_gamma_gain() {
+    use crate::adjust::LiftGammaGain;
+
+    let colors = [
+        Rgb::new(0, 0, 0),
+        Rgb::new(30, 140, 220),
+        Rgb::new(128, 128, 128),
+        Rgb::new(255, 255, 255),
+    ];
+    let neutral = LiftGammaGain::default();
+    assert!(neutral.is_neutral());
+    for &c in colors.iter() {
+        assert_eq!(neutral.apply(c), c);
+        assert_eq!(neutral.lut().apply(c), c);
+    }
+
+    // Lift raises black but keeps white; gain scales white but keeps black.
+    let lift = LiftGammaGain { lift: [0.1; 3], ..neutral };
+    assert!(lift.apply(Rgb::new(0, 0, 0)).r > 0);
+    assert_eq!(lift.apply(Rgb::new(255, 255, 255)), Rgb::new(255, 255, 255));
+    let gain = LiftGammaGain { gain: [0.5; 3], ..neutral };
+    assert_eq!(gain.apply(Rgb::new(0, 0, 0)), Rgb::new(0, 0, 0));
+    assert!(gain.apply(Rgb::new(255, 255, 255)).r < 255);
+
+    // Gamma bends the midtones and keeps both ends.
+    let gamma = LiftGammaGain { gamma: [2.0, 1.0, 0.5], ..neutral };
+    assert!(!gamma.is_neutral());
+    let mid = gamma.apply(Rgb::new(128, 128, 128));
+    assert!(mid.r > 128 && mid.g == 128 && mid.b < 128);
+    assert_eq!(gamma.apply(Rgb::new(0, 0, 0)), Rgb::new(0, 0, 0));
+    assert_eq!(gamma.apply(Rgb::new(255, 255, 255)),
+        Rgb::new(255, 255, 255));
+
+    let grade = LiftGammaGain {
+        lift: [0.02, 0.0, -0.01],
+        gamma: [1.1, 0.9, 1.0],
+        gain: [1.0, 1.1, 0.9],
+    };
+    let mut pixels = colors.to_vec();
+    grade.lut().apply_pixels(&mut pixels);
+    for (p, &c) in pixels.iter().zip(colors.iter()) {
+        assert_eq!(*p, grade.apply(c));
+    }
+}
+
+/// Tests vibrance adjustments favoring muted colors and protecting skin.
+#[test]
+fn vibrance_adjustment() {
+    let chroma = |c: Rgb| Oklch::from(c).c;
+    let gain = |c: Rgb, amount: f32| chroma(c.vibrance(amount)) / chroma(c);
+
+    // Muted colors gain more than saturated ones, which are left alone.
+    let muted = Rgb::new(120, 130, 110);
+    let moderate = Rgb::new(90, 150, 90);
+    let saturated = Rgb::new(0, 200, 0);
+    assert!(gain(muted, 0.8) > gain(moderate, 0.8));
+    assert!(close(gain(saturated, 0.8), 1.0, 0.02));
+
+    // Skin tones are boosted less than other hues of the same chroma.
+    let skin = Rgb::from(Oklch::new(0.7, 0.06, 50.0));
+    let teal = Rgb::from(Oklch::new(0.7, 0.06, 200.0));
+    assert!(gain(skin, 0.8) < gain(teal, 0.8));
+    assert!(gain(skin, 0.8) > 1.0);
+
+    // Negative amounts mute, zero is the identity, and grays stay gray.
+    assert!(gain(muted, -0.8) < 1.0);
+    assert_eq!(muted.vibrance(0.0), muted);
+    assert_eq!(Rgb::new(128, 128, 128).vibrance(1.0),
+        Rgb::new(128, 128, 128));
+}
+
+/// Tests baking color transforms into 3D lookup tables and exporting them.
+#[test]
+fn lut3d_baking() {
+    use crate::adjust::Lut3d;
+
+    let colors = [
+        Rgb::new(0, 0, 0),
+        Rgb::new(255, 255, 255),
+        Rgb::new(12, 130, 250),
+        Rgb::new(201, 77, 33),
+    ];
+
+    // An identity table reproduces its inputs exactly.
+    let identity = Lut3d::bake(|c| c, 2);
+    assert_eq!(identity.size(), 2);
+    for &c in colors.iter() {
+        assert_eq!(identity.apply(c), c);
+    }
+
+    // Channel-mixing transforms are reproduced closely.
+    let swap = |c: Rgb| Rgb::new(c.b, c.r, c.g).rotate_hue(30.0);
+    let lut = Lut3d::bake(swap, 33);
+    let mut pixels = colors.to_vec();
+    lut.apply_pixels(&mut pixels);
+    for (p, &c) in pixels.iter().zip(colors.iter()) {
+        assert!(Rgb::distance(*p, swap(c)) < 12.0);
+    }
+
+    // The cube export lists each entry with red varying fastest.
+    let invert = Lut3d::bake(|c| c.invert(), 2);
+    let mut cube = Vec::new();
+    invert.write_cube(&mut cube).unwrap();
+    let text = String::from_utf8(cube).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 3 + 8);
+    assert_eq!(lines[0], "LUT_3D_SIZE 2");
+    assert_eq!(lines[3], "1.000000 1.000000 1.000000");
+    assert_eq!(lines[4], "0.000000 1.000000 1.000000");
+    assert_eq!(lines[10], "0.000000 0.000000 0.000000");
+}
+
+/// Tests standard illuminant chromaticities and white points.
+#[test]
+fn illuminant_white_points() {
+    use crate::illuminant::Illuminant;
+
+    assert_eq!(Illuminant::default(), Illuminant::D65);
+
+    // The D65 white point matches the reference white used by Lab.
+    let d65 = Illuminant::D65.white_point();
+    assert!(close(d65.x, 0.95047, 0.001));
+    assert!(close(d65.z, 1.08883, 0.001));
+
+    let e = Illuminant::E.white_point();
+    assert!(close(e.x, 1.0, 0.0001) && close(e.z, 1.0, 0.0001));
+
+    // Warmer illuminants have white points shifted toward red.
+    let a = Illuminant::A.white_point();
+    assert!(a.x > d65.x && a.z < d65.z);
+
+    // Each white point reproduces its chromaticity.
+    for &illuminant in [Illuminant::D50, Illuminant::F2, Illuminant::F11]
+        .iter()
+    {
+        let w = illuminant.white_point();
+        let sum = w.x + w.y + w.z;
+        let [x, y] = illuminant.chromaticity();
+        assert!(close(w.x / sum, x, 0.0001) && close(w.y / sum, y, 0.0001));
+    }
+}
+
+/// Tests chromatic adaptation between illuminants.
+#[test]
+fn chromatic_adaptation() {
+    use crate::illuminant::AdaptationMethod;
+    use crate::illuminant::Illuminant;
+    use crate::Xyz;
+
+    let methods = [
+        AdaptationMethod::Bradford,
+        AdaptationMethod::VonKries,
+        AdaptationMethod::Cat02,
+        AdaptationMethod::Cat16,
+    ];
+
+    // Every method maps the source white point to the target white point.
+    let d65 = Illuminant::D65.white_point();
+    let d50 = Illuminant::D50.white_point();
+    for &method in methods.iter() {
+        let white = d65.adapt(Illuminant::D65, Illuminant::D50, method);
+        assert!(Xyz::distance(white, d50) < 0.0001);
+    }
+
+    // The Bradford D65 to D50 transform matches the ICC reference.
+    let red = Xyz { x: 0.4124, y: 0.2126, z: 0.0193 };
+    let adapted = red.adapt(
+        Illuminant::D65,
+        Illuminant::D50,
+        AdaptationMethod::Bradford);
+    assert!(close(adapted.x, 0.4361, 0.001));
+    assert!(close(adapted.y, 0.2225, 0.001));
+    assert!(close(adapted.z, 0.0139, 0.001));
+
+    // Adapting there and back again is lossless.
+    for &method in methods.iter() {
+        let round_trip = red
+            .adapt(Illuminant::D65, Illuminant::A, method)
+            .adapt(Illuminant::A, Illuminant::D65, method);
+        assert!(Xyz::distance(round_trip, red) < 0.0001);
+    }
+
+    // Adapting to the same illuminant leaves colors unchanged.
+    assert_eq!(
+        red.adapt(Illuminant::D50, Illuminant::D50, AdaptationMethod::Cat16),
+        red);
+}
+
+/// Tests D50-relative Lab conversions of adapted colors.
+#[test]
+fn lab_d50_round_trip() {
+    use crate::illuminant::AdaptationMethod;
+    use crate::illuminant::Illuminant;
+    use crate::Xyz;
+
+    // The white point of the reference illuminant is Lab white.
+    let white = Lab::from_xyz(Illuminant::D50.white_point(), Illuminant::D50);
+    assert!(close(white.l, 100.0, 0.0001));
+    assert!(close(white.a, 0.0, 0.0001));
+    assert!(close(white.b, 0.0, 0.0001));
+    let white = Lab::new(100.0, 0.0, 0.0).to_xyz(Illuminant::D50);
+    assert!(Xyz::distance(white, Illuminant::D50.white_point()) < 0.0001);
+
+    // The D65 conversions agree with the D65 white point.
+    let color = Rgb::new(200, 60, 140);
+    let xyz = Xyz::from(color);
+    assert_eq!(Lab::from(xyz), Lab::from_xyz(xyz, Illuminant::D65));
+    assert!(Lab::distance(Lab::from(xyz), Lab::from(color)) < 0.0001);
+    assert!(Xyz::distance(Xyz::from(Lab::from(xyz)), xyz) < 0.0001);
+
+    // sRGB colors survive a round trip through D50 Lab.
+    for color in [
+        Rgb::new(0, 0, 0),
+        Rgb::new(255, 255, 255),
+        Rgb::new(200, 60, 140),
+        Rgb::new(12, 188, 255),
+    ] {
+        let d50 = Xyz::from(color).adapt(
+            Illuminant::D65,
+            Illuminant::D50,
+            AdaptationMethod::Bradford);
+        let lab = Lab::from_xyz(d50, Illuminant::D50);
+        let round_trip = lab
+            .to_xyz(Illuminant::D50)
+            .adapt(Illuminant::D50, Illuminant::D65, AdaptationMethod::Bradford);
+        assert_eq!(Rgb::from(round_trip), color);
+    }
+}
+
+/// Tests RGB space matrices derived from primaries and white points.
+#[test]
+#[allow(clippy::excessive_precision)]
+fn rgb_space_matrices() {
+    use crate::illuminant::Illuminant;
+    use crate::rgb_space::AdobeRgb;
+    use crate::rgb_space::DisplayP3;
+    use crate::rgb_space::Rec2020;
+    use crate::rgb_space::RgbSpace;
+    use crate::rgb_space::Srgb;
+    use crate::utility::matrix_product;
+
+    // The derived sRGB matrices match the published values.
+    let published = [
+        [0.4124564, 0.3575761, 0.1804375],
+        [0.2126729, 0.7151522, 0.0721750],
+        [0.0193339, 0.1191920, 0.9503041],
+    ];
+    let m = Srgb::XYZ_FROM_LINEAR;
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!(close(m[i][j], published[i][j], 0.0002));
+        }
+    }
+
+    // The luminance row of Display P3 and Rec. 2020.
+    let p3 = DisplayP3::XYZ_FROM_LINEAR[1];
+    assert!(close(p3[0], 0.2290, 0.0005));
+    assert!(close(p3[1], 0.6917, 0.0005));
+    let rec2020 = Rec2020::XYZ_FROM_LINEAR[1];
+    assert!(close(rec2020[0], 0.2627, 0.0005));
+    assert!(close(rec2020[2], 0.0593, 0.0005));
+
+    fn check<S: RgbSpace>() {
+        // White maps to the white point.
+        let m = S::XYZ_FROM_LINEAR;
+        let white = S::WHITE_POINT.white_point();
+        assert!(close(m[0].iter().sum(), white.x, 0.0001));
+        assert!(close(m[1].iter().sum(), white.y, 0.0001));
+        assert!(close(m[2].iter().sum(), white.z, 0.0001));
+
+        // The matrices are inverses.
+        let identity = matrix_product(S::LINEAR_FROM_XYZ, m);
+        for (i, row) in identity.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!(close(v, expected, 0.0001));
+            }
+        }
+    }
+    check::<Srgb>();
+    check::<DisplayP3>();
+    check::<AdobeRgb>();
+    check::<Rec2020>();
+
+    assert_eq!(Srgb::WHITE_POINT, Illuminant::D65);
+}
+
+/// Tests that conversions between Rgb and Xyz apply the sRGB transfer
+/// function.
+#[test]
+fn rgb_xyz_transfer_function() {
+    use crate::Xyz;
+
+    // Middle gray has a linear luminance of about 0.2.
+    let gray = Xyz::from(Rgb::new(128, 128, 128));
+    assert!(close(gray.y, 0.2158, 0.001));
+
+    // White maps to the D65 white point.
+    let white = Xyz::from(Rgb::new(255, 255, 255));
+    assert!(close(white.x, 0.9505, 0.001));
+    assert!(close(white.y, 1.0, 0.0001));
+    assert!(close(white.z, 1.0889, 0.001));
+
+    // The conversion round trips.
+    for &color in [
+        Rgb::new(0, 0, 0),
+        Rgb::new(1, 2, 3),
+        Rgb::new(200, 80, 40),
+        Rgb::new(18, 250, 128),
+        Rgb::new(255, 255, 255),
+    ].iter()
+    {
+        assert_eq!(Rgb::from(Xyz::from(color)), color);
+    }
+
+    // The deprecated conversions keep the previous behavior.
+    #[allow(deprecated)]
+    {
+        let encoded = Xyz::from_encoded_rgb(Rgb::new(128, 128, 128));
+        assert!(close(encoded.y, 128.0 / 255.0, 0.001));
+        let color = Rgb::new(200, 80, 40);
+        let round_trip = Xyz::from_encoded_rgb(color).to_encoded_rgb();
+        assert!(Rgb::distance(round_trip, color) <= 3.0);
+    }
+}
+
+/// Tests transfer function encoding and decoding.
+#[test]
+fn transfer_functions() {
+    use crate::rgb_space::AdobeRgb;
+    use crate::rgb_space::RgbSpace;
+    use crate::transfer::TransferFunction;
+
+    let functions = [
+        TransferFunction::Linear,
+        TransferFunction::Srgb,
+        TransferFunction::Rec709,
+        TransferFunction::Gamma(2.2),
+        TransferFunction::Pq,
+        TransferFunction::Hlg,
+    ];
+
+    // Each function fixes the endpoints and round trips.
+    for &f in functions.iter() {
+        assert!(close(f.encode(0.0), 0.0, 0.0001));
+        assert!(close(f.encode(1.0), 1.0, 0.0001));
+        for i in 0..=20 {
+            let v = i as f32 / 20.0;
+            assert!(close(f.decode(f.encode(v)), v, 0.0001));
+        }
+    }
+
+    // The sRGB function matches the conversion used by Rgb.
+    let color = Rgb::new(200, 80, 40);
+    assert_eq!(TransferFunction::Srgb.decode_rgb(color),
+        color.ratios().map(crate::utility::srgb_to_linear));
+    assert_eq!(TransferFunction::Srgb.encode_rgb(
+        TransferFunction::Srgb.decode_rgb(color)), color);
+
+    // PQ reference points: 100 nits encodes to about 0.508, and HLG
+    // reference white at 1/12 of the peak encodes to 0.5.
+    assert!(close(TransferFunction::Pq.encode(0.01), 0.5081, 0.001));
+    assert!(close(TransferFunction::Hlg.encode(1.0 / 12.0), 0.5, 0.0001));
+
+    // Negative linear values are clamped.
+    assert_eq!(TransferFunction::Gamma(2.2).encode(-1.0), 0.0);
+
+    assert_eq!(AdobeRgb::TRANSFER_FUNCTION,
+        TransferFunction::Gamma(563.0 / 256.0));
+}
+
+/// Returns the bytes of an ICC profile with the given header fields and tags.
+#[cfg(feature = "icc")]
+fn icc_profile_bytes(
+    version: u8,
+    class: &[u8; 4],
+    space: &[u8; 4],
+    pcs: &[u8; 4],
+    tags: &[(&[u8; 4], Vec<u8>)])
+    -> Vec<u8>
+{
+    let mut data = vec![0; 128];
+    data[8] = version;
+    data[12..16].copy_from_slice(class);
+    data[16..20].copy_from_slice(space);
+    data[20..24].copy_from_slice(pcs);
+    data[36..40].copy_from_slice(b"acsp");
+    for (i, v) in [0.9642f32, 1.0, 0.8249].iter().enumerate() {
+        data[68 + i * 4..72 + i * 4].copy_from_slice(&icc_fixed(*v));
+    }
+
+    data.extend_from_slice(&(tags.len() as u32).to_be_bytes());
+    let mut offset = 132 + tags.len() * 12;
+    for (signature, bytes) in tags {
+        data.extend_from_slice(*signature);
+        data.extend_from_slice(&(offset as u32).to_be_bytes());
+        data.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        offset += (bytes.len() + 3) & !3;
+    }
+    for (_, bytes) in tags {
+        data.extend_from_slice(bytes);
+        data.resize((data.len() + 3) & !3, 0);
+    }
+    let size = data.len() as u32;
+    data[0..4].copy_from_slice(&size.to_be_bytes());
+    data
+}
+
+/// Returns the ICC `s15Fixed16Number` encoding of the given value.
+#[cfg(feature = "icc")]
+fn icc_fixed(value: f32) -> [u8; 4] {
+    ((value * 65536.0).round() as i32).to_be_bytes()
+}
+
+/// Returns an ICC tag of the given type holding the given data.
+#[cfg(feature = "icc")]
+fn icc_tag(signature: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut tag = signature.to_vec();
+    tag.extend_from_slice(&[0; 4]);
+    tag.extend_from_slice(data);
+    tag
+}
+
+/// Returns an ICC tag holding the given `s15Fixed16Number` values.
+#[cfg(feature = "icc")]
+fn icc_fixed_tag(signature: &[u8; 4], values: &[f32]) -> Vec<u8> {
+    let data: Vec<u8> = values.iter().flat_map(|&v| icc_fixed(v)).collect();
+    icc_tag(signature, &data)
+}
+
+/// Returns an ICC `parametricCurveType` tag of the sRGB curve.
+#[cfg(feature = "icc")]
+fn icc_srgb_curve() -> Vec<u8> {
+    let mut data = vec![0, 3, 0, 0];
+    for &v in [2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045].iter() {
+        data.extend_from_slice(&icc_fixed(v));
+    }
+    icc_tag(b"para", &data)
+}
+
+/// Returns the bytes of a version 4 sRGB matrix/TRC display profile.
+#[cfg(feature = "icc")]
+fn icc_srgb_profile() -> Vec<u8> {
+    use crate::illuminant::adaptation_matrix;
+    use crate::illuminant::AdaptationMethod;
+    use crate::illuminant::Illuminant;
+    use crate::rgb_space::RgbSpace;
+    use crate::rgb_space::Srgb;
+    use crate::utility::matrix_product;
+
+    let chad = adaptation_matrix(
+        Illuminant::D65.white_point(),
+        Illuminant::D50.white_point(),
+        AdaptationMethod::Bradford);
+    let m = matrix_product(chad, Srgb::XYZ_FROM_LINEAR);
+    let colorant = |j: usize| [m[0][j], m[1][j], m[2][j]];
+
+    let mut desc = Vec::new();
+    desc.extend_from_slice(&1u32.to_be_bytes());
+    desc.extend_from_slice(&12u32.to_be_bytes());
+    desc.extend_from_slice(b"enUS");
+    let text: Vec<u8> = "sRGB test".encode_utf16()
+        .flat_map(|u| u.to_be_bytes())
+        .collect();
+    desc.extend_from_slice(&(text.len() as u32).to_be_bytes());
+    desc.extend_from_slice(&28u32.to_be_bytes());
+    desc.extend_from_slice(&text);
+
+    let chad_values: Vec<f32> = chad.iter().flatten().copied().collect();
+    let mut profile = icc_profile_bytes(4, b"mntr", b"RGB ", b"XYZ ", &[
+        (b"desc", icc_tag(b"mluc", &desc)),
+        (b"wtpt", icc_fixed_tag(b"XYZ ", &[0.9642, 1.0, 0.8249])),
+        (b"chad", icc_fixed_tag(b"sf32", &chad_values)),
+        (b"rXYZ", icc_fixed_tag(b"XYZ ", &colorant(0))),
+        (b"gXYZ", icc_fixed_tag(b"XYZ ", &colorant(1))),
+        (b"bXYZ", icc_fixed_tag(b"XYZ ", &colorant(2))),
+        (b"rTRC", icc_srgb_curve()),
+        (b"gTRC", icc_srgb_curve()),
+        (b"bTRC", icc_srgb_curve()),
+    ]);
+    profile[9] = 0x30;
+    profile
+}
+
+/// Tests parsing ICC profiles.
+#[cfg(feature = "icc")]
+#[test]
+fn icc_profile_parsing() {
+    use crate::icc::ColorSpaceSignature;
+    use crate::icc::IccError;
+    use crate::icc::IccProfile;
+    use crate::icc::LutKind;
+    use crate::icc::ProfileClass;
+    use crate::icc::RenderingIntent;
+    use crate::icc::Stage;
+    use crate::icc::ToneCurve;
+    use crate::utility::srgb_to_linear;
+
+    // A matrix/TRC profile.
+    let data = icc_srgb_profile();
+    let profile = IccProfile::read(&mut &data[..]).unwrap();
+    assert_eq!(profile.version(), (4, 3));
+    assert_eq!(profile.class(), ProfileClass::Display);
+    assert_eq!(profile.color_space(), ColorSpaceSignature::Rgb);
+    assert_eq!(profile.pcs(), ColorSpaceSignature::Xyz);
+    assert_eq!(profile.description(), Some("sRGB test"));
+    assert!(close(profile.pcs_illuminant().x, 0.9642, 0.0001));
+
+    let primaries = profile.primaries().unwrap();
+    let expected = [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]];
+    for (p, e) in primaries.iter().zip(expected.iter()) {
+        assert!(close(p[0], e[0], 0.001) && close(p[1], e[1], 0.001));
+    }
+    let white = profile.white_point();
+    assert!(close(white.x, 0.9504, 0.001) && close(white.z, 1.0889, 0.001));
+
+    let curve = &profile.matrix_trc().unwrap().curves[0];
+    for &v in [0.0, 0.02, 0.2, 0.5, 1.0].iter() {
+        assert!(close(curve.value(v), srgb_to_linear(v), 0.0001));
+    }
+    assert_eq!(profile.supported_intents().len(), 4);
+    assert!(profile.a_to_b(RenderingIntent::Perceptual).is_none());
+
+    // A LUT-based CMYK profile with a 16-bit AToB table and a BToA
+    // transform.
+    let mut mft2 = vec![4, 3, 2, 0];
+    for v in [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0].iter() {
+        mft2.extend_from_slice(&icc_fixed(*v));
+    }
+    mft2.extend_from_slice(&2u16.to_be_bytes());
+    mft2.extend_from_slice(&2u16.to_be_bytes());
+    let ramp = [0u16, 0xFFFF];
+    for _ in 0..4 {
+        for v in ramp.iter() { mft2.extend_from_slice(&v.to_be_bytes()); }
+    }
+    // Output the fraction of ink at each of the 16 grid points.
+    for corner in 0..16u32 {
+        let ink = corner.count_ones() as f32 / 4.0;
+        let v = ((1.0 - ink) * 65535.0) as u16;
+        for _ in 0..3 { mft2.extend_from_slice(&v.to_be_bytes()); }
+    }
+    for _ in 0..3 {
+        for v in ramp.iter() { mft2.extend_from_slice(&v.to_be_bytes()); }
+    }
+
+    let mut mba = vec![3, 4, 0, 0];
+    for offset in [32u32, 0, 0, 68, 0].iter() {
+        mba.extend_from_slice(&offset.to_be_bytes());
+    }
+    for _ in 0..3 { mba.extend_from_slice(&icc_tag(b"curv", &[0; 4])); }
+    let mut grid = [0u8; 16];
+    grid[..3].copy_from_slice(&[2, 2, 2]);
+    mba.extend_from_slice(&grid);
+    mba.extend_from_slice(&[1, 0, 0, 0]);
+    for corner in 0..8u32 {
+        let k = 255 - (corner.count_ones() * 85) as u8;
+        mba.extend_from_slice(&[0, 0, 0, k]);
+    }
+
+    let mut desc = b"CMYK test".to_vec();
+    desc.push(0);
+    let mut desc_data = (desc.len() as u32).to_be_bytes().to_vec();
+    desc_data.extend_from_slice(&desc);
+
+    let data = icc_profile_bytes(2, b"prtr", b"CMYK", b"Lab ", &[
+        (b"desc", icc_tag(b"desc", &desc_data)),
+        (b"A2B0", icc_tag(b"mft2", &mft2)),
+        (b"B2A0", icc_tag(b"mBA ", &mba)),
+    ]);
+    let profile = IccProfile::from_bytes(&data).unwrap();
+    assert_eq!(profile.version(), (2, 0));
+    assert_eq!(profile.class(), ProfileClass::Output);
+    assert_eq!(profile.color_space().channels(), Some(4));
+    assert_eq!(profile.pcs(), ColorSpaceSignature::Lab);
+    assert_eq!(profile.description(), Some("CMYK test"));
+    assert!(profile.matrix_trc().is_none() && profile.primaries().is_none());
+    assert_eq!(profile.supported_intents(), vec![RenderingIntent::Perceptual]);
+
+    // Missing intents fall back to the perceptual transform.
+    let a_to_b = profile.a_to_b(RenderingIntent::Saturation).unwrap();
+    assert_eq!(a_to_b.kind(), LutKind::Lut16);
+    assert_eq!((a_to_b.input_channels(), a_to_b.output_channels()), (4, 3));
+    assert!(matches!(a_to_b.stages()[0], Stage::Curves(_)));
+    let out = a_to_b.apply(&[1.0, 1.0, 0.0, 0.0]);
+    assert!(out.iter().all(|&v| close(v, 0.5, 0.0001)));
+    let out = a_to_b.apply(&[0.5, 0.0, 0.0, 0.0]);
+    assert!(out.iter().all(|&v| close(v, 0.875, 0.0001)));
+
+    let b_to_a = profile.b_to_a(RenderingIntent::Perceptual).unwrap();
+    assert_eq!(b_to_a.kind(), LutKind::BToA);
+    assert_eq!(b_to_a.stages()[0],
+        Stage::Curves(vec![ToneCurve::Identity; 3]));
+    let out = b_to_a.apply(&[1.0, 1.0, 0.0]);
+    assert!(close(out[3], 85.0 / 255.0, 0.0001));
+
+    // Errors.
+    let mut bad = data.clone();
+    bad[36] = b'x';
+    assert!(matches!(IccProfile::from_bytes(&bad),
+        Err(IccError::InvalidSignature)));
+    let mut bad = data.clone();
+    bad[8] = 5;
+    assert!(matches!(IccProfile::from_bytes(&bad),
+        Err(IccError::UnsupportedVersion(5))));
+    assert!(matches!(IccProfile::from_bytes(&data[..200]),
+        Err(IccError::Malformed(_))));
+    let bad = icc_profile_bytes(4, b"mntr", b"GRAY", b"XYZ ", &[
+        (b"kTRC", icc_tag(b"text", b"none")),
+    ]);
+    assert!(matches!(IccProfile::from_bytes(&bad),
+        Err(IccError::UnsupportedTagType(t)) if &t == b"text"));
+}
+
+/// Returns the bytes of a version 4 CMYK profile whose black ink alone sets
+/// the lightness, with the given media white point.
+#[cfg(feature = "icc")]
+fn icc_gray_ink_profile(media_white: [f32; 3]) -> Vec<u8> {
+    let identity = || icc_tag(b"curv", &[0; 4]);
+    let clut_header = |grid: &[u8]| {
+        let mut header = [0u8; 20];
+        header[..grid.len()].copy_from_slice(grid);
+        header[16] = 1;
+        header.to_vec()
+    };
+
+    // Device to PCS: the lightness is 1 - K, with neutral a and b.
+    let mut mab = vec![4, 3, 0, 0];
+    for offset in [148u32, 0, 0, 80, 32].iter() {
+        mab.extend_from_slice(&offset.to_be_bytes());
+    }
+    for _ in 0..4 { mab.extend_from_slice(&identity()); }
+    mab.extend_from_slice(&clut_header(&[2, 2, 2, 2]));
+    for corner in 0..16u32 {
+        let l = if corner & 1 == 1 { 0 } else { 255 };
+        mab.extend_from_slice(&[l, 128, 128]);
+    }
+    for _ in 0..3 { mab.extend_from_slice(&identity()); }
+
+    // PCS to device: K is 1 - L.
+    let mut mba = vec![3, 4, 0, 0];
+    for offset in [32u32, 0, 0, 68, 120].iter() {
+        mba.extend_from_slice(&offset.to_be_bytes());
+    }
+    for _ in 0..3 { mba.extend_from_slice(&identity()); }
+    mba.extend_from_slice(&clut_header(&[2, 2, 2]));
+    for corner in 0..8u32 {
+        let k = if corner & 4 == 4 { 0 } else { 255 };
+        mba.extend_from_slice(&[0, 0, 0, k]);
+    }
+    for _ in 0..4 { mba.extend_from_slice(&identity()); }
+
+    icc_profile_bytes(4, b"prtr", b"CMYK", b"Lab ", &[
+        (b"wtpt", icc_fixed_tag(b"XYZ ", &media_white)),
+        (b"A2B0", icc_tag(b"mAB ", &mab)),
+        (b"B2A0", icc_tag(b"mBA ", &mba)),
+    ])
+}
+
+/// Tests color conversion through ICC profiles.
+#[cfg(feature = "icc")]
+#[test]
+fn icc_profile_conversion() {
+    use crate::icc::convert_icc;
+    use crate::icc::convert_icc_buffer;
+    use crate::icc::IccError;
+    use crate::icc::IccProfile;
+    use crate::icc::IccTransform;
+    use crate::icc::RenderingIntent;
+
+    let srgb = IccProfile::from_bytes(&icc_srgb_profile()).unwrap();
+    let press = IccProfile::from_bytes(
+        &icc_gray_ink_profile([0.9642, 1.0, 0.8249])).unwrap();
+    let intent = RenderingIntent::RelativeColorimetric;
+
+    // Matrix/TRC profiles round trip through the PCS.
+    let identity = IccTransform::new(&srgb, &srgb, intent).unwrap();
+    for color in [[0.2, 0.4, 0.6], [1.0, 0.0, 0.5], [0.9, 0.9, 0.1]].iter() {
+        let out = identity.apply(color);
+        for i in 0..3 { assert!(close(out[i], color[i], 0.001)); }
+    }
+
+    // Separation of RGB into the press inks.
+    let cmyk = convert_icc(&srgb, &press, intent, &[1.0, 1.0, 1.0]).unwrap();
+    assert!(cmyk.iter().all(|&v| close(v, 0.0, 0.01)));
+    let cmyk = convert_icc(&srgb, &press, intent, &[0.0, 0.0, 0.0]).unwrap();
+    assert!(close(cmyk[3], 1.0, 0.01));
+
+    // Gray round trips through the press, and buffers convert each color.
+    let gray = [0.5, 0.5, 0.5, 0.25, 0.25, 0.25];
+    let separated = convert_icc_buffer(&srgb, &press, intent, &gray).unwrap();
+    assert_eq!(separated.len(), 8);
+    let expected = convert_icc(&srgb, &press, intent, &gray[3..]).unwrap();
+    assert_eq!(&separated[4..], &expected[..]);
+    let proof = convert_icc_buffer(&press, &srgb, intent, &separated)
+        .unwrap();
+    for (p, g) in proof.iter().zip(gray.iter()) {
+        assert!(close(*p, *g, 0.01));
+    }
+
+    // Absolute colorimetric proofs simulate the tint of the paper.
+    let paper = IccProfile::from_bytes(
+        &icc_gray_ink_profile([0.92, 0.95, 0.70])).unwrap();
+    let relative = convert_icc(&paper, &srgb, intent, &[0.0; 4]).unwrap();
+    assert!(relative.iter().all(|&v| close(v, 1.0, 0.01)));
+    let absolute = convert_icc(&paper, &srgb,
+        RenderingIntent::AbsoluteColorimetric, &[0.0; 4]).unwrap();
+    assert!(absolute[2] < absolute[0] - 0.05);
+
+    // Device links have no connection to the PCS.
+    let mut link = icc_gray_ink_profile([0.9642, 1.0, 0.8249]);
+    link[12..16].copy_from_slice(b"link");
+    let link = IccProfile::from_bytes(&link).unwrap();
+    assert!(matches!(IccTransform::new(&srgb, &link, intent),
+        Err(IccError::NoTransform)));
+}
+
+/// Tests gamut checks and clipping reports for the unbounded color spaces.
+#[test]
+fn gamut_checks() {
+    use crate::gamut::in_gamut;
+    use crate::rgb_space::DisplayP3;
+    use crate::rgb_space::Rec2020;
+    use crate::rgb_space::Srgb;
+    use crate::Xyz;
+
+    // Every sRGB color is displayable, including the corners of the cube.
+    for &rgb in [
+        Rgb::new(0, 0, 0), Rgb::new(255, 255, 255), Rgb::new(255, 0, 0),
+        Rgb::new(0, 255, 0), Rgb::new(0, 0, 255), Rgb::new(12, 200, 99),
+    ].iter() {
+        assert!(Xyz::from(rgb).is_displayable());
+        assert!(Lab::from(rgb).is_displayable());
+        assert!(Lch::from(rgb).is_displayable());
+        assert!(Oklab::from(rgb).is_displayable());
+        assert!(Oklch::from(rgb).is_displayable());
+        assert_eq!(Oklch::from(rgb).checked_rgb(), Ok(rgb));
+        assert!(in_gamut::<Rec2020>(Xyz::from(rgb)));
+    }
+
+    // A vivid green beyond sRGB but within the wider spaces.
+    let green = Oklch::new(0.85, 0.3, 145.0);
+    assert!(!green.is_displayable());
+    assert!(green.in_gamut::<DisplayP3>());
+    assert!(green.in_gamut::<Rec2020>());
+    assert!(!Oklab::from(green).in_gamut::<Srgb>());
+
+    // Clipping is reported with the clamped color.
+    let error = green.checked_rgb().unwrap_err();
+    assert_eq!(error.clamped(), Rgb::from(green));
+    assert!(error.excess() > 0.0);
+    assert!(Lab::new(50.0, 0.0, -150.0).checked_rgb().is_err());
+    assert!(Xyz { x: 1.2, y: 1.2, z: 1.2 }.checked_rgb().is_err());
+}
+
+/// Tests soft gamut compression of wide gamut colors into sRGB.
+#[test]
+fn gamut_compression() {
+    use crate::gamut::GamutCompression;
+    use crate::rgb_space::Rec2020;
+
+    let compression = GamutCompression::default();
+
+    // Colors well within sRGB are unchanged.
+    let muted = Oklch::new(0.7, 0.05, 200.0);
+    assert_eq!(compression.compress(muted), muted);
+    assert_eq!(compression.to_rgb(muted), Rgb::from(muted));
+
+    // Increasing chroma stays increasing after compression, where clipping
+    // would collapse it, and the lightness and hue are preserved.
+    let mut last = 0.0;
+    for i in 0..8 {
+        let color = Oklch::new(0.7, 0.1 + 0.03 * i as f32, 150.0);
+        let compressed = compression.compress_from::<Rec2020, _>(color);
+        assert!(compressed.is_displayable());
+        assert!(compressed.c > last);
+        assert_eq!((compressed.l, compressed.h), (color.l, color.h));
+        last = compressed.c;
+    }
+
+    // Chroma beyond the limit is clipped to the boundary.
+    let vivid = compression.compress(Oklch::new(0.5, 0.45, 20.0));
+    assert!(vivid.is_displayable());
+    assert!(!Oklch { c: vivid.c + 0.01, ..vivid }.is_displayable());
+}
+
+/// Tests the HDR tone mapping operators.
+#[test]
+fn tone_mapping() {
+    use crate::tone_map::ToneMapOperator;
+
+    let operators = [
+        ToneMapOperator::Reinhard,
+        ToneMapOperator::ReinhardExtended { white: 4.0 },
+        ToneMapOperator::Hable,
+        ToneMapOperator::Aces,
+    ];
+    for &op in operators.iter() {
+        // Black stays black, and brighter inputs map brighter.
+        assert!(op.map([0.0; 3]).iter().all(|&v| close(v, 0.0, 1e-6)));
+        let mut last = 0.0;
+        for i in 1..20 {
+            let v = op.map([i as f32 * 0.5; 3])[1];
+            assert!((v > last || v == 1.0) && v <= 1.0);
+            last = v;
+        }
+
+        // Buffers match mapping each color.
+        let buffer = [[0.2, 0.4, 0.8], [3.0, 1.0, 0.1]];
+        let mut mapped = buffer;
+        op.map_buffer(&mut mapped);
+        assert_eq!(mapped[1], op.map(buffer[1]));
+        assert_eq!(op.to_rgb_buffer(&buffer)[0], op.to_rgb(buffer[0]));
+    }
+
+    // Reinhard preserves the chromaticity of colors below white.
+    let color = [0.6, 0.3, 0.15];
+    let mapped = ToneMapOperator::Reinhard.map(color);
+    assert!(close(mapped[0] / mapped[1], 2.0, 0.0001));
+    assert!(close(mapped[1] / mapped[2], 2.0, 0.0001));
+
+    // The extended Reinhard maps its white to 1.
+    let white = ToneMapOperator::ReinhardExtended { white: 4.0 };
+    assert!(close(white.map([4.0; 3])[0], 1.0, 0.0001));
+    assert!(close(ToneMapOperator::Aces.map([100.0; 3])[0], 1.0, 0.001));
+}
+
+/// Tests PQ and HLG conversions to and from display luminance in nits.
+#[test]
+fn hdr_nit_scaling() {
+    use crate::transfer::hlg_decode;
+    use crate::transfer::hlg_encode;
+    use crate::transfer::pq_decode;
+    use crate::transfer::pq_encode;
+    use crate::transfer::PQ_PEAK_NITS;
+
+    // PQ reference points from ITU-R BT.2100.
+    assert!(close(pq_encode(0.0), 0.0, 1e-5));
+    assert!(close(pq_encode(100.0), 0.508, 0.001));
+    assert!(close(pq_encode(1000.0), 0.752, 0.001));
+    assert!(close(pq_encode(PQ_PEAK_NITS), 1.0, 1e-5));
+    assert!(close(pq_encode(20000.0), 1.0, 1e-5));
+    for &nits in [0.5, 80.0, 203.0, 4000.0].iter() {
+        assert!(close(pq_decode(pq_encode(nits)) / nits, 1.0, 0.001));
+    }
+
+    // HLG white maps to the peak, and the system gamma depends on it.
+    for &peak in [400.0, 1000.0, 2000.0].iter() {
+        let white = hlg_decode([1.0; 3], peak);
+        assert!(white.iter().all(|&v| close(v / peak, 1.0, 0.0001)));
+
+        let color = [peak * 0.3, peak * 0.1, peak * 0.02];
+        let round_trip = hlg_decode(hlg_encode(color, peak), peak);
+        for (a, b) in round_trip.iter().zip(color.iter()) {
+            assert!(close(a / b, 1.0, 0.001));
+        }
+    }
+    let dim = hlg_decode([0.5; 3], 400.0)[0] / 400.0;
+    let bright = hlg_decode([0.5; 3], 2000.0)[0] / 2000.0;
+    assert!(bright < dim);
+    assert_eq!(hlg_decode([0.0; 3], 1000.0), [0.0; 3]);
+}
+
+/// Tests the double precision Xyz64 and Lab64 colorimetric chain.
+#[test]
+fn double_precision_colors() {
+    use crate::illuminant::AdaptationMethod;
+    use crate::illuminant::Illuminant;
+    use crate::Lab64;
+    use crate::Xyz;
+    use crate::Xyz64;
+
+    // Conversions agree with the single precision spaces.
+    for &rgb in [
+        Rgb::new(255, 0, 0), Rgb::new(12, 200, 99), Rgb::new(128, 128, 128),
+    ].iter() {
+        let xyz = Xyz64::from(rgb);
+        let xyz32 = Xyz::from(rgb);
+        assert!(close(xyz.x as f32, xyz32.x, 0.0001));
+        assert!(close(xyz.z as f32, xyz32.z, 0.0001));
+        let lab = Lab64::from(rgb);
+        let lab32 = Lab::from(rgb);
+        assert!(close(lab.l as f32, lab32.l, 0.01));
+        assert!(close(lab.b as f32, lab32.b, 0.01));
+        assert_eq!(Rgb::from(lab), rgb);
+        assert_eq!(Lab::from(lab).l, lab.l as f32);
+    }
+
+    // D50-relative conversions agree with the single precision spaces, and
+    // round trip in double precision.
+    for &rgb in [Rgb::new(255, 0, 0), Rgb::new(12, 200, 99)].iter() {
+        let xyz = Xyz64::from(rgb).adapt(
+            Illuminant::D65, Illuminant::D50, AdaptationMethod::Bradford);
+        let xyz32 = Xyz::from(rgb).adapt(
+            Illuminant::D65, Illuminant::D50, AdaptationMethod::Bradford);
+        let lab = Lab64::from_xyz(xyz, Illuminant::D50);
+        let lab32 = Lab::from_xyz(xyz32, Illuminant::D50);
+        assert!(close(lab.l as f32, lab32.l, 0.01));
+        assert!(close(lab.a as f32, lab32.a, 0.01));
+        assert!(close(lab.b as f32, lab32.b, 0.01));
+
+        let round_trip = lab.to_xyz(Illuminant::D50);
+        assert!((round_trip.x - xyz.x).abs() < 1e-12);
+        assert!((round_trip.y - xyz.y).abs() < 1e-12);
+        assert!((round_trip.z - xyz.z).abs() < 1e-12);
+    }
+
+    // Long adaptation chains stay tight in double precision.
+    let methods = [
+        AdaptationMethod::Bradford,
+        AdaptationMethod::Cat02,
+        AdaptationMethod::Cat16,
+        AdaptationMethod::VonKries,
+    ];
+    let start = Lab64::from(Rgb::new(40, 90, 200));
+    let mut xyz = Xyz64::from(start);
+    for _ in 0..50 {
+        for &method in methods.iter() {
+            xyz = xyz.adapt(Illuminant::D65, Illuminant::D50, method)
+                .adapt(Illuminant::D50, Illuminant::A, method)
+                .adapt(Illuminant::A, Illuminant::D65, method);
+        }
+    }
+    assert!(Lab64::delta_e(start, Lab64::from(xyz)) < 1e-9);
+
+    // CIEDE2000 reference data from Sharma, Wu, and Dalal (2005).
+    let pairs = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        ([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387], 1.2644),
+        ([22.7233, 20.0904, -46.6940], [23.0331, 14.9730, -42.5619], 2.0373),
+    ];
+    for &(a, b, expected) in pairs.iter() {
+        let de = Lab64::delta_e(Lab64::from(a), Lab64::from(b));
+        assert!((de - expected).abs() < 5e-5);
+    }
+}
+
+/// Tests the 16-bit per channel Rgb48 and Rgba64 colors.
+#[test]
+fn sixteen_bit_colors() {
+    use crate::Rgb48;
+    use crate::Rgba;
+    use crate::Rgba64;
+    use crate::Xyz;
+    use crate::Xyz64;
+
+    // Every octet round trips, and narrowing rounds to the nearest octet.
+    for v in 0..=255u8 {
+        let rgb = Rgb::new(v, v, v);
+        assert_eq!(Rgb::from(Rgb48::from(rgb)), rgb);
+        let rgba = Rgba::new(v, 0, 255, v);
+        assert_eq!(Rgba::from(Rgba64::from(rgba)), rgba);
+    }
+    assert_eq!(Rgb48::new(128, 385, 386).rgb(), Rgb::new(0, 1, 2));
+    assert_eq!(Rgb48::new(0xFFFF, 0xFF7E, 0xFF7F).rgb(),
+        Rgb::new(255, 254, 255));
+
+    // Conversion through XYZ keeps the full 16-bit precision.
+    for &color in [
+        Rgb48::new(0, 0, 0), Rgb48::new(0xFFFF, 0xFFFF, 0xFFFF),
+        Rgb48::new(0x0101, 0x7FFF, 0xFFFE), Rgb48::new(1, 2, 3),
+    ].iter() {
+        assert_eq!(Rgb48::from(Xyz64::from(color)), color);
+    }
+    let xyz = Xyz::from(Rgb48::from(Rgb::new(12, 200, 99)));
+    let expected = Xyz::from(Rgb::new(12, 200, 99));
+    assert!(close(xyz.y, expected.y, 1e-5));
+
+    // Alpha is kept, added as opaque, or discarded.
+    let rgba = Rgba64::new(1, 2, 3, 4);
+    assert_eq!(Rgb48::from(rgba), Rgb48::new(1, 2, 3));
+    assert!(Rgba64::from(Rgb48::new(1, 2, 3)).is_opaque());
+    assert!(Rgba64::from(Rgb::new(1, 2, 3)).is_opaque());
+    assert_eq!(format!("{:X}", rgba), "#0001000200030004");
+}
+
+/// Tests packed RGB565, ARGB1555, and RGB332 encoding and decoding.
+#[test]
+fn packed_formats() {
+    use crate::packed::decode_argb1555;
+    use crate::packed::decode_rgb332;
+    use crate::packed::decode_rgb565;
+    use crate::packed::encode_argb1555;
+    use crate::packed::encode_rgb332;
+    use crate::packed::encode_rgb565;
+
+    // Every packed value round trips through its decoded color.
+    for packed in 0..=u16::MAX {
+        assert_eq!(encode_rgb565(decode_rgb565(packed)), packed);
+        assert_eq!(encode_argb1555(decode_argb1555(packed)), packed);
+    }
+    for packed in 0..=u8::MAX {
+        assert_eq!(encode_rgb332(decode_rgb332(packed)), packed);
+    }
+
+    // Bit replication matches exact scaling of each level to 255.
+    for level in 0..32u16 {
+        let expected = (level as f32 * 255.0 / 31.0).round() as i32;
+        let decoded = decode_rgb565(level).b as i32;
+        assert!((decoded - expected).abs() <= 1);
+    }
+
+    // Encoding rounds to the nearest level.
+    for v in 0..=255u8 {
+        let decoded = decode_rgb565(encode_rgb565(Rgb::new(v, v, v)));
+        assert!((decoded.r as i32 - v as i32).abs() <= 4);
+        assert!((decoded.g as i32 - v as i32).abs() <= 2);
+    }
+    assert_eq!(encode_rgb332(Rgb::new(0, 0, 255)), 0b0000_0011);
+    assert_eq!(decode_rgb332(0xFF), Rgb::new(255, 255, 255));
+}
+
+/// Tests packing colors into a u32 in each channel order.
+#[test]
+fn u32_channel_order() {
+    use crate::packed::ChannelOrder;
+    use crate::Rgba;
+
+    let orders = [
+        ChannelOrder::Argb,
+        ChannelOrder::Rgba,
+        ChannelOrder::Abgr,
+        ChannelOrder::Bgra,
+    ];
+    let color = Rgba::new(0x12, 0x34, 0x56, 0x78);
+    let expected = [0x78123456, 0x12345678, 0x78563412, 0x56341278];
+    for (&order, &packed) in orders.iter().zip(expected.iter()) {
+        assert_eq!(color.to_u32(order), packed);
+        assert_eq!(Rgba::from_u32(packed, order), color);
+
+        let rgb = color.rgb();
+        assert_eq!(Rgb::from_u32(rgb.to_u32(order), order), rgb);
+        assert!(Rgba::from_u32(rgb.to_u32(order), order).is_opaque());
+    }
+
+    // Argb without alpha matches the hex code.
+    let rgb = Rgb::new(0x7F, 0xFF, 0x40);
+    assert_eq!(rgb.to_u32(ChannelOrder::Argb) & 0xFFFFFF, rgb.hex());
+
+    // The little endian bytes of Argb are the Bgra memory order.
+    let bytes = color.to_u32(ChannelOrder::Argb).to_le_bytes();
+    assert_eq!(bytes, [0x56, 0x34, 0x12, 0x78]);
+}
+
+/// Tests slice conversion against single color conversion.
+#[test]
+fn slice_conversion() {
+    use crate::bulk::convert_slice;
+    use crate::bulk::convert_slice_into;
+    use crate::Xyz;
+
+    let pixels: Vec<Rgb> = (0..=255u8)
+        .map(|v| Rgb::new(v, v.wrapping_mul(7), 255 - v))
+        .collect();
+
+    let hsl: Vec<Hsl> = convert_slice(&pixels);
+    let expected: Vec<Hsl> = pixels.iter().map(|&c| Hsl::from(c)).collect();
+    assert_eq!(hsl, expected);
+
+    let mut xyz = vec![Xyz::default(); pixels.len()];
+    convert_slice_into(&pixels, &mut xyz);
+    for (&c, x) in pixels.iter().zip(xyz.iter()) {
+        assert_eq!(*x, Xyz::from(c));
+    }
+
+    let empty: Vec<Hsl> = convert_slice::<Rgb, Hsl>(&[]);
+    assert!(empty.is_empty());
+}
+
+/// Tests that slice conversions between `Rgb` and `Oklab` use the default
+/// conversion backend.
+#[test]
+fn slice_conversion_backend() {
+    use crate::backend::Backend;
+    use crate::backend::ConversionBackend;
+    use crate::bulk::convert_slice;
+    use crate::bulk::convert_slice_into;
+
+    // More than one backend chunk, with a partial chunk at the end.
+    let pixels: Vec<Rgb> = (0..1000u32)
+        .map(|v| Rgb::new(v as u8, (v * 3) as u8, (v >> 2) as u8))
+        .collect();
+    let mut expected = vec![Oklab::default(); pixels.len()];
+    Backend::default().rgb_to_oklab(&pixels, &mut expected);
+
+    let oklab: Vec<Oklab> = convert_slice(&pixels);
+    assert_eq!(oklab, expected);
+
+    let mut rgb = vec![Rgb::default(); pixels.len()];
+    convert_slice_into(&oklab, &mut rgb);
+    assert_eq!(rgb, pixels);
+}
+
+/// Tests parallel slice conversion and palette extraction against their
+/// serial forms.
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_slice_conversion() {
+    use crate::bulk::convert_slice;
+    use crate::bulk::par_convert_slice;
+    use crate::bulk::par_convert_slice_into;
+    use crate::metric::Redmean;
+    use crate::quantize::KMeans;
+    use crate::quantize::median_cut;
+    use crate::quantize::octree;
+    use crate::quantize::palette_indices;
+    use crate::quantize::par_k_means;
+    use crate::quantize::par_median_cut;
+    use crate::quantize::par_octree;
+    use crate::quantize::par_palette_indices;
+
+    let pixels: Vec<Rgb> = (0..50_000u32)
+        .map(|v| Rgb::new((v * 7) as u8, (v >> 4) as u8, (v >> 9) as u8))
+        .collect();
+
+    let serial: Vec<Hsl> = convert_slice(&pixels);
+    assert_eq!(par_convert_slice::<Rgb, Hsl>(&pixels), serial);
+
+    let mut hsl = vec![Hsl::default(); pixels.len()];
+    par_convert_slice_into(&pixels, &mut hsl);
+    assert_eq!(hsl, serial);
+
+    let palette = median_cut(&pixels, 8);
+    assert_eq!(
+        par_palette_indices(&pixels, &palette, Redmean),
+        palette_indices(&pixels, &palette, Redmean));
+
+    assert_eq!(par_median_cut(&pixels, 8), palette);
+
+    // Within a single chunk, the parallel octree is the serial octree.
+    assert_eq!(par_octree(&pixels[..4000], 8), octree(&pixels[..4000], 8));
+    let merged = par_octree(&pixels, 8);
+    assert!(merged.len() <= 8 && !merged.is_empty());
+    assert_eq!(par_octree(&pixels, 8), merged);
+    assert!(par_octree(&[], 8).is_empty());
+    let kmeans = KMeans::new(6).with_iterations(6);
+    assert_eq!(kmeans.par_extract(&pixels), kmeans.extract(&pixels));
+    assert!(par_k_means(&[], 4).is_empty());
+}
+
+/// Tests the sRGB lookup tables against the transfer function formulas.
+#[test]
+fn srgb_lookup_tables() {
+    use crate::utility::linear_to_srgb;
+    use crate::utility::srgb_to_linear;
+
+    for v in 0..=255u8 {
+        let color = Rgb::new(v, v, v);
+        let linear = color.linear_ratios();
+        assert_eq!(linear, color.ratios().map(srgb_to_linear));
+        assert_eq!(Rgb::from_linear_ratios(linear), color);
+    }
+
+    for i in 0..=1000 {
+        let linear = i as f32 / 1000.0;
+        let expected = (linear_to_srgb(linear) * 255.0).round() as u8;
+        let [octet, _, _] = Rgb::from_linear_ratios([linear; 3]).octets();
+        assert!(octet.abs_diff(expected) <= 1);
+    }
+
+    assert_eq!(Rgb::from_linear_ratios([f32::NAN, -0.5, 1.5]),
+        Rgb::new(0, 0, 255));
+}
+
+/// Tests zero-copy views of byte buffers as color slices.
+#[cfg(feature = "bytemuck")]
+#[test]
+fn byte_buffer_views() {
+    use crate::Rgba;
+
+    let mut bytes = vec![10, 20, 30, 40, 50, 60, 70, 80];
+
+    let rgba = Rgba::from_bytes(&bytes);
+    assert_eq!(rgba, &[Rgba::new(10, 20, 30, 40), Rgba::new(50, 60, 70, 80)]);
+
+    let rgb = Rgb::from_bytes(&bytes[..6]);
+    assert_eq!(rgb, &[Rgb::new(10, 20, 30), Rgb::new(40, 50, 60)]);
+    assert_eq!(Rgb::as_bytes(rgb).as_ptr(), bytes.as_ptr());
+
+    for color in Rgba::from_bytes_mut(&mut bytes) {
+        color.a = 255;
+    }
+    assert_eq!(bytes, [10, 20, 30, 255, 50, 60, 70, 255]);
+
+    for color in Rgb::from_bytes_mut(&mut bytes[..6]) {
+        *color = color.invert();
+    }
+    assert_eq!(&bytes[..6], &[245, 235, 225, 0, 205, 195]);
+
+    assert!(Rgb::from_bytes(&[]).is_empty());
+    assert!(std::panic::catch_unwind(|| Rgba::from_bytes(&[0; 7])).is_err());
+
+    // The fallible views report lengths which aren't whole colors.
+    assert_eq!(
+        Rgb::try_from_bytes(&bytes[..6]),
+        Ok(&[Rgb::new(245, 235, 225), Rgb::new(0, 205, 195)][..]));
+    assert_eq!(
+        Rgba::try_from_bytes(&[0; 7]),
+        Err(bytemuck::PodCastError::OutputSliceWouldHaveSlop));
+    assert!(Rgb::try_from_bytes_mut(&mut bytes[..5]).is_err());
+    assert_eq!(Rgba::try_from_bytes_mut(&mut bytes).map(|c| c.len()), Ok(2));
+
+    let xyz = [crate::Xyz::new(0.25, 0.5, 0.75)];
+    assert_eq!(bytemuck::cast_slice::<crate::Xyz, f32>(&xyz), &[0.25, 0.5, 0.75]);
+}
+
+/// Tests colors defined in constant tables and match patterns.
+#[test]
+fn const_colors() {
+    use crate::packed::ChannelOrder;
+    use crate::Rgba;
+
+    const TABLE: [Rgb; 3] = [
+        Rgb::BLACK,
+        Rgb::from_hex(0xFF6347),
+        Rgb::WHITE.invert(),
+    ];
+    static OVERLAY: Rgba = Rgba::from_rgb(Rgb::from_hex(0x336699), 128);
+    const PACKED: u32 = Rgba::WHITE.to_u32(ChannelOrder::Bgra);
+
+    assert_eq!(TABLE[1], Rgb::from(0xFF6347));
+    assert_eq!(TABLE[2], Rgb::BLACK);
+    assert_eq!(OVERLAY.rgb().hex(), 0x336699);
+    assert_eq!(PACKED, u32::MAX);
+    assert_eq!(Rgba::from_u32(0, ChannelOrder::Argb), Rgba::TRANSPARENT);
+
+    let name = match Rgb::new(255, 255, 255) {
+        Rgb::BLACK => "black",
+        Rgb::WHITE => "white",
+        _ => "other",
+    };
+    assert_eq!(name, "white");
+}
+
+/// Tests constrained random color generation.
+#[test]
+fn random_color_builder() {
+    use crate::random::RandomColorBuilder;
+
+    let builder = RandomColorBuilder::new()
+        .with_hue(330.0, 30.0)
+        .with_saturation(0.6, 0.9)
+        .with_min_lightness(0.65)
+        .with_seed(1234);
+
+    let colors: Vec<Rgb> = builder.build().take(200).collect();
+    assert_eq!(colors, builder.build().take(200).collect::<Vec<_>>());
+    for &color in &colors {
+        let hsv = Hsv::from(color);
+        let hue = hsv.hue();
+        assert!(hue >= 328.0 || hue <= 32.0, "hue {} out of range", hue);
+        assert!(Oklab::from(color).l >= 0.65);
+    }
+
+    // Different seeds give different sequences.
+    let other: Vec<Rgb> = builder.with_seed(1235).build().take(200).collect();
+    assert_ne!(colors, other);
+
+    // Saturated blues are too dark, so their saturation is reduced.
+    let blues = RandomColorBuilder::new()
+        .with_hue(240.0, 240.0)
+        .with_saturation(0.0, 1.0)
+        .with_min_lightness(0.7);
+    for color in blues.build().take(50) {
+        assert!(Oklab::from(color).l >= 0.7);
+    }
+
+    // Keyed colors don't depend on the order they are requested in.
+    let keyed: Vec<Rgb> = (0..50).map(|k| builder.color_for(k)).collect();
+    for k in (0..50).rev() {
+        assert_eq!(builder.color_for(k), keyed[k as usize]);
+    }
+}
+
+/// Tests that deserialization rejects color maps and gradients which their
+/// constructors would reject.
+#[test]
+#[cfg(feature = "serde")]
+fn color_map_deserialize_validates() {
+    use crate::color_map::Discrete;
+
+    let table: LookupTable = serde_json::from_str(
+        r#"{"colors": [[0, 0, 0], [255, 255, 255]]}"#).unwrap();
+    assert_eq!(table.map(1.0), Rgb::new(255, 255, 255));
+    assert!(serde_json::from_str::<LookupTable>(r#"{"colors": []}"#).is_err());
+    assert!(serde_json::from_str::<Discrete>(r#"{"colors": []}"#).is_err());
+
+    let domain = Domain::symlog(-10.0, 10.0, 1.0);
+    let json = serde_json::to_string(&domain).unwrap();
+    assert_eq!(serde_json::from_str::<Domain>(&json).unwrap(), domain);
+    assert!(serde_json::from_str::<Domain>(
+        r#"{"min": -1, "max": 1, "scale": {"Symlog": {"threshold": 0}}}"#)
+        .is_err());
+    assert!(serde_json::from_str::<Domain>(
+        r#"{"min": 0, "max": 1, "scale": "Log"}"#).is_err());
+
+    let gradient = Gradient::new(Rgb::new(0, 0, 0), Rgb::new(255, 255, 255));
+    let json = serde_json::to_string(&gradient).unwrap();
+    assert_eq!(serde_json::from_str::<Gradient<Rgb>>(&json).unwrap(), gradient);
+    assert!(serde_json::from_str::<Gradient<Rgb>>(r#"{"stops": []}"#)
+        .is_err());
+    assert!(serde_json::from_str::<Gradient<Rgb>>(r#"{"stops": [
+        {"position": 1.0, "color": [0, 0, 0]},
+        {"position": 0.0, "color": [255, 255, 255]}
+    ]}"#).is_err());
+}
+
+/// Tests that converting an `Xyz` color to bytes and back is exact.
+#[test]
+fn xyz_ne_bytes_round_trip() {
+    use crate::Xyz;
+
+    let white = Xyz::from(Rgb::new(255, 255, 255));
+    assert!(white.z > 1.0);
+    assert_eq!(Xyz::from_ne_bytes(white.to_ne_bytes()), white);
+}
+
+/// Tests that a Catmull-Rom `Xyz` gradient through white returns white.
+#[test]
+fn gradient_catmull_rom_xyz_through_white() {
+    use crate::Xyz;
+
+    let white = Rgb::new(255, 255, 255);
+    let gradient: Gradient<Xyz> = Gradient::from_colors(vec![
+        Xyz::from(Rgb::new(255, 0, 0)),
+        Xyz::from(white),
+        Xyz::from(Rgb::new(0, 0, 255)),
+    ]).with_interpolation(Interpolation::CatmullRom);
+
+    assert_eq!(Rgb::from(gradient.color(0.5)), white);
+}
+
+/// Tests that `Xyz` construction preserves components beyond 1.
+#[test]
+fn xyz_new_unclamped() {
+    use crate::Xyz;
+
+    let white = Xyz::from(Rgb::new(255, 255, 255));
+    assert_eq!(Xyz::new(white.x, white.y, white.z), white);
+
+    let mut color = Xyz::default();
+    color.set_z(white.z);
+    assert_eq!(color.z(), white.z);
+    assert_eq!(Rgb::from(Xyz::new(white.x, white.y, white.z)),
+        Rgb::new(255, 255, 255));
+}
+
+/// Tests conversions to and from `image` pixels.
+#[cfg(feature = "image")]
+#[test]
+fn image_pixel_conversions() {
+    use crate::Luma;
+    use crate::Rgb48;
+    use crate::Rgba;
+    use crate::Rgba64;
+
+    let rgb = Rgb::new(10, 20, 30);
+    assert_eq!(image::Rgb::from(rgb), image::Rgb([10, 20, 30]));
+    assert_eq!(Rgb::from(image::Rgb([10, 20, 30])), rgb);
+
+    let rgba = Rgba::new(10, 20, 30, 40);
+    assert_eq!(image::Rgba::from(rgba), image::Rgba([10, 20, 30, 40]));
+    assert_eq!(Rgba::from(image::Rgba([10, 20, 30, 40])), rgba);
+
+    assert_eq!(image::Luma::from(Luma::new(77)), image::Luma([77]));
+    assert_eq!(Luma::from(image::Luma([77u8])), Luma::new(77));
+
+    let rgb48 = Rgb48::new(1000, 2000, 3000);
+    assert_eq!(Rgb48::from(image::Rgb::<u16>::from(rgb48)), rgb48);
+    let rgba64 = Rgba64::new(1000, 2000, 3000, 4000);
+    assert_eq!(Rgba64::from(image::Rgba::<u16>::from(rgba64)), rgba64);
+}
+
+/// Tests palette extraction and quantization of `image` buffers.
+#[cfg(feature = "image")]
+#[test]
+fn image_quantize() {
+    use crate::interop::image::extract_palette;
+    use crate::interop::image::quantize;
+    use crate::interop::image::rgb_pixels;
+    use crate::metric::Euclidean;
+    use crate::palette::Palette;
+    use crate::quantize::median_cut;
+    use image::DynamicImage;
+    use image::RgbaImage;
+
+    let buffer = RgbaImage::from_raw(2, 2, vec![
+        250, 10, 10, 255,   240, 20, 10, 128,
+        10, 10, 250, 0,     20, 10, 240, 255,
+    ]).unwrap();
+    let image = DynamicImage::ImageRgba8(buffer);
+
+    assert_eq!(rgb_pixels(&image)[3], Rgb::new(20, 10, 240));
+
+    let palette = extract_palette(&image, |pixels| median_cut(pixels, 2));
+    assert_eq!(palette.len(), 2);
+
+    let quantized = quantize(&image, &palette, Euclidean::Oklab);
+    let pixels = quantized.as_rgba8().expect("alpha is kept");
+    assert_eq!(pixels.get_pixel(0, 0).0[..3], pixels.get_pixel(1, 0).0[..3]);
+    assert_eq!(pixels.get_pixel(0, 0).0[3], 255);
+    assert_eq!(pixels.get_pixel(1, 0).0[3], 128);
+    assert_eq!(pixels.get_pixel(0, 1).0[3], 0);
+    assert_ne!(pixels.get_pixel(0, 0).0[..3], pixels.get_pixel(0, 1).0[..3]);
+
+    let empty = quantize(&image, &Palette::new(), Euclidean::Oklab);
+    assert_eq!(empty, image);
+}
+
+/// Tests conversions to and from `palette` colors.
+#[cfg(feature = "palette")]
+#[test]
+fn palette_crate_conversions() {
+    use crate::Lab;
+    use crate::Lch;
+    use crate::Oklab;
+    use crate::Rgba;
+    use crate::Xyz;
+    use palette::FromColor;
+
+    let rgb = Rgb::new(255, 99, 71);
+    assert_eq!(palette::Srgb::<u8>::from(rgb), palette::Srgb::new(255, 99, 71));
+    assert_eq!(Rgb::from(palette::Srgb::<u8>::from(rgb)), rgb);
+    assert_eq!(Rgb::from(palette::Srgb::<f32>::from(rgb)), rgb);
+
+    let rgba = Rgba::new(1, 2, 3, 4);
+    assert_eq!(Rgba::from(palette::Srgba::<u8>::from(rgba)), rgba);
+
+    // Both crates agree on the component ranges of each space.
+    let srgb = palette::Srgb::<f32>::from(rgb).into_linear();
+    let xyz = Xyz::from(palette::Xyz::from_color(srgb));
+    let lab = Lab::from(palette::Lab::from_color(srgb));
+    let lch = Lch::from(palette::Lch::from_color(srgb));
+    let oklab = Oklab::from(palette::Oklab::from_color(srgb));
+    assert_eq!(Rgb::from(xyz), rgb);
+    assert!(Lab::distance(rgb, lab) < 0.1);
+    assert!((Lch::from(rgb).h - lch.h).abs() < 0.1);
+    assert!(Oklab::distance(rgb, oklab) < 0.001);
+
+    assert_eq!(Xyz::from(palette::Xyz::from(xyz)), xyz);
+    assert_eq!(Lab::from(palette::Lab::from(lab)), lab);
+    assert_eq!(Lch::from(palette::Lch::from(lch)), lch);
+    assert_eq!(Oklab::from(palette::Oklab::from(oklab)), oklab);
+}
+
+/// Tests sampling colors from `rand` distributions.
+#[cfg(feature = "rand")]
+#[test]
+fn rand_distributions() {
+    use crate::Hsv;
+    use crate::Oklab;
+    use crate::random::UniformHue;
+    use crate::rgb_space::Srgb;
+    use crate::Rgba;
+    use rand::distributions::Uniform;
+    use rand::Rng;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let colors: Vec<Rgb> = (0..64).map(|_| rng.gen()).collect();
+    assert!(colors.windows(2).any(|w| w[0] != w[1]));
+
+    for _ in 0..256 {
+        let oklab: Oklab = rng.gen();
+        assert!(oklab.in_gamut::<Srgb>());
+
+        let hsv: Hsv = rng.gen();
+        assert!((0.0..360.0).contains(&hsv.hue()));
+
+        let hsv: Hsv = rng.sample(UniformHue::new(0.5, 2.0));
+        assert_eq!((hsv.saturation(), hsv.value()), (0.5, 1.0));
+
+        let rgba = rng.sample(Uniform::new_inclusive(
+            Rgba::new(10, 20, 30, 40),
+            Rgba::new(10, 25, 35, 255)));
+        assert_eq!(rgba.r, 10);
+        assert!((20..=25).contains(&rgba.g) && (30..=35).contains(&rgba.b));
+
+        let oklab = rng.sample(Uniform::new(
+            Oklab::new(0.5, -0.1, -0.1),
+            Oklab::new(0.6, 0.1, 0.1)));
+        assert!(oklab.l >= 0.5 && oklab.l < 0.6);
+    }
+
+    // Every sRGB color lies within the Oklab sampling bounds.
+    for color in [Rgb::new(255, 0, 255), Rgb::new(0, 255, 0),
+        Rgb::new(255, 255, 0), Rgb::new(0, 0, 255)]
+    {
+        let oklab = Oklab::from(color);
+        assert!(oklab.a > -0.24 && oklab.a < 0.28);
+        assert!(oklab.b > -0.32 && oklab.b < 0.2);
+    }
+}
+
+/// Tests approximate equality of floating point colors.
+#[cfg(feature = "approx")]
+#[test]
+fn approx_color_eq() {
+    use crate::Hsv;
+    use crate::Lab64;
+    use crate::Oklab;
+    use crate::Xyz;
+    use approx::abs_diff_eq;
+    use approx::assert_abs_diff_eq;
+    use approx::assert_relative_eq;
+    use approx::assert_relative_ne;
+    use approx::assert_ulps_eq;
+
+    let oklab = Oklab::from(Rgb::new(255, 99, 71));
+    let nudged = Oklab::new(oklab.l + 1e-4, oklab.a, oklab.b);
+    assert_abs_diff_eq!(oklab, nudged, epsilon = 1e-3);
+    assert_relative_ne!(oklab, nudged);
+    assert!(!abs_diff_eq!(oklab, Oklab::new(oklab.l, oklab.a, 0.0)));
+
+    let xyz = Xyz::from(Rgb::new(10, 200, 30));
+    assert_ulps_eq!(Xyz::from(Rgb::from(xyz)), xyz);
+    assert_relative_eq!(Hsv::new(120.0, 0.5, 0.5), Hsv::new(120.00001, 0.5, 0.5));
+    let next = f64::from_bits(10.0f64.to_bits() + 1);
+    assert_ulps_eq!(Lab64::new(50.0, 10.0, 10.0), Lab64::new(50.0, 10.0, next));
+}
+
+/// Tests that `proptest` strategies generate only valid colors.
+#[cfg(feature = "proptest")]
+mod proptest_arbitrary {
+    use crate::Hsv;
+    use crate::Lab;
+    use crate::Oklch;
+    use crate::Rgb;
+    use crate::Xyz;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn hsv_in_range(hsv in any::<Hsv>()) {
+            prop_assert!((0.0..360.0).contains(&hsv.hue()));
+            prop_assert!((0.0..=1.0).contains(&hsv.saturation()));
+            prop_assert!((0.0..=1.0).contains(&hsv.value()));
+        }
+
+        #[test]
+        fn converted_in_gamut(lab in any::<Lab>(), oklch in any::<Oklch>()) {
+            prop_assert!(lab.l.is_finite() && lab.a.is_finite());
+            prop_assert!((0.0..=100.001).contains(&lab.l));
+            prop_assert!((0.0..360.0).contains(&oklch.h));
+        }
+
+        #[test]
+        fn xyz_round_trip(xyz in any::<Xyz>()) {
+            prop_assert_eq!(Xyz::from(Rgb::from(xyz)), xyz);
+        }
+    }
+}
+
+/// Tests that `quickcheck` generators produce and shrink to valid colors.
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_arbitrary() {
+    use crate::Hsl;
+    use crate::Oklab;
+    use crate::rgb_space::Srgb;
+    use quickcheck::Arbitrary;
+    use quickcheck::quickcheck;
+
+    fn hsl_in_range(hsl: Hsl) -> bool {
+        (0.0..360.0).contains(&hsl.hue())
+            && (0.0..=1.0).contains(&hsl.saturation())
+            && (0.0..=1.0).contains(&hsl.lightness())
+            && hsl.shrink().all(|s| (0.0..=1.0).contains(&s.saturation()))
+    }
+
+    fn oklab_in_gamut(oklab: Oklab) -> bool {
+        oklab.in_gamut::<Srgb>()
+            && oklab.shrink().all(|s| s.in_gamut::<Srgb>())
+    }
+
+    let property: fn(Hsl) -> bool = hsl_in_range;
+    quickcheck(property);
+    let property: fn(Oklab) -> bool = oklab_in_gamut;
+    quickcheck(property);
+
+    assert!(Rgb::new(0, 0, 0).shrink().next().is_none());
+    assert!(Rgb::new(9, 0, 0).shrink().all(|s| s.r < 9));
+}
+
+/// Tests parsing CSS colors.
+#[cfg(feature = "parse")]
+#[test]
+fn parse_css_colors() {
+    use crate::CssColorParseError;
+    use crate::Rgba;
+
+    let tomato = Rgba::new(255, 99, 71, 255);
+    for text in [
+        "tomato", " TOMATO ", "#ff6347", "#FF6347FF", "rgb(255, 99, 71)",
+        "rgba(255,99,71,1)", "rgb(255 99 71)", "rgb(100% 38.8% 27.84%)",
+        "rgb(255 99 71 / 100%)", "rgb(300 99 71)",
+    ] {
+        assert_eq!(text.parse::<Rgba>(), Ok(tomato), "{}", text);
+    }
+
+    assert_eq!("#f00".parse(), Ok(Rgba::new(255, 0, 0, 255)));
+    assert_eq!("#f008".parse(), Ok(Rgba::new(255, 0, 0, 136)));
+    assert_eq!("transparent".parse(), Ok(Rgba::new(0, 0, 0, 0)));
+    assert_eq!("rgba(0, 0, 0, 0.5)".parse::<Rgba>().map(|c| c.a), Ok(128));
+    assert_eq!("rgb(0 0 0 / 25%)".parse::<Rgba>().map(|c| c.a), Ok(64));
+    assert_eq!("rebeccapurple".parse(), Ok(Rgb::new(0x66, 0x33, 0x99)));
+    assert_eq!("yellowgreen".parse(), Ok(Rgb::new(0x9A, 0xCD, 0x32)));
+
+    let red = Ok(Rgb::new(255, 0, 0));
+    for text in [
+        "hsl(0, 100%, 50%)", "hsla(360, 100%, 50%, 0.5)", "hsl(0deg 100% 50%)",
+        "hsl(1turn 100 50)", "hsl(400grad 100% 50%)", "hsl(0rad 100% 50%)",
+    ] {
+        assert_eq!(text.parse(), red, "{}", text);
+    }
+    assert_eq!("hsl(0.5turn 100% 50%)".parse(), Ok(Rgb::new(0, 255, 255)));
+
+    assert_eq!("#ff63".parse::<Rgb>(), Ok(Rgb::new(255, 255, 102)));
+    assert_eq!("#ff634".parse::<Rgb>(), Err(CssColorParseError::InvalidHexCode));
+    assert_eq!("#gg6347".parse::<Rgb>(), Err(CssColorParseError::InvalidHexCode));
+    assert_eq!("lab(50 0 0)".parse::<Rgb>(),
+        Err(CssColorParseError::UnknownFunction));
+    assert_eq!("rgb(1, 2)".parse::<Rgb>(),
+        Err(CssColorParseError::InvalidArguments));
+    assert_eq!("rgb(1 2 x)".parse::<Rgb>(),
+        Err(CssColorParseError::InvalidArguments));
+    assert_eq!("hsl(10% 50% 50%)".parse::<Rgb>(),
+        Err(CssColorParseError::InvalidArguments));
+    assert_eq!("hsl(1e38turn 50% 50%)".parse::<Rgb>(),
+        Err(CssColorParseError::InvalidArguments));
+    assert_eq!("rgb(inf 0 0)".parse::<Rgb>(),
+        Err(CssColorParseError::InvalidArguments));
+    assert_eq!("notacolor".parse::<Rgb>(), Err(CssColorParseError::UnknownName));
+    assert_eq!("".parse::<Rgb>(), Err(CssColorParseError::UnknownName));
+}
+
+/// Tests the JavaScript color bindings.
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm_color_bindings() {
+    use crate::Rgba;
+    use crate::wasm::JsColor;
+
+    let color = JsColor::from_hex("f63").unwrap();
+    assert_eq!((color.r(), color.g(), color.b(), color.a()), (255, 102, 51, 255));
+    assert_eq!(color.to_hex(), "#ff6633");
+    assert_eq!(JsColor::from_hex("#ff663380").unwrap().to_hex(), "#ff663380");
+
+    let color = JsColor::parse("rgb(255 99 71 / 50%)").unwrap();
+    assert_eq!(color.to_css(), "rgb(255 99 71 / 0.502)");
+    assert_eq!(JsColor::new(255, 99, 71, None).to_css(), "rgb(255 99 71)");
+    assert_eq!(Rgba::from(color), Rgba::new(255, 99, 71, 128));
+
+    let red = JsColor::new(255, 0, 0, None);
+    assert_eq!(red.to_hsl(), [0.0, 1.0, 0.5]);
+    assert_eq!(JsColor::from_hsl(0.0, 1.0, 0.5), red);
+    let tomato = JsColor::parse("tomato").unwrap();
+    let hsv = tomato.to_hsv();
+    assert_eq!(JsColor::from_hsv(hsv[0], hsv[1], hsv[2]), tomato);
+    let oklch = tomato.to_oklch();
+    assert_eq!(JsColor::from_oklch(oklch[0], oklch[1], oklch[2]), tomato);
+    let lab = tomato.to_lab();
+    assert_eq!(JsColor::from_lab(lab[0], lab[1], lab[2]), tomato);
+
+    let black = JsColor::new(0, 0, 0, None);
+    let white = JsColor::new(255, 255, 255, None);
+    assert!((black.contrast_ratio(&white) - 21.0).abs() < 0.01);
+}
@@ -0,0 +1,438 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Light and dark UI themes and shade scales derived from a brand color.
+//!
+//! Each role of a [`Theme`] and each step of a [`Shades`] scale has a target
+//! [`Oklch`] lightness, so the roles keep the same perceived contrast
+//! whatever the brand hue. Neutral roles are faintly tinted with the brand
+//! hue, and colors which cannot be shown in sRGB are reduced in chroma,
+//! keeping their hue and lightness.
+//!
+//! [`Theme`]: struct.Theme.html
+//! [`Shades`]: struct.Shades.html
+//! [`Oklch`]: ../struct.Oklch.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::color_space::oklch::gamut_map;
+use crate::contrast::contrast_ratio;
+use crate::Oklab;
+use crate::Oklch;
+use crate::Rgb;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The chroma of the brand-tinted neutral roles.
+const NEUTRAL_CHROMA: f32 = 0.012;
+
+/// The maximum chroma of the subtle accent roles.
+const SUBTLE_CHROMA: f32 = 0.06;
+
+/// The lightness targets of a light theme.
+const LIGHT: Targets = Targets {
+    background: 0.985,
+    surface: 0.96,
+    surface_raised: 0.93,
+    border: 0.86,
+    accent: [0.45, 0.62],
+    accent_subtle: 0.92,
+    on_background: 0.18,
+    on_surface: 0.22,
+    on_surface_muted: 0.45,
+    on_accent_subtle: 0.3,
+};
+
+/// The lightness targets of a dark theme.
+const DARK: Targets = Targets {
+    background: 0.17,
+    surface: 0.21,
+    surface_raised: 0.25,
+    border: 0.34,
+    accent: [0.68, 0.82],
+    accent_subtle: 0.3,
+    on_background: 0.95,
+    on_surface: 0.92,
+    on_surface_muted: 0.72,
+    on_accent_subtle: 0.9,
+};
+
+/// The step names of a [`Shades`] scale, from lightest to darkest.
+///
+/// [`Shades`]: struct.Shades.html
+pub const SHADE_STEPS: [u16; 11] = [
+    50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950,
+];
+
+/// The Oklch lightness of each shade step.
+const SHADE_LIGHTNESS: [f32; 11] = [
+    0.971, 0.936, 0.885, 0.808, 0.704, 0.637, 0.577, 0.505, 0.444, 0.396,
+    0.258,
+];
+
+/// The relative Oklch chroma of each shade step. Chroma peaks in the middle
+/// of the scale and tapers toward the lightest and darkest steps.
+const SHADE_CHROMA: [f32; 11] = [
+    0.055, 0.135, 0.26, 0.48, 0.8, 1.0, 1.0, 0.9, 0.75, 0.6, 0.39,
+];
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Theme
+////////////////////////////////////////////////////////////////////////////////
+/// The colors of a light or dark UI theme.
+///
+/// Each `on_` color is intended for text and icons drawn over the role it
+/// names.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::contrast::AA;
+/// # use color::contrast::contrast_ratio;
+/// # use color::theme::Theme;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let brand = Rgb::new(0x63, 0x66, 0xF1);
+///
+/// let light = Theme::light(brand);
+/// let dark = Theme::dark(brand);
+///
+/// assert!(contrast_ratio(light.on_surface, light.surface) >= AA);
+/// assert!(contrast_ratio(dark.on_surface, dark.surface) >= AA);
+/// assert!(contrast_ratio(dark.on_accent, dark.accent) >= AA);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Theme {
+    /// The window or page background.
+    pub background: Rgb,
+    /// Cards, panels, and other surfaces on the background.
+    pub surface: Rgb,
+    /// Surfaces raised above other surfaces, such as menus and dialogs.
+    pub surface_raised: Rgb,
+    /// Borders and dividers.
+    pub border: Rgb,
+    /// The brand accent, for primary buttons, links, and selection.
+    pub accent: Rgb,
+    /// A subdued accent, for highlighted or selected surfaces.
+    pub accent_subtle: Rgb,
+    /// Text and icons on the background.
+    pub on_background: Rgb,
+    /// Text and icons on surfaces.
+    pub on_surface: Rgb,
+    /// Secondary text and icons on surfaces.
+    pub on_surface_muted: Rgb,
+    /// Text and icons on the accent.
+    pub on_accent: Rgb,
+    /// Text and icons on the subtle accent.
+    pub on_accent_subtle: Rgb,
+}
+
+impl Theme {
+    /// Returns the light theme for the given brand color.
+    pub fn light<C>(brand: C) -> Self where C: Into<Rgb> {
+        Theme::from_targets(brand.into(), &LIGHT)
+    }
+
+    /// Returns the dark theme for the given brand color.
+    pub fn dark<C>(brand: C) -> Self where C: Into<Rgb> {
+        Theme::from_targets(brand.into(), &DARK)
+    }
+
+    /// Returns the theme at the ratio given by `amount` between the `start`
+    /// and `end` themes, for animating a transition between them. Each role
+    /// is interpolated in [`Oklab`]. `amount` is clamped between 0 and 1.
+    ///
+    /// [`Oklab`]: ../struct.Oklab.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::theme::Theme;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let light = Theme::light(Rgb::new(0x63, 0x66, 0xF1));
+    /// let dark = Theme::dark(Rgb::new(0xE1, 0x1D, 0x48));
+    ///
+    /// let halfway = Theme::interpolate(light, dark, 0.5);
+    ///
+    /// assert!(halfway.background.r < light.background.r);
+    /// assert!(halfway.background.r > dark.background.r);
+    /// assert_eq!(Theme::interpolate(light, dark, 0.0), light);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn interpolate(start: Theme, end: Theme, amount: f32) -> Self {
+        let lerp = |a: Rgb, b: Rgb| {
+            Rgb::from(Oklab::linear_interpolate(a, b, amount))
+        };
+        Theme {
+            background: lerp(start.background, end.background),
+            surface: lerp(start.surface, end.surface),
+            surface_raised: lerp(start.surface_raised, end.surface_raised),
+            border: lerp(start.border, end.border),
+            accent: lerp(start.accent, end.accent),
+            accent_subtle: lerp(start.accent_subtle, end.accent_subtle),
+            on_background: lerp(start.on_background, end.on_background),
+            on_surface: lerp(start.on_surface, end.on_surface),
+            on_surface_muted: lerp(
+                start.on_surface_muted,
+                end.on_surface_muted),
+            on_accent: lerp(start.on_accent, end.on_accent),
+            on_accent_subtle: lerp(
+                start.on_accent_subtle,
+                end.on_accent_subtle),
+        }
+    }
+
+    /// Returns the theme for the given brand color and lightness targets.
+    fn from_targets(brand: Rgb, targets: &Targets) -> Self {
+        let brand = Oklch::from(brand);
+        let hue = brand.h;
+        let neutral = |l| fit(l, NEUTRAL_CHROMA, hue);
+        let subtle_chroma = brand.c.min(SUBTLE_CHROMA);
+
+        let [accent_min, accent_max] = targets.accent;
+        let accent = fit(brand.l.clamp(accent_min, accent_max), brand.c, hue);
+        let black = Rgb::new(0, 0, 0);
+        let white = Rgb::new(255, 255, 255);
+        let on_accent = if contrast_ratio(black, accent)
+            > contrast_ratio(white, accent)
+        {
+            black
+        } else {
+            white
+        };
+
+        Theme {
+            background: neutral(targets.background),
+            surface: neutral(targets.surface),
+            surface_raised: neutral(targets.surface_raised),
+            border: neutral(targets.border),
+            accent,
+            accent_subtle: fit(targets.accent_subtle, subtle_chroma, hue),
+            on_background: neutral(targets.on_background),
+            on_surface: neutral(targets.on_surface),
+            on_surface_muted: neutral(targets.on_surface_muted),
+            on_accent,
+            on_accent_subtle: fit(targets.on_accent_subtle, subtle_chroma, hue),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ThemeVariants
+////////////////////////////////////////////////////////////////////////////////
+/// A matching pair of light and dark themes.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::theme::Theme;
+/// # use color::theme::ThemeVariants;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let brand = Rgb::new(0xE1, 0x1D, 0x48);
+///
+/// let themes = ThemeVariants::from_brand(brand);
+///
+/// assert_eq!(themes.light, Theme::light(brand));
+/// assert_eq!(themes.dark, Theme::dark(brand));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThemeVariants {
+    /// The light theme.
+    pub light: Theme,
+    /// The dark theme.
+    pub dark: Theme,
+}
+
+impl ThemeVariants {
+    /// Returns the light and dark themes for the given brand color.
+    pub fn from_brand<C>(brand: C) -> Self where C: Into<Rgb> {
+        let brand = brand.into();
+        ThemeVariants {
+            light: Theme::light(brand),
+            dark: Theme::dark(brand),
+        }
+    }
+
+    /// Returns the theme at the ratio given by `amount` between the light
+    /// theme at 0 and the dark theme at 1. See [`Theme::interpolate`].
+    ///
+    /// [`Theme::interpolate`]: struct.Theme.html#method.interpolate
+    pub fn interpolate(&self, amount: f32) -> Theme {
+        Theme::interpolate(self.light, self.dark, amount)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Shades
+////////////////////////////////////////////////////////////////////////////////
+/// An 11-step lightness scale of a single hue, named like the Tailwind CSS
+/// color scales from 50 (lightest) to 950 (darkest).
+///
+/// The reference color is kept exactly at the step nearest its Oklch
+/// lightness. The other steps share its Oklch hue, with chroma scaled
+/// relative to the reference and reduced where needed to fit within sRGB.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Oklch;
+/// # use color::Rgb;
+/// # use color::theme::Shades;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let brand = Rgb::new(0x3B, 0x82, 0xF6);
+///
+/// let shades = Shades::from_reference(brand);
+///
+/// assert_eq!(shades.get(500), Some(brand));
+/// assert_eq!(shades.reference_step(), 500);
+/// assert!(Oklch::from(shades.get(50).unwrap()).l > 0.95);
+/// assert!(Oklch::from(shades.get(950).unwrap()).l < 0.3);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Shades {
+    /// The colors of each step, in the order of [`SHADE_STEPS`].
+    ///
+    /// [`SHADE_STEPS`]: constant.SHADE_STEPS.html
+    colors: [Rgb; 11],
+    /// The index of the step holding the reference color.
+    reference: usize,
+}
+
+impl Shades {
+    /// Returns the shade scale for the given reference color.
+    pub fn from_reference<C>(reference: C) -> Self where C: Into<Rgb> {
+        let rgb = reference.into();
+        let base = Oklch::from(rgb);
+        let index = (0..SHADE_LIGHTNESS.len())
+            .min_by(|&a, &b| {
+                let da = (SHADE_LIGHTNESS[a] - base.l).abs();
+                let db = (SHADE_LIGHTNESS[b] - base.l).abs();
+                da.total_cmp(&db)
+            })
+            .expect("nonempty shade steps");
+
+        let chroma_scale = base.c / SHADE_CHROMA[index];
+        let mut colors = [rgb; 11];
+        for (i, color) in colors.iter_mut().enumerate() {
+            if i != index {
+                let chroma = chroma_scale * SHADE_CHROMA[i];
+                *color = fit(SHADE_LIGHTNESS[i], chroma, base.h);
+            }
+        }
+        Shades { colors, reference: index }
+    }
+
+    /// Returns the color of the given step, or `None` if it is not one of
+    /// the [`SHADE_STEPS`].
+    ///
+    /// [`SHADE_STEPS`]: constant.SHADE_STEPS.html
+    pub fn get(&self, step: u16) -> Option<Rgb> {
+        SHADE_STEPS.iter()
+            .position(|&s| s == step)
+            .map(|i| self.colors[i])
+    }
+
+    /// Returns the step holding the reference color.
+    pub fn reference_step(&self) -> u16 {
+        SHADE_STEPS[self.reference]
+    }
+
+    /// Returns the colors of each step, from lightest to darkest.
+    pub fn colors(&self) -> &[Rgb; 11] {
+        &self.colors
+    }
+
+    /// Returns an iterator over the steps and their colors, from lightest to
+    /// darkest.
+    pub fn iter(&self) -> impl Iterator<Item=(u16, Rgb)> + '_ {
+        SHADE_STEPS.iter().copied().zip(self.colors.iter().copied())
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Targets
+////////////////////////////////////////////////////////////////////////////////
+/// The Oklch lightness targets for each role of a theme.
+#[derive(Debug)]
+struct Targets {
+    background: f32,
+    surface: f32,
+    surface_raised: f32,
+    border: f32,
+    /// The range the brand lightness is clamped into for the accent.
+    accent: [f32; 2],
+    accent_subtle: f32,
+    on_background: f32,
+    on_surface: f32,
+    on_surface_muted: f32,
+    on_accent_subtle: f32,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the color with the given Oklch lightness and hue, and the given
+/// chroma or the largest chroma within sRGB if it is smaller.
+fn fit(l: f32, chroma: f32, hue: f32) -> Rgb {
+    gamut_map(Oklch::new(l, chroma, hue))
+}
@@ -0,0 +1,203 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! HDR tone mapping.
+//!
+//! A [`ToneMapOperator`] maps unbounded linear RGB intensities, such as
+//! those decoded from HDR content or produced by a renderer, into the [0, 1]
+//! range of SDR displays. Values of 1 are the SDR reference white, so
+//! exposure is adjusted by scaling the linear ratios before mapping.
+//!
+//! [`ToneMapOperator`]: enum.ToneMapOperator.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Rgb;
+use crate::rgb_space::RgbSpace;
+use crate::rgb_space::Srgb;
+use crate::transfer::TransferFunction;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The shoulder strength of the Hable filmic curve.
+const HABLE_A: f32 = 0.15;
+
+/// The linear strength of the Hable filmic curve.
+const HABLE_B: f32 = 0.50;
+
+/// The linear angle of the Hable filmic curve.
+const HABLE_C: f32 = 0.10;
+
+/// The toe strength of the Hable filmic curve.
+const HABLE_D: f32 = 0.20;
+
+/// The toe numerator of the Hable filmic curve.
+const HABLE_E: f32 = 0.02;
+
+/// The toe denominator of the Hable filmic curve.
+const HABLE_F: f32 = 0.30;
+
+/// The linear white point of the Hable filmic curve.
+const HABLE_WHITE: f32 = 11.2;
+
+/// The exposure bias applied before the Hable filmic curve.
+const HABLE_EXPOSURE_BIAS: f32 = 2.0;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ToneMapOperator
+////////////////////////////////////////////////////////////////////////////////
+/// An operator mapping HDR linear RGB intensities into the SDR range.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::Rgb;
+/// # use color::tone_map::ToneMapOperator;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let highlight = [2.0, 1.5, 1.0];
+///
+/// let mapped = ToneMapOperator::Reinhard.map(highlight);
+/// assert!(mapped.iter().all(|&v| v < 1.0));
+///
+/// assert_eq!(ToneMapOperator::Aces.to_rgb([0.0; 3]), Rgb::new(0, 0, 0));
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ToneMapOperator {
+    /// The Reinhard operator `L / (1 + L)`, applied to luminance to preserve
+    /// hue and saturation. It never reaches white.
+    Reinhard,
+    /// The extended Reinhard operator, applied to luminance, which maps the
+    /// given linear luminance to white.
+    ReinhardExtended {
+        /// The smallest linear luminance mapped to white.
+        white: f32,
+    },
+    /// John Hable's filmic curve from Uncharted 2, applied to each channel,
+    /// with a toe that deepens shadows and a soft shoulder.
+    Hable,
+    /// Krzysztof Narkowicz's fit of the ACES filmic reference rendering
+    /// transform, applied to each channel.
+    #[default]
+    Aces,
+}
+
+impl ToneMapOperator {
+    /// Returns the SDR linear ratios, between 0 and 1, of the given HDR
+    /// linear RGB intensities. Negative intensities are treated as 0.
+    pub fn map(self, linear: [f32; 3]) -> [f32; 3] {
+        use ToneMapOperator::*;
+        let linear = linear.map(|v| v.max(0.0));
+        match self {
+            Reinhard                   => scale_luminance(linear, |l| {
+                l / (1.0 + l)
+            }),
+            ReinhardExtended { white } => scale_luminance(linear, |l| {
+                l * (1.0 + l / (white * white)) / (1.0 + l)
+            }),
+            Hable                      => linear.map(|v| {
+                hable(v * HABLE_EXPOSURE_BIAS) / hable(HABLE_WHITE)
+            }),
+            Aces                       => linear.map(|v| {
+                (v * (2.51 * v + 0.03)) / (v * (2.43 * v + 0.59) + 0.14)
+            }),
+        }.map(|v| v.clamp(0.0, 1.0))
+    }
+
+    /// Returns the sRGB encoded [`Rgb`] color of the given HDR linear RGB
+    /// intensities.
+    ///
+    /// [`Rgb`]: ../struct.Rgb.html
+    pub fn to_rgb(self, linear: [f32; 3]) -> Rgb {
+        TransferFunction::Srgb.encode_rgb(self.map(linear))
+    }
+
+    /// Maps each of the given HDR linear RGB intensities into the SDR range
+    /// in place.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::tone_map::ToneMapOperator;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// let mut pixels = [[0.5, 0.5, 0.5], [8.0, 8.0, 8.0]];
+    ///
+    /// ToneMapOperator::ReinhardExtended { white: 8.0 }
+    ///     .map_buffer(&mut pixels);
+    ///
+    /// assert!((pixels[1][0] - 1.0).abs() < 1e-6);
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn map_buffer(self, buffer: &mut [[f32; 3]]) {
+        for linear in buffer.iter_mut() {
+            *linear = self.map(*linear);
+        }
+    }
+
+    /// Returns the sRGB encoded [`Rgb`] colors of the given HDR linear RGB
+    /// intensities.
+    ///
+    /// [`Rgb`]: ../struct.Rgb.html
+    pub fn to_rgb_buffer(self, buffer: &[[f32; 3]]) -> Vec<Rgb> {
+        buffer.iter().map(|&linear| self.to_rgb(linear)).collect()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Scales the given linear RGB intensities so that their luminance is mapped
+/// by the given curve.
+fn scale_luminance<F>(linear: [f32; 3], curve: F) -> [f32; 3]
+    where F: Fn(f32) -> f32
+{
+    let weights = Srgb::XYZ_FROM_LINEAR[1];
+    let luminance = linear.iter()
+        .zip(weights.iter())
+        .map(|(v, w)| v * w)
+        .sum::<f32>();
+    if luminance <= 0.0 { return [0.0; 3]; }
+    let scale = curve(luminance) / luminance;
+    linear.map(|v| v * scale)
+}
+
+/// The Hable filmic curve, before normalization by its white point.
+fn hable(x: f32) -> f32 {
+    ((x * (HABLE_A * x + HABLE_C * HABLE_B) + HABLE_D * HABLE_E)
+        / (x * (HABLE_A * x + HABLE_B) + HABLE_D * HABLE_F))
+        - HABLE_E / HABLE_F
+}
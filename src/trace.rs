@@ -0,0 +1,105 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Optional tracing instrumentation.
+//!
+//! With the `tracing` feature enabled, the `span!` and `event!` macros used
+//! throughout the crate are those of the `tracing` crate. Otherwise they
+//! expand to nothing, so per-color conversions pay nothing for
+//! instrumentation.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// External library imports.
+#[cfg(feature = "tracing")]
+pub(in crate) use tracing::event;
+#[cfg(feature = "tracing")]
+pub(in crate) use tracing::Level;
+#[cfg(feature = "tracing")]
+pub(in crate) use tracing::span;
+
+#[cfg(not(feature = "tracing"))]
+pub(in crate) use disabled::event;
+#[cfg(not(feature = "tracing"))]
+pub(in crate) use disabled::Level;
+#[cfg(not(feature = "tracing"))]
+pub(in crate) use disabled::span;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// without_spans
+////////////////////////////////////////////////////////////////////////////////
+/// Calls the given function with tracing disabled, so the spans opened by
+/// each color conversion are skipped.
+#[cfg(feature = "tracing")]
+pub(in crate) fn without_spans<F, R>(f: F) -> R where F: FnOnce() -> R {
+    tracing::dispatcher::with_default(&tracing::Dispatch::none(), f)
+}
+
+/// Calls the given function. Tracing is disabled, so there are no spans to
+/// skip.
+#[cfg(not(feature = "tracing"))]
+pub(in crate) fn without_spans<F, R>(f: F) -> R where F: FnOnce() -> R {
+    f()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// disabled
+////////////////////////////////////////////////////////////////////////////////
+/// No-op stand-ins for the `tracing` items, used when the `tracing` feature is
+/// disabled.
+#[cfg(not(feature = "tracing"))]
+mod disabled {
+    /// A stand-in for `tracing::Level`.
+    #[derive(Debug, Clone, Copy)]
+    pub(in crate) struct Level;
+
+    impl Level {
+        /// The "debug" level.
+        pub(in crate) const DEBUG: Level = Level;
+        /// The "trace" level.
+        pub(in crate) const TRACE: Level = Level;
+    }
+
+    /// A stand-in for `tracing::Span`, which records nothing.
+    #[derive(Debug, Clone, Copy)]
+    pub(in crate) struct Span;
+
+    impl Span {
+        /// Enters the span, which does nothing.
+        #[inline(always)]
+        pub(in crate) fn enter(&self) -> Span {
+            Span
+        }
+    }
+
+    /// A stand-in for `tracing::span!`, which evaluates only its level.
+    macro_rules! span {
+        ($level:expr, $($rest:tt)*) => {{
+            let _ = $level;
+            $crate::trace::Span
+        }};
+    }
+
+    /// A stand-in for `tracing::event!`, which evaluates only its level and
+    /// the unformatted message arguments.
+    macro_rules! event {
+        ($level:expr, $($arg:tt)+) => {{
+            let _ = $level;
+            let _ = format_args!($($arg)+);
+        }};
+    }
+
+    pub(in crate) use event;
+    pub(in crate) use span;
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(in crate) use disabled::Span;
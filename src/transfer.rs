@@ -0,0 +1,371 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Transfer functions between linear light and encoded RGB ratios.
+//!
+//! A [`TransferFunction`] describes how the linear light intensities of an
+//! RGB color are encoded into the stored channel ratios. [`Rgb`] colors are
+//! sRGB encoded by default, but the same container can hold colors in other
+//! encodings, such as Rec. 709 for broadcast video, or PQ and HLG for HDR
+//! video, by decoding and encoding them with the matching transfer function.
+//!
+//! HDR content is described by absolute display luminance in nits. The
+//! [`pq_encode`], [`pq_decode`], [`hlg_encode`], and [`hlg_decode`]
+//! functions convert HDR10 and HLG signals to and from nits, which are
+//! brought into the linear pipeline by dividing by [`REFERENCE_WHITE_NITS`].
+//!
+//! [`TransferFunction`]: enum.TransferFunction.html
+//! [`Rgb`]: ../struct.Rgb.html
+//! [`pq_encode`]: fn.pq_encode.html
+//! [`pq_decode`]: fn.pq_decode.html
+//! [`hlg_encode`]: fn.hlg_encode.html
+//! [`hlg_decode`]: fn.hlg_decode.html
+//! [`REFERENCE_WHITE_NITS`]: constant.REFERENCE_WHITE_NITS.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::Rgb;
+use crate::rgb_space::Rec2020;
+use crate::rgb_space::RgbSpace;
+use crate::utility::linear_to_srgb;
+use crate::utility::srgb_to_linear;
+
+// External library imports.
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Constants
+////////////////////////////////////////////////////////////////////////////////
+/// The luminance in nits of a PQ encoded value of 1.
+pub const PQ_PEAK_NITS: f32 = 10000.0;
+
+/// The luminance in nits of HDR reference white, or graphics white, from
+/// ITU-R BT.2408. Dividing absolute luminance by this gives linear ratios
+/// where 1 matches SDR white.
+pub const REFERENCE_WHITE_NITS: f32 = 203.0;
+
+/// The SMPTE ST 2084 (PQ) constant m1.
+const PQ_M1: f32 = 2610.0 / 16384.0;
+
+/// The SMPTE ST 2084 (PQ) constant m2.
+const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+
+/// The SMPTE ST 2084 (PQ) constant c1.
+const PQ_C1: f32 = 3424.0 / 4096.0;
+
+/// The SMPTE ST 2084 (PQ) constant c2.
+const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+
+/// The SMPTE ST 2084 (PQ) constant c3.
+const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+/// The ITU-R BT.2100 hybrid log-gamma (HLG) constant a.
+const HLG_A: f32 = 0.178_832_77;
+
+/// The ITU-R BT.2100 hybrid log-gamma (HLG) constant b.
+const HLG_B: f32 = 0.284_668_92;
+
+/// The ITU-R BT.2100 hybrid log-gamma (HLG) constant c.
+const HLG_C: f32 = 0.559_910_7;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TransferFunction
+////////////////////////////////////////////////////////////////////////////////
+/// A transfer function encoding linear light intensities as RGB ratios.
+///
+/// Encoded and linear values are ratios between 0 and 1. For
+/// [`TransferFunction::Pq`], a linear value of 1 is the peak of 10000 nits,
+/// and for [`TransferFunction::Hlg`], it is the peak scene light.
+///
+/// [`TransferFunction::Pq`]: #variant.Pq
+/// [`TransferFunction::Hlg`]: #variant.Hlg
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::transfer::TransferFunction;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let encoded = TransferFunction::Rec709.encode(0.18);
+///
+/// assert!((encoded - 0.409).abs() < 0.001);
+/// assert!((TransferFunction::Rec709.decode(encoded) - 0.18).abs() < 1e-5);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransferFunction {
+    /// The identity, for linear light data.
+    Linear,
+    /// The sRGB transfer function, used by [`Rgb`] colors.
+    ///
+    /// [`Rgb`]: ../struct.Rgb.html
+    #[default]
+    Srgb,
+    /// The ITU-R BT.709 transfer function of HD video, also used by
+    /// BT.2020.
+    Rec709,
+    /// A pure power function with the given exponent, such as 2.2 for
+    /// generic displays or 563/256 for Adobe RGB (1998).
+    Gamma(f32),
+    /// The SMPTE ST 2084 perceptual quantizer of HDR10 and Dolby Vision.
+    Pq,
+    /// The ITU-R BT.2100 hybrid log-gamma of HLG broadcast HDR.
+    Hlg,
+}
+
+impl TransferFunction {
+    /// Returns the encoded ratio of the given linear light intensity.
+    pub fn encode(self, linear: f32) -> f32 {
+        use TransferFunction::*;
+        match self {
+            Linear       => linear,
+            Srgb         => linear_to_srgb(linear.max(0.0)),
+            Rec709       => {
+                let l = linear.max(0.0);
+                if l < 0.018 {
+                    4.5 * l
+                } else {
+                    1.099 * l.powf(0.45) - 0.099
+                }
+            },
+            Gamma(gamma) => linear.max(0.0).powf(1.0 / gamma),
+            Pq           => {
+                let y = linear.max(0.0).powf(PQ_M1);
+                ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+            },
+            Hlg          => {
+                let e = linear.max(0.0);
+                if e <= 1.0 / 12.0 {
+                    (3.0 * e).sqrt()
+                } else {
+                    HLG_A * (12.0 * e - HLG_B).ln() + HLG_C
+                }
+            },
+        }
+    }
+
+    /// Returns the linear light intensity of the given encoded ratio.
+    pub fn decode(self, encoded: f32) -> f32 {
+        use TransferFunction::*;
+        match self {
+            Linear       => encoded,
+            Srgb         => srgb_to_linear(encoded.max(0.0)),
+            Rec709       => {
+                let v = encoded.max(0.0);
+                if v < 0.081 {
+                    v / 4.5
+                } else {
+                    ((v + 0.099) / 1.099).powf(1.0 / 0.45)
+                }
+            },
+            Gamma(gamma) => encoded.max(0.0).powf(gamma),
+            Pq           => {
+                let e = encoded.max(0.0).powf(1.0 / PQ_M2);
+                ((e - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * e)).powf(1.0 / PQ_M1)
+            },
+            Hlg          => {
+                let v = encoded.max(0.0);
+                if v <= 0.5 {
+                    v * v / 3.0
+                } else {
+                    (((v - HLG_C) / HLG_A).exp() + HLG_B) / 12.0
+                }
+            },
+        }
+    }
+
+    /// Returns the linear light ratios of the given [`Rgb`] color, treating
+    /// its channels as encoded by the transfer function.
+    ///
+    /// [`Rgb`]: ../struct.Rgb.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use color::Rgb;
+    /// # use color::transfer::TransferFunction;
+    /// # fn example() -> Result<(), Box<dyn Error>> {
+    /// # //-------------------------------------------------------------------
+    /// // Re-encode a Rec. 709 video color as sRGB.
+    /// let video = Rgb::new(180, 120, 60);
+    ///
+    /// let linear = TransferFunction::Rec709.decode_rgb(video);
+    /// let srgb = TransferFunction::Srgb.encode_rgb(linear);
+    ///
+    /// assert_eq!(TransferFunction::Srgb.decode_rgb(Rgb::new(255, 0, 0)),
+    ///     [1.0, 0.0, 0.0]);
+    /// assert_eq!(srgb, Rgb::new(188, 132, 75));
+    /// # //-------------------------------------------------------------------
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     example().unwrap();
+    /// # }
+    /// ```
+    pub fn decode_rgb(self, color: Rgb) -> [f32; 3] {
+        color.ratios().map(|v| self.decode(v))
+    }
+
+    /// Returns the [`Rgb`] color encoding the given linear light ratios with
+    /// the transfer function. The encoded ratios are clamped between 0 and 1.
+    ///
+    /// [`Rgb`]: ../struct.Rgb.html
+    pub fn encode_rgb(self, linear: [f32; 3]) -> Rgb {
+        let [r, g, b] = linear.map(|v| {
+            (self.encode(v).clamp(0.0, 1.0) * u8::MAX as f32).round() as u8
+        });
+        Rgb::new(r, g, b)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PQ
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the SMPTE ST 2084 (PQ) encoded ratio of the given display
+/// luminance in nits. Luminance above [`PQ_PEAK_NITS`] is clamped.
+///
+/// [`PQ_PEAK_NITS`]: constant.PQ_PEAK_NITS.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::transfer::pq_decode;
+/// # use color::transfer::pq_encode;
+/// # use color::transfer::REFERENCE_WHITE_NITS;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // HDR10 reference white is encoded at 58%.
+/// let encoded = pq_encode(REFERENCE_WHITE_NITS);
+/// assert!((encoded - 0.58).abs() < 0.001);
+///
+/// // Bring a 1000 nit highlight into the linear pipeline.
+/// let linear = pq_decode(0.7518) / REFERENCE_WHITE_NITS;
+/// assert!((linear - 1000.0 / 203.0).abs() < 0.01);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn pq_encode(nits: f32) -> f32 {
+    TransferFunction::Pq.encode((nits / PQ_PEAK_NITS).min(1.0))
+}
+
+/// Returns the display luminance in nits of the given SMPTE ST 2084 (PQ)
+/// encoded ratio.
+pub fn pq_decode(encoded: f32) -> f32 {
+    TransferFunction::Pq.decode(encoded.min(1.0)) * PQ_PEAK_NITS
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// HLG
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the hybrid log-gamma (HLG) encoded ratios of the given BT.2020
+/// display light in nits, for a display with the given peak luminance.
+///
+/// This inverts the ITU-R BT.2100 HLG OOTF, whose system gamma depends on
+/// the peak luminance, before applying the HLG OETF.
+///
+/// # Panics
+///
+/// Panics if `peak_nits` is not positive.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::transfer::hlg_decode;
+/// # use color::transfer::hlg_encode;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // Full signal white is the display peak.
+/// let white = hlg_decode([1.0, 1.0, 1.0], 1000.0);
+/// assert!((white[0] - 1000.0).abs() < 0.1);
+///
+/// let encoded = hlg_encode([200.0, 100.0, 50.0], 1000.0);
+/// let decoded = hlg_decode(encoded, 1000.0);
+/// assert!((decoded[0] - 200.0).abs() < 0.1);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn hlg_encode(nits: [f32; 3], peak_nits: f32) -> [f32; 3] {
+    assert!(peak_nits > 0.0, "HLG peak luminance must be positive");
+    let gamma = hlg_system_gamma(peak_nits);
+    let display = nits.map(|v| (v / peak_nits).max(0.0));
+    let luminance = rec2020_luminance(display);
+    let scale = if luminance > 0.0 {
+        luminance.powf((1.0 - gamma) / gamma)
+    } else {
+        0.0
+    };
+    display.map(|v| TransferFunction::Hlg.encode((v * scale).min(1.0)))
+}
+
+/// Returns the BT.2020 display light in nits of the given hybrid log-gamma
+/// (HLG) encoded ratios, for a display with the given peak luminance.
+///
+/// This applies the inverse HLG OETF followed by the ITU-R BT.2100 HLG
+/// OOTF, whose system gamma depends on the peak luminance.
+///
+/// # Panics
+///
+/// Panics if `peak_nits` is not positive.
+pub fn hlg_decode(encoded: [f32; 3], peak_nits: f32) -> [f32; 3] {
+    assert!(peak_nits > 0.0, "HLG peak luminance must be positive");
+    let gamma = hlg_system_gamma(peak_nits);
+    let scene = encoded.map(|v| TransferFunction::Hlg.decode(v.min(1.0)));
+    let luminance = rec2020_luminance(scene);
+    let scale = if luminance > 0.0 {
+        peak_nits * luminance.powf(gamma - 1.0)
+    } else {
+        0.0
+    };
+    scene.map(|v| v * scale)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Helper functions
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the HLG system gamma for a display with the given peak luminance.
+fn hlg_system_gamma(peak_nits: f32) -> f32 {
+    1.2 + 0.42 * (peak_nits / 1000.0).log10()
+}
+
+/// Returns the luminance of the given linear BT.2020 ratios.
+fn rec2020_luminance(linear: [f32; 3]) -> f32 {
+    let weights = Rec2020::XYZ_FROM_LINEAR[1];
+    linear.iter().zip(weights.iter()).map(|(v, w)| v * w).sum()
+}
@@ -12,7 +12,12 @@
 ////////////////////////////////////////////////////////////////////////////////
 // Standard library imports.
 use std::f32;
+use std::iter::Sum;
+use std::ops::Add;
+use std::ops::Div;
+use std::ops::Mul;
 use std::ops::Sub;
+use std::sync::OnceLock;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -48,19 +53,7 @@ use std::ops::Sub;
 /// ```
 #[inline]
 pub fn nearly_equal(a: f32, b: f32) -> bool {
-    let abs_a = a.abs();
-    let abs_b = b.abs();
-    let diff = (a - b).abs();
-
-    if a == b { // Shortcut, handles infinities.
-        true
-    } else if a == 0.0 || b == 0.0 || diff < f32::MIN_POSITIVE {
-        // a or b is zero or both are extremely close to it
-        // relative error is less meaningful here
-        diff < (f32::EPSILON * f32::MIN_POSITIVE)
-    } else { // Use relative error.
-        (diff / f32::min(abs_a + abs_b, f32::MAX)) < f32::EPSILON
-    }
+    crate::math::nearly_equal(a, b, crate::math::DEFAULT_EPSILON)
 }
 
 
@@ -339,3 +332,437 @@ pub fn cerp_f32(
         + (-2.0*a3 + 3.0*a2) * e
         + (a3 - a2) * end_slope
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// monotone_cerp_u8
+////////////////////////////////////////////////////////////////////////////////
+/// Performs a monotone cubic interpolation between `start` and `end`,
+/// returning the value located at the ratio given by `amount`, which is
+/// clamped between 0 and 1.
+///
+/// This is the same as [`cerp_u8`], except that the slopes are first limited
+/// using the Fritsch-Carlson method, so the result never leaves the range
+/// between `start` and `end`.
+///
+/// [`cerp_u8`]: fn.cerp_u8.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::utility::monotone_cerp_u8;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// assert_eq!(monotone_cerp_u8(15, 167, 0.0, 0.0, 0.34), 55);
+/// assert!(monotone_cerp_u8(15, 167, 900.0, 0.0, 0.5) <= 167);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn monotone_cerp_u8(
+    start: u8,
+    end: u8,
+    start_slope: f32,
+    end_slope: f32,
+    amount: f32)
+    -> u8
+{
+    monotone_cerp_f32(
+        start as f32,
+        end as f32,
+        start_slope,
+        end_slope,
+        amount) as u8
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// monotone_cerp_f32
+////////////////////////////////////////////////////////////////////////////////
+/// Performs a monotone cubic interpolation between `start` and `end`,
+/// returning the value located at the ratio given by `amount`, which is
+/// clamped between 0 and 1.
+///
+/// This is the same as [`cerp_f32`], except that the slopes are first limited
+/// using the Fritsch-Carlson method: slopes opposing the direction from
+/// `start` to `end` are set to zero, and slopes which are too steep are
+/// scaled down. The result never leaves the range between `start` and `end`.
+///
+/// [`cerp_f32`]: fn.cerp_f32.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::utility::cerp_f32;
+/// # use color::utility::monotone_cerp_f32;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// // Steep slopes make the ordinary cubic overshoot the end value.
+/// assert!(cerp_f32(0.0, 1.0, 6.0, 0.0, 0.5) > 1.0);
+/// assert!(monotone_cerp_f32(0.0, 1.0, 6.0, 0.0, 0.5) <= 1.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[inline]
+pub fn monotone_cerp_f32(
+    start: f32,
+    end: f32,
+    start_slope: f32,
+    end_slope: f32,
+    amount: f32)
+    -> f32
+{
+    let a = clamped(amount, 0.0, 1.0);
+    let delta = end - start;
+
+    let (start_slope, end_slope) = if delta == 0.0 {
+        (0.0, 0.0)
+    } else {
+        let alpha = (start_slope / delta).max(0.0);
+        let beta = (end_slope / delta).max(0.0);
+        let radius = alpha.hypot(beta);
+        let scale = if radius > 3.0 { 3.0 / radius } else { 1.0 };
+        (alpha * scale * delta, beta * scale * delta)
+    };
+
+    let a2 = a * a;
+    let a3 = a2 * a;
+
+    let value = (2.0*a3 - 3.0*a2 + 1.0) * start
+        + (a3 - 2.0*a2 + a) * start_slope
+        + (-2.0*a3 + 3.0*a2) * end
+        + (a3 - a2) * end_slope;
+    clamped(value, start.min(end), start.max(end))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// finite_difference_slopes
+////////////////////////////////////////////////////////////////////////////////
+/// Returns estimated slopes for a cubic curve through evenly spaced `values`,
+/// using finite differences.
+///
+/// The slope at each interior value is the average of the slopes of the
+/// segments on either side, and the slope at each end is the slope of its
+/// only segment. Slopes are measured per step between values, as expected by
+/// [`cerp_f32`] and [`cubic_curve_f32`].
+///
+/// [`cerp_f32`]: fn.cerp_f32.html
+/// [`cubic_curve_f32`]: fn.cubic_curve_f32.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::utility::finite_difference_slopes;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let slopes = finite_difference_slopes(&[0.0, 1.0, 4.0, 5.0]);
+///
+/// assert_eq!(slopes, [1.0, 2.0, 2.0, 1.0]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn finite_difference_slopes(values: &[f32]) -> Vec<f32> {
+    let last = values.len().saturating_sub(1);
+    (0..values.len())
+        .map(|i| {
+            if last == 0 { return 0.0; }
+            let before = if i > 0 { values[i] - values[i - 1] } else { 0.0 };
+            let after = if i < last { values[i + 1] - values[i] } else { 0.0 };
+            if i == 0 || i == last {
+                before + after
+            } else {
+                (before + after) / 2.0
+            }
+        })
+        .collect()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// cardinal_slopes
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the slopes of a cardinal spline through evenly spaced `values`,
+/// with the given `tension`.
+///
+/// A tension of 0 gives a Catmull-Rom spline, and a tension of 1 gives zero
+/// slopes, so the curve flattens at every value. Negative tensions produce
+/// looser curves. The values beyond each end are taken to equal the end
+/// value. Slopes are measured per step between values, as expected by
+/// [`cerp_f32`] and [`cubic_curve_f32`].
+///
+/// [`cerp_f32`]: fn.cerp_f32.html
+/// [`cubic_curve_f32`]: fn.cubic_curve_f32.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::utility::cardinal_slopes;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let values = [0.0, 1.0, 4.0, 5.0];
+///
+/// assert_eq!(cardinal_slopes(&values, 0.0), [0.5, 2.0, 2.0, 0.5]);
+/// assert_eq!(cardinal_slopes(&values, 0.5), [0.25, 1.0, 1.0, 0.25]);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn cardinal_slopes(values: &[f32], tension: f32) -> Vec<f32> {
+    let last = values.len().saturating_sub(1);
+    (0..values.len())
+        .map(|i| {
+            let before = values[i.saturating_sub(1)];
+            let after = values[(i + 1).min(last)];
+            (1.0 - tension) * (after - before) / 2.0
+        })
+        .collect()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// cubic_curve_f32
+////////////////////////////////////////////////////////////////////////////////
+/// Evaluates the piecewise cubic curve through evenly spaced `values` with the
+/// given `slopes`, returning the value located at the ratio given by `amount`
+/// along the whole curve, which is clamped between 0 and 1.
+///
+/// # Panics
+///
+/// Panics if `values` is empty or if `slopes` has a different length.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::utility::cardinal_slopes;
+/// # use color::utility::cubic_curve_f32;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let values = [0.0, 1.0, 4.0, 5.0];
+/// let slopes = cardinal_slopes(&values, 0.0);
+///
+/// assert_eq!(cubic_curve_f32(&values, &slopes, 0.0), 0.0);
+/// assert_eq!(cubic_curve_f32(&values, &slopes, 0.5), 2.5);
+/// assert_eq!(cubic_curve_f32(&values, &slopes, 1.0), 5.0);
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+pub fn cubic_curve_f32(values: &[f32], slopes: &[f32], amount: f32) -> f32 {
+    assert!(!values.is_empty());
+    assert_eq!(values.len(), slopes.len());
+
+    let last = values.len() - 1;
+    if last == 0 { return values[0]; }
+
+    let position = clamped(amount, 0.0, 1.0) * last as f32;
+    let i = (position.floor() as usize).min(last - 1);
+    let a = position - i as f32;
+    let a2 = a * a;
+    let a3 = a2 * a;
+
+    (2.0*a3 - 3.0*a2 + 1.0) * values[i]
+        + (a3 - 2.0*a2 + a) * slopes[i]
+        + (-2.0*a3 + 3.0*a2) * values[i + 1]
+        + (a3 - a2) * slopes[i + 1]
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// unit_random
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a pseudo-random number in [0, 1) using the SplitMix64 generator,
+/// advancing the given state.
+pub(in crate) fn unit_random(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// srgb_to_linear
+////////////////////////////////////////////////////////////////////////////////
+/// Applies the sRGB electro-optical transfer function to the given ratio,
+/// returning the linear light intensity.
+#[inline]
+pub(in crate) fn srgb_to_linear(ratio: f32) -> f32 {
+    if ratio <= 0.04045 {
+        ratio / 12.92
+    } else {
+        ((ratio + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// linear_to_srgb
+////////////////////////////////////////////////////////////////////////////////
+/// Applies the sRGB opto-electronic transfer function to the given linear
+/// light intensity, returning the encoded ratio.
+#[inline]
+pub(in crate) fn linear_to_srgb(linear: f32) -> f32 {
+    if linear <= 0.0031308 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// srgb_octet_to_linear
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the linear light intensity of the given sRGB encoded octet, using
+/// a lookup table built on first use.
+#[inline]
+pub(in crate) fn srgb_octet_to_linear(octet: u8) -> f32 {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        let mut table = [0.0; 256];
+        for (octet, linear) in table.iter_mut().enumerate() {
+            *linear = srgb_to_linear(octet as f32 / u8::MAX as f32);
+        }
+        table
+    });
+    table[octet as usize]
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// linear_to_srgb_octet
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the nearest sRGB encoded octet of the given linear light
+/// intensity, clamping it into range.
+///
+/// This is a binary search over a table of the linear intensities halfway
+/// between each pair of adjacent octets, built on first use, so the number
+/// of thresholds below an intensity is its rounded octet.
+#[inline]
+pub(in crate) fn linear_to_srgb_octet(linear: f32) -> u8 {
+    static TABLE: OnceLock<[f32; 255]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        let mut table = [0.0; 255];
+        for (octet, threshold) in table.iter_mut().enumerate() {
+            let ratio = (octet as f32 + 0.5) / u8::MAX as f32;
+            *threshold = srgb_to_linear(ratio);
+        }
+        table
+    });
+    table.partition_point(|&t| t < linear) as u8
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// srgb_to_linear_f64
+////////////////////////////////////////////////////////////////////////////////
+/// Applies the sRGB electro-optical transfer function to the given ratio in
+/// double precision.
+#[inline]
+pub(in crate) fn srgb_to_linear_f64(ratio: f64) -> f64 {
+    if ratio <= 0.04045 {
+        ratio / 12.92
+    } else {
+        ((ratio + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// linear_to_srgb_f64
+////////////////////////////////////////////////////////////////////////////////
+/// Applies the sRGB opto-electronic transfer function to the given linear
+/// light intensity in double precision.
+#[inline]
+pub(in crate) fn linear_to_srgb_f64(linear: f64) -> f64 {
+    if linear <= 0.0031308 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// MatrixElement
+////////////////////////////////////////////////////////////////////////////////
+/// The element types of the 3×3 matrix functions, allowing them to operate in
+/// single or double precision.
+pub(in crate) trait MatrixElement: Copy + Default + Sum + From<f32>
+    + Add<Output=Self> + Sub<Output=Self>
+    + Mul<Output=Self> + Div<Output=Self>
+{}
+
+impl MatrixElement for f32 {}
+
+impl MatrixElement for f64 {}
+
+////////////////////////////////////////////////////////////////////////////////
+// matrix_product
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the product of the given 3×3 matrices.
+pub(in crate) fn matrix_product<T>(a: [[T; 3]; 3], b: [[T; 3]; 3])
+    -> [[T; 3]; 3]
+    where T: MatrixElement
+{
+    let mut product = [[T::default(); 3]; 3];
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    product
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// matrix_transform
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the given vector transformed by the given 3×3 matrix.
+pub(in crate) fn matrix_transform<T>(m: [[T; 3]; 3], v: [T; 3]) -> [T; 3]
+    where T: MatrixElement
+{
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// matrix_inverse
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the inverse of the given invertible 3×3 matrix.
+pub(in crate) fn matrix_inverse<T>(m: [[T; 3]; 3]) -> [[T; 3]; 3]
+    where T: MatrixElement
+{
+    let cofactor = |i: usize, j: usize| {
+        let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+        let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+        m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+    };
+    let determinant: T = (0..3).map(|j| m[0][j] * cofactor(0, j)).sum();
+    let mut inverse = [[T::default(); 3]; 3];
+    for (i, row) in inverse.iter_mut().enumerate() {
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = cofactor(j, i) / determinant;
+        }
+    }
+    inverse
+}
@@ -0,0 +1,252 @@
+// Copyright 2020 Skylor R. Schermer.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! JavaScript bindings using [wasm-bindgen](https://crates.io/crates/wasm-bindgen).
+//!
+//! The [`JsColor`] wrapper is exported to JavaScript as `Color`, so that web
+//! front-ends can parse, convert, and format colors with the same color math
+//! as Rust code using this crate:
+//!
+//! ```js
+//! const color = Color.parse("rgb(255 99 71 / 50%)");
+//! color.toHex();    // "#ff634780"
+//! color.toOklch();  // Float32Array [l, c, h]
+//! ```
+//!
+//! [`JsColor`]: struct.JsColor.html
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use crate::contrast::contrast_ratio;
+use crate::Hsl;
+use crate::Hsv;
+use crate::Lab;
+use crate::Oklab;
+use crate::Oklch;
+use crate::Rgb;
+use crate::Rgba;
+
+// External library imports.
+use wasm_bindgen::prelude::*;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// JsColor
+////////////////////////////////////////////////////////////////////////////////
+/// An 8-bit sRGB color with alpha, exported to JavaScript as `Color`.
+///
+/// Conversions to other color spaces return their components as a
+/// `Float32Array`, and conversions from other color spaces produce opaque
+/// colors.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use color::wasm::JsColor;
+/// # fn example() -> Result<(), Box<dyn Error>> {
+/// # //-------------------------------------------------------------------
+/// let color = JsColor::from_hsl(0.0, 1.0, 0.5);
+///
+/// assert_eq!(color.to_hex(), "#ff0000");
+/// assert_eq!(color.to_css(), "rgb(255 0 0)");
+/// # //-------------------------------------------------------------------
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+#[wasm_bindgen(js_name = Color)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsColor {
+    /// The wrapped color.
+    rgba: Rgba,
+}
+
+#[wasm_bindgen(js_class = Color)]
+impl JsColor {
+    /// Constructs a new color from its components, which is opaque if the
+    /// alpha is omitted.
+    #[wasm_bindgen(constructor)]
+    pub fn new(r: u8, g: u8, b: u8, a: Option<u8>) -> JsColor {
+        JsColor { rgba: Rgba::new(r, g, b, a.unwrap_or(u8::MAX)) }
+    }
+
+    /// Parses a hex code with 3, 4, 6, or 8 digits, and an optional leading
+    /// `#`.
+    ///
+    /// # Errors
+    ///
+    /// Throws an `Error` if the hex code is invalid.
+    #[wasm_bindgen(js_name = fromHex)]
+    pub fn from_hex(hex: &str) -> Result<JsColor, JsError> {
+        let hex = hex.trim();
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        JsColor::parse(&format!("#{}", digits))
+    }
+
+    /// Parses a CSS color value, such as `"tomato"`, `"#ff6347"`,
+    /// `"rgb(255 99 71 / 50%)"`, or `"hsl(9, 100%, 64%)"`.
+    ///
+    /// # Errors
+    ///
+    /// Throws an `Error` if the value is not a supported CSS color.
+    pub fn parse(css: &str) -> Result<JsColor, JsError> {
+        css.parse::<Rgba>()
+            .map(|rgba| JsColor { rgba })
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Constructs an opaque color from HSL components, with the hue in
+    /// degrees and the saturation and lightness as ratios.
+    #[wasm_bindgen(js_name = fromHsl)]
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> JsColor {
+        JsColor::from_rgb(Rgb::from(Hsl::new(h, s, l)))
+    }
+
+    /// Constructs an opaque color from HSV components, with the hue in
+    /// degrees and the saturation and value as ratios.
+    #[wasm_bindgen(js_name = fromHsv)]
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> JsColor {
+        JsColor::from_rgb(Rgb::from(Hsv::new(h, s, v)))
+    }
+
+    /// Constructs an opaque color from CIE L*a*b* components, clipped to the
+    /// sRGB gamut.
+    #[wasm_bindgen(js_name = fromLab)]
+    pub fn from_lab(l: f32, a: f32, b: f32) -> JsColor {
+        JsColor::from_rgb(Rgb::from(Lab::new(l, a, b)))
+    }
+
+    /// Constructs an opaque color from Oklab components, clipped to the sRGB
+    /// gamut.
+    #[wasm_bindgen(js_name = fromOklab)]
+    pub fn from_oklab(l: f32, a: f32, b: f32) -> JsColor {
+        JsColor::from_rgb(Rgb::from(Oklab::new(l, a, b)))
+    }
+
+    /// Constructs an opaque color from Oklch components, with the hue in
+    /// degrees, clipped to the sRGB gamut.
+    #[wasm_bindgen(js_name = fromOklch)]
+    pub fn from_oklch(l: f32, c: f32, h: f32) -> JsColor {
+        JsColor::from_rgb(Rgb::from(Oklch::new(l, c, h)))
+    }
+
+    /// Returns the red component.
+    #[wasm_bindgen(getter)]
+    pub fn r(&self) -> u8 {
+        self.rgba.r
+    }
+
+    /// Returns the green component.
+    #[wasm_bindgen(getter)]
+    pub fn g(&self) -> u8 {
+        self.rgba.g
+    }
+
+    /// Returns the blue component.
+    #[wasm_bindgen(getter)]
+    pub fn b(&self) -> u8 {
+        self.rgba.b
+    }
+
+    /// Returns the alpha component.
+    #[wasm_bindgen(getter)]
+    pub fn a(&self) -> u8 {
+        self.rgba.a
+    }
+
+    /// Returns the lowercase hex code of the color, with 6 digits if it is
+    /// opaque and 8 digits otherwise.
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(&self) -> String {
+        if self.rgba.a == u8::MAX {
+            format!("{:x}", self.rgba.rgb())
+        } else {
+            format!("{:x}", self.rgba)
+        }
+    }
+
+    /// Returns the color as a CSS `rgb()` value, with an alpha if it is not
+    /// opaque.
+    #[wasm_bindgen(js_name = toCss)]
+    pub fn to_css(&self) -> String {
+        let Rgba { r, g, b, a } = self.rgba;
+        if a == u8::MAX {
+            format!("rgb({} {} {})", r, g, b)
+        } else {
+            let alpha = (f32::from(a) / 255.0 * 1000.0).round() / 1000.0;
+            format!("rgb({} {} {} / {})", r, g, b, alpha)
+        }
+    }
+
+    /// Returns the `[h, s, l]` components of the color.
+    #[wasm_bindgen(js_name = toHsl)]
+    pub fn to_hsl(&self) -> Vec<f32> {
+        let hsl = Hsl::from(self.rgba.rgb());
+        vec![hsl.hue(), hsl.saturation(), hsl.lightness()]
+    }
+
+    /// Returns the `[h, s, v]` components of the color.
+    #[wasm_bindgen(js_name = toHsv)]
+    pub fn to_hsv(&self) -> Vec<f32> {
+        let hsv = Hsv::from(self.rgba.rgb());
+        vec![hsv.hue(), hsv.saturation(), hsv.value()]
+    }
+
+    /// Returns the CIE `[l, a, b]` components of the color.
+    #[wasm_bindgen(js_name = toLab)]
+    pub fn to_lab(&self) -> Vec<f32> {
+        let lab = Lab::from(self.rgba.rgb());
+        vec![lab.l, lab.a, lab.b]
+    }
+
+    /// Returns the Oklab `[l, a, b]` components of the color.
+    #[wasm_bindgen(js_name = toOklab)]
+    pub fn to_oklab(&self) -> Vec<f32> {
+        let oklab = Oklab::from(self.rgba.rgb());
+        vec![oklab.l, oklab.a, oklab.b]
+    }
+
+    /// Returns the Oklch `[l, c, h]` components of the color.
+    #[wasm_bindgen(js_name = toOklch)]
+    pub fn to_oklch(&self) -> Vec<f32> {
+        let oklch = Oklch::from(self.rgba.rgb());
+        vec![oklch.l, oklch.c, oklch.h]
+    }
+
+    /// Returns the WCAG contrast ratio between the color and another,
+    /// ignoring their alpha.
+    #[wasm_bindgen(js_name = contrastRatio)]
+    pub fn contrast_ratio(&self, other: &JsColor) -> f32 {
+        contrast_ratio(self.rgba.rgb(), other.rgba.rgb())
+    }
+}
+
+impl JsColor {
+    /// Constructs an opaque `JsColor` from the given color.
+    fn from_rgb(rgb: Rgb) -> JsColor {
+        JsColor { rgba: Rgba::from(rgb) }
+    }
+}
+
+impl From<Rgba> for JsColor {
+    fn from(rgba: Rgba) -> Self {
+        JsColor { rgba }
+    }
+}
+
+impl From<JsColor> for Rgba {
+    fn from(color: JsColor) -> Self {
+        color.rgba
+    }
+}